@@ -21,7 +21,9 @@ use std::sync::Arc;
 use tracing_subscriber::fmt::time::Uptime;
 use tracing_subscriber::EnvFilter;
 
-use legit_poker::engine::nl::types::{HandConfig, PlayerId, SeatId, TableStakes};
+use legit_poker::engine::nl::types::{
+    DealingOrder, HandConfig, PlayerId, SeatId, TableStakes, HOLDEM_HOLE_CARDS,
+};
 use legit_poker::ledger::actor::ShufflerActor;
 use legit_poker::ledger::hash::LedgerHasherSha256;
 use legit_poker::ledger::lobby::service::{LobbyService, LobbyServiceFactory};
@@ -29,7 +31,8 @@ use legit_poker::ledger::lobby::types::{
     CommenceGameParams, GameLobbyConfig, PlayerRecord, ShufflerRecord, ShufflerRegistrationConfig,
 };
 use legit_poker::ledger::messages::{
-    AnyMessageEnvelope, GameBlindingDecryptionMessage, GamePartialUnblindingShareMessage,
+    AnyMessageEnvelope, EnvelopedMessage, GameBlindingDecryptionMessage,
+    GameNonceCommitmentMessage, GamePartialUnblindingShareMessage,
 };
 use legit_poker::ledger::snapshot::{AnyTableSnapshot, TableAtDealing};
 use legit_poker::ledger::transition::apply_transition;
@@ -110,9 +113,12 @@ async fn main() -> Result<()> {
         rake_bps: 500, // 5%
         name: "High Stakes ZK Poker".to_string(),
         currency: "CHIPS".to_string(),
-        buy_in: 10_000,
+        min_buy_in: 4_000,
+        max_buy_in: 10_000,
         min_players_to_start: 6,
+        min_shufflers: NUM_SHUFFLERS as i16,
         check_raise_allowed: true,
+        max_bet_bb: None,
         action_time_limit: std::time::Duration::from_secs(30),
     };
 
@@ -157,7 +163,7 @@ async fn main() -> Result<()> {
     for (idx, player_record) in player_records.into_iter().enumerate() {
         let seat_id = idx as SeatId;
         let output = lobby_service
-            .join_game(&game_record, player_record.clone(), Some(seat_id))
+            .join_game(&game_record, player_record.clone(), Some(seat_id), None)
             .await?;
         tracing::debug!(
             target: LOG_TARGET,
@@ -176,11 +182,14 @@ async fn main() -> Result<()> {
         small_blind_seat: 1,
         big_blind_seat: 2,
         check_raise_allowed: true,
+        hole_cards: HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: DealingOrder::OneAtATime,
     };
 
     let commence_params = CommenceGameParams {
         game_id: game_record.state.id,
-        hand_no: 1,
+        hand_no: None,
         button_seat: hand_config.button,
         small_blind_seat: hand_config.small_blind_seat,
         big_blind_seat: hand_config.big_blind_seat,
@@ -225,6 +234,18 @@ async fn main() -> Result<()> {
         );
     }
 
+    // Every shuffler commits to its VRF nonce before the first shuffle can be applied
+    for (state, engine) in shuffler_states.iter_mut().zip(shuffler_engines.iter()) {
+        if let Some(any_envelope) = state.try_emit_nonce_commitment::<Schnorr254, _>(engine)? {
+            let envelope: EnvelopedMessage<Curve, GameNonceCommitmentMessage<Curve>> =
+                (&any_envelope).try_into()?;
+            match apply_transition(current_snapshot, &envelope, hasher.as_ref())? {
+                AnyTableSnapshot::Shuffling(snapshot) => current_snapshot = snapshot,
+                _ => return Err(anyhow::anyhow!("nonce commitment left the shuffling phase")),
+            }
+        }
+    }
+
     // Step 10: Execute the shuffle phase
     tracing::info!(target: LOG_TARGET, "🔄 Step 10: Executing shuffle phase");
     tracing::info!(target: LOG_TARGET, "   Each shuffler will shuffle the deck in order...");
@@ -423,7 +444,7 @@ fn extract_shuffle_envelope(
     >,
 > {
     use legit_poker::ledger::actor::AnyActor;
-    use legit_poker::ledger::messages::{AnyGameMessage, EnvelopedMessage};
+    use legit_poker::ledger::messages::AnyGameMessage;
     use legit_poker::signing::WithSignature;
 
     let actor = match &envelope.actor {
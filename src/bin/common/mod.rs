@@ -16,7 +16,7 @@ use legit_poker::{
         community_decryption::{decrypt_community_card, CommunityDecryptionShare},
         curve_absorb::{CurveAbsorb, CurveAbsorbGadget},
         data_structures::ElGamalCiphertext,
-        permutation_proof::PermutationGroth16,
+        permutation_proof::{PermutationGroth16, PointFromCoordinates},
         player_decryption::{
             combine_blinding_contributions_for_player, generate_committee_decryption_share,
             recover_card_value, PlayerTargetedBlindingContribution,
@@ -100,6 +100,7 @@ where
         generator: G::generator(),
         public_key: aggregated_public_key,
         perm_snark_keys: Default::default(),
+        deck_commitment: Default::default(),
     }
 }
 
@@ -132,7 +133,11 @@ where
     }
 
     let mut rng = StdRng::seed_from_u64(123_456_789);
-    let perm_sys = PermutationGroth16::<E, G, GV, N, LEVELS>::setup(&mut rng, num_samples)?;
+    let perm_sys = PermutationGroth16::<E, G, GV, N, LEVELS>::setup_with_deck_commitment(
+        &mut rng,
+        num_samples,
+        config.deck_commitment,
+    )?;
 
     config.perm_snark_keys.insert(
         num_samples,
@@ -175,7 +180,7 @@ pub fn perform_shuffle_with_proof<E, G, GV, R, const N: usize, const LEVELS: usi
 ) -> Result<([ElGamalCiphertext<G>; N], ShufflingProof<E, G, N>), Box<dyn std::error::Error>>
 where
     E: ark_ec::pairing::Pairing<ScalarField = G::BaseField>,
-    G: CurveGroup + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
+    G: PointFromCoordinates + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
     G::Config: CurveConfig,
     G::ScalarField: PrimeField + Absorb + UniformRand,
     G::BaseField: PrimeField + Absorb,
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -15,9 +16,10 @@ use tracing_subscriber::{fmt::time::Uptime, EnvFilter};
 use url::Url;
 
 use legit_poker::db::connect_to_postgres_db;
-use legit_poker::engine::nl::types::{Chips, HandConfig, SeatId, TableStakes};
+use legit_poker::engine::nl::types::{HandConfig, SeatId, TableStakes};
 use legit_poker::game::coordinator::{
-    GameCoordinator, GameCoordinatorConfig, ShufflerSecretConfig, SupabaseRealtimeClientConfig,
+    validate_shuffler_secrets, GameCoordinator, GameCoordinatorConfig, ShufflerSecretConfig,
+    SupabaseRealtimeClientConfig, DEFAULT_MAX_CONCURRENT_HANDS,
 };
 use legit_poker::ledger::lobby::types::{
     CommenceGameParams, GameLobbyConfig, GameMetadata, PlayerRecord, PlayerSeatSnapshot,
@@ -29,9 +31,10 @@ use legit_poker::ledger::store::{
     EventStore, SeaOrmEventStore, SeaOrmSnapshotStore, SnapshotStore,
 };
 use legit_poker::ledger::typestate::MaybeSaved;
-use legit_poker::ledger::verifier::{LedgerVerifier, Verifier};
+use legit_poker::ledger::verifier::{LedgerVerifier, SchnorrSignatureValidator, Verifier};
 use legit_poker::ledger::{CanonicalKey, HandId};
 use legit_poker::ledger::{LobbyService, LobbyServiceFactory};
+use legit_poker::shuffler::shuffler_signing_params;
 use legit_poker::shuffling::{draw_shuffler_public_key, make_global_public_keys};
 
 const LOG_TARGET: &str = "bin::coordinator_demo";
@@ -162,8 +165,11 @@ async fn run_demo(config: Config) -> Result<()> {
     let snapshot_store: Arc<dyn SnapshotStore<Curve>> =
         Arc::new(SeaOrmSnapshotStore::<Curve>::new(conn.clone()));
     let state = Arc::new(LedgerState::<Curve>::new());
-    let verifier: Arc<dyn Verifier<Curve> + Send + Sync> =
-        Arc::new(LedgerVerifier::<Curve>::new(Arc::clone(&state)));
+    let signing_params = shuffler_signing_params::<Curve>()?;
+    let signature = SchnorrSignatureValidator::<Curve>::new(signing_params.clone());
+    let verifier: Arc<dyn Verifier<Curve> + Send + Sync> = Arc::new(
+        LedgerVerifier::<Curve>::with_signature_validator(Arc::clone(&state), Arc::new(signature)),
+    );
 
     let player_specs = build_players(&mut rng)?;
     let lobby_config = build_lobby_config();
@@ -176,7 +182,7 @@ async fn run_demo(config: Config) -> Result<()> {
         state: MaybeSaved { id: None },
     };
     let metadata = lobby
-        .host_game(host_registration, lobby_config.clone())
+        .host_game(host_registration, lobby_config)
         .await
         .context("failed to host game")?;
 
@@ -187,8 +193,7 @@ async fn run_demo(config: Config) -> Result<()> {
         "game hosted"
     );
 
-    let seated_players =
-        seat_players(&lobby, &metadata, &player_specs, lobby_config.buy_in).await?;
+    let seated_players = seat_players(&lobby, &metadata, &player_specs).await?;
     let registered_shufflers = register_shufflers(&lobby, &metadata, &shuffler_materials).await?;
 
     let shuffler_secret_configs = registered_shufflers
@@ -199,6 +204,17 @@ async fn run_demo(config: Config) -> Result<()> {
             secret: material.secret.clone(),
         })
         .collect::<Vec<_>>();
+    let registered_keys = registered_shufflers
+        .iter()
+        .map(|registration| {
+            (
+                registration.shuffler.state.id,
+                registration.shuffler.public_key,
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    validate_shuffler_secrets(&shuffler_secret_configs, &registered_keys)
+        .context("shuffler secrets do not match registered shufflers")?;
 
     let coordinator_config = GameCoordinatorConfig {
         verifier,
@@ -208,7 +224,11 @@ async fn run_demo(config: Config) -> Result<()> {
         supabase: SupabaseRealtimeClientConfig::new(realtime_url, config.supabase_key.clone()),
         shufflers: shuffler_secret_configs,
         submit_channel_capacity: SUBMIT_CHANNEL_CAPACITY,
+        max_concurrent_hands: DEFAULT_MAX_CONCURRENT_HANDS,
         rng_seed: config.rng_seed.map(seed_to_bytes),
+        signing_params,
+        action_deadlines: None,
+        nonce_source: None,
     };
 
     info!(target = LOG_TARGET, "spawning game coordinator");
@@ -222,7 +242,7 @@ async fn run_demo(config: Config) -> Result<()> {
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: None,
         button_seat: hand_config.button,
         small_blind_seat: hand_config.small_blind_seat,
         big_blind_seat: hand_config.big_blind_seat,
@@ -283,7 +303,6 @@ async fn seat_players(
     lobby: &Arc<dyn LobbyService<Curve>>,
     metadata: &GameMetadata<Curve>,
     specs: &[PlayerSpec],
-    starting_stack: Chips,
 ) -> Result<Vec<PlayerSeatSnapshot<Curve>>> {
     let mut snapshots = Vec::with_capacity(PLAYER_COUNT);
 
@@ -297,14 +316,15 @@ async fn seat_players(
         },
     };
     let host_join = lobby
-        .join_game(&metadata.record, host, Some(specs[0].seat))
+        .join_game(&metadata.record, host, Some(specs[0].seat), None)
         .await
         .context("failed to seat host player")?;
 
     snapshots.push(PlayerSeatSnapshot::new(
         host_join.player.clone(),
         specs[0].seat,
-        starting_stack,
+        host_join.buy_in,
+        host_join.buy_in,
         specs[0].public_key.clone(),
     ));
     info!(
@@ -322,14 +342,15 @@ async fn seat_players(
             state: MaybeSaved { id: None },
         };
         let join = lobby
-            .join_game(&metadata.record, record, Some(spec.seat))
+            .join_game(&metadata.record, record, Some(spec.seat), None)
             .await
             .with_context(|| format!("failed to seat {}", spec.name))?;
 
         snapshots.push(PlayerSeatSnapshot::new(
             join.player.clone(),
             spec.seat,
-            starting_stack,
+            join.buy_in,
+            join.buy_in,
             spec.public_key.clone(),
         ));
         info!(
@@ -545,11 +566,15 @@ fn build_lobby_config() -> GameLobbyConfig {
         },
         max_players: 9,
         rake_bps: 0,
-        name: "Coordinator Demo Table".into(),
+        // Seeded runs reuse the host key, and a host's game names must be unique.
+        name: format!("Coordinator Demo Table {}", uuid::Uuid::new_v4()),
         currency: "chips".into(),
-        buy_in: 10_000,
+        min_buy_in: 4_000,
+        max_buy_in: 10_000,
         min_players_to_start: PLAYER_COUNT as i16,
+        min_shufflers: SHUFFLER_COUNT as i16,
         check_raise_allowed: true,
+        max_bet_bb: None,
         action_time_limit: Duration::from_secs(30),
     }
 }
@@ -568,6 +593,9 @@ fn build_hand_config(players: &[PlayerSpec]) -> HandConfig {
         small_blind_seat: small_blind,
         big_blind_seat: big_blind,
         check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
     }
 }
 
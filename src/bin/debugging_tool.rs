@@ -269,6 +269,9 @@ mod tests {
             small_blind_seat: 1,
             big_blind_seat: 2,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
 
         let shuffler_public = Curve::generator();
@@ -295,6 +298,7 @@ mod tests {
                 player_id: 200,
                 nonce: 0,
                 seat: 0,
+                auto_muck_losers: false,
             },
         );
 
@@ -320,6 +324,7 @@ mod tests {
             steps: Vec::new(),
             final_deck: deck,
             expected_order: vec![shuffler_key],
+            nonce_commitments: Vec::new(),
         };
 
         let table: TableSnapshot<PhaseShuffling, Curve> = TableSnapshot {
@@ -361,6 +366,7 @@ mod tests {
                 signature: vec![0, 1, 2, 3],
                 transcript,
             },
+            correlation_id: None,
         };
         let finalized = FinalizedAnyMessageEnvelope::new(
             envelope,
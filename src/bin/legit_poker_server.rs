@@ -6,12 +6,14 @@ use rand::{rngs::StdRng, SeedableRng};
 use std::env;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 use tracing::warn;
-use tracing_subscriber::{fmt, EnvFilter};
 use url::Url;
 
 use legit_poker::game::coordinator::{load_shuffler_secrets_from_env, ShufflerSecret};
-use legit_poker::server::{run_server as run_http_server, ServerConfig};
+use legit_poker::server::{
+    run_server as run_http_server, LogConfig, LogFormat, RateLimitConfig, ServerConfig,
+};
 
 use serde_json::Value as JsonValue;
 
@@ -48,20 +50,40 @@ struct Args {
     #[arg(long, env = "SERVER_RNG_SEED")]
     rng_seed: Option<u64>,
 
-    /// Toggle structured (JSON) logs
+    /// Log output format: `pretty` or `json`
+    #[arg(long, env = "SERVER_LOG_FORMAT", default_value_t = LogFormat::Pretty)]
+    log_format: LogFormat,
+
+    /// Shorthand for `--log-format json`
     #[arg(long, env = "SERVER_LOG_JSON", default_value_t = false)]
     json: bool,
 
+    /// Log secret-bearing fields verbatim instead of redacting them (local debugging only)
+    #[arg(long, env = "SERVER_UNSAFE_LOGGING", default_value_t = false)]
+    unsafe_logging: bool,
+
     /// Environment variable name (or inline JSON string) for shuffler secrets
     #[arg(long, env = "SERVER_SHUFFLER_SOURCE", default_value = DEFAULT_SHUFFLER_ENV)]
     shuffler_source: String,
+
+    /// Demo sessions a client may create back to back
+    #[arg(long, env = "SERVER_DEMO_RATE_BURST", default_value_t = RateLimitConfig::default().burst)]
+    demo_rate_burst: u32,
+
+    /// Demo sessions added to each client's allowance per minute
+    #[arg(long, env = "SERVER_DEMO_RATE_PER_MINUTE", default_value_t = RateLimitConfig::default().refill_per_minute)]
+    demo_rate_per_minute: u32,
+
+    /// Seconds a seat gets to act before the table checks or folds for it (untimed if unset)
+    #[arg(long, env = "SERVER_ACTION_TIMEOUT_SECS")]
+    action_timeout_secs: Option<u64>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     load_dotenv();
     let args = Args::parse();
-    init_tracing(args.json)?;
+    init_tracing(&args)?;
     let config = build_config(args).context("failed to build server config")?;
     run_http_server(config).await
 }
@@ -73,17 +95,16 @@ fn load_dotenv() {
     dotenv::dotenv().ok();
 }
 
-fn init_tracing(json: bool) -> Result<()> {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-    let builder = fmt::fmt().with_env_filter(filter).with_target(false);
-
-    if json {
-        builder.json().flatten_event(true).init();
-    } else {
-        builder.compact().init();
-    }
-
-    Ok(())
+fn init_tracing(args: &Args) -> Result<()> {
+    let config = LogConfig {
+        format: if args.json {
+            LogFormat::Json
+        } else {
+            args.log_format
+        },
+        unsafe_logging: args.unsafe_logging,
+    };
+    config.init()
 }
 
 fn build_config(args: Args) -> Result<ServerConfig<Curve>> {
@@ -114,6 +135,11 @@ fn build_config(args: Args) -> Result<ServerConfig<Curve>> {
         supabase_anon_key: args.supabase_anon_key,
         shufflers,
         rng_seed,
+        demo_rate_limit: RateLimitConfig {
+            burst: args.demo_rate_burst,
+            refill_per_minute: args.demo_rate_per_minute,
+        },
+        action_timeout: args.action_timeout_secs.map(Duration::from_secs),
     })
 }
 
@@ -1,9 +1,10 @@
 use crate::curve_absorb::CurveAbsorb;
 use crate::poseidon_config;
 use crate::signing::DomainSeparated;
+use crate::transcript::Transcript;
 use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge};
 use ark_ec::CurveGroup;
-use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::rand::Rng;
 use ark_std::Zero;
@@ -131,21 +132,15 @@ where
     {
         tracing::debug!(target: LOG_TARGET, "Computing Fiat-Shamir challenge (native)");
 
-        // Absorb t_g as affine point (matching circuit behavior)
+        // Absorb t_g and t_h as affine points (matching circuit behavior)
         tracing::debug!(target: LOG_TARGET, "Absorbing t_g: {:?}", t_g);
-        t_g.curve_absorb(sponge);
-
-        // Absorb t_h as affine point (matching circuit behavior)
+        sponge.absorb_point(&t_g);
         tracing::debug!(target: LOG_TARGET, "Absorbing t_h: {:?}", t_h);
-        t_h.curve_absorb(sponge);
-
-        // Generate challenge in base field
-        let challenge_base: C::BaseField = sponge.squeeze_field_elements::<C::BaseField>(1)[0];
-        tracing::debug!(target: LOG_TARGET, "Computed challenge (base field): {:?}", challenge_base);
+        sponge.absorb_point(&t_h);
 
-        // Convert to scalar field - must match circuit's embed_to_emulated behavior
-        let bytes = challenge_base.into_bigint().to_bytes_le();
-        let challenge_scalar = C::ScalarField::from_le_bytes_mod_order(&bytes);
+        // Squeeze in the base field and reduce into the scalar field, matching the circuit's
+        // embed_to_emulated behavior
+        let challenge_scalar: C::ScalarField = sponge.challenge_scalar::<C::BaseField, _>();
 
         tracing::debug!(target: LOG_TARGET, "Converted challenge (scalar field): {:?}", challenge_scalar);
 
@@ -270,6 +265,27 @@ mod tests {
         );
     }
 
+    /// Golden vector: the challenge the raw sponge produced before it went through `Transcript`.
+    #[test]
+    fn test_challenge_matches_golden_vector() {
+        use std::str::FromStr;
+        type ScalarField = <G1Projective as PrimeGroup>::ScalarField;
+        type BaseField = <G1Projective as CurveGroup>::BaseField;
+
+        let t_g = G1Projective::generator() * ScalarField::from(2u64);
+        let t_h = G1Projective::generator() * ScalarField::from(3u64);
+        let expected = ScalarField::from_str(
+            "18907265389057276641920110094523861550611983730631066366213840947454163382344",
+        )
+        .unwrap();
+
+        let mut sponge = PoseidonSponge::new(&poseidon_config::<BaseField>());
+        assert_eq!(
+            ChaumPedersenProof::<G1Projective>::compute_challenge(&mut sponge, t_g, t_h),
+            expected
+        );
+    }
+
     #[test]
     fn test_batch_verification() {
         let mut rng = test_rng();
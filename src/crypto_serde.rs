@@ -5,11 +5,12 @@ use serde::ser::Error as SerError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::ledger::serialization::{
-    canonical_deserialize_hex, canonical_serialize_hex_prefixed, deserialize_curve_hex,
-    serialize_curve_hex,
+    canonical_deserialize_hex, canonical_serialize_hex_prefixed, deserialize_curve_hex_any,
+    serialize_curve_hex_with, PointEncoding,
 };
 
-/// Serde helpers for encoding curve points as 0x-prefixed hex strings.
+/// Serde helpers for encoding curve points as 0x-prefixed hex strings, written in
+/// [`PointEncoding::current`]. Decoding accepts either [`PointEncoding`].
 pub mod curve {
     use super::*;
 
@@ -18,7 +19,8 @@ pub mod curve {
         C: CurveGroup + CanonicalSerialize,
         S: Serializer,
     {
-        let hex = serialize_curve_hex(value).map_err(SerError::custom)?;
+        let hex =
+            serialize_curve_hex_with(value, PointEncoding::current()).map_err(SerError::custom)?;
         serializer.serialize_str(&hex)
     }
 
@@ -28,7 +30,7 @@ pub mod curve {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        deserialize_curve_hex(&s).map_err(DeError::custom)
+        deserialize_curve_hex_any(&s).map_err(DeError::custom)
     }
 }
 
@@ -55,7 +57,8 @@ pub mod curve_map {
         let entries: Vec<Entry<K>> = value
             .iter()
             .map(|(key, point)| {
-                let hex = serialize_curve_hex(point).map_err(SerError::custom)?;
+                let hex = serialize_curve_hex_with(point, PointEncoding::current())
+                    .map_err(SerError::custom)?;
                 Ok(Entry {
                     key: key.clone(),
                     value: hex,
@@ -77,7 +80,7 @@ pub mod curve_map {
         entries
             .into_iter()
             .map(|entry| {
-                let point = deserialize_curve_hex(&entry.value).map_err(DeError::custom)?;
+                let point = deserialize_curve_hex_any(&entry.value).map_err(DeError::custom)?;
                 Ok((entry.key, point))
             })
             .collect()
@@ -411,6 +414,32 @@ pub mod chaum_pedersen {
     }
 }
 
+/// Serde helpers for optional scalar/base-field elements; `None` is encoded as `null`.
+pub mod field_option {
+    use super::*;
+
+    pub fn serialize<F, S>(value: &Option<F>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        F: CanonicalSerialize,
+        S: Serializer,
+    {
+        match value {
+            Some(field) => super::field::serialize(field, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, F, D>(deserializer: D) -> std::result::Result<Option<F>, D::Error>
+    where
+        F: CanonicalDeserialize,
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| canonical_deserialize_hex(&s).map_err(DeError::custom))
+            .transpose()
+    }
+}
+
 /// Serde helpers for vectors of scalar/base field elements encoded as hex strings.
 pub mod field_vec {
     use super::*;
@@ -766,7 +795,8 @@ pub mod player_unblinding_combined_map {
         let entries: Result<Vec<Entry>, _> = value
             .iter()
             .map(|((seat, hole_index), point)| {
-                let hex = serialize_curve_hex(point).map_err(SerError::custom)?;
+                let hex = serialize_curve_hex_with(point, PointEncoding::current())
+                    .map_err(SerError::custom)?;
                 Ok(Entry {
                     seat: *seat,
                     hole_index: *hole_index,
@@ -788,7 +818,7 @@ pub mod player_unblinding_combined_map {
         entries
             .into_iter()
             .map(|entry| {
-                let point = deserialize_curve_hex(&entry.value).map_err(DeError::custom)?;
+                let point = deserialize_curve_hex_any(&entry.value).map_err(DeError::custom)?;
                 Ok(((entry.seat, entry.hole_index), point))
             })
             .collect()
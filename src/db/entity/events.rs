@@ -37,6 +37,7 @@ pub struct Model {
     pub payload: Json,
     pub signature: Vec<u8>,
     pub inserted_at: TimeDateTimeWithTimeZone,
+    pub correlation_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -60,6 +61,7 @@ pub enum Column {
     Payload,
     Signature,
     InsertedAt,
+    CorrelationId,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -104,6 +106,7 @@ impl ColumnTrait for Column {
             Self::Payload => ColumnType::JsonBinary.def(),
             Self::Signature => ColumnType::VarBinary(StringLen::None).def(),
             Self::InsertedAt => ColumnType::TimestampWithTimeZone.def(),
+            Self::CorrelationId => ColumnType::Text.def().null(),
         }
     }
 }
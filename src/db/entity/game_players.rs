@@ -21,6 +21,7 @@ pub struct Model {
     pub player_id: i64,
     pub seat_preference: Option<i16>,
     pub joined_at: TimeDateTimeWithTimeZone,
+    pub buy_in: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -29,6 +30,7 @@ pub enum Column {
     PlayerId,
     SeatPreference,
     JoinedAt,
+    BuyIn,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -59,6 +61,7 @@ impl ColumnTrait for Column {
             Self::PlayerId => ColumnType::BigInteger.def(),
             Self::SeatPreference => ColumnType::SmallInteger.def().null(),
             Self::JoinedAt => ColumnType::TimestampWithTimeZone.def(),
+            Self::BuyIn => ColumnType::BigInteger.def().null(),
         }
     }
 }
@@ -30,7 +30,7 @@ pub struct Model {
     pub ante: i64,
     pub rake_bps: i16,
     pub status: GameStatus,
-    pub buy_in: i64,
+    pub max_buy_in: i64,
     pub min_players_to_start: i16,
     pub check_raise_allowed: bool,
     pub action_time_limit_secs: i32,
@@ -38,6 +38,10 @@ pub struct Model {
     pub current_state_hash: Option<Vec<u8>>,
     pub current_phase: Option<PhaseKind>,
     pub default_hand_config_id: Option<i64>,
+    pub min_buy_in: i64,
+    pub last_hand_no: i64,
+    pub min_shufflers: i16,
+    pub max_bet_bb: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -53,7 +57,7 @@ pub enum Column {
     Ante,
     RakeBps,
     Status,
-    BuyIn,
+    MaxBuyIn,
     MinPlayersToStart,
     CheckRaiseAllowed,
     ActionTimeLimitSecs,
@@ -61,6 +65,10 @@ pub enum Column {
     CurrentStateHash,
     CurrentPhase,
     DefaultHandConfigId,
+    MinBuyIn,
+    LastHandNo,
+    MinShufflers,
+    MaxBetBb,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -102,7 +110,7 @@ impl ColumnTrait for Column {
             Self::Ante => ColumnType::BigInteger.def(),
             Self::RakeBps => ColumnType::SmallInteger.def(),
             Self::Status => GameStatus::db_type().get_column_type().to_owned().def(),
-            Self::BuyIn => ColumnType::BigInteger.def(),
+            Self::MaxBuyIn => ColumnType::BigInteger.def(),
             Self::MinPlayersToStart => ColumnType::SmallInteger.def(),
             Self::CheckRaiseAllowed => ColumnType::Boolean.def(),
             Self::ActionTimeLimitSecs => ColumnType::Integer.def(),
@@ -114,6 +122,10 @@ impl ColumnTrait for Column {
                 .def()
                 .null(),
             Self::DefaultHandConfigId => ColumnType::BigInteger.def().null(),
+            Self::MinBuyIn => ColumnType::BigInteger.def(),
+            Self::LastHandNo => ColumnType::BigInteger.def(),
+            Self::MinShufflers => ColumnType::SmallInteger.def(),
+            Self::MaxBetBb => ColumnType::BigInteger.def().null(),
         }
     }
 }
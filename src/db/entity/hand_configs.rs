@@ -26,6 +26,9 @@ pub struct Model {
     pub small_blind_seat: i16,
     pub big_blind_seat: i16,
     pub check_raise_allowed: bool,
+    pub hole_cards: i16,
+    pub max_bet_bb: Option<i64>,
+    pub dealing_order: i16,
     pub created_at: TimeDateTimeWithTimeZone,
 }
 
@@ -40,6 +43,9 @@ pub enum Column {
     SmallBlindSeat,
     BigBlindSeat,
     CheckRaiseAllowed,
+    HoleCards,
+    MaxBetBb,
+    DealingOrder,
     CreatedAt,
 }
 
@@ -75,6 +81,9 @@ impl ColumnTrait for Column {
             Self::SmallBlindSeat => ColumnType::SmallInteger.def(),
             Self::BigBlindSeat => ColumnType::SmallInteger.def(),
             Self::CheckRaiseAllowed => ColumnType::Boolean.def(),
+            Self::HoleCards => ColumnType::SmallInteger.def(),
+            Self::MaxBetBb => ColumnType::BigInteger.def().null(),
+            Self::DealingOrder => ColumnType::SmallInteger.def(),
             Self::CreatedAt => ColumnType::TimestampWithTimeZone.def(),
         }
     }
@@ -25,6 +25,7 @@ pub struct Model {
     pub nonce: i64,
     pub starting_stack: i64,
     pub joined_at: TimeDateTimeWithTimeZone,
+    pub auto_muck_losers: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -37,6 +38,7 @@ pub enum Column {
     Nonce,
     StartingStack,
     JoinedAt,
+    AutoMuckLosers,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -71,6 +73,7 @@ impl ColumnTrait for Column {
             Self::Nonce => ColumnType::BigInteger.def(),
             Self::StartingStack => ColumnType::BigInteger.def(),
             Self::JoinedAt => ColumnType::TimestampWithTimeZone.def(),
+            Self::AutoMuckLosers => ColumnType::Boolean.def(),
         }
     }
 }
@@ -21,6 +21,7 @@ pub struct Model {
     pub display_name: String,
     pub public_key: Vec<u8>,
     pub created_at: TimeDateTimeWithTimeZone,
+    pub auto_muck_losers: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -29,6 +30,7 @@ pub enum Column {
     DisplayName,
     PublicKey,
     CreatedAt,
+    AutoMuckLosers,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -58,6 +60,7 @@ impl ColumnTrait for Column {
             Self::DisplayName => ColumnType::Text.def(),
             Self::PublicKey => ColumnType::VarBinary(StringLen::None).def().unique(),
             Self::CreatedAt => ColumnType::TimestampWithTimeZone.def(),
+            Self::AutoMuckLosers => ColumnType::Boolean.def(),
         }
     }
 }
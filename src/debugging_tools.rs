@@ -1,10 +1,15 @@
 use std::collections::{HashMap, HashSet};
+use std::{fmt, io};
 
 use anyhow::{anyhow, Context, Result};
 use sea_orm::prelude::TimeDateTimeWithTimeZone;
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
 use serde_json::Value as JsonValue;
 use time::format_description::well_known::Rfc3339;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::field::{MakeVisitor, VisitFmt, VisitOutput};
+use tracing_subscriber::fmt::format::{DefaultVisitor, Writer};
+use tracing_subscriber::fmt::MakeWriter;
 
 use crate::db::entity::sea_orm_active_enums::{
     ApplicationStatus, EventPhase, GameStatus, HandStatus, PhaseKind,
@@ -79,6 +84,9 @@ pub struct HandConfigRecord {
     pub small_blind_seat: i16,
     pub big_blind_seat: i16,
     pub check_raise_allowed: bool,
+    pub hole_cards: i16,
+    pub max_bet_bb: Option<i64>,
+    pub dealing_order: i16,
     pub created_at: String,
 }
 
@@ -159,6 +167,7 @@ pub struct EventRecord {
     pub payload: JsonValue,
     pub signature: String,
     pub inserted_at: String,
+    pub correlation_id: Option<String>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -308,7 +317,7 @@ impl From<games::Model> for GameRecord {
             ante,
             rake_bps,
             status,
-            buy_in: _,
+            max_buy_in: _,
             min_players_to_start: _,
             check_raise_allowed: _,
             action_time_limit_secs: _,
@@ -316,6 +325,10 @@ impl From<games::Model> for GameRecord {
             current_state_hash,
             current_phase,
             default_hand_config_id,
+            min_buy_in: _,
+            last_hand_no: _,
+            min_shufflers: _,
+            max_bet_bb: _,
         } = model;
 
         Self {
@@ -386,6 +399,9 @@ impl From<hand_configs::Model> for HandConfigRecord {
             small_blind_seat,
             big_blind_seat,
             check_raise_allowed,
+            hole_cards,
+            max_bet_bb,
+            dealing_order,
             created_at,
         } = model;
 
@@ -399,6 +415,9 @@ impl From<hand_configs::Model> for HandConfigRecord {
             small_blind_seat,
             big_blind_seat,
             check_raise_allowed,
+            hole_cards,
+            max_bet_bb,
+            dealing_order,
             created_at: format_timestamp(created_at),
         }
     }
@@ -415,6 +434,7 @@ impl From<hand_player::Model> for HandPlayerSeat {
             nonce,
             starting_stack: _,
             joined_at,
+            auto_muck_losers: _,
         } = model;
 
         Self {
@@ -436,6 +456,7 @@ impl From<players::Model> for PlayerRecord {
             display_name,
             public_key,
             created_at,
+            auto_muck_losers: _,
         } = model;
 
         Self {
@@ -523,6 +544,7 @@ impl From<events::Model> for EventRecord {
             payload,
             signature,
             inserted_at,
+            correlation_id,
         } = model;
 
         Self {
@@ -545,6 +567,7 @@ impl From<events::Model> for EventRecord {
             payload,
             signature: bytes_to_hex(signature),
             inserted_at: format_timestamp(inserted_at),
+            correlation_id,
         }
     }
 }
@@ -696,6 +719,203 @@ fn format_timestamp(ts: TimeDateTimeWithTimeZone) -> String {
     ts.format(&Rfc3339).unwrap_or_else(|_| ts.to_string())
 }
 
+/// Field names whose values carry key material or values derived from it.
+///
+/// Log sites that record secrets must use one of these names so [`RedactionLayer`] can find
+/// them.
+pub const SENSITIVE_LOG_FIELDS: &[&str] = &[
+    "combined_unblinding",
+    "player_secret",
+    "player_secret_bits",
+    "player_unblinding",
+    "recovered_card_point",
+    "secret",
+    "secret_key",
+    "unblinding_share",
+];
+
+/// Written in place of a sensitive field's value.
+///
+/// The value is dropped entirely rather than fingerprinted: card values and secret bits come
+/// from tiny domains, so any unkeyed digest of them can be reversed by enumeration.
+pub const REDACTED_PLACEHOLDER: &str = "<redacted>";
+
+/// Field formatter for the `tracing_subscriber::fmt` text formats that replaces sensitive
+/// fields with [`REDACTED_PLACEHOLDER`].
+///
+/// Install it with `fmt().fmt_fields(RedactionLayer::new(unsafe_logging))`. The JSON event
+/// formatter serializes event fields itself and bypasses field formatters, so JSON output is
+/// redacted by [`RedactingJsonWriter`] instead.
+#[derive(Clone, Copy, Debug)]
+pub struct RedactionLayer {
+    sensitive: &'static [&'static str],
+    unsafe_logging: bool,
+}
+
+impl RedactionLayer {
+    /// Redact [`SENSITIVE_LOG_FIELDS`] unless `unsafe_logging` is set.
+    pub fn new(unsafe_logging: bool) -> Self {
+        Self {
+            sensitive: SENSITIVE_LOG_FIELDS,
+            unsafe_logging,
+        }
+    }
+
+    pub fn with_sensitive_fields(mut self, fields: &'static [&'static str]) -> Self {
+        self.sensitive = fields;
+        self
+    }
+}
+
+impl<'a> MakeVisitor<Writer<'a>> for RedactionLayer {
+    type Visitor = RedactingVisitor<'a>;
+
+    fn make_visitor(&self, target: Writer<'a>) -> Self::Visitor {
+        RedactingVisitor {
+            inner: DefaultVisitor::new(target, true),
+            sensitive: if self.unsafe_logging {
+                &[]
+            } else {
+                self.sensitive
+            },
+        }
+    }
+}
+
+pub struct RedactingVisitor<'a> {
+    inner: DefaultVisitor<'a>,
+    sensitive: &'static [&'static str],
+}
+
+impl RedactingVisitor<'_> {
+    fn is_sensitive(&self, field: &Field) -> bool {
+        self.sensitive.contains(&field.name())
+    }
+}
+
+impl Visit for RedactingVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if self.is_sensitive(field) {
+            self.inner
+                .record_debug(field, &format_args!("{REDACTED_PLACEHOLDER}"));
+        } else {
+            self.inner.record_str(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.is_sensitive(field) {
+            self.inner
+                .record_debug(field, &format_args!("{REDACTED_PLACEHOLDER}"));
+        } else {
+            self.inner.record_debug(field, value);
+        }
+    }
+}
+
+impl VisitOutput<fmt::Result> for RedactingVisitor<'_> {
+    fn finish(self) -> fmt::Result {
+        self.inner.finish()
+    }
+}
+
+impl VisitFmt for RedactingVisitor<'_> {
+    fn writer(&mut self) -> &mut dyn fmt::Write {
+        self.inner.writer()
+    }
+}
+
+/// [`MakeWriter`] for the JSON log format that replaces the value of every sensitive key, at
+/// any depth (event fields, `span`, `spans`), with [`REDACTED_PLACEHOLDER`] before the line
+/// reaches `inner`.
+///
+/// The fmt layer hands each formatted event to its writer in one `write` call, so every call is
+/// treated as a batch of complete lines. Lines that are not JSON are passed through unchanged.
+#[derive(Clone, Debug)]
+pub struct RedactingJsonWriter<W> {
+    inner: W,
+    sensitive: &'static [&'static str],
+}
+
+impl<W> RedactingJsonWriter<W> {
+    /// Redact [`SENSITIVE_LOG_FIELDS`] unless `unsafe_logging` is set.
+    pub fn new(inner: W, unsafe_logging: bool) -> Self {
+        Self {
+            inner,
+            sensitive: if unsafe_logging {
+                &[]
+            } else {
+                SENSITIVE_LOG_FIELDS
+            },
+        }
+    }
+}
+
+impl<'a, W: MakeWriter<'a>> MakeWriter<'a> for RedactingJsonWriter<W> {
+    type Writer = RedactingJsonLines<W::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingJsonLines {
+            inner: self.inner.make_writer(),
+            sensitive: self.sensitive,
+        }
+    }
+}
+
+pub struct RedactingJsonLines<W> {
+    inner: W,
+    sensitive: &'static [&'static str],
+}
+
+impl<W: io::Write> io::Write for RedactingJsonLines<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.sensitive.is_empty() {
+            return self.inner.write(buf);
+        }
+        let mut out = Vec::with_capacity(buf.len());
+        for line in buf.split_inclusive(|byte| *byte == b'\n') {
+            let (body, newline) = match line.strip_suffix(b"\n") {
+                Some(body) => (body, &b"\n"[..]),
+                None => (line, &b""[..]),
+            };
+            match serde_json::from_slice::<JsonValue>(body) {
+                Ok(mut value) => {
+                    redact_json(&mut value, self.sensitive);
+                    serde_json::to_writer(&mut out, &value)?;
+                    out.extend_from_slice(newline);
+                }
+                Err(_) => out.extend_from_slice(line),
+            }
+        }
+        self.inner.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn redact_json(value: &mut JsonValue, sensitive: &[&str]) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if sensitive.contains(&key.as_str()) {
+                    *field = JsonValue::String(REDACTED_PLACEHOLDER.to_string());
+                } else {
+                    redact_json(field, sensitive);
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                redact_json(item, sensitive);
+            }
+        }
+        _ => {}
+    }
+}
+
 fn bytes_to_hex(bytes: Vec<u8>) -> String {
     let mut out = String::with_capacity(2 + bytes.len() * 2);
     out.push_str("0x");
@@ -706,6 +926,9 @@ fn bytes_to_hex(bytes: Vec<u8>) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ark_bn254::Fr;
+    use std::io;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn bytes_to_hex_prefix() {
@@ -720,4 +943,46 @@ mod tests {
         assert!(formatted.contains('T'));
         assert!(formatted.contains('Z') || formatted.contains('+'));
     }
+    #[derive(Clone)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture_logs(unsafe_logging: bool, emit: impl FnOnce()) -> String {
+        let captured = CapturedLogs(Arc::new(Mutex::new(Vec::new())));
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_max_level(tracing::Level::DEBUG)
+            .fmt_fields(RedactionLayer::new(unsafe_logging))
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, emit);
+        let bytes = captured.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn redaction_replaces_secret_scalar_with_placeholder() {
+        let secret = Fr::from(0x5ec7e7u64);
+        let rendered = format!("{secret:?}");
+        let emit = || tracing::debug!(player_secret = ?secret, seat = 3, "decrypting hole card");
+
+        let redacted = capture_logs(false, emit);
+        assert!(!redacted.contains(&rendered), "secret leaked: {redacted}");
+        assert!(redacted.contains(&format!("player_secret={REDACTED_PLACEHOLDER}")));
+        assert!(redacted.contains("seat=3"));
+
+        let unsafe_output = capture_logs(true, emit);
+        assert!(unsafe_output.contains(&rendered));
+    }
 }
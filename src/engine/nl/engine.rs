@@ -1,9 +1,9 @@
 use super::actions::PlayerBetAction;
 use super::errors::{ActionError, StateError};
-use super::events::{GameEvent, NormalizedAction};
+use super::events::{BettingEvent, GameEvent, NormalizedAction};
 use super::legals::legal_actions_for;
 use super::rules::NoLimitRules;
-use super::seating::Seating;
+use super::seating::{must_post_blind_to_enter, regular_blind, Seating};
 use super::state::BettingState;
 use super::types::{
     ActionLogEntry, Chips, HandConfig, PlayerState, PlayerStatus, Pots, SeatId, Street,
@@ -26,6 +26,16 @@ pub enum Transition {
     },
 }
 
+impl Transition {
+    pub fn events(&self) -> &[GameEvent] {
+        match self {
+            Transition::Continued { events, .. }
+            | Transition::StreetEnd { events, .. }
+            | Transition::HandEnd { events, .. } => events,
+        }
+    }
+}
+
 pub trait BettingEngineNL {
     fn new_after_deal(cfg: HandConfig, players: Vec<PlayerState>, pots: Pots) -> BettingState;
     fn legal_actions(state: &BettingState, seat: SeatId) -> super::legals::LegalActions;
@@ -40,6 +50,127 @@ pub trait BettingEngineNL {
 pub struct EngineNL;
 
 impl EngineNL {
+    /// [`BettingEngineNL::new_after_deal`], rejecting blind positions that fail
+    /// [`EngineNL::validate_blind_config`].
+    pub fn try_new_after_deal(
+        cfg: HandConfig,
+        players: Vec<PlayerState>,
+        pots: Pots,
+    ) -> Result<BettingState, StateError> {
+        Self::validate_blind_config(&cfg, &players)?;
+        Ok(Self::new_after_deal(cfg, players, pots))
+    }
+
+    /// Check that the small and big blind are distinct seats held by players dealt into the hand.
+    /// The button may be empty or sitting out (a dead button), and may post the small blind only
+    /// heads-up.
+    pub fn validate_blind_config(
+        cfg: &HandConfig,
+        players: &[PlayerState],
+    ) -> Result<(), StateError> {
+        let dealt_in =
+            |p: &PlayerState| matches!(p.status, PlayerStatus::Active | PlayerStatus::AllIn);
+        let is_dealt_in = |seat: SeatId| players.iter().any(|p| p.seat == seat && dealt_in(p));
+
+        if cfg.small_blind_seat == cfg.big_blind_seat {
+            return Err(StateError::InvalidBlindConfig(
+                "small and big blind share a seat",
+            ));
+        }
+        if !is_dealt_in(cfg.small_blind_seat) {
+            return Err(StateError::InvalidBlindConfig(
+                "small blind is not a seated player",
+            ));
+        }
+        if !is_dealt_in(cfg.big_blind_seat) {
+            return Err(StateError::InvalidBlindConfig(
+                "big blind is not a seated player",
+            ));
+        }
+        if cfg.button == cfg.big_blind_seat {
+            return Err(StateError::InvalidBlindConfig(
+                "button and big blind share a seat",
+            ));
+        }
+        if cfg.button == cfg.small_blind_seat && players.iter().filter(|p| dealt_in(p)).count() != 2
+        {
+            return Err(StateError::InvalidBlindConfig(
+                "button posts the small blind outside heads-up",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Deal a player who sat down mid-orbit into the preflop round built by `new_after_deal`
+    /// without having posted an entry blind up front.
+    ///
+    /// The entry blind from [`must_post_blind_to_enter`] is moved from the stack into the pot
+    /// (all-in if the stack is short) and counts as a call of the big blind. An entrant who has
+    /// to wait is marked `SittingOut` and dropped from the pot. Returns the chips posted.
+    pub fn seat_entrant(state: &mut BettingState, seat: SeatId) -> Result<Chips, StateError> {
+        if state.street != Street::Preflop {
+            return Err(StateError::InvalidTransition);
+        }
+        let entry_blind = must_post_blind_to_enter(state, seat);
+        let idx = state
+            .players
+            .iter()
+            .position(|p| p.seat == seat)
+            .ok_or(StateError::InvariantViolation("entrant seat not in hand"))?;
+
+        let Some(entry_blind) = entry_blind else {
+            state.players[idx].status = PlayerStatus::SittingOut;
+            state.pots.main.eligible.retain(|s| *s != seat);
+            state.pending_to_match.retain(|s| *s != seat);
+            if state.to_act == seat {
+                state.to_act = state.next_actor(seat);
+            }
+            return Ok(0);
+        };
+
+        let player = &mut state.players[idx];
+        let posted = entry_blind.min(player.stack);
+        player.stack -= posted;
+        player.committed_this_round += posted;
+        let committed = player.committed_this_round;
+        state.pots.main.amount += posted;
+        Self::mark_all_in_if_stack_empty(state, idx);
+        if committed >= state.current_bet_to_match
+            || state.players[idx].status != PlayerStatus::Active
+        {
+            state.pending_to_match.retain(|s| *s != seat);
+        }
+        Ok(posted)
+    }
+
+    /// Settle entry blinds posted before the deal. Chips a player committed beyond their ante and
+    /// regular blind are an entry blind: the part [`must_post_blind_to_enter`] asks for stays
+    /// in, any excess goes back to the stack, and an entrant who has to wait for the big blind
+    /// gets everything back and sits the hand out.
+    fn settle_entry_blinds(state: &mut BettingState) {
+        for idx in 0..state.players.len() {
+            let player = &state.players[idx];
+            let seat = player.seat;
+            let regular = state.cfg.stakes.ante + regular_blind(&state.cfg, seat);
+            let posted = player.committed_this_round.saturating_sub(regular);
+            if player.status != PlayerStatus::Active || posted == 0 {
+                continue;
+            }
+            let owed = must_post_blind_to_enter(state, seat);
+            let player = &mut state.players[idx];
+            let refund = match owed {
+                Some(owed) => posted.saturating_sub(owed),
+                None => {
+                    player.status = PlayerStatus::SittingOut;
+                    player.committed_this_round
+                }
+            };
+            player.committed_this_round -= refund;
+            player.stack += refund;
+            Self::mark_all_in_if_stack_empty(state, idx);
+        }
+    }
+
     fn next_pending_after(state: &BettingState, from: SeatId) -> Option<SeatId> {
         let n = state.players.len() as u8;
         let mut i = (from + 1) % n;
@@ -205,6 +336,22 @@ impl EngineNL {
         state.pending_to_match = pending;
     }
 
+    /// Whether `to` is over the hand's bet cap. Committing the whole stack (`max_to`) is an all-in,
+    /// which the cap never blocks.
+    fn exceeds_bet_cap(state: &BettingState, to: Chips, max_to: Chips) -> bool {
+        to != max_to && state.cfg.max_bet_to().is_some_and(|cap| to > cap)
+    }
+
+    /// Mark the player at `idx` all-in once their stack is exhausted; returns whether it was.
+    fn mark_all_in_if_stack_empty(state: &mut BettingState, idx: usize) -> bool {
+        if state.players[idx].stack == 0 {
+            state.players[idx].status = PlayerStatus::AllIn;
+            true
+        } else {
+            false
+        }
+    }
+
     fn push_log(state: &mut BettingState, seat: SeatId, action: NormalizedAction, price: Chips) {
         let entry = ActionLogEntry {
             street: state.street,
@@ -215,25 +362,35 @@ impl EngineNL {
         };
         state.action_log.0.push(entry);
     }
+
+    /// Structured outcome of the action just logged for the player at `idx`, read back from the
+    /// updated state so replays and animations don't have to recompute pots or all-in status.
+    fn betting_event(state: &BettingState, idx: usize) -> GameEvent {
+        let player = &state.players[idx];
+        let action = state
+            .action_log
+            .0
+            .last()
+            .map(|entry| entry.action.clone())
+            .expect("action logged before its betting event");
+        GameEvent::Betting(BettingEvent::ActionApplied {
+            seat: player.seat,
+            action,
+            resulting_bet: player.committed_this_round,
+            pot_after: state.pots.total(),
+            is_all_in: player.status == PlayerStatus::AllIn,
+        })
+    }
 }
 
 impl BettingEngineNL for EngineNL {
     fn new_after_deal(cfg: HandConfig, players: Vec<PlayerState>, pots: Pots) -> BettingState {
-        // Preflop setup assumes blinds/antes are already reflected in committed_this_round of players
-        // Determine current_bet_to_match from max committed among active players
-        let current_bet_to_match = players
-            .iter()
-            .filter(|p| p.status == PlayerStatus::Active)
-            .map(|p| p.committed_this_round)
-            .max()
-            .unwrap_or(0);
-
         let mut state = BettingState {
             street: Street::Preflop,
             button: cfg.button,
             first_to_act: 0,
             to_act: 0,
-            current_bet_to_match,
+            current_bet_to_match: 0,
             last_full_raise_amount: cfg.stakes.big_blind, // preflop min raise size = BB
             last_aggressor: None,
             voluntary_bet_opened: false,
@@ -245,7 +402,38 @@ impl BettingEngineNL for EngineNL {
             action_log: Default::default(),
         };
 
+        // Preflop setup assumes blinds/antes are already reflected in committed_this_round of players
+        // Mid-orbit entrants may also have posted an entry blind; settle it against the seat.
+        Self::settle_entry_blinds(&mut state);
+        // A player whose stack could not cover the ante posted what they had and is all-in; the
+        // short contribution becomes its own pot layer when pots are refreshed below.
+        let ante = state.cfg.stakes.ante;
+        for p in state.players.iter_mut() {
+            if p.status == PlayerStatus::Active
+                && p.stack == 0
+                && p.committed_this_round > 0
+                && p.committed_this_round <= ante
+            {
+                p.status = PlayerStatus::AllIn;
+            }
+        }
+        // Determine current_bet_to_match from max committed among players still in the hand
+        state.current_bet_to_match = state
+            .players
+            .iter()
+            .filter(|p| matches!(p.status, PlayerStatus::Active | PlayerStatus::AllIn))
+            .map(|p| p.committed_this_round)
+            .max()
+            .unwrap_or(0);
+
         state.first_to_act = state.compute_first_to_act(Street::Preflop);
+        let first_is_all_in = state
+            .players
+            .iter()
+            .any(|p| p.seat == state.first_to_act && p.status == PlayerStatus::AllIn);
+        if first_is_all_in {
+            state.first_to_act = state.next_actor(state.first_to_act);
+        }
         state.to_act = state.first_to_act;
         // pending: who must act before BB in unopened preflop (skip BTN),
         // otherwise default to anyone owing chips (< current_bet_to_match)
@@ -341,6 +529,8 @@ impl BettingEngineNL for EngineNL {
                 state.refresh_pots();
                 events.push(GameEvent::PotUpdated);
 
+                events.push(Self::betting_event(state, idx));
+
                 if let Some(t) = Self::hand_end_if_only_one_left(state, events.clone()) {
                     return Ok(t);
                 }
@@ -357,6 +547,7 @@ impl BettingEngineNL for EngineNL {
                     action: na.clone(),
                 });
                 Self::push_log(state, seat, na, price);
+                events.push(Self::betting_event(state, idx));
                 Ok(Self::end_street_if_done(state, events))
             }
             PlayerBetAction::Call => {
@@ -369,6 +560,7 @@ impl BettingEngineNL for EngineNL {
                         action: na.clone(),
                     });
                     Self::push_log(state, seat, na, price);
+                    events.push(Self::betting_event(state, idx));
                     return Ok(Self::end_street_if_done(state, events));
                 }
                 let can_add = state.players[idx].stack;
@@ -377,9 +569,8 @@ impl BettingEngineNL for EngineNL {
                 state.players[idx].committed_this_round = state.players[idx]
                     .committed_this_round
                     .saturating_add(to_add);
-                if to_add < price {
-                    state.players[idx].status = PlayerStatus::AllIn;
-                }
+                // A call that empties the stack is all-in, even when it matches the bet exactly.
+                let all_in = Self::mark_all_in_if_stack_empty(state, idx);
                 state.players[idx].has_acted_this_round = true;
                 state.pending_to_match.retain(|s| *s != seat);
 
@@ -396,6 +587,13 @@ impl BettingEngineNL for EngineNL {
                 state.refresh_pots();
                 events.push(GameEvent::PotUpdated);
 
+                if all_in {
+                    if let Some(ev) = state.recompute_lock_if_all_in() {
+                        events.push(ev);
+                    }
+                }
+
+                events.push(Self::betting_event(state, idx));
                 Ok(Self::end_street_if_done(state, events))
             }
             PlayerBetAction::BetTo { to } => {
@@ -412,9 +610,13 @@ impl BettingEngineNL for EngineNL {
                 if to > max_to {
                     return Err(ActionError::InsufficientChips);
                 }
+                if Self::exceeds_bet_cap(state, to, max_to) {
+                    return Err(ActionError::ExceedsBetCap);
+                }
                 let add = to - cur;
                 state.players[idx].stack -= add;
                 state.players[idx].committed_this_round = to;
+                let all_in = Self::mark_all_in_if_stack_empty(state, idx);
                 state.players[idx].has_acted_this_round = true;
                 state.last_full_raise_amount = to; // first open defines LFR
                 state.last_aggressor = Some(seat);
@@ -431,6 +633,13 @@ impl BettingEngineNL for EngineNL {
                 state.refresh_pots();
                 events.push(GameEvent::PotUpdated);
 
+                if all_in {
+                    if let Some(ev) = state.recompute_lock_if_all_in() {
+                        events.push(ev);
+                    }
+                }
+
+                events.push(Self::betting_event(state, idx));
                 Ok(Self::end_street_if_done(state, events))
             }
             PlayerBetAction::RaiseTo { to } => {
@@ -450,6 +659,9 @@ impl BettingEngineNL for EngineNL {
                 if to > max_to {
                     return Err(ActionError::InsufficientChips);
                 }
+                if Self::exceeds_bet_cap(state, to, max_to) {
+                    return Err(ActionError::ExceedsBetCap);
+                }
                 let raise_amount = to - state.current_bet_to_match;
                 let is_full = <BettingState as NoLimitRules>::is_full_raise(state, raise_amount)
                     || to == max_to; // allow all-in short raise via RaiseTo when equals max
@@ -462,6 +674,7 @@ impl BettingEngineNL for EngineNL {
                 let add = to - cur;
                 state.players[idx].stack -= add;
                 state.players[idx].committed_this_round = to;
+                let all_in = Self::mark_all_in_if_stack_empty(state, idx);
                 state.players[idx].has_acted_this_round = true;
                 if is_full && raise_amount > 0 {
                     state.last_full_raise_amount = raise_amount;
@@ -484,6 +697,13 @@ impl BettingEngineNL for EngineNL {
                 state.refresh_pots();
                 events.push(GameEvent::PotUpdated);
 
+                if all_in {
+                    if let Some(ev) = state.recompute_lock_if_all_in() {
+                        events.push(ev);
+                    }
+                }
+
+                events.push(Self::betting_event(state, idx));
                 Ok(Self::end_street_if_done(state, events))
             }
             PlayerBetAction::AllIn => {
@@ -558,6 +778,7 @@ impl BettingEngineNL for EngineNL {
                     events.push(ev);
                 }
 
+                events.push(Self::betting_event(state, idx));
                 Ok(Self::end_street_if_done(state, events))
             }
         }
@@ -8,12 +8,14 @@ pub enum ActionError {
     BadCallAmount,
     RaiseBelowMinimum,
     InsufficientChips,
+    ExceedsBetCap,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum StateError {
     InvalidTransition,
     InvariantViolation(&'static str),
+    InvalidBlindConfig(&'static str),
 }
 
 pub trait InvariantCheck {
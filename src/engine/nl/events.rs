@@ -1,4 +1,5 @@
 use super::types::{Chips, SeatId, Street};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +33,161 @@ pub enum NormalizedAction {
     },
 }
 
+impl CanonicalSerialize for NormalizedAction {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        match self {
+            NormalizedAction::Fold => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::Check => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::Call {
+                call_amount,
+                full_call,
+            } => {
+                2u8.serialize_with_mode(&mut writer, compress)?;
+                call_amount.serialize_with_mode(&mut writer, compress)?;
+                full_call.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::Bet { to } => {
+                3u8.serialize_with_mode(&mut writer, compress)?;
+                to.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::Raise {
+                to,
+                raise_amount,
+                full_raise,
+            } => {
+                4u8.serialize_with_mode(&mut writer, compress)?;
+                to.serialize_with_mode(&mut writer, compress)?;
+                raise_amount.serialize_with_mode(&mut writer, compress)?;
+                full_raise.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::AllInAsCall {
+                call_amount,
+                full_call,
+            } => {
+                5u8.serialize_with_mode(&mut writer, compress)?;
+                call_amount.serialize_with_mode(&mut writer, compress)?;
+                full_call.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::AllInAsBet { to } => {
+                6u8.serialize_with_mode(&mut writer, compress)?;
+                to.serialize_with_mode(&mut writer, compress)?;
+            }
+            NormalizedAction::AllInAsRaise {
+                to,
+                raise_amount,
+                full_raise,
+            } => {
+                7u8.serialize_with_mode(&mut writer, compress)?;
+                to.serialize_with_mode(&mut writer, compress)?;
+                raise_amount.serialize_with_mode(&mut writer, compress)?;
+                full_raise.serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        1 + match self {
+            NormalizedAction::Fold | NormalizedAction::Check => 0,
+            NormalizedAction::Call {
+                call_amount,
+                full_call,
+            }
+            | NormalizedAction::AllInAsCall {
+                call_amount,
+                full_call,
+            } => call_amount.serialized_size(compress) + full_call.serialized_size(compress),
+            NormalizedAction::Bet { to } | NormalizedAction::AllInAsBet { to } => {
+                to.serialized_size(compress)
+            }
+            NormalizedAction::Raise {
+                to,
+                raise_amount,
+                full_raise,
+            }
+            | NormalizedAction::AllInAsRaise {
+                to,
+                raise_amount,
+                full_raise,
+            } => {
+                to.serialized_size(compress)
+                    + raise_amount.serialized_size(compress)
+                    + full_raise.serialized_size(compress)
+            }
+        }
+    }
+}
+
+impl CanonicalDeserialize for NormalizedAction {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let discriminant = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        match discriminant {
+            0 => Ok(NormalizedAction::Fold),
+            1 => Ok(NormalizedAction::Check),
+            2 | 5 => {
+                let call_amount = Chips::deserialize_with_mode(&mut reader, compress, validate)?;
+                let full_call = bool::deserialize_with_mode(&mut reader, compress, validate)?;
+                Ok(if discriminant == 2 {
+                    NormalizedAction::Call {
+                        call_amount,
+                        full_call,
+                    }
+                } else {
+                    NormalizedAction::AllInAsCall {
+                        call_amount,
+                        full_call,
+                    }
+                })
+            }
+            3 => {
+                let to = Chips::deserialize_with_mode(&mut reader, compress, validate)?;
+                Ok(NormalizedAction::Bet { to })
+            }
+            6 => {
+                let to = Chips::deserialize_with_mode(&mut reader, compress, validate)?;
+                Ok(NormalizedAction::AllInAsBet { to })
+            }
+            4 | 7 => {
+                let to = Chips::deserialize_with_mode(&mut reader, compress, validate)?;
+                let raise_amount = Chips::deserialize_with_mode(&mut reader, compress, validate)?;
+                let full_raise = bool::deserialize_with_mode(&mut reader, compress, validate)?;
+                Ok(if discriminant == 4 {
+                    NormalizedAction::Raise {
+                        to,
+                        raise_amount,
+                        full_raise,
+                    }
+                } else {
+                    NormalizedAction::AllInAsRaise {
+                        to,
+                        raise_amount,
+                        full_raise,
+                    }
+                })
+            }
+            _ => Err(ark_serialize::SerializationError::InvalidData),
+        }
+    }
+}
+
+impl ark_serialize::Valid for NormalizedAction {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,6 +203,115 @@ mod tests {
 
         assert_round_trip_eq(&action);
     }
+
+    #[test]
+    fn betting_event_round_trips_canonically() {
+        let event = BettingEvent::ActionApplied {
+            seat: 3,
+            action: NormalizedAction::AllInAsRaise {
+                to: 120,
+                raise_amount: 40,
+                full_raise: false,
+            },
+            resulting_bet: 120,
+            pot_after: 310,
+            is_all_in: true,
+        };
+
+        let mut bytes = Vec::new();
+        event.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), event.compressed_size());
+        assert_eq!(
+            BettingEvent::deserialize_compressed(&*bytes).unwrap(),
+            event
+        );
+    }
+}
+
+/// Per-action outcome for animation and replay: the normalized action together with what it did
+/// to the table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BettingEvent {
+    ActionApplied {
+        seat: SeatId,
+        action: NormalizedAction,
+        /// The seat's total commitment this street after the action.
+        resulting_bet: Chips,
+        /// Sum of the main and side pots after the action.
+        pot_after: Chips,
+        is_all_in: bool,
+    },
+}
+
+impl CanonicalSerialize for BettingEvent {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        match self {
+            BettingEvent::ActionApplied {
+                seat,
+                action,
+                resulting_bet,
+                pot_after,
+                is_all_in,
+            } => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+                seat.serialize_with_mode(&mut writer, compress)?;
+                action.serialize_with_mode(&mut writer, compress)?;
+                resulting_bet.serialize_with_mode(&mut writer, compress)?;
+                pot_after.serialize_with_mode(&mut writer, compress)?;
+                is_all_in.serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        1 + match self {
+            BettingEvent::ActionApplied {
+                seat,
+                action,
+                resulting_bet,
+                pot_after,
+                is_all_in,
+            } => {
+                seat.serialized_size(compress)
+                    + action.serialized_size(compress)
+                    + resulting_bet.serialized_size(compress)
+                    + pot_after.serialized_size(compress)
+                    + is_all_in.serialized_size(compress)
+            }
+        }
+    }
+}
+
+impl CanonicalDeserialize for BettingEvent {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let discriminant = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        match discriminant {
+            0 => Ok(BettingEvent::ActionApplied {
+                seat: SeatId::deserialize_with_mode(&mut reader, compress, validate)?,
+                action: NormalizedAction::deserialize_with_mode(&mut reader, compress, validate)?,
+                resulting_bet: Chips::deserialize_with_mode(&mut reader, compress, validate)?,
+                pot_after: Chips::deserialize_with_mode(&mut reader, compress, validate)?,
+                is_all_in: bool::deserialize_with_mode(&mut reader, compress, validate)?,
+            }),
+            _ => Err(ark_serialize::SerializationError::InvalidData),
+        }
+    }
+}
+
+impl ark_serialize::Valid for BettingEvent {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -55,6 +320,7 @@ pub enum GameEvent {
         seat: SeatId,
         action: NormalizedAction,
     },
+    Betting(BettingEvent),
     PotUpdated,
     StreetEnded {
         street: Street,
@@ -65,3 +331,12 @@ pub enum GameEvent {
         pots: super::types::Pots,
     },
 }
+
+impl GameEvent {
+    pub fn as_betting(&self) -> Option<&BettingEvent> {
+        match self {
+            GameEvent::Betting(event) => Some(event),
+            _ => None,
+        }
+    }
+}
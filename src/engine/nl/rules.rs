@@ -20,6 +20,15 @@ pub trait NoLimitRules {
     fn is_full_raise(state: &BettingState, raise_amount: Chips) -> bool;
 }
 
+/// Largest bet-to a seat may choose: its whole stack, limited by the hand's bet cap if set.
+/// Going all-in past the cap remains possible through `PlayerBetAction::AllIn`.
+fn capped_bet_to(state: &BettingState, stack_cap: Chips) -> Chips {
+    state
+        .cfg
+        .max_bet_to()
+        .map_or(stack_cap, |cap| cap.min(stack_cap))
+}
+
 impl NoLimitRules for BettingState {
     fn price_to_call(state: &BettingState, seat: SeatId) -> Chips {
         let p = state
@@ -47,7 +56,7 @@ impl NoLimitRules for BettingState {
             return None;
         }
         let min = state.cfg.stakes.big_blind;
-        let max = p.committed_this_round + p.stack;
+        let max = capped_bet_to(state, p.committed_this_round + p.stack);
         if max < min {
             return None;
         }
@@ -75,7 +84,7 @@ impl NoLimitRules for BettingState {
         let min = state
             .current_bet_to_match
             .saturating_add(state.last_full_raise_amount);
-        let max = p.committed_this_round + p.stack;
+        let max = capped_bet_to(state, p.committed_this_round + p.stack);
         if max <= state.current_bet_to_match {
             return None;
         }
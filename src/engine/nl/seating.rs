@@ -1,4 +1,5 @@
-use super::types::{SeatId, Street};
+use super::state::BettingState;
+use super::types::{Chips, HandConfig, SeatId, Street};
 
 pub trait Seating {
     /// Next seat clockwise that is eligible to act (Active and not AllIn).
@@ -36,6 +37,54 @@ impl Seating for super::state::BettingState {
     }
 }
 
+/// Chips a player sitting down at `seat` mid-orbit must post, on top of any regular blind the
+/// seat already owes, to be dealt into this hand.
+///
+/// An entrant owes one big blind in total: the big blind seat's regular blind covers it, the
+/// small blind seat tops its blind up to a big blind, and every other seat posts a full one.
+/// An entrant on the button returns `None`: they must wait for the big blind to reach them
+/// rather than buy the button by posting.
+pub fn must_post_blind_to_enter(state: &BettingState, seat: SeatId) -> Option<Chips> {
+    let cfg = &state.cfg;
+    if seat == cfg.button {
+        return None;
+    }
+    let big_blind = cfg.stakes.big_blind;
+    Some(big_blind.saturating_sub(regular_blind(cfg, seat)))
+}
+
+/// Blind `seat` posts in the normal rotation: the small or big blind, or nothing.
+pub(crate) fn regular_blind(cfg: &HandConfig, seat: SeatId) -> Chips {
+    if seat == cfg.big_blind_seat {
+        cfg.stakes.big_blind
+    } else if seat == cfg.small_blind_seat {
+        cfg.stakes.small_blind
+    } else {
+        0
+    }
+}
+
+/// Small and big blind seats for a hand with the button on `button`.
+///
+/// The blinds go to the next occupied seats clockwise from the button, except heads-up, where
+/// the button posts the small blind and the other player the big blind. Returns `None` if fewer
+/// than two seats are occupied or the button seat is empty.
+pub fn blind_positions(occupied: &[SeatId], button: SeatId) -> Option<(SeatId, SeatId)> {
+    let mut seats = occupied.to_vec();
+    seats.sort_unstable();
+    seats.dedup();
+    let n = seats.len();
+    if n < 2 {
+        return None;
+    }
+    let at = seats.iter().position(|&seat| seat == button)?;
+    if n == 2 {
+        Some((button, seats[(at + 1) % n]))
+    } else {
+        Some((seats[(at + 1) % n], seats[(at + 2) % n]))
+    }
+}
+
 // Small helper on cfg for preflop convenience.
 trait SeatsExt {
     fn big_blind_seat(&self) -> SeatId;
@@ -2,9 +2,10 @@ use super::errors::{InvariantCheck, StateError};
 use super::events::GameEvent;
 use super::seating::Seating;
 use super::types::{ActionLog, Chips, HandConfig, PlayerState, PlayerStatus, Pots, SeatId, Street};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BettingState {
     // Street and turn order:
     pub street: Street,
@@ -33,6 +34,26 @@ pub struct BettingState {
     pub action_log: ActionLog,
 }
 
+/// Mutable part of a [`BettingState`], captured so a hypothetical action can be rolled back.
+///
+/// The hand config and button never change mid-hand and are not captured; the action log is
+/// append-only, so only its length is kept and restoring truncates it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BettingCheckpoint {
+    street: Street,
+    first_to_act: SeatId,
+    to_act: SeatId,
+    current_bet_to_match: Chips,
+    last_full_raise_amount: Chips,
+    last_aggressor: Option<SeatId>,
+    voluntary_bet_opened: bool,
+    players: Vec<PlayerState>,
+    pots: Pots,
+    pending_to_match: Vec<SeatId>,
+    betting_locked_all_in: bool,
+    action_log_len: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,6 +73,9 @@ mod tests {
             small_blind_seat: 1,
             big_blind_seat: 2,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
         let players = vec![
             PlayerState {
@@ -118,6 +142,39 @@ mod tests {
 }
 
 impl BettingState {
+    pub fn checkpoint(&self) -> BettingCheckpoint {
+        BettingCheckpoint {
+            street: self.street,
+            first_to_act: self.first_to_act,
+            to_act: self.to_act,
+            current_bet_to_match: self.current_bet_to_match,
+            last_full_raise_amount: self.last_full_raise_amount,
+            last_aggressor: self.last_aggressor,
+            voluntary_bet_opened: self.voluntary_bet_opened,
+            players: self.players.clone(),
+            pots: self.pots.clone(),
+            pending_to_match: self.pending_to_match.clone(),
+            betting_locked_all_in: self.betting_locked_all_in,
+            action_log_len: self.action_log.0.len(),
+        }
+    }
+
+    /// Roll back to `checkpoint`, which must have been taken from this state.
+    pub fn restore(&mut self, checkpoint: BettingCheckpoint) {
+        self.street = checkpoint.street;
+        self.first_to_act = checkpoint.first_to_act;
+        self.to_act = checkpoint.to_act;
+        self.current_bet_to_match = checkpoint.current_bet_to_match;
+        self.last_full_raise_amount = checkpoint.last_full_raise_amount;
+        self.last_aggressor = checkpoint.last_aggressor;
+        self.voluntary_bet_opened = checkpoint.voluntary_bet_opened;
+        self.players = checkpoint.players;
+        self.pots = checkpoint.pots;
+        self.pending_to_match = checkpoint.pending_to_match;
+        self.betting_locked_all_in = checkpoint.betting_locked_all_in;
+        self.action_log.0.truncate(checkpoint.action_log_len);
+    }
+
     pub fn active_non_allin_seats(&self) -> Vec<SeatId> {
         self.players
             .iter()
@@ -2,7 +2,8 @@ use crate::engine::nl::{BettingState, InvariantCheck};
 
 use super::actions::PlayerBetAction;
 use super::engine::{BettingEngineNL, EngineNL, Transition};
-use super::events::GameEvent;
+use super::errors::{ActionError, StateError};
+use super::events::{BettingEvent, GameEvent, NormalizedAction};
 use super::types::*;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
@@ -21,6 +22,9 @@ fn cfg_6max(sb: Chips, bb: Chips) -> HandConfig {
         small_blind_seat: 1,
         big_blind_seat: 2,
         check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
     }
 }
 
@@ -206,6 +210,173 @@ fn min_raise_equals_last_full_raise_amount_and_updates_on_full_raises() {
     assert_eq!(*raise_range.start(), 43);
 }
 
+#[test]
+fn unseated_big_blind_is_rejected() {
+    let cfg = cfg_6max(1, 3);
+    let players = [0, 1, 3, 4, 5]
+        .into_iter()
+        .map(|seat| player_active(seat, 100, 0))
+        .collect();
+    let err = EngineNL::try_new_after_deal(cfg, players, empty_pots()).unwrap_err();
+    assert_eq!(
+        err,
+        StateError::InvalidBlindConfig("big blind is not a seated player")
+    );
+}
+
+#[test]
+fn shared_blind_seat_is_rejected_three_handed() {
+    let mut cfg = cfg_6max(1, 3);
+    cfg.big_blind_seat = cfg.small_blind_seat;
+    let players = (0..3).map(|seat| player_active(seat, 100, 0)).collect();
+    let err = EngineNL::try_new_after_deal(cfg, players, empty_pots()).unwrap_err();
+    assert_eq!(
+        err,
+        StateError::InvalidBlindConfig("small and big blind share a seat")
+    );
+}
+
+#[test]
+fn heads_up_button_may_post_small_blind() {
+    let mut cfg = cfg_6max(1, 3);
+    cfg.small_blind_seat = 0;
+    cfg.big_blind_seat = 1;
+    let players = vec![player_active(0, 99, 1), player_active(1, 97, 3)];
+    let st = EngineNL::try_new_after_deal(cfg.clone(), players, empty_pots()).unwrap();
+    // The button/small blind acts first preflop heads-up.
+    assert_eq!(st.to_act, 0);
+
+    // The same layout with a third player dealt in is not heads-up.
+    let players = (0..3).map(|seat| player_active(seat, 100, 0)).collect();
+    let err = EngineNL::try_new_after_deal(cfg, players, empty_pots()).unwrap_err();
+    assert_eq!(
+        err,
+        StateError::InvalidBlindConfig("button posts the small blind outside heads-up")
+    );
+}
+
+#[test]
+fn blind_positions_follow_occupied_seats() {
+    use super::seating::blind_positions;
+
+    assert_eq!(blind_positions(&[0, 1, 2, 3], 0), Some((1, 2)));
+    // Empty seats are skipped and the order wraps past the last seat.
+    assert_eq!(blind_positions(&[1, 4, 7], 4), Some((7, 1)));
+    // Heads-up the button posts the small blind.
+    assert_eq!(blind_positions(&[3, 5], 5), Some((5, 3)));
+    assert_eq!(blind_positions(&[3], 3), None);
+    assert_eq!(blind_positions(&[0, 1, 2], 4), None);
+}
+
+#[test]
+fn partial_ante_goes_all_in_and_layers_pots() {
+    // Ante 10, blinds 5/10. The button can only cover half the ante.
+    let cfg = HandConfig {
+        stakes: TableStakes {
+            small_blind: 5,
+            big_blind: 10,
+            ante: 10,
+        },
+        button: 0,
+        small_blind_seat: 1,
+        big_blind_seat: 2,
+        check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+    };
+    let players = vec![
+        player_active(0, 0, 5),   // BTN: half the ante, nothing behind
+        player_active(1, 85, 15), // SB: ante + small blind
+        player_active(2, 80, 20), // BB: ante + big blind
+    ];
+    let st = EngineNL::new_after_deal(cfg, players, empty_pots());
+
+    assert_eq!(st.players[0].status, PlayerStatus::AllIn);
+    assert_eq!(st.players[1].status, PlayerStatus::Active);
+    assert_eq!(st.players[2].status, PlayerStatus::Active);
+    assert_eq!(st.current_bet_to_match, 20);
+    // The all-in button is skipped; the small blind acts first.
+    assert_eq!(st.to_act, 1);
+
+    // The short ante caps the main pot at 5 from each player.
+    assert_eq!(
+        st.pots.main,
+        Pot {
+            amount: 15,
+            eligible: vec![0, 1, 2],
+        }
+    );
+    assert_eq!(
+        st.pots.sides,
+        vec![
+            Pot {
+                amount: 20,
+                eligible: vec![1, 2],
+            },
+            Pot {
+                amount: 5,
+                eligible: vec![2],
+            },
+        ]
+    );
+    assert_eq!(st.pots.total(), 40);
+}
+
+#[test]
+fn bet_to_emits_betting_event_with_pot_after() {
+    let mut st = setup_preflop_6max(300, 1, 3);
+    let t = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 7 }).unwrap();
+    let betting: Vec<_> = t
+        .events()
+        .iter()
+        .filter_map(GameEvent::as_betting)
+        .collect();
+    // Blinds (1 + 3) plus the open to 7.
+    assert_eq!(
+        betting,
+        vec![&BettingEvent::ActionApplied {
+            seat: 3,
+            action: NormalizedAction::Bet { to: 7 },
+            resulting_bet: 7,
+            pot_after: 11,
+            is_all_in: false,
+        }]
+    );
+    assert_eq!(st.pots.total(), 11);
+}
+
+#[test]
+fn bet_cap_limits_bet_to_and_legal_range() {
+    let mut st = setup_preflop_6max(300, 1, 3);
+    st.cfg.max_bet_bb = Some(10);
+
+    let legals = EngineNL::legal_actions(&st, 3);
+    assert_eq!(legals.bet_to_range, Some(3..=30));
+
+    let err = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 31 }).unwrap_err();
+    assert_eq!(err, ActionError::ExceedsBetCap);
+    let _ = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 30 }).unwrap();
+
+    let err = EngineNL::apply_action(&mut st, 4, PlayerBetAction::RaiseTo { to: 60 }).unwrap_err();
+    assert_eq!(err, ActionError::ExceedsBetCap);
+}
+
+#[test]
+fn all_in_over_bet_cap_is_allowed() {
+    let mut st = setup_preflop_6max(300, 1, 3);
+    st.cfg.max_bet_bb = Some(10);
+    let _ = EngineNL::apply_action(&mut st, 3, PlayerBetAction::AllIn).unwrap();
+    assert_eq!(st.players[3].status, PlayerStatus::AllIn);
+    assert_eq!(st.current_bet_to_match, 300);
+
+    // Betting the whole stack through BetTo is the same all-in.
+    let mut st = setup_preflop_6max(300, 1, 3);
+    st.cfg.max_bet_bb = Some(10);
+    let _ = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 300 }).unwrap();
+    assert_eq!(st.players[3].status, PlayerStatus::AllIn);
+}
+
 #[test]
 fn short_all_in_raise_does_not_update_lfr_or_reopen_action() {
     let mut st = setup_preflop_6max(20, 1, 3);
@@ -280,6 +451,37 @@ fn short_call_sets_player_all_in_and_keeps_action_live_for_others() {
     }
 }
 
+#[test]
+fn call_that_exactly_empties_stack_is_all_in() {
+    let mut st = setup_preflop_6max(200, 1, 3);
+    let _ = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 100 }).unwrap();
+    st.players[4].stack = 100;
+    let tr = EngineNL::apply_action(&mut st, 4, PlayerBetAction::Call).unwrap();
+
+    assert_eq!(st.players[4].status, PlayerStatus::AllIn);
+    assert_eq!(st.players[4].stack, 0);
+    assert_eq!(st.players[4].committed_this_round, 100);
+    assert!(!st.pending_to_match.contains(&4));
+    match tr {
+        Transition::Continued { next_to_act, .. } => assert_ne!(next_to_act, 4),
+        other => panic!("expected action to continue, got {other:?}"),
+    }
+}
+
+#[test]
+fn bet_of_entire_stack_is_all_in() {
+    let mut st = setup_preflop_6max(100, 1, 3);
+    let tr = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 100 }).unwrap();
+
+    assert_eq!(st.players[3].status, PlayerStatus::AllIn);
+    assert_eq!(st.players[3].stack, 0);
+    assert!(!st.betting_locked_all_in);
+    match tr {
+        Transition::Continued { next_to_act, .. } => assert_ne!(next_to_act, 3),
+        other => panic!("expected action to continue, got {other:?}"),
+    }
+}
+
 #[test]
 fn all_players_all_in_locks_betting_and_emits_event() {
     let mut st = setup_preflop_6max(50, 1, 3);
@@ -590,3 +792,126 @@ fn e2e_random_full_hand_to_river() {
     // Final sanity checks
     super::state::BettingState::validate_invariants(&st).unwrap();
 }
+
+#[test]
+fn restore_rolls_back_a_previewed_raise() {
+    let mut st = setup_preflop_6max(300, 1, 3);
+    let _ = EngineNL::apply_action(&mut st, 3, PlayerBetAction::BetTo { to: 9 }).unwrap();
+    let before = st.checkpoint();
+    let before_json = serde_json::to_value(&st).unwrap();
+
+    let _ = EngineNL::apply_action(&mut st, 4, PlayerBetAction::RaiseTo { to: 30 }).unwrap();
+    assert_eq!(st.current_bet_to_match, 30);
+
+    st.restore(before.clone());
+    assert_eq!(st.current_bet_to_match, 9);
+    assert_eq!(st.checkpoint(), before);
+    assert_eq!(serde_json::to_value(&st).unwrap(), before_json);
+}
+
+fn setup_preflop_with_entrant(entrant: SeatId) -> BettingState {
+    // BTN=0, SB=2, BB=3: seat 1 sat empty when the blinds moved past it.
+    let cfg = HandConfig {
+        stakes: stakes(1, 2),
+        button: 0,
+        small_blind_seat: 2,
+        big_blind_seat: 3,
+        check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+    };
+    let players = (0u8..6)
+        .map(|seat| match seat {
+            2 => player_active(seat, 99, 1),
+            3 => player_active(seat, 98, 2),
+            _ => player_active(seat, 100, 0),
+        })
+        .collect();
+    let mut pots = empty_pots();
+    pots.main.amount = 3;
+    pots.main.eligible = (0u8..6).collect();
+    let mut state = EngineNL::new_after_deal(cfg, players, pots);
+    assert!(state.pending_to_match.contains(&entrant) || entrant == state.cfg.button);
+    EngineNL::seat_entrant(&mut state, entrant).unwrap();
+    state
+}
+
+#[test]
+fn entrant_between_button_and_small_blind_posts_big_blind() {
+    let state = setup_preflop_with_entrant(1);
+    assert_eq!(
+        crate::engine::nl::must_post_blind_to_enter(&state, 1),
+        Some(2)
+    );
+
+    let entrant = state.players.iter().find(|p| p.seat == 1).unwrap();
+    assert_eq!(entrant.committed_this_round, 2);
+    assert_eq!(entrant.stack, 98);
+    assert_eq!(entrant.status, PlayerStatus::Active);
+    assert_eq!(state.pots.main.amount, 5);
+    assert!(!state.pending_to_match.contains(&1));
+    assert!(state.validate_invariants().is_ok());
+}
+
+#[test]
+fn entrant_on_button_waits_for_big_blind() {
+    let state = setup_preflop_with_entrant(0);
+    assert_eq!(crate::engine::nl::must_post_blind_to_enter(&state, 0), None);
+
+    let entrant = state.players.iter().find(|p| p.seat == 0).unwrap();
+    assert_eq!(entrant.status, PlayerStatus::SittingOut);
+    assert_eq!(entrant.committed_this_round, 0);
+    assert_eq!(entrant.stack, 100);
+    assert_eq!(state.pots.main.amount, 3);
+    assert!(!state.pots.main.eligible.contains(&0));
+    assert!(!state.pending_to_match.contains(&0));
+}
+
+#[test]
+fn new_after_deal_settles_entry_blinds_posted_before_the_deal() {
+    // BTN=0, SB=2, BB=3. Seat 1 posted its entry blind while sitting down, and so did seat 0,
+    // which has to wait for the big blind instead.
+    let cfg = HandConfig {
+        stakes: stakes(1, 2),
+        button: 0,
+        small_blind_seat: 2,
+        big_blind_seat: 3,
+        check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+    };
+    let players = (0u8..6)
+        .map(|seat| match seat {
+            0 | 1 => player_active(seat, 98, 2),
+            2 => player_active(seat, 99, 1),
+            3 => player_active(seat, 98, 2),
+            _ => player_active(seat, 100, 0),
+        })
+        .collect();
+    let state = EngineNL::new_after_deal(cfg, players, empty_pots());
+
+    assert_eq!(
+        crate::engine::nl::must_post_blind_to_enter(&state, 2),
+        Some(1)
+    );
+    assert_eq!(
+        crate::engine::nl::must_post_blind_to_enter(&state, 3),
+        Some(0)
+    );
+
+    let entrant = state.players.iter().find(|p| p.seat == 1).unwrap();
+    assert_eq!(entrant.committed_this_round, 2);
+    assert_eq!(entrant.status, PlayerStatus::Active);
+    assert!(!state.pending_to_match.contains(&1));
+
+    let waiting = state.players.iter().find(|p| p.seat == 0).unwrap();
+    assert_eq!(waiting.status, PlayerStatus::SittingOut);
+    assert_eq!(waiting.committed_this_round, 0);
+    assert_eq!(waiting.stack, 100);
+
+    assert_eq!(state.current_bet_to_match, 2);
+    assert_eq!(state.pots.total(), 5);
+    assert_eq!(state.pending_to_match, vec![4, 5, 2]);
+}
@@ -16,6 +16,60 @@ pub enum Street {
     River,
 }
 
+impl Street {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 1,
+            Street::Turn => 2,
+            Street::River => 3,
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Street::Preflop),
+            1 => Some(Street::Flop),
+            2 => Some(Street::Turn),
+            3 => Some(Street::River),
+            _ => None,
+        }
+    }
+}
+
+impl CanonicalSerialize for Street {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        _compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        writer.write_all(&[self.as_byte()])?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: ark_serialize::Compress) -> usize {
+        1
+    }
+}
+
+impl ark_serialize::Valid for Street {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for Street {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        _compress: ark_serialize::Compress,
+        _validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        Street::from_byte(byte[0]).ok_or(ark_serialize::SerializationError::InvalidData)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PlayerStatus {
@@ -61,7 +115,9 @@ impl CanonicalDeserialize for PlayerStatus {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct PlayerState {
     pub seat: SeatId,
     pub player_id: Option<PlayerId>,
@@ -75,18 +131,31 @@ pub struct PlayerState {
     pub has_acted_this_round: bool, // for flow (check/raise cycles)
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct Pot {
     pub amount: Chips,
     pub eligible: Vec<SeatId>, // seats that can win this pot
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct Pots {
     pub main: Pot,
     pub sides: Vec<Pot>,
 }
 
+impl Pots {
+    /// Chips in the main pot and every side pot.
+    pub fn total(&self) -> Chips {
+        self.sides.iter().fold(self.main.amount, |total, pot| {
+            total.saturating_add(pot.amount)
+        })
+    }
+}
+
 #[derive(
     Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
 )]
@@ -96,6 +165,77 @@ pub struct TableStakes {
     pub ante: Chips, // 0 if none
 }
 
+/// Hole cards dealt to each player in hold'em.
+pub const HOLDEM_HOLE_CARDS: u8 = 2;
+/// Hole cards dealt to each player in Omaha.
+pub const OMAHA_HOLE_CARDS: u8 = 4;
+
+fn default_hole_cards() -> u8 {
+    HOLDEM_HOLE_CARDS
+}
+
+/// Order in which hole cards come off the deck.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DealingOrder {
+    /// One card to each seat clockwise from the left of the button, then around again, as
+    /// dealt at a real table.
+    #[default]
+    OneAtATime,
+    /// Every hole card of one seat before moving on to the next seat.
+    PerPlayer,
+}
+
+impl DealingOrder {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            DealingOrder::OneAtATime => 0,
+            DealingOrder::PerPlayer => 1,
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(DealingOrder::OneAtATime),
+            1 => Some(DealingOrder::PerPlayer),
+            _ => None,
+        }
+    }
+}
+
+impl CanonicalSerialize for DealingOrder {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        _compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        writer.write_all(&[self.as_byte()])?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: ark_serialize::Compress) -> usize {
+        1
+    }
+}
+
+impl ark_serialize::Valid for DealingOrder {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for DealingOrder {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        _compress: ark_serialize::Compress,
+        _validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        DealingOrder::from_byte(byte[0]).ok_or(ark_serialize::SerializationError::InvalidData)
+    }
+}
+
 /// Fixed for the hand (No-Limit only).
 #[derive(
     Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
@@ -106,6 +246,23 @@ pub struct HandConfig {
     pub small_blind_seat: SeatId,
     pub big_blind_seat: SeatId,
     pub check_raise_allowed: bool, // default true in standard NLH
+    /// Hole cards per player: [`HOLDEM_HOLE_CARDS`] or [`OMAHA_HOLE_CARDS`].
+    #[serde(default = "default_hole_cards")]
+    pub hole_cards: u8,
+    /// Responsible-gaming cap on `BetTo`/`RaiseTo`, in big blinds. An all-in may still exceed it.
+    #[serde(default)]
+    pub max_bet_bb: Option<u32>,
+    /// How hole cards are laid out in the card plan.
+    #[serde(default)]
+    pub dealing_order: DealingOrder,
+}
+
+impl HandConfig {
+    /// Largest bet-to amount allowed by [`HandConfig::max_bet_bb`], if capped.
+    pub fn max_bet_to(&self) -> Option<Chips> {
+        self.max_bet_bb
+            .map(|multiple| self.stakes.big_blind.saturating_mul(Chips::from(multiple)))
+    }
 }
 
 impl DomainSeparated for HandConfig {
@@ -135,7 +292,9 @@ impl PlayerStatus {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct ActionLogEntry {
     pub street: Street,
     pub seat: SeatId,
@@ -144,7 +303,17 @@ pub struct ActionLogEntry {
     pub current_bet_to_match_after: Chips,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
 #[serde(transparent)]
 pub struct ActionLog(pub Vec<ActionLogEntry>);
 
@@ -174,6 +343,9 @@ mod tests {
             small_blind_seat: 1,
             big_blind_seat: 2,
             check_raise_allowed: true,
+            hole_cards: HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: DealingOrder::OneAtATime,
         };
         assert_round_trip_eq(&cfg);
 
@@ -13,10 +13,48 @@ use ark_r1cs_std::{
     GR1CSVar,
 };
 use ark_relations::gr1cs::{ConstraintSystemRef, SynthesisError};
+use thiserror::Error;
 
 /// Type alias for the constraint field (base prime field)
 type ConstraintF<C> = <<C as CurveGroup>::BaseField as Field>::BasePrimeField;
 
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConversionError {
+    #[error("value does not fit in the target field modulus")]
+    OutOfRange,
+}
+
+/// Convert between prime fields by reducing the canonical integer representative of `x` modulo
+/// the target field order.
+///
+/// This is the native counterpart of the byte-wise conversion the gadgets perform. When the
+/// source modulus is larger than the target modulus distinct inputs can map to the same output;
+/// use [`try_convert`] where that must not happen silently.
+pub fn convert_mod<FromF: PrimeField, ToF: PrimeField>(x: FromF) -> ToF {
+    ToF::from_le_bytes_mod_order(&x.into_bigint().to_bytes_le())
+}
+
+/// Convert between prime fields, failing if `x` is not already a canonical element of the
+/// target field.
+pub fn try_convert<FromF: PrimeField, ToF: PrimeField>(x: FromF) -> Result<ToF, ConversionError> {
+    let converted: ToF = convert_mod(x);
+    let mut source = x.into_bigint().to_bytes_le();
+    let mut target = converted.into_bigint().to_bytes_le();
+    trim_trailing_zeros(&mut source);
+    trim_trailing_zeros(&mut target);
+    if source == target {
+        Ok(converted)
+    } else {
+        Err(ConversionError::OutOfRange)
+    }
+}
+
+fn trim_trailing_zeros(bytes: &mut Vec<u8>) {
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+}
+
 /// Convert a base field element to a scalar field element with its bit representation
 ///
 /// This function takes a base field element (typically from a Poseidon sponge squeeze),
@@ -161,6 +199,27 @@ mod tests {
 
     type TestCurve = ark_bn254::G1Projective;
 
+    #[test]
+    fn try_convert_accepts_values_below_the_target_modulus() {
+        let value = Fq::from(123_456_789u64);
+        assert_eq!(try_convert::<Fq, Fr>(value), Ok(Fr::from(123_456_789u64)));
+    }
+
+    #[test]
+    fn try_convert_rejects_values_above_the_target_modulus() {
+        // BN254's base field is larger than its scalar field, so q - 1 does not fit in Fr.
+        let value = -Fq::from(1u64);
+        assert_eq!(
+            try_convert::<Fq, Fr>(value),
+            Err(ConversionError::OutOfRange)
+        );
+        let reduced: Fr = convert_mod(value);
+        assert_ne!(
+            reduced.into_bigint().to_bytes_le(),
+            value.into_bigint().to_bytes_le()
+        );
+    }
+
     #[test]
     fn test_base_to_scalar_conversion() {
         let mut rng = test_rng();
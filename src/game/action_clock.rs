@@ -0,0 +1,183 @@
+//! Per-turn action deadlines.
+//!
+//! [`ActionClock`] starts a countdown when a seat is put on the clock and, once it runs out,
+//! names the action the table takes on that seat's behalf: a check when checking is free,
+//! otherwise a fold. Time comes from a [`Clock`], so tests drive deadlines with a
+//! [`MockClock`](crate::clock::MockClock).
+//!
+//! [`ActionDeadlines`] keeps one such clock per hand in play: the coordinator restarts it as
+//! each turn begins, and the ledger verifier consults it to accept a timed-out seat's default
+//! action without that player's signature.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::clock::Clock;
+use crate::engine::nl::types::Street;
+use crate::engine::nl::{legal_actions_for, BettingState, PlayerBetAction, SeatId};
+use crate::ledger::types::HandId;
+
+/// Time a seat gets to act before the table acts for it.
+pub const DEFAULT_ACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub struct ActionClock {
+    clock: Arc<dyn Clock>,
+    timeout: Duration,
+    deadline: Option<(SeatId, Instant)>,
+}
+
+impl ActionClock {
+    pub fn new(clock: Arc<dyn Clock>, timeout: Duration) -> Self {
+        Self {
+            clock,
+            timeout,
+            deadline: None,
+        }
+    }
+
+    /// Give `seat` a full timeout from now, replacing any running countdown.
+    pub fn start(&mut self, seat: SeatId) {
+        self.deadline = Some((seat, self.clock.now() + self.timeout));
+    }
+
+    pub fn stop(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Time left for the seat on the clock, if any.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|(_, deadline)| deadline.saturating_duration_since(self.clock.now()))
+    }
+
+    /// The seat whose deadline has passed, if any.
+    pub fn expired(&self) -> Option<SeatId> {
+        match self.deadline {
+            Some((seat, deadline)) if self.clock.now() >= deadline => Some(seat),
+            _ => None,
+        }
+    }
+
+    /// The action to apply for a seat that ran out of time, provided it is still that seat's
+    /// turn in `state`.
+    pub fn timeout_action(&self, state: &BettingState) -> Option<(SeatId, PlayerBetAction)> {
+        let seat = self.expired().filter(|seat| *seat == state.to_act)?;
+        let legal = legal_actions_for(state, seat);
+        if legal.may_check {
+            Some((seat, PlayerBetAction::Check))
+        } else if legal.may_fold {
+            Some((seat, PlayerBetAction::Fold))
+        } else {
+            None
+        }
+    }
+}
+
+/// A turn: the seat to act on a street after a number of logged actions.
+type Turn = (Street, SeatId, usize);
+
+fn turn_of(state: &BettingState) -> Turn {
+    (state.street, state.to_act, state.action_log.0.len())
+}
+
+/// The action clocks of every hand in play. Clones share the same clocks.
+#[derive(Clone, Debug)]
+pub struct ActionDeadlines {
+    clock: Arc<dyn Clock>,
+    timeout: Duration,
+    hands: Arc<Mutex<HashMap<HandId, (Turn, ActionClock)>>>,
+}
+
+impl ActionDeadlines {
+    pub fn new(clock: Arc<dyn Clock>, timeout: Duration) -> Self {
+        Self {
+            clock,
+            timeout,
+            hands: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Put the seat to act in `state` on the clock, unless this turn is already being timed.
+    pub fn observe(&self, hand_id: HandId, state: &BettingState) {
+        let turn = turn_of(state);
+        let mut hands = self.hands.lock();
+        if hands.get(&hand_id).is_some_and(|(timed, _)| *timed == turn) {
+            return;
+        }
+        let mut action_clock = ActionClock::new(Arc::clone(&self.clock), self.timeout);
+        action_clock.start(state.to_act);
+        hands.insert(hand_id, (turn, action_clock));
+    }
+
+    /// Stop timing `hand_id`, e.g. once betting is over.
+    pub fn release(&self, hand_id: HandId) {
+        self.hands.lock().remove(&hand_id);
+    }
+
+    /// Time left on the turn in `state`, if it is the one being timed.
+    pub fn remaining(&self, hand_id: HandId, state: &BettingState) -> Option<Duration> {
+        let hands = self.hands.lock();
+        let (turn, action_clock) = hands.get(&hand_id)?;
+        if *turn != turn_of(state) {
+            return None;
+        }
+        action_clock.remaining()
+    }
+
+    /// [`ActionClock::timeout_action`] for the turn in `state`, if it is the one being timed.
+    pub fn timeout_action(
+        &self,
+        hand_id: HandId,
+        state: &BettingState,
+    ) -> Option<(SeatId, PlayerBetAction)> {
+        let hands = self.hands.lock();
+        let (turn, action_clock) = hands.get(&hand_id)?;
+        if *turn != turn_of(state) {
+            return None;
+        }
+        action_clock.timeout_action(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::engine::nl::{BettingEngineNL, EngineNL, PlayerStatus};
+    use crate::ledger::test_support::{fixture_preflop_snapshot, FixtureContext};
+    use ark_bn254::G1Projective as Curve;
+
+    #[test]
+    fn passing_the_deadline_folds_the_player_facing_a_bet() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut state = fixture_preflop_snapshot(&ctx).betting.state;
+        let seat = state.to_act;
+        assert!(!legal_actions_for(&state, seat).may_check);
+
+        let clock = MockClock::new();
+        let mut action_clock = ActionClock::new(Arc::new(clock.clone()), Duration::from_secs(30));
+        action_clock.start(seat);
+
+        clock.advance(Duration::from_secs(29));
+        assert_eq!(action_clock.timeout_action(&state), None);
+        assert_eq!(action_clock.remaining(), Some(Duration::from_secs(1)));
+
+        clock.advance(Duration::from_secs(1));
+        let (timed_out, action) = action_clock
+            .timeout_action(&state)
+            .expect("deadline has passed");
+        assert_eq!((timed_out, &action), (seat, &PlayerBetAction::Fold));
+
+        EngineNL::apply_action(&mut state, timed_out, action).expect("fold is legal");
+        let player = state
+            .players
+            .iter()
+            .find(|player| player.seat == seat)
+            .expect("seat is dealt in");
+        assert_eq!(player.status, PlayerStatus::Folded);
+    }
+}
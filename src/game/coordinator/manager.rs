@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Context, Result};
 use ark_crypto_primitives::signature::{schnorr::SecretKey as SchnorrSecretKey, SignatureScheme};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
-use ark_ff::{PrimeField, UniformRand};
+use ark_ff::{PrimeField, UniformRand, Zero};
 use ark_serialize::CanonicalSerialize;
 use dashmap::DashMap;
+use parking_lot::Mutex;
 use rand::{rngs::StdRng, RngCore, SeedableRng};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::{
     signal,
-    sync::{broadcast, mpsc},
+    sync::{broadcast, mpsc, OwnedSemaphorePermit, Semaphore},
     task::JoinHandle,
 };
 use tokio_util::sync::CancellationToken;
@@ -20,23 +23,40 @@ use tracing::{info, warn};
 
 use crate::{
     curve_absorb::CurveAbsorb,
+    engine::nl::{engine::EngineNL, types::SeatId},
+    game::action_clock::ActionDeadlines,
     game::coordinator::realtime::{SupabaseRealtimeClient, SupabaseRealtimeClientConfig},
     ledger::{
+        actor::AnyActor,
         messages::{
-            AnyMessageEnvelope, EnvelopedMessage, FinalizedAnyMessageEnvelope, GameShuffleMessage,
+            AnyGameMessage, AnyMessageEnvelope, EnvelopedMessage, FinalizedAnyMessageEnvelope,
+            GamePlayerMessage, GameShuffleMessage,
         },
-        snapshot::{AnyTableSnapshot, Shared},
+        snapshot::{initial_player_states, AnyTableSnapshot, Shared},
+        state::nonce_key,
         store::{EventStore, SnapshotStore},
         types::{GameId, HandId, ShufflerId},
         verifier::Verifier,
         worker::{LedgerWorker, StagingLedgerUpdate, WorkerError},
         CommenceGameOutcome, LedgerOperator, LedgerState,
     },
-    shuffler::{HandSubscription, ShufflerRunConfig, ShufflerScheme, ShufflerService},
+    shuffler::{
+        HandSubscription, ProvingQueue, ShufflerRunConfig, ShufflerScheme, ShufflerService,
+    },
     shuffling::make_global_public_keys,
+    signing::WithSignature,
     tokio_tools::spawn_named_task,
+    vrf::NonceSource,
 };
 
+/// Whether the Supabase realtime client task is still running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeStatus {
+    Running,
+    Stopped,
+}
+
 /// Shuffler secret configuration without database ID (for initial parsing)
 #[derive(Clone)]
 pub struct ShufflerSecret<C: CurveGroup> {
@@ -50,6 +70,13 @@ pub struct ShufflerSecretConfig<C: CurveGroup> {
     pub secret: C::ScalarField,
 }
 
+impl<C: CurveGroup> ShufflerSecretConfig<C> {
+    /// Public key derived from the configured secret.
+    pub fn public_key(&self) -> C {
+        C::generator() * self.secret
+    }
+}
+
 impl<C: CurveGroup> From<(ShufflerId, ShufflerSecret<C>)> for ShufflerSecretConfig<C> {
     fn from((id, secret): (ShufflerId, ShufflerSecret<C>)) -> Self {
         Self {
@@ -79,22 +106,258 @@ where
     pub supabase: SupabaseRealtimeClientConfig,
     pub shufflers: Vec<ShufflerSecretConfig<C>>,
     pub submit_channel_capacity: usize,
+    /// Hands that may be attached at once; further attaches fail with [`CoordinatorBusy`]
+    /// until one is released.
+    pub max_concurrent_hands: usize,
     pub rng_seed: Option<[u8; 32]>,
+    /// Schnorr parameters the shufflers sign with, normally
+    /// [`shuffler_signing_params`](crate::shuffler::shuffler_signing_params). The verifier must
+    /// check signatures under the same parameters, e.g. with a
+    /// [`SchnorrSignatureValidator`](crate::ledger::SchnorrSignatureValidator) built from them.
+    pub signing_params: <ShufflerScheme<C> as SignatureScheme>::Parameters,
+    /// Per-turn deadlines to enforce on attached hands. Pass the same deadlines to the
+    /// verifier via [`LedgerVerifier::with_action_deadlines`](crate::ledger::LedgerVerifier)
+    /// so it accepts the default actions submitted for timed-out seats. `None` leaves turns
+    /// untimed.
+    pub action_deadlines: Option<ActionDeadlines>,
+    /// Where shufflers take their per-hand VRF nonces, e.g. a
+    /// [`DrandBeacon`](crate::vrf::DrandBeacon). `None` draws them locally.
+    pub nonce_source: Option<Arc<dyn NonceSource<C::BaseField>>>,
 }
 
-impl<C> GameCoordinatorConfig<C>
-where
-    C: CurveGroup,
-    C::ScalarField: PrimeField + UniformRand + CanonicalSerialize,
-{
-    pub fn require_shufflers(&self) -> Result<()> {
-        if self.shufflers.is_empty() {
-            return Err(anyhow!(
-                "GameCoordinatorConfig requires at least one shuffler secret"
-            ));
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("GameCoordinatorConfig requires at least one shuffler secret")]
+    NoShufflers,
+    #[error("shuffler {0} has a zero secret")]
+    ZeroShufflerSecret(ShufflerId),
+    #[error("shuffler {0} is configured more than once")]
+    DuplicateShuffler(ShufflerId),
+    #[error("{0} must be greater than zero")]
+    ZeroCapacity(&'static str),
+    #[error("invalid supabase realtime url {url}: {reason}")]
+    InvalidSupabaseUrl { url: String, reason: &'static str },
+}
+
+/// Default for [`GameCoordinatorConfig::max_concurrent_hands`].
+pub const DEFAULT_MAX_CONCURRENT_HANDS: usize = 64;
+
+/// Returned by [`GameCoordinator::attach_hand`] when every hand slot is taken.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("coordinator is already running its limit of {limit} concurrent hands")]
+pub struct CoordinatorBusy {
+    pub limit: usize,
+}
+
+/// Bounds how many hands a coordinator runs at once. A hand holds its [`HandSlot`] from attach
+/// until it completes or is released.
+#[derive(Debug)]
+struct HandSlots {
+    limit: usize,
+    permits: Arc<Semaphore>,
+}
+
+#[derive(Debug)]
+struct HandSlot {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl HandSlots {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            permits: Arc::new(Semaphore::new(limit)),
+        }
+    }
+
+    fn try_reserve(&self) -> Result<HandSlot, CoordinatorBusy> {
+        Arc::clone(&self.permits)
+            .try_acquire_owned()
+            .map(|permit| HandSlot { _permit: permit })
+            .map_err(|_| CoordinatorBusy { limit: self.limit })
+    }
+}
+
+struct ActiveHand<C: CurveGroup> {
+    subscriptions: Vec<HandSubscription<C>>,
+    slot: HandSlot,
+    completion_watch: Option<JoinHandle<()>>,
+    deadline_watch: Option<JoinHandle<()>>,
+}
+
+impl<C: CurveGroup> ActiveHand<C> {
+    fn cancel(self) -> HandSlot {
+        for sub in self.subscriptions {
+            sub.cancel();
+        }
+        if let Some(watch) = self.completion_watch {
+            watch.abort();
+        }
+        if let Some(watch) = self.deadline_watch {
+            watch.abort();
+        }
+        self.slot
+    }
+}
+
+/// Drop the hand's [`ActiveHand`] entry, and with it its [`HandSlot`], once the ledger
+/// publishes the hand's `Complete` snapshot.
+async fn release_on_completion<C: CurveGroup>(
+    active_hands: Arc<DashMap<(GameId, HandId), ActiveHand<C>>>,
+    mut snapshots: broadcast::Receiver<Shared<AnyTableSnapshot<C>>>,
+    state: Arc<LedgerState<C>>,
+    game_id: GameId,
+    hand_id: HandId,
+) {
+    let is_complete = |snapshot: &AnyTableSnapshot<C>| {
+        matches!(
+            snapshot,
+            AnyTableSnapshot::Complete(table)
+                if table.game_id == game_id && table.hand_id == Some(hand_id)
+        )
+    };
+    loop {
+        match snapshots.recv().await {
+            Ok(snapshot) if is_complete(snapshot.as_ref()) => break,
+            Ok(_) => {}
+            // The skipped updates may have included the completion; check the tip instead.
+            Err(broadcast::error::RecvError::Lagged(_)) => {
+                if state
+                    .tip_snapshot(hand_id)
+                    .is_some_and(|(_, tip)| is_complete(&tip))
+                {
+                    break;
+                }
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+    if let Some((_, hand)) = active_hands.remove(&(game_id, hand_id)) {
+        for sub in hand.subscriptions {
+            sub.cancel();
+        }
+        info!(
+            target = LOG_TARGET,
+            game_id, hand_id, "hand complete; released its slot"
+        );
+    }
+}
+
+/// Act for the seat to act in `hand_id` whenever its turn runs out on `deadlines`: the
+/// [`ActionClock`](crate::game::action_clock::ActionClock) default check or fold is submitted
+/// through the operator unsigned, which the verifier accepts only for a timed-out seat.
+async fn enforce_action_deadlines<C: CurveGroup>(
+    deadlines: ActionDeadlines,
+    operator: Arc<LedgerOperator<C>>,
+    state: Arc<LedgerState<C>>,
+    mut snapshots: broadcast::Receiver<Shared<AnyTableSnapshot<C>>>,
+    hand_id: HandId,
+) {
+    loop {
+        let tip = state.tip_snapshot(hand_id).map(|(_, tip)| tip);
+        if matches!(tip, Some(AnyTableSnapshot::Complete(_))) {
+            break;
+        }
+        let remaining = tip
+            .as_ref()
+            .and_then(AnyTableSnapshot::betting_state)
+            .and_then(|betting| {
+                deadlines.observe(hand_id, betting);
+                deadlines.remaining(hand_id, betting)
+            });
+        let expired = match remaining {
+            Some(remaining) => tokio::select! {
+                update = snapshots.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = update {
+                        break;
+                    }
+                    false
+                }
+                _ = tokio::time::sleep(remaining) => true,
+            },
+            None => match snapshots.recv().await {
+                Err(broadcast::error::RecvError::Closed) => break,
+                _ => false,
+            },
+        };
+        if !expired {
+            continue;
+        }
+        let timeout = state
+            .tip_snapshot(hand_id)
+            .and_then(|(_, tip)| timeout_envelope(&deadlines, &state, &tip, hand_id));
+        if let Some((seat, envelope)) = timeout {
+            info!(target: LOG_TARGET, hand_id, seat, "turn timed out; acting for the seat");
+            if let Err(err) = operator.submit(hand_id, envelope).await {
+                warn!(
+                    target: LOG_TARGET,
+                    hand_id,
+                    seat,
+                    error = %err,
+                    "timeout action was rejected"
+                );
+            }
+        }
+        // Let the ledger move on before timing the next turn.
+        if let Err(broadcast::error::RecvError::Closed) = snapshots.recv().await {
+            break;
         }
-        Ok(())
     }
+    deadlines.release(hand_id);
+}
+
+/// The timed-out seat in `snapshot` and the unsigned envelope that plays its timeout action.
+fn timeout_envelope<C: CurveGroup>(
+    deadlines: &ActionDeadlines,
+    state: &LedgerState<C>,
+    snapshot: &AnyTableSnapshot<C>,
+    hand_id: HandId,
+) -> Option<(SeatId, AnyMessageEnvelope<C>)> {
+    let (seat, action) = deadlines.timeout_action(hand_id, snapshot.betting_state()?)?;
+    let (game_id, identity, message) = match snapshot {
+        AnyTableSnapshot::Preflop(table) => (
+            table.game_id,
+            table.identity_at_seat(seat)?,
+            AnyGameMessage::PlayerPreflop(GamePlayerMessage::new(action)),
+        ),
+        AnyTableSnapshot::Flop(table) => (
+            table.game_id,
+            table.identity_at_seat(seat)?,
+            AnyGameMessage::PlayerFlop(GamePlayerMessage::new(action)),
+        ),
+        AnyTableSnapshot::Turn(table) => (
+            table.game_id,
+            table.identity_at_seat(seat)?,
+            AnyGameMessage::PlayerTurn(GamePlayerMessage::new(action)),
+        ),
+        AnyTableSnapshot::River(table) => (
+            table.game_id,
+            table.identity_at_seat(seat)?,
+            AnyGameMessage::PlayerRiver(GamePlayerMessage::new(action)),
+        ),
+        _ => return None,
+    };
+    let actor = AnyActor::Player {
+        seat_id: seat,
+        player_id: identity.player_id,
+        player_key: identity.player_key.clone(),
+    };
+    let last_nonce = nonce_key(hand_id, &actor)
+        .and_then(|key| state.nonce_counters().get(&key).copied())
+        .unwrap_or(identity.nonce);
+    let envelope = AnyMessageEnvelope {
+        hand_id,
+        game_id,
+        actor,
+        nonce: last_nonce + 1,
+        public_key: identity.public_key,
+        message: WithSignature {
+            value: message,
+            signature: Vec::new(),
+        },
+        correlation_id: None,
+    };
+    Some((seat, envelope))
 }
 
 #[derive(Deserialize)]
@@ -133,6 +396,28 @@ where
         .collect()
 }
 
+/// Checks that every configured secret derives the public key registered for its shuffler.
+///
+/// A secret that does not match would produce decryption shares nobody can verify, so the
+/// coordinator should refuse to start rather than fail mid-hand.
+pub fn validate_shuffler_secrets<C: CurveGroup>(
+    secrets: &[ShufflerSecretConfig<C>],
+    registered: &HashMap<ShufflerId, C>,
+) -> Result<()> {
+    for secret in secrets {
+        let expected = registered
+            .get(&secret.id)
+            .ok_or_else(|| anyhow!("shuffler {} has no registered public key", secret.id))?;
+        if secret.public_key() != *expected {
+            return Err(anyhow!(
+                "secret for shuffler {} does not match its registered public key",
+                secret.id
+            ));
+        }
+    }
+    Ok(())
+}
+
 const LOG_TARGET: &str = "legit_poker::game::coordinator";
 
 pub struct GameCoordinator<C>
@@ -152,12 +437,15 @@ where
     _snapshot_broadcast: broadcast::Sender<Shared<AnyTableSnapshot<C>>>,
     _staging_broadcast: broadcast::Sender<StagingLedgerUpdate<C>>,
     realtime_stop: CancellationToken,
-    realtime_handle: Option<JoinHandle<anyhow::Result<()>>>,
-    worker_handle: Option<JoinHandle<Result<(), WorkerError>>>,
+    realtime_handle: Mutex<Option<JoinHandle<anyhow::Result<()>>>>,
+    worker_stop: CancellationToken,
+    worker_handle: Mutex<Option<JoinHandle<Result<(), WorkerError>>>>,
     shufflers: Arc<HashMap<ShufflerId, Arc<ShufflerService<C, ShufflerScheme<C>>>>>,
     shuffler_order: Arc<HashMap<ShufflerId, usize>>,
     shuffler_key_to_id: Arc<HashMap<crate::ledger::CanonicalKey<C>, ShufflerId>>,
-    active_hands: Arc<DashMap<(GameId, HandId), Vec<HandSubscription<C>>>>,
+    active_hands: Arc<DashMap<(GameId, HandId), ActiveHand<C>>>,
+    hand_slots: HandSlots,
+    action_deadlines: Option<ActionDeadlines>,
 }
 
 impl<C> GameCoordinator<C>
@@ -167,8 +455,47 @@ where
     C::BaseField: PrimeField + Send + Sync,
     C::Affine: Absorb,
 {
+    /// Checks a configuration without opening connections or spawning tasks.
+    pub fn validate_config(config: &GameCoordinatorConfig<C>) -> Result<(), ConfigError> {
+        if config.shufflers.is_empty() {
+            return Err(ConfigError::NoShufflers);
+        }
+        let mut seen = HashSet::with_capacity(config.shufflers.len());
+        for shuffler in &config.shufflers {
+            if shuffler.secret.is_zero() {
+                return Err(ConfigError::ZeroShufflerSecret(shuffler.id));
+            }
+            if !seen.insert(shuffler.id) {
+                return Err(ConfigError::DuplicateShuffler(shuffler.id));
+            }
+        }
+
+        if config.submit_channel_capacity == 0 {
+            return Err(ConfigError::ZeroCapacity("submit_channel_capacity"));
+        }
+        if config.max_concurrent_hands == 0 {
+            return Err(ConfigError::ZeroCapacity("max_concurrent_hands"));
+        }
+        if config.supabase.broadcast_capacity == 0 {
+            return Err(ConfigError::ZeroCapacity("supabase.broadcast_capacity"));
+        }
+
+        let url = &config.supabase.realtime_url;
+        let invalid_url = |reason| ConfigError::InvalidSupabaseUrl {
+            url: url.to_string(),
+            reason,
+        };
+        if !matches!(url.scheme(), "ws" | "wss" | "http" | "https") {
+            return Err(invalid_url("unsupported scheme"));
+        }
+        if url.host_str().is_none() {
+            return Err(invalid_url("missing host"));
+        }
+        Ok(())
+    }
+
     pub async fn spawn(config: GameCoordinatorConfig<C>) -> Result<Self> {
-        config.require_shufflers()?;
+        Self::validate_config(&config)?;
 
         let mut rng = match config.rng_seed {
             Some(seed) => StdRng::from_seed(seed),
@@ -182,9 +509,6 @@ where
         }
         let aggregated_public_key = make_global_public_keys(public_keys.clone());
 
-        let schnorr_params = ShufflerScheme::<C>::setup(&mut rng)
-            .map_err(|err| anyhow!("failed to setup shuffler Schnorr parameters: {err}"))?;
-
         let (submit_tx, submit_rx): (mpsc::Sender<AnyMessageEnvelope<C>>, _) =
             mpsc::channel(config.submit_channel_capacity);
         let (events_tx, _) = broadcast::channel(1024);
@@ -204,7 +528,7 @@ where
         let (client, _rx0) =
             SupabaseRealtimeClient::new(config.supabase.clone(), realtime_stop.clone());
         let updates_tx = client.broadcaster();
-        let realtime_handle = Some(spawn_named_task(
+        let realtime_handle = Mutex::new(Some(spawn_named_task(
             "coordinator-realtime-client",
             async move {
                 client
@@ -212,7 +536,12 @@ where
                     .await
                     .map_err(|err| anyhow!("supabase realtime client exited with error: {err}"))
             },
-        ));
+        )));
+
+        // Every shuffler proves through one pool so concurrent hands share the CPU in priority
+        // order instead of all proving at once.
+        let proving_workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let proving_queue = ProvingQueue::new(proving_workers);
 
         let mut shufflers = HashMap::with_capacity(config.shufflers.len());
         let mut shuffler_order = HashMap::with_capacity(config.shufflers.len());
@@ -225,22 +554,26 @@ where
         {
             let mut seed = [0u8; 32];
             rng.fill_bytes(&mut seed);
-            let run_cfg = ShufflerRunConfig::new(seed);
+            let run_cfg = ShufflerRunConfig::new(seed).with_proving_queue(proving_queue.clone());
             let signing_secret = SchnorrSecretKey::<C>(shuffler.secret.clone());
             let events_rx = operator.event_updates();
             let snapshots_rx = operator.snapshot_updates();
             let canonical_key = crate::ledger::CanonicalKey::new(public_key);
-            let instance = Arc::new(ShufflerService::<C, ShufflerScheme<C>>::new(
+            let mut instance = ShufflerService::<C, ShufflerScheme<C>>::new(
                 shuffler.id,
                 public_key,
                 aggregated_public_key.clone(),
                 signing_secret.clone(),
-                schnorr_params.clone(),
+                config.signing_params.clone(),
                 submit_tx.clone(),
                 run_cfg,
                 events_rx,
                 snapshots_rx,
-            ));
+            );
+            if let Some(source) = &config.nonce_source {
+                instance = instance.with_nonce_source(Arc::clone(source));
+            }
+            let instance = Arc::new(instance);
             shufflers.insert(shuffler.id, instance);
             shuffler_order.insert(shuffler.id, index);
             shuffler_key_to_id.insert(canonical_key, shuffler.id);
@@ -251,6 +584,7 @@ where
         let shuffler_key_to_id = Arc::new(shuffler_key_to_id);
         let active_hands = Arc::new(DashMap::new());
 
+        let worker_stop = CancellationToken::new();
         let worker = LedgerWorker::new(
             submit_rx,
             Arc::clone(&config.event_store),
@@ -259,8 +593,9 @@ where
             events_tx.clone(),
             snapshots_tx.clone(),
             staging_tx.clone(),
-        );
-        let worker_handle = Some(operator.start(worker).await?);
+        )
+        .with_stop(worker_stop.clone());
+        let worker_handle = Mutex::new(Some(operator.start(worker).await?));
 
         Ok(Self {
             operator,
@@ -273,11 +608,14 @@ where
             _staging_broadcast: staging_tx,
             realtime_stop,
             realtime_handle,
+            worker_stop,
             worker_handle,
             shufflers,
             shuffler_order,
             shuffler_key_to_id,
             active_hands,
+            hand_slots: HandSlots::new(config.max_concurrent_hands),
+            action_deadlines: config.action_deadlines,
         })
     }
 
@@ -285,6 +623,17 @@ where
         Arc::clone(&self.state)
     }
 
+    /// `Running` while the realtime client task is alive and has not been asked to stop. The
+    /// client reconnects on its own, so this does not imply an open socket.
+    pub fn realtime_status(&self) -> RealtimeStatus {
+        match &*self.realtime_handle.lock() {
+            Some(handle) if !handle.is_finished() && !self.realtime_stop.is_cancelled() => {
+                RealtimeStatus::Running
+            }
+            _ => RealtimeStatus::Stopped,
+        }
+    }
+
     pub fn operator(&self) -> Arc<LedgerOperator<C>> {
         Arc::clone(&self.operator)
     }
@@ -321,9 +670,27 @@ where
         descriptors
     }
 
+    /// Subscribe the configured shufflers to a commenced hand and start its shuffle. Fails with
+    /// [`CoordinatorBusy`] when `max_concurrent_hands` hands are already attached; re-attaching
+    /// an attached hand replaces it without taking another slot. The slot is freed when the
+    /// hand reaches `Complete` or is released. Hands whose blind positions fail
+    /// [`EngineNL::validate_blind_config`] are rejected before any shuffler is subscribed.
     pub async fn attach_hand(&self, outcome: CommenceGameOutcome<C>) -> Result<()> {
         let hand_id = outcome.hand.state.id;
         let game_id = outcome.hand.game_id;
+        {
+            let snapshot = &outcome.initial_snapshot;
+            let players = initial_player_states(&snapshot.stacks, &snapshot.players);
+            EngineNL::validate_blind_config(&snapshot.cfg, &players).map_err(|err| {
+                anyhow!("hand {hand_id} has an invalid blind configuration: {err:?}")
+            })?;
+        }
+        let slot = match self.active_hands.remove(&(game_id, hand_id)) {
+            Some((_, previous)) => previous.cancel(),
+            None => self.hand_slots.try_reserve()?,
+        };
+        // Subscribe before the shuffle starts so the completion cannot be missed.
+        let snapshot_updates = self.operator.snapshot_updates();
         let snapshot = outcome.initial_snapshot;
         let expected_order = snapshot.shuffling.expected_order.clone();
 
@@ -387,19 +754,60 @@ where
                 })?;
         }
 
-        if let Some(previous) = self.active_hands.insert((game_id, hand_id), subscriptions) {
-            for sub in previous {
-                sub.cancel();
+        self.active_hands.insert(
+            (game_id, hand_id),
+            ActiveHand {
+                subscriptions,
+                slot,
+                completion_watch: None,
+                deadline_watch: None,
+            },
+        );
+        let deadline_watch = self.action_deadlines.clone().map(|deadlines| {
+            spawn_named_task(
+                format!("hand-{hand_id}-action-deadlines"),
+                enforce_action_deadlines(
+                    deadlines,
+                    Arc::clone(&self.operator),
+                    Arc::clone(&self.state),
+                    self.operator.snapshot_updates(),
+                    hand_id,
+                ),
+            )
+        });
+        let watch = spawn_named_task(
+            format!("hand-{hand_id}-completion"),
+            release_on_completion(
+                Arc::clone(&self.active_hands),
+                snapshot_updates,
+                Arc::clone(&self.state),
+                game_id,
+                hand_id,
+            ),
+        );
+        match self.active_hands.get_mut(&(game_id, hand_id)) {
+            Some(mut hand) => {
+                hand.completion_watch = Some(watch);
+                hand.deadline_watch = deadline_watch;
+            }
+            // Already complete and released by the watch itself.
+            None => {
+                drop(watch);
+                if let Some(deadline_watch) = deadline_watch {
+                    deadline_watch.abort();
+                }
             }
         }
         Ok(())
     }
 
+    /// Cancel a hand's shuffler tasks and free its slot before it completes.
     pub fn release_hand(&self, game_id: GameId, hand_id: HandId) {
-        if let Some((_, subs)) = self.active_hands.remove(&(game_id, hand_id)) {
-            for sub in subs {
-                sub.cancel();
-            }
+        if let Some((_, hand)) = self.active_hands.remove(&(game_id, hand_id)) {
+            hand.cancel();
+        }
+        if let Some(deadlines) = &self.action_deadlines {
+            deadlines.release(hand_id);
         }
     }
 
@@ -425,7 +833,42 @@ where
         }
     }
 
-    pub async fn shutdown(mut self) -> Result<()> {
+    /// Shut down like [`Self::shutdown`], aborting the realtime client and ledger worker if
+    /// they have not finished within `timeout`. Other handles to the coordinator, e.g. ones held
+    /// by HTTP handlers, may still be alive.
+    pub async fn shutdown_graceful(&self, timeout: Duration) -> Result<()> {
+        let abort_handles: Vec<_> = [
+            self.realtime_handle
+                .lock()
+                .as_ref()
+                .map(JoinHandle::abort_handle),
+            self.worker_handle
+                .lock()
+                .as_ref()
+                .map(JoinHandle::abort_handle),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        match tokio::time::timeout(timeout, self.shutdown()).await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!(
+                    target = LOG_TARGET,
+                    timeout_secs = timeout.as_secs_f32(),
+                    "coordinator shutdown timed out; aborting remaining tasks"
+                );
+                for handle in abort_handles {
+                    handle.abort();
+                }
+                Err(anyhow!("coordinator shutdown timed out after {timeout:?}"))
+            }
+        }
+    }
+
+    /// Cancel every hand and shuffler task, stop the realtime client, and wait for the ledger
+    /// worker to persist the events already queued. Later submissions are rejected.
+    pub async fn shutdown(&self) -> Result<()> {
         info!(
             target = LOG_TARGET,
             active_shuffles = self.active_hands.len(),
@@ -435,14 +878,12 @@ where
         let keys: Vec<_> = self.active_hands.iter().map(|entry| *entry.key()).collect();
         for key in keys {
             let (game_id, hand_id) = key;
-            if let Some((_, subs)) = self.active_hands.remove(&key) {
+            if let Some((_, hand)) = self.active_hands.remove(&key) {
                 info!(
                     target = LOG_TARGET,
                     game_id, hand_id, "cancelling hand subscriptions"
                 );
-                for sub in subs {
-                    sub.cancel();
-                }
+                hand.cancel();
             }
         }
 
@@ -454,7 +895,8 @@ where
             );
             shuffler.cancel_all();
         }
-        if let Some(handle) = self.realtime_handle.take() {
+        let realtime_handle = self.realtime_handle.lock().take();
+        if let Some(handle) = realtime_handle {
             info!(target = LOG_TARGET, "waiting for realtime task to finish");
             match handle.await {
                 Ok(result) => {
@@ -465,7 +907,9 @@ where
             }
         }
 
-        if let Some(handle) = self.worker_handle.take() {
+        self.worker_stop.cancel();
+        let worker_handle = self.worker_handle.lock().take();
+        if let Some(handle) = worker_handle {
             info!(target = LOG_TARGET, "waiting for ledger worker to finish");
             match handle.await {
                 Ok(Ok(())) => {
@@ -492,11 +936,162 @@ where
 {
     fn drop(&mut self) {
         self.realtime_stop.cancel();
-        if let Some(handle) = self.realtime_handle.take() {
+        self.worker_stop.cancel();
+        if let Some(handle) = self.realtime_handle.get_mut().take() {
             handle.abort();
         }
-        if let Some(handle) = self.worker_handle.take() {
+        if let Some(handle) = self.worker_handle.get_mut().take() {
             handle.abort();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::store::{SeaOrmEventStore, SeaOrmSnapshotStore};
+    use crate::ledger::test_support::{fixture_complete_snapshot, FixtureContext};
+    use crate::ledger::verifier::{LedgerVerifier, SchnorrSignatureValidator};
+    use crate::shuffler::shuffler_signing_params;
+    use ark_bn254::{Fr, G1Projective as Curve};
+    use ark_ec::PrimeGroup;
+    use sea_orm::DatabaseConnection;
+    use url::Url;
+
+    fn offline_config() -> GameCoordinatorConfig<Curve> {
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let signing_params = shuffler_signing_params::<Curve>().expect("schnorr params");
+        let signature = SchnorrSignatureValidator::<Curve>::new(signing_params.clone());
+        GameCoordinatorConfig {
+            verifier: Arc::new(LedgerVerifier::with_signature_validator(
+                Arc::clone(&state),
+                Arc::new(signature),
+            )),
+            event_store: Arc::new(SeaOrmEventStore::<Curve>::new(
+                DatabaseConnection::Disconnected,
+            )),
+            snapshot_store: Arc::new(SeaOrmSnapshotStore::<Curve>::new(
+                DatabaseConnection::Disconnected,
+            )),
+            state,
+            supabase: SupabaseRealtimeClientConfig::new(
+                Url::parse("wss://example.supabase.co/realtime/v1").expect("valid url"),
+                "anon-key",
+            ),
+            shufflers: vec![ShufflerSecretConfig {
+                id: 1,
+                secret: Fr::from(5u64),
+            }],
+            submit_channel_capacity: 32,
+            max_concurrent_hands: 1,
+            rng_seed: None,
+            signing_params,
+            action_deadlines: None,
+            nonce_source: None,
+        }
+    }
+
+    #[test]
+    fn validate_config_accepts_good_config_and_rejects_zero_capacity() {
+        let config = offline_config();
+        GameCoordinator::<Curve>::validate_config(&config).expect("config should be valid");
+
+        let config = GameCoordinatorConfig {
+            submit_channel_capacity: 0,
+            ..offline_config()
+        };
+        assert_eq!(
+            GameCoordinator::<Curve>::validate_config(&config).unwrap_err(),
+            ConfigError::ZeroCapacity("submit_channel_capacity")
+        );
+    }
+
+    #[tokio::test]
+    async fn completed_hand_returns_its_slot() {
+        let config = GameCoordinatorConfig {
+            max_concurrent_hands: 0,
+            ..offline_config()
+        };
+        assert_eq!(
+            GameCoordinator::<Curve>::validate_config(&config).unwrap_err(),
+            ConfigError::ZeroCapacity("max_concurrent_hands")
+        );
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let complete = fixture_complete_snapshot(&ctx);
+        let (game_id, hand_id) = (complete.game_id, complete.hand_id.expect("fixture hand"));
+
+        let slots = HandSlots::new(offline_config().max_concurrent_hands);
+        let active_hands = Arc::new(DashMap::new());
+        active_hands.insert(
+            (game_id, hand_id),
+            ActiveHand::<Curve> {
+                subscriptions: Vec::new(),
+                slot: slots.try_reserve().expect("a slot is free"),
+                completion_watch: None,
+            },
+        );
+        assert_eq!(
+            slots.try_reserve().unwrap_err(),
+            CoordinatorBusy { limit: 1 }
+        );
+
+        let (snapshots_tx, snapshots_rx) = broadcast::channel(4);
+        let watch = tokio::spawn(release_on_completion(
+            Arc::clone(&active_hands),
+            snapshots_rx,
+            Arc::new(LedgerState::<Curve>::new()),
+            game_id,
+            hand_id,
+        ));
+        snapshots_tx
+            .send(Arc::new(AnyTableSnapshot::Complete(complete)))
+            .expect("watch is subscribed");
+        watch.await.expect("watch finishes once the hand completes");
+
+        assert!(active_hands.is_empty());
+        slots
+            .try_reserve()
+            .expect("completed hand's slot is reusable");
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_succeeds_while_other_handles_are_alive() {
+        let coordinator = Arc::new(
+            GameCoordinator::<Curve>::spawn(offline_config())
+                .await
+                .expect("offline coordinator spawns"),
+        );
+        let handler_handle = Arc::clone(&coordinator);
+
+        coordinator
+            .shutdown_graceful(Duration::from_secs(5))
+            .await
+            .expect("shutdown does not wait for other handles");
+
+        assert_eq!(handler_handle.realtime_status(), RealtimeStatus::Stopped);
+        assert!(handler_handle.worker_handle.lock().is_none());
+    }
+
+    #[test]
+    fn validate_shuffler_secrets_rejects_mismatched_key() {
+        let good = ShufflerSecretConfig::<Curve> {
+            id: 7,
+            secret: Fr::from(11u64),
+        };
+        let wrong = ShufflerSecretConfig::<Curve> {
+            id: 8,
+            secret: Fr::from(12u64),
+        };
+        let registered = HashMap::from([
+            (7, Curve::generator() * Fr::from(11u64)),
+            (8, Curve::generator() * Fr::from(13u64)),
+        ]);
+
+        validate_shuffler_secrets(std::slice::from_ref(&good), &registered)
+            .expect("matching secret should validate");
+        let err = validate_shuffler_secrets(&[good, wrong], &registered)
+            .expect_err("mismatched secret must be rejected");
+        assert!(err.to_string().contains("shuffler 8"));
+    }
+}
@@ -2,7 +2,8 @@ pub mod manager;
 pub mod realtime;
 
 pub use manager::{
-    load_shuffler_secrets_from_env, GameCoordinator, GameCoordinatorConfig, ShufflerDescriptor,
-    ShufflerSecret, ShufflerSecretConfig,
+    load_shuffler_secrets_from_env, validate_shuffler_secrets, ConfigError, CoordinatorBusy,
+    GameCoordinator, GameCoordinatorConfig, RealtimeStatus, ShufflerDescriptor, ShufflerSecret,
+    ShufflerSecretConfig, DEFAULT_MAX_CONCURRENT_HANDS,
 };
 pub use realtime::{SupabaseRealtimeClient, SupabaseRealtimeClientConfig};
@@ -103,7 +103,11 @@ where
     pub async fn run(self) -> Result<()> {
         info!(target = LOG_TARGET, url = %self.cfg.realtime_url, "starting Supabase realtime client");
         while !self.stop.is_cancelled() {
-            match self.connect().await {
+            let connected = tokio::select! {
+                _ = self.stop.cancelled() => break,
+                connected = self.connect() => connected,
+            };
+            match connected {
                 Ok(stream) => {
                     if let Err(err) = self.pump(stream).await {
                         warn!(target = LOG_TARGET, error = %err, "realtime stream ended with error");
@@ -123,7 +127,10 @@ where
                 delay_secs = self.cfg.reconnect_delay.as_secs_f32(),
                 "waiting before reconnect attempt"
             );
-            sleep(self.cfg.reconnect_delay).await;
+            tokio::select! {
+                _ = self.stop.cancelled() => break,
+                _ = sleep(self.cfg.reconnect_delay) => {}
+            }
         }
 
         info!(target = LOG_TARGET, "Supabase realtime client stopped");
@@ -470,6 +477,7 @@ struct RawEventRow {
     payload: JsonValue,
     signature: String,
     inserted_at: String,
+    correlation_id: Option<String>,
 }
 
 fn parse_bytea(input: &str) -> Result<Vec<u8>> {
@@ -549,6 +557,7 @@ fn decode_event_row(value: Value) -> Result<events::Model> {
         payload: raw.payload,
         signature,
         inserted_at,
+        correlation_id: raw.correlation_id,
     })
 }
 
@@ -637,6 +646,7 @@ where
         nonce,
         public_key,
         message,
+        ..
     } = envelope;
 
     let WithSignature { value, signature } = message;
@@ -1 +1,2 @@
+pub mod action_clock;
 pub mod coordinator;
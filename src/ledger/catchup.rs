@@ -180,6 +180,47 @@ where
 
             apply_transition(table, &envelope, hasher)
         }
+        AnyGameMessage::NonceCommitment(message) => {
+            let table = match snapshot {
+                AnyTableSnapshot::Shuffling(table) => table,
+                _ => {
+                    return Err(CatchupError::transition_failed(
+                        sequence,
+                        anyhow::anyhow!(
+                            "nonce commitment can only be applied during shuffling phase"
+                        ),
+                    ))
+                }
+            };
+
+            let actor = match &event.actor {
+                AnyActor::Shuffler {
+                    shuffler_id,
+                    shuffler_key,
+                    ..
+                } => ShufflerActor {
+                    shuffler_id: *shuffler_id,
+                    shuffler_key: shuffler_key.clone(),
+                },
+                _ => {
+                    return Err(CatchupError::transition_failed(
+                        sequence,
+                        anyhow::anyhow!("nonce commitment must originate from a shuffler"),
+                    ))
+                }
+            };
+
+            let envelope = EnvelopedMessage {
+                hand_id: event.hand_id,
+                game_id: event.game_id,
+                actor,
+                nonce: event.nonce,
+                public_key: event.public_key.clone(),
+                message: remap_signature(&event.message, message.clone()),
+            };
+
+            apply_transition(table, &envelope, hasher)
+        }
         AnyGameMessage::Blinding(message) => {
             let table = match snapshot {
                 AnyTableSnapshot::Dealing(table) => table,
@@ -479,6 +520,47 @@ where
                 message: remap_signature(&event.message, message.clone()),
             };
 
+            apply_transition(table, &envelope, hasher)
+        }
+        AnyGameMessage::Muck(message) => {
+            let table = match snapshot {
+                AnyTableSnapshot::Showdown(table) => table,
+                _ => {
+                    return Err(CatchupError::transition_failed(
+                        sequence,
+                        anyhow::anyhow!("muck declaration can only be applied during showdown"),
+                    ))
+                }
+            };
+
+            let actor = match &event.actor {
+                AnyActor::Player {
+                    seat_id,
+                    player_id,
+                    player_key,
+                    ..
+                } => PlayerActor {
+                    seat_id: *seat_id,
+                    player_id: *player_id,
+                    player_key: player_key.clone(),
+                },
+                _ => {
+                    return Err(CatchupError::transition_failed(
+                        sequence,
+                        anyhow::anyhow!("muck declaration must originate from a player"),
+                    ))
+                }
+            };
+
+            let envelope = EnvelopedMessage {
+                hand_id: event.hand_id,
+                game_id: event.game_id,
+                actor,
+                nonce: event.nonce,
+                public_key: event.public_key.clone(),
+                message: remap_signature(&event.message, message.clone()),
+            };
+
             apply_transition(table, &envelope, hasher)
         }
     };
@@ -492,7 +574,7 @@ where
 /// transitions, validating sequence continuity along the way.
 ///
 /// Returns the final snapshot after all messages have been applied.
-fn replay_messages<C>(
+pub(crate) fn replay_messages<C>(
     mut snapshot: AnyTableSnapshot<C>,
     messages: Vec<FinalizedAnyMessageEnvelope<C>>,
     hasher: &dyn LedgerHasher,
@@ -1,5 +1,7 @@
 use sea_orm::DbErr;
 
+use crate::engine::nl::types::{Chips, SeatId};
+
 #[derive(Debug, thiserror::Error)]
 pub enum GameSetupError {
     #[error("database error: {0}")]
@@ -8,6 +10,16 @@ pub enum GameSetupError {
     Validation(String),
     #[error("{0} not found")]
     NotFound(&'static str),
+    #[error("buy-in {requested} outside allowed range [{min}, {max}]")]
+    InvalidBuyIn {
+        requested: Chips,
+        min: Chips,
+        max: Chips,
+    },
+    #[error("seat {seat} is already taken")]
+    SeatTaken { seat: SeatId },
+    #[error("{have} shufflers registered but {need} are required")]
+    InsufficientShufflers { have: usize, need: usize },
 }
 
 impl GameSetupError {
@@ -17,11 +17,11 @@ use crate::ledger::snapshot::{
     SeatingMap, ShufflerIdentity, ShufflerRoster, ShufflingSnapshot, SnapshotStatus,
     TableAtShuffling, TableSnapshot,
 };
-use crate::ledger::store::snapshot::prepare_snapshot;
-use crate::ledger::types::{GameId, HandId, ShufflerId};
+use crate::ledger::store::snapshot::{prepare_snapshot, SeaOrmSnapshotStore, SharedSnapshotStore};
+use crate::ledger::types::{EventPhase, GameId, HandId, ShufflerId};
 use crate::ledger::typestate::{MaybeSaved, Saved};
 use crate::ledger::CanonicalKey;
-use crate::shuffling::data_structures::{ElGamalCiphertext, DECK_SIZE};
+use crate::shuffling::deck_source::{DeckSource, RandomDeck};
 
 use super::error::GameSetupError;
 use super::storage::InMemoryLobbyStorage;
@@ -31,13 +31,15 @@ use super::storage::{
     NewHandShuffler, NewPlayer, NewShuffler,
 };
 use super::types::{
-    CommenceGameOutcome, CommenceGameParams, GameLobbyConfig, GameMetadata, GameRecord, HandRecord,
-    JoinGameOutput, PlayerRecord, PlayerSeatSnapshot, RegisterShufflerOutput, ShufflerAssignment,
-    ShufflerRecord, ShufflerRegistrationConfig,
+    CommenceGameOutcome, CommenceGameParams, GameLobbyConfig, GameMetadata, GamePhase, GameRecord,
+    GameSummary, HandRecord, JoinGameOutput, PlayerRecord, PlayerSeatSnapshot,
+    RegisterShufflerOutput, ShufflerAssignment, ShufflerOrder, ShufflerRecord,
+    ShufflerRegistrationConfig, StartReadiness,
 };
 use super::validation::{
-    ensure_buy_in, ensure_min_players, ensure_shuffler_sequence, ensure_unique_seats,
-    validate_blind_positions, validate_lobby_config,
+    ensure_aggregated_key, ensure_blind_config, ensure_buy_in, ensure_min_players,
+    ensure_shuffler_sequence, ensure_supported_hole_cards, ensure_unique_seats, required_players,
+    required_shufflers, resolve_buy_in, validate_lobby_config,
 };
 
 #[async_trait]
@@ -54,11 +56,22 @@ where
         lobby: GameLobbyConfig,
     ) -> Result<GameMetadata<C>, GameSetupError>;
 
+    /// Like [`host_game`](Self::host_game), but returns the existing game when the same host
+    /// key has already hosted a game with this name, so client retries don't create duplicates.
+    async fn host_game_idempotent(
+        &self,
+        host: PlayerRecord<C, MaybeSaved<PlayerId>>,
+        lobby: GameLobbyConfig,
+    ) -> Result<GameMetadata<C>, GameSetupError>;
+
+    /// Seat a player in the game. `buy_in` must fall within the game's
+    /// `[min_buy_in, max_buy_in]` range and defaults to `max_buy_in`.
     async fn join_game(
         &self,
         game: &GameRecord<Saved<GameId>>,
         player: PlayerRecord<C, MaybeSaved<PlayerId>>,
         seat_preference: Option<SeatId>,
+        buy_in: Option<Chips>,
     ) -> Result<JoinGameOutput<C>, GameSetupError>;
 
     async fn register_shuffler(
@@ -73,6 +86,25 @@ where
         hasher: &dyn LedgerHasher,
         params: CommenceGameParams,
     ) -> Result<CommenceGameOutcome<C>, GameSetupError>;
+
+    /// Canonical committee key for a game: the sum of all registered shuffler keys.
+    async fn aggregated_shuffler_key(&self, game_id: GameId) -> Result<C, GameSetupError>;
+
+    /// How many seated players and registered shufflers the game has against what
+    /// [`commence_game`](Self::commence_game) requires.
+    async fn start_readiness(&self, game_id: GameId) -> Result<StartReadiness, GameSetupError>;
+
+    /// Opt a player in or out of auto-mucking hands that cannot win at showdown. Takes effect
+    /// from the next hand that commences.
+    async fn set_auto_muck_losers(
+        &self,
+        player_id: PlayerId,
+        enabled: bool,
+    ) -> Result<(), GameSetupError>;
+
+    /// Every game that is not closed or archived, with its seated count and the phase and pot
+    /// of its current hand. Games without a hand in progress report [`GamePhase::Waiting`].
+    async fn list_active_games(&self) -> Result<Vec<GameSummary>, GameSetupError>;
 }
 
 #[derive(Clone)]
@@ -84,6 +116,9 @@ where
     C::Affine: Absorb,
 {
     storage: Arc<dyn LobbyStorage<C>>,
+    deck_source: Arc<dyn DeckSource<C>>,
+    /// Source of each current hand's latest snapshot for [`LobbyService::list_active_games`].
+    snapshot_store: Option<SharedSnapshotStore<C>>,
 }
 
 impl<C> LobbyServiceFactory<C>
@@ -94,13 +129,32 @@ where
     C::Affine: Absorb,
 {
     pub fn new(storage: Arc<dyn LobbyStorage<C>>) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            deck_source: Arc::new(RandomDeck),
+            snapshot_store: None,
+        }
+    }
+
+    /// Replace the source of each hand's initial deck, e.g. with a
+    /// [`PlaintextDeck`](crate::shuffling::deck_source::PlaintextDeck) in tests.
+    pub fn with_deck_source(mut self, deck_source: Arc<dyn DeckSource<C>>) -> Self {
+        self.deck_source = deck_source;
+        self
+    }
+
+    /// Read hands' latest snapshots from `snapshot_store` when listing active games. Without
+    /// one, a game with a hand underway reports it as pending with an empty pot.
+    pub fn with_snapshot_store(mut self, snapshot_store: SharedSnapshotStore<C>) -> Self {
+        self.snapshot_store = Some(snapshot_store);
+        self
     }
 
     pub fn from_sea_orm(connection: DatabaseConnection) -> Self {
+        let snapshot_store = Arc::new(SeaOrmSnapshotStore::<C>::new(connection.clone()));
         let storage =
             Arc::new(SeaOrmLobbyStorage::<C>::new(connection)) as Arc<dyn LobbyStorage<C>>;
-        Self::new(storage)
+        Self::new(storage).with_snapshot_store(snapshot_store)
     }
 
     pub fn in_memory() -> Self {
@@ -211,6 +265,48 @@ where
     })
 }
 
+async fn insert_hosted_game<C>(
+    txn: &mut dyn LobbyStorageTxn<C>,
+    host: PlayerRecord<C, MaybeSaved<PlayerId>>,
+    lobby: GameLobbyConfig,
+) -> Result<GameMetadata<C>, GameSetupError>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField> + CanonicalDeserialize + Send + Sync + 'static,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + UniformRand + Absorb,
+    C::Affine: Absorb,
+{
+    let host_saved = ensure_player_saved(txn, host).await?;
+
+    let game_id = txn
+        .insert_game(NewGame {
+            host_player_id: host_saved.state.id.clone(),
+            config: lobby.clone(),
+        })
+        .await?;
+
+    Ok(hosted_game_metadata(host_saved, lobby, game_id))
+}
+
+fn hosted_game_metadata<C: CurveGroup>(
+    host_saved: PlayerRecord<C, Saved<PlayerId>>,
+    lobby: GameLobbyConfig,
+    game_id: GameId,
+) -> GameMetadata<C> {
+    GameMetadata {
+        record: GameRecord {
+            name: lobby.name,
+            currency: lobby.currency,
+            stakes: lobby.stakes,
+            max_players: lobby.max_players,
+            rake_bps: lobby.rake_bps,
+            host: host_saved.state.id.clone(),
+            state: Saved { id: game_id },
+        },
+        host: host_saved,
+    }
+}
+
 #[async_trait]
 impl<C> LobbyService<C> for LobbyServiceFactory<C>
 where
@@ -226,28 +322,80 @@ where
     ) -> Result<GameMetadata<C>, GameSetupError> {
         validate_lobby_config(&lobby)?;
 
+        let mut txn = self.storage.begin().await?;
+        let result = insert_hosted_game(txn.as_mut(), host, lobby).await;
+
+        match result {
+            Ok(metadata) => {
+                txn.commit().await?;
+                Ok(metadata)
+            }
+            Err(err) => {
+                txn.rollback().await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn host_game_idempotent(
+        &self,
+        host: PlayerRecord<C, MaybeSaved<PlayerId>>,
+        lobby: GameLobbyConfig,
+    ) -> Result<GameMetadata<C>, GameSetupError> {
+        validate_lobby_config(&lobby)?;
+
         let mut txn = self.storage.begin().await?;
         let result = async {
-            let host_saved = ensure_player_saved(txn.as_mut(), host).await?;
+            let host_key = match host.state.id {
+                // Callers may pass only the id; resolve the key the same way ensure_player_saved does.
+                Some(id) if host.public_key == C::zero() => {
+                    txn.load_player_by_id(id)
+                        .await?
+                        .ok_or(GameSetupError::NotFound("player"))?
+                        .public_key
+                }
+                _ => host.public_key.clone(),
+            };
 
-            let game_id = txn
-                .insert_game(NewGame {
-                    host_player_id: host_saved.state.id.clone(),
-                    config: lobby.clone(),
-                })
-                .await?;
+            let host_key = CanonicalKey::new(host_key);
+            let seat_preference = host.seat_preference;
+            let existing = txn.find_game_by_host(&host_key, &lobby.name).await?;
+            let game_id = match existing {
+                Some(game_id) => game_id,
+                None => {
+                    let host_saved = ensure_player_saved(txn.as_mut(), host).await?;
+                    let inserted = txn
+                        .insert_game_unless_hosted(NewGame {
+                            host_player_id: host_saved.state.id.clone(),
+                            config: lobby.clone(),
+                        })
+                        .await?;
+                    match inserted {
+                        Some(game_id) => {
+                            return Ok(hosted_game_metadata(host_saved, lobby, game_id))
+                        }
+                        // A concurrent call hosted the same game between the lookup and the insert.
+                        None => txn
+                            .find_game_by_host(&host_key, &lobby.name)
+                            .await?
+                            .ok_or(GameSetupError::NotFound("game"))?,
+                    }
+                }
+            };
 
+            let record = txn.load_game(game_id).await?;
+            let stored_host = txn
+                .load_player_by_id(record.host)
+                .await?
+                .ok_or(GameSetupError::NotFound("player"))?;
             Ok(GameMetadata {
-                record: GameRecord {
-                    name: lobby.name,
-                    currency: lobby.currency,
-                    stakes: lobby.stakes,
-                    max_players: lobby.max_players,
-                    rake_bps: lobby.rake_bps,
-                    host: host_saved.state.id.clone(),
-                    state: Saved { id: game_id },
+                host: PlayerRecord {
+                    display_name: stored_host.display_name,
+                    public_key: stored_host.public_key,
+                    seat_preference,
+                    state: Saved { id: record.host },
                 },
-                host: host_saved,
+                record,
             })
         }
         .await;
@@ -269,14 +417,25 @@ where
         game: &GameRecord<Saved<GameId>>,
         player: PlayerRecord<C, MaybeSaved<PlayerId>>,
         seat_preference: Option<SeatId>,
+        buy_in: Option<Chips>,
     ) -> Result<JoinGameOutput<C>, GameSetupError> {
         let mut txn = self.storage.begin().await?;
         let result = async {
+            let game_config = txn.load_game_config(game.state.id).await?;
+            let buy_in = resolve_buy_in(&game_config, buy_in)?;
+            if let Some(seat) = seat_preference {
+                let seated = txn.load_game_players(game.state.id).await?;
+                if seated.iter().any(|(_, taken, ..)| *taken == Some(seat)) {
+                    return Err(GameSetupError::SeatTaken { seat });
+                }
+            }
+
             let player_saved = ensure_player_saved(txn.as_mut(), player).await?;
             txn.insert_game_player(NewGamePlayer {
                 game_id: game.state.id,
                 player_id: player_saved.state.id.clone(),
                 seat_preference,
+                buy_in,
             })
             .await?;
 
@@ -284,6 +443,7 @@ where
             Ok(JoinGameOutput {
                 player: player_saved,
                 game_player_row_id: (game.state.id, player_saved_clone.state.id),
+                buy_in,
             })
         }
         .await;
@@ -309,10 +469,19 @@ where
         let mut txn = self.storage.begin().await?;
         let result = async {
             let shuffler_saved = ensure_shuffler_saved(txn.as_mut(), shuffler).await?;
-            let sequence = match cfg.sequence {
-                Some(seq) => seq,
-                None => txn.count_game_shufflers(game.state.id).await?,
+            let registered = txn.load_game_shufflers(game.state.id).await?;
+            let order = ShufflerOrder::new(
+                registered
+                    .iter()
+                    .map(|(shuffler_id, sequence, _)| (*shuffler_id, *sequence)),
+            );
+            // A requested sequence that is already taken moves up to the next free one.
+            let requested = match cfg.sequence {
+                Some(sequence) => sequence,
+                None => u16::try_from(order.len())
+                    .map_err(|_| GameSetupError::validation("too many shufflers registered"))?,
             };
+            let sequence = order.next_available(requested)?;
 
             let shuffler_saved_clone = shuffler_saved.clone();
             txn.insert_game_shuffler(NewGameShuffler {
@@ -363,6 +532,9 @@ where
             small_blind_seat: params.small_blind_seat,
             big_blind_seat: params.big_blind_seat,
             check_raise_allowed: game_config.check_raise_allowed,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: game_config.max_bet_bb,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
 
         // Build stack map from params if provided
@@ -375,11 +547,14 @@ where
         // Reconstruct PlayerSeatSnapshot from queried data
         let player_snapshots: Vec<PlayerSeatSnapshot<C>> = joined_players
             .iter()
-            .map(|(player_id, seat_preference, public_key)| {
+            .map(|joined| {
+                let (player_id, seat_preference, public_key, buy_in, auto_muck) = joined;
                 let seat_id = seat_preference.ok_or_else(|| {
                     GameSetupError::validation("player must have seat assignment")
                 })?;
 
+                let buy_in = buy_in.unwrap_or(game_config.max_buy_in);
+
                 // Determine starting stack based on whether player_stacks was provided
                 let starting_stack = if params.player_stacks.is_some() {
                     // For subsequent hands: player MUST be in player_stacks
@@ -390,8 +565,8 @@ where
                         ))
                     })?
                 } else {
-                    // For first hand: default to buy-in
-                    game_config.buy_in
+                    // For first hand: the player's chosen buy-in
+                    buy_in
                 };
 
                 Ok(PlayerSeatSnapshot {
@@ -403,15 +578,15 @@ where
                     },
                     seat_id,
                     starting_stack,
+                    buy_in,
                     public_key: public_key.clone(),
+                    auto_muck_losers: *auto_muck,
                 })
             })
             .collect::<Result<Vec<_>, GameSetupError>>()?;
 
         // Compute aggregated public key
-        let aggregated_public_key = registered_shufflers
-            .iter()
-            .fold(C::zero(), |acc, (_, _, public_key)| acc + public_key);
+        let aggregated_public_key = aggregate_shuffler_keys(&registered_shufflers);
 
         // Reconstruct ShufflerAssignment from queried data
         let shuffler_assignments: Vec<ShufflerAssignment<C>> = registered_shufflers
@@ -429,31 +604,36 @@ where
             .collect();
 
         // Validation
-        validate_blind_positions(
-            params.button_seat,
-            params.small_blind_seat,
-            params.big_blind_seat,
-        )?;
+        ensure_supported_hole_cards(&hand_config)?;
         ensure_unique_seats(&player_snapshots)?;
+        ensure_blind_config(&hand_config, &player_snapshots)?;
         ensure_min_players(game_config.min_players_to_start, &player_snapshots)?;
-        ensure_shuffler_sequence(&shuffler_assignments)?;
+        ensure_shuffler_sequence(game_config.min_shufflers, &shuffler_assignments)?;
+        ensure_aggregated_key(&aggregated_public_key)?;
 
         // Only enforce buy-in minimum for first hand (when player_stacks is None)
         // For subsequent hands, players may have less than buy-in due to chip losses
         if params.player_stacks.is_none() {
-            ensure_buy_in(game_config.buy_in, &player_snapshots)?;
+            ensure_buy_in(game_config.min_buy_in, &player_snapshots)?;
         }
 
         let prepared_players = prepare_players::<C>(&player_snapshots)?;
         let prepared_shufflers = prepare_shufflers::<C>(&shuffler_assignments)?;
 
         let result = async {
+            let hand_no = match params.hand_no {
+                Some(hand_no) => {
+                    txn.record_hand_no(params.game_id, hand_no).await?;
+                    hand_no
+                }
+                None => txn.next_hand_no(params.game_id).await?,
+            };
             let hand_config_id = txn.insert_hand_config(params.game_id, &hand_config).await?;
 
             let hand_id = txn
                 .insert_hand(NewHand {
                     game_id: params.game_id,
-                    hand_no: params.hand_no,
+                    hand_no,
                     config_id: hand_config_id,
                     config: hand_config.clone(),
                     deck_commitment: params.deck_commitment.clone(),
@@ -467,6 +647,7 @@ where
                     player_id: seat.player.state.id,
                     seat: seat.seat_id,
                     starting_stack: seat.starting_stack,
+                    auto_muck_losers: seat.auto_muck_losers,
                 })
                 .await?;
             }
@@ -486,6 +667,7 @@ where
                 &hand_config,
                 &prepared_players,
                 &prepared_shufflers,
+                self.deck_source.as_ref(),
                 hasher,
             )?;
             let initial_snapshot = AnyTableSnapshot::Shuffling(snapshot.clone());
@@ -494,18 +676,18 @@ where
 
             txn.persist_snapshot(prepared).await?;
 
-            Ok((hand_id, snapshot))
+            Ok((hand_id, hand_no, snapshot))
         }
         .await;
 
         match result {
-            Ok((hand_id, snapshot)) => {
+            Ok((hand_id, hand_no, snapshot)) => {
                 txn.commit().await?;
 
                 Ok(CommenceGameOutcome {
                     hand: HandRecord {
                         game_id: params.game_id,
-                        hand_no: params.hand_no,
+                        hand_no,
                         status: crate::db::entity::sea_orm_active_enums::HandStatus::Pending,
                         state: Saved { id: hand_id },
                     },
@@ -519,6 +701,130 @@ where
             }
         }
     }
+
+    async fn aggregated_shuffler_key(&self, game_id: GameId) -> Result<C, GameSetupError> {
+        let mut txn = self.storage.begin().await?;
+        let result = txn.load_game_shufflers(game_id).await;
+        // Read-only: nothing to commit.
+        txn.rollback().await;
+
+        let registered_shufflers = result?;
+        if registered_shufflers.is_empty() {
+            return Err(GameSetupError::NotFound("game shufflers"));
+        }
+        Ok(aggregate_shuffler_keys(&registered_shufflers))
+    }
+
+    async fn start_readiness(&self, game_id: GameId) -> Result<StartReadiness, GameSetupError> {
+        let mut txn = self.storage.begin().await?;
+        let result = async {
+            let config = txn.load_game_config(game_id).await?;
+            let players = txn.load_game_players(game_id).await?;
+            let shufflers = txn.load_game_shufflers(game_id).await?;
+            Ok::<_, GameSetupError>((config, players, shufflers))
+        }
+        .await;
+        // Read-only: nothing to commit.
+        txn.rollback().await;
+
+        let (config, players, shufflers) = result?;
+        let seated = players
+            .iter()
+            .filter(|(_, seat, ..)| seat.is_some())
+            .count();
+        Ok(StartReadiness::new(
+            seated,
+            required_players(config.min_players_to_start),
+            shufflers.len(),
+            required_shufflers(config.min_shufflers),
+        ))
+    }
+
+    async fn set_auto_muck_losers(
+        &self,
+        player_id: PlayerId,
+        enabled: bool,
+    ) -> Result<(), GameSetupError> {
+        let mut txn = self.storage.begin().await?;
+        match txn.set_player_auto_muck_losers(player_id, enabled).await {
+            Ok(()) => txn.commit().await,
+            Err(err) => {
+                txn.rollback().await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn list_active_games(&self) -> Result<Vec<GameSummary>, GameSetupError> {
+        let mut txn = self.storage.begin().await?;
+        let result = txn.list_active_games().await;
+        // Read-only: nothing to commit.
+        txn.rollback().await;
+
+        let mut summaries = Vec::new();
+        for game in result? {
+            let (phase, pot) = match game.current_hand_id {
+                None => (GamePhase::Waiting, 0),
+                Some(hand_id) => {
+                    let snapshot = match &self.snapshot_store {
+                        Some(store) => {
+                            store.load_latest_snapshot(hand_id).await.map_err(|err| {
+                                GameSetupError::Database(DbErr::Custom(err.to_string()))
+                            })?
+                        }
+                        None => None,
+                    };
+                    hand_progress(snapshot.as_ref())
+                }
+            };
+            summaries.push(GameSummary {
+                game_id: game.game_id,
+                name: game.name,
+                seated: game.seated,
+                phase,
+                pot,
+            });
+        }
+        Ok(summaries)
+    }
+}
+
+/// Phase and pot of a game's current hand, judged from its latest snapshot.
+fn hand_progress<C>(snapshot: Option<&AnyTableSnapshot<C>>) -> (GamePhase, Chips)
+where
+    C: CurveGroup,
+{
+    let Some(snapshot) = snapshot else {
+        return (GamePhase::InHand(EventPhase::Pending), 0);
+    };
+    let betting = match snapshot {
+        AnyTableSnapshot::Complete(_) => return (GamePhase::Waiting, 0),
+        AnyTableSnapshot::Shuffling(_) | AnyTableSnapshot::Dealing(_) => None,
+        AnyTableSnapshot::Preflop(table) => Some(&table.betting),
+        AnyTableSnapshot::Flop(table) => Some(&table.betting),
+        AnyTableSnapshot::Turn(table) => Some(&table.betting),
+        AnyTableSnapshot::River(table) => Some(&table.betting),
+        AnyTableSnapshot::Showdown(table) => Some(&table.betting),
+    };
+    let pot = betting.map_or(0, |betting| {
+        betting.state.players.iter().fold(0, |pot: Chips, player| {
+            pot.saturating_add(player.committed_total)
+                .saturating_add(player.committed_this_round)
+        })
+    });
+    (GamePhase::InHand(snapshot.event_phase()), pot)
+}
+
+/// Sum shuffler public keys in (sequence, shuffler id) order.
+fn aggregate_shuffler_keys<C>(shufflers: &[(ShufflerId, u16, C)]) -> C
+where
+    C: CurveGroup,
+{
+    let mut ordered: Vec<_> = shufflers.iter().collect();
+    ordered.sort_by_key(|(shuffler_id, sequence, _)| (*sequence, *shuffler_id));
+    ordered
+        .into_iter()
+        .fold(C::zero(), |acc, (_, _, public_key)| acc + public_key)
 }
 
 #[derive(Clone)]
@@ -527,6 +833,7 @@ struct PreparedPlayer<C: CurveGroup> {
     seat: SeatId,
     starting_stack: Chips,
     public_key: C,
+    auto_muck_losers: bool,
 }
 
 #[derive(Clone)]
@@ -551,6 +858,7 @@ where
                 seat: seat.seat_id,
                 starting_stack: seat.starting_stack,
                 public_key: seat.public_key.clone(),
+                auto_muck_losers: seat.auto_muck_losers,
             })
         })
         .collect()
@@ -581,6 +889,7 @@ fn build_initial_snapshot<C>(
     hand_config: &HandConfig,
     players: &[PreparedPlayer<C>],
     shufflers: &[PreparedShuffler<C>],
+    deck_source: &dyn DeckSource<C>,
     hasher: &dyn LedgerHasher,
 ) -> Result<TableAtShuffling<C>, GameSetupError>
 where
@@ -603,6 +912,7 @@ where
                 player_id: player.player_id,
                 nonce: 0,
                 seat: player.seat,
+                auto_muck_losers: player.auto_muck_losers,
             },
         );
         seating.insert(player.seat, Some(player_key.clone()));
@@ -652,9 +962,7 @@ where
         })?;
 
     let mut rng = StdRng::from_entropy();
-    let initial_deck = std::array::from_fn::<_, DECK_SIZE, _>(|i| {
-        encrypt_zero::<C>(i, &aggregated_public_key, &mut rng)
-    });
+    let initial_deck = deck_source.encrypted_deck(&aggregated_public_key, &mut rng);
     let final_deck = initial_deck.clone();
 
     let mut snapshot: TableSnapshot<PhaseShuffling, C> = TableSnapshot {
@@ -674,6 +982,7 @@ where
             steps: Vec::new(),
             final_deck,
             expected_order,
+            nonce_commitments: Vec::new(),
         },
         dealing: (),
         betting: (),
@@ -684,23 +993,6 @@ where
     Ok(snapshot)
 }
 
-fn encrypt_zero<C>(
-    index: usize,
-    aggregated_public_key: &C,
-    rng: &mut StdRng,
-) -> ElGamalCiphertext<C>
-where
-    C: CurveGroup,
-    C::ScalarField: PrimeField + UniformRand,
-{
-    let message = C::ScalarField::from(index as u64);
-    let randomness = C::ScalarField::rand(rng);
-    let ciphertext =
-        ElGamalCiphertext::encrypt_scalar(message, randomness, aggregated_public_key.clone());
-
-    ciphertext
-}
-
 fn compute_initial_commitment(cfg: &HandConfig, seat: SeatId) -> Chips {
     let stakes: &TableStakes = &cfg.stakes;
     let mut committed = stakes.ante;
@@ -17,8 +17,8 @@ use crate::ledger::CanonicalKey;
 use crate::ledger::lobby::error::GameSetupError;
 
 use super::{
-    LobbyStorage, LobbyStorageTxn, NewGame, NewGamePlayer, NewGameShuffler, NewHand, NewHandPlayer,
-    NewHandShuffler, NewPlayer, NewShuffler, StoredPlayer, StoredShuffler,
+    ActiveGameRow, LobbyStorage, LobbyStorageTxn, NewGame, NewGamePlayer, NewGameShuffler, NewHand,
+    NewHandPlayer, NewHandShuffler, NewPlayer, NewShuffler, StoredPlayer, StoredShuffler,
 };
 
 struct Inner<C: CurveGroup> {
@@ -28,6 +28,7 @@ struct Inner<C: CurveGroup> {
     shufflers: HashMap<Vec<u8>, ShufflerId>,
     shufflers_by_id: HashMap<ShufflerId, StoredShuffler<C>>,
     games: HashMap<GameId, StoredGame>,
+    last_hand_nos: HashMap<GameId, i64>,
     game_players: Vec<NewGamePlayer>,
     game_shufflers: Vec<NewGameShuffler<C>>,
     hand_configs: HashMap<i64, HandConfig>,
@@ -50,6 +51,7 @@ impl<C: CurveGroup> Default for Inner<C> {
             shufflers: HashMap::new(),
             shufflers_by_id: HashMap::new(),
             games: HashMap::new(),
+            last_hand_nos: HashMap::new(),
             game_players: Vec::new(),
             game_shufflers: Vec::new(),
             hand_configs: HashMap::new(),
@@ -110,6 +112,7 @@ where
     players: Vec<(Vec<u8>, PlayerId, StoredPlayer<C>)>,
     shufflers: Vec<(Vec<u8>, ShufflerId, StoredShuffler<C>)>,
     games: Vec<(GameId, StoredGame)>,
+    last_hand_nos: HashMap<GameId, i64>,
     game_players: Vec<NewGamePlayer>,
     game_shufflers: Vec<NewGameShuffler<C>>,
     hand_configs: Vec<(i64, HandConfig)>,
@@ -137,6 +140,7 @@ where
             players: Vec::new(),
             shufflers: Vec::new(),
             games: Vec::new(),
+            last_hand_nos: HashMap::new(),
             game_players: Vec::new(),
             game_shufflers: Vec::new(),
             hand_configs: Vec::new(),
@@ -183,6 +187,20 @@ where
         id
     }
 
+    /// Last hand number allocated for `game_id` in this transaction or before it.
+    fn last_hand_no(&self, game_id: GameId) -> Result<i64, GameSetupError> {
+        if let Some(last) = self.last_hand_nos.get(&game_id) {
+            return Ok(*last);
+        }
+        let inner = self.inner.read();
+        let known =
+            inner.games.contains_key(&game_id) || self.games.iter().any(|(id, _)| *id == game_id);
+        if !known {
+            return Err(GameSetupError::NotFound("game"));
+        }
+        Ok(inner.last_hand_nos.get(&game_id).copied().unwrap_or(0))
+    }
+
     fn lookup_player(&self, key_bytes: &[u8]) -> Option<StoredPlayer<C>> {
         if let Some((_, _, stored)) = self
             .players
@@ -266,11 +284,29 @@ where
             StoredPlayer {
                 display_name: player.display_name,
                 public_key: player.public_key.clone(),
+                auto_muck_losers: false,
             },
         ));
         Ok((id, CanonicalKey::new(player.public_key)))
     }
 
+    async fn set_player_auto_muck_losers(
+        &mut self,
+        id: PlayerId,
+        enabled: bool,
+    ) -> Result<(), GameSetupError> {
+        let mut player = self
+            .lookup_player_by_id(id)
+            .ok_or(GameSetupError::NotFound("player"))?;
+        let key_bytes = serialize_curve_bytes(&player.public_key).map_err(|e| {
+            GameSetupError::validation(format!("failed to serialize public key: {}", e))
+        })?;
+        player.auto_muck_losers = enabled;
+        // Staged rows shadow committed ones until commit overwrites them.
+        self.players.push((key_bytes, id, player));
+        Ok(())
+    }
+
     async fn load_shuffler(
         &mut self,
         key: &CanonicalKey<C>,
@@ -320,6 +356,62 @@ where
         Ok(id)
     }
 
+    async fn insert_game_unless_hosted(
+        &mut self,
+        game: NewGame,
+    ) -> Result<Option<GameId>, GameSetupError> {
+        let hosted = |stored: &StoredGame| {
+            stored.host_player_id == game.host_player_id && stored.config.name == game.config.name
+        };
+        let taken = self
+            .inner
+            .read()
+            .games
+            .iter()
+            .any(|(_, stored)| hosted(stored))
+            || self.games.iter().any(|(_, stored)| hosted(stored));
+        if taken {
+            return Ok(None);
+        }
+        self.insert_game(game).await.map(Some)
+    }
+
+    async fn find_game_by_host(
+        &mut self,
+        host_key: &CanonicalKey<C>,
+        name: &str,
+    ) -> Result<Option<GameId>, GameSetupError> {
+        let key_bytes = serialize_curve_bytes(host_key.value()).map_err(|e| {
+            GameSetupError::validation(format!("failed to serialize public key: {}", e))
+        })?;
+
+        let inner = self.inner.read();
+        let host_id = self
+            .players
+            .iter()
+            .rev()
+            .find(|(k, _, _)| k.as_slice() == key_bytes.as_slice())
+            .map(|(_, id, _)| *id)
+            .or_else(|| inner.players.get(&key_bytes).copied());
+        let Some(host_id) = host_id else {
+            return Ok(None);
+        };
+
+        let matches =
+            |game: &StoredGame| game.host_player_id == host_id && game.config.name == name;
+        let committed = inner
+            .games
+            .iter()
+            .filter(|(_, game)| matches(game))
+            .map(|(id, _)| *id);
+        let pending = self
+            .games
+            .iter()
+            .filter(|(_, game)| matches(game))
+            .map(|(id, _)| *id);
+        Ok(committed.chain(pending).min())
+    }
+
     async fn insert_game_player(&mut self, row: NewGamePlayer) -> Result<(), GameSetupError> {
         self.game_players.push(row);
         Ok(())
@@ -360,6 +452,22 @@ where
         Ok(id)
     }
 
+    async fn next_hand_no(&mut self, game_id: GameId) -> Result<i64, GameSetupError> {
+        let next = self.last_hand_no(game_id)? + 1;
+        self.last_hand_nos.insert(game_id, next);
+        Ok(next)
+    }
+
+    async fn record_hand_no(
+        &mut self,
+        game_id: GameId,
+        hand_no: i64,
+    ) -> Result<(), GameSetupError> {
+        let last = self.last_hand_no(game_id)?;
+        self.last_hand_nos.insert(game_id, last.max(hand_no));
+        Ok(())
+    }
+
     async fn insert_hand(&mut self, hand: NewHand) -> Result<HandId, GameSetupError> {
         let id = self.next_hand_id();
         self.hands.push((id, StoredHand { record: hand }));
@@ -402,6 +510,7 @@ where
         for (id, game) in self.games {
             inner.games.insert(id, game);
         }
+        inner.last_hand_nos.extend(self.last_hand_nos);
         inner.game_players.extend(self.game_players);
         inner.game_shufflers.extend(self.game_shufflers);
         for (id, cfg) in self.hand_configs {
@@ -457,7 +566,16 @@ where
     async fn load_game_players(
         &mut self,
         game_id: GameId,
-    ) -> Result<Vec<(PlayerId, Option<crate::engine::nl::types::SeatId>, C)>, GameSetupError> {
+    ) -> Result<
+        Vec<(
+            PlayerId,
+            Option<crate::engine::nl::types::SeatId>,
+            C,
+            Option<crate::engine::nl::types::Chips>,
+            bool,
+        )>,
+        GameSetupError,
+    > {
         let inner = self.inner.read();
 
         let mut result = Vec::new();
@@ -476,6 +594,8 @@ where
                     game_player.player_id,
                     game_player.seat_preference,
                     player.public_key.clone(),
+                    Some(game_player.buy_in),
+                    player.auto_muck_losers,
                 ));
             }
         }
@@ -506,6 +626,33 @@ where
         Ok(result)
     }
 
+    async fn list_active_games(&mut self) -> Result<Vec<ActiveGameRow>, GameSetupError> {
+        let inner = self.inner.read();
+
+        let mut result: Vec<_> = inner
+            .games
+            .iter()
+            .map(|(game_id, game)| ActiveGameRow {
+                game_id: *game_id,
+                name: game.config.name.clone(),
+                seated: inner
+                    .game_players
+                    .iter()
+                    .filter(|row| row.game_id == *game_id && row.seat_preference.is_some())
+                    .count(),
+                current_hand_id: inner
+                    .hands
+                    .iter()
+                    .filter(|(_, hand)| hand.record.game_id == *game_id)
+                    .map(|(hand_id, _)| *hand_id)
+                    .max(),
+            })
+            .collect();
+        result.sort_by_key(|row| row.game_id);
+
+        Ok(result)
+    }
+
     async fn rollback(mut self: Box<Self>) {
         self.committed = true;
     }
@@ -1,7 +1,7 @@
 use ark_ec::CurveGroup;
 use async_trait::async_trait;
 
-use crate::engine::nl::types::{HandConfig, PlayerId, SeatId};
+use crate::engine::nl::types::{Chips, HandConfig, PlayerId, SeatId};
 use crate::ledger::store::snapshot::PreparedSnapshot;
 use crate::ledger::types::{GameId, HandId, ShufflerId};
 use crate::ledger::typestate::Saved;
@@ -38,6 +38,12 @@ where
         player: NewPlayer<C>,
     ) -> Result<(PlayerId, CanonicalKey<C>), GameSetupError>;
 
+    async fn set_player_auto_muck_losers(
+        &mut self,
+        id: PlayerId,
+        enabled: bool,
+    ) -> Result<(), GameSetupError>;
+
     async fn load_shuffler(
         &mut self,
         key: &CanonicalKey<C>,
@@ -55,6 +61,20 @@ where
 
     async fn insert_game(&mut self, game: NewGame) -> Result<GameId, GameSetupError>;
 
+    /// Insert `game` unless its host already has a game under the same name, in which case
+    /// nothing is written and `None` is returned. A host's game names are unique.
+    async fn insert_game_unless_hosted(
+        &mut self,
+        game: NewGame,
+    ) -> Result<Option<GameId>, GameSetupError>;
+
+    /// The game hosted by the player with `host_key` under `name`, if any.
+    async fn find_game_by_host(
+        &mut self,
+        host_key: &CanonicalKey<C>,
+        name: &str,
+    ) -> Result<Option<GameId>, GameSetupError>;
+
     async fn insert_game_player(&mut self, row: NewGamePlayer) -> Result<(), GameSetupError>;
 
     async fn count_game_shufflers(&mut self, game_id: GameId) -> Result<u16, GameSetupError>;
@@ -68,6 +88,14 @@ where
         cfg: &HandConfig,
     ) -> Result<i64, GameSetupError>;
 
+    /// Atomically allocate the next hand number for `game_id`, starting at 1.
+    async fn next_hand_no(&mut self, game_id: GameId) -> Result<i64, GameSetupError>;
+
+    /// Raise the last allocated hand number for `game_id` to at least `hand_no`, so
+    /// [`next_hand_no`](Self::next_hand_no) never hands out a number chosen by the caller.
+    async fn record_hand_no(&mut self, game_id: GameId, hand_no: i64)
+        -> Result<(), GameSetupError>;
+
     async fn insert_hand(&mut self, hand: NewHand) -> Result<HandId, GameSetupError>;
 
     async fn insert_hand_player(&mut self, row: NewHandPlayer) -> Result<(), GameSetupError>;
@@ -87,16 +115,21 @@ where
         game_id: GameId,
     ) -> Result<GameLobbyConfig, GameSetupError>;
 
+    /// Joined players as `(id, seat preference, public key, chosen buy-in, auto-muck losers)`;
+    /// the buy-in is `None` for rows written before buy-ins were recorded.
     async fn load_game_players(
         &mut self,
         game_id: GameId,
-    ) -> Result<Vec<(PlayerId, Option<SeatId>, C)>, GameSetupError>;
+    ) -> Result<Vec<(PlayerId, Option<SeatId>, C, Option<Chips>, bool)>, GameSetupError>;
 
     async fn load_game_shufflers(
         &mut self,
         game_id: GameId,
     ) -> Result<Vec<(ShufflerId, u16, C)>, GameSetupError>;
 
+    /// Games that are not closed or archived, in id order.
+    async fn list_active_games(&mut self) -> Result<Vec<ActiveGameRow>, GameSetupError>;
+
     async fn commit(self: Box<Self>) -> Result<(), GameSetupError>;
     async fn rollback(self: Box<Self>);
 }
@@ -105,6 +138,7 @@ where
 pub struct StoredPlayer<C: CurveGroup> {
     pub display_name: String,
     pub public_key: C,
+    pub auto_muck_losers: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -125,6 +159,16 @@ pub struct NewShuffler<C: CurveGroup> {
     pub public_key: C,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveGameRow {
+    pub game_id: GameId,
+    pub name: String,
+    /// Joined players holding a seat.
+    pub seated: usize,
+    /// Most recently commenced hand, whether or not it has finished.
+    pub current_hand_id: Option<HandId>,
+}
+
 #[derive(Clone, Debug)]
 pub struct NewGame {
     pub host_player_id: PlayerId,
@@ -136,6 +180,7 @@ pub struct NewGamePlayer {
     pub game_id: GameId,
     pub player_id: PlayerId,
     pub seat_preference: Option<SeatId>,
+    pub buy_in: Chips,
 }
 
 #[derive(Clone, Debug)]
@@ -162,6 +207,7 @@ pub struct NewHandPlayer {
     pub player_id: PlayerId,
     pub seat: SeatId,
     pub starting_stack: crate::engine::nl::types::Chips,
+    pub auto_muck_losers: bool,
 }
 
 #[derive(Clone, Debug)]
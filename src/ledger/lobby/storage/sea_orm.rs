@@ -1,9 +1,11 @@
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
-    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction,
+    DbBackend, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set, SqlErr, Statement,
+    TransactionTrait, Value,
 };
 
 use crate::db::entity::sea_orm_active_enums::{
@@ -22,8 +24,8 @@ use crate::ledger::CanonicalKey;
 use crate::ledger::lobby::error::GameSetupError;
 
 use super::{
-    LobbyStorage, LobbyStorageTxn, NewGame, NewGamePlayer, NewGameShuffler, NewHand, NewHandPlayer,
-    NewHandShuffler, NewPlayer, NewShuffler, StoredPlayer, StoredShuffler,
+    ActiveGameRow, LobbyStorage, LobbyStorageTxn, NewGame, NewGamePlayer, NewGameShuffler, NewHand,
+    NewHandPlayer, NewHandShuffler, NewPlayer, NewShuffler, StoredPlayer, StoredShuffler,
 };
 
 pub struct SeaOrmLobbyStorage<C>
@@ -94,6 +96,7 @@ where
                 Ok(Some(StoredPlayer {
                     display_name: model.display_name,
                     public_key,
+                    auto_muck_losers: model.auto_muck_losers,
                 }))
             }
             None => Ok(None),
@@ -116,6 +119,7 @@ where
                 Ok(Some(StoredPlayer {
                     display_name: model.display_name,
                     public_key,
+                    auto_muck_losers: model.auto_muck_losers,
                 }))
             }
             None => Ok(None),
@@ -142,6 +146,25 @@ where
         Ok((player_id, CanonicalKey::new(player.public_key)))
     }
 
+    async fn set_player_auto_muck_losers(
+        &mut self,
+        id: PlayerId,
+        enabled: bool,
+    ) -> Result<(), GameSetupError> {
+        let db_id = i64::try_from(id)
+            .map_err(|_| GameSetupError::validation(format!("player id {} out of range", id)))?;
+        let model = players::ActiveModel {
+            id: Set(db_id),
+            auto_muck_losers: Set(enabled),
+            ..Default::default()
+        };
+        match model.update(&self.txn).await {
+            Ok(_) => Ok(()),
+            Err(DbErr::RecordNotUpdated) => Err(GameSetupError::NotFound("player")),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     async fn load_shuffler(
         &mut self,
         key: &CanonicalKey<C>,
@@ -207,38 +230,62 @@ where
     }
 
     async fn insert_game(&mut self, game: NewGame) -> Result<GameId, GameSetupError> {
-        let stakes = game.config.stakes;
-        let small_blind = chips_to_i64(stakes.small_blind)?;
-        let big_blind = chips_to_i64(stakes.big_blind)?;
-        let ante = chips_to_i64(stakes.ante)?;
-        let host_player_id = i64::try_from(game.host_player_id).map_err(|_| {
-            GameSetupError::validation(format!(
-                "host player id {} out of range",
-                game.host_player_id
-            ))
-        })?;
+        let inserted = new_game_model(&game)?
+            .insert(&self.txn)
+            .await
+            .map_err(|err| match err.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => GameSetupError::validation(format!(
+                    "host already has a game named {:?}",
+                    game.config.name
+                )),
+                _ => err.into(),
+            })?;
+        Ok(inserted.id)
+    }
 
-        let buy_in = chips_to_i64(game.config.buy_in)?;
-        let action_time_limit_secs = game.config.action_time_limit.as_secs() as i32;
+    async fn insert_game_unless_hosted(
+        &mut self,
+        game: NewGame,
+    ) -> Result<Option<GameId>, GameSetupError> {
+        let inserted = games::Entity::insert(new_game_model(&game)?)
+            .on_conflict(
+                OnConflict::columns([games::Column::HostPlayerId, games::Column::Name])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec(&self.txn)
+            .await;
+        match inserted {
+            Ok(result) => Ok(Some(result.last_insert_id)),
+            Err(DbErr::RecordNotInserted) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
 
-        let active = games::ActiveModel {
-            host_player_id: Set(host_player_id),
-            name: Set(game.config.name.clone()),
-            currency: Set(game.config.currency.clone()),
-            max_players: Set(game.config.max_players),
-            small_blind: Set(small_blind),
-            big_blind: Set(big_blind),
-            ante: Set(ante),
-            rake_bps: Set(game.config.rake_bps),
-            status: Set(DbGameStatus::Onboarding),
-            buy_in: Set(buy_in),
-            min_players_to_start: Set(game.config.min_players_to_start),
-            check_raise_allowed: Set(game.config.check_raise_allowed),
-            action_time_limit_secs: Set(action_time_limit_secs),
-            ..Default::default()
+    async fn find_game_by_host(
+        &mut self,
+        host_key: &CanonicalKey<C>,
+        name: &str,
+    ) -> Result<Option<GameId>, GameSetupError> {
+        let public_key_bytes = serialize_curve_bytes(host_key.value()).map_err(|e| {
+            GameSetupError::validation(format!("failed to serialize public key: {}", e))
+        })?;
+
+        let Some(host) = players::Entity::find()
+            .filter(players::Column::PublicKey.eq(public_key_bytes))
+            .one(&self.txn)
+            .await?
+        else {
+            return Ok(None);
         };
-        let inserted = active.insert(&self.txn).await?;
-        Ok(inserted.id)
+
+        let game = games::Entity::find()
+            .filter(games::Column::HostPlayerId.eq(host.id))
+            .filter(games::Column::Name.eq(name))
+            .order_by_asc(games::Column::Id)
+            .one(&self.txn)
+            .await?;
+        Ok(game.map(|model| model.id))
     }
 
     async fn insert_game_player(&mut self, row: NewGamePlayer) -> Result<(), GameSetupError> {
@@ -249,6 +296,7 @@ where
             game_id: Set(row.game_id),
             player_id: Set(player_id),
             seat_preference: Set(row.seat_preference.map(|seat| seat as i16)),
+            buy_in: Set(Some(chips_to_i64(row.buy_in)?)),
             ..Default::default()
         };
         model.insert(&self.txn).await?;
@@ -302,6 +350,9 @@ where
             small_blind_seat: Set(i16::from(cfg.small_blind_seat)),
             big_blind_seat: Set(i16::from(cfg.big_blind_seat)),
             check_raise_allowed: Set(cfg.check_raise_allowed),
+            hole_cards: Set(i16::from(cfg.hole_cards)),
+            max_bet_bb: Set(cfg.max_bet_bb.map(i64::from)),
+            dealing_order: Set(i16::from(cfg.dealing_order.as_byte())),
             ..Default::default()
         };
 
@@ -309,6 +360,38 @@ where
         Ok(inserted.id)
     }
 
+    async fn next_hand_no(&mut self, game_id: GameId) -> Result<i64, GameSetupError> {
+        // The row lock taken by the UPDATE serialises concurrent commencements until commit.
+        let row = self
+            .txn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE public.games SET last_hand_no = last_hand_no + 1 \
+                 WHERE id = $1 RETURNING last_hand_no",
+                vec![Value::from(game_id)],
+            ))
+            .await?
+            .ok_or(GameSetupError::NotFound("game"))?;
+        Ok(row.try_get("", "last_hand_no")?)
+    }
+
+    async fn record_hand_no(
+        &mut self,
+        game_id: GameId,
+        hand_no: i64,
+    ) -> Result<(), GameSetupError> {
+        self.txn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE public.games SET last_hand_no = GREATEST(last_hand_no, $2) \
+                 WHERE id = $1 RETURNING last_hand_no",
+                vec![Value::from(game_id), Value::from(hand_no)],
+            ))
+            .await?
+            .ok_or(GameSetupError::NotFound("game"))?;
+        Ok(())
+    }
+
     async fn insert_hand(&mut self, hand: NewHand) -> Result<HandId, GameSetupError> {
         let model = hands::ActiveModel {
             game_id: Set(hand.game_id),
@@ -337,6 +420,7 @@ where
             seat: Set(row.seat as i16),
             nonce: Set(0),
             starting_stack: Set(starting_stack),
+            auto_muck_losers: Set(row.auto_muck_losers),
             ..Default::default()
         };
         model.insert(&self.txn).await?;
@@ -416,10 +500,16 @@ where
         let small_blind = i64_to_chips(game_model.small_blind)?;
         let big_blind = i64_to_chips(game_model.big_blind)?;
         let ante = i64_to_chips(game_model.ante)?;
-        let buy_in = i64_to_chips(game_model.buy_in)?;
+        let min_buy_in = i64_to_chips(game_model.min_buy_in)?;
+        let max_buy_in = i64_to_chips(game_model.max_buy_in)?;
 
         let action_time_limit_secs = u64::try_from(game_model.action_time_limit_secs)
             .map_err(|_| GameSetupError::validation("action_time_limit_secs out of range"))?;
+        let max_bet_bb = game_model
+            .max_bet_bb
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|_| GameSetupError::validation("max_bet_bb out of range"))?;
 
         Ok(GameLobbyConfig {
             stakes: TableStakes {
@@ -431,9 +521,12 @@ where
             rake_bps: game_model.rake_bps,
             name: game_model.name,
             currency: game_model.currency,
-            buy_in,
+            min_buy_in,
+            max_buy_in,
             min_players_to_start: game_model.min_players_to_start,
+            min_shufflers: game_model.min_shufflers,
             check_raise_allowed: game_model.check_raise_allowed,
+            max_bet_bb,
             action_time_limit: Duration::from_secs(action_time_limit_secs),
         })
     }
@@ -441,7 +534,7 @@ where
     async fn load_game_players(
         &mut self,
         game_id: crate::ledger::types::GameId,
-    ) -> Result<Vec<(PlayerId, Option<SeatId>, C)>, GameSetupError> {
+    ) -> Result<Vec<(PlayerId, Option<SeatId>, C, Option<Chips>, bool)>, GameSetupError> {
         let records = game_players::Entity::find()
             .filter(game_players::Column::GameId.eq(game_id))
             .find_also_related(players::Entity)
@@ -467,7 +560,15 @@ where
                     ))
                 })?;
 
-                Ok((player_id, seat_preference, public_key))
+                let buy_in = game_player.buy_in.map(i64_to_chips).transpose()?;
+
+                Ok((
+                    player_id,
+                    seat_preference,
+                    public_key,
+                    buy_in,
+                    player.auto_muck_losers,
+                ))
             })
             .collect()
     }
@@ -501,6 +602,31 @@ where
             .collect()
     }
 
+    async fn list_active_games(&mut self) -> Result<Vec<ActiveGameRow>, GameSetupError> {
+        let records = games::Entity::find()
+            .filter(games::Column::Status.is_in([DbGameStatus::Onboarding, DbGameStatus::Active]))
+            .order_by_asc(games::Column::Id)
+            .all(&self.txn)
+            .await?;
+
+        let mut result = Vec::with_capacity(records.len());
+        for game in records {
+            let seated = game_players::Entity::find()
+                .filter(game_players::Column::GameId.eq(game.id))
+                .filter(game_players::Column::SeatPreference.is_not_null())
+                .count(&self.txn)
+                .await?;
+            result.push(ActiveGameRow {
+                game_id: game.id,
+                name: game.name,
+                seated: seated as usize,
+                current_hand_id: game.current_hand_id,
+            });
+        }
+
+        Ok(result)
+    }
+
     async fn commit(mut self: Box<Self>) -> Result<(), GameSetupError> {
         self.txn.commit().await?;
         Ok(())
@@ -511,6 +637,43 @@ where
     }
 }
 
+fn new_game_model(game: &NewGame) -> Result<games::ActiveModel, GameSetupError> {
+    let stakes = &game.config.stakes;
+    let small_blind = chips_to_i64(stakes.small_blind)?;
+    let big_blind = chips_to_i64(stakes.big_blind)?;
+    let ante = chips_to_i64(stakes.ante)?;
+    let host_player_id = i64::try_from(game.host_player_id).map_err(|_| {
+        GameSetupError::validation(format!(
+            "host player id {} out of range",
+            game.host_player_id
+        ))
+    })?;
+
+    let min_buy_in = chips_to_i64(game.config.min_buy_in)?;
+    let max_buy_in = chips_to_i64(game.config.max_buy_in)?;
+    let action_time_limit_secs = game.config.action_time_limit.as_secs() as i32;
+
+    Ok(games::ActiveModel {
+        host_player_id: Set(host_player_id),
+        name: Set(game.config.name.clone()),
+        currency: Set(game.config.currency.clone()),
+        max_players: Set(game.config.max_players),
+        small_blind: Set(small_blind),
+        big_blind: Set(big_blind),
+        ante: Set(ante),
+        rake_bps: Set(game.config.rake_bps),
+        status: Set(DbGameStatus::Onboarding),
+        min_buy_in: Set(min_buy_in),
+        max_buy_in: Set(max_buy_in),
+        min_players_to_start: Set(game.config.min_players_to_start),
+        min_shufflers: Set(game.config.min_shufflers),
+        check_raise_allowed: Set(game.config.check_raise_allowed),
+        max_bet_bb: Set(game.config.max_bet_bb.map(i64::from)),
+        action_time_limit_secs: Set(action_time_limit_secs),
+        ..Default::default()
+    })
+}
+
 fn chips_to_i64(value: Chips) -> Result<i64, GameSetupError> {
     value
         .try_into()
@@ -1,8 +1,9 @@
 #![cfg(test)]
 
 use super::types::{
-    CommenceGameOutcome, CommenceGameParams, GameLobbyConfig, GameMetadata, PlayerRecord,
-    RegisterShufflerOutput, ShufflerRecord, ShufflerRegistrationConfig,
+    CommenceGameOutcome, CommenceGameParams, GameLobbyConfig, GameMetadata, GamePhase, GameSummary,
+    PlayerRecord, RegisterShufflerOutput, ShufflerOrder, ShufflerRecord,
+    ShufflerRegistrationConfig, StartReadiness,
 };
 use super::GameSetupError;
 use crate::curve_absorb::CurveAbsorb;
@@ -11,11 +12,11 @@ use crate::db::{connect_to_postgres_db, postgres_test_url};
 use crate::engine::nl::types::{HandConfig, PlayerId, SeatId, TableStakes};
 use crate::ledger::hash::LedgerHasher;
 use crate::ledger::lobby::storage::{LobbyStorage, SeaOrmLobbyStorage};
-use crate::ledger::snapshot::AnyTableSnapshot;
+use crate::ledger::snapshot::{AnyTableSnapshot, SnapshotSeq};
 use crate::ledger::state::LedgerState;
 use crate::ledger::store::snapshot::{PreparedSnapshot, SeaOrmSnapshotStore};
 use crate::ledger::store::{EventStore, SeaOrmEventStore, SnapshotStore};
-use crate::ledger::types::{GameId, HandId, ShufflerId};
+use crate::ledger::types::{EventPhase, GameId, HandId, ShufflerId};
 use crate::ledger::typestate::{MaybeSaved, Saved};
 use crate::ledger::verifier::LedgerVerifier;
 use crate::ledger::worker::LedgerWorker;
@@ -27,6 +28,7 @@ use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{CurveGroup, PrimeGroup};
 use ark_ff::PrimeField;
 use ark_ff::UniformRand;
+use ark_ff::Zero;
 use ark_serialize::CanonicalSerialize;
 use ark_std::rand::{rngs::StdRng, SeedableRng};
 use async_trait::async_trait;
@@ -57,6 +59,61 @@ async fn host_game_creates_new_game_and_host() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn host_game_idempotent_returns_existing_game() -> Result<()> {
+    let Some((lobby, conn)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let config = test_lobby_config();
+
+    let host = || PlayerRecord {
+        display_name: "Host".into(),
+        public_key: keys.host.point,
+        seat_preference: Some(0),
+        state: MaybeSaved { id: None },
+    };
+    let first = lobby.host_game_idempotent(host(), config.clone()).await?;
+    let second = lobby.host_game_idempotent(host(), config).await?;
+
+    assert_eq!(first.record.state.id, second.record.state.id);
+    assert_eq!(first.host.state.id, second.host.state.id);
+    assert_eq!(games::Entity::find().count(&conn).await?, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrent_idempotent_hosting_creates_one_game() -> Result<()> {
+    let Some((lobby, conn)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let host = || PlayerRecord {
+        display_name: "Host".into(),
+        public_key: keys.host.point,
+        seat_preference: Some(0),
+        state: MaybeSaved { id: None },
+    };
+    // Save the host up front so the two calls below only race on the game row.
+    let warm_up = GameLobbyConfig {
+        name: "Warm-up".into(),
+        ..test_lobby_config()
+    };
+    lobby.host_game(host(), warm_up).await?;
+
+    let config = test_lobby_config();
+    let (first, second) = tokio::join!(
+        lobby.host_game_idempotent(host(), config.clone()),
+        lobby.host_game_idempotent(host(), config.clone()),
+    );
+    assert_eq!(first?.record.state.id, second?.record.state.id);
+    assert_eq!(games::Entity::find().count(&conn).await?, 2);
+
+    let duplicate = lobby.host_game(host(), config).await;
+    assert!(matches!(duplicate, Err(GameSetupError::Validation(_))));
+    Ok(())
+}
+
 #[tokio::test]
 async fn join_game_inserts_membership() -> Result<()> {
     let Some((lobby, conn)) = setup_lobby().await? else {
@@ -84,6 +141,134 @@ async fn join_game_inserts_membership() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn join_game_rejects_a_taken_seat() -> Result<()> {
+    let Some((lobby, _)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    join_host(&lobby, &metadata).await?;
+
+    let joiner = PlayerRecord {
+        display_name: "Bob".into(),
+        public_key: keys.player.point,
+        seat_preference: Some(0),
+        state: MaybeSaved { id: None },
+    };
+    let err = join_game_curve(&lobby, &metadata.record, joiner, Some(0))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GameSetupError::SeatTaken { seat: 0 }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_game_accepts_buy_in_within_range() -> Result<()> {
+    let Some((lobby, conn)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (metadata, config) = create_game(&lobby, &keys).await?;
+
+    let output = lobby
+        .join_game(
+            &metadata.record,
+            PlayerRecord {
+                display_name: "Bob".into(),
+                public_key: keys.player.point,
+                seat_preference: Some(1),
+                state: MaybeSaved { id: None },
+            },
+            Some(1),
+            Some(config.min_buy_in),
+        )
+        .await?;
+    assert_eq!(output.buy_in, config.min_buy_in);
+
+    let membership = game_players::Entity::find_by_id((
+        metadata.record.state.id,
+        i64::try_from(output.player.state.id).unwrap(),
+    ))
+    .one(&conn)
+    .await?
+    .expect("membership row");
+    assert_eq!(membership.buy_in, Some(config.min_buy_in as i64));
+
+    let host = join_host(&lobby, &metadata).await?;
+    let host_membership = game_players::Entity::find_by_id((
+        metadata.record.state.id,
+        i64::try_from(host.state.id).unwrap(),
+    ))
+    .one(&conn)
+    .await?
+    .expect("host membership row");
+    assert_eq!(
+        host_membership.buy_in,
+        Some(config.max_buy_in as i64),
+        "buy-in defaults to max_buy_in"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_game_rejects_buy_in_below_min() -> Result<()> {
+    let Some((lobby, _)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (metadata, config) = create_game(&lobby, &keys).await?;
+
+    let err = lobby
+        .join_game(
+            &metadata.record,
+            PlayerRecord {
+                display_name: "Bob".into(),
+                public_key: keys.player.point,
+                seat_preference: Some(1),
+                state: MaybeSaved { id: None },
+            },
+            Some(1),
+            Some(config.min_buy_in - 1),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        GameSetupError::InvalidBuyIn { requested, .. } if requested == config.min_buy_in - 1
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_game_rejects_buy_in_above_max() -> Result<()> {
+    let Some((lobby, _)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (metadata, config) = create_game(&lobby, &keys).await?;
+
+    let err = lobby
+        .join_game(
+            &metadata.record,
+            PlayerRecord {
+                display_name: "Bob".into(),
+                public_key: keys.player.point,
+                seat_preference: Some(1),
+                state: MaybeSaved { id: None },
+            },
+            Some(1),
+            Some(config.max_buy_in + 1),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        GameSetupError::InvalidBuyIn { requested, .. } if requested == config.max_buy_in + 1
+    ));
+    Ok(())
+}
+
 #[tokio::test]
 async fn register_shuffler_assigns_sequence() -> Result<()> {
     let Some((lobby, _)) = setup_lobby().await? else {
@@ -125,6 +310,167 @@ async fn register_shuffler_assigns_sequence() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn register_shuffler_resolves_sequence_collision() -> Result<()> {
+    let lobby: Arc<TestLobby> = Arc::new(LobbyServiceFactory::<TestCurve>::in_memory());
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+
+    let mut assigned = Vec::new();
+    for name in ["First", "Second", "Third"] {
+        let output = register_shuffler_curve(
+            &lobby,
+            &metadata.record,
+            ShufflerRecord {
+                display_name: name.into(),
+                public_key: TestKeys::new().shuffler.point,
+                state: MaybeSaved { id: None },
+            },
+            ShufflerRegistrationConfig { sequence: Some(0) },
+        )
+        .await?;
+        assigned.push((output.shuffler.state.id, output.assigned_sequence));
+    }
+
+    assert_eq!(
+        assigned.iter().map(|(_, seq)| *seq).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+    let order = ShufflerOrder::new(assigned.iter().copied());
+    order.validate()?;
+    assert_eq!(order.turn_index(assigned[2].0), Some(2));
+    Ok(())
+}
+
+#[test]
+fn shuffler_order_rejects_gapped_sequences() {
+    let gapped = ShufflerOrder::new([(10, 0), (11, 2)]);
+    assert!(matches!(
+        gapped.validate(),
+        Err(GameSetupError::Validation(_))
+    ));
+    assert_eq!(gapped.next_available(0).unwrap(), 1);
+
+    let full = ShufflerOrder::new([(10, u16::MAX - 1), (11, u16::MAX)]);
+    assert!(matches!(
+        full.next_available(u16::MAX - 1),
+        Err(GameSetupError::Validation(_))
+    ));
+
+    let duplicated = ShufflerOrder::new([(10, 0), (11, 0)]);
+    assert!(duplicated.validate().is_err());
+    assert!(ShufflerOrder::new([(11, 1), (10, 0)]).validate().is_ok());
+}
+
+#[test]
+fn only_scorable_hole_card_counts_pass_commence() {
+    let mut cfg = HandConfig {
+        stakes: TableStakes {
+            small_blind: 1,
+            big_blind: 2,
+            ante: 0,
+        },
+        button: 0,
+        small_blind_seat: 1,
+        big_blind_seat: 2,
+        check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+    };
+    assert!(super::ensure_supported_hole_cards(&cfg).is_ok());
+
+    cfg.hole_cards = crate::engine::nl::types::OMAHA_HOLE_CARDS;
+    assert!(super::ensure_supported_hole_cards(&cfg).is_ok());
+
+    cfg.hole_cards = 3;
+    assert!(matches!(
+        super::ensure_supported_hole_cards(&cfg),
+        Err(GameSetupError::Validation(_))
+    ));
+}
+
+#[tokio::test]
+async fn aggregated_shuffler_key_sums_registered_shufflers() -> Result<()> {
+    let Some((lobby, _)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+
+    let shuffler_keys: Vec<TestCurve> = (0..3).map(|_| TestKeys::new().shuffler.point).collect();
+    for (idx, public_key) in shuffler_keys.iter().enumerate() {
+        register_shuffler_curve(
+            &lobby,
+            &metadata.record,
+            ShufflerRecord {
+                display_name: format!("Shuffler {idx}"),
+                public_key: *public_key,
+                state: MaybeSaved { id: None },
+            },
+            ShufflerRegistrationConfig { sequence: None },
+        )
+        .await?;
+    }
+
+    let expected = shuffler_keys
+        .iter()
+        .fold(TestCurve::zero(), |acc, key| acc + key);
+    let aggregated = lobby
+        .aggregated_shuffler_key(metadata.record.state.id)
+        .await?;
+    assert_eq!(aggregated, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn start_readiness_reports_missing_players_and_shufflers() -> Result<()> {
+    let lobby: Arc<TestLobby> = Arc::new(LobbyServiceFactory::<TestCurve>::in_memory());
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    let game_id = metadata.record.state.id;
+    join_host(&lobby, &metadata).await?;
+
+    let readiness = lobby.start_readiness(game_id).await?;
+    assert_eq!(
+        readiness,
+        StartReadiness {
+            seated: 1,
+            required: 3,
+            shufflers_registered: 0,
+            shufflers_required: 1,
+            ready: false,
+        }
+    );
+
+    for (seat, name) in [(1, "Bob"), (2, "Carol")] {
+        let player = PlayerRecord {
+            display_name: name.into(),
+            public_key: TestKeys::new().player.point,
+            seat_preference: Some(seat),
+            state: MaybeSaved { id: None },
+        };
+        join_game_curve(&lobby, &metadata.record, player, Some(seat)).await?;
+    }
+    register_shuffler_curve(
+        &lobby,
+        &metadata.record,
+        ShufflerRecord {
+            display_name: "Shuffler".into(),
+            public_key: keys.shuffler.point,
+            state: MaybeSaved { id: None },
+        },
+        ShufflerRegistrationConfig { sequence: Some(0) },
+    )
+    .await?;
+
+    let readiness = lobby.start_readiness(game_id).await?;
+    assert_eq!(readiness.seated, 3);
+    assert_eq!(readiness.shufflers_registered, 1);
+    assert!(readiness.ready);
+    Ok(())
+}
+
 #[tokio::test]
 async fn commence_game_creates_hand_artifacts() -> Result<()> {
     let Some((lobby, conn)) = setup_lobby().await? else {
@@ -179,11 +525,14 @@ async fn commence_game_creates_hand_artifacts() -> Result<()> {
         small_blind_seat: 1,
         big_blind_seat: 2,
         check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
     };
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: hand_cfg.button,
         small_blind_seat: hand_cfg.small_blind_seat,
         big_blind_seat: hand_cfg.big_blind_seat,
@@ -228,6 +577,139 @@ async fn commence_game_creates_hand_artifacts() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn commence_game_allocates_sequential_hand_numbers() -> Result<()> {
+    let Some((lobby, conn)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    join_host(&lobby, &metadata).await?;
+    for (seat, name) in [(1, "Bob"), (2, "Carol")] {
+        join_game_curve(
+            &lobby,
+            &metadata.record,
+            PlayerRecord {
+                display_name: name.into(),
+                public_key: TestKeys::new().player.point,
+                seat_preference: Some(seat),
+                state: MaybeSaved { id: None },
+            },
+            Some(seat),
+        )
+        .await?;
+    }
+    register_shuffler_curve(
+        &lobby,
+        &metadata.record,
+        ShufflerRecord {
+            display_name: "Shuffler".into(),
+            public_key: keys.shuffler.point,
+            state: MaybeSaved { id: None },
+        },
+        ShufflerRegistrationConfig { sequence: Some(0) },
+    )
+    .await?;
+    let Some(operator) = setup_operator(&conn).await else {
+        return Ok(());
+    };
+
+    let params = || CommenceGameParams {
+        game_id: metadata.record.state.id,
+        hand_no: None,
+        button_seat: 0,
+        small_blind_seat: 1,
+        big_blind_seat: 2,
+        deck_commitment: None,
+        player_stacks: None,
+    };
+    let (first, second, third) = tokio::join!(
+        commence_game_curve(&lobby, &operator, params()),
+        commence_game_curve(&lobby, &operator, params()),
+        commence_game_curve(&lobby, &operator, params()),
+    );
+
+    let mut hand_nos = vec![
+        first?.hand.hand_no,
+        second?.hand.hand_no,
+        third?.hand.hand_no,
+    ];
+    hand_nos.sort_unstable();
+    assert_eq!(hand_nos, vec![1, 2, 3]);
+
+    let stored: Vec<i64> = hands::Entity::find()
+        .filter(hands::Column::GameId.eq(metadata.record.state.id))
+        .order_by_asc(hands::Column::HandNo)
+        .all(&conn)
+        .await?
+        .into_iter()
+        .map(|hand| hand.hand_no)
+        .collect();
+    assert_eq!(stored, vec![1, 2, 3]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_active_games_reports_phase_of_current_hand() -> Result<()> {
+    let Some((lobby, _conn)) = setup_lobby().await? else {
+        return Ok(());
+    };
+    let keys = TestKeys::new();
+    let (playing, _) = create_game(&lobby, &keys).await?;
+    seat_three_players(&lobby, &playing).await?;
+    register_shuffler_curve(
+        &lobby,
+        &playing.record,
+        ShufflerRecord {
+            display_name: "Shuffler".into(),
+            public_key: keys.shuffler.point,
+            state: MaybeSaved { id: None },
+        },
+        ShufflerRegistrationConfig { sequence: Some(0) },
+    )
+    .await?;
+    commence_in_memory(&lobby, playing.record.state.id).await?;
+
+    let waiting_config = GameLobbyConfig {
+        name: "Quiet Table".into(),
+        ..test_lobby_config()
+    };
+    let waiting = host_game_curve(
+        &lobby,
+        PlayerRecord {
+            display_name: "Dana".into(),
+            public_key: TestKeys::new().host.point,
+            seat_preference: Some(0),
+            state: MaybeSaved { id: None },
+        },
+        waiting_config,
+    )
+    .await?;
+    join_host(&lobby, &waiting).await?;
+
+    let summaries = lobby.list_active_games().await?;
+    assert_eq!(
+        summaries,
+        vec![
+            GameSummary {
+                game_id: playing.record.state.id,
+                name: "Test Game".into(),
+                seated: 3,
+                phase: GamePhase::InHand(EventPhase::Shuffling),
+                pot: 0,
+            },
+            GameSummary {
+                game_id: waiting.record.state.id,
+                name: "Quiet Table".into(),
+                seated: 1,
+                phase: GamePhase::Waiting,
+                pot: 0,
+            },
+        ]
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn commence_game_rejects_duplicate_seats() -> Result<()> {
     let Some((lobby, conn)) = setup_lobby().await? else {
@@ -278,7 +760,7 @@ async fn commence_game_rejects_duplicate_seats() -> Result<()> {
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: 0,
         small_blind_seat: 0,
         big_blind_seat: 1,
@@ -331,7 +813,7 @@ async fn commence_game_requires_min_players() -> Result<()> {
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: 0,
         small_blind_seat: 0,
         big_blind_seat: 1,
@@ -348,6 +830,124 @@ async fn commence_game_requires_min_players() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn commence_game_requires_min_shufflers() -> Result<()> {
+    let lobby: Arc<TestLobby> = Arc::new(LobbyServiceFactory::<TestCurve>::in_memory());
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    seat_three_players(&lobby, &metadata).await?;
+
+    let err = commence_in_memory(&lobby, metadata.record.state.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        GameSetupError::InsufficientShufflers { have: 0, need: 1 }
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn commence_game_rejects_an_unseated_big_blind() -> Result<()> {
+    let lobby: Arc<TestLobby> = Arc::new(LobbyServiceFactory::<TestCurve>::in_memory());
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    seat_three_players(&lobby, &metadata).await?;
+    register_shuffler_curve(
+        &lobby,
+        &metadata.record,
+        ShufflerRecord {
+            display_name: "Shuffler".into(),
+            public_key: keys.shuffler.point,
+            state: MaybeSaved { id: None },
+        },
+        ShufflerRegistrationConfig { sequence: Some(0) },
+    )
+    .await?;
+
+    let state = LedgerState::<TestCurve>::new();
+    let params = CommenceGameParams {
+        game_id: metadata.record.state.id,
+        hand_no: Some(1),
+        button_seat: 0,
+        small_blind_seat: 1,
+        big_blind_seat: 5,
+        deck_commitment: None,
+        player_stacks: None,
+    };
+    let err = lobby
+        .commence_game(&state.hasher(), params)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, GameSetupError::Validation(reason) if reason.contains("big blind")),
+        "unexpected error: {err:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn allocated_hand_numbers_continue_after_an_explicit_one() -> Result<()> {
+    let lobby: Arc<TestLobby> = Arc::new(LobbyServiceFactory::<TestCurve>::in_memory());
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    seat_three_players(&lobby, &metadata).await?;
+    register_shuffler_curve(
+        &lobby,
+        &metadata.record,
+        ShufflerRecord {
+            display_name: "Shuffler".into(),
+            public_key: keys.shuffler.point,
+            state: MaybeSaved { id: None },
+        },
+        ShufflerRegistrationConfig { sequence: Some(0) },
+    )
+    .await?;
+
+    let state = LedgerState::<TestCurve>::new();
+    let params = |hand_no| CommenceGameParams {
+        game_id: metadata.record.state.id,
+        hand_no,
+        button_seat: 0,
+        small_blind_seat: 1,
+        big_blind_seat: 2,
+        deck_commitment: None,
+        player_stacks: None,
+    };
+    let explicit = lobby
+        .commence_game(&state.hasher(), params(Some(5)))
+        .await?;
+    let allocated = lobby.commence_game(&state.hasher(), params(None)).await?;
+    assert_eq!(explicit.hand.hand_no, 5);
+    assert_eq!(allocated.hand.hand_no, 6);
+    Ok(())
+}
+
+#[tokio::test]
+async fn commence_game_rejects_identity_aggregated_key() -> Result<()> {
+    let lobby: Arc<TestLobby> = Arc::new(LobbyServiceFactory::<TestCurve>::in_memory());
+    let keys = TestKeys::new();
+    let (metadata, _) = create_game(&lobby, &keys).await?;
+    seat_three_players(&lobby, &metadata).await?;
+    register_shuffler_curve(
+        &lobby,
+        &metadata.record,
+        ShufflerRecord {
+            display_name: "Identity".into(),
+            public_key: TestCurve::zero(),
+            state: MaybeSaved { id: None },
+        },
+        ShufflerRegistrationConfig { sequence: Some(0) },
+    )
+    .await?;
+
+    let err = commence_in_memory(&lobby, metadata.record.state.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GameSetupError::Validation(_)));
+    Ok(())
+}
+
 #[tokio::test]
 async fn commence_game_requires_buy_in() -> Result<()> {
     let Some((lobby, conn)) = setup_lobby().await? else {
@@ -398,7 +998,7 @@ async fn commence_game_requires_buy_in() -> Result<()> {
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: 0,
         small_blind_seat: 0,
         big_blind_seat: 1,
@@ -468,7 +1068,7 @@ async fn commence_game_rejects_invalid_player_key_bytes() -> Result<()> {
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: 0,
         small_blind_seat: 0,
         big_blind_seat: 1,
@@ -545,7 +1145,7 @@ async fn commence_game_with_partial_player_stacks_should_error() -> Result<()> {
     // First hand: player_stacks = None should succeed
     let params_hand_1 = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: 0,
         small_blind_seat: 1,
         big_blind_seat: 2,
@@ -560,7 +1160,7 @@ async fn commence_game_with_partial_player_stacks_should_error() -> Result<()> {
     // This should ERROR instead of silently defaulting to buy_in
     let params_hand_2 = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 2,
+        hand_no: Some(2),
         button_seat: 0,
         small_blind_seat: 1,
         big_blind_seat: 2,
@@ -678,26 +1278,33 @@ async fn setup_lobby() -> Result<Option<(Arc<TestLobby>, DatabaseConnection)>> {
     Ok(Some((lobby, conn)))
 }
 
-async fn create_game(
-    lobby: &Arc<TestLobby>,
-    keys: &TestKeys,
-) -> Result<(GameMetadata<TestCurve>, GameLobbyConfig)> {
+fn test_lobby_config() -> GameLobbyConfig {
     let stakes = TableStakes {
         small_blind: 50,
         big_blind: 100,
         ante: 0,
     };
-    let config = GameLobbyConfig {
-        stakes: stakes.clone(),
+    GameLobbyConfig {
+        stakes,
         max_players: 9,
         rake_bps: 0,
         name: "Test Game".into(),
         currency: "chips".into(),
-        buy_in: 1_000,
+        min_buy_in: 500,
+        max_buy_in: 1_000,
         min_players_to_start: 3,
+        min_shufflers: 1,
         check_raise_allowed: true,
+        max_bet_bb: None,
         action_time_limit: std::time::Duration::from_secs(30),
-    };
+    }
+}
+
+async fn create_game(
+    lobby: &Arc<TestLobby>,
+    keys: &TestKeys,
+) -> Result<(GameMetadata<TestCurve>, GameLobbyConfig)> {
+    let config = test_lobby_config();
     let host = PlayerRecord {
         display_name: "Host".into(),
         public_key: keys.host.point,
@@ -724,6 +1331,41 @@ async fn join_host(
     Ok(joined.player)
 }
 
+async fn seat_three_players(
+    lobby: &Arc<TestLobby>,
+    metadata: &GameMetadata<TestCurve>,
+) -> Result<()> {
+    join_host(lobby, metadata).await?;
+    for (seat, name) in [(1, "Bob"), (2, "Carol")] {
+        let player = PlayerRecord {
+            display_name: name.into(),
+            public_key: TestKeys::new().player.point,
+            seat_preference: Some(seat),
+            state: MaybeSaved { id: None },
+        };
+        join_game_curve(lobby, &metadata.record, player, Some(seat)).await?;
+    }
+    Ok(())
+}
+
+/// Commence the first hand without a ledger operator, for lobbies backed by in-memory storage.
+async fn commence_in_memory(
+    lobby: &Arc<TestLobby>,
+    game_id: GameId,
+) -> Result<CommenceGameOutcome<TestCurve>, GameSetupError> {
+    let state = LedgerState::<TestCurve>::new();
+    let params = CommenceGameParams {
+        game_id,
+        hand_no: Some(1),
+        button_seat: 0,
+        small_blind_seat: 1,
+        big_blind_seat: 2,
+        deck_commitment: None,
+        player_stacks: None,
+    };
+    lobby.commence_game(&state.hasher(), params).await
+}
+
 async fn reset_database(conn: &DatabaseConnection) -> Result<()> {
     conn.execute(Statement::from_string(
         DbBackend::Postgres,
@@ -760,7 +1402,7 @@ async fn join_game_curve(
     player: PlayerRecord<TestCurve, MaybeSaved<PlayerId>>,
     seat: Option<SeatId>,
 ) -> Result<super::types::JoinGameOutput<TestCurve>, GameSetupError> {
-    lobby.join_game(game, player, seat).await
+    lobby.join_game(game, player, seat, None).await
 }
 
 async fn register_shuffler_curve(
@@ -826,6 +1468,19 @@ where
     ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
         Ok(None)
     }
+
+    async fn snapshot_at(
+        &self,
+        _hand_id: HandId,
+        _sequence: SnapshotSeq,
+        _hasher: &Arc<dyn LedgerHasher + Send + Sync>,
+    ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
+        Ok(None)
+    }
+
+    async fn compact(&self, _hand_id: HandId, _keep_every: u32) -> anyhow::Result<u64> {
+        Ok(0)
+    }
 }
 
 async fn setup_operator(conn: &DatabaseConnection) -> Option<LedgerOperator<TestCurve>> {
@@ -1002,7 +1657,7 @@ async fn test_persist_and_recover_large_game_then_commence() -> Result<()> {
     assert_eq!(recovered_players.len(), 5, "should recover 5 players");
 
     // Verify player IDs match
-    let recovered_player_ids: HashSet<_> = recovered_players.iter().map(|(id, _, _)| *id).collect();
+    let recovered_player_ids: HashSet<_> = recovered_players.iter().map(|(id, ..)| *id).collect();
     let original_player_ids: HashSet<_> = all_players.iter().map(|p| p.state.id).collect();
     assert_eq!(recovered_player_ids, original_player_ids);
 
@@ -1065,11 +1720,14 @@ async fn test_persist_and_recover_large_game_then_commence() -> Result<()> {
         small_blind_seat: 1,
         big_blind_seat: 2,
         check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
     };
 
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: Some(1),
         button_seat: hand_cfg.button,
         small_blind_seat: hand_cfg.small_blind_seat,
         big_blind_seat: hand_cfg.big_blind_seat,
@@ -1110,7 +1768,7 @@ async fn test_persist_and_recover_large_game_then_commence() -> Result<()> {
     // Verify each player has correct stack
     for hp in &hand_players {
         assert_eq!(
-            hp.starting_stack, config.buy_in as i64,
+            hp.starting_stack, config.max_buy_in as i64,
             "player starting stack should equal buy-in"
         );
     }
@@ -1,6 +1,7 @@
+use super::error::GameSetupError;
 use crate::engine::nl::types::{Chips, PlayerId, SeatId, TableStakes};
 use crate::ledger::snapshot::TableAtShuffling;
-use crate::ledger::types::{GameId, HandId, ShufflerId};
+use crate::ledger::types::{EventPhase, GameId, HandId, ShufflerId};
 use crate::ledger::typestate::{DbRowStatus, MaybeSaved, NotSaved, Saved};
 use ark_ec::CurveGroup;
 
@@ -139,7 +140,11 @@ where
     pub player: PlayerRecord<C, Saved<PlayerId>>,
     pub seat_id: SeatId,
     pub starting_stack: Chips,
+    /// Buy-in the player chose when joining
+    pub buy_in: Chips,
     pub public_key: C,
+    /// Showdown preference copied into the hand when it commences
+    pub auto_muck_losers: bool,
 }
 
 impl<C> PlayerSeatSnapshot<C>
@@ -150,12 +155,14 @@ where
         player: PlayerRecord<C, Saved<PlayerId>>,
         seat_id: SeatId,
         starting_stack: Chips,
+        buy_in: Chips,
         public_key: C,
     ) -> Self {
         Self {
             player,
             seat_id,
             starting_stack,
+            buy_in,
             public_key,
         }
     }
@@ -194,9 +201,17 @@ pub struct GameLobbyConfig {
     pub rake_bps: i16,
     pub name: String,
     pub currency: String,
-    pub buy_in: Chips,
+    /// Smallest buy-in a player may bring
+    pub min_buy_in: Chips,
+    /// Largest buy-in a player may bring; used when a player doesn't request one
+    pub max_buy_in: Chips,
     pub min_players_to_start: i16,
+    /// Registered shufflers needed before a hand can commence
+    pub min_shufflers: i16,
     pub check_raise_allowed: bool,
+    /// Cap on any bet, as a multiple of the big blind, applied to every hand; `None` is
+    /// uncapped
+    pub max_bet_bb: Option<u32>,
     pub action_time_limit: std::time::Duration,
 }
 
@@ -210,6 +225,54 @@ pub struct GameMetadata<C: CurveGroup> {
 pub struct JoinGameOutput<C: CurveGroup> {
     pub player: PlayerRecord<C, Saved<PlayerId>>,
     pub game_player_row_id: (GameId, PlayerId),
+    pub buy_in: Chips,
+}
+
+/// Whether a game has enough seated players and registered shufflers to commence a hand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StartReadiness {
+    pub seated: usize,
+    pub required: usize,
+    pub shufflers_registered: usize,
+    pub shufflers_required: usize,
+    pub ready: bool,
+}
+
+impl StartReadiness {
+    pub fn new(
+        seated: usize,
+        required: usize,
+        shufflers_registered: usize,
+        shufflers_required: usize,
+    ) -> Self {
+        Self {
+            seated,
+            required,
+            shufflers_registered,
+            shufflers_required,
+            ready: seated >= required && shufflers_registered >= shufflers_required,
+        }
+    }
+}
+
+/// Where a game stands, as listed by
+/// [`LobbyService::list_active_games`](super::LobbyService::list_active_games).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GamePhase {
+    /// No hand has been commenced, or the last one has completed.
+    Waiting,
+    /// Phase of the hand being played.
+    InHand(EventPhase),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameSummary {
+    pub game_id: GameId,
+    pub name: String,
+    pub seated: usize,
+    pub phase: GamePhase,
+    /// Chips committed to the current hand so far, including bets on the current street.
+    pub pot: Chips,
 }
 
 #[derive(Clone, Debug)]
@@ -217,6 +280,66 @@ pub struct ShufflerRegistrationConfig {
     pub sequence: Option<u16>,
 }
 
+/// Shuffler turn order for a game, mapping each shuffler to its turn index.
+///
+/// Entries are kept sorted by `(sequence, shuffler id)`, so the mapping is deterministic even
+/// when the registered sequences are not yet valid.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShufflerOrder {
+    entries: Vec<(u16, ShufflerId)>,
+}
+
+impl ShufflerOrder {
+    pub fn new(shufflers: impl IntoIterator<Item = (ShufflerId, u16)>) -> Self {
+        let mut entries: Vec<_> = shufflers
+            .into_iter()
+            .map(|(shuffler_id, sequence)| (sequence, shuffler_id))
+            .collect();
+        entries.sort_unstable();
+        Self { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Position of `shuffler_id` in the shuffle order.
+    pub fn turn_index(&self, shuffler_id: ShufflerId) -> Option<usize> {
+        self.entries.iter().position(|(_, id)| *id == shuffler_id)
+    }
+
+    /// The first sequence at or after `requested` that no shuffler holds yet.
+    pub fn next_available(&self, requested: u16) -> Result<u16, GameSetupError> {
+        let mut sequence = requested;
+        for (taken, _) in &self.entries {
+            if *taken == sequence {
+                sequence = sequence.checked_add(1).ok_or_else(|| {
+                    GameSetupError::validation(format!(
+                        "no free shuffler sequence at or after {requested}"
+                    ))
+                })?;
+            }
+        }
+        Ok(sequence)
+    }
+
+    /// Sequences must be exactly `0..n` for `n` shufflers: no gaps and no duplicates.
+    pub fn validate(&self) -> Result<(), GameSetupError> {
+        for (turn_index, (sequence, shuffler_id)) in self.entries.iter().enumerate() {
+            if usize::from(*sequence) != turn_index {
+                return Err(GameSetupError::validation(format!(
+                    "shuffler {shuffler_id} has sequence {sequence}, expected {turn_index}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RegisterShufflerOutput<C: CurveGroup> {
     pub shuffler: ShufflerRecord<C, Saved<ShufflerId>>,
@@ -227,12 +350,13 @@ pub struct RegisterShufflerOutput<C: CurveGroup> {
 #[derive(Clone)]
 pub struct CommenceGameParams {
     pub game_id: GameId,
-    pub hand_no: i64,
+    /// Hand number to record. If None, the next number in the game's sequence is allocated.
+    pub hand_no: Option<i64>,
     pub button_seat: SeatId,
     pub small_blind_seat: SeatId,
     pub big_blind_seat: SeatId,
     pub deck_commitment: Option<DeckCommitmentBytes>,
-    /// Player stacks for this hand. If None, uses buy-in amount (for first hand).
+    /// Player stacks for this hand. If None, uses each player's chosen buy-in (for first hand).
     /// For subsequent hands, caller must provide each player's current stack.
     pub player_stacks: Option<Vec<(PlayerId, Chips)>>,
 }
@@ -1,5 +1,9 @@
-use super::types::{GameLobbyConfig, PlayerSeatSnapshot, ShufflerAssignment};
-use crate::engine::nl::types::Chips;
+use super::types::{GameLobbyConfig, PlayerSeatSnapshot, ShufflerAssignment, ShufflerOrder};
+use crate::engine::nl::engine::EngineNL;
+use crate::engine::nl::errors::StateError;
+use crate::engine::nl::types::{
+    Chips, HandConfig, PlayerState, PlayerStatus, HOLDEM_HOLE_CARDS, OMAHA_HOLE_CARDS,
+};
 use crate::ledger::GameSetupError;
 use ark_ec::CurveGroup;
 use std::collections::HashSet;
@@ -20,37 +24,90 @@ pub fn validate_lobby_config(cfg: &GameLobbyConfig) -> Result<(), GameSetupError
             "min_players_to_start cannot exceed max_players",
         ));
     }
+    if cfg.min_shufflers < 1 {
+        return Err(GameSetupError::validation(
+            "min_shufflers must be at least 1",
+        ));
+    }
+    if cfg.max_bet_bb == Some(0) {
+        return Err(GameSetupError::validation(
+            "max_bet_bb must be greater than zero when set",
+        ));
+    }
     if cfg.rake_bps < 0 {
         return Err(GameSetupError::validation("rake_bps cannot be negative"));
     }
-    if cfg.buy_in == 0 {
+    if cfg.min_buy_in == 0 {
         return Err(GameSetupError::validation(
-            "buy_in must be greater than zero",
+            "min_buy_in must be greater than zero",
+        ));
+    }
+    if cfg.min_buy_in > cfg.max_buy_in {
+        return Err(GameSetupError::validation(
+            "min_buy_in cannot exceed max_buy_in",
         ));
     }
     Ok(())
 }
 
+/// Resolve a player's buy-in against the game's range, defaulting to `max_buy_in`.
+pub fn resolve_buy_in(
+    cfg: &GameLobbyConfig,
+    requested: Option<Chips>,
+) -> Result<Chips, GameSetupError> {
+    let buy_in = requested.unwrap_or(cfg.max_buy_in);
+    if buy_in < cfg.min_buy_in || buy_in > cfg.max_buy_in {
+        return Err(GameSetupError::InvalidBuyIn {
+            requested: buy_in,
+            min: cfg.min_buy_in,
+            max: cfg.max_buy_in,
+        });
+    }
+    Ok(buy_in)
+}
+
+/// Reject hand configs the ledger cannot finish: showdown only scores hold'em
+/// ([`HOLDEM_HOLE_CARDS`]) and Omaha ([`OMAHA_HOLE_CARDS`]) hands.
+pub fn ensure_supported_hole_cards(cfg: &HandConfig) -> Result<(), GameSetupError> {
+    if cfg.hole_cards != HOLDEM_HOLE_CARDS && cfg.hole_cards != OMAHA_HOLE_CARDS {
+        return Err(GameSetupError::validation(format!(
+            "{} hole cards per player is not supported; expected {HOLDEM_HOLE_CARDS} or \
+             {OMAHA_HOLE_CARDS}",
+            cfg.hole_cards
+        )));
+    }
+    Ok(())
+}
+
 pub fn ensure_unique_seats<C: CurveGroup>(
     players: &[PlayerSeatSnapshot<C>],
 ) -> Result<(), GameSetupError> {
     let mut seen = HashSet::new();
     for snapshot in players {
         if !seen.insert(snapshot.seat_id) {
-            return Err(GameSetupError::validation(
-                "duplicate seat assignments detected",
-            ));
+            return Err(GameSetupError::SeatTaken {
+                seat: snapshot.seat_id,
+            });
         }
     }
     Ok(())
 }
 
+/// Seated players needed to start a hand; never fewer than three.
+pub fn required_players(min_players: i16) -> usize {
+    min_players.max(3) as usize
+}
+
+/// Registered shufflers needed to start a hand; never fewer than one.
+pub fn required_shufflers(min_shufflers: i16) -> usize {
+    min_shufflers.max(1) as usize
+}
+
 pub fn ensure_min_players<C: CurveGroup>(
     min_players: i16,
     players: &[PlayerSeatSnapshot<C>],
 ) -> Result<(), GameSetupError> {
-    let min_required = if min_players < 3 { 3 } else { min_players } as usize;
-    if players.len() < min_required {
+    if players.len() < required_players(min_players) {
         return Err(GameSetupError::validation(
             "not enough players to start the hand",
         ));
@@ -59,21 +116,34 @@ pub fn ensure_min_players<C: CurveGroup>(
 }
 
 pub fn ensure_shuffler_sequence<C: ark_ec::CurveGroup>(
+    min_shufflers: i16,
     shufflers: &[ShufflerAssignment<C>],
 ) -> Result<(), GameSetupError> {
-    if shufflers.is_empty() {
+    let need = required_shufflers(min_shufflers);
+    if shufflers.len() < need {
+        return Err(GameSetupError::InsufficientShufflers {
+            have: shufflers.len(),
+            need,
+        });
+    }
+    ShufflerOrder::new(
+        shufflers
+            .iter()
+            .map(|assignment| (assignment.shuffler.state.id, assignment.sequence)),
+    )
+    .validate()
+}
+
+/// Reject an aggregated shuffler key at the identity: every deck encrypted under it would be
+/// readable without any shuffler's secret.
+pub fn ensure_aggregated_key<C: CurveGroup>(
+    aggregated_public_key: &C,
+) -> Result<(), GameSetupError> {
+    if aggregated_public_key.is_zero() {
         return Err(GameSetupError::validation(
-            "at least one shuffler is required",
+            "aggregated shuffler key is the identity point",
         ));
     }
-    let mut seen = HashSet::new();
-    for assignment in shufflers {
-        if !seen.insert(assignment.sequence) {
-            return Err(GameSetupError::validation(
-                "duplicate shuffler sequence detected",
-            ));
-        }
-    }
     Ok(())
 }
 
@@ -91,27 +161,28 @@ pub fn ensure_buy_in<C: CurveGroup>(
     Ok(())
 }
 
-pub fn validate_blind_positions(
-    button: u8,
-    small_blind_seat: u8,
-    big_blind_seat: u8,
+/// Check the hand's blind positions against the seated players with
+/// [`EngineNL::validate_blind_config`], the same rule the betting round is started with.
+pub fn ensure_blind_config<C: CurveGroup>(
+    cfg: &HandConfig,
+    players: &[PlayerSeatSnapshot<C>],
 ) -> Result<(), GameSetupError> {
-    if button == small_blind_seat {
-        return Err(GameSetupError::validation(
-            "button and small blind cannot be the same seat",
-        ));
-    }
-    if button == big_blind_seat {
-        return Err(GameSetupError::validation(
-            "button and big blind cannot be the same seat",
-        ));
-    }
-    if small_blind_seat == big_blind_seat {
-        return Err(GameSetupError::validation(
-            "small blind and big blind cannot be the same seat",
-        ));
-    }
-    Ok(())
+    let seated: Vec<PlayerState> = players
+        .iter()
+        .map(|snapshot| PlayerState {
+            seat: snapshot.seat_id,
+            player_id: None,
+            stack: snapshot.starting_stack,
+            committed_this_round: 0,
+            committed_total: 0,
+            status: PlayerStatus::Active,
+            has_acted_this_round: false,
+        })
+        .collect();
+    EngineNL::validate_blind_config(cfg, &seated).map_err(|err| match err {
+        StateError::InvalidBlindConfig(reason) => GameSetupError::validation(reason),
+        other => GameSetupError::validation(format!("invalid blind configuration: {other:?}")),
+    })
 }
 
 // NOTE: validate_commence_params has been removed.
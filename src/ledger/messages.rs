@@ -9,8 +9,9 @@ use std::marker::PhantomData;
 
 use crate::chaum_pedersen::ChaumPedersenProof;
 use crate::engine::nl::actions::PlayerBetAction;
+use crate::engine::nl::types::SeatId;
 use crate::ledger::actor::AnyActor;
-use crate::ledger::{GameActor, GameId, HandId, PlayerActor, ShufflerActor};
+use crate::ledger::{CanonicalKey, GameActor, GameId, HandId, PlayerActor, ShufflerActor};
 use crate::shuffling::data_structures::{ElGamalCiphertext, ShuffleProof, DECK_SIZE};
 use crate::shuffling::player_decryption::{
     PartialUnblindingShare, PlayerAccessibleCiphertext, PlayerTargetedBlindingContribution,
@@ -22,7 +23,7 @@ use super::snapshot::phases::{
     HandPhase, PhaseBetting, PhaseDealing, PhaseShowdown, PhaseShuffling,
 };
 use super::snapshot::{SnapshotSeq, SnapshotStatus};
-use super::types::{EventPhase, HandStatus, SignatureBytes};
+use super::types::{CorrelationId, EventId, EventPhase, HandStatus, SignatureBytes};
 
 pub trait Street: Clone + Default + Serialize + DeserializeOwned {
     fn status() -> HandStatus;
@@ -300,6 +301,12 @@ where
     pub deck_out: [ElGamalCiphertext<C>; DECK_SIZE],
     pub proof: ShuffleProof<C>,
     pub _curve: PhantomData<C>,
+    /// Public randomness beacon round the VRF nonce was taken from, if any.
+    #[serde(default)]
+    pub beacon_round: Option<u64>,
+    /// Reveal of the VRF nonce this shuffler committed to before its turn.
+    #[serde(default, with = "crate::crypto_serde::field_option")]
+    pub vrf_nonce: Option<C::BaseField>,
 }
 
 impl<C> DomainSeparated for GameShuffleMessage<C>
@@ -328,6 +335,58 @@ where
             deck_out,
             proof,
             _curve: PhantomData,
+            beacon_round: None,
+            vrf_nonce: None,
+        }
+    }
+
+    /// Record the beacon round the VRF nonce for this shuffle was drawn from.
+    pub fn with_beacon_round(mut self, round: u64) -> Self {
+        self.beacon_round = Some(round);
+        self
+    }
+
+    /// Reveal the committed VRF nonce used for this shuffle.
+    pub fn with_vrf_nonce(mut self, nonce: C::BaseField) -> Self {
+        self.vrf_nonce = Some(nonce);
+        self
+    }
+}
+
+/// A shuffler's `Poseidon(nonce)` commitment. Every shuffler publishes one before the first
+/// shuffle of the hand is accepted.
+#[derive(Debug, Clone, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
+#[serde(bound(
+    serialize = "C: CanonicalSerialize, C::BaseField: CanonicalSerialize",
+    deserialize = "C: CanonicalDeserialize, C::BaseField: CanonicalDeserialize"
+))]
+pub struct GameNonceCommitmentMessage<C>
+where
+    C: CurveGroup,
+{
+    #[serde(with = "crate::crypto_serde::field")]
+    pub commitment: C::BaseField,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C> DomainSeparated for GameNonceCommitmentMessage<C>
+where
+    C: CurveGroup,
+{
+    fn domain_string() -> &'static str {
+        "ledger/game_nonce_commitment_message_v1"
+    }
+}
+
+impl<C> GameNonceCommitmentMessage<C>
+where
+    C: CurveGroup,
+{
+    #[inline]
+    pub fn new(commitment: C::BaseField) -> Self {
+        Self {
+            commitment,
+            _curve: PhantomData,
         }
     }
 }
@@ -429,9 +488,11 @@ pub struct GameShowdownMessage<C>
 where
     C: CurveGroup,
 {
-    pub chaum_pedersen_proofs: [ChaumPedersenProof<C>; 2],
-    pub card_in_deck_position: [u8; 2],
-    pub hole_ciphertexts: [PlayerAccessibleCiphertext<C>; 2],
+    /// One proof, deck position and ciphertext per hole card, in hole-index order; the hand
+    /// config's `hole_cards` fixes how many.
+    pub chaum_pedersen_proofs: Vec<ChaumPedersenProof<C>>,
+    pub card_in_deck_position: Vec<u8>,
+    pub hole_ciphertexts: Vec<PlayerAccessibleCiphertext<C>>,
     pub _curve: PhantomData<C>,
 }
 
@@ -450,9 +511,9 @@ where
 {
     #[inline]
     pub fn new(
-        chaum_pedersen_proofs: [ChaumPedersenProof<C>; 2],
-        card_in_deck_position: [u8; 2],
-        hole_ciphertexts: [PlayerAccessibleCiphertext<C>; 2],
+        chaum_pedersen_proofs: Vec<ChaumPedersenProof<C>>,
+        card_in_deck_position: Vec<u8>,
+        hole_ciphertexts: Vec<PlayerAccessibleCiphertext<C>>,
     ) -> Self {
         Self {
             chaum_pedersen_proofs,
@@ -463,6 +524,68 @@ where
     }
 }
 
+/// A player's statement that they decline to show their hole cards at showdown.
+///
+/// Submitted through the ledger like any other showdown message; the envelope signature is kept
+/// in the showdown snapshot, so a mucked hand cannot later be claimed as a winner.
+#[derive(Debug, Clone, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
+#[serde(bound(
+    serialize = "C: CanonicalSerialize",
+    deserialize = "C: CanonicalDeserialize"
+))]
+pub struct MuckDeclaration<C>
+where
+    C: CurveGroup,
+{
+    pub game_id: GameId,
+    pub hand_id: HandId,
+    pub seat: SeatId,
+    pub player_key: CanonicalKey<C>,
+}
+
+impl<C> DomainSeparated for MuckDeclaration<C>
+where
+    C: CurveGroup,
+{
+    fn domain_string() -> &'static str {
+        "ledger/muck_declaration_v1"
+    }
+}
+
+pub type SignedMuckDeclaration<C> = WithSignature<SignatureBytes, MuckDeclaration<C>>;
+
+/// A returning player's request for their seat's view of a hand in progress.
+///
+/// Signed by the seated player, so only the holder of that seat's key can recover its hole
+/// ciphertexts after a disconnect. `nonce` must exceed every nonce the server already accepted
+/// for the seat in this hand, so a captured request cannot be replayed.
+#[derive(Debug, Clone, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
+#[serde(bound(
+    serialize = "C: CanonicalSerialize",
+    deserialize = "C: CanonicalDeserialize"
+))]
+pub struct ReconnectRequest<C>
+where
+    C: CurveGroup,
+{
+    pub game_id: GameId,
+    pub hand_id: HandId,
+    pub seat: SeatId,
+    pub player_key: CanonicalKey<C>,
+    pub nonce: u64,
+}
+
+impl<C> DomainSeparated for ReconnectRequest<C>
+where
+    C: CurveGroup,
+{
+    fn domain_string() -> &'static str {
+        "ledger/reconnect_request_v1"
+    }
+}
+
+pub type SignedReconnectRequest<C> = WithSignature<SignatureBytes, ReconnectRequest<C>>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[serde(bound(
@@ -481,6 +604,8 @@ where
     PlayerTurn(GamePlayerMessage<TurnStreet, C>),
     PlayerRiver(GamePlayerMessage<RiverStreet, C>),
     Showdown(GameShowdownMessage<C>),
+    NonceCommitment(GameNonceCommitmentMessage<C>),
+    Muck(MuckDeclaration<C>),
 }
 
 impl<C> DomainSeparated for AnyGameMessage<C>
@@ -534,6 +659,14 @@ where
                 7u8.serialize_with_mode(&mut writer, compress)?;
                 msg.serialize_with_mode(&mut writer, compress)?;
             }
+            AnyGameMessage::NonceCommitment(msg) => {
+                8u8.serialize_with_mode(&mut writer, compress)?;
+                msg.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyGameMessage::Muck(msg) => {
+                9u8.serialize_with_mode(&mut writer, compress)?;
+                msg.serialize_with_mode(&mut writer, compress)?;
+            }
         }
         Ok(())
     }
@@ -548,6 +681,8 @@ where
             AnyGameMessage::PlayerTurn(msg) => msg.serialized_size(compress),
             AnyGameMessage::PlayerRiver(msg) => msg.serialized_size(compress),
             AnyGameMessage::Showdown(msg) => msg.serialized_size(compress),
+            AnyGameMessage::NonceCommitment(msg) => msg.serialized_size(compress),
+            AnyGameMessage::Muck(msg) => msg.serialized_size(compress),
         }
     }
 }
@@ -595,6 +730,12 @@ where
             7 => Ok(AnyGameMessage::Showdown(
                 GameShowdownMessage::deserialize_with_mode(&mut reader, compress, validate)?,
             )),
+            8 => Ok(AnyGameMessage::NonceCommitment(
+                GameNonceCommitmentMessage::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            9 => Ok(AnyGameMessage::Muck(
+                MuckDeclaration::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
             _ => Err(ark_serialize::SerializationError::InvalidData),
         }
     }
@@ -623,6 +764,36 @@ where
             AnyGameMessage::PlayerTurn(_) => EventPhase::Betting,
             AnyGameMessage::PlayerRiver(_) => EventPhase::Betting,
             AnyGameMessage::Showdown(_) => EventPhase::Showdown,
+            AnyGameMessage::NonceCommitment(_) => EventPhase::Shuffling,
+            AnyGameMessage::Muck(_) => EventPhase::Showdown,
+        }
+    }
+
+    /// Whether the message carries a share addressed to one player's hole card. Together these
+    /// shares rebuild the hole ciphertexts, so they must not reach spectators.
+    pub fn is_player_targeted(&self) -> bool {
+        matches!(
+            self,
+            AnyGameMessage::Blinding(_) | AnyGameMessage::PartialUnblinding(_)
+        )
+    }
+
+    /// Bytes the sender signed: the signing bytes of the wrapped message under its own domain,
+    /// as produced by [`sign_enveloped_action`]. Signatures are checked against these rather
+    /// than the `ledger/message_v1` bytes of the wrapper.
+    pub fn signed_bytes(&self) -> Result<Vec<u8>> {
+        use crate::signing::signing_bytes;
+        match self {
+            AnyGameMessage::Shuffle(msg) => signing_bytes(msg),
+            AnyGameMessage::Blinding(msg) => signing_bytes(msg),
+            AnyGameMessage::PartialUnblinding(msg) => signing_bytes(msg),
+            AnyGameMessage::PlayerPreflop(msg) => signing_bytes(msg),
+            AnyGameMessage::PlayerFlop(msg) => signing_bytes(msg),
+            AnyGameMessage::PlayerTurn(msg) => signing_bytes(msg),
+            AnyGameMessage::PlayerRiver(msg) => signing_bytes(msg),
+            AnyGameMessage::Showdown(msg) => signing_bytes(msg),
+            AnyGameMessage::NonceCommitment(msg) => signing_bytes(msg),
+            AnyGameMessage::Muck(msg) => signing_bytes(msg),
         }
     }
 }
@@ -636,6 +807,24 @@ where
     }
 }
 
+impl<C> From<GameNonceCommitmentMessage<C>> for AnyGameMessage<C>
+where
+    C: CurveGroup,
+{
+    fn from(message: GameNonceCommitmentMessage<C>) -> Self {
+        AnyGameMessage::NonceCommitment(message)
+    }
+}
+
+impl<C> From<MuckDeclaration<C>> for AnyGameMessage<C>
+where
+    C: CurveGroup,
+{
+    fn from(message: MuckDeclaration<C>) -> Self {
+        AnyGameMessage::Muck(message)
+    }
+}
+
 impl<C> From<GameBlindingDecryptionMessage<C>> for AnyGameMessage<C>
 where
     C: CurveGroup,
@@ -673,7 +862,7 @@ where
     pub message: WithSignature<SignatureBytes, M>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize"
@@ -689,6 +878,20 @@ where
     #[serde(with = "crate::crypto_serde::curve")]
     pub public_key: C,
     pub message: WithSignature<SignatureBytes, AnyGameMessage<C>>,
+    /// Id of the request that submitted this envelope; not covered by the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
+}
+
+impl<C> AnyMessageEnvelope<C>
+where
+    C: CurveGroup,
+{
+    /// Tag the envelope with the id of the request that submitted it.
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -706,6 +909,9 @@ where
     pub snapshot_sequence_id: SnapshotSeq,
     #[serde(with = "chrono::serde::ts_milliseconds")]
     pub created_timestamp: DateTime<Utc>,
+    /// Row id assigned by the event store; `None` until the event has been persisted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<EventId>,
 }
 
 impl<C> FinalizedAnyMessageEnvelope<C>
@@ -725,6 +931,7 @@ where
             applied_phase,
             snapshot_sequence_id,
             created_timestamp: Utc::now(),
+            event_id: None,
         }
     }
 
@@ -742,8 +949,90 @@ where
             applied_phase,
             snapshot_sequence_id,
             created_timestamp,
+            event_id: None,
         }
     }
+
+    /// Attach the id the event store assigned to this event.
+    pub fn with_event_id(mut self, event_id: EventId) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+}
+
+// `created_timestamp` is encoded as Unix milliseconds, matching its serde form.
+impl<C> CanonicalSerialize for FinalizedAnyMessageEnvelope<C>
+where
+    C: CurveGroup,
+{
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        self.envelope.serialize_with_mode(&mut writer, compress)?;
+        self.snapshot_status
+            .serialize_with_mode(&mut writer, compress)?;
+        self.applied_phase
+            .serialize_with_mode(&mut writer, compress)?;
+        self.snapshot_sequence_id
+            .serialize_with_mode(&mut writer, compress)?;
+        self.created_timestamp
+            .timestamp_millis()
+            .serialize_with_mode(&mut writer, compress)?;
+        self.event_id.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        self.envelope.serialized_size(compress)
+            + self.snapshot_status.serialized_size(compress)
+            + self.applied_phase.serialized_size(compress)
+            + self.snapshot_sequence_id.serialized_size(compress)
+            + self
+                .created_timestamp
+                .timestamp_millis()
+                .serialized_size(compress)
+            + self.event_id.serialized_size(compress)
+    }
+}
+
+impl<C> CanonicalDeserialize for FinalizedAnyMessageEnvelope<C>
+where
+    C: CurveGroup,
+{
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let envelope = AnyMessageEnvelope::deserialize_with_mode(&mut reader, compress, validate)?;
+        let snapshot_status =
+            SnapshotStatus::deserialize_with_mode(&mut reader, compress, validate)?;
+        let applied_phase = EventPhase::deserialize_with_mode(&mut reader, compress, validate)?;
+        let snapshot_sequence_id =
+            SnapshotSeq::deserialize_with_mode(&mut reader, compress, validate)?;
+        let millis = i64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let created_timestamp = DateTime::from_timestamp_millis(millis)
+            .ok_or(ark_serialize::SerializationError::InvalidData)?;
+        let event_id = Option::<EventId>::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self {
+            envelope,
+            snapshot_status,
+            applied_phase,
+            snapshot_sequence_id,
+            created_timestamp,
+            event_id,
+        })
+    }
+}
+
+impl<C> ark_serialize::Valid for FinalizedAnyMessageEnvelope<C>
+where
+    C: CurveGroup,
+{
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        self.envelope.check()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -803,6 +1092,11 @@ impl<C: CurveGroup> GameMessage<C> for GameShuffleMessage<C> {
     type Actor = ShufflerActor<C>;
 }
 
+impl<C: CurveGroup> GameMessage<C> for GameNonceCommitmentMessage<C> {
+    type Phase = PhaseShuffling;
+    type Actor = ShufflerActor<C>;
+}
+
 impl<C: CurveGroup> GameMessage<C> for GameBlindingDecryptionMessage<C> {
     type Phase = PhaseDealing;
     type Actor = ShufflerActor<C>;
@@ -838,6 +1132,11 @@ impl<C: CurveGroup> GameMessage<C> for GameShowdownMessage<C> {
     type Actor = PlayerActor<C>;
 }
 
+impl<C: CurveGroup> GameMessage<C> for MuckDeclaration<C> {
+    type Phase = PhaseShowdown;
+    type Actor = PlayerActor<C>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -896,9 +1195,9 @@ mod tests {
                 PlayerBetAction::RaiseTo { to: 64 },
             )),
             AnyGameMessage::Showdown(GameShowdownMessage::new(
-                [sample_cp_proof(), sample_cp_proof()],
-                [5u8, 6],
-                [
+                vec![sample_cp_proof(), sample_cp_proof()],
+                vec![5u8, 6],
+                vec![
                     sample_accessible_ciphertext(),
                     sample_accessible_ciphertext(),
                 ],
@@ -932,6 +1231,16 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn only_blinding_and_unblinding_shares_are_player_targeted() {
+        let targeted: Vec<_> = sample_ledger_messages()
+            .iter()
+            .filter(|message| message.is_player_targeted())
+            .map(|message| message.phase())
+            .collect();
+        assert_eq!(targeted, vec![EventPhase::Dealing, EventPhase::Dealing]);
+    }
+
     #[test]
     fn base_messages_have_canonical_transcripts() -> Result<()> {
         sign_and_verify(GameShuffleMessage::<GrumpkinProjective>::new(
@@ -953,9 +1262,9 @@ mod tests {
             ),
         )?;
         sign_and_verify(GameShowdownMessage::<GrumpkinProjective>::new(
-            [sample_cp_proof(), sample_cp_proof()],
-            [14, 15],
-            [
+            vec![sample_cp_proof(), sample_cp_proof()],
+            vec![14, 15],
+            vec![
                 sample_accessible_ciphertext(),
                 sample_accessible_ciphertext(),
             ],
@@ -996,9 +1305,9 @@ mod tests {
             ),
         );
         assert_round_trip_json(&GameShowdownMessage::<GrumpkinProjective>::new(
-            [sample_cp_proof(), sample_cp_proof()],
-            [7, 8],
-            [
+            vec![sample_cp_proof(), sample_cp_proof()],
+            vec![7, 8],
+            vec![
                 sample_accessible_ciphertext(),
                 sample_accessible_ciphertext(),
             ],
@@ -1056,6 +1365,7 @@ mod tests {
                 value: any_message,
                 signature: vec![4, 5, 6],
             },
+            correlation_id: None,
         };
         assert_round_trip_json(&envelope);
 
@@ -1160,6 +1470,41 @@ where
     }
 }
 
+impl<C> TryFrom<&AnyMessageEnvelope<C>> for EnvelopedMessage<C, GameNonceCommitmentMessage<C>>
+where
+    C: CurveGroup,
+{
+    type Error = anyhow::Error;
+
+    fn try_from(envelope: &AnyMessageEnvelope<C>) -> Result<Self> {
+        let actor = match &envelope.actor {
+            AnyActor::Shuffler {
+                shuffler_id,
+                shuffler_key,
+            } => ShufflerActor {
+                shuffler_id: *shuffler_id,
+                shuffler_key: shuffler_key.clone(),
+            },
+            _ => return Err(anyhow!("expected shuffler actor for nonce commitment")),
+        };
+
+        match &envelope.message.value {
+            AnyGameMessage::NonceCommitment(message) => Ok(EnvelopedMessage {
+                hand_id: envelope.hand_id,
+                game_id: envelope.game_id,
+                actor,
+                nonce: envelope.nonce,
+                public_key: envelope.public_key.clone(),
+                message: WithSignature {
+                    value: message.clone(),
+                    signature: envelope.message.signature.clone(),
+                },
+            }),
+            _ => Err(anyhow!("expected nonce commitment message variant")),
+        }
+    }
+}
+
 impl<C> TryFrom<&AnyMessageEnvelope<C>> for EnvelopedMessage<C, GameBlindingDecryptionMessage<C>>
 where
     C: CurveGroup,
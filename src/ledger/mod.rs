@@ -7,6 +7,7 @@ pub mod messages;
 mod operator;
 pub mod player_hole_card;
 pub mod query;
+pub mod result;
 pub mod serialization;
 pub mod snapshot;
 pub mod state;
@@ -29,27 +30,29 @@ pub use lobby::storage::{
     StoredPlayer, StoredShuffler,
 };
 pub use lobby::{
-    CommenceGameOutcome, CommenceGameParams, GameLobbyConfig, GameMetadata, GameSetupError,
-    JoinGameOutput, LobbyService, LobbyServiceFactory, RegisterShufflerOutput,
-    ShufflerRegistrationConfig,
+    CommenceGameOutcome, CommenceGameParams, GameLobbyConfig, GameMetadata, GamePhase,
+    GameSetupError, GameSummary, JoinGameOutput, LobbyService, LobbyServiceFactory,
+    RegisterShufflerOutput, ShufflerRegistrationConfig,
 };
 pub use messages::{
     AnyGameMessage, EnvelopedMessage, FlopStreet, GameBlindingDecryptionMessage,
     GamePartialUnblindingShareMessage, GamePlayerMessage, GameShowdownMessage, GameShuffleMessage,
-    PreflopStreet, RiverStreet, Street, TurnStreet,
+    MuckDeclaration, PreflopStreet, ReconnectRequest, RiverStreet, SignedMuckDeclaration,
+    SignedReconnectRequest, Street, TurnStreet,
 };
 pub use operator::LedgerOperator;
 pub use player_hole_card::PlayerHoleCard;
+pub use result::{hand_result_hash, HandResult, PotResult};
 pub use snapshot::{
     AnyTableSnapshot, TableAtComplete, TableAtDealing, TableAtFlop, TableAtPreflop, TableAtRiver,
     TableAtShowdown, TableAtShuffling, TableAtTurn, TableSnapshot,
 };
-pub use state::LedgerState;
+pub use state::{LedgerState, PendingWork, UpsertOutcome};
 pub use store::{EventStore, SeaOrmEventStore, SeaOrmSnapshotStore, SnapshotStore};
 pub use types::{
-    EntityKind, GameId, HandId, HandStatus, NonceKey, PublicKeyBytes, ShufflerId, SignatureBytes,
-    StateHash,
+    CorrelationId, EntityKind, GameId, HandId, HandStatus, NonceKey, PublicKeyBytes, ShufflerId,
+    SignatureBytes, StateHash,
 };
 pub use typestate::{DbRowStatus, MaybeSaved, NotSaved, Saved};
-pub use verifier::{LedgerVerifier, Verifier, VerifyError};
+pub use verifier::{LedgerVerifier, SchnorrSignatureValidator, Verifier, VerifyError};
 pub use worker::{LedgerWorker, StagingLedgerUpdate, WorkerError};
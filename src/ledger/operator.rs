@@ -1,5 +1,7 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::{bail, Context};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
@@ -7,8 +9,9 @@ use ark_ff::PrimeField;
 use super::messages::{AnyMessageEnvelope, FinalizedAnyMessageEnvelope};
 use super::snapshot::{AnyTableSnapshot, Shared};
 use super::state::LedgerState;
-use super::store::EventStore;
-use super::types::HandId;
+use super::store::archive;
+use super::store::{ArchivedHand, EventStore};
+use super::types::{CorrelationId, HandId};
 use super::verifier::{Verifier, VerifyError};
 use super::worker::StagingLedgerUpdate;
 use super::worker::{LedgerWorker, WorkerError};
@@ -16,7 +19,7 @@ use crate::curve_absorb::CurveAbsorb;
 use crate::tokio_tools::spawn_named_task;
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
-use tracing::{error, info, instrument, Span};
+use tracing::{error, info, instrument, warn, Span};
 
 const LOG_TARGET: &str = "legit_poker::ledger::operator";
 
@@ -30,7 +33,6 @@ where
 {
     verifier: Arc<dyn Verifier<C> + Send + Sync>,
     sender: mpsc::Sender<AnyMessageEnvelope<C>>,
-    #[allow(dead_code)]
     event_store: Arc<dyn EventStore<C>>,
     state: Arc<LedgerState<C>>,
     events_tx: broadcast::Sender<FinalizedAnyMessageEnvelope<C>>,
@@ -72,6 +74,7 @@ where
     ) -> anyhow::Result<JoinHandle<Result<(), WorkerError>>> {
         // let events = self.event_store.load_all_events().await?;
         // self.state.replay(events)?;
+        let worker = worker.with_verifier(Arc::clone(&self.verifier));
         let handle = spawn_named_task("ledger-worker", async move {
             let result = worker.run().await;
             if let Err(err) = &result {
@@ -83,30 +86,99 @@ where
     }
 
     /// Entry point for API submissions: verify and enqueue an action envelope.
+    ///
+    /// Envelopes without a correlation id are tagged with the id of the request being handled
+    /// (see [`CorrelationId::current`]), so the persisted event can be traced back to it.
     #[instrument(
         skip(self, envelope),
         level = "info",
         target = LOG_TARGET,
-        fields(hand_id = %hand_id, nonce = tracing::field::Empty)
+        fields(
+            hand_id = %hand_id,
+            nonce = tracing::field::Empty,
+            correlation_id = tracing::field::Empty
+        )
     )]
     pub async fn submit(
         &self,
         hand_id: HandId,
         envelope: AnyMessageEnvelope<C>,
     ) -> Result<(), VerifyError> {
+        let envelope = match (&envelope.correlation_id, CorrelationId::current()) {
+            (None, Some(correlation_id)) => envelope.with_correlation_id(correlation_id),
+            _ => envelope,
+        };
         let nonce = envelope.nonce;
         Span::current().record("nonce", &nonce);
+        if let Some(correlation_id) = &envelope.correlation_id {
+            Span::current().record("correlation_id", correlation_id.as_str());
+        }
         info!(target: LOG_TARGET, "verifying envelope");
-        let verified = self.verifier.verify(hand_id, envelope)?;
+        let verified = match self.verifier.verify(hand_id, envelope) {
+            Ok(verified) => verified,
+            Err(VerifyError::Duplicate) => {
+                info!(target: LOG_TARGET, "envelope already accepted; acknowledging retry");
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
         info!(target: LOG_TARGET, "enqueueing verified envelope");
-        self.sender.send(verified).await.map_err(|_| {
+        if let Err(err) = self.sender.send(verified).await {
             error!(target: LOG_TARGET, "failed to enqueue verified envelope");
-            VerifyError::InvalidMessage
-        })?;
+            self.verifier.release(&err.0);
+            return Err(VerifyError::InvalidMessage);
+        }
         info!(target: LOG_TARGET, "enqueued verified envelope");
         Ok(())
     }
 
+    /// Move a completed hand's events and snapshots to the archive tables and drop it from the
+    /// in-memory ledger. Fails if the hand is not loaded or its tip is not `Complete`.
+    pub async fn archive_hand(&self, hand_id: HandId) -> anyhow::Result<()> {
+        let (_, tip) = self
+            .state
+            .tip_snapshot(hand_id)
+            .with_context(|| format!("hand {hand_id} is not loaded"))?;
+        if !matches!(tip, AnyTableSnapshot::Complete(_)) {
+            bail!("hand {hand_id} is not complete and cannot be archived");
+        }
+
+        archive::archive_hand(self.event_store.connection(), hand_id).await?;
+        self.state.remove_hand(hand_id);
+        Ok(())
+    }
+
+    /// Archive every loaded hand that is complete and was created more than `older_than` ago,
+    /// returning the archived hand ids. A hand that fails to archive is logged and skipped.
+    pub async fn archive_completed_hands(
+        &self,
+        older_than: Duration,
+    ) -> anyhow::Result<Vec<HandId>> {
+        let conn = self.event_store.connection();
+        let mut archived = Vec::new();
+        for hand_id in self.state.hands() {
+            let complete = matches!(
+                self.state.tip_snapshot(hand_id),
+                Some((_, AnyTableSnapshot::Complete(_)))
+            );
+            if !complete || !archive::hand_older_than(conn, hand_id, older_than).await? {
+                continue;
+            }
+            match self.archive_hand(hand_id).await {
+                Ok(()) => archived.push(hand_id),
+                Err(err) => {
+                    warn!(target: LOG_TARGET, hand_id, error = ?err, "failed to archive hand")
+                }
+            }
+        }
+        Ok(archived)
+    }
+
+    /// Load a hand back from the archive tables. Slower than reading a live hand.
+    pub async fn load_archived_hand(&self, hand_id: HandId) -> anyhow::Result<ArchivedHand<C>> {
+        archive::load_archived_hand(self.event_store.connection(), hand_id).await
+    }
+
     pub fn state(&self) -> Arc<LedgerState<C>> {
         self.state.clone()
     }
@@ -142,15 +214,23 @@ mod tests {
     use crate::db::{connect_to_postgres_db, postgres_test_url};
     use crate::engine::nl::actions::PlayerBetAction;
     use crate::ledger::actor::AnyActor;
-    use crate::ledger::messages::{AnyGameMessage, GamePlayerMessage, PreflopStreet};
+    use crate::ledger::messages::{
+        AnyGameMessage, GamePlayerMessage, GameShuffleMessage, PreflopStreet,
+    };
+    use crate::ledger::snapshot::SnapshotStatus;
     use crate::ledger::store::{SeaOrmEventStore, SeaOrmSnapshotStore, SnapshotStore};
-    use crate::ledger::{GameId, HandId};
+    use crate::ledger::test_support::{
+        fixture_committed_shuffling_snapshot, fixture_vrf_nonce, FixtureContext,
+    };
+    use crate::ledger::{CorrelationId, GameId, HandId};
+    use crate::shuffling::mock_proof::MockShufflingProof;
     use crate::signing::WithSignature;
     use ark_bn254::G1Projective as Curve;
     use ark_ff::Zero;
     use sea_orm::{ConnectionTrait, DbBackend, Statement};
     use std::sync::Arc;
     use tokio::sync::{broadcast, mpsc};
+    use tokio::time::{timeout, Duration};
 
     fn sample_verified_envelope(nonce: u64) -> AnyMessageEnvelope<Curve> {
         let message = AnyGameMessage::PlayerPreflop(
@@ -167,6 +247,7 @@ mod tests {
                 value: message,
                 signature: Vec::new(),
             },
+            correlation_id: None,
         }
     }
 
@@ -211,6 +292,18 @@ mod tests {
         }
     }
 
+    struct PassThroughVerifier;
+
+    impl Verifier<Curve> for PassThroughVerifier {
+        fn verify(
+            &self,
+            _hand_id: HandId,
+            envelope: AnyMessageEnvelope<Curve>,
+        ) -> Result<AnyMessageEnvelope<Curve>, VerifyError> {
+            Ok(envelope)
+        }
+    }
+
     #[tokio::test]
     async fn operator_can_be_constructed() {
         let (tx, _rx) = mpsc::channel(4);
@@ -305,4 +398,117 @@ mod tests {
         let received = rx.recv().await.expect("message enqueued");
         assert_eq!(received.nonce, 0);
     }
+
+    #[tokio::test]
+    async fn submit_keeps_the_request_correlation_id() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let operator = LedgerOperator::new(
+            Arc::new(PassThroughVerifier),
+            tx,
+            store,
+            state,
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+        let correlation_id = CorrelationId::generate();
+        let envelope = sample_verified_envelope(3).with_correlation_id(correlation_id.clone());
+        operator.submit(0, envelope).await.unwrap();
+        let received = rx.recv().await.expect("message enqueued");
+        assert_eq!(received.correlation_id, Some(correlation_id));
+    }
+
+    #[tokio::test]
+    async fn mock_invalid_shuffle_records_failure_snapshot() {
+        let (tx, rx) = mpsc::channel(4);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let snapshot_store: Arc<dyn SnapshotStore<Curve>> =
+            Arc::new(SeaOrmSnapshotStore::new(store.connection.clone()));
+        let event_store_trait: Arc<dyn EventStore<Curve>> =
+            Arc::clone(&store) as Arc<dyn EventStore<Curve>>;
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let snapshot = fixture_committed_shuffling_snapshot(&ctx);
+        let deck_in = snapshot.shuffling.final_deck.clone();
+        let state = Arc::new(LedgerState::<Curve>::new());
+        state.upsert_snapshot(ctx.hand_id, AnyTableSnapshot::Shuffling(snapshot), true);
+
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let operator = LedgerOperator::new(
+            Arc::new(PassThroughVerifier),
+            tx,
+            Arc::clone(&event_store_trait),
+            Arc::clone(&state),
+            events_tx.clone(),
+            snapshots_tx.clone(),
+            staging_tx.clone(),
+        );
+        let worker = LedgerWorker::new(
+            rx,
+            event_store_trait,
+            snapshot_store,
+            Arc::clone(&state),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+        let mut staging = operator.staging_updates();
+        let handle = operator.start(worker).await.unwrap();
+
+        let shuffler_key = ctx.shuffler_keys.get(&10).expect("shuffler key").clone();
+        let public_key = ctx
+            .shufflers
+            .get(&shuffler_key)
+            .expect("shuffler identity")
+            .public_key;
+        let message = GameShuffleMessage::new(
+            deck_in.clone(),
+            deck_in.clone(),
+            MockShufflingProof::invalid(&deck_in, &deck_in),
+            0,
+        )
+        .with_vrf_nonce(fixture_vrf_nonce(10));
+        let envelope = AnyMessageEnvelope {
+            hand_id: ctx.hand_id,
+            game_id: ctx.game_id,
+            actor: AnyActor::Shuffler {
+                shuffler_id: 10,
+                shuffler_key,
+            },
+            nonce: 0,
+            public_key,
+            message: WithSignature {
+                value: AnyGameMessage::Shuffle(message),
+                signature: Vec::new(),
+            },
+            correlation_id: None,
+        };
+        operator.submit(ctx.hand_id, envelope).await.unwrap();
+
+        let update = timeout(Duration::from_secs(5), staging.recv())
+            .await
+            .expect("staging update before timeout")
+            .expect("staging channel open");
+        assert!(matches!(
+            update.event.snapshot_status,
+            SnapshotStatus::Failure(_)
+        ));
+        assert!(matches!(
+            update.snapshot.status(),
+            SnapshotStatus::Failure(reason) if reason.contains("mock proof flagged invalid")
+        ));
+
+        handle.abort();
+    }
 }
@@ -3,8 +3,9 @@ use std::sync::Arc;
 use ark_ec::CurveGroup;
 use thiserror::Error;
 
+use super::snapshot_diff::SnapshotDiff;
 use crate::ledger::{
-    snapshot::AnyTableSnapshot,
+    snapshot::{redact_for_spectator, AnyTableSnapshot, SnapshotSeq},
     state::LedgerState,
     types::{GameId, HandId},
 };
@@ -56,6 +57,30 @@ where
 
         Ok(snapshot)
     }
+
+    /// Changes from the snapshot at `from_sequence` to the hand's tip. Both ends are redacted
+    /// for spectators first, so the diff never carries hole-card material.
+    pub fn diff_since(
+        &self,
+        hand_id: HandId,
+        from_sequence: SnapshotSeq,
+    ) -> Result<SnapshotDiff, LatestSnapshotError> {
+        let (_, tip) = self
+            .state
+            .tip_snapshot(hand_id)
+            .ok_or(LatestSnapshotError::HandNotFound { hand_id })?;
+        let from = self
+            .state
+            .snapshot_at_sequence(hand_id, from_sequence)
+            .ok_or(LatestSnapshotError::SequenceNotFound {
+                hand_id,
+                sequence: from_sequence,
+            })?;
+        Ok(SnapshotDiff::between(
+            &redact_for_spectator(&from),
+            &redact_for_spectator(&tip),
+        ))
+    }
 }
 
 #[derive(Debug, Error)]
@@ -72,9 +97,16 @@ pub enum LatestSnapshotError {
     MissingHandId { requested: HandId },
     #[error("latest snapshot hand id mismatch: requested {requested}, actual {actual}")]
     HandMismatch { requested: HandId, actual: HandId },
+    #[error("hand {hand_id} has no snapshot at sequence {sequence} on its current chain")]
+    SequenceNotFound {
+        hand_id: HandId,
+        sequence: SnapshotSeq,
+    },
 }
 
-fn snapshot_ids<C: CurveGroup>(snapshot: &AnyTableSnapshot<C>) -> (GameId, Option<HandId>) {
+pub(crate) fn snapshot_ids<C: CurveGroup>(
+    snapshot: &AnyTableSnapshot<C>,
+) -> (GameId, Option<HandId>) {
     match snapshot {
         AnyTableSnapshot::Shuffling(table) => (table.game_id, table.hand_id),
         AnyTableSnapshot::Dealing(table) => (table.game_id, table.hand_id),
@@ -90,8 +122,12 @@ fn snapshot_ids<C: CurveGroup>(snapshot: &AnyTableSnapshot<C>) -> (GameId, Optio
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::nl::types::Street;
     use crate::ledger::state::LedgerState;
-    use crate::ledger::test_support::{fixture_shuffling_snapshot, FixtureContext};
+    use crate::ledger::test_support::{
+        fixture_flop_snapshot, fixture_preflop_snapshot, fixture_shuffling_snapshot, FixtureContext,
+    };
+    use crate::ledger::types::{EventPhase, StateHash};
     use ark_bn254::G1Projective as Curve;
 
     #[test]
@@ -122,4 +158,49 @@ mod tests {
             _ => panic!("expected Shuffling snapshot"),
         }
     }
+
+    #[test]
+    fn diff_from_preflop_to_flop_carries_only_flop_changes() {
+        let ctx = FixtureContext::<Curve>::new(&[1, 2, 3], &[10, 11, 12]);
+        // Chain the fixtures as consecutive snapshots of one hand.
+        let mut preflop = fixture_preflop_snapshot(&ctx);
+        preflop.sequence = 4;
+        preflop.state_hash = StateHash::new([4; 32]);
+        let mut flop = fixture_flop_snapshot(&ctx);
+        flop.sequence = 5;
+        flop.previous_hash = Some(preflop.state_hash);
+        flop.state_hash = StateHash::new([5; 32]);
+
+        let state = Arc::new(LedgerState::with_hasher(Arc::clone(&ctx.hasher)));
+        state.upsert_snapshot(
+            ctx.hand_id,
+            AnyTableSnapshot::Preflop(preflop.clone()),
+            true,
+        );
+        state.upsert_snapshot(ctx.hand_id, AnyTableSnapshot::Flop(flop.clone()), true);
+
+        let query = LatestSnapshotQuery::new(Arc::clone(&state));
+        let diff = query.diff_since(ctx.hand_id, 4).expect("diff");
+
+        assert_eq!((diff.from_sequence, diff.to_sequence), (4, 5));
+        assert_eq!(diff.to_state_hash, flop.state_hash);
+        assert_eq!(diff.street, Some(Street::Flop));
+        let board: Vec<u8> = diff
+            .board_cards
+            .iter()
+            .map(|card| card.board_index)
+            .collect();
+        assert_eq!(board, vec![0, 1, 2]);
+        let cards: Vec<u8> = diff.board_cards.iter().map(|card| card.card).collect();
+        assert_eq!(cards, flop.reveals.board);
+        // Both snapshots are betting phases that succeeded, with no actions in between.
+        assert_eq!(diff.phase, None::<EventPhase>);
+        assert_eq!(diff.status, None);
+        assert!(diff.betting_events.is_empty());
+
+        assert!(matches!(
+            query.diff_since(ctx.hand_id, 3),
+            Err(LatestSnapshotError::SequenceNotFound { sequence: 3, .. })
+        ));
+    }
 }
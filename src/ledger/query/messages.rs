@@ -1,13 +1,19 @@
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
+use crate::ledger::store::event::message_type;
 use crate::ledger::{
     messages::FinalizedAnyMessageEnvelope, snapshot::SnapshotSeq, store::EventStore, types::HandId,
 };
 
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+pub const MAX_PAGE_LIMIT: usize = 500;
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct SequenceBounds {
     pub from: Option<SnapshotSeq>,
@@ -25,6 +31,77 @@ impl SequenceBounds {
     }
 }
 
+/// Position of the last message on a page; the next page resumes strictly after it.
+///
+/// Messages are ordered by `(snapshot_sequence_id, nonce)`, and since the log is append-only
+/// a cursor stays valid while new messages arrive. Rendered as `"{sequence}:{nonce}"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MessageCursor {
+    pub snapshot_sequence_id: SnapshotSeq,
+    pub nonce: u64,
+}
+
+impl MessageCursor {
+    pub fn of<C: CurveGroup>(event: &FinalizedAnyMessageEnvelope<C>) -> Self {
+        Self {
+            snapshot_sequence_id: event.snapshot_sequence_id,
+            nonce: event.envelope.nonce,
+        }
+    }
+}
+
+impl fmt::Display for MessageCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.snapshot_sequence_id, self.nonce)
+    }
+}
+
+impl FromStr for MessageCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (sequence, nonce) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("invalid cursor {s:?}"))?;
+        Ok(Self {
+            snapshot_sequence_id: sequence
+                .parse()
+                .map_err(|_| anyhow!("invalid cursor {s:?}"))?,
+            nonce: nonce.parse().map_err(|_| anyhow!("invalid cursor {s:?}"))?,
+        })
+    }
+}
+
+/// Which slice of a hand's messages to return.
+#[derive(Clone, Debug, Default)]
+pub struct MessagePageRequest {
+    /// Page size; defaults to [`DEFAULT_PAGE_LIMIT`] and may not exceed [`MAX_PAGE_LIMIT`].
+    pub limit: Option<usize>,
+    pub after: Option<MessageCursor>,
+    /// Only return messages whose stored `message_type` is listed.
+    pub types: Option<Vec<String>>,
+}
+
+impl MessagePageRequest {
+    /// Effective page size, rejecting zero or anything above [`MAX_PAGE_LIMIT`].
+    pub fn page_limit(&self) -> Result<usize> {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if limit == 0 || limit > MAX_PAGE_LIMIT {
+            bail!("limit must be between 1 and {MAX_PAGE_LIMIT}, got {limit}");
+        }
+        Ok(limit)
+    }
+}
+
+pub struct MessagePage<C>
+where
+    C: CurveGroup,
+{
+    pub messages: Vec<FinalizedAnyMessageEnvelope<C>>,
+    /// Cursor for the following page, or `None` once the last matching message was returned.
+    pub next_cursor: Option<MessageCursor>,
+}
+
 pub struct HandMessagesQuery<C>
 where
     C: CurveGroup,
@@ -52,4 +129,37 @@ where
         events.sort_by_key(|event| (event.snapshot_sequence_id, event.envelope.nonce));
         Ok(events)
     }
+
+    /// One page of the messages [`Self::execute`] would return, filtered by type.
+    pub async fn execute_page(
+        &self,
+        hand_id: HandId,
+        bounds: &SequenceBounds,
+        page: &MessagePageRequest,
+    ) -> Result<MessagePage<C>> {
+        let limit = page.page_limit()?;
+        let events = self.execute(hand_id, bounds).await?;
+        let mut matching = events
+            .into_iter()
+            .filter(|event| {
+                page.after
+                    .is_none_or(|after| MessageCursor::of(event) > after)
+            })
+            .filter(|event| {
+                page.types.as_ref().is_none_or(|types| {
+                    let kind = message_type(&event.envelope.message.value);
+                    types.iter().any(|allowed| allowed == kind)
+                })
+            });
+
+        let messages: Vec<_> = matching.by_ref().take(limit).collect();
+        let next_cursor = match matching.next() {
+            Some(_) => messages.last().map(MessageCursor::of),
+            None => None,
+        };
+        Ok(MessagePage {
+            messages,
+            next_cursor,
+        })
+    }
 }
@@ -1,5 +1,10 @@
 pub mod latest_snapshot;
 pub mod messages;
+pub mod snapshot_diff;
 
 pub use latest_snapshot::{LatestSnapshotError, LatestSnapshotQuery};
-pub use messages::{HandMessagesQuery, SequenceBounds};
+pub use messages::{
+    HandMessagesQuery, MessageCursor, MessagePage, MessagePageRequest, SequenceBounds,
+    DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT,
+};
+pub use snapshot_diff::{BoardCardUpdate, SnapshotDiff};
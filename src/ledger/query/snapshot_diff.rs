@@ -0,0 +1,139 @@
+//! Incremental updates between two snapshots of the same hand.
+//!
+//! Live tables advance one small step per message, so a client that already holds the snapshot
+//! at sequence `N` only needs what changed on the way to the tip: newly opened board cards, the
+//! betting actions taken since, phase/status/street transitions and the current pots.
+
+use ark_ec::CurveGroup;
+use serde::Serialize;
+
+use crate::engine::nl::types::{ActionLogEntry, Pots, Street};
+use crate::ledger::snapshot::{
+    AnyTableSnapshot, BettingSnapshot, CardDestination, CardIndex, DealingSnapshot, SnapshotSeq,
+    SnapshotStatus,
+};
+use crate::ledger::types::{EventPhase, StateHash};
+
+/// A board card that was opened between the two snapshots.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BoardCardUpdate {
+    /// Position on the board, `0..5`.
+    pub board_index: u8,
+    pub card: CardIndex,
+}
+
+/// What changed between the snapshot at `from_sequence` and the one at `to_sequence`.
+///
+/// Optional fields are `None` when the value is unchanged.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SnapshotDiff {
+    pub from_sequence: SnapshotSeq,
+    pub to_sequence: SnapshotSeq,
+    /// State hash of the target snapshot, so clients can confirm they converged.
+    pub to_state_hash: StateHash,
+    pub phase: Option<EventPhase>,
+    pub status: Option<SnapshotStatus>,
+    pub street: Option<Street>,
+    /// Newly opened board cards, in board order.
+    pub board_cards: Vec<BoardCardUpdate>,
+    /// Betting actions logged after `from_sequence`, in order.
+    pub betting_events: Vec<ActionLogEntry>,
+    pub pots: Option<Pots>,
+}
+
+impl SnapshotDiff {
+    pub fn between<C: CurveGroup>(from: &AnyTableSnapshot<C>, to: &AnyTableSnapshot<C>) -> Self {
+        let from_betting = betting(from);
+        let to_betting = betting(to);
+
+        // The action log is append-only across streets, so new entries are its suffix.
+        let betting_events = match to_betting {
+            Some(to_betting) => {
+                let seen = from_betting.map_or(0, |betting| betting.state.action_log.0.len());
+                to_betting
+                    .state
+                    .action_log
+                    .0
+                    .iter()
+                    .skip(seen)
+                    .cloned()
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        let street = to_betting
+            .map(|betting| betting.state.street)
+            .filter(|street| from_betting.map(|betting| betting.state.street) != Some(*street));
+        let pots = to_betting
+            .map(|betting| &betting.state.pots)
+            .filter(|pots| from_betting.map(|betting| &betting.state.pots) != Some(*pots))
+            .cloned();
+
+        Self {
+            from_sequence: from.sequence(),
+            to_sequence: to.sequence(),
+            to_state_hash: to.state_hash(),
+            phase: changed(from.event_phase(), to.event_phase()),
+            status: changed(from.status(), to.status()).cloned(),
+            street,
+            board_cards: added_board_cards(dealing(from), dealing(to)),
+            betting_events,
+            pots,
+        }
+    }
+}
+
+fn added_board_cards<C: CurveGroup>(
+    from: Option<&DealingSnapshot<C>>,
+    to: Option<&DealingSnapshot<C>>,
+) -> Vec<BoardCardUpdate> {
+    let Some(to) = to else {
+        return Vec::new();
+    };
+    let mut added: Vec<_> = to
+        .community_cards
+        .iter()
+        .filter(|(deal_index, _)| {
+            from.is_none_or(|from| !from.community_cards.contains_key(deal_index))
+        })
+        .filter_map(|(deal_index, card)| match to.card_plan.get(deal_index) {
+            Some(CardDestination::Board { board_index }) => Some(BoardCardUpdate {
+                board_index: *board_index,
+                card: *card,
+            }),
+            _ => None,
+        })
+        .collect();
+    added.sort_by_key(|update| update.board_index);
+    added
+}
+
+fn changed<T: PartialEq>(from: T, to: T) -> Option<T> {
+    (from != to).then_some(to)
+}
+
+fn dealing<C: CurveGroup>(snapshot: &AnyTableSnapshot<C>) -> Option<&DealingSnapshot<C>> {
+    match snapshot {
+        AnyTableSnapshot::Shuffling(_) => None,
+        AnyTableSnapshot::Dealing(table) => Some(&table.dealing),
+        AnyTableSnapshot::Preflop(table) => Some(&table.dealing),
+        AnyTableSnapshot::Flop(table) => Some(&table.dealing),
+        AnyTableSnapshot::Turn(table) => Some(&table.dealing),
+        AnyTableSnapshot::River(table) => Some(&table.dealing),
+        AnyTableSnapshot::Showdown(table) => Some(&table.dealing),
+        AnyTableSnapshot::Complete(table) => Some(&table.dealing),
+    }
+}
+
+fn betting<C: CurveGroup>(snapshot: &AnyTableSnapshot<C>) -> Option<&BettingSnapshot<C>> {
+    match snapshot {
+        AnyTableSnapshot::Shuffling(_) | AnyTableSnapshot::Dealing(_) => None,
+        AnyTableSnapshot::Preflop(table) => Some(&table.betting),
+        AnyTableSnapshot::Flop(table) => Some(&table.betting),
+        AnyTableSnapshot::Turn(table) => Some(&table.betting),
+        AnyTableSnapshot::River(table) => Some(&table.betting),
+        AnyTableSnapshot::Showdown(table) => Some(&table.betting),
+        AnyTableSnapshot::Complete(table) => Some(&table.betting),
+    }
+}
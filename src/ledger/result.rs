@@ -0,0 +1,215 @@
+//! Deterministic summary of a finished hand.
+//!
+//! [`hand_result_hash`] commits to what a hand paid out rather than to the full table state:
+//! the board, every revealed hole, each pot and how it was split. Two servers that replay the
+//! same event log must agree on it even if their snapshot encodings (and therefore their
+//! `state_hash` chains) differ, which makes it a cheap value to cross-check between operators.
+
+use std::collections::BTreeMap;
+
+use ark_ec::CurveGroup;
+use sha2::{Digest, Sha256};
+
+use crate::engine::nl::types::{Chips, Pot, SeatId};
+use crate::ledger::snapshot::{CardIndex, TableAtComplete};
+
+const HAND_RESULT_DOMAIN: &[u8] = b"legit_poker/hand_result_v1";
+
+/// How one pot was awarded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PotResult {
+    pub amount: Chips,
+    /// Seats that could win the pot, as recorded by the betting engine.
+    pub eligible: Vec<SeatId>,
+    /// Winning seats and the chips each received, in odd-chip order.
+    pub awards: Vec<(SeatId, Chips)>,
+}
+
+/// Outcome of a completed hand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandResult {
+    pub board: Vec<CardIndex>,
+    pub revealed: BTreeMap<SeatId, Vec<CardIndex>>,
+    pub mucked: Vec<SeatId>,
+    /// Main pot first, then side pots in creation order.
+    pub pots: Vec<PotResult>,
+}
+
+impl HandResult {
+    /// Split every pot among the eligible seats holding the best revealed hand. Odd chips go to
+    /// the winners closest to the left of the button. A pot with a single eligible seat (everyone
+    /// else folded) goes to that seat whether or not it showed.
+    pub fn from_snapshot<C: CurveGroup>(snapshot: &TableAtComplete<C>) -> Self {
+        let state = &snapshot.betting.state;
+        let reveals = &snapshot.reveals;
+        let pots = std::iter::once(&state.pots.main)
+            .chain(state.pots.sides.iter())
+            .map(|pot| {
+                let scores = pot
+                    .eligible
+                    .iter()
+                    .filter_map(|seat| {
+                        reveals
+                            .revealed_holes
+                            .get(seat)
+                            .map(|hand| (*seat, hand.best_score))
+                    })
+                    .collect();
+                award_pot(pot, scores, state.button)
+            })
+            .collect();
+        let mut mucked: Vec<SeatId> = reveals
+            .mucked
+            .keys()
+            .chain(&reveals.auto_mucked)
+            .copied()
+            .collect();
+        mucked.sort_unstable();
+
+        Self {
+            board: reveals.board.clone(),
+            revealed: reveals
+                .revealed_holes
+                .iter()
+                .map(|(seat, hand)| (*seat, hand.hole.clone()))
+                .collect(),
+            mucked,
+            pots,
+        }
+    }
+
+    /// SHA-256 over a fixed, length-prefixed encoding of the result.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(HAND_RESULT_DOMAIN);
+        hasher.update((self.board.len() as u32).to_be_bytes());
+        hasher.update(&self.board);
+        hasher.update((self.revealed.len() as u32).to_be_bytes());
+        for (seat, hole) in &self.revealed {
+            hasher.update([*seat]);
+            hasher.update((hole.len() as u32).to_be_bytes());
+            hasher.update(hole);
+        }
+        hasher.update((self.mucked.len() as u32).to_be_bytes());
+        hasher.update(&self.mucked);
+        hasher.update((self.pots.len() as u32).to_be_bytes());
+        for pot in &self.pots {
+            hasher.update(pot.amount.to_be_bytes());
+            hasher.update((pot.eligible.len() as u32).to_be_bytes());
+            hasher.update(&pot.eligible);
+            hasher.update((pot.awards.len() as u32).to_be_bytes());
+            for (seat, chips) in &pot.awards {
+                hasher.update([*seat]);
+                hasher.update(chips.to_be_bytes());
+            }
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Deterministic hash of a completed hand's pots, winners and revealed cards.
+///
+/// Distinct from the snapshot `state_hash`, which commits to the whole table state.
+pub fn hand_result_hash<C: CurveGroup>(snapshot: &TableAtComplete<C>) -> [u8; 32] {
+    HandResult::from_snapshot(snapshot).hash()
+}
+
+fn award_pot(pot: &Pot, scores: Vec<(SeatId, u32)>, button: SeatId) -> PotResult {
+    if let [sole] = pot.eligible[..] {
+        return PotResult {
+            amount: pot.amount,
+            eligible: pot.eligible.clone(),
+            awards: vec![(sole, pot.amount)],
+        };
+    }
+    let Some(best) = scores.iter().map(|(_, score)| *score).max() else {
+        return PotResult {
+            amount: pot.amount,
+            eligible: pot.eligible.clone(),
+            awards: Vec::new(),
+        };
+    };
+
+    let mut winners: Vec<SeatId> = scores
+        .into_iter()
+        .filter(|(_, score)| *score == best)
+        .map(|(seat, _)| seat)
+        .collect();
+    winners.sort_by_key(|seat| (*seat <= button, *seat));
+
+    let share = pot.amount / winners.len() as Chips;
+    let odd_chips = (pot.amount % winners.len() as Chips) as usize;
+    let awards = winners
+        .into_iter()
+        .enumerate()
+        .map(|(idx, seat)| (seat, share + Chips::from(idx < odd_chips)))
+        .collect();
+
+    PotResult {
+        amount: pot.amount,
+        eligible: pot.eligible.clone(),
+        awards,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::test_support::{fixture_complete_snapshot, FixtureContext};
+    use ark_bn254::G1Projective as Curve;
+
+    fn complete_hand() -> TableAtComplete<Curve> {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_complete_snapshot(&ctx);
+        snapshot.betting.state.pots.main = Pot {
+            amount: 7,
+            eligible: snapshot.reveals.revealed_holes.keys().copied().collect(),
+        };
+        snapshot
+    }
+
+    #[test]
+    fn replays_agree_and_awards_change_the_hash() {
+        let first = complete_hand();
+        let second = complete_hand();
+        assert_eq!(hand_result_hash(&first), hand_result_hash(&second));
+
+        let result = HandResult::from_snapshot(&first);
+        let awarded: Chips = result.pots[0].awards.iter().map(|(_, chips)| chips).sum();
+        assert_eq!(awarded, 7);
+        assert_eq!(result.hash(), hand_result_hash(&first));
+
+        let mut altered = result.clone();
+        altered.pots[0].awards[0].1 += 1;
+        assert_ne!(altered.hash(), result.hash());
+    }
+
+    #[test]
+    fn odd_chips_go_left_of_the_button() {
+        let pot = Pot {
+            amount: 5,
+            eligible: vec![0, 1, 2],
+        };
+        let result = award_pot(&pot, vec![(0, 9), (1, 3), (2, 9)], 1);
+        assert_eq!(result.awards, vec![(2, 3), (0, 2)]);
+    }
+
+    #[test]
+    fn pot_won_by_folds_goes_to_the_sole_eligible_seat() {
+        let pot = Pot {
+            amount: 12,
+            eligible: vec![2],
+        };
+        let result = award_pot(&pot, Vec::new(), 0);
+        assert_eq!(result.awards, vec![(2, 12)]);
+        assert_eq!(result.eligible, vec![2]);
+    }
+
+    #[test]
+    fn eligible_seats_change_the_hash() {
+        let result = HandResult::from_snapshot(&complete_hand());
+        let mut altered = result.clone();
+        altered.pots[0].eligible.pop();
+        assert_ne!(altered.hash(), result.hash());
+    }
+}
@@ -1,6 +1,65 @@
 use anyhow::{anyhow, Result};
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Byte layout used for curve points in events and snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PointEncoding {
+    /// arkworks compressed form: the x coordinate plus sign and infinity flags.
+    #[default]
+    CompressedProjective,
+    /// Both affine coordinates, uncompressed, for consumers that cannot decompress points
+    /// (e.g. EVM precompiles).
+    UncompressedAffine,
+}
+
+impl PointEncoding {
+    /// Guess the encoding of `bytes` from their length.
+    pub fn detect<C>(bytes: &[u8]) -> Option<Self>
+    where
+        C: CurveGroup,
+    {
+        let affine = C::Affine::default();
+        if bytes.len() == affine.compressed_size() {
+            Some(Self::CompressedProjective)
+        } else if bytes.len() == affine.uncompressed_size() {
+            Some(Self::UncompressedAffine)
+        } else {
+            None
+        }
+    }
+}
+
+thread_local! {
+    static POINT_ENCODING: Cell<PointEncoding> =
+        const { Cell::new(PointEncoding::CompressedProjective) };
+}
+
+impl PointEncoding {
+    /// Encoding the serde curve helpers in [`crate::crypto_serde`] currently write with.
+    pub fn current() -> Self {
+        POINT_ENCODING.with(Cell::get)
+    }
+}
+
+/// Runs `f` with the serde curve helpers writing points in `encoding`, so a single event or
+/// snapshot response can be rendered for an affine consumer. Stored and signed payloads keep the
+/// default encoding; decoding accepts both.
+pub fn with_point_encoding<T>(encoding: PointEncoding, f: impl FnOnce() -> T) -> T {
+    struct Restore(PointEncoding);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            POINT_ENCODING.with(|cell| cell.set(self.0));
+        }
+    }
+
+    let _restore = Restore(POINT_ENCODING.with(|cell| cell.replace(encoding)));
+    f()
+}
 
 /// Canonically serializes any arkworks type into a compressed byte vector.
 pub fn canonical_serialize_bytes<T>(value: &T) -> Result<Vec<u8>>
@@ -49,6 +108,32 @@ where
         .map_err(|err| anyhow!("failed to serialize curve point: {err}"))
 }
 
+/// Serializes a curve point using the given [`PointEncoding`].
+pub fn serialize_curve_bytes_with<C>(value: &C, encoding: PointEncoding) -> Result<Vec<u8>>
+where
+    C: CurveGroup + CanonicalSerialize,
+{
+    match encoding {
+        PointEncoding::CompressedProjective => serialize_curve_bytes(value),
+        PointEncoding::UncompressedAffine => {
+            let mut buf = Vec::new();
+            value
+                .into_affine()
+                .serialize_uncompressed(&mut buf)
+                .map_err(|err| anyhow!("failed to serialize curve point: {err}"))?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Serializes a curve point using the given [`PointEncoding`] and returns it as 0x-prefixed hex.
+pub fn serialize_curve_hex_with<C>(value: &C, encoding: PointEncoding) -> Result<String>
+where
+    C: CurveGroup + CanonicalSerialize,
+{
+    serialize_curve_bytes_with(value, encoding).map(|bytes| format!("0x{}", hex::encode(bytes)))
+}
+
 /// Canonically deserializes a value from a hex string (accepts optional 0x prefix).
 pub fn canonical_deserialize_hex<T>(value: &str) -> Result<T>
 where
@@ -98,6 +183,39 @@ where
     }
 }
 
+/// Deserializes a curve point written with the given [`PointEncoding`].
+pub fn deserialize_curve_bytes_with<C>(bytes: &[u8], encoding: PointEncoding) -> Result<C>
+where
+    C: CurveGroup + CanonicalDeserialize,
+{
+    match encoding {
+        PointEncoding::CompressedProjective => deserialize_curve_bytes(bytes),
+        PointEncoding::UncompressedAffine => C::Affine::deserialize_uncompressed(&mut &bytes[..])
+            .map(Into::into)
+            .map_err(|err| anyhow!("curve deserialization failed: {err}")),
+    }
+}
+
+/// Deserializes a curve point from hex written with the given [`PointEncoding`].
+pub fn deserialize_curve_hex_with<C>(value: &str, encoding: PointEncoding) -> Result<C>
+where
+    C: CurveGroup + CanonicalDeserialize,
+{
+    let bytes = decode_hex_bytes(value)?;
+    deserialize_curve_bytes_with(&bytes, encoding)
+}
+
+/// Deserializes a curve point from hex in either [`PointEncoding`], telling them apart by length.
+pub fn deserialize_curve_hex_any<C>(value: &str) -> Result<C>
+where
+    C: CurveGroup + CanonicalDeserialize,
+{
+    let bytes = decode_hex_bytes(value)?;
+    let encoding = PointEncoding::detect::<C>(&bytes)
+        .ok_or_else(|| anyhow!("unexpected curve point length {}", bytes.len()))?;
+    deserialize_curve_bytes_with(&bytes, encoding)
+}
+
 fn decode_hex_bytes(value: &str) -> Result<Vec<u8>> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
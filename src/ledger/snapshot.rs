@@ -1,11 +1,12 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context, Result};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::db::entity::sea_orm_active_enums::{ApplicationStatus, PhaseKind};
 use crate::db::entity::{
@@ -13,16 +14,21 @@ use crate::db::entity::{
     table_snapshots,
 };
 use crate::engine::nl::actions::PlayerBetAction;
-use crate::engine::nl::engine::{BettingEngineNL, EngineNL};
+use crate::engine::nl::engine::EngineNL;
+use crate::engine::nl::errors::StateError;
+use crate::engine::nl::events::BettingEvent;
 use crate::engine::nl::state::BettingState;
-use crate::engine::nl::types::{HandConfig, PlayerState, PlayerStatus, Pot, Pots, SeatId, Street};
+use crate::engine::nl::types::{
+    DealingOrder, HandConfig, PlayerState, PlayerStatus, Pot, Pots, SeatId, Street,
+};
 use crate::ledger::hash::{chain_hash, initial_snapshot_hash, message_hash, LedgerHasher};
 use crate::ledger::messages::{
-    EnvelopedMessage, FlopStreet, GameMessage, GamePlayerMessage, PreflopStreet, RiverStreet,
-    TurnStreet,
+    EnvelopedMessage, FinalizedAnyMessageEnvelope, FlopStreet, GameMessage, GamePlayerMessage,
+    PreflopStreet, RiverStreet, SignedMuckDeclaration, SignedReconnectRequest, TurnStreet,
 };
 use crate::ledger::serialization::deserialize_curve_bytes;
 use crate::ledger::types::{EventPhase, GameId, HandId, StateHash};
+use crate::ledger::verifier::SignatureValidator;
 use crate::ledger::{CanonicalKey, PlayerHoleCard};
 use crate::showdown::HandCategory;
 use crate::shuffling::community_decryption::CommunityDecryptionShare;
@@ -58,6 +64,9 @@ pub struct PlayerIdentity<C: CurveGroup> {
     pub player_id: crate::engine::nl::types::PlayerId,
     pub nonce: u64,
     pub seat: SeatId,
+    /// Muck this player's hand at showdown when it cannot win any pot they are in.
+    #[serde(default)]
+    pub auto_muck_losers: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
@@ -79,6 +88,17 @@ pub type ShufflerRoster<C> = BTreeMap<CanonicalKey<C>, ShufflerIdentity<C>>;
 pub type SeatingMap<C> = BTreeMap<SeatId, Option<CanonicalKey<C>>>;
 pub type PlayerStacks<C> = BTreeMap<SeatId, PlayerStackInfo<C>>;
 
+/// Inconsistency between a snapshot's `seating`, `players` and `stacks`.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SeatingError {
+    #[error("seat {seat} is claimed by more than one player")]
+    DuplicateSeat { seat: SeatId },
+    #[error("the player at seat {first} is also seated at seat {second}")]
+    PlayerSeatedTwice { first: SeatId, second: SeatId },
+    #[error("player with a stack at seat {seat} is not seated there")]
+    PlayerWithoutSeat { seat: SeatId },
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SnapshotStatus {
@@ -86,6 +106,57 @@ pub enum SnapshotStatus {
     Failure(String),
 }
 
+impl CanonicalSerialize for SnapshotStatus {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        match self {
+            SnapshotStatus::Success => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+            }
+            SnapshotStatus::Failure(reason) => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                reason.serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        1 + match self {
+            SnapshotStatus::Success => 0,
+            SnapshotStatus::Failure(reason) => reason.serialized_size(compress),
+        }
+    }
+}
+
+impl CanonicalDeserialize for SnapshotStatus {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let discriminant = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        match discriminant {
+            0 => Ok(SnapshotStatus::Success),
+            1 => Ok(SnapshotStatus::Failure(String::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?)),
+            _ => Err(ark_serialize::SerializationError::InvalidData),
+        }
+    }
+}
+
+impl ark_serialize::Valid for SnapshotStatus {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 impl<C: CurveGroup> DomainSeparated for PlayerIdentity<C> {
     fn domain_string() -> &'static str {
         "ledger/player_identity_v1"
@@ -100,7 +171,7 @@ impl<C: CurveGroup> DomainSeparated for ShufflerIdentity<C> {
 
 // ---- Shuffling -----------------------------------------------------------------------------
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize, C::BaseField: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize, C::BaseField: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
@@ -109,9 +180,29 @@ pub struct ShufflingStep<C: CurveGroup> {
     #[serde(with = "crate::crypto_serde::curve")]
     pub shuffler_public_key: C,
     pub proof: ShuffleProof<C>,
+    /// Beacon round the VRF nonce was taken from, so the nonce can be re-fetched and checked.
+    #[serde(default)]
+    pub beacon_round: Option<u64>,
+    /// VRF nonce revealed with the shuffle, checked against the shuffler's commitment.
+    #[serde(default, with = "crate::crypto_serde::field_option")]
+    pub vrf_nonce: Option<C::BaseField>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// A shuffler's `Poseidon(nonce)` commitment, published before its shuffle turn.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
+#[serde(bound(
+    serialize = "C: CanonicalSerialize, C::BaseField: CanonicalSerialize",
+    deserialize = "C: CanonicalDeserialize, C::BaseField: CanonicalDeserialize"
+))]
+pub struct NonceCommitment<C: CurveGroup> {
+    pub shuffler_key: CanonicalKey<C>,
+    #[serde(with = "crate::crypto_serde::field")]
+    pub commitment: C::BaseField,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize, C::BaseField: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize, C::BaseField: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
@@ -123,11 +214,55 @@ pub struct ShufflingSnapshot<C: CurveGroup> {
     #[serde(with = "crate::crypto_serde::elgamal_array")]
     pub final_deck: [ElGamalCiphertext<C>; DECK_SIZE],
     pub expected_order: Vec<CanonicalKey<C>>,
+    #[serde(default)]
+    pub nonce_commitments: Vec<NonceCommitment<C>>,
+}
+
+impl<C: CurveGroup> ShufflingSnapshot<C> {
+    pub fn nonce_commitment(&self, shuffler_key: &CanonicalKey<C>) -> Option<&C::BaseField> {
+        self.nonce_commitments
+            .iter()
+            .find(|entry| &entry.shuffler_key == shuffler_key)
+            .map(|entry| &entry.commitment)
+    }
+
+    /// Shufflers in `expected_order` that have not committed to a nonce yet.
+    pub fn missing_nonce_commitments(&self) -> impl Iterator<Item = &CanonicalKey<C>> + '_ {
+        self.expected_order
+            .iter()
+            .filter(|key| self.nonce_commitment(key).is_none())
+    }
+
+    /// Record `shuffler_key`'s nonce commitment. Each shuffler commits once, and only before
+    /// the first shuffle has been applied, so no shuffler can pick its nonce after seeing
+    /// another's shuffle.
+    pub fn record_nonce_commitment(
+        &mut self,
+        shuffler_key: CanonicalKey<C>,
+        commitment: C::BaseField,
+    ) -> Result<()> {
+        if !self.expected_order.contains(&shuffler_key) {
+            return Err(anyhow!(
+                "nonce commitment from a shuffler outside the order"
+            ));
+        }
+        if !self.steps.is_empty() {
+            return Err(anyhow!("nonce commitment arrived after shuffling started"));
+        }
+        if self.nonce_commitment(&shuffler_key).is_some() {
+            return Err(anyhow!("shuffler already committed to a nonce"));
+        }
+        self.nonce_commitments.push(NonceCommitment {
+            shuffler_key,
+            commitment,
+        });
+        Ok(())
+    }
 }
 
 // ---- Dealing -------------------------------------------------------------------------------
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize, C::BaseField: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize, C::BaseField: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
@@ -137,6 +272,27 @@ pub struct DealtCard<C: CurveGroup> {
     pub source_index: Option<u8>,
 }
 
+impl<C: CurveGroup> DealtCard<C> {
+    /// Open this card against the shuffled deck: `cipher` must be the entry at `source_index`.
+    ///
+    /// The final deck is public and bound by the shuffle proofs, so the position opening is a
+    /// plain equality check; it stops a coordinator from substituting a different ciphertext.
+    pub fn verify_deck_position(&self, deck: &[ElGamalCiphertext<C>]) -> Result<()> {
+        let index = self
+            .source_index
+            .ok_or_else(|| anyhow!("dealt card has no deck position to open"))?;
+        let committed = deck
+            .get(index as usize)
+            .ok_or_else(|| anyhow!("deck position {index} is outside the deck"))?;
+        if committed != &self.cipher {
+            return Err(anyhow!(
+                "dealt card does not match the shuffled deck at position {index}"
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize",
@@ -165,8 +321,99 @@ pub enum CardDestination {
     Unused,
 }
 
+impl CanonicalSerialize for CardDestination {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        match self {
+            CardDestination::Hole { seat, hole_index } => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+                seat.serialize_with_mode(&mut writer, compress)?;
+                hole_index.serialize_with_mode(&mut writer, compress)?;
+            }
+            CardDestination::Board { board_index } => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                board_index.serialize_with_mode(&mut writer, compress)?;
+            }
+            CardDestination::Burn => {
+                2u8.serialize_with_mode(&mut writer, compress)?;
+            }
+            CardDestination::Unused => {
+                3u8.serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        1 + match self {
+            CardDestination::Hole { seat, hole_index } => {
+                seat.serialized_size(compress) + hole_index.serialized_size(compress)
+            }
+            CardDestination::Board { board_index } => board_index.serialized_size(compress),
+            CardDestination::Burn | CardDestination::Unused => 0,
+        }
+    }
+}
+
+impl CanonicalDeserialize for CardDestination {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let discriminant = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        match discriminant {
+            0 => Ok(CardDestination::Hole {
+                seat: SeatId::deserialize_with_mode(&mut reader, compress, validate)?,
+                hole_index: u8::deserialize_with_mode(&mut reader, compress, validate)?,
+            }),
+            1 => Ok(CardDestination::Board {
+                board_index: u8::deserialize_with_mode(&mut reader, compress, validate)?,
+            }),
+            2 => Ok(CardDestination::Burn),
+            3 => Ok(CardDestination::Unused),
+            _ => Err(ark_serialize::SerializationError::InvalidData),
+        }
+    }
+}
+
+impl ark_serialize::Valid for CardDestination {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 pub type CardPlan = BTreeMap<u8, CardDestination>;
 
+/// Location of a community card within the board.
+///
+/// Slots order by street: `Flop(0) < Flop(1) < Flop(2) < Turn < River`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum BoardCardSlot {
+    Flop(u8),
+    Turn,
+    River,
+}
+
+impl BoardCardSlot {
+    pub fn from_board_index(index: u8) -> Option<Self> {
+        match index {
+            0..=2 => Some(Self::Flop(index)),
+            3 => Some(Self::Turn),
+            4 => Some(Self::River),
+            _ => None,
+        }
+    }
+
+    /// Every board slot in the order the cards are dealt.
+    pub fn all_in_order() -> impl Iterator<Item = BoardCardSlot> {
+        (0..5).filter_map(Self::from_board_index)
+    }
+}
+
 pub fn build_default_card_plan<C>(cfg: &HandConfig, seating: &SeatingMap<C>) -> CardPlan
 where
     C: CurveGroup,
@@ -191,11 +438,18 @@ where
     }
 
     let mut next_card: u8 = 0;
-    for hole_index in 0..2 {
-        for &seat in &active_seats {
-            plan.insert(next_card, CardDestination::Hole { seat, hole_index });
-            next_card += 1;
-        }
+    let holes: Vec<(SeatId, u8)> = match cfg.dealing_order {
+        DealingOrder::OneAtATime => (0..cfg.hole_cards)
+            .flat_map(|hole_index| active_seats.iter().map(move |&seat| (seat, hole_index)))
+            .collect(),
+        DealingOrder::PerPlayer => active_seats
+            .iter()
+            .flat_map(|&seat| (0..cfg.hole_cards).map(move |hole_index| (seat, hole_index)))
+            .collect(),
+    };
+    for (seat, hole_index) in holes {
+        plan.insert(next_card, CardDestination::Hole { seat, hole_index });
+        next_card += 1;
     }
 
     let push_burn = |plan: &mut CardPlan, next: &mut u8| {
@@ -224,11 +478,12 @@ where
     plan
 }
 
-pub fn build_initial_betting_state<C>(
-    cfg: &HandConfig,
+/// Betting-engine view of the seated players, ordered by seat, with posted blinds moved from
+/// the stack into the current round.
+pub fn initial_player_states<C>(
     stacks: &PlayerStacks<C>,
     players: &PlayerRoster<C>,
-) -> BettingStateNL
+) -> Vec<PlayerState>
 where
     C: CurveGroup,
 {
@@ -253,6 +508,20 @@ where
         .collect();
 
     player_states.sort_by_key(|p| p.seat);
+    player_states
+}
+
+/// Initial preflop betting state for a dealt hand. Fails when `cfg`'s blind positions do not
+/// pass [`EngineNL::validate_blind_config`] for the seated players.
+pub fn build_initial_betting_state<C>(
+    cfg: &HandConfig,
+    stacks: &PlayerStacks<C>,
+    players: &PlayerRoster<C>,
+) -> Result<BettingStateNL, StateError>
+where
+    C: CurveGroup,
+{
+    let player_states = initial_player_states(stacks, players);
 
     let main_amount: u64 = player_states.iter().map(|p| p.committed_this_round).sum();
 
@@ -270,20 +539,23 @@ where
         sides: Vec::new(),
     };
 
-    EngineNL::new_after_deal(cfg.clone(), player_states, pots)
+    EngineNL::try_new_after_deal(cfg.clone(), player_states, pots)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::engine::nl::types::Street;
+    use crate::ledger::messages::ReconnectRequest;
     use crate::ledger::snapshot::phases::AnyPhase;
     use crate::ledger::test_support::{
         fixture_complete_snapshot, fixture_dealing_snapshot, fixture_flop_snapshot,
         fixture_preflop_snapshot, fixture_river_snapshot, fixture_showdown_snapshot,
         fixture_shuffling_snapshot, fixture_turn_snapshot, FixtureContext,
     };
+    use crate::ledger::verifier::SchnorrSignatureValidator;
     use crate::ledger::CanonicalKey;
+    use crate::signing::{SignatureBytes, WithSignature};
     use crate::test_utils::serde::{assert_round_trip_eq, assert_round_trip_json};
     use crate::{
         chaum_pedersen::ChaumPedersenProof,
@@ -297,9 +569,12 @@ mod tests {
         },
     };
     use ark_bn254::G1Projective;
+    use ark_crypto_primitives::signature::{schnorr::Schnorr, SignatureScheme};
     use ark_ec::PrimeGroup;
     use ark_serialize::CanonicalSerialize;
+    use rand::{rngs::StdRng, SeedableRng};
     use serde::{de::DeserializeOwned, Serialize};
+    use sha2::Sha256;
 
     type Curve = G1Projective;
 
@@ -375,6 +650,9 @@ mod tests {
             small_blind_seat: 1,
             big_blind_seat: 2,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
 
         let key1 = CanonicalKey::new(Curve::generator());
@@ -411,6 +689,7 @@ mod tests {
                 player_id: 1,
                 nonce: 0,
                 seat: 1,
+                auto_muck_losers: false,
             },
         );
         roster.insert(
@@ -421,10 +700,11 @@ mod tests {
                 player_id: 2,
                 nonce: 0,
                 seat: 2,
+                auto_muck_losers: false,
             },
         );
 
-        let state = build_initial_betting_state(&cfg, &stacks, &roster);
+        let state = build_initial_betting_state(&cfg, &stacks, &roster).expect("valid blinds");
 
         assert_eq!(state.street, Street::Preflop);
         assert_eq!(state.players.len(), 2);
@@ -526,6 +806,53 @@ mod tests {
         assert_named_round_trip("any_complete", &any_complete);
     }
 
+    #[test]
+    fn identity_at_seat_resolves_seated_players() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11, 12]);
+        let mut snapshot = fixture_shuffling_snapshot(&ctx);
+        Arc::make_mut(&mut snapshot.seating).insert(5, None);
+
+        let identity = snapshot.identity_at_seat(1).expect("seat 1 is occupied");
+        assert_eq!(identity.seat, 1);
+        assert_eq!(
+            Some(&identity.player_key),
+            snapshot.seating.get(&1).and_then(Option::as_ref)
+        );
+        assert!(snapshot.identity_at_seat(5).is_none());
+        assert!(snapshot.identity_at_seat(7).is_none());
+    }
+
+    #[test]
+    fn validate_seating_rejects_two_players_on_one_seat() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_shuffling_snapshot(&ctx);
+        assert_eq!(snapshot.validate_seating(), Ok(()));
+
+        let players = Arc::make_mut(&mut snapshot.players);
+        let second = players
+            .values_mut()
+            .find(|identity| identity.seat == 2)
+            .expect("seat 2 is occupied");
+        second.seat = 1;
+
+        assert_eq!(
+            snapshot.validate_seating(),
+            Err(SeatingError::DuplicateSeat { seat: 1 })
+        );
+    }
+
+    #[test]
+    fn validate_seating_rejects_stacked_player_without_seat() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_shuffling_snapshot(&ctx);
+        Arc::make_mut(&mut snapshot.seating).insert(2, None);
+
+        assert_eq!(
+            snapshot.validate_seating(),
+            Err(SeatingError::PlayerWithoutSeat { seat: 2 })
+        );
+    }
+
     #[test]
     fn dealing_snapshot_tuple_maps_serialize() {
         let mut assignments = BTreeMap::new();
@@ -569,9 +896,164 @@ mod tests {
 
         let _ = serde_json::to_string(&snapshot).expect("dealing snapshot should serialize");
     }
+
+    #[test]
+    fn board_slots_follow_street_order() {
+        let slots: Vec<BoardCardSlot> = BoardCardSlot::all_in_order().collect();
+        assert_eq!(
+            slots,
+            vec![
+                BoardCardSlot::Flop(0),
+                BoardCardSlot::Flop(1),
+                BoardCardSlot::Flop(2),
+                BoardCardSlot::Turn,
+                BoardCardSlot::River,
+            ]
+        );
+
+        let mut shuffled = vec![
+            BoardCardSlot::River,
+            BoardCardSlot::Flop(2),
+            BoardCardSlot::Turn,
+            BoardCardSlot::Flop(0),
+            BoardCardSlot::Flop(1),
+        ];
+        shuffled.sort();
+        assert_eq!(shuffled, slots);
+    }
+
+    #[test]
+    fn card_plan_deals_one_card_at_a_time_clockwise() {
+        let mut cfg = crate::ledger::test_support::fixture_hand_config(0, 1, 2);
+        let seating: SeatingMap<Curve> = (0..3)
+            .map(|seat| (seat, Some(CanonicalKey::new(Curve::generator()))))
+            .collect();
+        let holes = |plan: &CardPlan| -> Vec<(SeatId, u8)> {
+            (0..6)
+                .map(|deck_index| match plan[&deck_index] {
+                    CardDestination::Hole { seat, hole_index } => (seat, hole_index),
+                    ref other => panic!("deck index {deck_index} dealt to {other:?}"),
+                })
+                .collect()
+        };
+
+        let plan = build_default_card_plan(&cfg, &seating);
+        assert_eq!(
+            holes(&plan),
+            vec![(1, 0), (2, 0), (0, 0), (1, 1), (2, 1), (0, 1)]
+        );
+        assert_eq!(plan[&6], CardDestination::Burn);
+
+        cfg.dealing_order = DealingOrder::PerPlayer;
+        let plan = build_default_card_plan(&cfg, &seating);
+        assert_eq!(
+            holes(&plan),
+            vec![(1, 0), (1, 1), (2, 0), (2, 1), (0, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn spectator_redaction_strips_hole_ciphertexts() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11, 12]);
+        let dealing = fixture_dealing_snapshot(&ctx);
+        assert!(!dealing.dealing.player_ciphertexts.is_empty());
+
+        let redacted = redact_for_spectator(&AnyTableSnapshot::Dealing(dealing.clone()));
+        let table: &TableAtDealing<Curve> = (&redacted).try_into().expect("still dealing");
+        assert!(table.dealing.player_ciphertexts.is_empty());
+        assert!(table.dealing.player_unblinding_shares.is_empty());
+        assert!(table.dealing.player_unblinding_combined.is_empty());
+        assert_eq!(table.dealing.card_plan, dealing.dealing.card_plan);
+        assert_eq!(
+            table.dealing.assignments.len(),
+            dealing.dealing.assignments.len()
+        );
+        assert_eq!(table.state_hash, dealing.state_hash);
+    }
+
+    #[test]
+    fn player_view_keeps_only_own_hole_ciphertexts() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11, 12]);
+        let preflop = AnyTableSnapshot::Preflop(fixture_preflop_snapshot(&ctx));
+
+        let view = view_for_player(&preflop, 1);
+        assert_eq!(view.seat, 1);
+        let table: &TableAtPreflop<Curve> = (&view.snapshot).try_into().expect("still preflop");
+        let holes: Vec<_> = table.dealing.player_ciphertexts.keys().copied().collect();
+        assert_eq!(holes, vec![(1, 0), (1, 1)]);
+        let combined: Vec<_> = table
+            .dealing
+            .player_unblinding_combined
+            .keys()
+            .copied()
+            .collect();
+        assert_eq!(combined, vec![(1, 0), (1, 1)]);
+        assert!(!table.dealing.player_ciphertexts.contains_key(&(2, 0)));
+        assert!(!table.dealing.player_ciphertexts.contains_key(&(2, 1)));
+    }
+
+    #[test]
+    fn reconnect_requires_the_seat_key_holder() {
+        type Scheme = Schnorr<Curve, Sha256>;
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let params = Scheme::setup(&mut rng).expect("setup");
+        let (owner_pk, owner_sk) = Scheme::keygen(&params, &mut rng).expect("keygen");
+        let (_, intruder_sk) = Scheme::keygen(&params, &mut rng).expect("keygen");
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11, 12]);
+        let mut table = fixture_preflop_snapshot(&ctx);
+        let player_key = table.seating[&1].clone().expect("seat 1 occupied");
+        Arc::make_mut(&mut table.players)
+            .get_mut(&player_key)
+            .expect("seat 1 identity")
+            .public_key = owner_pk.into();
+        let snapshot = AnyTableSnapshot::Preflop(table);
+
+        let request = ReconnectRequest {
+            game_id: ctx.game_id,
+            hand_id: ctx.hand_id,
+            seat: 1,
+            player_key,
+            nonce: 1,
+        };
+        let mut sign = |nonce: u64, sk: &<Scheme as SignatureScheme>::SecretKey| {
+            let request = ReconnectRequest {
+                nonce,
+                ..request.clone()
+            };
+            let signed = WithSignature::new::<Scheme, _>(request, &params, sk, &mut rng)
+                .expect("sign reconnect");
+            WithSignature {
+                value: signed.value,
+                signature: signed.signature.to_bytes(),
+            }
+        };
+        let forged = sign(1, &intruder_sk);
+        let genuine = sign(1, &owner_sk);
+        let validator = SchnorrSignatureValidator::<Curve>::new(params.clone());
+        let nonces = ReconnectNonces::default();
+
+        let err = reconnect_player(&snapshot, &forged, &validator, &nonces).expect_err("wrong key");
+        assert!(err.to_string().contains("invalid signature"), "{err}");
+
+        let view = reconnect_player(&snapshot, &genuine, &validator, &nonces).expect("seat owner");
+        assert_eq!(view.seat, 1);
+        let table: &TableAtPreflop<Curve> = (&view.snapshot).try_into().expect("still preflop");
+        let holes: Vec<_> = table.dealing.player_ciphertexts.keys().copied().collect();
+        assert_eq!(holes, vec![(1, 0), (1, 1)]);
+
+        // A captured request cannot be replayed; the owner moves on with a fresh nonce.
+        let err = reconnect_player(&snapshot, &genuine, &validator, &nonces).expect_err("replay");
+        assert!(err.to_string().contains("nonce"), "{err}");
+        let fresh = sign(2, &owner_sk);
+        reconnect_player(&snapshot, &fresh, &validator, &nonces).expect("fresh nonce");
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Dealing-phase state. Every map is a `BTreeMap` so serialization walks entries in key order
+/// and the dealing hash does not depend on insertion order; keep it that way.
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize, C::BaseField: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize, C::BaseField: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
@@ -777,6 +1259,74 @@ pub enum AnyPlayerActionMsg<C: CurveGroup> {
     River(GamePlayerMessage<RiverStreet, C>),
 }
 
+impl<C: CurveGroup> CanonicalSerialize for AnyPlayerActionMsg<C> {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        match self {
+            AnyPlayerActionMsg::Preflop(msg) => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+                msg.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyPlayerActionMsg::Flop(msg) => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                msg.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyPlayerActionMsg::Turn(msg) => {
+                2u8.serialize_with_mode(&mut writer, compress)?;
+                msg.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyPlayerActionMsg::River(msg) => {
+                3u8.serialize_with_mode(&mut writer, compress)?;
+                msg.serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        1 + match self {
+            AnyPlayerActionMsg::Preflop(msg) => msg.serialized_size(compress),
+            AnyPlayerActionMsg::Flop(msg) => msg.serialized_size(compress),
+            AnyPlayerActionMsg::Turn(msg) => msg.serialized_size(compress),
+            AnyPlayerActionMsg::River(msg) => msg.serialized_size(compress),
+        }
+    }
+}
+
+impl<C: CurveGroup> CanonicalDeserialize for AnyPlayerActionMsg<C> {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let discriminant = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        match discriminant {
+            0 => Ok(AnyPlayerActionMsg::Preflop(
+                GamePlayerMessage::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            1 => Ok(AnyPlayerActionMsg::Flop(
+                GamePlayerMessage::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            2 => Ok(AnyPlayerActionMsg::Turn(
+                GamePlayerMessage::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            3 => Ok(AnyPlayerActionMsg::River(
+                GamePlayerMessage::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            _ => Err(ark_serialize::SerializationError::InvalidData),
+        }
+    }
+}
+
+impl<C: CurveGroup> ark_serialize::Valid for AnyPlayerActionMsg<C> {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct PlayerActionSerde {
     street: String,
@@ -835,7 +1385,7 @@ where
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize"
@@ -843,27 +1393,32 @@ where
 pub struct BettingSnapshot<C: CurveGroup> {
     pub state: BettingStateNL,
     pub last_events: Vec<AnyPlayerActionMsg<C>>,
+    /// Structured outcome of each entry in `last_events`, derived by the engine. Not part of the
+    /// state hash.
+    #[serde(default)]
+    pub betting_events: Vec<BettingEvent>,
 }
 
 // ---- Reveals -------------------------------------------------------------------------------
 
 pub type CardIndex = u8;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
 ))]
 pub struct RevealedHand<C: CurveGroup> {
-    pub hole: [CardIndex; 2],
-    pub hole_ciphertexts: [PlayerAccessibleCiphertext<C>; 2],
+    /// Hole cards in hole-index order, as many as the hand config deals.
+    pub hole: Vec<CardIndex>,
+    pub hole_ciphertexts: Vec<PlayerAccessibleCiphertext<C>>,
     pub best_five: [CardIndex; 5],
     pub best_category: HandCategory,
     pub best_tiebreak: [u8; 5],
     pub best_score: u32,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
 #[serde(bound(
     serialize = "C: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
     deserialize = "C: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
@@ -875,6 +1430,26 @@ pub struct RevealsSnapshot<C: CurveGroup> {
         deserialize_with = "crate::crypto_serde::simple_map::deserialize"
     )]
     pub revealed_holes: BTreeMap<SeatId, RevealedHand<C>>,
+    /// Declarations, with their envelope signatures, from players who chose not to show at
+    /// showdown.
+    #[serde(
+        default,
+        serialize_with = "crate::crypto_serde::simple_map::serialize",
+        deserialize_with = "crate::crypto_serde::simple_map::deserialize"
+    )]
+    pub mucked: BTreeMap<SeatId, SignedMuckDeclaration<C>>,
+    /// Seats that opted into auto-mucking and whose verified hand could not win any pot.
+    #[serde(default)]
+    pub auto_mucked: BTreeSet<SeatId>,
+}
+
+impl<C: CurveGroup> RevealsSnapshot<C> {
+    /// Whether `seat` has revealed, mucked or been auto-mucked.
+    pub fn has_resolved(&self, seat: SeatId) -> bool {
+        self.revealed_holes.contains_key(&seat)
+            || self.mucked.contains_key(&seat)
+            || self.auto_mucked.contains(&seat)
+    }
 }
 
 // ---- Table snapshot ------------------------------------------------------------------------
@@ -911,6 +1486,132 @@ where
     pub reveals: P::RevealsS,
 }
 
+// The shared rosters and config are written inline; deserialization gives each snapshot its own
+// `Arc`s.
+impl<P, C> CanonicalSerialize for TableSnapshot<P, C>
+where
+    P: HandPhase<C>,
+    C: CurveGroup,
+    P::ShufflingS: CanonicalSerialize,
+    P::DealingS: CanonicalSerialize,
+    P::BettingS: CanonicalSerialize,
+    P::RevealsS: CanonicalSerialize,
+{
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        self.game_id.serialize_with_mode(&mut writer, compress)?;
+        self.hand_id.serialize_with_mode(&mut writer, compress)?;
+        self.sequence.serialize_with_mode(&mut writer, compress)?;
+        self.cfg.serialize_with_mode(&mut writer, compress)?;
+        self.shufflers.serialize_with_mode(&mut writer, compress)?;
+        self.players.serialize_with_mode(&mut writer, compress)?;
+        self.seating.serialize_with_mode(&mut writer, compress)?;
+        self.stacks.serialize_with_mode(&mut writer, compress)?;
+        self.previous_hash
+            .serialize_with_mode(&mut writer, compress)?;
+        self.state_hash.serialize_with_mode(&mut writer, compress)?;
+        self.status.serialize_with_mode(&mut writer, compress)?;
+        self.shuffling.serialize_with_mode(&mut writer, compress)?;
+        self.dealing.serialize_with_mode(&mut writer, compress)?;
+        self.betting.serialize_with_mode(&mut writer, compress)?;
+        self.reveals.serialize_with_mode(&mut writer, compress)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        self.game_id.serialized_size(compress)
+            + self.hand_id.serialized_size(compress)
+            + self.sequence.serialized_size(compress)
+            + self.cfg.serialized_size(compress)
+            + self.shufflers.serialized_size(compress)
+            + self.players.serialized_size(compress)
+            + self.seating.serialized_size(compress)
+            + self.stacks.serialized_size(compress)
+            + self.previous_hash.serialized_size(compress)
+            + self.state_hash.serialized_size(compress)
+            + self.status.serialized_size(compress)
+            + self.shuffling.serialized_size(compress)
+            + self.dealing.serialized_size(compress)
+            + self.betting.serialized_size(compress)
+            + self.reveals.serialized_size(compress)
+    }
+}
+
+impl<P, C> CanonicalDeserialize for TableSnapshot<P, C>
+where
+    P: HandPhase<C>,
+    C: CurveGroup,
+    P::ShufflingS: CanonicalDeserialize,
+    P::DealingS: CanonicalDeserialize,
+    P::BettingS: CanonicalDeserialize,
+    P::RevealsS: CanonicalDeserialize,
+{
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        Ok(Self {
+            game_id: GameId::deserialize_with_mode(&mut reader, compress, validate)?,
+            hand_id: Option::<HandId>::deserialize_with_mode(&mut reader, compress, validate)?,
+            sequence: SnapshotSeq::deserialize_with_mode(&mut reader, compress, validate)?,
+            cfg: Arc::new(HandConfig::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?),
+            shufflers: Arc::new(ShufflerRoster::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?),
+            players: Arc::new(PlayerRoster::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?),
+            seating: Arc::new(SeatingMap::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?),
+            stacks: Arc::new(PlayerStacks::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?),
+            previous_hash: Option::<StateHash>::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            state_hash: StateHash::deserialize_with_mode(&mut reader, compress, validate)?,
+            status: SnapshotStatus::deserialize_with_mode(&mut reader, compress, validate)?,
+            shuffling: P::ShufflingS::deserialize_with_mode(&mut reader, compress, validate)?,
+            dealing: P::DealingS::deserialize_with_mode(&mut reader, compress, validate)?,
+            betting: P::BettingS::deserialize_with_mode(&mut reader, compress, validate)?,
+            reveals: P::RevealsS::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<P, C> ark_serialize::Valid for TableSnapshot<P, C>
+where
+    P: HandPhase<C>,
+    C: CurveGroup,
+    P::ShufflingS: CanonicalDeserialize,
+    P::DealingS: CanonicalDeserialize,
+    P::BettingS: CanonicalDeserialize,
+    P::RevealsS: CanonicalDeserialize,
+{
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 pub type TableAtShuffling<C> = TableSnapshot<PhaseShuffling, C>;
 pub type TableAtDealing<C> = TableSnapshot<PhaseDealing, C>;
 pub type TableAtPreflop<C> = TableSnapshot<PhaseBetting<PreflopStreet>, C>;
@@ -949,6 +1650,48 @@ where
         &self.seating
     }
 
+    /// Identity of the player seated at `seat`, or `None` if the seat is empty or unknown.
+    pub fn identity_at_seat(&self, seat: SeatId) -> Option<&PlayerIdentity<C>> {
+        let player_key = self.seating.get(&seat)?.as_ref()?;
+        self.players.get(player_key)
+    }
+
+    /// Check that `seating` is a bijection between occupied seats and players, that no two
+    /// roster entries claim the same seat, and that every player in `stacks` sits at their seat.
+    pub fn validate_seating(&self) -> std::result::Result<(), SeatingError> {
+        let mut seat_of: BTreeMap<&CanonicalKey<C>, SeatId> = BTreeMap::new();
+        for (&seat, player_key) in self.seating.iter() {
+            let Some(player_key) = player_key else {
+                continue;
+            };
+            if let Some(first) = seat_of.insert(player_key, seat) {
+                return Err(SeatingError::PlayerSeatedTwice {
+                    first,
+                    second: seat,
+                });
+            }
+        }
+
+        let mut claimed = BTreeSet::new();
+        for identity in self.players.values() {
+            if !claimed.insert(identity.seat) {
+                return Err(SeatingError::DuplicateSeat {
+                    seat: identity.seat,
+                });
+            }
+        }
+
+        for (&seat, info) in self.stacks.iter() {
+            let Some(player_key) = info.player_key.as_ref() else {
+                continue;
+            };
+            if seat_of.get(player_key) != Some(&seat) {
+                return Err(SeatingError::PlayerWithoutSeat { seat });
+            }
+        }
+        Ok(())
+    }
+
     pub fn dealing(&self) -> &P::DealingS {
         &self.dealing
     }
@@ -973,6 +1716,7 @@ where
         self.previous_hash = Some(self.state_hash);
         self.state_hash = chained;
         self.sequence = self.sequence.saturating_add(1);
+        debug_assert_eq!(self.validate_seating(), Ok(()), "inconsistent seating");
         self.status = SnapshotStatus::Success;
     }
 }
@@ -997,6 +1741,106 @@ pub enum AnyTableSnapshot<C: CurveGroup> {
     Complete(TableAtComplete<C>),
 }
 
+impl<C: CurveGroup> CanonicalSerialize for AnyTableSnapshot<C> {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        match self {
+            AnyTableSnapshot::Shuffling(table) => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::Dealing(table) => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::Preflop(table) => {
+                2u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::Flop(table) => {
+                3u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::Turn(table) => {
+                4u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::River(table) => {
+                5u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::Showdown(table) => {
+                6u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+            AnyTableSnapshot::Complete(table) => {
+                7u8.serialize_with_mode(&mut writer, compress)?;
+                table.serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        1 + match self {
+            AnyTableSnapshot::Shuffling(table) => table.serialized_size(compress),
+            AnyTableSnapshot::Dealing(table) => table.serialized_size(compress),
+            AnyTableSnapshot::Preflop(table) => table.serialized_size(compress),
+            AnyTableSnapshot::Flop(table) => table.serialized_size(compress),
+            AnyTableSnapshot::Turn(table) => table.serialized_size(compress),
+            AnyTableSnapshot::River(table) => table.serialized_size(compress),
+            AnyTableSnapshot::Showdown(table) => table.serialized_size(compress),
+            AnyTableSnapshot::Complete(table) => table.serialized_size(compress),
+        }
+    }
+}
+
+impl<C: CurveGroup> CanonicalDeserialize for AnyTableSnapshot<C> {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let discriminant = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        match discriminant {
+            0 => Ok(AnyTableSnapshot::Shuffling(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            1 => Ok(AnyTableSnapshot::Dealing(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            2 => Ok(AnyTableSnapshot::Preflop(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            3 => Ok(AnyTableSnapshot::Flop(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            4 => Ok(AnyTableSnapshot::Turn(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            5 => Ok(AnyTableSnapshot::River(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            6 => Ok(AnyTableSnapshot::Showdown(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            7 => Ok(AnyTableSnapshot::Complete(
+                TableSnapshot::deserialize_with_mode(&mut reader, compress, validate)?,
+            )),
+            _ => Err(ark_serialize::SerializationError::InvalidData),
+        }
+    }
+}
+
+impl<C: CurveGroup> ark_serialize::Valid for AnyTableSnapshot<C> {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
 impl<C: CurveGroup> AnyTableSnapshot<C> {
     pub fn state_hash(&self) -> StateHash {
         match self {
@@ -1063,6 +1907,31 @@ impl<C: CurveGroup> AnyTableSnapshot<C> {
         }
     }
 
+    /// Shuffle record for the hand; every phase carries it once shuffling has started.
+    pub fn shuffling(&self) -> &ShufflingSnapshot<C> {
+        match self {
+            AnyTableSnapshot::Shuffling(table) => &table.shuffling,
+            AnyTableSnapshot::Dealing(table) => &table.shuffling,
+            AnyTableSnapshot::Preflop(table) => &table.shuffling,
+            AnyTableSnapshot::Flop(table) => &table.shuffling,
+            AnyTableSnapshot::Turn(table) => &table.shuffling,
+            AnyTableSnapshot::River(table) => &table.shuffling,
+            AnyTableSnapshot::Showdown(table) => &table.shuffling,
+            AnyTableSnapshot::Complete(table) => &table.shuffling,
+        }
+    }
+
+    /// Betting state of a hand in one of its betting rounds.
+    pub fn betting_state(&self) -> Option<&BettingState> {
+        match self {
+            AnyTableSnapshot::Preflop(table) => Some(&table.betting.state),
+            AnyTableSnapshot::Flop(table) => Some(&table.betting.state),
+            AnyTableSnapshot::Turn(table) => Some(&table.betting.state),
+            AnyTableSnapshot::River(table) => Some(&table.betting.state),
+            _ => None,
+        }
+    }
+
     pub fn failure_reason(&self) -> Option<&str> {
         match self.status() {
             SnapshotStatus::Success => None,
@@ -1233,6 +2102,17 @@ fn hand_config_from_model(model: &hand_configs::Model) -> Result<HandConfig> {
         big_blind_seat: u8::try_from(model.big_blind_seat)
             .map_err(|_| anyhow!("big blind seat exceeds u8 range"))?,
         check_raise_allowed: model.check_raise_allowed,
+        hole_cards: u8::try_from(model.hole_cards)
+            .map_err(|_| anyhow!("hole card count exceeds u8 range"))?,
+        max_bet_bb: model
+            .max_bet_bb
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|_| anyhow!("max bet multiple exceeds u32 range"))?,
+        dealing_order: u8::try_from(model.dealing_order)
+            .ok()
+            .and_then(DealingOrder::from_byte)
+            .ok_or_else(|| anyhow!("dealing order {} is invalid", model.dealing_order))?,
     })
 }
 
@@ -1275,6 +2155,7 @@ where
                 player_id,
                 nonce,
                 seat,
+                auto_muck_losers: seat_row.auto_muck_losers,
             },
         );
         seating.insert(seat, Some(player_key));
@@ -1507,6 +2388,7 @@ where
         let empty_reveals = || RevealsSnapshot::<C> {
             board: Vec::new(),
             revealed_holes: BTreeMap::new(),
+            mucked: BTreeMap::new(),
         };
 
         let table = match betting_snapshot.state.street {
@@ -1646,3 +2528,179 @@ pub fn clone_snapshot_for_failure<C: CurveGroup>(
     }
     failed
 }
+
+/// Copy of `snapshot` that is safe to show a spectator: every player-targeted hole-card
+/// ciphertext and unblinding value is dropped, along with any revealed hand whose seat has since
+/// mucked. The board, betting state, card plan and hashes are kept as-is, so the result is for
+/// display only and will not verify against the ledger.
+pub fn redact_for_spectator<C: CurveGroup>(snapshot: &AnyTableSnapshot<C>) -> AnyTableSnapshot<C> {
+    retain_hole_cards(snapshot, |_| false)
+}
+
+/// Ledger messages that may accompany a [`redact_for_spectator`] snapshot. Player-targeted
+/// blinding and unblinding shares are dropped, since they would restore what the snapshot
+/// redaction removed.
+pub fn redact_messages_for_spectator<C: CurveGroup>(
+    messages: Vec<FinalizedAnyMessageEnvelope<C>>,
+) -> Vec<FinalizedAnyMessageEnvelope<C>> {
+    messages
+        .into_iter()
+        .filter(|message| !message.envelope.message.value.is_player_targeted())
+        .collect()
+}
+
+/// A snapshot as one seated player may see it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "C: CanonicalSerialize",
+    deserialize = "C: CanonicalDeserialize"
+))]
+pub struct PlayerTableView<C: CurveGroup> {
+    pub seat: SeatId,
+    pub snapshot: AnyTableSnapshot<C>,
+}
+
+/// Like [`redact_for_spectator`], except that `seat`'s own hole ciphertexts and unblinding
+/// values are kept. This is what the server may send to that player; it must never send them
+/// the unredacted snapshot.
+pub fn view_for_player<C: CurveGroup>(
+    snapshot: &AnyTableSnapshot<C>,
+    seat: SeatId,
+) -> PlayerTableView<C> {
+    PlayerTableView {
+        seat,
+        snapshot: retain_hole_cards(snapshot, |hole_seat| hole_seat == seat),
+    }
+}
+
+/// Highest reconnect nonce accepted per hand and seat.
+#[derive(Debug, Default)]
+pub struct ReconnectNonces {
+    last: Mutex<HashMap<(HandId, SeatId), u64>>,
+}
+
+impl ReconnectNonces {
+    fn accept(&self, hand_id: HandId, seat: SeatId, nonce: u64) -> Result<()> {
+        let mut last = self.last.lock().expect("reconnect nonces poisoned");
+        if let Some(previous) = last.get(&(hand_id, seat)) {
+            ensure!(
+                nonce > *previous,
+                "reconnect nonce {nonce} for seat {seat} is not above {previous}"
+            );
+        }
+        last.insert((hand_id, seat), nonce);
+        Ok(())
+    }
+}
+
+/// The view a returning player gets back after a disconnect: the request must name this hand
+/// and an occupied seat, carry a signature from that seat's player key, and use a nonce above
+/// any this seat already reconnected with.
+pub fn reconnect_player<C: CurveGroup>(
+    snapshot: &AnyTableSnapshot<C>,
+    request: &SignedReconnectRequest<C>,
+    validator: &dyn SignatureValidator<C>,
+    nonces: &ReconnectNonces,
+) -> Result<PlayerTableView<C>> {
+    match snapshot {
+        AnyTableSnapshot::Shuffling(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::Dealing(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::Preflop(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::Flop(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::Turn(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::River(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::Showdown(table) => verify_reconnect(table, request, validator)?,
+        AnyTableSnapshot::Complete(table) => verify_reconnect(table, request, validator)?,
+    }
+    let reconnect = &request.value;
+    nonces.accept(reconnect.hand_id, reconnect.seat, reconnect.nonce)?;
+    Ok(view_for_player(snapshot, reconnect.seat))
+}
+
+fn verify_reconnect<P, C>(
+    table: &TableSnapshot<P, C>,
+    request: &SignedReconnectRequest<C>,
+    validator: &dyn SignatureValidator<C>,
+) -> Result<()>
+where
+    P: HandPhase<C>,
+    C: CurveGroup,
+{
+    let reconnect = &request.value;
+    let seat = reconnect.seat;
+    ensure!(
+        reconnect.game_id == table.game_id && Some(reconnect.hand_id) == table.hand_id,
+        "reconnect request is for game {} hand {}, not this hand",
+        reconnect.game_id,
+        reconnect.hand_id
+    );
+
+    let player_identity = table
+        .identity_at_seat(seat)
+        .with_context(|| format!("seat {seat} has no player assigned"))?;
+    ensure!(
+        player_identity.player_key == reconnect.player_key,
+        "reconnect request player key does not match seat {seat}"
+    );
+
+    ensure!(
+        !request.signature.is_empty(),
+        "reconnect request for seat {seat} is unsigned"
+    );
+    let transcript = crate::signing::signing_bytes(reconnect)?;
+    ensure!(
+        validator.verify(&player_identity.public_key, &transcript, &request.signature),
+        "reconnect request for seat {seat} has an invalid signature"
+    );
+    Ok(())
+}
+
+fn retain_hole_cards<C: CurveGroup>(
+    snapshot: &AnyTableSnapshot<C>,
+    keep: impl Fn(SeatId) -> bool,
+) -> AnyTableSnapshot<C> {
+    let mut redacted = snapshot.clone();
+    match &mut redacted {
+        AnyTableSnapshot::Shuffling(_) => {}
+        AnyTableSnapshot::Dealing(table) => retain_dealt_holes(&mut table.dealing, &keep),
+        AnyTableSnapshot::Preflop(table) => retain_dealt_holes(&mut table.dealing, &keep),
+        AnyTableSnapshot::Flop(table) => retain_dealt_holes(&mut table.dealing, &keep),
+        AnyTableSnapshot::Turn(table) => retain_dealt_holes(&mut table.dealing, &keep),
+        AnyTableSnapshot::River(table) => retain_dealt_holes(&mut table.dealing, &keep),
+        AnyTableSnapshot::Showdown(table) => {
+            retain_dealt_holes(&mut table.dealing, &keep);
+            retain_shown_hands(&mut table.reveals);
+        }
+        AnyTableSnapshot::Complete(table) => {
+            retain_dealt_holes(&mut table.dealing, &keep);
+            retain_shown_hands(&mut table.reveals);
+        }
+    }
+    redacted
+}
+
+fn retain_dealt_holes<C: CurveGroup>(
+    dealing: &mut DealingSnapshot<C>,
+    keep: &impl Fn(SeatId) -> bool,
+) {
+    dealing
+        .player_ciphertexts
+        .retain(|(seat, _), _| keep(*seat));
+    // The shares combine into the unblinding value, so they follow it.
+    dealing
+        .player_unblinding_shares
+        .retain(|(seat, _), _| keep(*seat));
+    dealing
+        .player_unblinding_combined
+        .retain(|(seat, _), _| keep(*seat));
+}
+
+fn retain_shown_hands<C: CurveGroup>(reveals: &mut RevealsSnapshot<C>) {
+    let RevealsSnapshot {
+        revealed_holes,
+        mucked,
+        auto_mucked,
+        ..
+    } = reveals;
+    revealed_holes.retain(|seat, _| !mucked.contains_key(seat) && !auto_mucked.contains(seat));
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::{hash_map::Entry, HashMap};
 use std::sync::{Arc, RwLock};
 
@@ -5,22 +6,49 @@ use anyhow::{bail, Context};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use crate::curve_absorb::CurveAbsorb;
+use crate::engine::nl::types::SeatId;
 use crate::ledger::actor::{AnyActor, PlayerActor, ShufflerActor};
 use crate::ledger::hash::{default_poseidon_hasher, LedgerHasher};
 use crate::ledger::messages::{
     AnyGameMessage, AnyMessageEnvelope, EnvelopedMessage, FinalizedAnyMessageEnvelope, FlopStreet,
-    GameBlindingDecryptionMessage, GamePartialUnblindingShareMessage, GamePlayerMessage,
-    GameShowdownMessage, GameShuffleMessage, PreflopStreet, RiverStreet, TurnStreet,
+    GameBlindingDecryptionMessage, GameNonceCommitmentMessage, GamePartialUnblindingShareMessage,
+    GamePlayerMessage, GameShowdownMessage, GameShuffleMessage, MuckDeclaration, PreflopStreet,
+    RiverStreet, TurnStreet,
+};
+use crate::ledger::snapshot::{
+    clone_snapshot_for_failure, AnyTableSnapshot, BettingSnapshot, BoardCardSlot, CardDestination,
+    DealingSnapshot, SnapshotSeq, SnapshotStatus,
 };
-use crate::ledger::snapshot::{clone_snapshot_for_failure, AnyTableSnapshot, SnapshotStatus};
 use crate::ledger::transition::apply_transition;
-use crate::ledger::types::{HandId, StateHash};
+use crate::ledger::types::{EntityKind, HandId, NonceKey, ShufflerId, StateHash};
 use crate::signing::{DomainSeparated, WithSignature};
+use tracing::warn;
+
+const LOG_TARGET: &str = "legit_poker::ledger::state";
+
 type SharedHasher = Arc<dyn LedgerHasher + Send + Sync>;
 
+/// Leading bytes of every [`LedgerState::export`] payload.
+const EXPORT_MAGIC: &[u8; 4] = b"LPLS";
+
+/// Layout version of [`LedgerState::export`]. Bump it whenever [`ExportedHand`] or the snapshot
+/// encoding changes; imports refuse other versions, and a replica that cannot import simply
+/// falls back to replaying from the database.
+pub const LEDGER_EXPORT_VERSION: u16 = 2;
+
+/// One hand as carried by [`LedgerState::export`].
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+struct ExportedHand<C: CurveGroup> {
+    hand_id: HandId,
+    tip_hash: StateHash,
+    snapshots: Vec<AnyTableSnapshot<C>>,
+    /// Accepted messages in arrival order, each with the snapshot it produced.
+    messages: Vec<(FinalizedAnyMessageEnvelope<C>, AnyTableSnapshot<C>)>,
+}
+
 struct HandLedger<C: CurveGroup> {
     tip_hash: StateHash,
     tip_snapshot: AnyTableSnapshot<C>,
@@ -43,13 +71,24 @@ impl<C: CurveGroup> HandLedger<C> {
         }
     }
 
-    fn insert(&mut self, snapshot: AnyTableSnapshot<C>, make_tip: bool) {
+    fn insert(&mut self, snapshot: AnyTableSnapshot<C>, make_tip: bool) -> UpsertOutcome {
         let hash = snapshot.state_hash();
+        let existing_sequence = self.tip_snapshot.sequence();
+        let outcome = match snapshot.sequence().cmp(&existing_sequence) {
+            Ordering::Greater => UpsertOutcome::Advanced,
+            Ordering::Less => UpsertOutcome::ReplacedNewer,
+            Ordering::Equal if hash == self.tip_hash => UpsertOutcome::Equal,
+            Ordering::Equal => UpsertOutcome::Conflict {
+                existing: self.tip_hash,
+                incoming: hash,
+            },
+        };
         self.snapshots.insert(hash, snapshot.clone());
         if make_tip || hash == self.tip_hash {
             self.tip_hash = hash;
             self.tip_snapshot = snapshot;
         }
+        outcome
     }
 
     fn insert_message_snapshot(
@@ -63,6 +102,106 @@ impl<C: CurveGroup> HandLedger<C> {
     }
 }
 
+/// How an upserted snapshot relates to the tip it was compared against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    /// No snapshot was recorded for the hand yet.
+    Inserted,
+    /// The previous tip was at an earlier sequence.
+    Advanced,
+    /// The previous tip had the same sequence and state hash.
+    Equal,
+    /// The previous tip was at a later sequence.
+    ReplacedNewer,
+    /// The previous tip had the same sequence but a different state hash.
+    Conflict {
+        existing: StateHash,
+        incoming: StateHash,
+    },
+}
+
+/// What a hand is currently blocked on, derived from its tip snapshot.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PendingWork {
+    /// Shufflers that have not yet submitted their shuffle, in turn order.
+    pub awaiting_shuffles: Vec<ShufflerId>,
+    /// Hole cards whose player ciphertext or combined unblinding share is still missing.
+    pub awaiting_blinding: Vec<(SeatId, u8)>,
+    /// Board cards for the current street that have not been revealed yet.
+    pub awaiting_board: Vec<BoardCardSlot>,
+    /// Seat expected to act next, unless betting is locked because everyone is all-in.
+    pub to_act: Option<SeatId>,
+}
+
+impl PendingWork {
+    pub fn from_snapshot<C: CurveGroup>(snapshot: &AnyTableSnapshot<C>) -> Self {
+        match snapshot {
+            AnyTableSnapshot::Shuffling(table) => Self {
+                awaiting_shuffles: table
+                    .shuffling
+                    .expected_order
+                    .iter()
+                    .skip(table.shuffling.steps.len())
+                    .filter_map(|key| table.shufflers.get(key).map(|id| id.shuffler_id))
+                    .collect(),
+                ..Self::default()
+            },
+            AnyTableSnapshot::Dealing(table) => Self {
+                awaiting_blinding: awaiting_blinding(&table.dealing),
+                ..Self::default()
+            },
+            AnyTableSnapshot::Preflop(table) => Self::betting(&table.dealing, &table.betting, 0),
+            AnyTableSnapshot::Flop(table) => Self::betting(&table.dealing, &table.betting, 3),
+            AnyTableSnapshot::Turn(table) => Self::betting(&table.dealing, &table.betting, 4),
+            AnyTableSnapshot::River(table) => Self::betting(&table.dealing, &table.betting, 5),
+            AnyTableSnapshot::Showdown(_) | AnyTableSnapshot::Complete(_) => Self::default(),
+        }
+    }
+
+    /// `board_cards` is how many board cards the street shows (0 preflop, 3 on the flop, ...).
+    fn betting<C: CurveGroup>(
+        dealing: &DealingSnapshot<C>,
+        betting: &BettingSnapshot<C>,
+        board_cards: u8,
+    ) -> Self {
+        let awaiting_board = dealing
+            .card_plan
+            .iter()
+            .filter_map(|(deal_index, destination)| match destination {
+                CardDestination::Board { board_index }
+                    if *board_index < board_cards
+                        && !dealing.community_cards.contains_key(deal_index) =>
+                {
+                    BoardCardSlot::from_board_index(*board_index)
+                }
+                _ => None,
+            })
+            .collect();
+        let to_act = (!betting.state.betting_locked_all_in).then_some(betting.state.to_act);
+
+        Self {
+            awaiting_board,
+            to_act,
+            ..Self::default()
+        }
+    }
+}
+
+fn awaiting_blinding<C: CurveGroup>(dealing: &DealingSnapshot<C>) -> Vec<(SeatId, u8)> {
+    dealing
+        .card_plan
+        .values()
+        .filter_map(|destination| match destination {
+            CardDestination::Hole { seat, hole_index } => Some((*seat, *hole_index)),
+            _ => None,
+        })
+        .filter(|key| {
+            !dealing.player_ciphertexts.contains_key(key)
+                || !dealing.player_unblinding_combined.contains_key(key)
+        })
+        .collect()
+}
+
 pub struct LedgerState<C>
 where
     C: CurveGroup,
@@ -103,6 +242,14 @@ where
             .map(|ledger| (ledger.tip_hash, ledger.tip_snapshot.clone()))
     }
 
+    /// Summarise what the hand's tip snapshot is waiting on, for operator tooling.
+    pub fn pending_work(&self, hand_id: HandId) -> Option<PendingWork> {
+        let guard = self.inner.read().expect("ledger state poisoned");
+        guard
+            .get(&hand_id)
+            .map(|ledger| PendingWork::from_snapshot(&ledger.tip_snapshot))
+    }
+
     pub fn snapshot(&self, hand_id: HandId, hash: StateHash) -> Option<AnyTableSnapshot<C>> {
         let guard = self.inner.read().expect("ledger state poisoned");
         guard
@@ -110,16 +257,48 @@ where
             .and_then(|ledger| ledger.snapshots.get(&hash).cloned())
     }
 
-    pub fn upsert_snapshot(&self, hand_id: HandId, snapshot: AnyTableSnapshot<C>, make_tip: bool) {
+    /// The snapshot at `sequence` on the chain leading to the tip, found by following
+    /// `previous_hash` links back from the tip.
+    pub fn snapshot_at_sequence(
+        &self,
+        hand_id: HandId,
+        sequence: SnapshotSeq,
+    ) -> Option<AnyTableSnapshot<C>> {
+        let guard = self.inner.read().expect("ledger state poisoned");
+        let ledger = guard.get(&hand_id)?;
+        let mut current = &ledger.tip_snapshot;
+        while current.sequence() > sequence {
+            current = ledger.snapshots.get(&current.previous_hash()?)?;
+        }
+        (current.sequence() == sequence).then(|| current.clone())
+    }
+
+    pub fn upsert_snapshot(
+        &self,
+        hand_id: HandId,
+        snapshot: AnyTableSnapshot<C>,
+        make_tip: bool,
+    ) -> UpsertOutcome {
         let mut guard = self.inner.write().expect("ledger state poisoned");
-        match guard.entry(hand_id) {
-            Entry::Occupied(mut entry) => {
-                entry.get_mut().insert(snapshot, make_tip);
-            }
+        let sequence = snapshot.sequence();
+        let outcome = match guard.entry(hand_id) {
+            Entry::Occupied(mut entry) => entry.get_mut().insert(snapshot, make_tip),
             Entry::Vacant(entry) => {
                 entry.insert(HandLedger::new(snapshot));
+                UpsertOutcome::Inserted
             }
+        };
+        if let UpsertOutcome::Conflict { existing, incoming } = outcome {
+            warn!(
+                target = LOG_TARGET,
+                hand_id,
+                sequence,
+                ?existing,
+                ?incoming,
+                "upserted snapshot conflicts with tip at the same sequence"
+            );
         }
+        outcome
     }
 
     pub fn insert_message_snapshot(
@@ -197,6 +376,112 @@ where
         guard.remove(&hand_id);
     }
 
+    /// Last accepted nonce per actor, derived from the recorded messages of every hand.
+    ///
+    /// These are the counters `LedgerVerifier` enforces; seed a fresh verifier with them via
+    /// `LedgerVerifier::restore_nonces` after importing a state.
+    pub fn nonce_counters(&self) -> HashMap<NonceKey, u64> {
+        let guard = self.inner.read().expect("ledger state poisoned");
+        let mut counters = HashMap::new();
+        for (hand_id, ledger) in guard.iter() {
+            for hash in &ledger.message_order {
+                let Some((message, _)) = ledger.message_snapshots.get(hash) else {
+                    continue;
+                };
+                let envelope = &message.envelope;
+                let Some(key) = nonce_key(*hand_id, &envelope.actor) else {
+                    continue;
+                };
+                let last = counters.entry(key).or_insert(envelope.nonce);
+                *last = (*last).max(envelope.nonce);
+            }
+        }
+        counters
+    }
+
+    /// Serialize every in-memory hand (tip, snapshots and message log) so another process can
+    /// warm-start with [`LedgerState::import`] instead of replaying from the database. Nonce
+    /// counters travel implicitly with the message log; see [`LedgerState::nonce_counters`].
+    pub fn export(&self) -> anyhow::Result<Vec<u8>> {
+        let guard = self.inner.read().expect("ledger state poisoned");
+        let mut hand_ids: Vec<_> = guard.keys().copied().collect();
+        hand_ids.sort_unstable();
+        let hands: Vec<ExportedHand<C>> = hand_ids
+            .into_iter()
+            .map(|hand_id| {
+                let ledger = &guard[&hand_id];
+                ExportedHand {
+                    hand_id,
+                    tip_hash: ledger.tip_hash,
+                    snapshots: ledger.snapshots.values().cloned().collect(),
+                    messages: ledger
+                        .message_order
+                        .iter()
+                        .filter_map(|hash| ledger.message_snapshots.get(hash).cloned())
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let mut bytes = EXPORT_MAGIC.to_vec();
+        LEDGER_EXPORT_VERSION
+            .serialize_compressed(&mut bytes)
+            .context("failed to write ledger export version")?;
+        hands
+            .serialize_compressed(&mut bytes)
+            .context("failed to serialize exported hands")?;
+        Ok(bytes)
+    }
+
+    /// Rebuild a state from [`LedgerState::export`] output, hashing new snapshots with `hasher`.
+    pub fn import_with_hasher(bytes: &[u8], hasher: SharedHasher) -> anyhow::Result<Self> {
+        let Some(mut reader) = bytes.strip_prefix(EXPORT_MAGIC.as_slice()) else {
+            bail!("not a ledger state export");
+        };
+        let version = u16::deserialize_compressed(&mut reader)
+            .context("failed to read ledger export version")?;
+        if version != LEDGER_EXPORT_VERSION {
+            bail!("unsupported ledger export version {version} (expected {LEDGER_EXPORT_VERSION})");
+        }
+        let hands = Vec::<ExportedHand<C>>::deserialize_compressed(&mut reader)
+            .context("failed to read exported hands")?;
+        if !reader.is_empty() {
+            bail!("{} trailing bytes after ledger export", reader.len());
+        }
+
+        let mut inner = HashMap::with_capacity(hands.len());
+        for exported in hands {
+            let hand_id = exported.hand_id;
+            let snapshots: HashMap<_, _> = exported
+                .snapshots
+                .into_iter()
+                .map(|snapshot| (snapshot.state_hash(), snapshot))
+                .collect();
+            let tip_snapshot = snapshots
+                .get(&exported.tip_hash)
+                .cloned()
+                .with_context(|| format!("exported hand {hand_id} is missing its tip snapshot"))?;
+            let mut ledger = HandLedger {
+                tip_hash: exported.tip_hash,
+                tip_snapshot,
+                snapshots,
+                message_snapshots: HashMap::new(),
+                message_order: Vec::new(),
+            };
+            for (message, snapshot) in exported.messages {
+                ledger.insert_message_snapshot(message, snapshot);
+            }
+            if inner.insert(hand_id, ledger).is_some() {
+                bail!("ledger export contains hand {hand_id} twice");
+            }
+        }
+
+        Ok(Self {
+            inner: RwLock::new(inner),
+            hasher,
+        })
+    }
+
     pub fn preview_event(
         &self,
         event: &AnyMessageEnvelope<C>,
@@ -269,6 +554,19 @@ where
     }
 }
 
+pub(crate) fn nonce_key<C: CurveGroup>(hand_id: HandId, actor: &AnyActor<C>) -> Option<NonceKey> {
+    let (entity_kind, entity_id) = match actor {
+        AnyActor::Player { player_id, .. } => (EntityKind::Player, *player_id as i64),
+        AnyActor::Shuffler { shuffler_id, .. } => (EntityKind::Shuffler, *shuffler_id),
+        AnyActor::None => return None,
+    };
+    Some(NonceKey {
+        hand_id,
+        entity_kind,
+        entity_id,
+    })
+}
+
 fn remap_signature<C, M>(
     original: &WithSignature<crate::ledger::SignatureBytes, AnyGameMessage<C>>,
     value: M,
@@ -291,11 +589,16 @@ where
     pub fn new() -> Self {
         Self::with_hasher(default_poseidon_hasher::<C::BaseField>())
     }
+
+    /// [`LedgerState::import_with_hasher`] with the default Poseidon hasher.
+    pub fn import(bytes: &[u8]) -> anyhow::Result<Self> {
+        Self::import_with_hasher(bytes, default_poseidon_hasher::<C::BaseField>())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
 
     use super::*;
     use crate::ledger::actor::AnyActor;
@@ -338,9 +641,12 @@ mod tests {
             steps: vec![ShufflingStep {
                 shuffler_public_key: C::zero(),
                 proof: sample_shuffle_proof(),
+                beacon_round: None,
+                vrf_nonce: None,
             }],
             final_deck: std::array::from_fn(|_| sample_cipher()),
             expected_order: vec![shuffler_key.clone()],
+            nonce_commitments: Vec::new(),
         };
 
         let player_public = C::zero();
@@ -361,6 +667,8 @@ mod tests {
         let _reveals: RevealsSnapshot<C> = RevealsSnapshot {
             board: Vec::new(),
             revealed_holes: BTreeMap::new(),
+            mucked: BTreeMap::new(),
+            auto_mucked: BTreeSet::new(),
         };
 
         let hand_cfg = HandConfig {
@@ -373,6 +681,9 @@ mod tests {
             small_blind_seat: 0,
             big_blind_seat: 0,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
 
         let mut seating_map: SeatingMap<C> = BTreeMap::new();
@@ -399,6 +710,7 @@ mod tests {
                 player_id: 0,
                 nonce: 0,
                 seat: 0,
+                auto_muck_losers: false,
             },
         );
 
@@ -437,6 +749,33 @@ mod tests {
         assert_eq!(tip_hash, snapshot.state_hash);
     }
 
+    #[test]
+    fn upsert_reports_conflicting_snapshot_at_same_sequence() {
+        let state = LedgerState::<Curve>::new();
+        let hasher = state.hasher();
+        let snapshot = sample_table_snapshot::<Curve>(&*hasher);
+        assert_eq!(
+            state.upsert_snapshot(3, AnyTableSnapshot::Shuffling(snapshot.clone()), true),
+            UpsertOutcome::Inserted
+        );
+        assert_eq!(
+            state.upsert_snapshot(3, AnyTableSnapshot::Shuffling(snapshot.clone()), true),
+            UpsertOutcome::Equal
+        );
+
+        let mut diverged = snapshot.clone();
+        diverged.game_id = 99;
+        diverged.initialize_hash(&*hasher);
+        assert_ne!(diverged.state_hash, snapshot.state_hash);
+        assert_eq!(
+            state.upsert_snapshot(3, AnyTableSnapshot::Shuffling(diverged.clone()), true),
+            UpsertOutcome::Conflict {
+                existing: snapshot.state_hash,
+                incoming: diverged.state_hash,
+            }
+        );
+    }
+
     #[test]
     fn snapshot_lookup_by_hash() {
         let state = LedgerState::<Curve>::new();
@@ -500,6 +839,7 @@ mod tests {
                 value: AnyGameMessage::Shuffle(shuffle_message),
                 signature: Vec::new(),
             },
+            correlation_id: None,
         };
 
         let failure_reason = "boom".to_string();
@@ -524,6 +864,169 @@ mod tests {
         assert_eq!(tip.sequence(), initial_sequence + 1);
         assert_eq!(tip.previous_hash(), Some(initial_hash));
     }
+
+    fn recorded_message(
+        hand_id: HandId,
+        actor: AnyActor<Curve>,
+        nonce: u64,
+        snapshot: &AnyTableSnapshot<Curve>,
+    ) -> FinalizedAnyMessageEnvelope<Curve> {
+        let envelope = AnyMessageEnvelope {
+            hand_id,
+            game_id: 11,
+            actor,
+            nonce,
+            public_key: Curve::zero(),
+            message: WithSignature {
+                value: AnyGameMessage::Shuffle(GameShuffleMessage::new(
+                    std::array::from_fn(|_| sample_cipher::<Curve>()),
+                    std::array::from_fn(|_| sample_cipher::<Curve>()),
+                    sample_shuffle_proof::<Curve>(),
+                    0,
+                )),
+                signature: Vec::new(),
+            },
+            correlation_id: None,
+        };
+        FinalizedAnyMessageEnvelope::new(
+            envelope,
+            SnapshotStatus::Success,
+            snapshot.event_phase(),
+            snapshot.sequence(),
+        )
+    }
+
+    #[test]
+    fn export_round_trips_tips_and_nonce_counters() {
+        use crate::ledger::test_support::{fixture_preflop_snapshot, FixtureContext};
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let state = LedgerState::<Curve>::with_hasher(Arc::clone(&ctx.hasher));
+
+        let shuffling = AnyTableSnapshot::Shuffling(sample_table_snapshot::<Curve>(&*ctx.hasher));
+        let shuffler = AnyActor::Shuffler {
+            shuffler_id: 0,
+            shuffler_key: crate::ledger::CanonicalKey::new(Curve::zero()),
+        };
+        state.upsert_snapshot(1, shuffling.clone(), true);
+        let mut shuffled = shuffling.clone();
+        if let AnyTableSnapshot::Shuffling(table) = &mut shuffled {
+            table.sequence = 1;
+            table.previous_hash = Some(table.state_hash);
+            table.state_hash = StateHash::new([1; 32]);
+        }
+        state.upsert_snapshot(1, shuffled.clone(), true);
+        for (nonce, snapshot) in [(0, &shuffling), (1, &shuffled)] {
+            let message = recorded_message(1, shuffler.clone(), nonce, snapshot);
+            state.insert_message_snapshot(1, message, snapshot.clone());
+        }
+
+        let preflop = fixture_preflop_snapshot(&ctx);
+        let identity = preflop
+            .players
+            .values()
+            .next()
+            .expect("fixture has players");
+        let player = AnyActor::Player {
+            seat_id: identity.seat,
+            player_id: identity.player_id,
+            player_key: identity.player_key.clone(),
+        };
+        let preflop = AnyTableSnapshot::Preflop(preflop);
+        state.upsert_snapshot(2, preflop.clone(), true);
+        let message = recorded_message(2, player, 4, &preflop);
+        state.insert_message_snapshot(2, message, preflop);
+
+        let exported = state.export().expect("export state");
+        let imported = LedgerState::<Curve>::import_with_hasher(&exported, Arc::clone(&ctx.hasher))
+            .expect("import exported state");
+
+        let mut hands = imported.hands();
+        hands.sort_unstable();
+        assert_eq!(hands, vec![1, 2]);
+        for hand_id in hands {
+            assert_eq!(imported.tip_hash(hand_id), state.tip_hash(hand_id));
+            assert_eq!(
+                imported.messages_up_to_sequence(hand_id, u32::MAX).len(),
+                state.messages_up_to_sequence(hand_id, u32::MAX).len()
+            );
+        }
+        assert!(imported.snapshot(1, shuffling.state_hash()).is_some());
+        let counters = imported.nonce_counters();
+        assert_eq!(counters, state.nonce_counters());
+        assert_eq!(counters.len(), 2);
+        assert_eq!(
+            counters[&NonceKey {
+                hand_id: 1,
+                entity_kind: EntityKind::Shuffler,
+                entity_id: 0,
+            }],
+            1
+        );
+    }
+
+    #[test]
+    fn import_rejects_unknown_export_version() {
+        let state = LedgerState::<Curve>::new();
+        let hasher = state.hasher();
+        let snapshot = sample_table_snapshot::<Curve>(&*hasher);
+        state.upsert_snapshot(5, AnyTableSnapshot::Shuffling(snapshot), true);
+
+        let mut bytes = state.export().expect("export state");
+        let version = EXPORT_MAGIC.len();
+        bytes[version..version + 2].copy_from_slice(&(LEDGER_EXPORT_VERSION + 1).to_le_bytes());
+
+        let err = LedgerState::<Curve>::import(&bytes).expect_err("future version rejected");
+        assert!(err
+            .to_string()
+            .contains("unsupported ledger export version"));
+        assert!(LedgerState::<Curve>::import(b"not an export").is_err());
+    }
+
+    #[test]
+    fn pending_work_lists_hole_cards_awaiting_blinding() {
+        use crate::ledger::test_support::{fixture_dealing_snapshot, FixtureContext};
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_dealing_snapshot(&ctx);
+        snapshot.dealing.player_ciphertexts.remove(&(1, 0));
+        snapshot.dealing.player_unblinding_combined.remove(&(2, 1));
+
+        let state = LedgerState::<Curve>::with_hasher(Arc::clone(&ctx.hasher));
+        state.upsert_snapshot(ctx.hand_id, AnyTableSnapshot::Dealing(snapshot), true);
+
+        let pending = state.pending_work(ctx.hand_id).expect("hand tracked");
+        assert_eq!(pending.awaiting_blinding, vec![(1, 0), (2, 1)]);
+        assert!(pending.awaiting_shuffles.is_empty());
+        assert!(pending.awaiting_board.is_empty());
+        assert_eq!(pending.to_act, None);
+    }
+
+    #[test]
+    fn pending_work_reports_seat_to_act_during_betting() {
+        use crate::ledger::test_support::{fixture_flop_snapshot, FixtureContext};
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_flop_snapshot(&ctx);
+        snapshot.dealing.community_cards.clear();
+        let expected_seat = snapshot.betting.state.to_act;
+
+        let state = LedgerState::<Curve>::with_hasher(Arc::clone(&ctx.hasher));
+        state.upsert_snapshot(ctx.hand_id, AnyTableSnapshot::Flop(snapshot), true);
+
+        let pending = state.pending_work(ctx.hand_id).expect("hand tracked");
+        assert_eq!(pending.to_act, Some(expected_seat));
+        assert_eq!(
+            pending.awaiting_board,
+            vec![
+                BoardCardSlot::Flop(0),
+                BoardCardSlot::Flop(1),
+                BoardCardSlot::Flop(2)
+            ]
+        );
+        assert!(pending.awaiting_blinding.is_empty());
+        assert!(state.pending_work(ctx.hand_id + 1).is_none());
+    }
 }
 
 impl<C> LedgerState<C>
@@ -567,6 +1070,12 @@ where
             AnyGameMessage::Showdown(message) => {
                 self.apply_showdown(snapshot, event, message.clone(), hasher)
             }
+            AnyGameMessage::NonceCommitment(message) => {
+                self.apply_nonce_commitment(snapshot, event, message.clone(), hasher)
+            }
+            AnyGameMessage::Muck(message) => {
+                self.apply_muck(snapshot, event, message.clone(), hasher)
+            }
         }
     }
 
@@ -576,7 +1085,10 @@ where
         event: &AnyMessageEnvelope<C>,
         message: GameShuffleMessage<C>,
         hasher: &SharedHasher,
-    ) -> anyhow::Result<AnyTableSnapshot<C>> {
+    ) -> anyhow::Result<AnyTableSnapshot<C>>
+    where
+        C::BaseField: PrimeField,
+    {
         let table = match snapshot {
             AnyTableSnapshot::Shuffling(table) => table,
             _ => bail!("shuffle message can only be applied during shuffling phase"),
@@ -606,6 +1118,42 @@ where
         apply_transition(table, &envelope, hasher)
     }
 
+    fn apply_nonce_commitment(
+        &self,
+        snapshot: AnyTableSnapshot<C>,
+        event: &AnyMessageEnvelope<C>,
+        message: GameNonceCommitmentMessage<C>,
+        hasher: &SharedHasher,
+    ) -> anyhow::Result<AnyTableSnapshot<C>> {
+        let table = match snapshot {
+            AnyTableSnapshot::Shuffling(table) => table,
+            _ => bail!("nonce commitment can only be applied during shuffling phase"),
+        };
+
+        let actor = match &event.actor {
+            AnyActor::Shuffler {
+                shuffler_id,
+                shuffler_key,
+                ..
+            } => ShufflerActor {
+                shuffler_id: *shuffler_id,
+                shuffler_key: shuffler_key.clone(),
+            },
+            _ => bail!("nonce commitment must originate from a shuffler"),
+        };
+
+        let envelope = EnvelopedMessage {
+            hand_id: event.hand_id,
+            game_id: event.game_id,
+            actor,
+            nonce: event.nonce,
+            public_key: event.public_key.clone(),
+            message: remap_signature(&event.message, message),
+        };
+
+        apply_transition(table, &envelope, hasher)
+    }
+
     fn apply_blinding(
         &self,
         snapshot: AnyTableSnapshot<C>,
@@ -880,4 +1428,42 @@ where
 
         apply_transition(table, &envelope, hasher)
     }
+
+    fn apply_muck(
+        &self,
+        snapshot: AnyTableSnapshot<C>,
+        event: &AnyMessageEnvelope<C>,
+        message: MuckDeclaration<C>,
+        hasher: &SharedHasher,
+    ) -> anyhow::Result<AnyTableSnapshot<C>> {
+        let table = match snapshot {
+            AnyTableSnapshot::Showdown(table) => table,
+            _ => bail!("muck declaration can only be applied during showdown phase"),
+        };
+
+        let actor = match &event.actor {
+            AnyActor::Player {
+                seat_id,
+                player_id,
+                player_key,
+                ..
+            } => PlayerActor {
+                seat_id: *seat_id,
+                player_id: *player_id,
+                player_key: player_key.clone(),
+            },
+            _ => bail!("muck declaration must originate from a player"),
+        };
+
+        let envelope = EnvelopedMessage {
+            hand_id: event.hand_id,
+            game_id: event.game_id,
+            actor,
+            nonce: event.nonce,
+            public_key: event.public_key.clone(),
+            message: remap_signature(&event.message, message),
+        };
+
+        apply_transition(table, &envelope, hasher)
+    }
 }
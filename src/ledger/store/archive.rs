@@ -0,0 +1,169 @@
+//! Cold storage for completed hands.
+//!
+//! Archiving moves a hand's rows from `events` / `table_snapshots` into `archived_events` /
+//! `archived_snapshots` in a single transaction. The archive tables mirror the live columns,
+//! so archived rows decode through the same entity models and reconstruction helpers.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, IdenStatic, Iterable, Statement,
+    TransactionTrait, Value,
+};
+use tracing::info;
+
+use super::event::model_to_envelope;
+use super::snapshot::reconstruct_snapshot_from_db;
+use crate::curve_absorb::CurveAbsorb;
+use crate::db::entity::{events, table_snapshots};
+use crate::ledger::messages::FinalizedAnyMessageEnvelope;
+use crate::ledger::snapshot::AnyTableSnapshot;
+use crate::ledger::types::HandId;
+
+const LOG_TARGET: &str = "legit_poker::ledger::archive";
+
+/// A hand loaded back from the archive tables.
+pub struct ArchivedHand<C: CurveGroup> {
+    /// Archived events in nonce order.
+    pub events: Vec<FinalizedAnyMessageEnvelope<C>>,
+    /// Number of snapshot rows archived for the hand.
+    pub snapshot_count: usize,
+    /// The highest-sequence archived snapshot, if any were stored.
+    pub tip: Option<AnyTableSnapshot<C>>,
+}
+
+/// Move every event and snapshot of `hand_id` into the archive tables, returning the number of
+/// event and snapshot rows moved.
+pub async fn archive_hand(
+    conn: &DatabaseConnection,
+    hand_id: HandId,
+) -> anyhow::Result<(u64, u64)> {
+    let columns = event_columns();
+    let txn = conn.begin().await?;
+    let moved_events = txn
+        .execute(hand_statement(
+            &format!(
+                "INSERT INTO public.archived_events ({columns}) \
+                 SELECT {columns} FROM public.events WHERE hand_id = $1"
+            ),
+            hand_id,
+        ))
+        .await
+        .context("failed to copy events into archive")?
+        .rows_affected();
+    let moved_snapshots = txn
+        .execute(hand_statement(
+            "INSERT INTO public.archived_snapshots \
+             SELECT * FROM public.table_snapshots WHERE hand_id = $1",
+            hand_id,
+        ))
+        .await
+        .context("failed to copy snapshots into archive")?
+        .rows_affected();
+    txn.execute(hand_statement(
+        "DELETE FROM public.events WHERE hand_id = $1",
+        hand_id,
+    ))
+    .await
+    .context("failed to delete archived events")?;
+    txn.execute(hand_statement(
+        "DELETE FROM public.table_snapshots WHERE hand_id = $1",
+        hand_id,
+    ))
+    .await
+    .context("failed to delete archived snapshots")?;
+    txn.commit().await?;
+
+    info!(
+        target: LOG_TARGET,
+        hand_id,
+        moved_events,
+        moved_snapshots,
+        "archived hand"
+    );
+    Ok((moved_events, moved_snapshots))
+}
+
+/// Load an archived hand. The tip snapshot is rebuilt from the archive rows, so this is
+/// considerably slower than reading a live hand from `LedgerState`.
+pub async fn load_archived_hand<C>(
+    conn: &DatabaseConnection,
+    hand_id: HandId,
+) -> anyhow::Result<ArchivedHand<C>>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField> + Send + Sync + 'static,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + Absorb,
+    C::Affine: Absorb,
+{
+    let event_rows = events::Entity::find()
+        .from_raw_sql(hand_statement(
+            "SELECT * FROM public.archived_events WHERE hand_id = $1 ORDER BY nonce ASC",
+            hand_id,
+        ))
+        .all(conn)
+        .await
+        .context("failed to load archived events")?;
+    let events = event_rows
+        .into_iter()
+        .map(model_to_envelope)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut snapshot_rows = table_snapshots::Entity::find()
+        .from_raw_sql(hand_statement(
+            "SELECT * FROM public.archived_snapshots WHERE hand_id = $1 ORDER BY sequence ASC",
+            hand_id,
+        ))
+        .all(conn)
+        .await
+        .context("failed to load archived snapshots")?;
+    let snapshot_count = snapshot_rows.len();
+    let tip = match snapshot_rows.pop() {
+        Some(row) => Some(reconstruct_snapshot_from_db(row, conn).await?),
+        None => None,
+    };
+
+    Ok(ArchivedHand {
+        events,
+        snapshot_count,
+        tip,
+    })
+}
+
+/// Whether the hand row was created more than `age` ago. Missing hands are never expired.
+pub async fn hand_older_than(
+    conn: &DatabaseConnection,
+    hand_id: HandId,
+    age: Duration,
+) -> anyhow::Result<bool> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT created_at < NOW() - make_interval(secs => $2) AS expired \
+             FROM public.hands WHERE id = $1",
+            vec![Value::from(hand_id), Value::from(age.as_secs_f64())],
+        ))
+        .await
+        .context("failed to query hand age")?;
+    match row {
+        Some(row) => Ok(row.try_get("", "expired")?),
+        None => Ok(false),
+    }
+}
+
+/// Event columns, named explicitly because columns added to `events` after the archive table
+/// was created come after `archived_at` in `archived_events`, so `SELECT *` would misalign them.
+fn event_columns() -> String {
+    events::Column::iter()
+        .map(|column| column.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn hand_statement(sql: &str, hand_id: HandId) -> Statement {
+    Statement::from_sql_and_values(DbBackend::Postgres, sql, vec![Value::from(hand_id)])
+}
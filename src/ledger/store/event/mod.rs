@@ -2,31 +2,73 @@ mod serialization;
 
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Context};
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{BoxStream, StreamExt};
+use sea_orm::sqlx::postgres::PgListener;
 use sea_orm::{
     ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder, Set,
 };
 use serde_json::Value as JsonValue;
-use tracing::{debug, Level};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, warn, Level};
 
 use crate::db::entity::events;
 use crate::ledger::messages::FinalizedAnyMessageEnvelope;
 use crate::ledger::snapshot::{SnapshotSeq, SnapshotStatus};
-use crate::ledger::types::HandId;
+use crate::ledger::types::{EventId, HandId};
 
-pub use self::serialization::model_to_envelope;
+pub use self::serialization::{message_type, model_to_envelope};
 
-use self::serialization::{encode_actor, message_type, to_db_event_phase};
+use self::serialization::{encode_actor, to_db_event_phase};
 
 pub type SharedEventStore<C> = Arc<dyn EventStore<C>>;
 
 #[allow(dead_code)]
 const LOG_TARGET: &str = "legit_poker::ledger::event_store";
 
+/// Channel the `events` insert trigger notifies on (payload: hand id).
+pub const EVENTS_NOTIFY_CHANNEL: &str = "ledger_events";
+/// How often a tail re-queries when no notification arrives (or LISTEN is unavailable).
+const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const TAIL_BUFFER_SIZE: usize = 256;
+
+/// Total order over events from any number of hands.
+///
+/// Events are ordered by creation time; events stamped in the same instant fall back to
+/// `(hand_id, nonce)` and finally to the store-assigned [`EventId`]. Nonces are only unique per
+/// actor, so the event id is what makes the order total: merging the logs of several hands gives
+/// the same sequence regardless of the order they were loaded in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlobalEventOrder {
+    pub created_at: DateTime<Utc>,
+    pub hand_id: HandId,
+    pub nonce: u64,
+    pub event_id: Option<EventId>,
+}
+
+impl GlobalEventOrder {
+    pub fn of<C: CurveGroup>(event: &FinalizedAnyMessageEnvelope<C>) -> Self {
+        Self {
+            created_at: event.created_timestamp,
+            hand_id: event.envelope.hand_id,
+            nonce: event.envelope.nonce,
+            event_id: event.event_id,
+        }
+    }
+}
+
+/// Sort events from one or more hands into [`GlobalEventOrder`].
+pub fn sort_by_global_order<C: CurveGroup>(events: &mut [FinalizedAnyMessageEnvelope<C>]) {
+    events.sort_by_key(GlobalEventOrder::of);
+}
+
 pub fn serialize_curve<C>(value: &C) -> anyhow::Result<Vec<u8>>
 where
     C: CanonicalSerialize,
@@ -105,6 +147,11 @@ where
         message_type: Set(message_type.to_string()),
         payload: Set(payload_value.clone()),
         signature: Set(event.envelope.message.signature.clone()),
+        correlation_id: Set(event
+            .envelope
+            .correlation_id
+            .as_ref()
+            .map(|id| id.as_str().to_string())),
         ..Default::default()
     })
 }
@@ -132,6 +179,16 @@ where
         from: Option<SnapshotSeq>,
         to: Option<SnapshotSeq>,
     ) -> anyhow::Result<Vec<FinalizedAnyMessageEnvelope<C>>>;
+    /// Live stream of a hand's events in insertion order, starting with stored events whose
+    /// [`EventId`] is greater than `after` (or every stored event when `after` is `None`) and
+    /// continuing with events as they are persisted. Every yielded event carries its `event_id`;
+    /// reconnecting clients resume by passing the last one they received. The stream ends when
+    /// dropped.
+    fn tail(
+        &self,
+        hand_id: HandId,
+        after: Option<EventId>,
+    ) -> BoxStream<'static, FinalizedAnyMessageEnvelope<C>>;
     fn connection(&self) -> &DatabaseConnection;
 }
 
@@ -271,11 +328,125 @@ where
         rows.into_iter().map(model_to_envelope).collect()
     }
 
+    fn tail(
+        &self,
+        hand_id: HandId,
+        after: Option<EventId>,
+    ) -> BoxStream<'static, FinalizedAnyMessageEnvelope<C>> {
+        let (tx, rx) = mpsc::channel(TAIL_BUFFER_SIZE);
+        tokio::spawn(run_tail::<C>(self.connection.clone(), hand_id, after, tx));
+        ReceiverStream::new(rx).boxed()
+    }
+
     fn connection(&self) -> &DatabaseConnection {
         &self.connection
     }
 }
 
+async fn listen_for_events(connection: &DatabaseConnection) -> Option<PgListener> {
+    let DatabaseConnection::SqlxPostgresPoolConnection(_) = connection else {
+        return None;
+    };
+    let pool = connection.get_postgres_connection_pool();
+    let mut listener = match PgListener::connect_with(pool).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            warn!(target: LOG_TARGET, error = %err, "LISTEN unavailable; tail will poll");
+            return None;
+        }
+    };
+    if let Err(err) = listener.listen(EVENTS_NOTIFY_CHANNEL).await {
+        warn!(target: LOG_TARGET, error = %err, "LISTEN failed; tail will poll");
+        return None;
+    }
+    Some(listener)
+}
+
+/// Forward a hand's events to `tx` until the receiver is dropped.
+///
+/// Rows are paged by their primary key rather than by nonce, since nonces are only unique per
+/// actor. Each NOTIFY (or poll tick) triggers a re-query, so missed or coalesced notifications
+/// never lose events.
+async fn run_tail<C>(
+    connection: DatabaseConnection,
+    hand_id: HandId,
+    after: Option<EventId>,
+    tx: mpsc::Sender<FinalizedAnyMessageEnvelope<C>>,
+) where
+    C: CurveGroup + CanonicalSerialize + CanonicalDeserialize + Send + Sync + 'static,
+{
+    let mut listener = listen_for_events(&connection).await;
+    let mut last_id = after.unwrap_or(0);
+
+    loop {
+        let rows = events::Entity::find()
+            .filter(events::Column::HandId.eq(hand_id))
+            .filter(events::Column::Id.gt(last_id))
+            .order_by_asc(events::Column::Id)
+            .all(&connection)
+            .await;
+
+        match rows {
+            Ok(rows) => {
+                for row in rows {
+                    let row_id = row.id;
+                    match model_to_envelope::<C>(row) {
+                        Ok(event) => {
+                            if tx.send(event).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(err) => {
+                            warn!(
+                                target: LOG_TARGET,
+                                hand_id,
+                                row_id,
+                                error = %err,
+                                "skipping undecodable event in tail"
+                            );
+                        }
+                    }
+                    last_id = row_id;
+                }
+            }
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    hand_id,
+                    error = %err,
+                    "tail query failed; retrying"
+                );
+            }
+        }
+
+        let woke = match listener.as_mut() {
+            Some(listener) => {
+                tokio::select! {
+                    _ = tx.closed() => return,
+                    received = tokio::time::timeout(TAIL_POLL_INTERVAL, listener.recv()) => {
+                        received
+                    }
+                }
+            }
+            None => {
+                tokio::select! {
+                    _ = tx.closed() => return,
+                    _ = tokio::time::sleep(TAIL_POLL_INTERVAL) => continue,
+                }
+            }
+        };
+        if let Ok(Err(err)) = woke {
+            warn!(
+                target: LOG_TARGET,
+                hand_id,
+                error = %err,
+                "LISTEN connection lost; falling back to polling"
+            );
+            listener = None;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,7 +466,7 @@ mod tests {
     use crate::ledger::snapshot::SnapshotStatus;
     use crate::ledger::state::LedgerState;
     use crate::ledger::store::SeaOrmEventStore;
-    use crate::ledger::types::EventPhase;
+    use crate::ledger::types::{CorrelationId, EventPhase};
     use crate::ledger::typestate::MaybeSaved;
     use crate::ledger::verifier::LedgerVerifier;
     use crate::ledger::{GameId, HandId, LobbyService, LobbyServiceFactory};
@@ -449,9 +620,12 @@ mod tests {
             },
             max_players: 6,
             rake_bps: 0,
-            buy_in: 100,
+            min_buy_in: 100,
+            max_buy_in: 100,
             min_players_to_start: 3,
+            min_shufflers: 1,
             check_raise_allowed: true,
+            max_bet_bb: None,
             action_time_limit: std::time::Duration::from_secs(30),
         };
 
@@ -478,6 +652,7 @@ mod tests {
                     },
                 },
                 Some(0),
+                None,
             )
             .await
             .expect("join_game (host) should succeed in prepare_environment")
@@ -492,7 +667,7 @@ mod tests {
         };
 
         let guest_saved = lobby
-            .join_game(&metadata.record, guest, Some(1))
+            .join_game(&metadata.record, guest, Some(1), None)
             .await
             .expect("join_game (guest) should succeed in prepare_environment")
             .player;
@@ -506,7 +681,7 @@ mod tests {
         };
 
         let third_saved = lobby
-            .join_game(&metadata.record, third_player, Some(2))
+            .join_game(&metadata.record, third_player, Some(2), None)
             .await
             .expect("join_game (third) should succeed in prepare_environment")
             .player;
@@ -527,7 +702,7 @@ mod tests {
 
         let params = CommenceGameParams {
             game_id: metadata.record.state.id,
-            hand_no: 0,
+            hand_no: None,
             button_seat: 0,
             small_blind_seat: 1,
             big_blind_seat: 2,
@@ -572,6 +747,7 @@ mod tests {
                 value: message,
                 signature: Vec::new(),
             },
+            correlation_id: None,
         }
     }
 
@@ -584,6 +760,44 @@ mod tests {
         )
     }
 
+    #[test]
+    fn interleaved_hands_merge_into_a_stable_global_order() {
+        let t0 = DateTime::from_timestamp_millis(1_000).unwrap();
+        let t1 = DateTime::from_timestamp_millis(2_000).unwrap();
+        let event = |event_id, hand_id, nonce, at| {
+            let mut event =
+                finalized(sample_shuffle_envelope(hand_id, 1, nonce)).with_event_id(event_id);
+            event.created_timestamp = at;
+            event
+        };
+        // Events 5 and 6 come from different actors of hand 3 that share nonce 0.
+        let first = vec![
+            event(1, 7, 0, t0),
+            event(2, 7, 1, t1),
+            event(6, 3, 0, t0),
+            event(5, 3, 0, t0),
+            event(4, 3, 1, t1),
+        ];
+        let second = vec![
+            event(4, 3, 1, t1),
+            event(5, 3, 0, t0),
+            event(1, 7, 0, t0),
+            event(2, 7, 1, t1),
+            event(6, 3, 0, t0),
+        ];
+
+        let merged = |mut events: Vec<FinalizedAnyMessageEnvelope<Curve>>| {
+            sort_by_global_order(&mut events);
+            events
+                .iter()
+                .map(|event| event.event_id.expect("fixture events carry ids"))
+                .collect::<Vec<_>>()
+        };
+        // Same-instant events tie-break on hand id, then nonce, then event id.
+        assert_eq!(merged(first.clone()), vec![5, 6, 1, 4, 2]);
+        assert_eq!(merged(first), merged(second));
+    }
+
     #[tokio::test]
     async fn stored_message_roundtrip_player_action() {
         let message = AnyGameMessage::PlayerPreflop(
@@ -618,6 +832,65 @@ mod tests {
         assert_eq!(loaded[0].envelope.hand_id, hand_id);
     }
 
+    #[tokio::test]
+    async fn persisted_event_keeps_request_correlation_id() {
+        let Some((store, hand_id, game_id)) = prepare_environment().await else {
+            return;
+        };
+
+        let correlation_id = CorrelationId::parse("req-7f3a").expect("valid correlation id");
+        let envelope = sample_shuffle_envelope(hand_id, game_id, 11)
+            .with_correlation_id(correlation_id.clone());
+        store.persist_event(&finalized(envelope)).await.unwrap();
+
+        let loaded = store.load_hand_events(hand_id).await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].envelope.correlation_id, Some(correlation_id));
+    }
+
+    #[tokio::test]
+    async fn tail_yields_events_persisted_after_subscribing() {
+        let Some((store, hand_id, game_id)) = prepare_environment().await else {
+            return;
+        };
+
+        let earlier = sample_shuffle_envelope(hand_id, game_id, 3);
+        store.persist_event(&finalized(earlier)).await.unwrap();
+        let resume_from = store.load_hand_events(hand_id).await.unwrap()[0]
+            .event_id
+            .expect("stored events carry their id");
+
+        let mut tail = store.tail(hand_id, Some(resume_from));
+
+        // A different actor's nonces start from zero, below the last one the client saw.
+        let mut other_actor = sample_shuffle_envelope(hand_id, game_id, 0);
+        other_actor.actor = AnyActor::Shuffler {
+            shuffler_id: 1,
+            shuffler_key: crate::ledger::CanonicalKey::new(Curve::zero()),
+        };
+        store.persist_event(&finalized(other_actor)).await.unwrap();
+        let envelope = sample_shuffle_envelope(hand_id, game_id, 7);
+        store
+            .persist_event(&finalized(envelope.clone()))
+            .await
+            .unwrap();
+
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(5), tail.next())
+                .await
+                .expect("tail should observe the new event")
+                .expect("tail stream open");
+            received.push(event);
+        }
+        let (first, second) = (&received[0], &received[1]);
+        assert_eq!(first.envelope.hand_id, hand_id);
+        assert_eq!(first.envelope.nonce, 0);
+        assert_eq!(second.envelope.nonce, envelope.nonce);
+        assert!(first.event_id > Some(resume_from));
+        assert!(second.event_id > first.event_id);
+    }
+
     #[tokio::test]
     async fn remove_event_clears_rows() {
         let Some((store, hand_id, game_id)) = prepare_environment().await else {
@@ -4,14 +4,15 @@ use crate::ledger::actor::AnyActor;
 use crate::ledger::messages::{AnyGameMessage, AnyMessageEnvelope, FinalizedAnyMessageEnvelope};
 use crate::ledger::serialization::deserialize_curve_bytes;
 use crate::ledger::snapshot::SnapshotStatus;
-use crate::ledger::types::EventPhase;
+use crate::ledger::types::{CorrelationId, EventPhase};
 use crate::signing::WithSignature;
 use anyhow::{anyhow, Context};
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use chrono::{DateTime, Utc};
 
-pub(super) fn message_type<C>(message: &AnyGameMessage<C>) -> &'static str
+/// Name stored in the `message_type` column and accepted by message-type filters.
+pub fn message_type<C>(message: &AnyGameMessage<C>) -> &'static str
 where
     C: CurveGroup,
 {
@@ -24,6 +25,8 @@ where
         AnyGameMessage::PlayerTurn(_) => "player_turn",
         AnyGameMessage::PlayerRiver(_) => "player_river",
         AnyGameMessage::Showdown(_) => "showdown",
+        AnyGameMessage::NonceCommitment(_) => "nonce_commitment",
+        AnyGameMessage::Muck(_) => "muck",
     }
 }
 
@@ -62,6 +65,7 @@ where
         nonce,
         public_key,
         message: with_signature,
+        correlation_id: row.correlation_id.as_deref().and_then(CorrelationId::parse),
     };
 
     let snapshot_sequence_id = u32::try_from(row.snapshot_number)
@@ -89,7 +93,8 @@ where
         from_db_event_phase(row.resulting_phase),
         snapshot_sequence_id,
         created_timestamp,
-    ))
+    )
+    .with_event_id(row.id))
 }
 
 pub(super) fn encode_actor<C>(actor: &AnyActor<C>) -> anyhow::Result<ActorColumns>
@@ -209,10 +214,85 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ledger::serialization::deserialize_curve_bytes;
+    use crate::ledger::serialization::{
+        deserialize_curve_bytes, deserialize_curve_bytes_with, deserialize_curve_hex_any,
+        serialize_curve_bytes_with, serialize_curve_hex_with, PointEncoding,
+    };
     use ark_bn254::G1Projective as Curve;
     use ark_ec::PrimeGroup;
     use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+    use ark_std::{test_rng, UniformRand, Zero};
+
+    fn assert_point_encodings_round_trip<C>()
+    where
+        C: CurveGroup + CanonicalSerialize + CanonicalDeserialize + UniformRand,
+    {
+        let mut rng = test_rng();
+        for point in [C::rand(&mut rng), C::rand(&mut rng), C::zero()] {
+            for encoding in [
+                PointEncoding::CompressedProjective,
+                PointEncoding::UncompressedAffine,
+            ] {
+                let bytes = serialize_curve_bytes_with(&point, encoding).expect("encode point");
+                assert_eq!(PointEncoding::detect::<C>(&bytes), Some(encoding));
+                let decoded: C =
+                    deserialize_curve_bytes_with(&bytes, encoding).expect("decode point");
+                assert_eq!(decoded, point);
+
+                let hex = serialize_curve_hex_with(&point, encoding).expect("encode hex");
+                let decoded: C = deserialize_curve_hex_any(&hex).expect("decode hex");
+                assert_eq!(decoded, point);
+            }
+        }
+    }
+
+    #[test]
+    fn point_encodings_round_trip() {
+        assert_point_encodings_round_trip::<Curve>();
+        assert_point_encodings_round_trip::<ark_grumpkin::Projective>();
+    }
+
+    #[test]
+    fn serde_helpers_write_the_scoped_point_encoding() {
+        use crate::ledger::serialization::with_point_encoding;
+        use crate::shuffling::data_structures::ElGamalCiphertext;
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Wrapper {
+            #[serde(with = "crate::crypto_serde::elgamal")]
+            ciphertext: ElGamalCiphertext<Curve>,
+        }
+
+        let mut rng = test_rng();
+        let ciphertext = ElGamalCiphertext::new(Curve::rand(&mut rng), Curve::rand(&mut rng));
+        let wrapper = Wrapper {
+            ciphertext: ciphertext.clone(),
+        };
+        let affine = <Curve as CurveGroup>::Affine::default();
+
+        let compressed = serde_json::to_value(&wrapper).expect("compressed json");
+        let uncompressed = with_point_encoding(PointEncoding::UncompressedAffine, || {
+            serde_json::to_value(&wrapper).expect("affine json")
+        });
+        assert_eq!(
+            PointEncoding::current(),
+            PointEncoding::CompressedProjective
+        );
+
+        for (json, encoding) in [
+            (compressed, PointEncoding::CompressedProjective),
+            (uncompressed, PointEncoding::UncompressedAffine),
+        ] {
+            let hex = json["ciphertext"]["c1"].as_str().expect("c1 hex");
+            let expected_len = match encoding {
+                PointEncoding::CompressedProjective => affine.compressed_size(),
+                PointEncoding::UncompressedAffine => affine.uncompressed_size(),
+            };
+            assert_eq!(hex.len(), 2 + 2 * expected_len);
+            let decoded: Wrapper = serde_json::from_value(json).expect("decode json");
+            assert_eq!(decoded.ciphertext, ciphertext);
+        }
+    }
 
     #[test]
     fn deserialize_curve_handles_negative_infinity_flags() {
@@ -1,5 +1,7 @@
+pub mod archive;
 pub mod event;
 pub mod snapshot;
 
-pub use event::{EventStore, SeaOrmEventStore, SharedEventStore};
-pub use snapshot::{SeaOrmSnapshotStore, SharedSnapshotStore, SnapshotStore};
+pub use archive::ArchivedHand;
+pub use event::{EventStore, GlobalEventOrder, SeaOrmEventStore, SharedEventStore};
+pub use snapshot::{SeaOrmSnapshotStore, SharedSnapshotStore, SnapshotError, SnapshotStore};
@@ -3,6 +3,7 @@ mod serialization;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
+use anyhow::{anyhow, ensure};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
@@ -11,22 +12,37 @@ use sea_orm::{
     ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder,
     TransactionTrait,
 };
-use tracing::info;
+use thiserror::Error;
+use tracing::{info, warn};
 
 use crate::curve_absorb::CurveAbsorb;
-use crate::db::entity::table_snapshots;
+use crate::db::entity::{events, table_snapshots};
+use crate::ledger::catchup::{replay_messages, CatchupError};
 use crate::ledger::hash::LedgerHasher;
-use crate::ledger::snapshot::AnyTableSnapshot;
+use crate::ledger::snapshot::{AnyTableSnapshot, SnapshotSeq};
+use crate::ledger::store::event::model_to_envelope;
 use crate::ledger::types::HandId;
 
-use self::serialization::{
-    prepare_snapshot_data, reconstruct_snapshot_from_db, SNAPSHOT_LOG_TARGET,
-};
+use self::serialization::{prepare_snapshot_data, SNAPSHOT_LOG_TARGET};
+
+pub(crate) use self::serialization::reconstruct_snapshot_from_db;
 
 pub use self::serialization::{compute_dealing_hash, persist_prepared_snapshot, PreparedSnapshot};
 
 pub type SharedSnapshotStore<C> = Arc<dyn SnapshotStore<C>>;
 
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// A phase payload no longer hashes to the hash the snapshot row references, e.g. after the
+    /// row or payload was altered in the database.
+    #[error("snapshot {sequence} of hand {hand_id}: {phase} phase does not match its stored hash")]
+    HashMismatch {
+        hand_id: HandId,
+        sequence: i32,
+        phase: &'static str,
+    },
+}
+
 pub fn prepare_snapshot<C>(
     snapshot: &AnyTableSnapshot<C>,
     hasher: &dyn LedgerHasher,
@@ -65,6 +81,24 @@ where
         &self,
         hand_id: HandId,
     ) -> anyhow::Result<Option<AnyTableSnapshot<C>>>;
+
+    /// Materializes the snapshot at `sequence` from the nearest stored snapshot at or before it,
+    /// replaying the hand's events in between, so sequences removed by [`Self::compact`] can
+    /// still be read. Returns None if no stored snapshot precedes `sequence` or the hand has not
+    /// reached it yet.
+    async fn snapshot_at(
+        &self,
+        hand_id: HandId,
+        sequence: SnapshotSeq,
+        hasher: &Arc<dyn LedgerHasher + Send + Sync>,
+    ) -> anyhow::Result<Option<AnyTableSnapshot<C>>>;
+
+    /// Deletes intermediate snapshots for a hand, returning how many rows were removed.
+    ///
+    /// The first snapshot, the tip, the first snapshot of every phase and every snapshot whose
+    /// sequence is a multiple of `keep_every` are retained, so replaying the hand's events from
+    /// any kept snapshot still reaches the tip. Events are never touched.
+    async fn compact(&self, hand_id: HandId, keep_every: u32) -> anyhow::Result<u64>;
 }
 
 pub struct SeaOrmSnapshotStore<C>
@@ -75,6 +109,9 @@ where
     C::Affine: Absorb,
 {
     pub connection: DatabaseConnection,
+    /// When set, loaded snapshots are re-hashed with this hasher and checked against the
+    /// stored phase hashes.
+    verify_on_load: Option<Arc<dyn LedgerHasher + Send + Sync>>,
     _marker: PhantomData<C>,
 }
 
@@ -88,9 +125,20 @@ where
     pub fn new(connection: DatabaseConnection) -> Self {
         Self {
             connection,
+            verify_on_load: None,
             _marker: PhantomData,
         }
     }
+
+    /// Re-hash every loaded snapshot's phases with `hasher` and fail with
+    /// [`SnapshotError::HashMismatch`] if one differs from the hash stored on its row.
+    ///
+    /// The chained `state_hash` cannot be recomputed from a snapshot alone, but the phase hashes
+    /// it is built over can, and they address the stored phase payloads.
+    pub fn with_verify_on_load(mut self, hasher: Arc<dyn LedgerHasher + Send + Sync>) -> Self {
+        self.verify_on_load = Some(hasher);
+        self
+    }
 }
 
 #[async_trait]
@@ -163,7 +211,10 @@ where
                     sequence = row.sequence,
                     "found latest snapshot"
                 );
-                let snapshot = reconstruct_snapshot_from_db(row, &self.connection).await?;
+                let snapshot = reconstruct_snapshot_from_db(row.clone(), &self.connection).await?;
+                if let Some(hasher) = &self.verify_on_load {
+                    verify_phase_hashes(&row, &snapshot, hasher.as_ref())?;
+                }
                 Ok(Some(snapshot))
             }
             None => {
@@ -176,4 +227,211 @@ where
             }
         }
     }
+
+    async fn snapshot_at(
+        &self,
+        hand_id: HandId,
+        sequence: SnapshotSeq,
+        hasher: &Arc<dyn LedgerHasher + Send + Sync>,
+    ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
+        let target = i32::try_from(sequence).map_err(|_| {
+            anyhow!("snapshot sequence {sequence} exceeds i32::MAX and cannot be queried")
+        })?;
+
+        let Some(row) = table_snapshots::Entity::find()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .filter(table_snapshots::Column::Sequence.lte(target))
+            .order_by_desc(table_snapshots::Column::Sequence)
+            .one(&self.connection)
+            .await?
+        else {
+            info!(
+                target = SNAPSHOT_LOG_TARGET,
+                hand_id = hand_id,
+                sequence = target,
+                "no stored snapshot at or before requested sequence"
+            );
+            return Ok(None);
+        };
+        let base_sequence = row.sequence;
+        let base = reconstruct_snapshot_from_db(row, &self.connection).await?;
+        if base_sequence == target {
+            return Ok(Some(base));
+        }
+
+        let events = events::Entity::find()
+            .filter(events::Column::HandId.eq(hand_id))
+            .filter(events::Column::SnapshotNumber.gt(base_sequence))
+            .filter(events::Column::SnapshotNumber.lte(target))
+            .order_by_asc(events::Column::SnapshotNumber)
+            .order_by_asc(events::Column::Nonce)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(model_to_envelope)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if events.last().map(|event| event.snapshot_sequence_id) != Some(sequence) {
+            return Ok(None);
+        }
+        let expected = base_sequence as i64 + 1;
+        let first_sequence = events[0].snapshot_sequence_id as i64;
+        if first_sequence != expected {
+            return Err(CatchupError::discontinuity(expected, first_sequence).into());
+        }
+
+        info!(
+            target = SNAPSHOT_LOG_TARGET,
+            hand_id = hand_id,
+            base_sequence = base_sequence,
+            sequence = target,
+            event_count = events.len(),
+            "replaying events to materialize snapshot"
+        );
+        let snapshot = replay_messages(base, events, hasher.as_ref())?;
+        Ok(Some(snapshot))
+    }
+
+    async fn compact(&self, hand_id: HandId, keep_every: u32) -> anyhow::Result<u64> {
+        ensure!(keep_every > 0, "keep_every must be at least 1");
+
+        let rows = table_snapshots::Entity::find()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .order_by_asc(table_snapshots::Column::Sequence)
+            .all(&self.connection)
+            .await?;
+        let layout: Vec<(i32, [bool; 3])> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.sequence,
+                    [
+                        row.dealing_hash.is_some(),
+                        row.betting_hash.is_some(),
+                        row.reveals_hash.is_some(),
+                    ],
+                )
+            })
+            .collect();
+        let pruned: Vec<Vec<u8>> = rows
+            .into_iter()
+            .zip(snapshots_to_keep(&layout, keep_every))
+            .filter(|(_, keep)| !keep)
+            .map(|(row, _)| row.snapshot_hash)
+            .collect();
+        if pruned.is_empty() {
+            return Ok(0);
+        }
+
+        // Kept snapshots whose predecessor is pruned have `previous_hash` cleared by the
+        // foreign key; their own `state_hash` is unaffected.
+        let txn = self.connection.begin().await?;
+        let result = table_snapshots::Entity::delete_many()
+            .filter(table_snapshots::Column::SnapshotHash.is_in(pruned))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+
+        info!(
+            target = SNAPSHOT_LOG_TARGET,
+            hand_id = hand_id,
+            keep_every = keep_every,
+            deleted = result.rows_affected,
+            "compacted hand snapshots"
+        );
+        Ok(result.rows_affected)
+    }
+}
+
+/// Recompute `snapshot`'s phase hashes and compare them with those stored on `row`.
+fn verify_phase_hashes<C>(
+    row: &table_snapshots::Model,
+    snapshot: &AnyTableSnapshot<C>,
+    hasher: &dyn LedgerHasher,
+) -> anyhow::Result<()>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField> + Send + Sync + 'static,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + Absorb,
+    C::Affine: Absorb,
+{
+    let recomputed = prepare_snapshot_data(snapshot, hasher)?;
+    let phases = [
+        ("shuffling", &row.shuffling_hash, recomputed.shuffling_hash),
+        ("dealing", &row.dealing_hash, recomputed.dealing_hash),
+        ("betting", &row.betting_hash, recomputed.betting_hash),
+        ("reveals", &row.reveals_hash, recomputed.reveals_hash),
+    ];
+    for (phase, stored, recomputed) in phases {
+        if stored.as_deref() != recomputed.as_ref().map(|hash| hash.as_bytes().as_slice()) {
+            warn!(
+                target = SNAPSHOT_LOG_TARGET,
+                hand_id = row.hand_id,
+                sequence = row.sequence,
+                phase,
+                "stored snapshot failed integrity check"
+            );
+            return Err(SnapshotError::HashMismatch {
+                hand_id: row.hand_id,
+                sequence: row.sequence,
+                phase,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Decide which snapshots survive compaction, given each row's sequence and which of the
+/// dealing, betting and reveals phase hashes it carries (in ascending sequence order).
+fn snapshots_to_keep(layout: &[(i32, [bool; 3])], keep_every: u32) -> Vec<bool> {
+    let last = layout.len().saturating_sub(1);
+    layout
+        .iter()
+        .enumerate()
+        .map(|(idx, (sequence, phases))| {
+            let phase_boundary = idx == 0 || layout[idx - 1].1 != *phases;
+            let checkpoint = u32::try_from(*sequence).is_ok_and(|seq| seq % keep_every == 0);
+            idx == last || phase_boundary || checkpoint
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compaction_keeps_tip_phase_boundaries_and_checkpoints() {
+        const SHUFFLING: [bool; 3] = [false, false, false];
+        const DEALING: [bool; 3] = [true, false, false];
+        const BETTING: [bool; 3] = [true, true, false];
+        let layout = [
+            (1, SHUFFLING),
+            (2, SHUFFLING),
+            (3, SHUFFLING),
+            (4, SHUFFLING),
+            (5, DEALING),
+            (6, DEALING),
+            (7, DEALING),
+            (8, BETTING),
+            (9, BETTING),
+            (10, BETTING),
+            (11, BETTING),
+        ];
+
+        let kept: Vec<i32> = layout
+            .iter()
+            .zip(snapshots_to_keep(&layout, 3))
+            .filter(|(_, keep)| *keep)
+            .map(|((sequence, _), _)| *sequence)
+            .collect();
+
+        assert_eq!(kept, vec![1, 3, 5, 6, 8, 9, 11]);
+    }
+
+    #[test]
+    fn compaction_of_single_snapshot_keeps_it() {
+        assert_eq!(snapshots_to_keep(&[(0, [false; 3])], 5), vec![true]);
+        assert!(snapshots_to_keep(&[], 5).is_empty());
+    }
 }
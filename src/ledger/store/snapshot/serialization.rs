@@ -25,7 +25,7 @@ use crate::db::entity::{
 use crate::engine::nl::events::NormalizedAction;
 use crate::engine::nl::state::BettingState;
 use crate::engine::nl::types::{
-    HandConfig, PlayerState as EnginePlayerState, PlayerStatus as EnginePlayerStatus,
+    DealingOrder, HandConfig, PlayerState as EnginePlayerState, PlayerStatus as EnginePlayerStatus,
     Pot as EnginePot, Pots as EnginePots, Street, TableStakes,
 };
 use crate::ledger::hash::LedgerHasher;
@@ -314,6 +314,10 @@ where
     })
 }
 
+const SHUFFLING_PHASE_DOMAIN_V1: &[u8] = b"ledger/phase/shuffling\0";
+/// v2 adds each step's revealed VRF nonce and the shufflers' nonce commitments.
+const SHUFFLING_PHASE_DOMAIN_V2: &[u8] = b"ledger/phase/shuffling/v2\0";
+
 fn build_shuffling_phase<C>(
     shuffling: &ShufflingSnapshot<C>,
     hasher: &dyn LedgerHasher,
@@ -324,8 +328,19 @@ where
     C::ScalarField: PrimeField + Absorb + CanonicalSerialize,
     C::Affine: Absorb,
 {
+    // Snapshots without nonce commitments, reveals or beacon rounds keep the v1 encoding, so
+    // hashes stored before commitments existed still verify.
+    let versioned = !shuffling.nonce_commitments.is_empty()
+        || shuffling
+            .steps
+            .iter()
+            .any(|step| step.vrf_nonce.is_some() || step.beacon_round.is_some());
     let mut bytes = Vec::new();
-    bytes.extend_from_slice(b"ledger/phase/shuffling\0");
+    if versioned {
+        bytes.extend_from_slice(SHUFFLING_PHASE_DOMAIN_V2);
+    } else {
+        bytes.extend_from_slice(SHUFFLING_PHASE_DOMAIN_V1);
+    }
 
     (DECK_SIZE as u64).serialize_compressed(&mut bytes)?;
     for cipher in &shuffling.initial_deck {
@@ -336,6 +351,10 @@ where
     for step in &shuffling.steps {
         step.shuffler_public_key.serialize_compressed(&mut bytes)?;
         step.proof.serialize_compressed(&mut bytes)?;
+        if versioned {
+            step.beacon_round.serialize_compressed(&mut bytes)?;
+            step.vrf_nonce.serialize_compressed(&mut bytes)?;
+        }
     }
 
     (DECK_SIZE as u64).serialize_compressed(&mut bytes)?;
@@ -348,6 +367,14 @@ where
         key.serialize_compressed(&mut bytes)?;
     }
 
+    if versioned {
+        (shuffling.nonce_commitments.len() as u64).serialize_compressed(&mut bytes)?;
+        for entry in &shuffling.nonce_commitments {
+            entry.shuffler_key.serialize_compressed(&mut bytes)?;
+            entry.commitment.serialize_compressed(&mut bytes)?;
+        }
+    }
+
     let hash = hasher.hash(&bytes);
     let payload_json =
         serde_json::to_value(shuffling).context("failed to serialize shuffling phase")?;
@@ -547,6 +574,7 @@ where
     (reveals.revealed_holes.len() as u64).serialize_compressed(&mut bytes)?;
     for (&seat, hand) in reveals.revealed_holes.iter() {
         seat.serialize_compressed(&mut bytes)?;
+        (hand.hole.len() as u64).serialize_compressed(&mut bytes)?;
         for value in &hand.hole {
             value.serialize_compressed(&mut bytes)?;
         }
@@ -565,6 +593,24 @@ where
         (hand.best_score as u64).serialize_compressed(&mut bytes)?;
     }
 
+    // Only hashed once someone mucks, so reveals without mucks keep their existing hash.
+    if !reveals.mucked.is_empty() {
+        bytes.extend_from_slice(b"mucked\0");
+        (reveals.mucked.len() as u64).serialize_compressed(&mut bytes)?;
+        for (&seat, declaration) in reveals.mucked.iter() {
+            seat.serialize_compressed(&mut bytes)?;
+            declaration.value.serialize_compressed(&mut bytes)?;
+            declaration.signature.serialize_compressed(&mut bytes)?;
+        }
+    }
+    if !reveals.auto_mucked.is_empty() {
+        bytes.extend_from_slice(b"auto_mucked\0");
+        (reveals.auto_mucked.len() as u64).serialize_compressed(&mut bytes)?;
+        for &seat in &reveals.auto_mucked {
+            seat.serialize_compressed(&mut bytes)?;
+        }
+    }
+
     let hash = hasher.hash(&bytes);
     let payload_json =
         serde_json::to_value(reveals).context("failed to serialize reveals phase")?;
@@ -649,6 +695,13 @@ fn serialize_betting_state(bytes: &mut Vec<u8>, state: &BettingState) -> anyhow:
         .serialize_compressed(&mut *bytes)?;
     state.cfg.big_blind_seat.serialize_compressed(&mut *bytes)?;
     (state.cfg.check_raise_allowed as u8).serialize_compressed(&mut *bytes)?;
+    state.cfg.hole_cards.serialize_compressed(&mut *bytes)?;
+    state.cfg.max_bet_bb.serialize_compressed(&mut *bytes)?;
+    state
+        .cfg
+        .dealing_order
+        .as_byte()
+        .serialize_compressed(&mut *bytes)?;
 
     (state.pending_to_match.len() as u64).serialize_compressed(&mut *bytes)?;
     for seat in &state.pending_to_match {
@@ -909,6 +962,7 @@ async fn ensure_hand_config(
     let button = i16::from(config.button);
     let small_blind_seat = i16::from(config.small_blind_seat);
     let big_blind_seat = i16::from(config.big_blind_seat);
+    let max_bet_bb = config.max_bet_bb.map(i64::from);
 
     if let Some(existing) = hand_configs::Entity::find()
         .filter(Column::GameId.eq(game_id))
@@ -919,6 +973,12 @@ async fn ensure_hand_config(
         .filter(Column::SmallBlindSeat.eq(small_blind_seat))
         .filter(Column::BigBlindSeat.eq(big_blind_seat))
         .filter(Column::CheckRaiseAllowed.eq(config.check_raise_allowed))
+        .filter(Column::HoleCards.eq(i16::from(config.hole_cards)))
+        .filter(Column::DealingOrder.eq(i16::from(config.dealing_order.as_byte())))
+        .filter(match max_bet_bb {
+            Some(max_bet_bb) => Column::MaxBetBb.eq(max_bet_bb),
+            None => Column::MaxBetBb.is_null(),
+        })
         .order_by_desc(Column::CreatedAt)
         .one(txn)
         .await?
@@ -935,6 +995,9 @@ async fn ensure_hand_config(
         small_blind_seat: Set(small_blind_seat),
         big_blind_seat: Set(big_blind_seat),
         check_raise_allowed: Set(config.check_raise_allowed),
+        hole_cards: Set(i16::from(config.hole_cards)),
+        max_bet_bb: Set(max_bet_bb),
+        dealing_order: Set(i16::from(config.dealing_order.as_byte())),
         ..Default::default()
     };
 
@@ -1022,6 +1085,7 @@ where
                 .seat
                 .try_into()
                 .map_err(|_| anyhow!("player seat {} is invalid", hp.seat))?,
+            auto_muck_losers: hp.auto_muck_losers,
         };
 
         roster.insert(player_key, identity);
@@ -1533,6 +1597,19 @@ async fn load_hand_config(
             .try_into()
             .map_err(|_| anyhow!("big_blind_seat {} is invalid", config_row.big_blind_seat))?,
         check_raise_allowed: config_row.check_raise_allowed,
+        hole_cards: config_row
+            .hole_cards
+            .try_into()
+            .map_err(|_| anyhow!("hole_cards {} is invalid", config_row.hole_cards))?,
+        max_bet_bb: config_row
+            .max_bet_bb
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|_| anyhow!("max_bet_bb {:?} is invalid", config_row.max_bet_bb))?,
+        dealing_order: u8::try_from(config_row.dealing_order)
+            .ok()
+            .and_then(DealingOrder::from_byte)
+            .ok_or_else(|| anyhow!("dealing_order {} is invalid", config_row.dealing_order))?,
     };
 
     Ok(Arc::new(config))
@@ -1650,8 +1727,22 @@ where
 mod tests {
     use super::*;
     use crate::db::entity::sea_orm_active_enums as db_enums;
-    use crate::ledger::test_support::FixtureContext;
+    use crate::engine::nl::types::{DealingOrder, OMAHA_HOLE_CARDS};
+    use crate::ledger::test_support::{
+        fixture_dealing_snapshot, fixture_preflop_snapshot, fixture_shuffling_snapshot,
+        populate_board_cards_upto, FixtureContext,
+    };
     use ark_bn254::G1Projective as TestCurve;
+    use std::sync::Arc;
+
+    /// Rebuild `map` by inserting its entries one at a time in reverse key order.
+    fn insert_reversed<K: Ord + Clone, V: Clone>(map: &BTreeMap<K, V>) -> BTreeMap<K, V> {
+        let mut rebuilt = BTreeMap::new();
+        for (key, value) in map.iter().rev() {
+            rebuilt.insert(key.clone(), value.clone());
+        }
+        rebuilt
+    }
 
     #[test]
     fn assemble_roster_assigns_global_key() {
@@ -1708,4 +1799,99 @@ mod tests {
 
         assert_eq!(phase, db_enums::PhaseKind::Betting);
     }
+
+    #[test]
+    fn equal_snapshots_built_in_different_orders_hash_identically() {
+        let ctx = FixtureContext::<TestCurve>::new(&[0, 1, 2], &[10, 11]);
+        let mut table = fixture_dealing_snapshot(&ctx);
+        populate_board_cards_upto(&mut table.dealing, 5);
+        table.initialize_hash(ctx.hasher.as_ref());
+
+        let mut rebuilt = table.clone();
+        rebuilt.dealing.assignments = insert_reversed(&table.dealing.assignments);
+        rebuilt.dealing.player_ciphertexts = insert_reversed(&table.dealing.player_ciphertexts);
+        rebuilt.dealing.player_unblinding_combined =
+            insert_reversed(&table.dealing.player_unblinding_combined);
+        rebuilt.dealing.community_cards = insert_reversed(&table.dealing.community_cards);
+        rebuilt.dealing.card_plan = insert_reversed(&table.dealing.card_plan);
+        rebuilt.seating = Arc::new(insert_reversed(&table.seating));
+        rebuilt.stacks = Arc::new(insert_reversed(&table.stacks));
+        rebuilt.initialize_hash(ctx.hasher.as_ref());
+
+        let mut original_bytes = Vec::new();
+        serialize_dealing_phase(&mut original_bytes, &table.dealing).expect("serialize original");
+        let mut rebuilt_bytes = Vec::new();
+        serialize_dealing_phase(&mut rebuilt_bytes, &rebuilt.dealing).expect("serialize rebuilt");
+        assert_eq!(original_bytes, rebuilt_bytes);
+
+        let (original_phase, original_hash) =
+            build_dealing_phase(&table.dealing, ctx.hasher.as_ref()).expect("original phase");
+        let (rebuilt_phase, rebuilt_hash) =
+            build_dealing_phase(&rebuilt.dealing, ctx.hasher.as_ref()).expect("rebuilt phase");
+        assert_eq!(original_hash, rebuilt_hash);
+        assert_eq!(original_phase.payload, rebuilt_phase.payload);
+
+        assert_eq!(table.state_hash, rebuilt.state_hash);
+    }
+
+    #[test]
+    fn betting_hash_covers_the_hand_config() {
+        let ctx = FixtureContext::<TestCurve>::new(&[0, 1, 2], &[10, 11]);
+        let state = fixture_preflop_snapshot(&ctx).betting.state;
+        let encode = |state: &BettingState| {
+            let mut bytes = Vec::new();
+            serialize_betting_state(&mut bytes, state).expect("serialize betting state");
+            bytes
+        };
+
+        let mut capped = state.clone();
+        capped.cfg.max_bet_bb = Some(50);
+        assert_ne!(encode(&capped), encode(&state));
+
+        let mut per_player = state.clone();
+        per_player.cfg.dealing_order = DealingOrder::PerPlayer;
+        assert_ne!(encode(&per_player), encode(&state));
+
+        let mut omaha = state.clone();
+        omaha.cfg.hole_cards = OMAHA_HOLE_CARDS;
+        assert_ne!(encode(&omaha), encode(&state));
+    }
+
+    #[test]
+    fn shuffling_hash_without_commitments_keeps_v1_encoding() {
+        let ctx = FixtureContext::<TestCurve>::new(&[0, 1, 2], &[10, 11]);
+        let mut table = fixture_shuffling_snapshot(&ctx);
+        let shuffling = &table.shuffling;
+
+        let mut v1 = Vec::new();
+        v1.extend_from_slice(SHUFFLING_PHASE_DOMAIN_V1);
+        (DECK_SIZE as u64).serialize_compressed(&mut v1).unwrap();
+        for cipher in &shuffling.initial_deck {
+            cipher.serialize_compressed(&mut v1).unwrap();
+        }
+        0u64.serialize_compressed(&mut v1).unwrap();
+        (DECK_SIZE as u64).serialize_compressed(&mut v1).unwrap();
+        for cipher in &shuffling.final_deck {
+            cipher.serialize_compressed(&mut v1).unwrap();
+        }
+        (shuffling.expected_order.len() as u64)
+            .serialize_compressed(&mut v1)
+            .unwrap();
+        for key in &shuffling.expected_order {
+            key.serialize_compressed(&mut v1).unwrap();
+        }
+
+        let (_, plain_hash) =
+            build_shuffling_phase(shuffling, ctx.hasher.as_ref()).expect("plain phase");
+        assert_eq!(plain_hash, ctx.hasher.hash(&v1));
+
+        let shuffler_key = table.shuffling.expected_order[0].clone();
+        table
+            .shuffling
+            .record_nonce_commitment(shuffler_key, Default::default())
+            .expect("commit before shuffling");
+        let (_, committed_hash) =
+            build_shuffling_phase(&table.shuffling, ctx.hasher.as_ref()).expect("v2 phase");
+        assert_ne!(committed_hash, plain_hash);
+    }
 }
@@ -0,0 +1,158 @@
+//! A shuffler committee with known secrets, for tests that need to decrypt cards.
+
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::Rng;
+
+use crate::curve_absorb::CurveAbsorb;
+use crate::ledger::CanonicalKey;
+use crate::shuffling::community_decryption::CommunityDecryptionShare;
+use crate::shuffling::data_structures::ElGamalCiphertext;
+use crate::shuffling::make_global_public_keys;
+use crate::shuffling::player_decryption::{
+    generate_committee_decryption_share, PartialUnblindingShare, PlayerAccessibleCiphertext,
+    PlayerTargetedBlindingContribution,
+};
+
+/// `n` committee members, each holding a secret `x_j` with public key `g^x_j`. Cards encrypted
+/// to [`Committee::aggregated_key`] decrypt with shares from every member.
+#[derive(Clone, Debug)]
+pub struct Committee<C: CurveGroup> {
+    secrets: Vec<C::ScalarField>,
+    public_keys: Vec<C>,
+}
+
+impl<C> Committee<C>
+where
+    C: CurveGroup + CanonicalSerialize,
+{
+    /// Draw `n` member secrets from `rng`, in member order.
+    pub fn new<R: Rng>(n: usize, rng: &mut R) -> Self {
+        let secrets: Vec<C::ScalarField> = (0..n).map(|_| C::ScalarField::rand(rng)).collect();
+        let public_keys = secrets
+            .iter()
+            .map(|secret| C::generator() * secret)
+            .collect();
+        Self {
+            secrets,
+            public_keys,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.secrets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    pub fn secrets(&self) -> &[C::ScalarField] {
+        &self.secrets
+    }
+
+    pub fn public_keys(&self) -> &[C] {
+        &self.public_keys
+    }
+
+    /// Sum of the member public keys; the key decks are encrypted to.
+    pub fn aggregated_key(&self) -> C {
+        make_global_public_keys(self.public_keys.clone())
+    }
+
+    pub fn member_keys(&self) -> Vec<CanonicalKey<C>> {
+        self.public_keys
+            .iter()
+            .map(|public_key| CanonicalKey::new(*public_key))
+            .collect()
+    }
+
+    /// Every member's partial unblinding share for a player-targeted ciphertext.
+    pub fn unblinding_shares(
+        &self,
+        ciphertext: &PlayerAccessibleCiphertext<C>,
+    ) -> Vec<PartialUnblindingShare<C>> {
+        self.secrets
+            .iter()
+            .zip(self.member_keys())
+            .map(|(secret, member_key)| {
+                generate_committee_decryption_share(ciphertext, *secret, member_key)
+            })
+            .collect()
+    }
+}
+
+impl<C> Committee<C>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField> + CanonicalSerialize,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + Absorb,
+{
+    /// Every member's community decryption share for `ciphertext`, with proofs.
+    pub fn community_shares<R: Rng>(
+        &self,
+        ciphertext: &ElGamalCiphertext<C>,
+        rng: &mut R,
+    ) -> Vec<CommunityDecryptionShare<C>> {
+        self.secrets
+            .iter()
+            .zip(self.member_keys())
+            .map(|(secret, member_key)| {
+                CommunityDecryptionShare::generate(ciphertext, *secret, member_key, rng)
+            })
+            .collect()
+    }
+
+    /// Every member's blinding contribution towards `player_public_key`.
+    pub fn blinding_contributions<R: Rng>(
+        &self,
+        player_public_key: C,
+        rng: &mut R,
+    ) -> Vec<PlayerTargetedBlindingContribution<C>> {
+        let aggregated_key = self.aggregated_key();
+        self.secrets
+            .iter()
+            .map(|secret| {
+                PlayerTargetedBlindingContribution::generate(
+                    *secret,
+                    aggregated_key,
+                    player_public_key,
+                    rng,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shuffling::community_decryption::decrypt_community_card;
+    use ark_ec::PrimeGroup;
+    use ark_grumpkin::Projective as Curve;
+    use ark_std::test_rng;
+
+    #[test]
+    fn committee_shares_decrypt_a_known_card() {
+        let mut rng = test_rng();
+        let committee = Committee::<Curve>::new(3, &mut rng);
+        let card = 25u8;
+        let randomness = <Curve as PrimeGroup>::ScalarField::rand(&mut rng);
+        let ciphertext = ElGamalCiphertext::encrypt_scalar(
+            <Curve as PrimeGroup>::ScalarField::from(card),
+            randomness,
+            committee.aggregated_key(),
+        );
+
+        let shares = committee.community_shares(&ciphertext, &mut rng);
+        for (share, public_key) in shares.iter().zip(committee.public_keys()) {
+            assert!(share.verify(&ciphertext, *public_key));
+        }
+        assert_eq!(
+            decrypt_community_card(&ciphertext, shares, committee.len()),
+            Ok(card)
+        );
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use ark_ec::{CurveConfig, CurveGroup, PrimeGroup};
@@ -23,6 +23,7 @@ use crate::showdown::HandCategory;
 use crate::shuffling::data_structures::{ElGamalCiphertext, DECK_SIZE};
 use crate::shuffling::generate_random_ciphertexts;
 use crate::shuffling::player_decryption::PlayerAccessibleCiphertext;
+use crate::vrf::commit_nonce;
 
 const FIXTURE_GAME_ID: GameId = 42;
 const FIXTURE_HAND_ID: HandId = 7;
@@ -130,6 +131,9 @@ pub fn fixture_hand_config(button: SeatId, small_blind: SeatId, big_blind: SeatI
         small_blind_seat: small_blind,
         big_blind_seat: big_blind,
         check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
     }
 }
 
@@ -168,6 +172,7 @@ where
                 player_id,
                 nonce: 0,
                 seat: *seat,
+                auto_muck_losers: false,
             },
         );
         seating.insert(*seat, Some(player_key.clone()));
@@ -322,6 +327,7 @@ where
         steps: Vec::new(),
         final_deck: initial,
         expected_order: ctx.expected_shuffler_order.clone(),
+        nonce_commitments: Vec::new(),
     }
 }
 
@@ -395,6 +401,30 @@ where
     snapshot
 }
 
+/// VRF nonce the fixtures commit to on behalf of `shuffler_id`.
+pub fn fixture_vrf_nonce<F: PrimeField>(shuffler_id: ShufflerId) -> F {
+    F::from(shuffler_id as u64 + 1)
+}
+
+/// Shuffling snapshot in which every shuffler has committed to its [`fixture_vrf_nonce`], so
+/// the first shuffle can be applied.
+pub fn fixture_committed_shuffling_snapshot<C>(ctx: &FixtureContext<C>) -> TableAtShuffling<C>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    let mut snapshot = fixture_shuffling_snapshot(ctx);
+    for (&shuffler_id, shuffler_key) in &ctx.shuffler_keys {
+        let nonce = fixture_vrf_nonce::<C::BaseField>(shuffler_id);
+        snapshot
+            .shuffling
+            .record_nonce_commitment(shuffler_key.clone(), commit_nonce(&nonce))
+            .expect("fixture shufflers commit before shuffling");
+    }
+    snapshot.initialize_hash(ctx.hasher.as_ref());
+    snapshot
+}
+
 pub fn fixture_dealing_snapshot<C>(ctx: &FixtureContext<C>) -> TableAtDealing<C>
 where
     C: CurveGroup,
@@ -444,12 +474,16 @@ where
                 ctx.cfg.as_ref(),
                 ctx.stacks.as_ref(),
                 ctx.players.as_ref(),
-            ),
+            )
+            .expect("fixture blinds are valid"),
             last_events: Vec::new(),
+            betting_events: Vec::new(),
         },
         reveals: RevealsSnapshot {
             board: Vec::new(),
             revealed_holes: BTreeMap::new(),
+            mucked: BTreeMap::new(),
+            auto_mucked: BTreeSet::new(),
         },
     };
     snapshot.initialize_hash(ctx.hasher.as_ref());
@@ -704,8 +738,8 @@ where
         reveals.revealed_holes.insert(
             seat,
             RevealedHand {
-                hole: hole_values,
-                hole_ciphertexts,
+                hole: hole_values.to_vec(),
+                hole_ciphertexts: hole_ciphertexts.to_vec(),
                 best_five,
                 best_category: HandCategory::HighCard,
                 best_tiebreak: [0; 5],
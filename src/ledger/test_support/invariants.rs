@@ -0,0 +1,247 @@
+//! Structural invariants every snapshot should satisfy, for use as test post-conditions.
+//!
+//! [`check_invariants`] runs all checks and reports every violation rather than stopping at the
+//! first, so a broken transition shows the full extent of the damage in one failure message.
+
+use std::collections::BTreeMap;
+
+use ark_ec::CurveGroup;
+use thiserror::Error;
+
+use crate::engine::nl::state::BettingState;
+use crate::engine::nl::types::{Chips, PlayerStatus, SeatId};
+use crate::ledger::snapshot::{
+    AnyTableSnapshot, CardDestination, DealingSnapshot, HandPhase, SeatingError, SnapshotSeq,
+    TableSnapshot,
+};
+use crate::shuffling::data_structures::DECK_SIZE;
+
+/// Board slots every card plan must fill, in board order.
+const BOARD_SLOTS: [u8; 5] = [0, 1, 2, 3, 4];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvariantViolation {
+    #[error("seating is inconsistent: {0}")]
+    Seating(#[from] SeatingError),
+    #[error("card plan deals hole indices {planned:?} to seat {seat}, expected 0..{expected}")]
+    HoleCardsMismatch {
+        seat: SeatId,
+        planned: Vec<u8>,
+        expected: u8,
+    },
+    #[error("card plan deals hole cards to empty seat {seat}")]
+    HoleCardsForEmptySeat { seat: SeatId },
+    #[error("card plan fills board slots {planned:?}, expected each of 0..5 once")]
+    BoardSlotsMismatch { planned: Vec<u8> },
+    #[error("card plan references deck position {deck_index} outside the deck")]
+    CardPlanOutOfDeck { deck_index: u8 },
+    #[error("pots hold {pots} chips but live players committed {committed}")]
+    PotTotalMismatch { pots: Chips, committed: Chips },
+    #[error("snapshot {sequence} has no previous hash")]
+    MissingPreviousHash { sequence: SnapshotSeq },
+    #[error("initial snapshot links to a previous hash")]
+    UnexpectedPreviousHash,
+    #[error("snapshot {sequence} links to its own state hash")]
+    SelfLinkedHash { sequence: SnapshotSeq },
+}
+
+/// Run every snapshot invariant and return all violations found.
+pub fn check_invariants<C: CurveGroup>(
+    snapshot: &AnyTableSnapshot<C>,
+) -> Result<(), Vec<InvariantViolation>> {
+    let mut violations = Vec::new();
+    match snapshot {
+        AnyTableSnapshot::Shuffling(table) => check_table(table, None, None, &mut violations),
+        AnyTableSnapshot::Dealing(table) => {
+            check_table(table, Some(&table.dealing), None, &mut violations)
+        }
+        AnyTableSnapshot::Preflop(table) => check_table(
+            table,
+            Some(&table.dealing),
+            Some(&table.betting.state),
+            &mut violations,
+        ),
+        AnyTableSnapshot::Flop(table) => check_table(
+            table,
+            Some(&table.dealing),
+            Some(&table.betting.state),
+            &mut violations,
+        ),
+        AnyTableSnapshot::Turn(table) => check_table(
+            table,
+            Some(&table.dealing),
+            Some(&table.betting.state),
+            &mut violations,
+        ),
+        AnyTableSnapshot::River(table) => check_table(
+            table,
+            Some(&table.dealing),
+            Some(&table.betting.state),
+            &mut violations,
+        ),
+        AnyTableSnapshot::Showdown(table) => check_table(
+            table,
+            Some(&table.dealing),
+            Some(&table.betting.state),
+            &mut violations,
+        ),
+        AnyTableSnapshot::Complete(table) => check_table(
+            table,
+            Some(&table.dealing),
+            Some(&table.betting.state),
+            &mut violations,
+        ),
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn check_table<P, C>(
+    table: &TableSnapshot<P, C>,
+    dealing: Option<&DealingSnapshot<C>>,
+    betting: Option<&BettingState>,
+    violations: &mut Vec<InvariantViolation>,
+) where
+    P: HandPhase<C>,
+    C: CurveGroup,
+{
+    if let Err(err) = table.validate_seating() {
+        violations.push(err.into());
+    }
+    if let Some(dealing) = dealing {
+        check_card_plan(table, dealing, violations);
+    }
+    if let Some(state) = betting {
+        check_pot_total(state, violations);
+    }
+    check_hash_link(table, violations);
+}
+
+/// Every occupied seat is dealt hole indices `0..hole_cards` exactly once, no empty seat is
+/// dealt anything, and the five board slots are each filled once.
+fn check_card_plan<P, C>(
+    table: &TableSnapshot<P, C>,
+    dealing: &DealingSnapshot<C>,
+    violations: &mut Vec<InvariantViolation>,
+) where
+    P: HandPhase<C>,
+    C: CurveGroup,
+{
+    let mut holes: BTreeMap<SeatId, Vec<u8>> = table
+        .seating
+        .iter()
+        .filter(|(_, player)| player.is_some())
+        .map(|(&seat, _)| (seat, Vec::new()))
+        .collect();
+    let mut board = Vec::new();
+
+    for (&deck_index, destination) in &dealing.card_plan {
+        if usize::from(deck_index) >= DECK_SIZE {
+            violations.push(InvariantViolation::CardPlanOutOfDeck { deck_index });
+        }
+        match destination {
+            CardDestination::Hole { seat, hole_index } => match holes.get_mut(seat) {
+                Some(planned) => planned.push(*hole_index),
+                None => violations.push(InvariantViolation::HoleCardsForEmptySeat { seat: *seat }),
+            },
+            CardDestination::Board { board_index } => board.push(*board_index),
+            CardDestination::Burn | CardDestination::Unused => {}
+        }
+    }
+
+    let expected = table.cfg.hole_cards;
+    for (seat, mut planned) in holes {
+        planned.sort_unstable();
+        if !planned.iter().copied().eq(0..expected) {
+            violations.push(InvariantViolation::HoleCardsMismatch {
+                seat,
+                planned,
+                expected,
+            });
+        }
+    }
+
+    board.sort_unstable();
+    if board != BOARD_SLOTS {
+        violations.push(InvariantViolation::BoardSlotsMismatch { planned: board });
+    }
+}
+
+/// The pots hold exactly what the live players have put in. Folded chips are dropped from the
+/// pots by [`BettingState::compute_pots`], so folded players are not counted.
+fn check_pot_total(state: &BettingState, violations: &mut Vec<InvariantViolation>) {
+    let pots = state.pots.total();
+    let committed: Chips = state
+        .players
+        .iter()
+        .filter(|player| player.status != PlayerStatus::Folded)
+        .map(|player| player.committed_total + player.committed_this_round)
+        .sum();
+    if pots != committed {
+        violations.push(InvariantViolation::PotTotalMismatch { pots, committed });
+    }
+}
+
+/// Only the initial snapshot lacks a previous hash, and no snapshot links to itself.
+fn check_hash_link<P, C>(table: &TableSnapshot<P, C>, violations: &mut Vec<InvariantViolation>)
+where
+    P: HandPhase<C>,
+    C: CurveGroup,
+{
+    let sequence = table.sequence;
+    match table.previous_hash {
+        None if sequence > 0 => {
+            violations.push(InvariantViolation::MissingPreviousHash { sequence })
+        }
+        Some(_) if sequence == 0 => violations.push(InvariantViolation::UnexpectedPreviousHash),
+        Some(previous) if previous == table.state_hash => {
+            violations.push(InvariantViolation::SelfLinkedHash { sequence })
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::test_support::{
+        fixture_complete_snapshot, fixture_dealing_snapshot, fixture_preflop_snapshot,
+        fixture_shuffling_snapshot, FixtureContext,
+    };
+    use ark_bn254::G1Projective as Curve;
+
+    #[test]
+    fn fixtures_satisfy_every_invariant() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let snapshots = [
+            AnyTableSnapshot::Shuffling(fixture_shuffling_snapshot(&ctx)),
+            AnyTableSnapshot::Dealing(fixture_dealing_snapshot(&ctx)),
+            AnyTableSnapshot::Preflop(fixture_preflop_snapshot(&ctx)),
+            AnyTableSnapshot::Complete(fixture_complete_snapshot(&ctx)),
+        ];
+        for snapshot in &snapshots {
+            let phase = snapshot.event_phase();
+            assert_eq!(check_invariants(snapshot), Ok(()), "{phase:?}");
+        }
+    }
+
+    #[test]
+    fn corrupted_pot_total_is_reported() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_preflop_snapshot(&ctx);
+        let committed = snapshot.betting.state.pots.total();
+        snapshot.betting.state.pots.main.amount += 5;
+
+        assert_eq!(
+            check_invariants(&AnyTableSnapshot::Preflop(snapshot)),
+            Err(vec![InvariantViolation::PotTotalMismatch {
+                pots: committed + 5,
+                committed,
+            }])
+        );
+    }
+}
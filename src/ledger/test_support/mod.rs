@@ -3,6 +3,10 @@
 //! This module provides helpers and fixtures for writing tests against
 //! the ledger system, including snapshot fixtures and test data generators.
 
+pub mod committee;
 pub mod fixtures;
+pub mod invariants;
 
+pub use committee::Committee;
 pub use fixtures::*;
+pub use invariants::{check_invariants, InvariantViolation};
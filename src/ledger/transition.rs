@@ -6,25 +6,29 @@ use ark_serialize::CanonicalSerialize;
 
 use crate::curve_absorb::CurveAbsorb;
 use crate::engine::nl::engine::{BettingEngineNL, EngineNL, Transition};
+use crate::engine::nl::events::GameEvent;
 use crate::engine::nl::types::{PlayerStatus, SeatId, Street as EngineStreet};
 use crate::ledger::hash::LedgerHasher;
 use crate::ledger::messages::{
-    EnvelopedMessage, GameBlindingDecryptionMessage, GameMessage,
+    EnvelopedMessage, GameBlindingDecryptionMessage, GameMessage, GameNonceCommitmentMessage,
     GamePartialUnblindingShareMessage, GamePlayerMessage, GameShowdownMessage, GameShuffleMessage,
+    MuckDeclaration,
 };
 use crate::ledger::snapshot::{
     build_default_card_plan, build_initial_betting_state, AnyPlayerActionMsg, AnyTableSnapshot,
     BettingSnapshot, CardDestination, CardPlan, DealingSnapshot, DealtCard, PhaseBetting,
     PhaseDealing, PhaseShowdown, PhaseShuffling, PlayerRoster, RevealedHand, RevealsSnapshot,
-    ShufflingStep, SnapshotStatus, TableSnapshot,
+    ShufflingSnapshot, ShufflingStep, SnapshotStatus, TableAtShowdown, TableSnapshot,
 };
 use crate::ledger::store::snapshot::compute_dealing_hash;
 use crate::ledger::{FlopStreet, PreflopStreet, RiverStreet, TurnStreet};
 use crate::poseidon_config;
-use crate::showdown::{choose_best5_from7, idx_of};
+use crate::showdown::{choose_best5_for_hole, could_win_a_pot, idx_of, reveal_order};
 use crate::shuffling::data_structures::{ElGamalCiphertext, DECK_SIZE};
 use crate::shuffling::player_decryption::combine_unblinding_shares;
-use std::collections::BTreeMap;
+use crate::vrf::commit_nonce;
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
 use tracing::{
     error,
     field::{debug, display},
@@ -33,6 +37,23 @@ use tracing::{
 
 const LOG_TARGET: &str = "legit_poker::ledger::transition";
 
+/// Typed transition failures that callers may want to match on; everything else is reported
+/// through `anyhow` context.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TransitionError {
+    #[error("deck position {pos} out of range for deck of {deck_len} cards")]
+    DeckPositionOutOfRange { pos: u8, deck_len: usize },
+}
+
+fn check_deck_position(pos: u8, deck_len: usize) -> Result<usize, TransitionError> {
+    let idx = pos as usize;
+    if idx < deck_len {
+        Ok(idx)
+    } else {
+        Err(TransitionError::DeckPositionOutOfRange { pos, deck_len })
+    }
+}
+
 pub trait TransitionHandler<C>:
     GameMessage<C> + CanonicalSerialize + crate::signing::DomainSeparated
 where
@@ -59,6 +80,61 @@ where
     <M as TransitionHandler<C>>::apply_transition(snapshot, envelope, hasher)
 }
 
+/// Append the betting events produced by an engine transition to the snapshot's event log.
+fn record_betting_events<C: CurveGroup>(betting: &mut BettingSnapshot<C>, result: &Transition) {
+    betting.betting_events.extend(
+        result
+            .events()
+            .iter()
+            .filter_map(GameEvent::as_betting)
+            .cloned(),
+    );
+}
+
+/// First seat in reveal order that has neither revealed nor mucked.
+fn next_to_reveal<C: CurveGroup>(snapshot: &TableAtShowdown<C>) -> Option<SeatId> {
+    reveal_order(
+        &snapshot.betting.state,
+        snapshot.betting.state.last_aggressor,
+    )
+    .into_iter()
+    .find(|seat| !snapshot.reveals.has_resolved(*seat))
+}
+
+/// Move to `Complete` once every contender has revealed, mucked or been auto-mucked.
+fn finish_showdown_if_resolved<C: CurveGroup>(snapshot: TableAtShowdown<C>) -> AnyTableSnapshot<C> {
+    let all_resolved = snapshot
+        .stacks
+        .values()
+        .filter(|info| {
+            info.player_key.is_some()
+                && matches!(info.status, PlayerStatus::Active | PlayerStatus::AllIn)
+        })
+        .all(|info| snapshot.reveals.has_resolved(info.seat));
+
+    if !all_resolved {
+        return AnyTableSnapshot::Showdown(snapshot);
+    }
+
+    AnyTableSnapshot::Complete(TableSnapshot {
+        game_id: snapshot.game_id,
+        hand_id: snapshot.hand_id,
+        sequence: snapshot.sequence,
+        cfg: snapshot.cfg,
+        shufflers: snapshot.shufflers,
+        players: snapshot.players,
+        seating: snapshot.seating,
+        stacks: snapshot.stacks,
+        previous_hash: snapshot.previous_hash,
+        state_hash: snapshot.state_hash,
+        status: SnapshotStatus::Success,
+        shuffling: snapshot.shuffling,
+        dealing: snapshot.dealing,
+        betting: snapshot.betting,
+        reveals: snapshot.reveals,
+    })
+}
+
 fn curve_hex<C>(value: &C) -> Result<String>
 where
     C: CurveGroup + CanonicalSerialize,
@@ -123,7 +199,27 @@ fn initialize_dealing_state<C: CurveGroup>(
     }
 }
 
-fn promote_to_dealing<C: CurveGroup>(
+/// Open each board card about to be revealed against the shuffled deck.
+///
+/// Shufflers run the same check before contributing decryption shares; repeating it here stops
+/// a card whose ciphertext was swapped after the shuffle from reaching the board.
+fn check_board_positions<C: CurveGroup>(
+    dealing: &DealingSnapshot<C>,
+    shuffling: &ShufflingSnapshot<C>,
+    card_refs: &[u8],
+) -> Result<()> {
+    for card_ref in card_refs {
+        dealing
+            .assignments
+            .get(card_ref)
+            .with_context(|| format!("board card {card_ref} has no deck assignment"))?
+            .verify_deck_position(&shuffling.final_deck)
+            .with_context(|| format!("board card {card_ref} failed deck position check"))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn promote_to_dealing<C: CurveGroup>(
     table: TableSnapshot<PhaseShuffling, C>,
 ) -> Result<AnyTableSnapshot<C>> {
     let TableSnapshot {
@@ -214,6 +310,7 @@ fn promote_to_showdown_from_river<C: CurveGroup>(
 impl<C> TransitionHandler<C> for GameShuffleMessage<C>
 where
     C: CurveGroup,
+    C::BaseField: PrimeField,
 {
     fn apply_transition(
         mut snapshot: TableSnapshot<Self::Phase, C>,
@@ -249,6 +346,33 @@ where
             shuffler_key == envelope.actor.shuffler_key,
             "shuffle actor key mismatch"
         );
+        if expected_index == 0 {
+            let missing = snapshot.shuffling.missing_nonce_commitments().count();
+            ensure!(
+                missing == 0,
+                "{missing} shuffler(s) have not committed to a VRF nonce before the first shuffle"
+            );
+        }
+        let commitment = snapshot
+            .shuffling
+            .nonce_commitment(&shuffler_key)
+            .context("shuffler did not commit to a VRF nonce")?;
+        let nonce = message
+            .vrf_nonce
+            .context("shuffle does not reveal its committed VRF nonce")?;
+        ensure!(
+            commit_nonce(&nonce) == *commitment,
+            "revealed VRF nonce does not match the shuffler's commitment"
+        );
+        ensure!(
+            message.beacon_round.is_none() || message.vrf_nonce.is_some(),
+            "shuffle records a beacon round without revealing its VRF nonce"
+        );
+        #[cfg(any(test, feature = "test-mocks"))]
+        ensure!(
+            !crate::shuffling::mock_proof::MockShufflingProof::is_rejected(&message.proof),
+            "shuffle proof rejected: mock proof flagged invalid"
+        );
 
         if snapshot.shuffling.steps.is_empty() {
             snapshot.shuffling.initial_deck = message.deck_in.clone();
@@ -263,6 +387,8 @@ where
         snapshot.shuffling.steps.push(ShufflingStep {
             shuffler_public_key,
             proof: message.proof.clone(),
+            beacon_round: message.beacon_round,
+            vrf_nonce: message.vrf_nonce,
         });
 
         snapshot.advance_state_with_message(envelope, hasher);
@@ -275,6 +401,31 @@ where
     }
 }
 
+impl<C> TransitionHandler<C> for GameNonceCommitmentMessage<C>
+where
+    C: CurveGroup,
+{
+    fn apply_transition(
+        mut snapshot: TableSnapshot<Self::Phase, C>,
+        envelope: &EnvelopedMessage<C, Self>,
+        hasher: &dyn LedgerHasher,
+    ) -> Result<AnyTableSnapshot<C>> {
+        ensure!(
+            snapshot
+                .shufflers
+                .contains_key(&envelope.actor.shuffler_key),
+            "unknown shuffler for nonce commitment"
+        );
+        snapshot.shuffling.record_nonce_commitment(
+            envelope.actor.shuffler_key.clone(),
+            envelope.message.value.commitment,
+        )?;
+
+        snapshot.advance_state_with_message(envelope, hasher);
+        Ok(AnyTableSnapshot::Shuffling(snapshot))
+    }
+}
+
 impl<C> TransitionHandler<C> for GameBlindingDecryptionMessage<C>
 where
     C: CurveGroup + CanonicalSerialize + CurveAbsorb<C::BaseField>,
@@ -305,6 +456,22 @@ where
         span.record("shuffler_id", &debug(&shuffler_key));
         let card_pos = envelope.message.value.card_in_deck_position;
         let card_ref = card_pos;
+        let deck_len = snapshot.shuffling.final_deck.len();
+        let deck_idx = match check_deck_position(card_pos, deck_len) {
+            Ok(idx) => idx,
+            Err(err) => {
+                warn!(
+                    target = LOG_TARGET,
+                    game_id = snapshot.game_id,
+                    hand_id = snapshot.hand_id,
+                    ?shuffler_key,
+                    card_pos,
+                    deck_len,
+                    "blinding contribution references deck position out of range"
+                );
+                return Err(err.into());
+            }
+        };
 
         let shuffler = match snapshot.shufflers.get(&shuffler_key) {
             Some(identity) => identity,
@@ -352,23 +519,7 @@ where
             }
         };
 
-        let player_key = match snapshot.seating.get(&seat).and_then(|key| key.clone()) {
-            Some(key) => key,
-            None => {
-                warn!(
-                    target = LOG_TARGET,
-                    game_id = snapshot.game_id,
-                    hand_id = snapshot.hand_id,
-                    ?shuffler_key,
-                    seat,
-                    hole_index,
-                    "blinding contribution received for empty seat"
-                );
-                return Err(anyhow!("seat has no player assigned"));
-            }
-        };
-
-        let player_identity = match snapshot.players.get(&player_key) {
+        let player_identity = match snapshot.identity_at_seat(seat) {
             Some(identity) => identity,
             None => {
                 warn!(
@@ -378,9 +529,9 @@ where
                     ?shuffler_key,
                     seat,
                     hole_index,
-                    "blinding contribution referenced missing player identity"
+                    "blinding contribution received for seat without a player"
                 );
-                return Err(anyhow!("player identity not found"));
+                return Err(anyhow!("seat {seat} has no player assigned"));
             }
         };
 
@@ -486,21 +637,7 @@ where
         );
 
         if ready_contribs.len() == snapshot.shufflers.len() {
-            let deck_cipher = match snapshot.shuffling.final_deck.get(card_pos as usize) {
-                Some(cipher) => cipher.clone(),
-                None => {
-                    warn!(
-                        target = LOG_TARGET,
-                        game_id = snapshot.game_id,
-                        hand_id = snapshot.hand_id,
-                        seat,
-                        hole_index,
-                        card_pos,
-                        "deck position out of range when assembling ciphertext"
-                    );
-                    return Err(anyhow!("deck position out of range"));
-                }
-            };
+            let deck_cipher = snapshot.shuffling.final_deck[deck_idx].clone();
 
             let combined =
                 crate::shuffling::player_decryption::combine_blinding_contributions_for_player(
@@ -643,16 +780,9 @@ where
             other => bail!("partial unblinding share targets non-hole card: {other:?}"),
         };
 
-        let player_key = snapshot
-            .seating
-            .get(&seat)
-            .and_then(|id| id.clone())
-            .context("seat has no player assigned")?;
-
         let player_identity = snapshot
-            .players
-            .get(&player_key)
-            .context("player identity not found")?;
+            .identity_at_seat(seat)
+            .with_context(|| format!("seat {seat} has no player assigned"))?;
 
         let target_player_public_key = envelope.message.value.target_player_public_key.clone();
 
@@ -713,15 +843,19 @@ where
                 snapshot.cfg.as_ref(),
                 snapshot.stacks.as_ref(),
                 snapshot.players.as_ref(),
-            );
+            )
+            .map_err(|err| anyhow!("cannot start betting: {err:?}"))?;
             let betting = BettingSnapshot {
                 state: betting_state,
                 last_events: Vec::new(),
+                betting_events: Vec::new(),
             };
 
             let reveals = RevealsSnapshot {
                 board: Vec::new(),
                 revealed_holes: BTreeMap::new(),
+                mucked: BTreeMap::new(),
+                auto_mucked: BTreeSet::new(),
             };
 
             return Ok(AnyTableSnapshot::Preflop(TableSnapshot {
@@ -782,6 +916,7 @@ where
             .betting
             .last_events
             .push(AnyPlayerActionMsg::Preflop(envelope.message.value.clone()));
+        record_betting_events(&mut snapshot.betting, &result);
 
         snapshot.advance_state_with_message(envelope, hasher);
 
@@ -809,6 +944,7 @@ where
                         .all(|r| snapshot.dealing.community_cards.contains_key(r)),
                     "flop community cards not fully decrypted"
                 );
+                check_board_positions(&snapshot.dealing, &snapshot.shuffling, &flop_refs)?;
 
                 EngineNL::advance_street(&mut snapshot.betting.state)
                     .map_err(|err| anyhow!("failed to advance street: {:?}", err))?;
@@ -819,6 +955,8 @@ where
                         .map(|r| snapshot.dealing.community_cards[r])
                         .collect(),
                     revealed_holes: snapshot.reveals.revealed_holes.clone(),
+                    mucked: snapshot.reveals.mucked.clone(),
+                    auto_mucked: snapshot.reveals.auto_mucked.clone(),
                 };
 
                 Ok(AnyTableSnapshot::Flop(TableSnapshot {
@@ -895,6 +1033,7 @@ where
             .betting
             .last_events
             .push(AnyPlayerActionMsg::Flop(envelope.message.value.clone()));
+        record_betting_events(&mut snapshot.betting, &result);
 
         snapshot.advance_state_with_message(envelope, hasher);
 
@@ -915,6 +1054,7 @@ where
                         _ => None,
                     })
                     .context("turn card not present in card plan")?;
+                check_board_positions(&snapshot.dealing, &snapshot.shuffling, &[turn_ref])?;
 
                 let turn_card = snapshot
                     .dealing
@@ -945,6 +1085,8 @@ where
                 let reveals = RevealsSnapshot {
                     board,
                     revealed_holes: snapshot.reveals.revealed_holes.clone(),
+                    mucked: snapshot.reveals.mucked.clone(),
+                    auto_mucked: snapshot.reveals.auto_mucked.clone(),
                 };
 
                 Ok(AnyTableSnapshot::Turn(TableSnapshot {
@@ -1021,6 +1163,7 @@ where
             .betting
             .last_events
             .push(AnyPlayerActionMsg::Turn(envelope.message.value.clone()));
+        record_betting_events(&mut snapshot.betting, &result);
 
         snapshot.advance_state_with_message(envelope, hasher);
 
@@ -1040,6 +1183,7 @@ where
                         _ => None,
                     })
                     .context("river card not present in card plan")?;
+                check_board_positions(&snapshot.dealing, &snapshot.shuffling, &[river_ref])?;
 
                 let river_card = snapshot
                     .dealing
@@ -1071,6 +1215,8 @@ where
                 let reveals = RevealsSnapshot {
                     board,
                     revealed_holes: snapshot.reveals.revealed_holes.clone(),
+                    mucked: snapshot.reveals.mucked.clone(),
+                    auto_mucked: snapshot.reveals.auto_mucked.clone(),
                 };
 
                 Ok(AnyTableSnapshot::River(TableSnapshot {
@@ -1147,6 +1293,7 @@ where
             .betting
             .last_events
             .push(AnyPlayerActionMsg::River(envelope.message.value.clone()));
+        record_betting_events(&mut snapshot.betting, &result);
 
         snapshot.advance_state_with_message(envelope, hasher);
 
@@ -1194,6 +1341,17 @@ where
             !snapshot.reveals.revealed_holes.contains_key(&seat),
             "player at seat {seat} has already revealed their hand"
         );
+        ensure!(
+            !snapshot.reveals.mucked.contains_key(&seat)
+                && !snapshot.reveals.auto_mucked.contains(&seat),
+            "player at seat {seat} has already mucked their hand"
+        );
+
+        let expected_seat = next_to_reveal(&snapshot);
+        ensure!(
+            expected_seat == Some(seat),
+            "seat {seat} revealed out of turn; next to reveal is {expected_seat:?}"
+        );
 
         let player_identity = snapshot
             .players
@@ -1212,10 +1370,18 @@ where
         );
 
         let message = &envelope.message.value;
+        let hole_cards = usize::from(snapshot.cfg.hole_cards);
+        ensure!(
+            message.card_in_deck_position.len() == hole_cards
+                && message.hole_ciphertexts.len() == hole_cards
+                && message.chaum_pedersen_proofs.len() == hole_cards,
+            "showdown for seat {seat} must reveal {hole_cards} hole cards"
+        );
+
         let poseidon_params = poseidon_config::<C::BaseField>();
-        let mut revealed_hole_cards = [0u8; 2];
+        let mut revealed_hole_cards = vec![0u8; hole_cards];
 
-        for hole_idx in 0..2 {
+        for hole_idx in 0..hole_cards {
             let hole_index_u8 = hole_idx as u8;
             let deck_position = message.card_in_deck_position[hole_idx];
             let provided_cipher = &message.hole_ciphertexts[hole_idx];
@@ -1290,16 +1456,39 @@ where
             revealed_hole_cards[hole_idx] = card_value;
         }
 
-        let board_slice = snapshot.reveals.board.as_slice();
-        let cards7 = std::array::from_fn(|idx| {
-            if idx < 5 {
-                board_slice[idx]
-            } else {
-                revealed_hole_cards[idx - 5]
+        let board: [u8; 5] = snapshot
+            .reveals
+            .board
+            .as_slice()
+            .try_into()
+            .context("showdown requires all five community cards to be revealed")?;
+        let best = choose_best5_for_hole(&revealed_hole_cards, board)
+            .with_context(|| format!("no showdown evaluator for {hole_cards} hole cards"))?;
+
+        // A verified hand that cannot win any pot is mucked for players who opted in, so they
+        // don't have to sign a separate muck declaration.
+        if player_identity.auto_muck_losers {
+            let revealed_scores = snapshot
+                .reveals
+                .revealed_holes
+                .iter()
+                .map(|(seat, hand)| (*seat, hand.best_score))
+                .collect();
+            let pots = &snapshot.betting.state.pots;
+            if !could_win_a_pot(pots, seat, best.score_u32, &revealed_scores) {
+                info!(
+                    target = LOG_TARGET,
+                    game_id = snapshot.game_id,
+                    hand_id = snapshot.hand_id,
+                    seat,
+                    "auto-mucked a hand that cannot win any pot"
+                );
+                snapshot.reveals.auto_mucked.insert(seat);
+                snapshot.advance_state_with_message(envelope, hasher);
+                return Ok(finish_showdown_if_resolved(snapshot));
             }
-        });
+        }
 
-        let best = choose_best5_from7(cards7);
         let best_indices: [u8; 5] = best.hand.cards.map(|card| idx_of(card.rank, card.suit));
 
         let revealed_hand = RevealedHand {
@@ -1319,40 +1508,58 @@ where
 
         snapshot.advance_state_with_message(envelope, hasher);
 
-        let pending_reveals: Vec<_> = snapshot
-            .stacks
-            .values()
-            .filter(|info| {
-                info.player_key.is_some()
-                    && matches!(info.status, PlayerStatus::Active | PlayerStatus::AllIn)
-            })
-            .map(|info| info.seat)
-            .collect();
-        let all_revealed = pending_reveals
-            .iter()
-            .all(|seat_id| snapshot.reveals.revealed_holes.contains_key(seat_id));
+        Ok(finish_showdown_if_resolved(snapshot))
+    }
+}
 
-        if all_revealed {
-            Ok(AnyTableSnapshot::Complete(TableSnapshot {
-                game_id: snapshot.game_id,
-                hand_id: snapshot.hand_id,
-                sequence: snapshot.sequence,
-                cfg: snapshot.cfg,
-                shufflers: snapshot.shufflers,
-                players: snapshot.players,
-                seating: snapshot.seating,
-                stacks: snapshot.stacks,
-                previous_hash: snapshot.previous_hash,
-                state_hash: snapshot.state_hash,
-                status: SnapshotStatus::Success,
-                shuffling: snapshot.shuffling,
-                dealing: snapshot.dealing,
-                betting: snapshot.betting,
-                reveals: snapshot.reveals,
-            }))
-        } else {
-            Ok(AnyTableSnapshot::Showdown(snapshot))
-        }
+/// Record a player's decision to muck instead of revealing at showdown.
+///
+/// The declaration must name this hand and the acting seat, and mucking counts as that seat's
+/// showdown turn. The envelope signature was checked by the verifier and is kept with the
+/// declaration in the snapshot.
+impl<C> TransitionHandler<C> for MuckDeclaration<C>
+where
+    C: CurveGroup,
+{
+    fn apply_transition(
+        mut snapshot: TableSnapshot<Self::Phase, C>,
+        envelope: &EnvelopedMessage<C, Self>,
+        hasher: &dyn LedgerHasher,
+    ) -> Result<AnyTableSnapshot<C>> {
+        let muck = &envelope.message.value;
+        let seat = muck.seat;
+        ensure!(
+            muck.game_id == snapshot.game_id && Some(muck.hand_id) == snapshot.hand_id,
+            "muck declaration is for game {} hand {}, not this hand",
+            muck.game_id,
+            muck.hand_id
+        );
+        ensure!(
+            envelope.actor.seat_id == seat && envelope.actor.player_key == muck.player_key,
+            "muck declaration for seat {seat} was not submitted by that seat"
+        );
+
+        let player_identity = snapshot
+            .identity_at_seat(seat)
+            .with_context(|| format!("seat {seat} has no player assigned"))?;
+        ensure!(
+            player_identity.player_key == muck.player_key,
+            "muck declaration player key does not match seat {seat}"
+        );
+
+        let expected_seat = next_to_reveal(&snapshot);
+        ensure!(
+            expected_seat == Some(seat),
+            "seat {seat} mucked out of turn; next to reveal is {expected_seat:?}"
+        );
+
+        snapshot
+            .reveals
+            .mucked
+            .insert(seat, envelope.message.clone());
+        snapshot.advance_state_with_message(envelope, hasher);
+
+        Ok(finish_showdown_if_resolved(snapshot))
     }
 }
 
@@ -1376,25 +1583,30 @@ mod tests {
     use super::*;
     use crate::chaum_pedersen::ChaumPedersenProof;
     use crate::engine::nl::actions::PlayerBetAction;
-    use crate::engine::nl::types::{PlayerId, PlayerStatus, SeatId, Street as EngineStreet};
+    use crate::engine::nl::types::{
+        HandConfig, PlayerId, PlayerStatus, Pot, Pots, SeatId, Street as EngineStreet,
+        OMAHA_HOLE_CARDS,
+    };
     use crate::ledger::actor::{PlayerActor, ShufflerActor};
     use crate::ledger::messages::Street;
     use crate::ledger::messages::{
         EnvelopedMessage, GameBlindingDecryptionMessage, GameMessage, GamePlayerMessage,
-        GameShowdownMessage, GameShuffleMessage,
+        GameShowdownMessage, GameShuffleMessage, MuckDeclaration,
     };
     use crate::ledger::snapshot::{
         AnyPlayerActionMsg, AnyTableSnapshot, CardDestination, TableAtDealing, TableAtShowdown,
     };
     use crate::ledger::test_support::{
-        active_seats, fixture_dealing_snapshot, fixture_flop_snapshot, fixture_preflop_snapshot,
+        active_seats, check_invariants, fixture_committed_shuffling_snapshot,
+        fixture_dealing_snapshot, fixture_flop_snapshot, fixture_preflop_snapshot,
         fixture_river_snapshot, fixture_showdown_snapshot, fixture_shuffling_snapshot,
-        fixture_turn_snapshot, populate_board_cards_upto, FixtureContext,
+        fixture_turn_snapshot, fixture_vrf_nonce, populate_board_cards_upto, FixtureContext,
     };
     use crate::ledger::types::ShufflerId;
     use crate::shuffling::data_structures::{ElGamalCiphertext, ShuffleProof, DECK_SIZE};
+    use crate::shuffling::mock_proof::MockShufflingProof;
     use crate::shuffling::player_decryption::PlayerTargetedBlindingContribution;
-    use crate::signing::WithSignature;
+    use crate::signing::{DomainSeparated, WithSignature};
     use ark_bn254::G1Projective as Curve;
     use ark_ec::PrimeGroup;
     use ark_ff::Zero;
@@ -1436,14 +1648,16 @@ mod tests {
         )
     }
 
-    fn build_shuffle_envelope<C: CurveGroup>(
+    fn build_shuffle_envelope<C, M>(
         ctx: &FixtureContext<C>,
         shuffler_id: ShufflerId,
-        message: GameShuffleMessage<C>,
-    ) -> EnvelopedMessage<C, GameShuffleMessage<C>>
+        message: M,
+    ) -> EnvelopedMessage<C, M>
     where
+        C: CurveGroup,
         C::BaseField: Zero,
         C::ScalarField: Zero,
+        M: GameMessage<C, Actor = ShufflerActor<C>> + CanonicalSerialize + DomainSeparated,
     {
         let with_sig = WithSignature {
             value: message,
@@ -1506,6 +1720,13 @@ mod tests {
         new_deck
     }
 
+    /// Post-condition for every successful transition below.
+    fn assert_invariants(snapshot: &AnyTableSnapshot<Curve>) {
+        if let Err(violations) = check_invariants(snapshot) {
+            panic!("snapshot invariants violated: {violations:?}");
+        }
+    }
+
     fn player_actor_info(
         ctx: &FixtureContext<Curve>,
         seat: SeatId,
@@ -1557,23 +1778,20 @@ mod tests {
         seat: SeatId,
         player_secret: <Curve as PrimeGroup>::ScalarField,
     ) -> GameShowdownMessage<Curve> {
-        let mut positions = [0u8; 2];
-        let ciphertexts = [
-            snapshot
-                .dealing
-                .player_ciphertexts
-                .get(&(seat, 0))
-                .cloned()
-                .expect("hole 0 ciphertext present"),
-            snapshot
-                .dealing
-                .player_ciphertexts
-                .get(&(seat, 1))
-                .cloned()
-                .expect("hole 1 ciphertext present"),
-        ];
+        let hole_cards = snapshot.cfg.hole_cards;
+        let mut positions = vec![0u8; usize::from(hole_cards)];
+        let ciphertexts: Vec<_> = (0..hole_cards)
+            .map(|hole_index| {
+                snapshot
+                    .dealing
+                    .player_ciphertexts
+                    .get(&(seat, hole_index))
+                    .cloned()
+                    .expect("hole ciphertext present")
+            })
+            .collect();
 
-        for hole_index in 0..2u8 {
+        for hole_index in 0..hole_cards {
             let card_ref = snapshot
                 .dealing
                 .card_plan
@@ -1597,17 +1815,21 @@ mod tests {
         }
 
         let poseidon_params = poseidon_config::<<Curve as CurveGroup>::BaseField>();
-        let proofs = std::array::from_fn(|idx| {
-            let mut sponge = PoseidonSponge::new(&poseidon_params);
-            let mut rng = StdRng::seed_from_u64(0xC0DEC0DEu64 ^ idx as u64);
-            ChaumPedersenProof::prove(
-                &mut sponge,
-                player_secret,
-                Curve::generator(),
-                ciphertexts[idx].player_unblinding_helper,
-                &mut rng,
-            )
-        });
+        let proofs = ciphertexts
+            .iter()
+            .enumerate()
+            .map(|(idx, ciphertext)| {
+                let mut sponge = PoseidonSponge::new(&poseidon_params);
+                let mut rng = StdRng::seed_from_u64(0xC0DEC0DEu64 ^ idx as u64);
+                ChaumPedersenProof::prove(
+                    &mut sponge,
+                    player_secret,
+                    Curve::generator(),
+                    ciphertext.player_unblinding_helper,
+                    &mut rng,
+                )
+            })
+            .collect();
 
         GameShowdownMessage::new(proofs, positions, ciphertexts)
     }
@@ -1641,15 +1863,17 @@ mod tests {
     #[test]
     fn shuffle_stays_in_phase_until_final_step() {
         let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
-        let snapshot = fixture_shuffling_snapshot(&ctx);
+        let snapshot = fixture_committed_shuffling_snapshot(&ctx);
 
         let deck_out = swap_deck_entries(&snapshot.shuffling.final_deck, 0, 1);
-        let message = build_shuffle_message(&snapshot.shuffling.final_deck, &deck_out, 0);
+        let message = build_shuffle_message(&snapshot.shuffling.final_deck, &deck_out, 0)
+            .with_vrf_nonce(fixture_vrf_nonce(10));
         let envelope = build_shuffle_envelope(&ctx, 10, message);
 
         let result =
             GameShuffleMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
                 .expect("shuffle transition should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Shuffling(next) => {
@@ -1674,10 +1898,130 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn shuffle_accepts_mock_valid_and_rejects_mock_invalid_proofs() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let snapshot = fixture_committed_shuffling_snapshot(&ctx);
+        let deck_in = snapshot.shuffling.final_deck.clone();
+        let deck_out = swap_deck_entries(&deck_in, 0, 1);
+
+        let valid = GameShuffleMessage::new(
+            deck_in.clone(),
+            deck_out.clone(),
+            MockShufflingProof::valid(&deck_in, &deck_out),
+            0,
+        )
+        .with_vrf_nonce(fixture_vrf_nonce(10));
+        let envelope = build_shuffle_envelope(&ctx, 10, valid);
+        assert!(GameShuffleMessage::<Curve>::apply_transition(
+            snapshot.clone(),
+            &envelope,
+            &ctx.hasher
+        )
+        .is_ok());
+
+        let invalid = GameShuffleMessage::new(
+            deck_in.clone(),
+            deck_out.clone(),
+            MockShufflingProof::invalid(&deck_in, &deck_out),
+            0,
+        )
+        .with_vrf_nonce(fixture_vrf_nonce(10));
+        let envelope = build_shuffle_envelope(&ctx, 10, invalid);
+        let err = GameShuffleMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
+            .expect_err("mock-invalid proof should be rejected");
+        assert!(err.to_string().contains("mock proof flagged invalid"));
+    }
+
+    #[test]
+    fn shuffle_rejects_nonce_that_does_not_match_commitment() {
+        type BaseField = <Curve as CurveGroup>::BaseField;
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let nonce = BaseField::from(42u64);
+        let commitment = build_shuffle_envelope(
+            &ctx,
+            10,
+            GameNonceCommitmentMessage::<Curve>::new(commit_nonce(&nonce)),
+        );
+        let snapshot = match GameNonceCommitmentMessage::apply_transition(
+            fixture_shuffling_snapshot(&ctx),
+            &commitment,
+            &ctx.hasher,
+        )
+        .expect("commit before shuffling")
+        {
+            AnyTableSnapshot::Shuffling(next) => next,
+            other => panic!("expected shuffling snapshot, got {:?}", other),
+        };
+        assert_eq!(snapshot.shuffling.nonce_commitments.len(), 1);
+        assert!(GameNonceCommitmentMessage::apply_transition(
+            snapshot.clone(),
+            &commitment,
+            &ctx.hasher
+        )
+        .is_err());
+
+        let deck_in = snapshot.shuffling.final_deck.clone();
+        let deck_out = swap_deck_entries(&deck_in, 0, 1);
+
+        // Shuffler 11 has not committed yet, so not even an honest first shuffle is accepted.
+        let early = build_shuffle_message(&deck_in, &deck_out, 0).with_vrf_nonce(nonce);
+        let envelope = build_shuffle_envelope(&ctx, 10, early);
+        let err =
+            GameShuffleMessage::<Curve>::apply_transition(snapshot.clone(), &envelope, &ctx.hasher)
+                .expect_err("shuffling must wait for every nonce commitment");
+        assert!(err.to_string().contains("have not committed"));
+
+        let other = build_shuffle_envelope(
+            &ctx,
+            11,
+            GameNonceCommitmentMessage::<Curve>::new(commit_nonce(&fixture_vrf_nonce(11))),
+        );
+        let snapshot =
+            match GameNonceCommitmentMessage::apply_transition(snapshot, &other, &ctx.hasher)
+                .expect("second shuffler commits")
+            {
+                AnyTableSnapshot::Shuffling(next) => next,
+                other => panic!("expected shuffling snapshot, got {:?}", other),
+            };
+
+        let ground = build_shuffle_message(&deck_in, &deck_out, 0)
+            .with_vrf_nonce(nonce + BaseField::from(1u64));
+        let envelope = build_shuffle_envelope(&ctx, 10, ground);
+        let err =
+            GameShuffleMessage::<Curve>::apply_transition(snapshot.clone(), &envelope, &ctx.hasher)
+                .expect_err("nonce differing from the commitment should be rejected");
+        assert!(err.to_string().contains("does not match"));
+
+        let unrevealed = build_shuffle_message(&deck_in, &deck_out, 0);
+        let envelope = build_shuffle_envelope(&ctx, 10, unrevealed);
+        assert!(GameShuffleMessage::<Curve>::apply_transition(
+            snapshot.clone(),
+            &envelope,
+            &ctx.hasher
+        )
+        .is_err());
+
+        let honest = build_shuffle_message(&deck_in, &deck_out, 0)
+            .with_vrf_nonce(nonce)
+            .with_beacon_round(4_242_424);
+        let envelope = build_shuffle_envelope(&ctx, 10, honest);
+        match GameShuffleMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
+            .expect("committed nonce should be accepted")
+        {
+            AnyTableSnapshot::Shuffling(next) => {
+                assert_eq!(next.shuffling.steps[0].vrf_nonce, Some(nonce));
+                assert_eq!(next.shuffling.steps[0].beacon_round, Some(4_242_424));
+            }
+            other => panic!("expected shuffling snapshot, got {:?}", other),
+        }
+    }
+
     #[test]
     fn shuffle_promotes_to_dealing_after_last_shuffler() {
         let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
-        let mut snapshot = fixture_shuffling_snapshot(&ctx);
+        let mut snapshot = fixture_committed_shuffling_snapshot(&ctx);
 
         // Simulate first shuffler already acted.
         let prefinal_deck = swap_deck_entries(&snapshot.shuffling.final_deck, 0, 1);
@@ -1693,16 +2037,20 @@ mod tests {
         snapshot.shuffling.steps.push(ShufflingStep {
             shuffler_public_key: identity.public_key.clone(),
             proof: dummy_shuffle_proof(&snapshot.shuffling.final_deck, &prefinal_deck),
+            beacon_round: None,
+            vrf_nonce: Some(fixture_vrf_nonce(10)),
         });
         snapshot.shuffling.final_deck = prefinal_deck.clone();
 
         let final_deck = swap_deck_entries(&prefinal_deck, 2, 3);
-        let message = build_shuffle_message(&prefinal_deck, &final_deck, 1);
+        let message = build_shuffle_message(&prefinal_deck, &final_deck, 1)
+            .with_vrf_nonce(fixture_vrf_nonce(11));
         let envelope = build_shuffle_envelope(&ctx, 11, message);
 
         let result =
             GameShuffleMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
                 .expect("shuffle transition should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Dealing(next) => {
@@ -1856,6 +2204,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("correct deal index should succeed");
+        assert_invariants(&result);
 
         assert!(
             matches!(result, AnyTableSnapshot::Dealing(_)),
@@ -1863,6 +2212,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blinding_contribution_rejects_deck_position_out_of_range() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let snapshot = fixture_dealing_snapshot(&ctx);
+        let (_seat, _player_id, player_pk) = first_player(&ctx);
+
+        let bad_pos = DECK_SIZE as u8 + 3;
+        let share = generate_blinding_contribution(&ctx, 10, player_pk.clone(), 0);
+        let message = GameBlindingDecryptionMessage::new(bad_pos, share, player_pk);
+        let envelope = build_blinding_envelope(&ctx, 10, message);
+
+        let err = GameBlindingDecryptionMessage::<Curve>::apply_transition(
+            snapshot,
+            &envelope,
+            &ctx.hasher,
+        )
+        .expect_err("out-of-range deck position should be rejected");
+        assert_eq!(
+            err.downcast_ref::<TransitionError>(),
+            Some(&TransitionError::DeckPositionOutOfRange {
+                pos: bad_pos,
+                deck_len: DECK_SIZE,
+            })
+        );
+    }
+
     #[test]
     fn blinding_contribution_stays_in_dealing_until_hole_complete() {
         let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
@@ -1884,6 +2259,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("blinding transition should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Dealing(next) => {
@@ -1960,6 +2336,7 @@ mod tests {
                     &ctx.hasher,
                 )
                 .expect("blinding transition should succeed");
+                assert_invariants(&result);
 
                 match result {
                     AnyTableSnapshot::Preflop(next) => {
@@ -2035,6 +2412,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         populate_board_cards_upto(&mut snapshot.dealing, 3);
@@ -2046,6 +2424,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("preflop action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Preflop(next) => {
@@ -2095,6 +2474,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         populate_board_cards_upto(&mut snapshot.dealing, 3);
@@ -2106,6 +2486,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("preflop action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Flop(next) => {
@@ -2123,6 +2504,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn flop_is_not_revealed_from_a_card_that_does_not_open_the_deck() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_preflop_snapshot(&ctx);
+        let seats = active_seats(&ctx);
+        let seat = seats[1];
+        let opponent = seats[0];
+
+        {
+            let state = &mut snapshot.betting.state;
+            state.street = EngineStreet::Preflop;
+            state.first_to_act = seat;
+            state.to_act = seat;
+            state.pending_to_match.clear();
+            state.voluntary_bet_opened = false;
+            state.current_bet_to_match = 0;
+            state.last_full_raise_amount = 0;
+            state.last_aggressor = None;
+            state.betting_locked_all_in = false;
+            for player in state.players.iter_mut() {
+                if player.seat == seat {
+                    player.status = PlayerStatus::Active;
+                    player.has_acted_this_round = false;
+                } else if player.seat == opponent {
+                    player.status = PlayerStatus::Active;
+                    player.has_acted_this_round = true;
+                } else {
+                    player.status = PlayerStatus::Folded;
+                }
+                player.committed_this_round = 0;
+            }
+            state.refresh_pots();
+        }
+
+        populate_board_cards_upto(&mut snapshot.dealing, 3);
+        let flop_ref = snapshot
+            .dealing
+            .card_plan
+            .iter()
+            .find_map(|(&card_ref, destination)| match destination {
+                CardDestination::Board { board_index: 0 } => Some(card_ref),
+                _ => None,
+            })
+            .expect("card plan has a flop");
+        // Swap in a different deck card while keeping the claimed position.
+        let other = (flop_ref as usize + 1) % DECK_SIZE;
+        snapshot
+            .dealing
+            .assignments
+            .get_mut(&flop_ref)
+            .expect("flop card assigned")
+            .cipher = snapshot.shuffling.final_deck[other].clone();
+
+        let envelope = build_player_envelope::<PreflopStreet>(&ctx, seat, PlayerBetAction::Check);
+        let err = GamePlayerMessage::<PreflopStreet, Curve>::apply_transition(
+            snapshot,
+            &envelope,
+            &ctx.hasher,
+        )
+        .expect_err("tampered flop card must not be revealed");
+        assert!(
+            format!("{err:#}").contains("deck position"),
+            "unexpected error: {err:#}"
+        );
+    }
+
     #[test]
     fn preflop_fold_ends_hand() {
         let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
@@ -2152,6 +2599,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         let envelope = build_player_envelope::<PreflopStreet>(&ctx, seat, PlayerBetAction::Fold);
@@ -2161,6 +2609,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("preflop fold should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Showdown(next) => {
@@ -2216,6 +2665,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         populate_board_cards_upto(&mut snapshot.dealing, 4);
@@ -2227,6 +2677,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("flop action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Flop(next) => {
@@ -2272,6 +2723,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         populate_board_cards_upto(&mut snapshot.dealing, 4);
@@ -2283,6 +2735,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("flop action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Turn(next) => {
@@ -2322,6 +2775,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         let envelope = build_player_envelope::<FlopStreet>(&ctx, seat, PlayerBetAction::Fold);
@@ -2331,6 +2785,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("flop fold should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Showdown(next) => {
@@ -2379,6 +2834,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         populate_board_cards_upto(&mut snapshot.dealing, 5);
@@ -2390,6 +2846,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("turn action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Turn(next) => {
@@ -2431,6 +2888,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         populate_board_cards_upto(&mut snapshot.dealing, 5);
@@ -2442,6 +2900,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("turn action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::River(next) => {
@@ -2480,6 +2939,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         let envelope = build_player_envelope::<TurnStreet>(&ctx, seat, PlayerBetAction::Fold);
@@ -2489,6 +2949,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("turn fold should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Showdown(next) => {
@@ -2537,6 +2998,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         let envelope = build_player_envelope::<RiverStreet>(&ctx, seat, PlayerBetAction::Check);
@@ -2546,6 +3008,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("river action should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::River(next) => {
@@ -2585,6 +3048,7 @@ mod tests {
                 }
                 player.committed_this_round = 0;
             }
+            state.refresh_pots();
         }
 
         let envelope = build_player_envelope::<RiverStreet>(&ctx, seat, PlayerBetAction::Fold);
@@ -2594,6 +3058,7 @@ mod tests {
             &ctx.hasher,
         )
         .expect("river fold should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Showdown(next) => {
@@ -2622,6 +3087,7 @@ mod tests {
         let result =
             GameShowdownMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
                 .expect("showdown reveal should succeed");
+        assert_invariants(&result);
 
         match result {
             AnyTableSnapshot::Showdown(next) => {
@@ -2632,6 +3098,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn omaha_showdown_reveals_every_hole_card() {
+        let mut ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        ctx.cfg = Arc::new(HandConfig {
+            hole_cards: OMAHA_HOLE_CARDS,
+            ..(*ctx.cfg).clone()
+        });
+        let snapshot = fixture_showdown_snapshot(&ctx);
+        let seat = active_seats(&ctx)[0];
+
+        let mut short = build_showdown_envelope(&ctx, &snapshot, seat);
+        short.message.value.card_in_deck_position.pop();
+        short.message.value.hole_ciphertexts.pop();
+        short.message.value.chaum_pedersen_proofs.pop();
+        let err =
+            GameShowdownMessage::<Curve>::apply_transition(snapshot.clone(), &short, &ctx.hasher)
+                .expect_err("a three-card reveal must be rejected for Omaha");
+        assert!(err.to_string().contains("must reveal 4 hole cards"));
+
+        let envelope = build_showdown_envelope(&ctx, &snapshot, seat);
+        let result =
+            GameShowdownMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
+                .expect("Omaha showdown reveal should succeed");
+
+        match result {
+            AnyTableSnapshot::Showdown(next) => {
+                let hand = &next.reveals.revealed_holes[&seat];
+                assert_eq!(hand.hole.len(), usize::from(OMAHA_HOLE_CARDS));
+                assert_eq!(hand.hole_ciphertexts.len(), usize::from(OMAHA_HOLE_CARDS));
+            }
+            other => panic!("expected showdown snapshot, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn showdown_rejects_reveal_out_of_order() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut snapshot = fixture_showdown_snapshot(&ctx);
+        let seats = active_seats(&ctx);
+        snapshot.betting.state.last_aggressor = Some(seats[1]);
+
+        let early = build_showdown_envelope(&ctx, &snapshot, seats[0]);
+        let err =
+            GameShowdownMessage::<Curve>::apply_transition(snapshot.clone(), &early, &ctx.hasher)
+                .expect_err("non-aggressor must not reveal first");
+        assert!(err.to_string().contains("out of turn"));
+
+        let aggressor = build_showdown_envelope(&ctx, &snapshot, seats[1]);
+        GameShowdownMessage::<Curve>::apply_transition(snapshot, &aggressor, &ctx.hasher)
+            .expect("last aggressor reveals first");
+    }
+
     #[test]
     fn showdown_reveals_all_players_completes_hand() {
         let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
@@ -2650,6 +3168,7 @@ mod tests {
                 &ctx.hasher,
             )
             .expect("showdown reveal should succeed");
+            assert_invariants(&result);
 
             if index + 1 == seats.len() {
                 match result {
@@ -2666,4 +3185,149 @@ mod tests {
 
         panic!("showdown did not complete after revealing all seats");
     }
+
+    fn build_muck_envelope(
+        ctx: &FixtureContext<Curve>,
+        snapshot: &TableAtShowdown<Curve>,
+        seat: SeatId,
+    ) -> EnvelopedMessage<Curve, MuckDeclaration<Curve>> {
+        let (actor, _, public_key) = player_actor_info(ctx, seat);
+        let value = MuckDeclaration {
+            game_id: snapshot.game_id,
+            hand_id: snapshot.hand_id.expect("hand id"),
+            seat,
+            player_key: actor.player_key.clone(),
+        };
+        EnvelopedMessage {
+            hand_id: ctx.hand_id,
+            game_id: ctx.game_id,
+            actor,
+            nonce: 0,
+            public_key,
+            message: WithSignature {
+                value,
+                signature: vec![1, 2, 3],
+            },
+        }
+    }
+
+    #[test]
+    fn muck_declaration_must_come_from_the_declaring_seat_in_turn() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let snapshot = fixture_showdown_snapshot(&ctx);
+        let seats = active_seats(&ctx);
+
+        let mut impersonated = build_muck_envelope(&ctx, &snapshot, seats[0]);
+        impersonated.actor = player_actor_info(&ctx, seats[1]).0;
+        let err = MuckDeclaration::<Curve>::apply_transition(
+            snapshot.clone(),
+            &impersonated,
+            &ctx.hasher,
+        )
+        .expect_err("muck for another seat must be rejected");
+        assert!(err.to_string().contains("not submitted by that seat"));
+
+        let out_of_turn = build_muck_envelope(&ctx, &snapshot, seats[1]);
+        let err = MuckDeclaration::<Curve>::apply_transition(snapshot, &out_of_turn, &ctx.hasher)
+            .expect_err("muck out of turn must be rejected");
+        assert!(err.to_string().contains("out of turn"));
+    }
+
+    #[test]
+    fn muck_declaration_is_recorded_and_resolves_showdown() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let snapshot = fixture_showdown_snapshot(&ctx);
+        let seats = active_seats(&ctx);
+        let previous_hash = snapshot.state_hash;
+
+        let envelope = build_muck_envelope(&ctx, &snapshot, seats[0]);
+        let next =
+            match MuckDeclaration::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
+                .expect("muck in turn should be accepted")
+            {
+                AnyTableSnapshot::Showdown(next) => next,
+                other => panic!("expected showdown snapshot, got {:?}", other),
+            };
+        let recorded = next.reveals.mucked.get(&seats[0]).expect("muck recorded");
+        assert_eq!(recorded.signature, envelope.message.signature);
+        assert_ne!(next.state_hash, previous_hash);
+
+        let envelope = build_showdown_envelope(&ctx, &next, seats[1]);
+        match GameShowdownMessage::<Curve>::apply_transition(next, &envelope, &ctx.hasher)
+            .expect("remaining seat reveals")
+        {
+            AnyTableSnapshot::Complete(complete) => {
+                assert_eq!(complete.reveals.mucked.len(), 1);
+                assert_eq!(complete.reveals.revealed_holes.len(), 1);
+            }
+            other => panic!("expected complete snapshot, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn auto_muck_spares_a_hand_still_live_for_a_side_pot() {
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let seats = active_seats(&ctx);
+        let snapshot = fixture_showdown_snapshot(&ctx);
+
+        let envelope = build_showdown_envelope(&ctx, &snapshot, seats[0]);
+        let mut next =
+            match GameShowdownMessage::<Curve>::apply_transition(snapshot, &envelope, &ctx.hasher)
+                .expect("first seat reveals")
+            {
+                AnyTableSnapshot::Showdown(next) => next,
+                other => panic!("expected showdown snapshot, got {:?}", other),
+            };
+        // The first hand beats anything, and the second seat opted into auto-mucking.
+        next.reveals
+            .revealed_holes
+            .get_mut(&seats[0])
+            .expect("first seat revealed")
+            .best_score = u32::MAX;
+        let player_key = next
+            .identity_at_seat(seats[1])
+            .expect("seat occupied")
+            .player_key
+            .clone();
+        Arc::make_mut(&mut next.players)
+            .get_mut(&player_key)
+            .expect("player identity")
+            .auto_muck_losers = true;
+        next.betting.state.pots = Pots {
+            main: Pot {
+                amount: 20,
+                eligible: vec![seats[0], seats[1]],
+            },
+            sides: vec![Pot {
+                amount: 10,
+                eligible: vec![seats[1]],
+            }],
+        };
+
+        // Beaten for the main pot and in no side pot: mucked without a declaration.
+        let mut beaten = next.clone();
+        beaten.betting.state.pots.sides.clear();
+        let envelope = build_showdown_envelope(&ctx, &beaten, seats[1]);
+        match GameShowdownMessage::<Curve>::apply_transition(beaten, &envelope, &ctx.hasher)
+            .expect("auto-muck resolves the seat")
+        {
+            AnyTableSnapshot::Complete(complete) => {
+                assert!(complete.reveals.auto_mucked.contains(&seats[1]));
+                assert!(!complete.reveals.revealed_holes.contains_key(&seats[1]));
+            }
+            other => panic!("expected complete snapshot, got {:?}", other),
+        }
+
+        // Still the only contender for the side pot: the hand is shown, not mucked.
+        let envelope = build_showdown_envelope(&ctx, &next, seats[1]);
+        match GameShowdownMessage::<Curve>::apply_transition(next, &envelope, &ctx.hasher)
+            .expect("side-pot contender reveals")
+        {
+            AnyTableSnapshot::Complete(complete) => {
+                assert!(complete.reveals.auto_mucked.is_empty());
+                assert!(complete.reveals.revealed_holes.contains_key(&seats[1]));
+            }
+            other => panic!("expected complete snapshot, got {:?}", other),
+        }
+    }
 }
@@ -1,7 +1,10 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 
 pub type GameId = i64;
 pub type HandId = i64;
+/// Primary key of a persisted ledger event; increases with insertion order across all hands.
+pub type EventId = i64;
 pub type ShufflerId = i64;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -36,6 +39,68 @@ pub enum EventPhase {
     Cancelled,
 }
 
+impl EventPhase {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            EventPhase::Pending => 0,
+            EventPhase::Shuffling => 1,
+            EventPhase::Dealing => 2,
+            EventPhase::Betting => 3,
+            EventPhase::Reveals => 4,
+            EventPhase::Showdown => 5,
+            EventPhase::Complete => 6,
+            EventPhase::Cancelled => 7,
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(EventPhase::Pending),
+            1 => Some(EventPhase::Shuffling),
+            2 => Some(EventPhase::Dealing),
+            3 => Some(EventPhase::Betting),
+            4 => Some(EventPhase::Reveals),
+            5 => Some(EventPhase::Showdown),
+            6 => Some(EventPhase::Complete),
+            7 => Some(EventPhase::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+impl CanonicalSerialize for EventPhase {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        _compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        writer.write_all(&[self.as_byte()])?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: ark_serialize::Compress) -> usize {
+        1
+    }
+}
+
+impl ark_serialize::Valid for EventPhase {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for EventPhase {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        _compress: ark_serialize::Compress,
+        _validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        EventPhase::from_byte(byte[0]).ok_or(ark_serialize::SerializationError::InvalidData)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NonceKey {
     pub hand_id: HandId,
@@ -46,7 +111,100 @@ pub struct NonceKey {
 pub type SignatureBytes = Vec<u8>;
 pub type PublicKeyBytes = Vec<u8>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Identifier tying an inbound request to the log lines and ledger events it produced.
+///
+/// Clients may supply one (see [`CorrelationId::parse`]); otherwise the server generates a
+/// UUID. Ids are capped at [`CorrelationId::MAX_LEN`] visible ASCII characters so they are
+/// safe to echo in headers and log fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    pub const MAX_LEN: usize = 128;
+
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Accept a client-supplied id, rejecting empty, oversized or non-printable values.
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= Self::MAX_LEN
+            && value.bytes().all(|b| b.is_ascii_graphic());
+        valid.then(|| Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Run `future` with `self` as the correlation id of the request being handled, so
+    /// envelopes submitted from within it are tagged with it (see [`CorrelationId::current`]).
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        CURRENT_CORRELATION_ID.scope(self, future).await
+    }
+
+    /// The id of the request the current task is handling, if it runs inside
+    /// [`CorrelationId::scope`].
+    pub fn current() -> Option<Self> {
+        CURRENT_CORRELATION_ID.try_with(Clone::clone).ok()
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_CORRELATION_ID: CorrelationId;
+}
+
+impl CanonicalSerialize for CorrelationId {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        self.0.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        self.0.serialized_size(compress)
+    }
+}
+
+impl ark_serialize::Valid for CorrelationId {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for CorrelationId {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let value = String::deserialize_with_mode(reader, compress, validate)?;
+        CorrelationId::parse(&value).ok_or(ark_serialize::SerializationError::InvalidData)
+    }
+}
+
+impl std::fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
 pub struct StateHash(#[serde(with = "state_hash_hex")] [u8; 32]);
 
 impl StateHash {
@@ -162,4 +320,16 @@ mod tests {
         };
         assert_round_trip_eq(&key);
     }
+
+    #[test]
+    fn correlation_ids_reject_unprintable_or_oversized_values() {
+        assert_eq!(
+            CorrelationId::parse("req-42").map(|id| id.to_string()),
+            Some("req-42".to_string())
+        );
+        assert!(CorrelationId::parse("").is_none());
+        assert!(CorrelationId::parse("has space").is_none());
+        assert!(CorrelationId::parse(&"a".repeat(CorrelationId::MAX_LEN + 1)).is_none());
+        assert!(CorrelationId::parse(CorrelationId::generate().as_str()).is_some());
+    }
 }
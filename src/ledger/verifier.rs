@@ -1,25 +1,32 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
+use ark_crypto_primitives::signature::schnorr::{
+    Parameters as SchnorrParameters, Schnorr, Signature as SchnorrSignature,
+};
+use ark_crypto_primitives::signature::SignatureScheme;
 use ark_ec::CurveGroup;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 use crate::engine::nl::actions::PlayerBetAction;
 use crate::engine::nl::legals::{legal_actions_for, LegalActions};
 use crate::engine::nl::types::{PlayerId, PlayerStatus, SeatId};
+use crate::game::action_clock::ActionDeadlines;
 use crate::ledger::actor::{AnyActor, PlayerActor, ShufflerActor};
 use crate::ledger::messages::{
     AnyGameMessage, AnyMessageEnvelope, GameBlindingDecryptionMessage,
-    GamePartialUnblindingShareMessage, GameShowdownMessage, GameShuffleMessage,
+    GamePartialUnblindingShareMessage, GameShowdownMessage, GameShuffleMessage, MuckDeclaration,
 };
 use crate::ledger::snapshot::{
     AnyTableSnapshot, CardDestination, PlayerIdentity, PlayerRoster, PlayerStacks, SeatingMap,
     ShufflerRoster, TableAtDealing, TableAtShowdown, TableAtShuffling,
 };
-use crate::ledger::state::LedgerState;
+use crate::ledger::state::{nonce_key, LedgerState};
 use crate::ledger::types::{EntityKind, GameId, HandId, NonceKey, ShufflerId};
 use crate::ledger::CanonicalKey;
-use crate::showdown::choose_best5_from7;
+use crate::showdown::choose_best5_for_hole;
+use crate::signing::SignatureBytes;
 
 pub trait Verifier<C>
 where
@@ -30,6 +37,10 @@ where
         hand_id: HandId,
         envelope: AnyMessageEnvelope<C>,
     ) -> Result<AnyMessageEnvelope<C>, VerifyError>;
+
+    /// Undo the nonce reservation taken when `envelope` was verified, because it was never
+    /// persisted. The actor may then resubmit with the same nonce.
+    fn release(&self, _envelope: &AnyMessageEnvelope<C>) {}
 }
 
 #[derive(Debug, Error)]
@@ -44,6 +55,9 @@ pub enum VerifyError {
     NonceConflict,
     #[error("invalid message")]
     InvalidMessage,
+    /// The message repeats one the ledger has already accepted (e.g. a client retry).
+    #[error("duplicate of an accepted message")]
+    Duplicate,
 }
 
 pub trait SignatureValidator<C: CurveGroup>: Send + Sync {
@@ -59,10 +73,49 @@ impl<C: CurveGroup> SignatureValidator<C> for TranscriptSignatureValidator {
     }
 }
 
+/// Verifies Schnorr signatures whose challenge is hashed with `D`.
+///
+/// The digest must match the one the signers use (see [`crate::shuffler::ShufflerScheme`]);
+/// a signature produced under a different hash does not verify.
+pub struct SchnorrSignatureValidator<C: CurveGroup, D: Digest = Sha256> {
+    params: SchnorrParameters<C, D>,
+}
+
+impl<C: CurveGroup, D: Digest> SchnorrSignatureValidator<C, D> {
+    pub fn new(params: SchnorrParameters<C, D>) -> Self {
+        Self { params }
+    }
+}
+
+impl<C, D> SignatureValidator<C> for SchnorrSignatureValidator<C, D>
+where
+    C: CurveGroup,
+    D: Digest + Send + Sync,
+    Schnorr<C, D>: SignatureScheme<
+        Parameters = SchnorrParameters<C, D>,
+        PublicKey = C::Affine,
+        Signature = SchnorrSignature<C>,
+    >,
+{
+    fn verify(&self, public_key: &C, transcript: &[u8], signature: &[u8]) -> bool {
+        let Ok(signature) = SchnorrSignature::<C>::from_bytes(signature) else {
+            return false;
+        };
+        Schnorr::<C, D>::verify(
+            &self.params,
+            &public_key.into_affine(),
+            transcript,
+            &signature,
+        )
+        .unwrap_or(false)
+    }
+}
+
 pub struct LedgerVerifier<C: CurveGroup> {
     state: Arc<LedgerState<C>>,
     signature: Arc<dyn SignatureValidator<C>>,
     nonces: RwLock<HashMap<NonceKey, u64>>,
+    action_deadlines: Option<ActionDeadlines>,
 }
 
 impl<C: CurveGroup> LedgerVerifier<C> {
@@ -78,6 +131,50 @@ impl<C: CurveGroup> LedgerVerifier<C> {
             state,
             signature,
             nonces: RwLock::new(HashMap::new()),
+            action_deadlines: None,
+        }
+    }
+
+    /// Accept a seat's default check or fold without its signature once that seat's turn has
+    /// timed out in `deadlines`.
+    pub fn with_action_deadlines(mut self, deadlines: ActionDeadlines) -> Self {
+        self.action_deadlines = Some(deadlines);
+        self
+    }
+
+    /// Whether `envelope` is the action the table takes for a seat whose turn has timed out.
+    fn is_timeout_action(
+        &self,
+        hand_id: HandId,
+        snapshot: &AnyTableSnapshot<C>,
+        envelope: &AnyMessageEnvelope<C>,
+    ) -> bool {
+        let (Some(deadlines), Some(state), AnyActor::Player { seat_id, .. }) = (
+            &self.action_deadlines,
+            snapshot.betting_state(),
+            &envelope.actor,
+        ) else {
+            return false;
+        };
+        let action = match &envelope.message.value {
+            AnyGameMessage::PlayerPreflop(msg) => &msg.action,
+            AnyGameMessage::PlayerFlop(msg) => &msg.action,
+            AnyGameMessage::PlayerTurn(msg) => &msg.action,
+            AnyGameMessage::PlayerRiver(msg) => &msg.action,
+            _ => return false,
+        };
+        deadlines
+            .timeout_action(hand_id, state)
+            .is_some_and(|(seat, timeout)| seat == *seat_id && timeout == *action)
+    }
+
+    /// Seed the nonce cache, e.g. from [`LedgerState::nonce_counters`] after importing a state
+    /// exported by another process. Counters that are already further ahead are kept.
+    pub fn restore_nonces(&self, counters: impl IntoIterator<Item = (NonceKey, u64)>) {
+        let mut guard = self.nonces.write().expect("nonce cache poisoned");
+        for (key, nonce) in counters {
+            let last = guard.entry(key).or_insert(nonce);
+            *last = (*last).max(nonce);
         }
     }
 }
@@ -109,16 +206,19 @@ impl<C: CurveGroup> Verifier<C> for LedgerVerifier<C> {
         }
 
         // Compute signing bytes on-demand
-        let signing_bytes = crate::signing::signing_bytes(&envelope.message.value)
+        let signing_bytes = envelope
+            .message
+            .value
+            .signed_bytes()
             .map_err(|_| VerifyError::InvalidMessage)?;
-        self.signature
-            .verify(
-                &envelope.public_key,
-                &signing_bytes,
-                &envelope.message.signature,
-            )
-            .then_some(())
-            .ok_or(VerifyError::BadSignature)?;
+        let signed = self.signature.verify(
+            &envelope.public_key,
+            &signing_bytes,
+            &envelope.message.signature,
+        );
+        if !signed && !self.is_timeout_action(hand_id, &snapshot, &envelope) {
+            return Err(VerifyError::BadSignature);
+        }
 
         let (players, shufflers, seating, stacks) = snapshot_common(&snapshot);
 
@@ -130,6 +230,10 @@ impl<C: CurveGroup> Verifier<C> for LedgerVerifier<C> {
             &envelope.actor,
         )?;
 
+        if is_accepted_shuffle(&snapshot, &actor_ctx, &envelope.message.value) {
+            return Err(VerifyError::Duplicate);
+        }
+
         let nonce_reservation = enforce_nonce(&self.nonces, hand_id, &actor_ctx, envelope.nonce)?;
 
         match (&snapshot, &envelope.message.value, &actor_ctx) {
@@ -148,6 +252,13 @@ impl<C: CurveGroup> Verifier<C> for LedgerVerifier<C> {
                 };
                 validate_shuffle(table, &actor, msg)?;
             }
+            (
+                AnyTableSnapshot::Shuffling(table),
+                AnyGameMessage::NonceCommitment(_),
+                ActorContext::Shuffler { shuffler_key, .. },
+            ) => {
+                validate_nonce_commitment(table, shuffler_key)?;
+            }
             (
                 AnyTableSnapshot::Dealing(table),
                 AnyGameMessage::Blinding(msg),
@@ -263,6 +374,23 @@ impl<C: CurveGroup> Verifier<C> for LedgerVerifier<C> {
                 };
                 validate_showdown(&table, seating, players, &actor, &msg)?;
             }
+            (
+                AnyTableSnapshot::Showdown(table),
+                AnyGameMessage::Muck(msg),
+                ActorContext::Player {
+                    seat,
+                    player_id,
+                    player_key,
+                    ..
+                },
+            ) => {
+                let actor = PlayerActor {
+                    seat_id: *seat,
+                    player_id: *player_id,
+                    player_key: player_key.clone(),
+                };
+                validate_muck(&table, seating, players, &actor, &msg)?;
+            }
             (AnyTableSnapshot::Complete(_), _, _) => return Err(VerifyError::PhaseMismatch),
             _ => return Err(VerifyError::PhaseMismatch),
         }
@@ -271,6 +399,25 @@ impl<C: CurveGroup> Verifier<C> for LedgerVerifier<C> {
 
         Ok(envelope)
     }
+
+    fn release(&self, envelope: &AnyMessageEnvelope<C>) {
+        let Some(key) = nonce_key(envelope.hand_id, &envelope.actor) else {
+            return;
+        };
+        let mut guard = self.nonces.write().expect("nonce cache poisoned");
+        // Only roll back if nothing newer was reserved on top of this nonce.
+        if guard.get(&key) != Some(&envelope.nonce) {
+            return;
+        }
+        match envelope.nonce.checked_sub(1) {
+            Some(previous) => {
+                guard.insert(key, previous);
+            }
+            None => {
+                guard.remove(&key);
+            }
+        }
+    }
 }
 
 fn snapshot_common<'a, C: CurveGroup>(
@@ -475,6 +622,27 @@ impl<'a> NonceReservation<'a> {
     }
 }
 
+/// Whether `message` is a shuffle this shuffler already had accepted at the same turn, compared
+/// by proof content id so a retried submission is recognised without comparing decks.
+fn is_accepted_shuffle<C: CurveGroup>(
+    snapshot: &AnyTableSnapshot<C>,
+    actor: &ActorContext<'_, C>,
+    message: &AnyGameMessage<C>,
+) -> bool {
+    let (AnyGameMessage::Shuffle(msg), ActorContext::Shuffler { shuffler_key, .. }) =
+        (message, actor)
+    else {
+        return false;
+    };
+    let shuffling = snapshot.shuffling();
+    let turn = usize::from(msg.turn_index);
+    shuffling.expected_order.get(turn) == Some(shuffler_key)
+        && shuffling
+            .steps
+            .get(turn)
+            .is_some_and(|step| step.proof.content_id() == msg.proof.content_id())
+}
+
 fn validate_shuffle<C: CurveGroup>(
     table: &TableAtShuffling<C>,
     actor: &ShufflerActor<C>,
@@ -501,6 +669,25 @@ fn validate_shuffle<C: CurveGroup>(
     Ok(())
 }
 
+fn validate_nonce_commitment<C: CurveGroup>(
+    table: &TableAtShuffling<C>,
+    shuffler_key: &CanonicalKey<C>,
+) -> Result<(), VerifyError> {
+    if !table.shufflers.contains_key(shuffler_key) {
+        return Err(VerifyError::Unauthorized);
+    }
+    if !table.shuffling.expected_order.contains(shuffler_key) {
+        return Err(VerifyError::InvalidMessage);
+    }
+    if !table.shuffling.steps.is_empty() {
+        return Err(VerifyError::InvalidMessage);
+    }
+    if table.shuffling.nonce_commitment(shuffler_key).is_some() {
+        return Err(VerifyError::InvalidMessage);
+    }
+    Ok(())
+}
+
 fn validate_blinding<C: CurveGroup>(
     table: &TableAtDealing<C>,
     seating: &SeatingMap<C>,
@@ -659,7 +846,14 @@ fn validate_showdown<C: CurveGroup>(
     if player_identity.player_id != actor.player_id {
         return Err(VerifyError::Unauthorized);
     }
-    let mut seen_cards = [0u8; 2];
+    let hole_cards = usize::from(table.cfg.hole_cards);
+    if message.card_in_deck_position.len() != hole_cards
+        || message.hole_ciphertexts.len() != hole_cards
+        || message.chaum_pedersen_proofs.len() != hole_cards
+    {
+        return Err(VerifyError::InvalidMessage);
+    }
+    let mut seen_cards = vec![0u8; hole_cards];
     for (idx, (&deck_pos, provided_cipher)) in message
         .card_in_deck_position
         .iter()
@@ -700,16 +894,46 @@ fn validate_showdown<C: CurveGroup>(
     }
 
     // Additional sanity: ensure we can derive best 5 (board + hole)
-    let mut seven_cards = [0u8; 7];
-    let board = &table.reveals.board;
-    if board.len() != 5 {
+    let board: [u8; 5] = table
+        .reveals
+        .board
+        .as_slice()
+        .try_into()
+        .map_err(|_| VerifyError::InvalidMessage)?;
+    choose_best5_for_hole(&seen_cards, board).ok_or(VerifyError::InvalidMessage)?;
+
+    Ok(())
+}
+
+fn validate_muck<C: CurveGroup>(
+    table: &TableAtShowdown<C>,
+    seating: &SeatingMap<C>,
+    players: &PlayerRoster<C>,
+    actor: &PlayerActor<C>,
+    message: &MuckDeclaration<C>,
+) -> Result<(), VerifyError> {
+    if message.seat != actor.seat_id || message.player_key != actor.player_key {
+        return Err(VerifyError::Unauthorized);
+    }
+    if message.game_id != table.game_id || Some(message.hand_id) != table.hand_id {
         return Err(VerifyError::InvalidMessage);
     }
-    seven_cards[..5].copy_from_slice(&board[..5]);
-    seven_cards[5] = seen_cards[0];
-    seven_cards[6] = seen_cards[1];
-    let _ = choose_best5_from7(seven_cards);
-
+    if table.reveals.has_resolved(actor.seat_id) {
+        return Err(VerifyError::InvalidMessage);
+    }
+    let player_key = seating
+        .get(&actor.seat_id)
+        .and_then(|value| value.clone())
+        .ok_or(VerifyError::InvalidMessage)?;
+    if player_key != actor.player_key {
+        return Err(VerifyError::Unauthorized);
+    }
+    let player_identity = players
+        .get(&player_key)
+        .ok_or(VerifyError::InvalidMessage)?;
+    if player_identity.player_id != actor.player_id {
+        return Err(VerifyError::Unauthorized);
+    }
     Ok(())
 }
 
@@ -717,14 +941,15 @@ fn validate_showdown<C: CurveGroup>(
 mod tests {
     use super::*;
     use crate::chaum_pedersen::ChaumPedersenProof;
+    use crate::clock::MockClock;
     use crate::engine::nl::state::BettingState;
     use crate::engine::nl::types::{ActionLog, HandConfig, PlayerState, PlayerStatus, TableStakes};
     use crate::ledger::hash::LedgerHasher;
     use crate::ledger::messages::{GamePlayerMessage, PreflopStreet};
     use crate::ledger::snapshot::{
-        build_default_card_plan, BettingSnapshot, CardPlan, DealingSnapshot, DealtCard,
-        RevealsSnapshot, ShufflerIdentity, ShufflingSnapshot, ShufflingStep, SnapshotStatus,
-        TableSnapshot,
+        build_default_card_plan, BettingSnapshot, CardPlan, DealingSnapshot, DealtCard, HandPhase,
+        PhaseBetting, PhaseShowdown, RevealsSnapshot, ShufflerIdentity, ShufflingSnapshot,
+        ShufflingStep, SnapshotStatus, TableSnapshot,
     };
     use crate::ledger::types::{GameId, StateHash};
     use crate::shuffling::data_structures::{ElGamalCiphertext, ShuffleProof, DECK_SIZE};
@@ -736,6 +961,7 @@ mod tests {
     use ark_ff::Zero;
     use ark_std::UniformRand;
     use std::collections::BTreeMap;
+    use std::time::Duration;
 
     const GAME_ID: GameId = 1;
     const HAND_ID: HandId = 99;
@@ -754,6 +980,30 @@ mod tests {
         assert!(matches!(result, Err(VerifyError::BadSignature)));
     }
 
+    #[test]
+    fn unsigned_default_action_is_accepted_once_the_seat_times_out() {
+        let harness = TestHarness::preflop();
+        let clock = MockClock::new();
+        let deadlines = ActionDeadlines::new(Arc::new(clock.clone()), Duration::from_secs(30));
+        let verifier = harness.verifier().with_action_deadlines(deadlines.clone());
+        let (_, tip) = harness.state.tip_snapshot(HAND_ID).expect("tip snapshot");
+        deadlines.observe(HAND_ID, tip.betting_state().expect("betting snapshot"));
+
+        let mut check = harness.player_envelope();
+        check.message.signature.clear();
+        let result = verifier.verify(HAND_ID, check.clone());
+        assert!(matches!(result, Err(VerifyError::BadSignature)));
+
+        clock.advance(Duration::from_secs(30));
+        let mut fold = check.clone();
+        if let AnyGameMessage::PlayerPreflop(ref mut msg) = fold.message.value {
+            msg.action = PlayerBetAction::Fold;
+        }
+        let result = verifier.verify(HAND_ID, fold);
+        assert!(matches!(result, Err(VerifyError::BadSignature)));
+        assert!(verifier.verify(HAND_ID, check).is_ok());
+    }
+
     #[test]
     fn rejects_unauthorized_actors() {
         let mut harness = TestHarness::base(TestPhase::Preflop);
@@ -801,7 +1051,10 @@ mod tests {
             msg.card_in_deck_position = 200; // invalid
         }
         // Recompute signature after modifying message
-        let signing_bytes = crate::signing::signing_bytes(&envelope.message.value)
+        let signing_bytes = envelope
+            .message
+            .value
+            .signed_bytes()
             .expect("signing bytes should compute");
         envelope.message.signature = signing_bytes;
 
@@ -846,6 +1099,19 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn released_nonce_can_be_resubmitted() {
+        let harness = TestHarness::preflop();
+        let envelope = harness.player_envelope();
+        let verifier = harness.verifier();
+        verifier.verify(HAND_ID, envelope.clone()).unwrap();
+
+        // The store failed to persist the event, so the reservation is rolled back.
+        verifier.release(&envelope);
+
+        assert!(verifier.verify(HAND_ID, envelope).is_ok());
+    }
+
     #[test]
     fn accepts_valid_envelopes() {
         let harness = TestHarness::preflop();
@@ -855,6 +1121,29 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn muck_is_accepted_only_at_showdown_for_the_acting_seat() {
+        let harness = TestHarness::showdown();
+        let verifier = harness.verifier();
+
+        let mut unsigned = harness.muck_envelope(PLAYER_SEAT);
+        unsigned.message.signature.clear();
+        let result = verifier.verify(HAND_ID, unsigned);
+        assert!(matches!(result, Err(VerifyError::BadSignature)));
+
+        let result = verifier.verify(HAND_ID, harness.muck_envelope(PLAYER_SEAT + 1));
+        assert!(matches!(result, Err(VerifyError::Unauthorized)));
+
+        let result = verifier.verify(HAND_ID, harness.muck_envelope(PLAYER_SEAT));
+        assert!(result.is_ok());
+
+        let preflop = TestHarness::preflop();
+        let result = preflop
+            .verifier()
+            .verify(HAND_ID, preflop.muck_envelope(PLAYER_SEAT));
+        assert!(matches!(result, Err(VerifyError::PhaseMismatch)));
+    }
+
     #[test]
     fn rejects_out_of_turn_betting() {
         let harness = TestHarness::preflop_with_to_act(2);
@@ -932,6 +1221,190 @@ mod tests {
         assert!(matches!(result, Err(VerifyError::InvalidMessage)));
     }
 
+    #[test]
+    fn resubmitted_shuffle_is_reported_as_duplicate() {
+        let harness = TestHarness::shuffling();
+        let (_, tip) = harness.state.tip_snapshot(HAND_ID).expect("tip");
+        let AnyTableSnapshot::Shuffling(mut snapshot) = tip else {
+            panic!("expected shuffling snapshot");
+        };
+        snapshot.shuffling.steps.push(ShufflingStep {
+            shuffler_public_key: Curve::zero(),
+            proof: sample_shuffle_proof(),
+            beacon_round: None,
+            vrf_nonce: None,
+        });
+        snapshot.sequence += 1;
+        snapshot.initialize_hash(harness.hasher.as_ref());
+        harness
+            .state
+            .upsert_snapshot(HAND_ID, AnyTableSnapshot::Shuffling(snapshot), true);
+
+        let verifier = harness.verifier();
+        let envelope = harness.shuffle_envelope(SHUFFLER_ID);
+        let result = verifier.verify(HAND_ID, envelope);
+        assert!(matches!(result, Err(VerifyError::Duplicate)));
+    }
+
+    #[test]
+    fn schnorr_validator_requires_matching_hash() {
+        use crate::shuffler::ShufflerScheme;
+        use crate::signing::WithSignature;
+        use ark_grumpkin::Projective as Grumpkin;
+        use rand::{rngs::StdRng, SeedableRng};
+        use sha3::Keccak256;
+
+        type KeccakScheme = ShufflerScheme<Grumpkin, Keccak256>;
+
+        // Identical seeds give identical generator and salt, so only the hash differs.
+        let keccak_params =
+            KeccakScheme::setup(&mut StdRng::seed_from_u64(5)).expect("keccak params");
+        let sha_params = ShufflerScheme::<Grumpkin>::setup(&mut StdRng::seed_from_u64(5))
+            .expect("sha256 params");
+        let mut rng = StdRng::seed_from_u64(6);
+        let (public_key, secret_key) =
+            KeccakScheme::keygen(&keccak_params, &mut rng).expect("keygen");
+
+        let zero = ElGamalCiphertext::new(Grumpkin::zero(), Grumpkin::zero());
+        let proof = ShuffleProof::new(
+            vec![zero.clone(); DECK_SIZE],
+            vec![(zero.clone(), ark_grumpkin::Fq::zero()); DECK_SIZE],
+            vec![ark_grumpkin::Fr::zero(); DECK_SIZE],
+        )
+        .expect("proof lengths");
+        let message = GameShuffleMessage::<Grumpkin>::new(
+            std::array::from_fn(|_| zero.clone()),
+            std::array::from_fn(|_| zero.clone()),
+            proof,
+            0,
+        );
+        let signed = WithSignature::<<KeccakScheme as SignatureScheme>::Signature, _>::new::<
+            KeccakScheme,
+            StdRng,
+        >(message, &keccak_params, &secret_key, &mut rng)
+        .expect("sign shuffle message");
+        let transcript = crate::signing::signing_bytes(&signed.value).expect("signing bytes");
+        let signature = signed.signature.to_bytes();
+        let public_key = Grumpkin::from(public_key);
+
+        let keccak = SchnorrSignatureValidator::<Grumpkin, Keccak256>::new(keccak_params);
+        assert!(keccak.verify(&public_key, &transcript, &signature));
+
+        let sha = SchnorrSignatureValidator::<Grumpkin>::new(sha_params);
+        assert!(!sha.verify(&public_key, &transcript, &signature));
+    }
+
+    #[test]
+    fn schnorr_validator_accepts_shuffler_keys_under_published_params() {
+        use crate::shuffler::{shuffler_signing_params, ShufflerScheme};
+        use crate::signing::WithSignature;
+        use ark_crypto_primitives::signature::schnorr::SecretKey as SchnorrSecretKey;
+        use ark_grumpkin::Projective as Grumpkin;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        type Scheme = ShufflerScheme<Grumpkin>;
+
+        // Shuffler keys come from the curve generator, not from `Scheme::keygen`.
+        let mut rng = StdRng::seed_from_u64(11);
+        let secret = ark_grumpkin::Fr::rand(&mut rng);
+        let public_key = Grumpkin::generator() * secret;
+        let params = shuffler_signing_params::<Grumpkin>().expect("schnorr params");
+
+        let zero = ElGamalCiphertext::new(Grumpkin::zero(), Grumpkin::zero());
+        let proof = ShuffleProof::new(
+            vec![zero.clone(); DECK_SIZE],
+            vec![(zero.clone(), ark_grumpkin::Fq::zero()); DECK_SIZE],
+            vec![ark_grumpkin::Fr::zero(); DECK_SIZE],
+        )
+        .expect("proof lengths");
+        let message = GameShuffleMessage::<Grumpkin>::new(
+            std::array::from_fn(|_| zero.clone()),
+            std::array::from_fn(|_| zero.clone()),
+            proof,
+            0,
+        );
+        let signed = WithSignature::<<Scheme as SignatureScheme>::Signature, _>::new::<
+            Scheme,
+            StdRng,
+        >(message, &params, &SchnorrSecretKey(secret), &mut rng)
+        .expect("sign shuffle message");
+        let transcript = crate::signing::signing_bytes(&signed.value).expect("signing bytes");
+        let signature = signed.signature.to_bytes();
+
+        // Anyone rebuilding the published params gets a validator that accepts the signature.
+        let validator = SchnorrSignatureValidator::<Grumpkin>::new(
+            shuffler_signing_params::<Grumpkin>().expect("schnorr params"),
+        );
+        assert!(validator.verify(&public_key, &transcript, &signature));
+
+        // Randomly sampled params use another generator and salt, so they reject it.
+        let random = SchnorrSignatureValidator::<Grumpkin>::new(
+            Scheme::setup(&mut StdRng::seed_from_u64(12)).expect("random params"),
+        );
+        assert!(!random.verify(&public_key, &transcript, &signature));
+    }
+
+    #[test]
+    fn schnorr_validator_accepts_envelopes_from_sign_enveloped_action() {
+        use crate::ledger::messages::{sign_enveloped_action, MetadataEnvelope};
+        use crate::shuffler::ShufflerScheme;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        type Scheme = ShufflerScheme<Curve>;
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let params = Scheme::setup(&mut rng).expect("schnorr params");
+        let (public_key, secret_key) = Scheme::keygen(&params, &mut rng).expect("keygen");
+        let public_key = Curve::from(public_key);
+
+        let mut harness = TestHarness::base(TestPhase::Preflop);
+        harness.set_player_public_key(PLAYER_ID, public_key);
+        harness.push_snapshot();
+        let player_key = harness.player_key(PLAYER_ID).clone();
+
+        let meta = MetadataEnvelope {
+            hand_id: HAND_ID,
+            game_id: GAME_ID,
+            actor: PlayerActor {
+                seat_id: PLAYER_SEAT,
+                player_id: PLAYER_ID,
+                player_key: player_key.clone(),
+            },
+            nonce: 1,
+            public_key,
+        };
+        let signed = sign_enveloped_action::<Scheme, Curve, _, _>(
+            meta,
+            GamePlayerMessage::<PreflopStreet, Curve>::new(PlayerBetAction::Check),
+            &params,
+            &secret_key,
+            &mut rng,
+        )
+        .expect("sign check");
+        let envelope = AnyMessageEnvelope {
+            hand_id: HAND_ID,
+            game_id: GAME_ID,
+            actor: AnyActor::Player {
+                seat_id: PLAYER_SEAT,
+                player_id: PLAYER_ID,
+                player_key,
+            },
+            nonce: 1,
+            public_key,
+            message: crate::signing::WithSignature {
+                value: AnyGameMessage::PlayerPreflop(signed.message.value),
+                signature: signed.message.signature,
+            },
+            correlation_id: None,
+        };
+
+        let verifier = LedgerVerifier::with_signature_validator(
+            Arc::clone(&harness.state),
+            Arc::new(SchnorrSignatureValidator::<Curve>::new(params)),
+        );
+        assert!(verifier.verify(HAND_ID, envelope).is_ok());
+    }
+
     #[test]
     fn rejects_shuffle_with_wrong_turn_index() {
         let mut harness = TestHarness::base(TestPhase::Shuffling);
@@ -984,6 +1457,7 @@ mod tests {
         Shuffling,
         Dealing,
         Preflop,
+        Showdown,
     }
 
     impl TestHarness {
@@ -1005,6 +1479,12 @@ mod tests {
             harness
         }
 
+        fn showdown() -> Self {
+            let mut harness = Self::base(TestPhase::Showdown);
+            harness.push_snapshot();
+            harness
+        }
+
         fn preflop_with_to_act(to_act: SeatId) -> Self {
             let mut harness = Self::base(TestPhase::Preflop);
             harness.override_to_act = Some(to_act);
@@ -1026,6 +1506,7 @@ mod tests {
                     player_id: PLAYER_ID,
                     nonce: 0,
                     seat: PLAYER_SEAT,
+                    auto_muck_losers: false,
                 },
             );
             let mut player_keys = BTreeMap::new();
@@ -1099,6 +1580,24 @@ mod tests {
             );
         }
 
+        fn set_player_public_key(&mut self, id: PlayerId, public_key: Curve) {
+            let old_key = self.player_keys.insert(id, CanonicalKey::new(public_key));
+            let old_key = old_key.expect("player key");
+            let key = self.player_key(id).clone();
+            let mut identity = self.players.remove(&old_key).expect("player identity");
+            identity.public_key = public_key;
+            identity.player_key = key.clone();
+            for entry in self.seating.values_mut() {
+                if entry.as_ref() == Some(&old_key) {
+                    *entry = Some(key.clone());
+                }
+            }
+            if let Some(stack) = self.stacks.get_mut(&identity.seat) {
+                stack.player_key = Some(key.clone());
+            }
+            self.players.insert(key, identity);
+        }
+
         fn player_identity(&self, id: PlayerId) -> &PlayerIdentity<Curve> {
             let key = self.player_key(id);
             self.players.get(key).expect("player identity")
@@ -1181,6 +1680,7 @@ mod tests {
                 TestPhase::Shuffling => self.push_shuffling(),
                 TestPhase::Dealing => self.push_dealing(),
                 TestPhase::Preflop => self.push_preflop(),
+                TestPhase::Showdown => self.push_showdown(),
             }
         }
 
@@ -1213,6 +1713,7 @@ mod tests {
                     steps: Vec::new(),
                     final_deck: sample_deck(),
                     expected_order: self.expected_shuffler_order(),
+                    nonce_commitments: Vec::new(),
                 },
                 dealing: (),
                 betting: (),
@@ -1278,9 +1779,12 @@ mod tests {
                     steps: vec![ShufflingStep {
                         shuffler_public_key: Curve::zero(),
                         proof: sample_shuffle_proof(),
+                        beacon_round: None,
+                        vrf_nonce: None,
                     }],
                     final_deck: sample_deck(),
                     expected_order: self.expected_shuffler_order(),
+                    nonce_commitments: Vec::new(),
                 },
                 dealing,
                 betting: (),
@@ -1292,6 +1796,29 @@ mod tests {
         }
 
         fn push_preflop(&mut self) {
+            let mut snapshot = self.dealt_table::<PhaseBetting<PreflopStreet>>();
+            snapshot.initialize_hash(self.hasher.as_ref());
+            self.state
+                .upsert_snapshot(HAND_ID, AnyTableSnapshot::Preflop(snapshot), true);
+        }
+
+        fn push_showdown(&mut self) {
+            let mut snapshot = self.dealt_table::<PhaseShowdown>();
+            snapshot.initialize_hash(self.hasher.as_ref());
+            self.state
+                .upsert_snapshot(HAND_ID, AnyTableSnapshot::Showdown(snapshot), true);
+        }
+
+        fn dealt_table<P>(&self) -> TableSnapshot<P, Curve>
+        where
+            P: HandPhase<
+                Curve,
+                ShufflingS = ShufflingSnapshot<Curve>,
+                DealingS = DealingSnapshot<Curve>,
+                BettingS = BettingSnapshot<Curve>,
+                RevealsS = RevealsSnapshot<Curve>,
+            >,
+        {
             let mut betting_state = default_betting_state();
             if let Some(to_act) = self.override_to_act {
                 betting_state.to_act = to_act;
@@ -1299,8 +1826,9 @@ mod tests {
             let betting = BettingSnapshot {
                 state: betting_state,
                 last_events: Vec::new(),
+                betting_events: Vec::new(),
             };
-            let mut snapshot = TableSnapshot {
+            TableSnapshot {
                 game_id: GAME_ID,
                 hand_id: Some(HAND_ID),
                 sequence: 0,
@@ -1317,9 +1845,12 @@ mod tests {
                     steps: vec![ShufflingStep {
                         shuffler_public_key: Curve::zero(),
                         proof: sample_shuffle_proof(),
+                        beacon_round: None,
+                        vrf_nonce: None,
                     }],
                     final_deck: sample_deck(),
                     expected_order: self.expected_shuffler_order(),
+                    nonce_commitments: Vec::new(),
                 },
                 dealing: DealingSnapshot {
                     assignments: BTreeMap::new(),
@@ -1335,11 +1866,31 @@ mod tests {
                 reveals: RevealsSnapshot {
                     board: Vec::from([1, 2, 3, 4, 5]),
                     revealed_holes: Default::default(),
+                    mucked: Default::default(),
+                    auto_mucked: Default::default(),
                 },
-            };
-            snapshot.initialize_hash(self.hasher.as_ref());
-            self.state
-                .upsert_snapshot(HAND_ID, AnyTableSnapshot::Preflop(snapshot), true);
+            }
+        }
+
+        fn muck_envelope(&self, seat: SeatId) -> AnyMessageEnvelope<Curve> {
+            let player_identity = self.player_identity(PLAYER_ID);
+            let message = AnyGameMessage::Muck(MuckDeclaration {
+                game_id: GAME_ID,
+                hand_id: HAND_ID,
+                seat,
+                player_key: player_identity.player_key.clone(),
+            });
+            build_envelope(
+                HAND_ID,
+                message,
+                AnyActor::Player {
+                    seat_id: PLAYER_SEAT,
+                    player_id: PLAYER_ID,
+                    player_key: player_identity.player_key.clone(),
+                },
+                Curve::zero(),
+                1,
+            )
         }
 
         fn shuffle_envelope(&self, shuffler_id: ShufflerId) -> AnyMessageEnvelope<Curve> {
@@ -1402,6 +1953,9 @@ mod tests {
             small_blind_seat: 0,
             big_blind_seat: 1,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         }
     }
 
@@ -1449,7 +2003,8 @@ mod tests {
         C: ark_serialize::CanonicalSerialize,
     {
         // Compute signing bytes for the test signature validator
-        let signing_bytes = crate::signing::signing_bytes(&message)
+        let signing_bytes = message
+            .signed_bytes()
             .expect("signing bytes computation should not fail");
 
         AnyMessageEnvelope {
@@ -1463,6 +2018,7 @@ mod tests {
                 // TranscriptSignatureValidator expects signature == signing_bytes
                 signature: signing_bytes,
             },
+            correlation_id: None,
         }
     }
 
@@ -8,14 +8,16 @@ use thiserror::Error;
 use super::messages::{AnyMessageEnvelope, FinalizedAnyMessageEnvelope};
 use super::state::LedgerState;
 use super::store::{EventStore, SnapshotStore};
+use super::verifier::Verifier;
 use crate::curve_absorb::CurveAbsorb;
 use crate::ledger::snapshot::{
     clone_snapshot_for_failure, AnyTableSnapshot, Shared, SnapshotStatus,
 };
-use crate::ledger::store::snapshot::prepare_snapshot;
+use crate::ledger::store::snapshot::{prepare_snapshot, PreparedSnapshot};
 use sea_orm::TransactionTrait;
 use tokio::sync::{broadcast, mpsc};
-use tracing::{error, info, instrument, warn};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument, warn, Span};
 
 const LOG_TARGET: &str = "legit_poker::ledger::worker";
 
@@ -42,6 +44,8 @@ where
     events_tx: broadcast::Sender<FinalizedAnyMessageEnvelope<C>>,
     snapshots_tx: broadcast::Sender<Shared<AnyTableSnapshot<C>>>,
     stage_tx: broadcast::Sender<StagingLedgerUpdate<C>>,
+    verifier: Option<Arc<dyn Verifier<C> + Send + Sync>>,
+    stop: CancellationToken,
     _marker: std::marker::PhantomData<C>,
 }
 
@@ -69,13 +73,45 @@ where
             events_tx,
             snapshots_tx,
             stage_tx,
+            verifier: None,
+            stop: CancellationToken::new(),
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// Stop accepting submissions once `stop` is cancelled, then exit after the events already
+    /// queued have been handled. Without it the worker runs until every sender is dropped.
+    pub fn with_stop(mut self, stop: CancellationToken) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Release nonce reservations through `verifier` when an event fails to persist, so the
+    /// actor can retry with the same nonce.
+    pub fn with_verifier(mut self, verifier: Arc<dyn Verifier<C> + Send + Sync>) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
     #[instrument(skip(self), level = "info", target = LOG_TARGET)]
     pub async fn run(mut self) -> Result<(), WorkerError> {
-        while let Some(event) = self.receiver.recv().await {
+        let stop = self.stop.clone();
+        let mut closing = false;
+        loop {
+            let next = tokio::select! {
+                event = self.receiver.recv() => Some(event),
+                _ = stop.cancelled(), if !closing => None,
+            };
+            let event = match next {
+                Some(Some(event)) => event,
+                Some(None) => break,
+                None => {
+                    info!(target: LOG_TARGET, "stop requested; draining queued events");
+                    closing = true;
+                    self.receiver.close();
+                    continue;
+                }
+            };
             let hand_id = event.hand_id;
             let nonce = event.nonce;
             info!(
@@ -115,11 +151,18 @@ where
     #[instrument(
         skip(self, event),
         target = LOG_TARGET,
-        fields(hand_id = %event.hand_id, nonce = %event.nonce)
+        fields(
+            hand_id = %event.hand_id,
+            nonce = %event.nonce,
+            correlation_id = tracing::field::Empty
+        )
     )]
     pub async fn handle_event(&self, event: AnyMessageEnvelope<C>) -> Result<(), WorkerError> {
         let hand_id = event.hand_id;
         let nonce = event.nonce;
+        if let Some(correlation_id) = &event.correlation_id {
+            Span::current().record("correlation_id", correlation_id.as_str());
+        }
 
         let tip_before = match self.state.tip_snapshot(hand_id) {
             Some((_, snapshot)) => snapshot,
@@ -193,10 +236,88 @@ where
                     nonce,
                     "failed to prepare snapshot"
                 );
+                self.release_nonce(&event);
                 return Err(WorkerError::Database);
             }
         };
 
+        // Persist event and snapshot together; the in-memory state is only touched once the
+        // transaction has committed, so a persistence failure leaves `LedgerState` as it was.
+        if let Err(err) = self.persist(&finalized_event, &prepared).await {
+            self.release_nonce(&event);
+            return Err(err);
+        }
+
+        self.state.upsert_snapshot(hand_id, snapshot.clone(), true);
+        self.state
+            .insert_message_snapshot(hand_id, finalized_event.clone(), snapshot.clone());
+        crate::metrics_counter_inc!(EVENTS_APPLIED);
+
+        if let Err(err) = self.events_tx.send(finalized_event.clone()) {
+            warn!(
+                target: LOG_TARGET,
+                error = %err,
+                hand_id,
+                nonce,
+                "failed to broadcast finalized event"
+            );
+        }
+
+        if let Err(err) = self.snapshots_tx.send(Arc::clone(&staged_snapshot)) {
+            warn!(
+                target: LOG_TARGET,
+                error = %err,
+                hand_id,
+                nonce,
+                "failed to broadcast snapshot"
+            );
+        }
+
+        match apply_error {
+            None => {
+                info!(
+                    target: LOG_TARGET,
+                    hand_id,
+                    nonce,
+                    "state applied successfully"
+                );
+                info!(
+                    target: LOG_TARGET,
+                    hand_id,
+                    nonce,
+                    "persisted snapshot"
+                );
+                Ok(())
+            }
+            Some(reason) => {
+                warn!(
+                    target: LOG_TARGET,
+                    hand_id,
+                    nonce,
+                    failure_reason = reason,
+                    "recorded failure snapshot"
+                );
+                Err(WorkerError::Apply)
+            }
+        }
+    }
+
+    fn release_nonce(&self, event: &AnyMessageEnvelope<C>) {
+        if let Some(verifier) = &self.verifier {
+            verifier.release(event);
+        }
+    }
+
+    /// Write `finalized_event` and its snapshot in a single database transaction, rolling back
+    /// if either insert fails.
+    async fn persist(
+        &self,
+        finalized_event: &FinalizedAnyMessageEnvelope<C>,
+        prepared: &PreparedSnapshot,
+    ) -> Result<(), WorkerError> {
+        let hand_id = finalized_event.envelope.hand_id;
+        let nonce = finalized_event.envelope.nonce;
+
         let txn = match self.event_store.connection().begin().await {
             Ok(txn) => txn,
             Err(err) => {
@@ -213,7 +334,7 @@ where
 
         if let Err(err) = self
             .event_store
-            .persist_event_in_txn(&txn, &finalized_event)
+            .persist_event_in_txn(&txn, finalized_event)
             .await
         {
             error!(
@@ -236,7 +357,7 @@ where
 
         if let Err(err) = self
             .snapshot_store
-            .persist_snapshot_in_txn(&txn, &prepared)
+            .persist_snapshot_in_txn(&txn, prepared)
             .await
         {
             error!(
@@ -261,57 +382,7 @@ where
             return Err(WorkerError::Database);
         }
 
-        self.state.upsert_snapshot(hand_id, snapshot.clone(), true);
-        self.state
-            .insert_message_snapshot(hand_id, finalized_event.clone(), snapshot.clone());
-
-        if let Err(err) = self.events_tx.send(finalized_event.clone()) {
-            warn!(
-                target: LOG_TARGET,
-                error = %err,
-                hand_id,
-                nonce,
-                "failed to broadcast finalized event"
-            );
-        }
-
-        if let Err(err) = self.snapshots_tx.send(Arc::clone(&staged_snapshot)) {
-            warn!(
-                target: LOG_TARGET,
-                error = %err,
-                hand_id,
-                nonce,
-                "failed to broadcast snapshot"
-            );
-        }
-
-        match apply_error {
-            None => {
-                info!(
-                    target: LOG_TARGET,
-                    hand_id,
-                    nonce,
-                    "state applied successfully"
-                );
-                info!(
-                    target: LOG_TARGET,
-                    hand_id,
-                    nonce,
-                    "persisted snapshot"
-                );
-                Ok(())
-            }
-            Some(reason) => {
-                warn!(
-                    target: LOG_TARGET,
-                    hand_id,
-                    nonce,
-                    failure_reason = reason,
-                    "recorded failure snapshot"
-                );
-                Err(WorkerError::Apply)
-            }
-        }
+        Ok(())
     }
 }
 
@@ -326,15 +397,17 @@ pub enum WorkerError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::entity::table_snapshots;
     use crate::db::{connect_to_postgres_db, postgres_test_url};
     use crate::ledger::actor::AnyActor;
-    use crate::ledger::types::HandId;
+    use crate::ledger::types::{CorrelationId, HandId};
     use crate::shuffling::data_structures::{ElGamalCiphertext, ShuffleProof, DECK_SIZE};
     use anyhow::{Context, Result};
     use ark_bn254::{Fq, Fr, G1Projective as Curve};
     use ark_ff::Zero;
     use sea_orm::{
-        ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, Statement, Value,
+        ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend,
+        EntityTrait, QueryFilter, QueryOrder, Statement, Value,
     };
     use std::sync::Arc;
     use tokio::sync::{broadcast, mpsc};
@@ -346,14 +419,18 @@ mod tests {
     use crate::ledger::hash::LedgerHasher;
     use crate::ledger::messages::{AnyGameMessage, GameShuffleMessage};
     use crate::ledger::snapshot::{
-        AnyTableSnapshot, PhaseShuffling, ShufflerIdentity, ShufflerRoster, ShufflingSnapshot,
-        SnapshotStatus, TableSnapshot,
+        AnyTableSnapshot, NonceCommitment, PhaseShuffling, ShufflerIdentity, ShufflerRoster,
+        ShufflingSnapshot, SnapshotSeq, SnapshotStatus, TableSnapshot,
     };
     use crate::ledger::store::snapshot::PreparedSnapshot;
-    use crate::ledger::store::SeaOrmEventStore;
+    use crate::ledger::store::{SeaOrmEventStore, SeaOrmSnapshotStore, SnapshotError};
+    use crate::ledger::test_support::{fixture_complete_snapshot, FixtureContext};
     use crate::ledger::types::StateHash;
+    use crate::ledger::verifier::LedgerVerifier;
     use crate::ledger::worker::WorkerError;
+    use crate::ledger::LedgerOperator;
     use crate::signing::WithSignature;
+    use crate::vrf::commit_nonce;
     use async_trait::async_trait;
 
     #[derive(Default)]
@@ -391,6 +468,70 @@ mod tests {
         ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
             Ok(None)
         }
+
+        async fn snapshot_at(
+            &self,
+            _hand_id: HandId,
+            _sequence: SnapshotSeq,
+            _hasher: &Arc<dyn LedgerHasher + Send + Sync>,
+        ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
+            Ok(None)
+        }
+
+        async fn compact(&self, _hand_id: HandId, _keep_every: u32) -> anyhow::Result<u64> {
+            Ok(0)
+        }
+    }
+
+    /// Snapshot store whose transactional insert always fails, to exercise the rollback path.
+    #[derive(Default)]
+    struct FailingSnapshotStore<C> {
+        _marker: std::marker::PhantomData<C>,
+    }
+
+    #[async_trait]
+    impl<C> SnapshotStore<C> for FailingSnapshotStore<C>
+    where
+        C: CurveGroup + CurveAbsorb<C::BaseField> + Send + Sync + 'static,
+        C::BaseField: PrimeField,
+        C::ScalarField: PrimeField + Absorb,
+        C::Affine: Absorb,
+    {
+        async fn persist_snapshot(
+            &self,
+            _snapshot: &AnyTableSnapshot<C>,
+            _hasher: &Arc<dyn LedgerHasher + Send + Sync>,
+        ) -> anyhow::Result<()> {
+            anyhow::bail!("injected snapshot persistence failure")
+        }
+
+        async fn persist_snapshot_in_txn(
+            &self,
+            _txn: &DatabaseTransaction,
+            _prepared: &PreparedSnapshot,
+        ) -> anyhow::Result<()> {
+            anyhow::bail!("injected snapshot persistence failure")
+        }
+
+        async fn load_latest_snapshot(
+            &self,
+            _hand_id: HandId,
+        ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
+            Ok(None)
+        }
+
+        async fn snapshot_at(
+            &self,
+            _hand_id: HandId,
+            _sequence: SnapshotSeq,
+            _hasher: &Arc<dyn LedgerHasher + Send + Sync>,
+        ) -> anyhow::Result<Option<AnyTableSnapshot<C>>> {
+            Ok(None)
+        }
+
+        async fn compact(&self, _hand_id: HandId, _keep_every: u32) -> anyhow::Result<u64> {
+            Ok(0)
+        }
     }
 
     fn sample_cipher() -> ElGamalCiphertext<Curve> {
@@ -416,11 +557,16 @@ mod tests {
 
         let shuffler_public = Curve::zero();
         let shuffler_key = crate::ledger::CanonicalKey::new(shuffler_public.clone());
+        let vrf_nonce = Fq::from(7u64);
         let shuffling = ShufflingSnapshot {
             initial_deck: deck_in.clone(),
             steps: Vec::new(),
             final_deck: deck_out.clone(),
             expected_order: vec![shuffler_key.clone()],
+            nonce_commitments: vec![NonceCommitment {
+                shuffler_key: shuffler_key.clone(),
+                commitment: commit_nonce(&vrf_nonce),
+            }],
         };
 
         let mut roster = ShufflerRoster::new();
@@ -444,6 +590,9 @@ mod tests {
             small_blind_seat: 0,
             big_blind_seat: 0,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
 
         let mut snapshot: TableSnapshot<PhaseShuffling, Curve> = TableSnapshot {
@@ -468,12 +617,10 @@ mod tests {
         snapshot.initialize_hash(&*hasher);
         state.upsert_snapshot(hand_id, AnyTableSnapshot::Shuffling(snapshot), true);
 
-        let message = AnyGameMessage::Shuffle(GameShuffleMessage::new(
-            deck_in,
-            deck_out,
-            shuffle_proof(),
-            0,
-        ));
+        let message = AnyGameMessage::Shuffle(
+            GameShuffleMessage::new(deck_in, deck_out, shuffle_proof(), 0)
+                .with_vrf_nonce(vrf_nonce),
+        );
 
         AnyMessageEnvelope {
             hand_id,
@@ -488,6 +635,7 @@ mod tests {
                 value: message,
                 signature: Vec::new(),
             },
+            correlation_id: None,
         }
     }
 
@@ -703,6 +851,37 @@ mod tests {
         let _ = worker;
     }
 
+    #[tokio::test]
+    async fn stop_ends_the_worker_while_senders_remain() {
+        let _guard = setup_test_tracing();
+        let (tx, rx) = mpsc::channel::<AnyMessageEnvelope<Curve>>(16);
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let stop = CancellationToken::new();
+        let worker = LedgerWorker::new(
+            rx,
+            Arc::new(SeaOrmEventStore::<Curve>::new(
+                DatabaseConnection::Disconnected,
+            )),
+            Arc::new(NoopSnapshotStore::<Curve>::default()),
+            Arc::new(LedgerState::<Curve>::new()),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        )
+        .with_stop(stop.clone());
+        let handle = tokio::spawn(worker.run());
+
+        stop.cancel();
+        timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("worker exits once stopped")
+            .expect("worker task")
+            .expect("worker exits cleanly");
+        assert!(tx.is_closed());
+    }
+
     #[tokio::test]
     async fn persist_before_apply() {
         let _guard = setup_test_tracing();
@@ -742,6 +921,69 @@ mod tests {
         assert_ne!(before_tip, after_tip);
     }
 
+    #[tokio::test]
+    async fn submitted_event_is_persisted_with_the_request_correlation_id() {
+        let _guard = setup_test_tracing();
+        let (tx, rx) = mpsc::channel(16);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let hand_id: HandId = 1;
+        seed_hand_rows(&store.connection, [hand_id])
+            .await
+            .expect("seed worker hand");
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let verifier = Arc::new(LedgerVerifier::new(state.clone()));
+        let worker = LedgerWorker::new(
+            rx,
+            store.clone(),
+            Arc::new(NoopSnapshotStore::<Curve>::default()),
+            state.clone(),
+            events_tx.clone(),
+            snapshots_tx.clone(),
+            staging_tx.clone(),
+        )
+        .with_verifier(verifier.clone());
+        let operator = LedgerOperator::new(
+            verifier,
+            tx,
+            store.clone(),
+            state.clone(),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+        let runner = tokio::spawn(async move { worker.run().await.unwrap() });
+
+        let mut event = prepare_shuffle_event(&state, hand_id, 0);
+        event.message.signature = event.message.value.signed_bytes().unwrap();
+        let correlation_id = CorrelationId::generate();
+        correlation_id
+            .clone()
+            .scope(operator.submit(hand_id, event))
+            .await
+            .expect("submitted");
+
+        let persisted = timeout(Duration::from_secs(5), async {
+            loop {
+                let persisted = store.load_hand_events(hand_id).await.unwrap();
+                if !persisted.is_empty() {
+                    break persisted;
+                }
+                sleep(Duration::from_millis(25)).await;
+            }
+        })
+        .await
+        .expect("worker persisted the event");
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].envelope.correlation_id, Some(correlation_id));
+
+        runner.abort();
+    }
+
     #[tokio::test]
     async fn rollback_on_state_failure() {
         let _guard = setup_test_tracing();
@@ -779,6 +1021,408 @@ mod tests {
         assert_eq!(state.tip_hash(hand_id), before_tip);
     }
 
+    #[tokio::test]
+    async fn persistence_failure_leaves_state_unchanged() {
+        let _guard = setup_test_tracing();
+        let (_tx, rx) = mpsc::channel(16);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let hand_id: HandId = 11;
+        seed_hand_rows(&store.connection, [hand_id])
+            .await
+            .expect("seed worker hand");
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, mut snapshots_rx) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let verifier = Arc::new(LedgerVerifier::new(state.clone()));
+        let worker = LedgerWorker::new(
+            rx,
+            store.clone(),
+            Arc::new(FailingSnapshotStore::<Curve>::default()),
+            state.clone(),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        )
+        .with_verifier(verifier.clone());
+
+        let mut event = prepare_shuffle_event(&state, hand_id, 0);
+        event.message.signature = event.message.value.signed_bytes().unwrap();
+        let (before_hash, before_snapshot) = state.tip_snapshot(hand_id).expect("seeded tip");
+
+        let verified = verifier.verify(hand_id, event.clone()).expect("verified");
+        let result = worker.handle_event(verified).await;
+        assert!(matches!(result, Err(WorkerError::Database)));
+
+        // The nonce reservation was rolled back, so a retry with the same nonce is accepted.
+        assert!(verifier.verify(hand_id, event).is_ok());
+
+        // The event insert shared the failed transaction, so it must have been rolled back.
+        let persisted = store.load_hand_events(hand_id).await.unwrap();
+        assert!(persisted.is_empty());
+
+        let (after_hash, after_snapshot) = state.tip_snapshot(hand_id).expect("tip retained");
+        assert_eq!(after_hash, before_hash);
+        assert_eq!(after_snapshot.sequence(), before_snapshot.sequence());
+        assert!(state.messages_up_to_sequence(hand_id, u32::MAX).is_empty());
+        assert!(snapshots_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn compaction_preserves_replay_to_tip() {
+        let _guard = setup_test_tracing();
+        let (_tx, rx) = mpsc::channel(16);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let hand_id: HandId = 12;
+        seed_hand_rows(&store.connection, [hand_id])
+            .await
+            .expect("seed worker hand");
+        table_snapshots::Entity::delete_many()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .exec(&store.connection)
+            .await
+            .expect("clear snapshots from earlier runs");
+        let snapshot_store = Arc::new(SeaOrmSnapshotStore::<Curve>::new(store.connection.clone()));
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let worker = LedgerWorker::new(
+            rx,
+            store.clone(),
+            snapshot_store.clone(),
+            state.clone(),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+
+        // The first shuffle is accepted; the repeats are recorded as failure snapshots, which
+        // still advance the sequence and give the hand a run of intermediate snapshots.
+        let event = prepare_shuffle_event(&state, hand_id, 0);
+        for nonce in 0..8 {
+            let mut event = event.clone();
+            event.nonce = nonce;
+            let result = worker.handle_event(event).await;
+            assert!(!matches!(result, Err(WorkerError::Database)));
+        }
+        let tip_hash = state.tip_hash(hand_id).expect("tip after events");
+
+        let deleted = snapshot_store.compact(hand_id, 3).await.unwrap();
+        assert!(deleted > 0);
+
+        let kept = table_snapshots::Entity::find()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .order_by_asc(table_snapshots::Column::Sequence)
+            .all(&store.connection)
+            .await
+            .unwrap();
+        assert_eq!(kept.len() as u64 + deleted, 8);
+        let latest = snapshot_store
+            .load_latest_snapshot(hand_id)
+            .await
+            .unwrap()
+            .expect("tip survives compaction");
+        assert_eq!(latest.state_hash(), tip_hash);
+
+        // Replaying the events recorded after the last kept checkpoint must land on the tip.
+        let checkpoint = &kept[kept.len() - 2];
+        let checkpoint_hash = StateHash::from_bytes(checkpoint.state_hash.clone()).unwrap();
+        let checkpoint_snapshot = state
+            .snapshot(hand_id, checkpoint_hash)
+            .expect("checkpoint known to ledger state");
+        let replayed = LedgerState::<Curve>::new();
+        replayed.upsert_snapshot(hand_id, checkpoint_snapshot, true);
+        let events = store
+            .load_hand_events_in_sequence_range(hand_id, Some(checkpoint.sequence as u32 + 1), None)
+            .await
+            .unwrap();
+        assert!(!events.is_empty());
+        replayed.replay(events).unwrap();
+        assert_eq!(replayed.tip_hash(hand_id), Some(tip_hash));
+    }
+
+    #[tokio::test]
+    async fn snapshot_at_rebuilds_compacted_sequences() {
+        let _guard = setup_test_tracing();
+        let (_tx, rx) = mpsc::channel(16);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let hand_id: HandId = 15;
+        seed_hand_rows(&store.connection, [hand_id])
+            .await
+            .expect("seed worker hand");
+        table_snapshots::Entity::delete_many()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .exec(&store.connection)
+            .await
+            .expect("clear snapshots from earlier runs");
+        let snapshot_store = Arc::new(SeaOrmSnapshotStore::<Curve>::new(store.connection.clone()));
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let worker = LedgerWorker::new(
+            rx,
+            store.clone(),
+            snapshot_store.clone(),
+            state.clone(),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+
+        let event = prepare_shuffle_event(&state, hand_id, 0);
+        for nonce in 0..8 {
+            let mut event = event.clone();
+            event.nonce = nonce;
+            let result = worker.handle_event(event).await;
+            assert!(!matches!(result, Err(WorkerError::Database)));
+        }
+        let original: Vec<(i32, Vec<u8>)> = table_snapshots::Entity::find()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .order_by_asc(table_snapshots::Column::Sequence)
+            .all(&store.connection)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.sequence, row.state_hash))
+            .collect();
+
+        snapshot_store.compact(hand_id, 3).await.unwrap();
+        let kept: Vec<i32> = table_snapshots::Entity::find()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .all(&store.connection)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.sequence)
+            .collect();
+        let pruned: Vec<_> = original
+            .iter()
+            .filter(|(sequence, _)| !kept.contains(sequence))
+            .collect();
+        assert!(!pruned.is_empty());
+
+        let hasher = state.hasher();
+        for (sequence, state_hash) in pruned {
+            let rebuilt = snapshot_store
+                .snapshot_at(hand_id, *sequence as SnapshotSeq, &hasher)
+                .await
+                .unwrap()
+                .expect("pruned sequence is rebuilt");
+            assert_eq!(rebuilt.sequence(), *sequence as SnapshotSeq);
+            assert_eq!(
+                rebuilt.state_hash(),
+                StateHash::from_bytes(state_hash.clone()).unwrap()
+            );
+        }
+
+        let (tip_sequence, _) = original.last().expect("snapshots persisted");
+        assert!(snapshot_store
+            .snapshot_at(hand_id, *tip_sequence as SnapshotSeq + 1, &hasher)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn verify_on_load_detects_tampered_phase() {
+        let _guard = setup_test_tracing();
+        let (_tx, rx) = mpsc::channel(16);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let hand_id: HandId = 14;
+        seed_hand_rows(&store.connection, [hand_id])
+            .await
+            .expect("seed worker hand");
+        table_snapshots::Entity::delete_many()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .exec(&store.connection)
+            .await
+            .expect("clear snapshots from earlier runs");
+        let snapshot_store = Arc::new(SeaOrmSnapshotStore::<Curve>::new(store.connection.clone()));
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let worker = LedgerWorker::new(
+            rx,
+            store.clone(),
+            snapshot_store.clone(),
+            state.clone(),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+        let result = worker
+            .handle_event(prepare_shuffle_event(&state, hand_id, 0))
+            .await;
+        assert!(!matches!(result, Err(WorkerError::Database)));
+
+        let verifying = SeaOrmSnapshotStore::<Curve>::new(store.connection.clone())
+            .with_verify_on_load(state.hasher());
+        verifying
+            .load_latest_snapshot(hand_id)
+            .await
+            .expect("untouched snapshot verifies")
+            .expect("snapshot persisted");
+
+        // Phase rows are shared between hands by hash, so rather than editing the stored payload
+        // in place, point the tip at a copy whose expected order has been emptied.
+        let tip = table_snapshots::Entity::find()
+            .filter(table_snapshots::Column::HandId.eq(hand_id))
+            .order_by_desc(table_snapshots::Column::Sequence)
+            .one(&store.connection)
+            .await
+            .unwrap()
+            .expect("tip row");
+        let tampered_hash = vec![0xee; 32];
+        store
+            .connection
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "INSERT INTO phases (hash, phase_type, payload, message_id, created_at) \
+                 SELECT $1, phase_type, jsonb_set(payload, '{expected_order}', '[]'::jsonb), \
+                 message_id, created_at FROM phases WHERE hash = $2 \
+                 ON CONFLICT (hash) DO NOTHING",
+                [
+                    Value::from(tampered_hash.clone()),
+                    Value::from(tip.shuffling_hash.clone().expect("shuffling hash")),
+                ],
+            ))
+            .await
+            .expect("insert tampered phase");
+        store
+            .connection
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE table_snapshots SET shuffling_hash = $1 \
+                 WHERE hand_id = $2 AND sequence = $3",
+                [
+                    Value::from(tampered_hash),
+                    Value::from(hand_id),
+                    Value::from(tip.sequence),
+                ],
+            ))
+            .await
+            .expect("point tip at tampered phase");
+
+        snapshot_store
+            .load_latest_snapshot(hand_id)
+            .await
+            .expect("unverified load still succeeds")
+            .expect("snapshot persisted");
+        let err = verifying
+            .load_latest_snapshot(hand_id)
+            .await
+            .expect_err("tampered phase must be detected");
+        match err.downcast_ref::<SnapshotError>() {
+            Some(SnapshotError::HashMismatch {
+                hand_id: failed_hand,
+                sequence,
+                phase,
+            }) => {
+                assert_eq!(*failed_hand, hand_id);
+                assert_eq!(*sequence, tip.sequence);
+                assert_eq!(*phase, "shuffling");
+            }
+            None => panic!("expected a hash mismatch, got {err:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn archived_hand_can_be_loaded_back() {
+        let _guard = setup_test_tracing();
+        let (_tx, rx) = mpsc::channel(16);
+        let Some(store) = setup_event_store().await else {
+            return;
+        };
+        let hand_id: HandId = 13;
+        seed_hand_rows(&store.connection, [hand_id])
+            .await
+            .expect("seed worker hand");
+        for table in ["table_snapshots", "archived_events", "archived_snapshots"] {
+            store
+                .connection
+                .execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    format!("DELETE FROM public.{table} WHERE hand_id = $1"),
+                    vec![Value::from(hand_id)],
+                ))
+                .await
+                .expect("clear rows from earlier runs");
+        }
+        let snapshot_store = Arc::new(SeaOrmSnapshotStore::<Curve>::new(store.connection.clone()));
+        let state = Arc::new(LedgerState::<Curve>::new());
+        let (events_tx, _) = broadcast::channel(16);
+        let (snapshots_tx, _) = broadcast::channel(16);
+        let (staging_tx, _) = broadcast::channel(16);
+        let worker = LedgerWorker::new(
+            rx,
+            store.clone(),
+            snapshot_store.clone(),
+            state.clone(),
+            events_tx.clone(),
+            snapshots_tx.clone(),
+            staging_tx.clone(),
+        );
+        let (operator_tx, _operator_rx) = mpsc::channel(16);
+        let operator = LedgerOperator::new(
+            Arc::new(LedgerVerifier::new(state.clone())),
+            operator_tx,
+            store.clone(),
+            state.clone(),
+            events_tx,
+            snapshots_tx,
+            staging_tx,
+        );
+
+        let event = prepare_shuffle_event(&state, hand_id, 0);
+        for nonce in 0..3 {
+            let mut event = event.clone();
+            event.nonce = nonce;
+            let result = worker.handle_event(event).await;
+            assert!(!matches!(result, Err(WorkerError::Database)));
+        }
+        let (persisted_tip_hash, persisted_tip) = state.tip_snapshot(hand_id).expect("tip");
+
+        assert!(operator.archive_hand(hand_id).await.is_err());
+
+        let ctx = FixtureContext::<Curve>::new(&[0, 1, 2], &[10, 11]);
+        let mut complete = fixture_complete_snapshot(&ctx);
+        complete.hand_id = Some(hand_id);
+        complete.sequence = persisted_tip.sequence() + 1;
+        state.upsert_snapshot(hand_id, AnyTableSnapshot::Complete(complete), true);
+
+        operator.archive_hand(hand_id).await.unwrap();
+
+        assert!(store.load_hand_events(hand_id).await.unwrap().is_empty());
+        assert!(snapshot_store
+            .load_latest_snapshot(hand_id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(state.tip_hash(hand_id).is_none());
+
+        let archived = operator.load_archived_hand(hand_id).await.unwrap();
+        let nonces: Vec<u64> = archived
+            .events
+            .iter()
+            .map(|event| event.envelope.nonce)
+            .collect();
+        assert_eq!(nonces, vec![0, 1, 2]);
+        assert_eq!(archived.snapshot_count, 3);
+        let tip = archived.tip.expect("archived tip snapshot");
+        assert_eq!(tip.state_hash(), persisted_tip_hash);
+    }
+
     #[tokio::test]
     async fn run_loop_drains_queue() {
         let _guard = setup_test_tracing();
@@ -1,4 +1,5 @@
 pub mod chaum_pedersen;
+pub mod clock;
 pub mod config;
 pub mod crypto_serde;
 pub mod curve_absorb;
@@ -9,6 +10,7 @@ pub mod game;
 pub mod ledger;
 pub mod logup;
 pub mod macros;
+pub mod metrics;
 pub mod pedersen_commitment;
 pub mod player;
 pub mod server;
@@ -17,6 +19,7 @@ pub mod shuffler;
 pub mod shuffling;
 pub mod signing;
 pub mod tokio_tools;
+pub mod transcript;
 pub mod vrf;
 
 pub mod db;
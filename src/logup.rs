@@ -6,8 +6,20 @@
 //! The core identity being proved is:
 //! Σ_j 1/(α - y_j) = Σ_i μ_i/(α - x_i)
 //! where y_j are query values, x_i are table entries, and μ_i are multiplicities.
+//!
+//! # Usage
+//! The prover computes the multiplicities natively with [`prove_lookup`], allocates them as
+//! `UInt8` witnesses, and the circuit checks the identity at a verifier challenge α with
+//! [`verify_lookup`], which range-checks every multiplicity. α must be sampled after the
+//! table, queries and multiplicities are fixed; a prover-chosen α proves nothing.
+//!
+//! For a fixed table whose entries are each queried at most once, [`verify_distinct_lookup`]
+//! takes one-bit multiplicities and squeezes α from a Poseidon sponge over the queries and
+//! multiplicities inside the circuit, so the caller supplies no challenge at all.
 
-use ark_ff::PrimeField;
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_ff::{Field, PrimeField};
 use ark_relations::gr1cs::{ConstraintSystemRef, SynthesisError};
 use ark_std::vec::Vec;
 
@@ -21,6 +33,31 @@ use ark_r1cs_std::{
     GR1CSVar,
 };
 
+/// Native witness for a lookup: the table and how many times each entry is queried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LookupWitness<F: PrimeField> {
+    pub table_entries: Vec<F>,
+    /// `multiplicities[i]` counts the queries equal to `table_entries[i]`.
+    pub multiplicities: Vec<u64>,
+}
+
+/// Compute the multiplicities witness that proves every query belongs to `table`.
+///
+/// Every table entry is kept (unused ones get multiplicity zero) so a fixed table stays
+/// constant in the circuit. Returns `None` if a query is not in the table. Duplicate table
+/// entries are credited on their first occurrence only.
+pub fn prove_lookup<F: PrimeField>(table: &[F], queries: &[F]) -> Option<LookupWitness<F>> {
+    let mut multiplicities = vec![0u64; table.len()];
+    for query in queries {
+        let position = table.iter().position(|entry| entry == query)?;
+        multiplicities[position] += 1;
+    }
+    Some(LookupWitness {
+        table_entries: table.to_vec(),
+        multiplicities,
+    })
+}
+
 /// Verifies a LogUp lookup argument with a single challenge.
 ///
 /// # Arguments
@@ -46,7 +83,6 @@ pub fn verify_lookup<F: PrimeField>(
         return Err(SynthesisError::Unsatisfiable);
     }
 
-    let n_used = table_entries.len();
     let m = query_values.len();
 
     if m == 0 {
@@ -70,6 +106,100 @@ pub fn verify_lookup<F: PrimeField>(
     let m_fp = FpVar::<F>::constant(F::from(m as u64));
     mu_sum.enforce_equal(&m_fp)?;
 
+    let mu_fps = multiplicities
+        .iter()
+        .map(uint_to_field)
+        .collect::<Result<Vec<_>, _>>()?;
+    enforce_logup_identity(cs, alpha, table_entries, query_values, &mu_fps)
+}
+
+/// Verifies that every query is a distinct entry of the fixed `table`, deriving α in-circuit.
+///
+/// # Arguments
+/// * `cs` - The constraint system
+/// * `table` - Fixed table entries, allocated as constants
+/// * `query_values` - All queried values, each below `2^query_bits`
+/// * `query_bits` - Bit bound on the queries, used to pack them into the challenge transcript
+/// * `multiplicities` - One bit per table entry: whether that entry is queried
+///
+/// # Constraints
+/// - Multiplicities are bits, so each lies in `{0, 1}` and the queries are pairwise distinct
+/// - Enforces Σ multiplicities = query_values.len()
+/// - Squeezes α from Poseidon over the packed queries and multiplicities
+/// - Enforces LogUp equality: Σ 1/(α-y_j) = Σ μ_i/(α-x_i), one constraint per table entry
+///
+/// The queries are packed several to a field element, which is only injective if every query
+/// really is below `2^query_bits`; the caller must constrain that.
+pub fn verify_distinct_lookup<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    table: &[F],
+    query_values: &[FpVar<F>],
+    query_bits: u32,
+    multiplicities: &[Boolean<F>],
+) -> Result<(), SynthesisError> {
+    if table.len() != multiplicities.len() || query_values.is_empty() || query_bits == 0 {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let mu_fps: Vec<FpVar<F>> = multiplicities.iter().cloned().map(FpVar::from).collect();
+    let mu_sum = mu_fps.iter().fold(FpVar::<F>::zero(), |acc, mu| acc + mu);
+    mu_sum.enforce_equal(&FpVar::constant(F::from(query_values.len() as u64)))?;
+
+    // The table is constant, so the queries and multiplicities are everything the prover
+    // chose; α depends on all of them.
+    let mut transcript = pack_limbs(query_values, query_bits);
+    transcript.extend(pack_limbs(&mu_fps, 1));
+    let mut sponge = PoseidonSpongeVar::new(cs.clone(), &crate::config::poseidon_config::<F>());
+    sponge.absorb(&transcript)?;
+    let alpha = sponge.squeeze_field_elements(1)?.remove(0);
+
+    // S_T = Σ_i μ_i/(α-x_i), witnessing each term t_i and enforcing t_i·(α-x_i) = μ_i
+    let mut s_t = FpVar::<F>::zero();
+    for (entry, mu) in table.iter().zip(mu_fps.iter()) {
+        let den = &alpha - FpVar::constant(*entry);
+        let term = FpVar::new_witness(cs.clone(), || {
+            Ok(mu.value()? * den.value()?.inverse().unwrap_or_default())
+        })?;
+        term.mul_equals(&den, mu)?;
+        s_t += term;
+    }
+
+    // S_F = Σ_j 1/(α-y_j)
+    let mut s_f = FpVar::<F>::zero();
+    for den in compute_denominators(&alpha, query_values)? {
+        let inv = FpVar::new_witness(cs.clone(), || {
+            Ok(den.value()?.inverse().unwrap_or_default())
+        })?;
+        inv.mul_equals(&den, &FpVar::one())?;
+        s_f += inv;
+    }
+
+    s_t.enforce_equal(&s_f)
+}
+
+/// Pack values below `2^bits` into as few field elements as possible, lowest value first.
+fn pack_limbs<F: PrimeField>(values: &[FpVar<F>], bits: u32) -> Vec<FpVar<F>> {
+    let per_limb = ((F::MODULUS_BIT_SIZE - 1) / bits).max(1) as usize;
+    let shift = F::from(2u64).pow([u64::from(bits)]);
+    values
+        .chunks(per_limb)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(FpVar::<F>::zero(), |acc, value| acc * shift + value)
+        })
+        .collect()
+}
+
+/// Enforce Σ_j 1/(α-y_j) = Σ_i μ_i/(α-x_i).
+fn enforce_logup_identity<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    alpha: &FpVar<F>,
+    table_entries: &[FpVar<F>],
+    query_values: &[FpVar<F>],
+    multiplicities: &[FpVar<F>],
+) -> Result<(), SynthesisError> {
     // 2. Compute denominators and inverses for table entries
     let table_denominators = compute_denominators(alpha, table_entries)?;
     let table_inverses = compute_and_constrain_inverses(cs.clone(), &table_denominators)?;
@@ -81,9 +211,8 @@ pub fn verify_lookup<F: PrimeField>(
     // 4. Compute LogUp sums
     // S_T = Σ_i (μ_i * inv_d_i)
     let mut s_t = FpVar::<F>::zero();
-    for i in 0..n_used {
-        let mu_fp = uint_to_field(&multiplicities[i])?;
-        s_t += mu_fp * &table_inverses[i];
+    for (mu, inv) in multiplicities.iter().zip(table_inverses.iter()) {
+        s_t += mu * inv;
     }
 
     // S_F = Σ_j inv_e_j
@@ -230,6 +359,52 @@ mod tests {
         assert!(cs.is_satisfied().unwrap());
     }
 
+    #[test]
+    fn test_prove_lookup_witness_satisfies_verifier() {
+        let table: Vec<Fr> = [2u64, 7, 11].iter().map(|&v| Fr::from(v)).collect();
+        let queries: Vec<Fr> = [7u64, 2, 2].iter().map(|&v| Fr::from(v)).collect();
+        let witness = prove_lookup(&table, &queries).unwrap();
+        assert_eq!(witness.multiplicities, vec![2, 1, 0]);
+        assert!(prove_lookup(&table, &[Fr::from(5u64)]).is_none());
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let alpha = FpVar::new_witness(cs.clone(), || Ok(Fr::from(10u64))).unwrap();
+        let table_vars = new_table_vars(cs.clone(), &table, AllocationMode::Constant).unwrap();
+        let query_vars = new_table_vars(cs.clone(), &queries, AllocationMode::Witness).unwrap();
+        let mu_vars: Vec<UInt8<Fr>> = witness
+            .multiplicities
+            .iter()
+            .map(|&mu| UInt8::new_witness(cs.clone(), || Ok(mu as u8)).unwrap())
+            .collect();
+        verify_lookup(cs.clone(), &alpha, &table_vars, &query_vars, &mu_vars).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_distinct_lookup_derives_its_own_challenge() {
+        let table: Vec<Fr> = [2u64, 7, 11].iter().map(|&v| Fr::from(v)).collect();
+        let check = |queries: &[u64], bits: [bool; 3]| {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let query_vars: Vec<FpVar<Fr>> = queries
+                .iter()
+                .map(|&q| FpVar::new_witness(cs.clone(), || Ok(Fr::from(q))).unwrap())
+                .collect();
+            let mu_vars: Vec<Boolean<Fr>> = bits
+                .iter()
+                .map(|&b| Boolean::new_witness(cs.clone(), || Ok(b)).unwrap())
+                .collect();
+            verify_distinct_lookup(cs.clone(), &table, &query_vars, 4, &mu_vars).unwrap();
+            cs.is_satisfied().unwrap()
+        };
+
+        assert!(check(&[11, 2], [true, false, true]));
+        // A query outside the table fails whichever bits the prover picks.
+        assert!(!check(&[11, 5], [true, false, true]));
+        assert!(!check(&[11, 5], [false, true, true]));
+        // A repeated query needs a multiplicity of two, which one bit cannot hold.
+        assert!(!check(&[7, 7], [false, true, false]));
+    }
+
     #[test]
     fn test_wrong_multiplicity_sum() {
         let cs = ConstraintSystem::<Fr>::new_ref();
@@ -0,0 +1,169 @@
+//! Minimal Prometheus-style metrics.
+//!
+//! With the `metrics` feature enabled, [`metrics_counter_inc!`](crate::metrics_counter_inc) and
+//! [`metrics_time!`](crate::metrics_time) record into a fixed, process-wide set of counters and
+//! histograms, and `render` formats them in the Prometheus text exposition format. The server
+//! exposes this at `/metrics`. Without the feature the macros expand to nothing (or to the
+//! timed block itself), so call sites cost nothing.
+
+/// Increment one of the counters defined in [`crate::metrics`].
+#[cfg(feature = "metrics")]
+#[macro_export]
+macro_rules! metrics_counter_inc {
+    ($counter:ident) => {
+        $crate::metrics::$counter.inc()
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+#[macro_export]
+macro_rules! metrics_counter_inc {
+    ($counter:ident) => {
+        ()
+    };
+}
+
+/// Evaluate `$body`, recording its wall-clock duration in one of the histograms defined in
+/// [`crate::metrics`].
+#[cfg(feature = "metrics")]
+#[macro_export]
+macro_rules! metrics_time {
+    ($histogram:ident, $body:expr) => {{
+        let started = ::std::time::Instant::now();
+        let result = $body;
+        $crate::metrics::$histogram.observe(started.elapsed().as_secs_f64());
+        result
+    }};
+}
+
+#[cfg(not(feature = "metrics"))]
+#[macro_export]
+macro_rules! metrics_time {
+    ($histogram:ident, $body:expr) => {
+        $body
+    };
+}
+
+#[cfg(feature = "metrics")]
+pub use registry::*;
+
+#[cfg(feature = "metrics")]
+mod registry {
+    use std::fmt::Write;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Ledger events the ledger worker persisted, including events whose transition failed and
+    /// were recorded with a failure status. Events that could not be persisted are not counted.
+    pub static EVENTS_APPLIED: Counter = Counter::new(
+        "legit_poker_events_applied_total",
+        "Ledger events persisted by the ledger worker, including failed transitions",
+    );
+
+    /// Hole cards a player failed to decrypt from the collected shares.
+    pub static DECRYPT_FAILURES: Counter = Counter::new(
+        "legit_poker_decrypt_failures_total",
+        "Hole card decryptions that failed",
+    );
+
+    /// Time spent generating a shuffle and its proof.
+    pub static PROOF_GEN_SECONDS: Histogram = Histogram::new(
+        "legit_poker_proof_gen_seconds",
+        "Time spent generating a shuffle proof",
+    );
+
+    const HISTOGRAM_BUCKETS: [f64; 8] = [0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0];
+
+    pub struct Counter {
+        name: &'static str,
+        help: &'static str,
+        value: AtomicU64,
+    }
+
+    impl Counter {
+        const fn new(name: &'static str, help: &'static str) -> Self {
+            Self {
+                name,
+                help,
+                value: AtomicU64::new(0),
+            }
+        }
+
+        pub fn inc(&self) {
+            self.value.fetch_add(1, Ordering::Relaxed);
+        }
+
+        pub fn get(&self) -> u64 {
+            self.value.load(Ordering::Relaxed)
+        }
+
+        fn render(&self, out: &mut String) {
+            let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+            let _ = writeln!(out, "# TYPE {} counter", self.name);
+            let _ = writeln!(out, "{} {}", self.name, self.get());
+        }
+    }
+
+    pub struct Histogram {
+        name: &'static str,
+        help: &'static str,
+        buckets: [AtomicU64; HISTOGRAM_BUCKETS.len()],
+        count: AtomicU64,
+        /// Sum of observations in microseconds, so it can be kept in an atomic integer.
+        sum_micros: AtomicU64,
+    }
+
+    impl Histogram {
+        const fn new(name: &'static str, help: &'static str) -> Self {
+            Self {
+                name,
+                help,
+                buckets: [const { AtomicU64::new(0) }; HISTOGRAM_BUCKETS.len()],
+                count: AtomicU64::new(0),
+                sum_micros: AtomicU64::new(0),
+            }
+        }
+
+        pub fn observe(&self, seconds: f64) {
+            for (bound, bucket) in HISTOGRAM_BUCKETS.iter().zip(&self.buckets) {
+                if seconds <= *bound {
+                    bucket.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.sum_micros
+                .fetch_add((seconds * 1e6) as u64, Ordering::Relaxed);
+        }
+
+        pub fn count(&self) -> u64 {
+            self.count.load(Ordering::Relaxed)
+        }
+
+        fn render(&self, out: &mut String) {
+            let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+            let _ = writeln!(out, "# TYPE {} histogram", self.name);
+            for (bound, bucket) in HISTOGRAM_BUCKETS.iter().zip(&self.buckets) {
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{{le=\"{}\"}} {}",
+                    self.name,
+                    bound,
+                    bucket.load(Ordering::Relaxed)
+                );
+            }
+            let count = self.count();
+            let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", self.name, count);
+            let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6;
+            let _ = writeln!(out, "{}_sum {}", self.name, sum);
+            let _ = writeln!(out, "{}_count {}", self.name, count);
+        }
+    }
+
+    /// Render every metric in the Prometheus text exposition format.
+    pub fn render() -> String {
+        let mut out = String::new();
+        EVENTS_APPLIED.render(&mut out);
+        DECRYPT_FAILURES.render(&mut out);
+        PROOF_GEN_SECONDS.render(&mut out);
+        out
+    }
+}
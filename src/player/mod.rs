@@ -1,6 +1,6 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use ark_crypto_primitives::signature::schnorr::Schnorr;
 use ark_crypto_primitives::signature::SignatureScheme;
 use ark_ec::CurveGroup;
@@ -9,10 +9,11 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
 use sha2::Digest;
+use thiserror::Error;
 
 use crate::engine::nl::actions::PlayerBetAction;
-use crate::engine::nl::types::SeatId;
-use crate::showdown::{choose_best5_from7, pack_score_field, Card, HandCategory, Index};
+use crate::engine::nl::types::{SeatId, HOLDEM_HOLE_CARDS};
+use crate::showdown::{choose_best5_for_hole, pack_score_field, Card, HandCategory, Index};
 use crate::signing::{DomainSeparated, SignatureBytes, WithSignature};
 use crate::PlayerAccessibleCiphertext;
 
@@ -22,7 +23,7 @@ use rand::SeedableRng;
 pub struct PlayerActionBet {
     pub seat: SeatId,
     pub action: PlayerBetAction,
-    /// Optional anti-replay field (caller managed). 0 if unused.
+    /// Anti-replay counter; [`PlayerSigner`] assigns consecutive values starting at 0.
     pub nonce: u64,
 }
 
@@ -41,10 +42,20 @@ pub struct PlayerShowdownResult<F: PrimeField> {
     pub score_field: F,
 }
 
+/// The player's signing and decryption keys are not derived from the same secret.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum KeyBindingError {
+    #[error("signing public key was not derived from the signer's secret key")]
+    SigningKeyMismatch,
+    #[error("decryption public key is not g^sk for the signing secret; showdown would decrypt with the wrong key")]
+    DecryptionKeyMismatch,
+}
+
 /// Unblinding context (committee shares and configuration) for decrypting
 /// player-targeted hole ciphertexts.
 struct UnblindingContext<G: CurveGroup> {
-    shares: [Vec<crate::shuffling::player_decryption::PartialUnblindingShare<G>>; 2],
+    /// Committee shares per hole card, in hole order.
+    shares: Vec<Vec<crate::shuffling::player_decryption::PartialUnblindingShare<G>>>,
     expected_members: usize,
 }
 
@@ -74,8 +85,9 @@ pub trait PlayerApi {
     where
         <Self::SigScheme as SignatureScheme>::Signature: SignatureBytes;
 
-    /// Compute the player's showdown result deterministically from their two
-    /// hole cards and the five-card public board.
+    /// Compute the player's showdown result deterministically from their hole
+    /// cards and the five-card public board. Two hole cards are scored as hold'em,
+    /// four as Omaha.
     ///
     /// - `community_board` – Five public cards as `Index` values.
     ///
@@ -103,7 +115,9 @@ where
     pub pk: S::PublicKey,
     pub sk: S::SecretKey,
     rng: RefCell<StdRng>,
-    hole_ciphertexts: Option<[PlayerAccessibleCiphertext<G>; 2]>,
+    next_nonce: Cell<u64>,
+    hole_cards: u8,
+    hole_ciphertexts: Option<Vec<PlayerAccessibleCiphertext<G>>>,
 
     /// Aggregated committee/shuffler public key Σ_j pk_j
     aggregated_shuffler_public_key: Option<G>,
@@ -129,28 +143,57 @@ where
             pk,
             sk: sk.clone(),
             rng: RefCell::new(rng),
+            next_nonce: Cell::new(0),
+            hole_cards: HOLDEM_HOLE_CARDS,
             hole_ciphertexts: None,
             aggregated_shuffler_public_key: None,
             unblinding: None,
         }
     }
 
-    pub fn set_hole_ciphertexts(&mut self, hole: [PlayerAccessibleCiphertext<G>; 2]) {
+    /// Nonce that the next signed action will carry.
+    pub fn next_nonce(&self) -> u64 {
+        self.next_nonce.get()
+    }
+
+    /// Deal this player `hole_cards` cards per hand instead of hold'em's two.
+    pub fn with_hole_cards(mut self, hole_cards: u8) -> Self {
+        self.hole_cards = hole_cards;
+        self
+    }
+
+    pub fn hole_cards(&self) -> u8 {
+        self.hole_cards
+    }
+
+    /// Record the player's hole ciphertexts in hole order; there must be one per hole card.
+    pub fn set_hole_ciphertexts(
+        &mut self,
+        hole: impl Into<Vec<PlayerAccessibleCiphertext<G>>>,
+    ) -> Result<()> {
+        let hole = hole.into();
+        ensure!(
+            hole.len() == usize::from(self.hole_cards),
+            "expected {} hole ciphertexts, got {}",
+            self.hole_cards,
+            hole.len()
+        );
         self.hole_ciphertexts = Some(hole);
+        Ok(())
     }
 
     pub fn set_aggregated_shuffler_public_key(&mut self, pk: G) {
         self.aggregated_shuffler_public_key = Some(pk);
     }
 
+    /// Committee unblinding shares for each hole card, in hole order.
     pub fn set_unblinding_context(
         &mut self,
-        shares0: Vec<crate::shuffling::player_decryption::PartialUnblindingShare<G>>,
-        shares1: Vec<crate::shuffling::player_decryption::PartialUnblindingShare<G>>,
+        shares: impl Into<Vec<Vec<crate::shuffling::player_decryption::PartialUnblindingShare<G>>>>,
         expected_members: usize,
     ) {
         self.unblinding = Some(UnblindingContext {
-            shares: [shares0, shares1],
+            shares: shares.into(),
             expected_members,
         });
     }
@@ -166,18 +209,94 @@ where
     pub fn sign_action(
         &self,
         action: PlayerBetAction,
+    ) -> anyhow::Result<WithSignature<<S as SignatureScheme>::Signature, PlayerActionBet>> {
+        let mut rng = self.rng.borrow_mut();
+        let nonce = self.next_nonce.get();
+        let env = self.sign_with_nonce(action, nonce, &mut rng)?;
+        self.next_nonce.set(nonce + 1);
+        Ok(env)
+    }
+
+    /// Sign several actions with consecutive nonces, borrowing the RNG once.
+    ///
+    /// Nonces continue from the last signed action, and the counter only advances if every
+    /// action in the batch was signed.
+    pub fn sign_actions(
+        &self,
+        actions: &[PlayerBetAction],
+    ) -> anyhow::Result<Vec<WithSignature<<S as SignatureScheme>::Signature, PlayerActionBet>>>
+    {
+        let mut rng = self.rng.borrow_mut();
+        let first_nonce = self.next_nonce.get();
+        let signed = actions
+            .iter()
+            .zip(first_nonce..)
+            .map(|(action, nonce)| self.sign_with_nonce(action.clone(), nonce, &mut rng))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.next_nonce.set(first_nonce + signed.len() as u64);
+        Ok(signed)
+    }
+
+    fn sign_with_nonce(
+        &self,
+        action: PlayerBetAction,
+        nonce: u64,
+        rng: &mut StdRng,
     ) -> anyhow::Result<WithSignature<<S as SignatureScheme>::Signature, PlayerActionBet>> {
         let payload = PlayerActionBet {
             seat: self.seat,
             action,
-            nonce: 0,
+            nonce,
         };
-        let mut rng = self.rng.borrow_mut();
-        let env = WithSignature::<<S as SignatureScheme>::Signature, PlayerActionBet>::new::<
-            S,
-            StdRng,
-        >(payload, &self.params, &self.sk, &mut *rng)?;
-        Ok(env)
+        WithSignature::<<S as SignatureScheme>::Signature, PlayerActionBet>::new::<S, StdRng>(
+            payload,
+            &self.params,
+            &self.sk,
+            rng,
+        )
+    }
+}
+
+impl<D, G> PlayerSigner<Schnorr<G, D>, G>
+where
+    D: Digest + Send + Sync,
+    G: CurveGroup,
+{
+    /// Build the signer for a registered player, checking its keys with
+    /// [`verify_key_binding`](Self::verify_key_binding) against `pk` and the decryption key
+    /// the player registered. Use this rather than [`PlayerSigner::new`] for any signer that
+    /// will reach showdown.
+    pub fn new_registered(
+        seat: SeatId,
+        params: <Schnorr<G, D> as SignatureScheme>::Parameters,
+        pk: <Schnorr<G, D> as SignatureScheme>::PublicKey,
+        sk: <Schnorr<G, D> as SignatureScheme>::SecretKey,
+        seed: [u8; 32],
+        decryption_pk: &G,
+    ) -> std::result::Result<Self, KeyBindingError> {
+        let signer = Self::new(seat, params, pk, sk, seed);
+        signer.verify_key_binding(&signer.pk, decryption_pk)?;
+        Ok(signer)
+    }
+
+    /// Confirm that `signing_pk` and `decryption_pk` both come from this signer's secret.
+    ///
+    /// Showdown decrypts hole cards with the Schnorr secret (see `provide_showdown_result`), so
+    /// the key the committee blinds to must be `g^sk` for that same scalar. Call this at startup
+    /// with the registered keys; a mismatch would otherwise only surface as garbage cards.
+    pub fn verify_key_binding(
+        &self,
+        signing_pk: &G::Affine,
+        decryption_pk: &G,
+    ) -> std::result::Result<(), KeyBindingError> {
+        let sk = self.sk.0;
+        if (self.params.generator * sk).into_affine() != *signing_pk {
+            return Err(KeyBindingError::SigningKeyMismatch);
+        }
+        if G::generator() * sk != *decryption_pk {
+            return Err(KeyBindingError::DecryptionKeyMismatch);
+        }
+        Ok(())
     }
 }
 
@@ -195,19 +314,7 @@ where
     where
         <Self::SigScheme as SignatureScheme>::Signature: SignatureBytes,
     {
-        // Sign using our concrete scheme S.
-        let payload = PlayerActionBet {
-            seat: self.seat,
-            action,
-            nonce: 0,
-        };
-        let mut rng = self.rng.borrow_mut();
-        let env =
-            WithSignature::<<Self::SigScheme as SignatureScheme>::Signature, PlayerActionBet>::new::<
-                Self::SigScheme,
-                StdRng,
-            >(payload, &self.params, &self.sk, &mut *rng)?;
-        Ok(env)
+        self.sign_action(action)
     }
 
     fn provide_showdown_result<F: PrimeField, C: CurveGroup>(
@@ -230,31 +337,28 @@ where
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("unblinding context not set on PlayerSigner"))?;
 
-        let v0 = crate::shuffling::player_decryption::recover_card_value::<G>(
-            &cts[0],
-            sk,
-            ctx.shares[0].clone(),
-            ctx.expected_members,
-        )
-        .map_err(|e| anyhow::anyhow!(e))?;
-        let v1 = crate::shuffling::player_decryption::recover_card_value::<G>(
-            &cts[1],
-            sk,
-            ctx.shares[1].clone(),
-            ctx.expected_members,
-        )
-        .map_err(|e| anyhow::anyhow!(e))?;
-        let hole: [Index; 2] = [v0, v1];
-        let idx7 = [
-            hole[0],
-            hole[1],
-            community_board[0],
-            community_board[1],
-            community_board[2],
-            community_board[3],
-            community_board[4],
-        ];
-        let best = choose_best5_from7(idx7);
+        ensure!(
+            ctx.shares.len() == cts.len(),
+            "unblinding shares cover {} hole cards but {} are dealt",
+            ctx.shares.len(),
+            cts.len()
+        );
+
+        let hole = cts
+            .iter()
+            .zip(&ctx.shares)
+            .map(|(ct, shares)| {
+                crate::shuffling::player_decryption::recover_card_value::<G>(
+                    ct,
+                    sk,
+                    shares.clone(),
+                    ctx.expected_members,
+                )
+                .map_err(|e| anyhow::anyhow!(e))
+            })
+            .collect::<Result<Vec<Index>>>()?;
+        let best = choose_best5_for_hole(&hole, community_board)
+            .ok_or_else(|| anyhow::anyhow!("no evaluator for {} hole cards", hole.len()))?;
         let best5 = best.hand.cards;
         let category = best.hand.category;
         let tiebreak = best.tiebreak;
@@ -274,16 +378,13 @@ where
 mod tests {
     use super::*;
     use crate::engine::nl::{engine::BettingEngineNL, engine::EngineNL, types::*, BettingState};
+    use crate::ledger::test_support::Committee;
     use crate::showdown::{idx_of, Suit};
     use crate::shuffling::data_structures::ElGamalCiphertext;
-    use crate::shuffling::player_decryption::{
-        combine_blinding_contributions_for_player, generate_committee_decryption_share,
-        PlayerTargetedBlindingContribution,
-    };
+    use crate::shuffling::player_decryption::combine_blinding_contributions_for_player;
     use ark_bn254::Fr;
     use ark_crypto_primitives::signature::{schnorr::Schnorr, SignatureScheme};
     use ark_ec::PrimeGroup;
-    use ark_std::UniformRand;
     use ark_std::Zero;
     use sha2::Sha256;
 
@@ -304,7 +405,9 @@ mod tests {
         let mut rng = ark_std::test_rng();
         let params = Scheme::setup(&mut rng).expect("setup");
         let (pk, sk) = Scheme::keygen(&params, &mut rng).expect("keygen");
-        PlayerSigner::new(seat, params, pk, sk, [7u8; 32])
+        let decryption_pk = GrumpkinProjective::generator() * sk.0;
+        PlayerSigner::new_registered(seat, params, pk, sk, [7u8; 32], &decryption_pk)
+            .expect("keys derive from one secret")
     }
 
     fn setup_preflop_state() -> BettingState {
@@ -319,6 +422,9 @@ mod tests {
             small_blind_seat: 1,
             big_blind_seat: 2,
             check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
         };
         // Seats 0..5; post blinds for 1(SB) and 2(BB)
         let mut players: Vec<PlayerState> = (0u8..6)
@@ -338,6 +444,91 @@ mod tests {
         EngineNL::new_after_deal(cfg, players, empty_pots())
     }
 
+    #[test]
+    fn sign_actions_assigns_contiguous_nonces() {
+        let signer = new_signer(3);
+        let first = signer.sign_action(PlayerBetAction::Check).unwrap();
+        assert_eq!(first.value.nonce, 0);
+
+        let batch = signer
+            .sign_actions(&[
+                PlayerBetAction::Call,
+                PlayerBetAction::BetTo { to: 10 },
+                PlayerBetAction::Fold,
+            ])
+            .unwrap();
+        let nonces: Vec<u64> = batch.iter().map(|signed| signed.value.nonce).collect();
+        assert_eq!(nonces, vec![1, 2, 3]);
+        for signed in &batch {
+            assert_eq!(signed.value.seat, 3);
+            assert!(signed.verify::<Scheme>(&signer.params, &signer.pk).unwrap());
+        }
+
+        let next = signer.sign_action(PlayerBetAction::Check).unwrap();
+        assert_eq!(next.value.nonce, 4);
+        assert_eq!(signer.next_nonce(), 5);
+    }
+
+    #[test]
+    fn key_binding_accepts_matching_keys_and_rejects_divergent_ones() {
+        let signer = new_signer(0);
+        let decryption_pk = GrumpkinProjective::generator() * signer.sk.0;
+        assert_eq!(
+            signer.verify_key_binding(&signer.pk, &decryption_pk),
+            Ok(())
+        );
+
+        let other_pk = GrumpkinProjective::generator() * (signer.sk.0 + signer.sk.0);
+        assert_eq!(
+            signer.verify_key_binding(&signer.pk, &other_pk),
+            Err(KeyBindingError::DecryptionKeyMismatch)
+        );
+        assert_eq!(
+            signer.verify_key_binding(&other_pk.into_affine(), &decryption_pk),
+            Err(KeyBindingError::SigningKeyMismatch)
+        );
+
+        let refused = PlayerSigner::new_registered(
+            1,
+            signer.params.clone(),
+            signer.pk,
+            signer.sk.clone(),
+            [7u8; 32],
+            &other_pk,
+        );
+        assert!(matches!(
+            refused,
+            Err(KeyBindingError::DecryptionKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn hole_ciphertext_slots_follow_hole_card_count() {
+        use ark_std::Zero;
+
+        let ciphertext = PlayerAccessibleCiphertext {
+            blinded_base: GrumpkinProjective::zero(),
+            blinded_message_with_player_key: GrumpkinProjective::zero(),
+            player_unblinding_helper: GrumpkinProjective::zero(),
+            shuffler_proofs: Vec::new(),
+        };
+
+        let mut holdem = new_signer(0);
+        assert_eq!(holdem.hole_cards(), HOLDEM_HOLE_CARDS);
+        assert!(holdem
+            .set_hole_ciphertexts(vec![ciphertext.clone(); 4])
+            .is_err());
+
+        let mut omaha = new_signer(1).with_hole_cards(OMAHA_HOLE_CARDS);
+        assert!(omaha
+            .set_hole_ciphertexts([ciphertext.clone(), ciphertext.clone()])
+            .is_err());
+        omaha
+            .set_hole_ciphertexts(vec![ciphertext; 4])
+            .expect("one ciphertext per Omaha hole card");
+        assert_eq!(omaha.hole_ciphertexts.as_ref().map(Vec::len), Some(4));
+    }
+
     #[test]
     fn preflop_unopened_until_street_end_then_advance() {
         let mut state = setup_preflop_state();
@@ -626,13 +817,8 @@ mod tests {
 
         // Committee/shufflers: 3 secrets and aggregated PK
         let mut rng = ark_std::test_rng();
-        let shuffler_sk1 = Scalar::rand(&mut rng);
-        let shuffler_sk2 = Scalar::rand(&mut rng);
-        let shuffler_sk3 = Scalar::rand(&mut rng);
-        let shuffler_pk1 = G::generator() * shuffler_sk1;
-        let shuffler_pk2 = G::generator() * shuffler_sk2;
-        let shuffler_pk3 = G::generator() * shuffler_sk3;
-        let shuffler_agg_pk = shuffler_pk1 + shuffler_pk2 + shuffler_pk3;
+        let committee = Committee::<G>::new(3, &mut rng);
+        let shuffler_agg_pk = committee.aggregated_key();
         player1.set_aggregated_shuffler_public_key(shuffler_agg_pk);
         player2.set_aggregated_shuffler_public_key(shuffler_agg_pk);
         player3.set_aggregated_shuffler_public_key(shuffler_agg_pk);
@@ -648,25 +834,7 @@ mod tests {
             let message_point = G::generator() * m_scalar;
             tracing::info!(target = "player_test", m_index_1based, m_scalar = ?m_scalar, ?message_point, "Building player-accessible ciphertext for index");
             let initial_ct = ElGamalCiphertext::new(G::zero(), message_point);
-            let c1 = PlayerTargetedBlindingContribution::generate(
-                shuffler_sk1,
-                shuffler_agg_pk,
-                player_pk,
-                &mut rng,
-            );
-            let c2 = PlayerTargetedBlindingContribution::generate(
-                shuffler_sk2,
-                shuffler_agg_pk,
-                player_pk,
-                &mut rng,
-            );
-            let c3 = PlayerTargetedBlindingContribution::generate(
-                shuffler_sk3,
-                shuffler_agg_pk,
-                player_pk,
-                &mut rng,
-            );
-            let contributions = vec![c1, c2, c3];
+            let contributions = committee.blinding_contributions(player_pk, &mut rng);
             let pact = combine_blinding_contributions_for_player(
                 &initial_ct,
                 &contributions,
@@ -692,51 +860,37 @@ mod tests {
         let p3_ct0 = make_ct(p3_holes[0], player3_pk);
         let p3_ct1 = make_ct(p3_holes[1], player3_pk);
 
-        player1.set_hole_ciphertexts([p1_ct0.clone(), p1_ct1.clone()]);
-        player2.set_hole_ciphertexts([p2_ct0.clone(), p2_ct1.clone()]);
-        player3.set_hole_ciphertexts([p3_ct0.clone(), p3_ct1.clone()]);
-
-        // Create canonical keys for committee members
-        let member_key1 = crate::ledger::CanonicalKey::new(shuffler_pk1);
-        let member_key2 = crate::ledger::CanonicalKey::new(shuffler_pk2);
-        let member_key3 = crate::ledger::CanonicalKey::new(shuffler_pk3);
-
-        let p1_shares0 = vec![
-            generate_committee_decryption_share(&p1_ct0, shuffler_sk1, member_key1.clone()),
-            generate_committee_decryption_share(&p1_ct0, shuffler_sk2, member_key2.clone()),
-            generate_committee_decryption_share(&p1_ct0, shuffler_sk3, member_key3.clone()),
-        ];
-        tracing::info!(target = "player_test", shares = ?p1_shares0.iter().map(|s| &s.share).collect::<Vec<_>>(), "p1 first card shares");
-        let p1_shares1 = vec![
-            generate_committee_decryption_share(&p1_ct1, shuffler_sk1, member_key1.clone()),
-            generate_committee_decryption_share(&p1_ct1, shuffler_sk2, member_key2.clone()),
-            generate_committee_decryption_share(&p1_ct1, shuffler_sk3, member_key3.clone()),
-        ];
-        player1.set_unblinding_context(p1_shares0, p1_shares1, 3);
-
-        let p2_shares0 = vec![
-            generate_committee_decryption_share(&p2_ct0, shuffler_sk1, member_key1.clone()),
-            generate_committee_decryption_share(&p2_ct0, shuffler_sk2, member_key2.clone()),
-            generate_committee_decryption_share(&p2_ct0, shuffler_sk3, member_key3.clone()),
-        ];
-        let p2_shares1 = vec![
-            generate_committee_decryption_share(&p2_ct1, shuffler_sk1, member_key1.clone()),
-            generate_committee_decryption_share(&p2_ct1, shuffler_sk2, member_key2.clone()),
-            generate_committee_decryption_share(&p2_ct1, shuffler_sk3, member_key3.clone()),
-        ];
-        player2.set_unblinding_context(p2_shares0, p2_shares1, 3);
-
-        let p3_shares0 = vec![
-            generate_committee_decryption_share(&p3_ct0, shuffler_sk1, member_key1.clone()),
-            generate_committee_decryption_share(&p3_ct0, shuffler_sk2, member_key2.clone()),
-            generate_committee_decryption_share(&p3_ct0, shuffler_sk3, member_key3.clone()),
-        ];
-        let p3_shares1 = vec![
-            generate_committee_decryption_share(&p3_ct1, shuffler_sk1, member_key1.clone()),
-            generate_committee_decryption_share(&p3_ct1, shuffler_sk2, member_key2.clone()),
-            generate_committee_decryption_share(&p3_ct1, shuffler_sk3, member_key3.clone()),
-        ];
-        player3.set_unblinding_context(p3_shares0, p3_shares1, 3);
+        player1
+            .set_hole_ciphertexts([p1_ct0.clone(), p1_ct1.clone()])
+            .expect("two hole ciphertexts");
+        player2
+            .set_hole_ciphertexts([p2_ct0.clone(), p2_ct1.clone()])
+            .expect("two hole ciphertexts");
+        player3
+            .set_hole_ciphertexts([p3_ct0.clone(), p3_ct1.clone()])
+            .expect("two hole ciphertexts");
+
+        player1.set_unblinding_context(
+            [
+                committee.unblinding_shares(&p1_ct0),
+                committee.unblinding_shares(&p1_ct1),
+            ],
+            3,
+        );
+        player2.set_unblinding_context(
+            [
+                committee.unblinding_shares(&p2_ct0),
+                committee.unblinding_shares(&p2_ct1),
+            ],
+            3,
+        );
+        player3.set_unblinding_context(
+            [
+                committee.unblinding_shares(&p3_ct0),
+                committee.unblinding_shares(&p3_ct1),
+            ],
+            3,
+        );
 
         // Public board
         let board: [Index; 5] = [
@@ -1,47 +1,66 @@
+use std::collections::HashMap;
+use std::future::{Future, IntoFuture};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::CanonicalSerialize;
+use axum::Router;
 use sea_orm::{ActiveModelTrait, ActiveValue::NotSet, ActiveValue::Set, DatabaseConnection};
 use tokio::net::TcpListener;
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 use url::Url;
 
+use crate::clock::SystemClock;
 use crate::curve_absorb::CurveAbsorb;
 use crate::db::connect_to_postgres_db;
 use crate::db::entity::shufflers;
+use crate::game::action_clock::ActionDeadlines;
 use crate::game::coordinator::{
-    GameCoordinator, GameCoordinatorConfig, ShufflerSecret, ShufflerSecretConfig,
-    SupabaseRealtimeClientConfig,
+    validate_shuffler_secrets, GameCoordinator, GameCoordinatorConfig, ShufflerSecret,
+    ShufflerSecretConfig, SupabaseRealtimeClientConfig, DEFAULT_MAX_CONCURRENT_HANDS,
 };
-use crate::ledger::serialization::serialize_curve_bytes;
+use crate::ledger::serialization::{deserialize_curve_bytes, serialize_curve_bytes};
 use crate::ledger::state::LedgerState;
 use crate::ledger::store::{EventStore, SeaOrmEventStore, SeaOrmSnapshotStore, SnapshotStore};
-use crate::ledger::verifier::{LedgerVerifier, Verifier};
+use crate::ledger::types::ShufflerId;
+use crate::ledger::verifier::{
+    LedgerVerifier, SchnorrSignatureValidator, SignatureValidator, Verifier,
+};
 use crate::ledger::{LobbyService, LobbyServiceFactory};
+use crate::shuffler::shuffler_signing_params;
 
+use super::health;
+use super::rate_limit::RateLimitConfig;
 use super::routes::LegitPokerServer;
 
 const LOG_TARGET: &str = "server::bootstrap";
+/// How long in-flight requests and streams get to finish once shutdown starts.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long the coordinator gets to stop its shufflers, realtime client and ledger worker.
+const COORDINATOR_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Bootstrap coordinator shufflers into the database using SeaORM.
 ///
 /// Takes shuffler secrets without IDs, inserts them into the database,
-/// and returns configs with database-assigned IDs paired with their secrets.
+/// and returns configs with database-assigned IDs paired with their secrets, along with the
+/// public key each stored row holds.
 async fn bootstrap_coordinator_shufflers<C>(
     secrets: Vec<ShufflerSecret<C>>,
     db: &DatabaseConnection,
-) -> Result<Vec<ShufflerSecretConfig<C>>>
+) -> Result<(Vec<ShufflerSecretConfig<C>>, HashMap<ShufflerId, C>)>
 where
     C: CurveGroup + CanonicalSerialize + CurveAbsorb<C::BaseField>,
     C::ScalarField: PrimeField,
     C::BaseField: PrimeField,
 {
     let mut configs = Vec::with_capacity(secrets.len());
+    let mut registered = HashMap::with_capacity(secrets.len());
 
     for (idx, secret) in secrets.into_iter().enumerate() {
         // 1. Compute public key from secret
@@ -63,6 +82,9 @@ where
             .with_context(|| format!("failed to insert shuffler {}", idx))?;
 
         let db_id = inserted.id;
+        let registered_key = deserialize_curve_bytes::<C>(&inserted.public_key)
+            .with_context(|| format!("failed to decode stored key for shuffler {}", db_id))?;
+        registered.insert(db_id, registered_key);
 
         // 3. Update display name with actual DB ID using SeaORM
         let mut update_model: shufflers::ActiveModel = inserted.into();
@@ -86,7 +108,7 @@ where
         });
     }
 
-    Ok(configs)
+    Ok((configs, registered))
 }
 
 pub struct ServerConfig<C>
@@ -102,6 +124,11 @@ where
     pub supabase_anon_key: String,
     pub shufflers: Vec<ShufflerSecret<C>>,
     pub rng_seed: Option<[u8; 32]>,
+    /// Per-client limit on `POST /games/demo`.
+    pub demo_rate_limit: RateLimitConfig,
+    /// Time a seat gets to act before the table checks or folds for it. `None` leaves turns
+    /// untimed.
+    pub action_timeout: Option<Duration>,
 }
 
 pub async fn run_server<C>(config: ServerConfig<C>) -> Result<()>
@@ -117,8 +144,18 @@ where
         Arc::new(SeaOrmSnapshotStore::<C>::new(db.clone()));
     let state = Arc::new(LedgerState::<C>::new());
 
-    let verifier: Arc<dyn Verifier<C> + Send + Sync> =
-        Arc::new(LedgerVerifier::new(Arc::clone(&state)));
+    let signing_params = shuffler_signing_params::<C>()?;
+    let signature: Arc<dyn SignatureValidator<C>> =
+        Arc::new(SchnorrSignatureValidator::<C>::new(signing_params.clone()));
+    let action_deadlines = config
+        .action_timeout
+        .map(|timeout| ActionDeadlines::new(Arc::new(SystemClock), timeout));
+    let mut verifier =
+        LedgerVerifier::with_signature_validator(Arc::clone(&state), Arc::clone(&signature));
+    if let Some(deadlines) = &action_deadlines {
+        verifier = verifier.with_action_deadlines(deadlines.clone());
+    }
+    let verifier: Arc<dyn Verifier<C> + Send + Sync> = Arc::new(verifier);
 
     let supabase_cfg = SupabaseRealtimeClientConfig::new(
         config.supabase_realtime.clone(),
@@ -126,9 +163,12 @@ where
     );
 
     // Bootstrap shufflers into database and get back configs with IDs
-    let shufflers_with_db_ids = bootstrap_coordinator_shufflers(config.shufflers, &db)
-        .await
-        .context("failed to bootstrap coordinator shufflers")?;
+    let (shufflers_with_db_ids, registered_keys) =
+        bootstrap_coordinator_shufflers(config.shufflers, &db)
+            .await
+            .context("failed to bootstrap coordinator shufflers")?;
+    validate_shuffler_secrets(&shufflers_with_db_ids, &registered_keys)
+        .context("shuffler secrets do not match registered shufflers")?;
 
     tracing::info!(
         target: LOG_TARGET,
@@ -144,7 +184,11 @@ where
         supabase: supabase_cfg,
         shufflers: shufflers_with_db_ids,
         submit_channel_capacity: 256,
+        max_concurrent_hands: DEFAULT_MAX_CONCURRENT_HANDS,
         rng_seed: config.rng_seed,
+        signing_params,
+        action_deadlines,
+        nonce_source: None,
     };
 
     let coordinator = GameCoordinator::spawn(coordinator_config)
@@ -155,9 +199,15 @@ where
     let lobby: Arc<dyn LobbyService<C>> =
         Arc::new(LobbyServiceFactory::<C>::from_sea_orm(db.clone()));
 
-    let server = LegitPokerServer::new(Arc::clone(&coordinator), Arc::clone(&lobby));
-    let router = server.into_router();
-    let make_service = router.into_make_service();
+    let server = LegitPokerServer::new(
+        Arc::clone(&coordinator),
+        Arc::clone(&lobby),
+        signature,
+        config.demo_rate_limit,
+    );
+    let router = server
+        .into_router()
+        .merge(health::router(db.clone(), Arc::clone(&coordinator)));
 
     let listener = TcpListener::bind(config.bind)
         .await
@@ -170,21 +220,165 @@ where
         "legit poker server listening"
     );
 
-    axum::serve(listener, make_service)
-        .with_graceful_shutdown(shutdown_signal())
+    let served =
+        serve_until_shutdown(listener, router, shutdown_signal(), SHUTDOWN_DRAIN_TIMEOUT).await;
+    let stopped = coordinator
+        .shutdown_graceful(COORDINATOR_SHUTDOWN_TIMEOUT)
         .await
-        .context("server exited with error")
+        .context("failed to shut down game coordinator");
+    served.and(stopped)
+}
+
+/// Serve `router` until `shutdown` resolves, then stop accepting connections and wait up to
+/// `drain_timeout` for in-flight requests and streams to complete. Handlers can read the peer
+/// address through `ConnectInfo<SocketAddr>`.
+pub async fn serve_until_shutdown(
+    listener: TcpListener,
+    router: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+    drain_timeout: Duration,
+) -> Result<()> {
+    let draining = CancellationToken::new();
+    let trigger = draining.clone();
+    let server = axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown.await;
+        trigger.cancel();
+    })
+    .into_future();
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return result.context("server exited with error"),
+        _ = draining.cancelled() => {}
+    }
+
+    info!(
+        target = LOG_TARGET,
+        drain_secs = drain_timeout.as_secs_f32(),
+        "draining in-flight connections"
+    );
+    match tokio::time::timeout(drain_timeout, server).await {
+        Ok(result) => result.context("server exited with error"),
+        Err(_) => {
+            warn!(
+                target = LOG_TARGET,
+                "connections still open after drain timeout; closing them"
+            );
+            Ok(())
+        }
+    }
 }
 
+/// Resolves on Ctrl-C or, on Unix, SIGTERM.
 async fn shutdown_signal() {
-    use tracing::warn;
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            warn!(
+                target = LOG_TARGET,
+                error = %err,
+                "failed to install ctrl-c handler"
+            );
+            std::future::pending::<()>().await;
+        }
+    };
 
-    if let Err(err) = tokio::signal::ctrl_c().await {
-        warn!(
-            target = LOG_TARGET,
-            error = %err,
-            "failed to install ctrl-c handler"
-        );
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                warn!(
+                    target = LOG_TARGET,
+                    error = %err,
+                    "failed to install SIGTERM handler"
+                );
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
     }
     info!(target = LOG_TARGET, "shutdown signal received");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    use axum::response::sse::{Event, Sse};
+    use axum::routing::get;
+    use futures::stream::{self, StreamExt};
+    use tokio::sync::oneshot;
+
+    const STREAM_EVENTS: usize = 3;
+
+    fn slow_stream_router() -> Router {
+        Router::new().route(
+            "/stream",
+            get(|| async {
+                let events = stream::iter(0..STREAM_EVENTS).then(|idx| async move {
+                    if idx > 0 {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                    }
+                    Ok::<_, Infallible>(Event::default().data(format!("event-{idx}")))
+                });
+                Sse::new(events)
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_in_flight_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(serve_until_shutdown(
+            listener,
+            slow_stream_router(),
+            async move {
+                let _ = shutdown_rx.await;
+            },
+            Duration::from_secs(5),
+        ));
+
+        let mut response = reqwest::get(format!("http://{addr}/stream"))
+            .await
+            .expect("open stream");
+        let mut body = String::from_utf8(
+            response
+                .chunk()
+                .await
+                .expect("first chunk")
+                .expect("stream open")
+                .to_vec(),
+        )
+        .expect("utf8");
+
+        // Shut down while the stream still has events to send.
+        shutdown_tx.send(()).expect("server still running");
+        while let Some(chunk) = response.chunk().await.expect("stream chunk") {
+            body.push_str(std::str::from_utf8(&chunk).expect("utf8"));
+        }
+
+        let received: Vec<&str> = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .collect();
+        assert_eq!(received, vec!["event-0", "event-1", "event-2"]);
+        server.await.expect("server task").expect("clean shutdown");
+    }
+}
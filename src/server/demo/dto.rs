@@ -1,11 +1,37 @@
 use ark_ec::CurveGroup;
 use ark_serialize::CanonicalSerialize;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::ledger::snapshot::TableAtShuffling;
 use crate::ledger::types::{GameId, HandId};
 
+use super::DemoConfig;
+
+/// Request payload for POST /games/demo. An empty body creates the default table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateDemoRequest {
+    /// NPCs seated next to the viewer; the default table is used when omitted.
+    #[serde(default)]
+    pub npc_count: Option<usize>,
+
+    /// NPC display names by seat order
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+impl CreateDemoRequest {
+    pub fn into_config(self) -> DemoConfig {
+        let defaults = DemoConfig::default();
+        DemoConfig {
+            npc_count: self.npc_count.unwrap_or(defaults.npc_count),
+            names: self.names,
+            ..defaults
+        }
+    }
+}
+
 /// Response payload for POST /games/demo endpoint.
 #[derive(Serialize)]
 #[serde(bound(serialize = "TableAtShuffling<C>: Serialize"))]
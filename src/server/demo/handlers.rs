@@ -6,25 +6,27 @@ use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use axum::body::Bytes;
 use axum::extract::{Extension, Path};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::Json;
 use futures::StreamExt;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::{error, info, warn};
+use tracing::{error, info};
 use uuid::Uuid;
 
 use crate::curve_absorb::CurveAbsorb;
 use crate::ledger::hash::LedgerHasherSha256;
 use crate::ledger::snapshot::AnyTableSnapshot;
 
-use super::dto::CreateDemoResponse;
+use super::dto::{CreateDemoRequest, CreateDemoResponse};
 use super::phase_execution::{execute_deal_phase, execute_shuffle_phase};
-use super::session_factory::create_demo_session;
+use super::session_factory::create_demo_session_with_clock;
 use super::session_store::DemoSessionStore;
 use super::state::DemoPhase;
 use super::stream_event::DemoStreamEvent;
+use super::DEMO_MAX_PLAYERS;
 use crate::server::error::ApiError;
 
 const LOG_TARGET: &str = "legit_poker::server::demo::handlers";
@@ -33,6 +35,7 @@ const CHANNEL_BUFFER_SIZE: usize = 512;
 /// POST /games/demo - Create a new interactive demo session
 pub async fn create_demo<C>(
     Extension(store): Extension<Arc<DemoSessionStore<C>>>,
+    body: Bytes,
 ) -> Result<Json<CreateDemoResponse<C>>, ApiError>
 where
     C: CurveGroup
@@ -46,13 +49,30 @@ where
     C::BaseField: PrimeField + Send + Sync,
     C::Affine: Absorb + CanonicalSerialize,
 {
-    info!(target: LOG_TARGET, "Creating new demo session");
+    let request = if body.is_empty() {
+        CreateDemoRequest::default()
+    } else {
+        serde_json::from_slice::<CreateDemoRequest>(&body)
+            .map_err(|e| ApiError::bad_request(format!("invalid demo request: {}", e)))?
+    };
+    let config = request.into_config();
+    config
+        .validate(DEMO_MAX_PLAYERS)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    info!(
+        target: LOG_TARGET,
+        npc_count = config.npc_count,
+        "Creating new demo session"
+    );
 
     // Create demo session in blocking task
-    let state = tokio::task::spawn_blocking(create_demo_session::<C>)
-        .await
-        .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?
-        .map_err(|e| ApiError::internal(format!("Demo creation failed: {}", e)))?;
+    let clock = store.clock();
+    let state =
+        tokio::task::spawn_blocking(move || create_demo_session_with_clock::<C>(&config, clock))
+            .await
+            .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?
+            .map_err(|e| ApiError::from(e.context("demo creation failed")))?;
 
     let demo_id = state.id;
     let game_id = state.game_id;
@@ -266,21 +286,14 @@ where
                         "Failed to transition to Complete"
                     );
                 } else {
-                    // Remove session immediately after completion
+                    // Keep the completed session until TTL eviction so it can be replayed
                     let rt = tokio::runtime::Handle::current();
-                    if rt.block_on(store_clone.remove_session(demo_id)) {
-                        info!(
-                            target: LOG_TARGET,
-                            demo_id = %demo_id,
-                            "Demo session removed after completion"
-                        );
-                    } else {
-                        warn!(
-                            target: LOG_TARGET,
-                            demo_id = %demo_id,
-                            "Failed to remove session after completion"
-                        );
-                    }
+                    let session_id = rt.block_on(store_clone.create_session(updated_state));
+                    info!(
+                        target: LOG_TARGET,
+                        demo_id = %session_id,
+                        "Re-inserted completed session into store for replay"
+                    );
                 }
             }
             Err(e) => {
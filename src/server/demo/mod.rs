@@ -11,7 +11,7 @@ pub mod stream_event;
 pub use dto::CreateDemoResponse;
 pub use handlers::{create_demo, stream_deal, stream_shuffle};
 pub use session_store::DemoSessionStore;
-pub use state::{DemoPhase, DemoState};
+pub use state::{DemoPhase, DemoPhaseMachine, DemoState};
 pub use stream_event::DemoStreamEvent;
 
 use std::sync::Arc;
@@ -38,25 +38,79 @@ use crate::ledger::snapshot::{rehydrate_snapshot, AnyTableSnapshot};
 use crate::ledger::types::{GameId, HandId, StateHash};
 use crate::ledger::typestate::{MaybeSaved, Saved};
 use crate::ledger::LobbyService;
+use crate::shuffling::data_structures::DECK_SIZE;
 use crate::shuffling::draw_shuffler_public_key;
 
+use crate::engine::nl::seating::blind_positions;
 use crate::engine::nl::types::{SeatId, TableStakes};
 
-pub(crate) const DEMO_PLAYER_COUNT: usize = 7;
-pub(crate) const NPC_COUNT: usize = DEMO_PLAYER_COUNT - 1;
-pub(crate) const NPC_NAMES: [&str; NPC_COUNT] = [
-    "demo-npc-1",
-    "demo-npc-2",
-    "demo-npc-3",
-    "demo-npc-4",
-    "demo-npc-5",
-    "demo-npc-6",
-];
+pub(crate) const DEFAULT_NPC_COUNT: usize = 6;
+pub(crate) const DEMO_MAX_PLAYERS: i16 = 9;
 pub(crate) const LOBBY_NAME: &str = "Coordinator Demo Table";
 pub(crate) const LOBBY_CURRENCY: &str = "chips";
 pub(crate) const VIEWER_NAME: &str = "demo-viewer";
 pub(crate) const RNG_SEED: u64 = 1337;
 
+/// Table composition for a seeded demo hand: the viewer plus `npc_count` NPCs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoConfig {
+    pub npc_count: usize,
+    /// NPC display names by seat order; seats without a name get `demo-npc-<n>`.
+    pub names: Vec<String>,
+    /// NON-PRODUCTION: deal these card indices in deck order and skip the shuffle entirely,
+    /// so anyone who knows the script knows every card. Only for showcases and screenshots.
+    pub scripted_deck: Option<[u8; DECK_SIZE]>,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        Self {
+            npc_count: DEFAULT_NPC_COUNT,
+            names: Vec::new(),
+            scripted_deck: None,
+        }
+    }
+}
+
+impl DemoConfig {
+    pub fn player_count(&self) -> usize {
+        self.npc_count + 1
+    }
+
+    pub fn validate(&self, max_players: i16) -> Result<()> {
+        if self.npc_count == 0 {
+            return Err(anyhow!("demo requires at least one NPC"));
+        }
+        if self.player_count() > max_players.max(0) as usize {
+            return Err(anyhow!(
+                "demo with {} NPCs needs {} seats but the table allows {}",
+                self.npc_count,
+                self.player_count(),
+                max_players
+            ));
+        }
+        if let Some(script) = &self.scripted_deck {
+            let mut seen = [false; DECK_SIZE];
+            for &card in script {
+                let slot = seen
+                    .get_mut(card as usize)
+                    .ok_or_else(|| anyhow!("scripted deck card {card} is out of range"))?;
+                if std::mem::replace(slot, true) {
+                    return Err(anyhow!("scripted deck contains card {card} more than once"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn npc_name(&self, index: usize) -> String {
+        self.names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("demo-npc-{}", index + 1))
+    }
+}
+
 pub struct SeedDemoResult<C: CurveGroup> {
     pub game_id: GameId,
     pub hand_id: HandId,
@@ -75,6 +129,7 @@ pub async fn seed_demo_hand<C>(
     lobby: Arc<dyn LobbyService<C>>,
     coordinator: &GameCoordinator<C>,
     viewer_public_key: C,
+    config: &DemoConfig,
 ) -> Result<SeedDemoResult<C>>
 where
     C: CurveGroup
@@ -94,7 +149,13 @@ where
     }
 
     let mut rng = StdRng::seed_from_u64(RNG_SEED);
-    let lobby_config = build_lobby_config();
+    let lobby_config = build_lobby_config(config);
+    config.validate(lobby_config.max_players)?;
+    if config.scripted_deck.is_some() {
+        return Err(anyhow!(
+            "scripted decks are only supported by interactive demo sessions"
+        ));
+    }
 
     let host_registration = PlayerRecord {
         display_name: VIEWER_NAME.into(),
@@ -103,22 +164,22 @@ where
         state: MaybeSaved { id: None },
     };
     let metadata = lobby
-        .host_game(host_registration, lobby_config.clone())
+        .host_game(host_registration, lobby_config)
         .await
         .context("failed to host demo game")?;
 
     let players = seat_players::<C>(
         &lobby,
         &metadata,
-        &lobby_config,
         viewer_public_key.clone(),
+        config,
         &mut rng,
     )
     .await?;
-    if players.len() != DEMO_PLAYER_COUNT {
+    if players.len() != config.player_count() {
         return Err(anyhow!(
             "expected {} players but prepared {}",
-            DEMO_PLAYER_COUNT,
+            config.player_count(),
             players.len()
         ));
     }
@@ -128,13 +189,14 @@ where
         return Err(anyhow!("no shufflers registered for demo hand"));
     }
 
-    let hand_config = build_hand_config();
+    let seats: Vec<SeatId> = players.iter().map(|player| player.seat_id).collect();
+    let hand_config = build_hand_config(&seats)?;
     let player_count = players.len();
     let operator = coordinator.operator();
     let hasher = operator.state().hasher();
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: None,
         button_seat: hand_config.button,
         small_blind_seat: hand_config.small_blind_seat,
         big_blind_seat: hand_config.big_blind_seat,
@@ -166,12 +228,15 @@ mod tests {
     use super::*;
     use crate::db::entity::shufflers;
     use crate::db::{connect_to_postgres_db, postgres_test_url};
-    use crate::game::coordinator::{GameCoordinator, GameCoordinatorConfig, ShufflerSecretConfig};
+    use crate::game::coordinator::{
+        GameCoordinator, GameCoordinatorConfig, ShufflerSecretConfig, DEFAULT_MAX_CONCURRENT_HANDS,
+    };
     use crate::ledger::serialization::serialize_curve_bytes;
     use crate::ledger::store::{SeaOrmEventStore, SeaOrmSnapshotStore, SnapshotStore};
-    use crate::ledger::verifier::LedgerVerifier;
+    use crate::ledger::verifier::{LedgerVerifier, SchnorrSignatureValidator};
     use crate::ledger::LobbyService;
     use crate::ledger::{LedgerState, LobbyServiceFactory};
+    use crate::shuffler::shuffler_signing_params;
     use anyhow::Result;
     use ark_bn254::G1Projective as TestCurve;
     use ark_ec::PrimeGroup;
@@ -226,8 +291,11 @@ mod tests {
             Arc::new(SeaOrmEventStore::<TestCurve>::new(conn.clone()));
         let snapshot_store: Arc<dyn SnapshotStore<TestCurve>> =
             Arc::new(SeaOrmSnapshotStore::<TestCurve>::new(conn.clone()));
-        let verifier: Arc<dyn crate::ledger::Verifier<TestCurve> + Send + Sync> =
-            Arc::new(LedgerVerifier::new(Arc::clone(&state)));
+        let signing_params = shuffler_signing_params::<TestCurve>().expect("schnorr params");
+        let signature = SchnorrSignatureValidator::<TestCurve>::new(signing_params.clone());
+        let verifier: Arc<dyn crate::ledger::Verifier<TestCurve> + Send + Sync> = Arc::new(
+            LedgerVerifier::with_signature_validator(Arc::clone(&state), Arc::new(signature)),
+        );
 
         let mut supabase_cfg = crate::game::coordinator::SupabaseRealtimeClientConfig::new(
             Url::parse("ws://localhost:12345/socket").expect("valid url"),
@@ -250,7 +318,11 @@ mod tests {
             supabase: supabase_cfg,
             shufflers: vec![shuffler_config],
             submit_channel_capacity: 32,
+            max_concurrent_hands: DEFAULT_MAX_CONCURRENT_HANDS,
             rng_seed: Some([1u8; 32]),
+            signing_params,
+            action_deadlines: None,
+            nonce_source: None,
         };
 
         let coordinator = match GameCoordinator::spawn(coordinator_config).await {
@@ -267,7 +339,13 @@ mod tests {
         let viewer_secret = <TestCurve as PrimeGroup>::ScalarField::rand(&mut rng);
         let viewer_public = TestCurve::generator() * viewer_secret;
 
-        let seed_result = seed_demo_hand(Arc::clone(&lobby), &coordinator, viewer_public).await?;
+        let seed_result = seed_demo_hand(
+            Arc::clone(&lobby),
+            &coordinator,
+            viewer_public,
+            &DemoConfig::default(),
+        )
+        .await?;
 
         let tip_snapshot = coordinator
             .state()
@@ -283,6 +361,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn demo_config_scales_from_heads_up_to_full_ring() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let heads_up = DemoConfig {
+            npc_count: 1,
+            names: vec!["villain".into()],
+            ..DemoConfig::default()
+        };
+        heads_up.validate(DEMO_MAX_PLAYERS).expect("heads-up fits");
+        let npcs = generate_npc_specs::<TestCurve>(&heads_up, &mut rng).expect("npc specs");
+        assert_eq!(npcs.len(), 1);
+        assert_eq!(npcs[0].display_name, "villain");
+        assert_eq!(build_lobby_config(&heads_up).min_players_to_start, 2);
+
+        let full_ring = DemoConfig {
+            npc_count: DEMO_MAX_PLAYERS as usize - 1,
+            ..DemoConfig::default()
+        };
+        full_ring
+            .validate(DEMO_MAX_PLAYERS)
+            .expect("full ring fits");
+        let npcs = generate_npc_specs::<TestCurve>(&full_ring, &mut rng).expect("npc specs");
+        assert_eq!(npcs.len() + 1, DEMO_MAX_PLAYERS as usize);
+        assert_eq!(npcs[7].display_name, "demo-npc-8");
+
+        let oversized = DemoConfig {
+            npc_count: DEMO_MAX_PLAYERS as usize,
+            ..DemoConfig::default()
+        };
+        assert!(oversized.validate(DEMO_MAX_PLAYERS).is_err());
+    }
+
     async fn reset_database(conn: &DatabaseConnection) -> Result<()> {
         conn.execute(Statement::from_string(
             DbBackend::Postgres,
@@ -309,8 +420,8 @@ mod tests {
 async fn seat_players<C>(
     lobby: &Arc<dyn LobbyService<C>>,
     metadata: &GameMetadata<C>,
-    lobby_config: &GameLobbyConfig,
     viewer_public_key: C,
+    config: &DemoConfig,
     rng: &mut StdRng,
 ) -> Result<Vec<PlayerSeatSnapshot<C>>>
 where
@@ -325,7 +436,7 @@ where
     C::BaseField: PrimeField + CanonicalSerialize + Send + Sync,
     C::Affine: Absorb,
 {
-    let mut snapshots = Vec::with_capacity(DEMO_PLAYER_COUNT);
+    let mut snapshots = Vec::with_capacity(config.player_count());
 
     let host_registration = PlayerRecord {
         display_name: metadata.host.display_name.clone(),
@@ -336,18 +447,22 @@ where
         },
     };
     let host_join = lobby
-        .join_game(&metadata.record, host_registration, Some(0))
+        .join_game(&metadata.record, host_registration, Some(0), None)
         .await
         .context("failed to seat viewer in demo game")?;
 
     snapshots.push(PlayerSeatSnapshot::new(
         host_join.player.clone(),
         0,
-        lobby_config.buy_in,
+        host_join.buy_in,
+        host_join.buy_in,
         viewer_public_key.clone(),
     ));
 
-    for (idx, spec) in generate_npc_specs::<C>(rng)?.into_iter().enumerate() {
+    for (idx, spec) in generate_npc_specs::<C>(config, rng)?
+        .into_iter()
+        .enumerate()
+    {
         let seat = (idx + 1) as SeatId;
         let record = PlayerRecord {
             display_name: spec.display_name,
@@ -356,14 +471,15 @@ where
             state: MaybeSaved { id: None },
         };
         let join = lobby
-            .join_game(&metadata.record, record, Some(seat))
+            .join_game(&metadata.record, record, Some(seat), None)
             .await
             .with_context(|| format!("failed to seat NPC at seat {}", seat))?;
 
         snapshots.push(PlayerSeatSnapshot::new(
             join.player.clone(),
             seat,
-            lobby_config.buy_in,
+            join.buy_in,
+            join.buy_in,
             spec.public_key.clone(),
         ));
     }
@@ -426,51 +542,69 @@ pub(crate) struct NpcSpec<C> {
     pub(crate) public_key: C,
 }
 
-pub(crate) fn generate_npc_specs<C>(rng: &mut StdRng) -> Result<Vec<NpcSpec<C>>>
+pub(crate) fn generate_npc_specs<C>(
+    config: &DemoConfig,
+    rng: &mut StdRng,
+) -> Result<Vec<NpcSpec<C>>>
 where
     C: CurveGroup + CanonicalSerialize,
 {
-    let mut specs = Vec::with_capacity(NPC_COUNT);
-    for name in NPC_NAMES {
+    let mut specs = Vec::with_capacity(config.npc_count);
+    for index in 0..config.npc_count {
         let (_, public_key) = draw_shuffler_public_key::<C, _>(rng);
         specs.push(NpcSpec {
-            display_name: name.to_string(),
+            display_name: config.npc_name(index),
             public_key,
         });
     }
     Ok(specs)
 }
 
-pub(crate) fn build_lobby_config() -> GameLobbyConfig {
+pub(crate) fn build_lobby_config(config: &DemoConfig) -> GameLobbyConfig {
     GameLobbyConfig {
         stakes: TableStakes {
             small_blind: 50,
             big_blind: 100,
             ante: 0,
         },
-        max_players: 9,
+        max_players: DEMO_MAX_PLAYERS,
         rake_bps: 0,
-        name: LOBBY_NAME.into(),
+        // A viewer hosts every demo they start, and a host's game names must be unique.
+        name: format!("{LOBBY_NAME} {}", uuid::Uuid::new_v4()),
         currency: LOBBY_CURRENCY.into(),
-        buy_in: 10_000,
-        min_players_to_start: DEMO_PLAYER_COUNT as i16,
+        min_buy_in: 4_000,
+        max_buy_in: 10_000,
+        min_players_to_start: config.player_count() as i16,
+        min_shufflers: 1,
         check_raise_allowed: true,
+        max_bet_bb: None,
         action_time_limit: Duration::from_secs(30),
     }
 }
 
-pub(crate) fn build_hand_config() -> crate::engine::nl::types::HandConfig {
-    crate::engine::nl::types::HandConfig {
+/// Hand configuration with the button on the lowest occupied seat and the blinds after it.
+pub(crate) fn build_hand_config(seats: &[SeatId]) -> Result<crate::engine::nl::types::HandConfig> {
+    let button = seats
+        .iter()
+        .copied()
+        .min()
+        .ok_or_else(|| anyhow!("demo hand has no seated players"))?;
+    let (small_blind_seat, big_blind_seat) = blind_positions(seats, button)
+        .ok_or_else(|| anyhow!("demo hand needs at least two seated players"))?;
+    Ok(crate::engine::nl::types::HandConfig {
         stakes: TableStakes {
             small_blind: 50,
             big_blind: 100,
             ante: 0,
         },
-        button: 0,
-        small_blind_seat: 1,
-        big_blind_seat: 2,
+        button,
+        small_blind_seat,
+        big_blind_seat,
         check_raise_allowed: true,
-    }
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+    })
 }
 
 pub async fn rehydrate_commence_outcome<C>(
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, ensure, Result};
 use ark_crypto_primitives::crh::sha256::Sha256;
 use ark_crypto_primitives::signature::schnorr::Schnorr;
 use ark_crypto_primitives::sponge::Absorb;
@@ -6,20 +6,39 @@ use ark_ec::CurveGroup;
 use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use tokio::sync::mpsc;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::curve_absorb::CurveAbsorb;
 use crate::ledger::hash::LedgerHasher;
-use crate::ledger::snapshot::AnyTableSnapshot;
+use crate::ledger::snapshot::{AnyTableSnapshot, PhaseDealing, PhaseShuffling, TableSnapshot};
+use crate::ledger::transition::promote_to_dealing;
 use crate::shuffler::{run_dealing_phase, run_shuffling_phase};
 
 use super::state::DemoState;
 use super::stream_event::DemoStreamEvent;
 
 const LOG_TARGET: &str = "legit_poker::server::demo::phase_execution";
+const RECORD_BUFFER_SIZE: usize = 4096;
 
 type Schnorr254<C> = Schnorr<C, Sha256>;
 
+/// Promotes a shuffling snapshot straight to dealing, dealing its initial deck in order.
+///
+/// Only for scripted demo decks: no shuffle is applied, so whoever encrypted the deck knows
+/// every card.
+fn promote_unshuffled_to_dealing<C: CurveGroup>(
+    table: TableSnapshot<PhaseShuffling, C>,
+) -> Result<TableSnapshot<PhaseDealing, C>> {
+    ensure!(
+        table.shuffling.steps.is_empty(),
+        "deck has already been shuffled"
+    );
+    match promote_to_dealing(table)? {
+        AnyTableSnapshot::Dealing(dealing) => Ok(dealing),
+        _ => bail!("unshuffled promotion did not produce a dealing snapshot"),
+    }
+}
+
 /// Execute the shuffling phase for a demo session.
 /// Consumes the DemoState and returns an updated version with the dealing snapshot.
 pub fn execute_shuffle_phase<C>(
@@ -58,14 +77,29 @@ where
         }
     };
 
+    if state.scripted_deck {
+        let dealing_snapshot = promote_unshuffled_to_dealing(shuffling_snapshot)?;
+        warn!(
+            target: LOG_TARGET,
+            demo_id = %state.id,
+            "Scripted deck in use; skipped shuffle phase"
+        );
+        state.snapshot = AnyTableSnapshot::Dealing(dealing_snapshot);
+        return Ok(state);
+    }
+
     // Execute shuffle phase
-    let dealing_snapshot = run_shuffling_phase(
-        shuffling_snapshot,
-        &state.shuffler_engines,
-        &mut state.shuffler_states,
-        hasher,
-        event_tx,
-    )?;
+    let (result, recorded) = run_recorded(event_tx, |tx| {
+        run_shuffling_phase(
+            shuffling_snapshot,
+            &state.shuffler_engines,
+            &mut state.shuffler_states,
+            hasher,
+            tx,
+        )
+    });
+    state.event_log.extend(recorded);
+    let dealing_snapshot = result?;
 
     info!(
         target: LOG_TARGET,
@@ -119,16 +153,20 @@ where
     };
 
     // Execute dealing phase
-    run_dealing_phase(
-        &mut dealing_snapshot,
-        &state.shuffler_engines,
-        &state.player_records,
-        &state.player_keys,
-        &state.aggregated_public_key,
-        &mut state.rng,
-        hasher,
-        Some(event_tx),
-    )?;
+    let (result, recorded) = run_recorded(event_tx, |tx| {
+        run_dealing_phase(
+            &mut dealing_snapshot,
+            &state.shuffler_engines,
+            &state.player_records,
+            &state.player_keys,
+            &state.aggregated_public_key,
+            &mut state.rng,
+            hasher,
+            Some(tx),
+        )
+    });
+    state.event_log.extend(recorded);
+    result?;
 
     info!(
         target: LOG_TARGET,
@@ -141,3 +179,41 @@ where
 
     Ok(state)
 }
+
+/// Run a phase while forwarding its events to `event_tx` and keeping a copy of each one
+/// so the session can be replayed later.
+///
+/// Recording never waits on the client. Forwarding happens on its own thread and waits for
+/// room in `event_tx`, so a slow client sees every event, just later; it stops only once the
+/// client disconnects. Returns after the client has been sent every event or has gone.
+fn run_recorded<C, T>(
+    event_tx: &mpsc::Sender<DemoStreamEvent<C>>,
+    run: impl FnOnce(&mpsc::Sender<DemoStreamEvent<C>>) -> Result<T>,
+) -> (Result<T>, Vec<DemoStreamEvent<C>>)
+where
+    C: CurveGroup + CanonicalSerialize,
+{
+    let (record_tx, mut record_rx) = mpsc::channel(RECORD_BUFFER_SIZE);
+    let (live_tx, live_rx) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            for event in live_rx {
+                if event_tx.blocking_send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        let recorder = scope.spawn(move || {
+            let mut recorded = Vec::new();
+            while let Some(event) = record_rx.blocking_recv() {
+                let _ = live_tx.send(event.clone());
+                recorded.push(event);
+            }
+            recorded
+        });
+        let result = run(&record_tx);
+        drop(record_tx);
+        let recorded = recorder.join().expect("demo event recorder panicked");
+        (result, recorded)
+    })
+}
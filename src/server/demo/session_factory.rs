@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use ark_crypto_primitives::crh::sha256::Sha256;
 use ark_crypto_primitives::signature::schnorr::Schnorr;
 use ark_crypto_primitives::sponge::Absorb;
@@ -9,10 +9,12 @@ use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::rngs::StdRng;
 use rand::{RngCore, SeedableRng};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::clock::{Clock, SystemClock};
 use crate::curve_absorb::CurveAbsorb;
+use crate::engine::nl::seating::blind_positions;
 use crate::engine::nl::types::{HandConfig, PlayerId, SeatId, TableStakes};
 use crate::ledger::lobby::service::LobbyServiceFactory;
 use crate::ledger::lobby::types::{
@@ -23,18 +25,39 @@ use crate::ledger::types::{GameId, HandId, ShufflerId};
 use crate::ledger::typestate::MaybeSaved;
 use crate::ledger::LobbyService;
 use crate::shuffler::{ShufflerEngine, ShufflerHandState};
+use crate::shuffling::data_structures::{ElGamalCiphertext, DECK_SIZE};
 
 use super::state::DemoState;
+use super::{DemoConfig, DEMO_MAX_PLAYERS};
 
 const LOG_TARGET: &str = "legit_poker::server::demo::session_factory";
 const NUM_SHUFFLERS: usize = 5;
-const NUM_PLAYERS: usize = 7;
 
 type Schnorr254<C> = Schnorr<C, Sha256>;
 
 /// Create a new demo session with all necessary setup.
 /// Returns a DemoState in Initialized phase ready for shuffling.
-pub fn create_demo_session<C>() -> Result<DemoState<C>>
+pub fn create_demo_session<C>(config: &DemoConfig) -> Result<DemoState<C>>
+where
+    C: CurveGroup
+        + CanonicalSerialize
+        + CanonicalDeserialize
+        + CurveAbsorb<C::BaseField>
+        + Send
+        + Sync
+        + 'static,
+    C::ScalarField: PrimeField + UniformRand + Absorb + CanonicalSerialize + Send + Sync + Clone,
+    C::BaseField: PrimeField + Send + Sync,
+    C::Affine: Absorb + CanonicalSerialize,
+{
+    create_demo_session_with_clock(config, Arc::new(SystemClock))
+}
+
+/// [`create_demo_session`] with the session's timestamps read from `clock`.
+pub fn create_demo_session_with_clock<C>(
+    config: &DemoConfig,
+    clock: Arc<dyn Clock>,
+) -> Result<DemoState<C>>
 where
     C: CurveGroup
         + CanonicalSerialize
@@ -48,6 +71,9 @@ where
     C::Affine: Absorb + CanonicalSerialize,
 {
     let demo_id = Uuid::new_v4();
+    let num_players = config.player_count();
+    let lobby_config = build_lobby_config(num_players);
+    config.validate(lobby_config.max_players)?;
     info!(
         target: LOG_TARGET,
         demo_id = %demo_id,
@@ -74,19 +100,18 @@ where
     // Step 4: Generate player identities
     info!(
         target: LOG_TARGET,
-        num_players = NUM_PLAYERS,
+        num_players,
         "👥 Generating player identities"
     );
-    let player_keys = generate_player_keys(&mut rng, NUM_PLAYERS);
+    let player_keys = generate_player_keys(&mut rng, num_players);
     let player_records = create_player_records(&player_keys);
 
     // Step 5: Host a game (using player 0 as host)
     info!(target: LOG_TARGET, "🎮 Hosting game");
     let host = player_records[0].clone();
 
-    let lobby_config = build_lobby_config();
-    let metadata = tokio::runtime::Handle::current()
-        .block_on(lobby.host_game(host, lobby_config.clone()))?;
+    let metadata =
+        tokio::runtime::Handle::current().block_on(lobby.host_game(host, lobby_config.clone()))?;
 
     let game_id: GameId = metadata.record.state.id;
 
@@ -100,9 +125,11 @@ where
         let cfg = ShufflerRegistrationConfig {
             sequence: Some(idx as u16),
         };
-        let output = tokio::runtime::Handle::current().block_on(
-            lobby.register_shuffler(&metadata.record, shuffler_record.clone(), cfg),
-        )?;
+        let output = tokio::runtime::Handle::current().block_on(lobby.register_shuffler(
+            &metadata.record,
+            shuffler_record.clone(),
+            cfg,
+        ))?;
         debug!(
             target: LOG_TARGET,
             shuffler_index = idx,
@@ -114,24 +141,28 @@ where
     // Step 7: Join players to the game
     info!(
         target: LOG_TARGET,
-        num_players = NUM_PLAYERS,
+        num_players,
         "👥 Joining players"
     );
-    let mut saved_player_records = Vec::with_capacity(NUM_PLAYERS);
+    let mut saved_player_records = Vec::with_capacity(num_players);
     for (idx, player_record) in player_records.into_iter().enumerate() {
         let seat_id = idx as SeatId;
-        let output = tokio::runtime::Handle::current().block_on(
-            lobby.join_game(&metadata.record, player_record.clone(), Some(seat_id)),
-        )?;
+        let output = tokio::runtime::Handle::current().block_on(lobby.join_game(
+            &metadata.record,
+            player_record.clone(),
+            Some(seat_id),
+            None,
+        ))?;
         saved_player_records.push((output.player, seat_id));
     }
 
     // Step 8: Commence the game
     info!(target: LOG_TARGET, "🚀 Commencing game");
-    let hand_config = build_hand_config();
+    let seats: Vec<SeatId> = saved_player_records.iter().map(|(_, seat)| *seat).collect();
+    let hand_config = build_hand_config(&seats)?;
     let params = CommenceGameParams {
         game_id: metadata.record.state.id,
-        hand_no: 1,
+        hand_no: None,
         button_seat: hand_config.button,
         small_blind_seat: hand_config.small_blind_seat,
         big_blind_seat: hand_config.big_blind_seat,
@@ -139,11 +170,22 @@ where
         player_stacks: None,
     };
 
-    let outcome = tokio::runtime::Handle::current()
-        .block_on(lobby.commence_game(hasher.as_ref(), params))?;
+    let outcome =
+        tokio::runtime::Handle::current().block_on(lobby.commence_game(hasher.as_ref(), params))?;
 
     let hand_id: HandId = outcome.hand.state.id;
-    let initial_snapshot = outcome.initial_snapshot.clone();
+    let mut initial_snapshot = outcome.initial_snapshot.clone();
+    if let Some(script) = &config.scripted_deck {
+        warn!(
+            target: LOG_TARGET,
+            demo_id = %demo_id,
+            "🃏 Using scripted deck; this hand will not be shuffled"
+        );
+        let deck = encrypt_scripted_deck(script, &aggregated_public_key, &mut rng);
+        initial_snapshot.shuffling.initial_deck = deck.clone();
+        initial_snapshot.shuffling.final_deck = deck;
+        initial_snapshot.initialize_hash(hasher.as_ref());
+    }
 
     // Step 9: Initialize shuffler hand states
     info!(
@@ -169,7 +211,7 @@ where
     }
 
     // Step 10: Create DemoState
-    let demo_state = DemoState::new(
+    let mut demo_state = DemoState::new(
         demo_id,
         game_id,
         hand_id,
@@ -181,7 +223,9 @@ where
         player_keys,
         aggregated_public_key,
         rng,
+        clock,
     );
+    demo_state.scripted_deck = config.scripted_deck.is_some();
 
     info!(
         target: LOG_TARGET,
@@ -263,7 +307,23 @@ where
         .collect()
 }
 
-fn build_lobby_config() -> GameLobbyConfig {
+fn encrypt_scripted_deck<C>(
+    script: &[u8; DECK_SIZE],
+    aggregated_public_key: &C,
+    rng: &mut StdRng,
+) -> [ElGamalCiphertext<C>; DECK_SIZE]
+where
+    C: CurveGroup,
+    C::ScalarField: PrimeField + UniformRand,
+{
+    std::array::from_fn(|position| {
+        let message = C::ScalarField::from(script[position] as u64);
+        let randomness = C::ScalarField::rand(rng);
+        ElGamalCiphertext::encrypt_scalar(message, randomness, aggregated_public_key.clone())
+    })
+}
+
+fn build_lobby_config(num_players: usize) -> GameLobbyConfig {
     use std::time::Duration;
 
     GameLobbyConfig {
@@ -272,27 +332,115 @@ fn build_lobby_config() -> GameLobbyConfig {
             small_blind: 50,
             big_blind: 100,
         },
-        max_players: 9,
+        max_players: DEMO_MAX_PLAYERS,
         rake_bps: 500,
         name: "High Stakes ZK Poker".to_string(),
         currency: "CHIPS".to_string(),
-        buy_in: 10_000,
-        min_players_to_start: 6,
+        min_buy_in: 4_000,
+        max_buy_in: 10_000,
+        min_players_to_start: num_players as i16,
+        min_shufflers: NUM_SHUFFLERS as i16,
         check_raise_allowed: true,
+        max_bet_bb: None,
         action_time_limit: Duration::from_secs(30),
     }
 }
 
-fn build_hand_config() -> HandConfig {
-    HandConfig {
+/// Hand configuration with the button on the lowest occupied seat and the blinds after it.
+fn build_hand_config(seats: &[SeatId]) -> Result<HandConfig> {
+    let button = seats
+        .iter()
+        .copied()
+        .min()
+        .ok_or_else(|| anyhow!("demo hand has no seated players"))?;
+    let (small_blind_seat, big_blind_seat) = blind_positions(seats, button)
+        .ok_or_else(|| anyhow!("demo hand needs at least two seated players"))?;
+    Ok(HandConfig {
         stakes: TableStakes {
             ante: 10,
             small_blind: 50,
             big_blind: 100,
         },
-        button: 0,
-        small_blind_seat: 1,
-        big_blind_seat: 2,
+        button,
+        small_blind_seat,
+        big_blind_seat,
         check_raise_allowed: true,
+        hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+        max_bet_bb: None,
+        dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::hash::LedgerHasherSha256;
+    use crate::server::demo::phase_execution::{execute_deal_phase, execute_shuffle_phase};
+    use crate::server::demo::stream_event::DemoStreamEvent;
+    use crate::showdown::{idx_of, Suit};
+    use ark_bn254::G1Projective as Curve;
+    use tokio::sync::mpsc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn heads_up_demo_puts_the_small_blind_on_the_button() {
+        let config = DemoConfig {
+            npc_count: 1,
+            ..DemoConfig::default()
+        };
+        let state = tokio::task::spawn_blocking(move || create_demo_session::<Curve>(&config))
+            .await
+            .expect("demo task")
+            .expect("heads-up demo is created");
+
+        let AnyTableSnapshot::Shuffling(snapshot) = &state.snapshot else {
+            panic!("expected shuffling snapshot");
+        };
+        assert_eq!(snapshot.cfg.button, 0);
+        assert_eq!(snapshot.cfg.small_blind_seat, 0);
+        assert_eq!(snapshot.cfg.big_blind_seat, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn scripted_deck_deals_expected_viewer_hole_cards() {
+        let aces = [idx_of(14, Suit::Spades), idx_of(14, Suit::Hearts)];
+        let mut script: [u8; DECK_SIZE] = std::array::from_fn(|i| i as u8);
+        // Button on seat 0 with seven players puts the viewer's holes at deck positions 6 and 13.
+        script.swap(6, aces[0] as usize);
+        script.swap(13, aces[1] as usize);
+        let config = DemoConfig {
+            scripted_deck: Some(script),
+            ..DemoConfig::default()
+        };
+
+        let (event_tx, mut event_rx) = mpsc::channel(16 * 1024);
+        tokio::task::spawn_blocking(move || {
+            let hasher = LedgerHasherSha256;
+            let state = create_demo_session::<Curve>(&config)?;
+            let state = execute_shuffle_phase(state, &hasher, &event_tx)?;
+            execute_deal_phase(state, &hasher, &event_tx)
+        })
+        .await
+        .expect("demo task")
+        .expect("scripted demo deals");
+
+        let mut viewer_cards = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if let DemoStreamEvent::HoleCardsDecrypted {
+                seat: 0,
+                hole_id,
+                card,
+                ..
+            } = event
+            {
+                viewer_cards.push((hole_id, card));
+            }
+        }
+        viewer_cards.sort_by_key(|(hole_id, _)| *hole_id);
+        let expected = aces
+            .iter()
+            .enumerate()
+            .map(|(hole, &ace)| (hole as u8, crate::showdown::decode_card(ace)))
+            .collect::<Vec<_>>();
+        assert_eq!(viewer_cards, expected);
     }
 }
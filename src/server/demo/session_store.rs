@@ -11,10 +11,12 @@ use tokio::task::JoinHandle;
 use tracing::{debug, info};
 use uuid::Uuid;
 
+use crate::clock::{Clock, SystemClock};
 use crate::curve_absorb::CurveAbsorb;
 use crate::ledger::types::{GameId, HandId};
 
 use super::state::{DemoPhase, DemoState};
+use super::stream_event::DemoStreamEvent;
 
 const LOG_TARGET: &str = "legit_poker::server::demo::session_store";
 const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60); // 5 minutes
@@ -36,6 +38,7 @@ where
 {
     sessions: Arc<RwLock<HashMap<Uuid, DemoState<C>>>>,
     ttl: Duration,
+    clock: Arc<dyn Clock>,
     cleanup_handle: Option<JoinHandle<()>>,
 }
 
@@ -59,6 +62,11 @@ where
 
     /// Create a new session store with custom TTL.
     pub fn with_ttl(ttl: Duration) -> Self {
+        Self::with_clock(ttl, Arc::new(SystemClock))
+    }
+
+    /// A store whose sessions age by `clock` rather than the system clock.
+    pub fn with_clock(ttl: Duration, clock: Arc<dyn Clock>) -> Self {
         let sessions = Arc::new(RwLock::new(HashMap::new()));
         let cleanup_handle = Self::start_cleanup_task(Arc::clone(&sessions), ttl);
 
@@ -72,10 +80,17 @@ where
         Self {
             sessions,
             ttl,
+            clock,
             cleanup_handle: Some(cleanup_handle),
         }
     }
 
+    /// The clock new sessions should be stamped with, see
+    /// [`create_demo_session_with_clock`](super::session_factory::create_demo_session_with_clock).
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock)
+    }
+
     /// Start background cleanup task that runs every CLEANUP_INTERVAL.
     fn start_cleanup_task(
         sessions: Arc<RwLock<HashMap<Uuid, DemoState<C>>>>,
@@ -85,37 +100,17 @@ where
             let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
             loop {
                 interval.tick().await;
-
-                let mut sessions_lock = sessions.write().await;
-                let initial_count = sessions_lock.len();
-
-                sessions_lock.retain(|id, state| {
-                    let expired = state.is_expired(ttl);
-                    if expired {
-                        debug!(
-                            target: LOG_TARGET,
-                            demo_id = %id,
-                            phase = ?state.phase,
-                            age_secs = state.created_at.elapsed().as_secs(),
-                            "Evicting expired demo session"
-                        );
-                    }
-                    !expired
-                });
-
-                let evicted_count = initial_count - sessions_lock.len();
-                if evicted_count > 0 {
-                    info!(
-                        target: LOG_TARGET,
-                        evicted_count,
-                        remaining_count = sessions_lock.len(),
-                        "Cleaned up expired demo sessions"
-                    );
-                }
+                evict_expired(&sessions, ttl).await;
             }
         })
     }
 
+    /// Drop every session that has not been accessed within the TTL. Returns how many were
+    /// evicted.
+    pub async fn evict_expired(&self) -> usize {
+        evict_expired(&self.sessions, self.ttl).await
+    }
+
     /// Insert a new demo session.
     pub async fn create_session(&self, state: DemoState<C>) -> Uuid {
         let id = state.id;
@@ -169,6 +164,11 @@ where
         }
     }
 
+    /// Reconstruct the stream events a fresh client would have seen for a session, in order.
+    pub async fn replay(&self, id: Uuid) -> Option<Vec<DemoStreamEvent<C>>> {
+        self.with_session(id, |state| state.event_log.clone()).await
+    }
+
     /// Update an existing demo session.
     pub async fn update_session(&self, state: DemoState<C>) -> Result<(), String> {
         let mut sessions = self.sessions.write().await;
@@ -227,6 +227,48 @@ where
     }
 }
 
+async fn evict_expired<C>(sessions: &RwLock<HashMap<Uuid, DemoState<C>>>, ttl: Duration) -> usize
+where
+    C: CurveGroup
+        + CanonicalSerialize
+        + CanonicalDeserialize
+        + CurveAbsorb<C::BaseField>
+        + Send
+        + Sync
+        + 'static,
+    C::ScalarField: PrimeField + UniformRand + Absorb + CanonicalSerialize + Send + Sync + Clone,
+    C::BaseField: PrimeField + Send + Sync,
+    C::Affine: Absorb + CanonicalSerialize,
+{
+    let mut sessions_lock = sessions.write().await;
+    let initial_count = sessions_lock.len();
+
+    sessions_lock.retain(|id, state| {
+        let expired = state.is_expired(ttl);
+        if expired {
+            debug!(
+                target: LOG_TARGET,
+                demo_id = %id,
+                phase = ?state.phase,
+                age_secs = state.age().as_secs(),
+                "Evicting expired demo session"
+            );
+        }
+        !expired
+    });
+
+    let evicted_count = initial_count - sessions_lock.len();
+    if evicted_count > 0 {
+        info!(
+            target: LOG_TARGET,
+            evicted_count,
+            remaining_count = sessions_lock.len(),
+            "Cleaned up expired demo sessions"
+        );
+    }
+    evicted_count
+}
+
 impl<C> Drop for DemoSessionStore<C>
 where
     C: CurveGroup
@@ -266,3 +308,71 @@ where
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::ledger::hash::LedgerHasherSha256;
+    use crate::server::demo::phase_execution::{execute_deal_phase, execute_shuffle_phase};
+    use crate::server::demo::session_factory::{
+        create_demo_session, create_demo_session_with_clock,
+    };
+    use crate::server::demo::DemoConfig;
+    use ark_bn254::G1Projective as Curve;
+    use tokio::sync::mpsc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replay_matches_live_event_sequence() {
+        let store = DemoSessionStore::<Curve>::new();
+        let (event_tx, mut event_rx) = mpsc::channel(16 * 1024);
+
+        let state = tokio::task::spawn_blocking(move || {
+            let hasher = LedgerHasherSha256;
+            let state = create_demo_session::<Curve>(&DemoConfig::default())?;
+            let state = execute_shuffle_phase(state, &hasher, &event_tx)?;
+            execute_deal_phase(state, &hasher, &event_tx)
+        })
+        .await
+        .expect("demo task")
+        .expect("demo phases succeed");
+        let id = store.create_session(state).await;
+
+        let mut live = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            live.push(serde_json::to_value(&event).expect("serialize live event"));
+        }
+        assert!(!live.is_empty());
+
+        let replayed = store
+            .replay(id)
+            .await
+            .expect("session exists")
+            .iter()
+            .map(|event| serde_json::to_value(event).expect("serialize replayed event"))
+            .collect::<Vec<_>>();
+        assert_eq!(replayed, live);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn idle_session_is_evicted_once_the_ttl_passes() {
+        let clock = MockClock::new();
+        let store = DemoSessionStore::<Curve>::with_clock(DEFAULT_TTL, Arc::new(clock.clone()));
+        let session_clock = store.clock();
+        let state = tokio::task::spawn_blocking(move || {
+            create_demo_session_with_clock::<Curve>(&DemoConfig::default(), session_clock)
+        })
+        .await
+        .expect("demo task")
+        .expect("demo session is created");
+        let id = store.create_session(state).await;
+
+        clock.advance(DEFAULT_TTL - Duration::from_secs(1));
+        assert!(store.get_session_data(id).await.is_some());
+
+        clock.advance(DEFAULT_TTL);
+        assert_eq!(store.evict_expired().await, 0);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(store.evict_expired().await, 1);
+        assert_eq!(store.session_count().await, 0);
+    }
+}
@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ark_crypto_primitives::crh::sha256::Sha256;
 use ark_crypto_primitives::signature::schnorr::Schnorr;
@@ -9,9 +9,12 @@ use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::sync::Notify;
+use tracing::debug;
 use uuid::Uuid;
 
+use crate::clock::Clock;
 use crate::curve_absorb::CurveAbsorb;
 use crate::engine::nl::types::{PlayerId, SeatId};
 use crate::ledger::lobby::types::PlayerRecord;
@@ -20,6 +23,10 @@ use crate::ledger::types::{GameId, HandId};
 use crate::ledger::typestate::Saved;
 use crate::shuffler::{ShufflerEngine, ShufflerHandState};
 
+use super::stream_event::DemoStreamEvent;
+
+const LOG_TARGET: &str = "legit_poker::server::demo::state";
+
 type Schnorr254<C> = Schnorr<C, Sha256>;
 
 /// Phase of the interactive demo session.
@@ -39,16 +46,95 @@ pub enum DemoPhase {
 }
 
 impl DemoPhase {
+    /// The phase that follows this one, or `None` once the demo is complete.
+    pub fn next(&self) -> Option<DemoPhase> {
+        use DemoPhase::*;
+        match self {
+            Initialized => Some(Shuffling),
+            Shuffling => Some(ShuffleComplete),
+            ShuffleComplete => Some(Dealing),
+            Dealing => Some(Complete),
+            Complete => None,
+        }
+    }
+
     /// Check if transition from current phase to target phase is valid.
     pub fn can_transition_to(&self, target: DemoPhase) -> bool {
-        use DemoPhase::*;
-        matches!(
-            (self, target),
-            (Initialized, Shuffling)
-                | (Shuffling, ShuffleComplete)
-                | (ShuffleComplete, Dealing)
-                | (Dealing, Complete)
-        )
+        self.next() == Some(target)
+    }
+}
+
+/// Milestones reached when the demo enters a phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemoPhaseEvent {
+    ShuffleStarted,
+    ShuffleFinished,
+    DealStarted,
+    HandCompleted,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DemoPhaseError {
+    #[error("Invalid phase transition from {from:?} to {to:?}")]
+    InvalidTransition { from: DemoPhase, to: DemoPhase },
+    #[error("Demo is already complete")]
+    AlreadyComplete,
+}
+
+/// Result of advancing the demo by one phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoPhaseTransition {
+    pub from: DemoPhase,
+    pub to: DemoPhase,
+    pub events: Vec<DemoPhaseEvent>,
+}
+
+/// Drives a demo through its phases in order, rejecting skipped or repeated phases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DemoPhaseMachine {
+    phase: DemoPhase,
+}
+
+impl Default for DemoPhaseMachine {
+    fn default() -> Self {
+        Self::new(DemoPhase::Initialized)
+    }
+}
+
+impl DemoPhaseMachine {
+    pub fn new(phase: DemoPhase) -> Self {
+        Self { phase }
+    }
+
+    pub fn phase(&self) -> DemoPhase {
+        self.phase
+    }
+
+    /// Move to the next phase and return the events entering it produces.
+    pub fn advance(&mut self) -> Result<DemoPhaseTransition, DemoPhaseError> {
+        let to = self.phase.next().ok_or(DemoPhaseError::AlreadyComplete)?;
+        self.advance_to(to)
+    }
+
+    /// Move to `target`, which must be the phase directly after the current one.
+    pub fn advance_to(&mut self, target: DemoPhase) -> Result<DemoPhaseTransition, DemoPhaseError> {
+        let from = self.phase;
+        if !from.can_transition_to(target) {
+            return Err(DemoPhaseError::InvalidTransition { from, to: target });
+        }
+        self.phase = target;
+        let events = match target {
+            DemoPhase::Initialized => Vec::new(),
+            DemoPhase::Shuffling => vec![DemoPhaseEvent::ShuffleStarted],
+            DemoPhase::ShuffleComplete => vec![DemoPhaseEvent::ShuffleFinished],
+            DemoPhase::Dealing => vec![DemoPhaseEvent::DealStarted],
+            DemoPhase::Complete => vec![DemoPhaseEvent::HandCompleted],
+        };
+        Ok(DemoPhaseTransition {
+            from,
+            to: target,
+            events,
+        })
     }
 }
 
@@ -105,11 +191,20 @@ where
     /// Notification for shuffle phase completion
     pub shuffle_complete_notify: Arc<Notify>,
 
+    /// Stream events emitted so far, in order, for replay
+    pub event_log: Vec<DemoStreamEvent<C>>,
+
+    /// Deck was scripted and must be dealt without shuffling (non-production)
+    pub scripted_deck: bool,
+
     /// Timestamp when session was created
     pub created_at: Instant,
 
     /// Timestamp of last access (for TTL)
     pub last_accessed: Instant,
+
+    /// Time source for the timestamps above
+    pub clock: Arc<dyn Clock>,
 }
 
 impl<C> DemoState<C>
@@ -139,8 +234,9 @@ where
         player_keys: Vec<(C::ScalarField, C)>,
         aggregated_public_key: C,
         rng: StdRng,
+        clock: Arc<dyn Clock>,
     ) -> Self {
-        let now = Instant::now();
+        let now = clock.now();
         Self {
             id,
             game_id,
@@ -155,31 +251,83 @@ where
             aggregated_public_key,
             rng,
             shuffle_complete_notify: Arc::new(Notify::new()),
+            event_log: Vec::new(),
+            scripted_deck: false,
             created_at: now,
             last_accessed: now,
+            clock,
         }
     }
 
     /// Update last accessed timestamp.
     pub fn touch(&mut self) {
-        self.last_accessed = Instant::now();
+        self.last_accessed = self.clock.now();
     }
 
     /// Transition to a new phase if valid.
     pub fn transition_to(&mut self, target: DemoPhase) -> Result<(), String> {
-        if !self.phase.can_transition_to(target) {
-            return Err(format!(
-                "Invalid phase transition from {:?} to {:?}",
-                self.phase, target
-            ));
-        }
-        self.phase = target;
+        let mut machine = DemoPhaseMachine::new(self.phase);
+        let transition = machine.advance_to(target).map_err(|err| err.to_string())?;
+        debug!(
+            target: LOG_TARGET,
+            demo_id = %self.id,
+            from = ?transition.from,
+            to = ?transition.to,
+            events = ?transition.events,
+            "Demo phase advanced"
+        );
+        self.phase = machine.phase();
         self.touch();
         Ok(())
     }
 
+    /// Time since the session was created.
+    pub fn age(&self) -> Duration {
+        self.clock.now().saturating_duration_since(self.created_at)
+    }
+
     /// Check if session has expired based on TTL.
-    pub fn is_expired(&self, ttl: std::time::Duration) -> bool {
-        self.last_accessed.elapsed() > ttl
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        self.clock
+            .now()
+            .saturating_duration_since(self.last_accessed)
+            > ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phase_machine_advances_in_order_and_rejects_skips() {
+        let mut machine = DemoPhaseMachine::default();
+        let mut visited = vec![machine.phase()];
+        while machine.phase() != DemoPhase::Complete {
+            let transition = machine.advance().expect("advance to next phase");
+            assert_eq!(transition.events.len(), 1);
+            visited.push(transition.to);
+        }
+        assert_eq!(
+            visited,
+            vec![
+                DemoPhase::Initialized,
+                DemoPhase::Shuffling,
+                DemoPhase::ShuffleComplete,
+                DemoPhase::Dealing,
+                DemoPhase::Complete,
+            ]
+        );
+        assert_eq!(machine.advance(), Err(DemoPhaseError::AlreadyComplete));
+
+        let mut machine = DemoPhaseMachine::new(DemoPhase::ShuffleComplete);
+        assert_eq!(
+            machine.advance_to(DemoPhase::Complete),
+            Err(DemoPhaseError::InvalidTransition {
+                from: DemoPhase::ShuffleComplete,
+                to: DemoPhase::Complete,
+            })
+        );
+        assert_eq!(machine.phase(), DemoPhase::ShuffleComplete);
     }
 }
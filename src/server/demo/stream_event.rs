@@ -9,7 +9,7 @@ use crate::ledger::types::{GameId, HandId};
 use crate::showdown::Card;
 
 /// Events emitted over the demo streaming endpoint.
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 #[serde(
     tag = "type",
     rename_all = "snake_case",
@@ -4,6 +4,7 @@ use ark_serialize::CanonicalSerialize;
 use serde::{Deserialize, Serialize};
 
 use crate::ledger::messages::FinalizedAnyMessageEnvelope;
+use crate::ledger::query::{MessagePage, SnapshotDiff};
 use crate::ledger::snapshot::AnyTableSnapshot;
 use crate::ledger::types::{GameId, HandId};
 
@@ -63,6 +64,8 @@ where
     pub game_id: GameId,
     pub hand_id: HandId,
     pub messages: Vec<FinalizedAnyMessageEnvelope<C>>,
+    /// Pass back as `cursor` to fetch the next page; `null` on the last page.
+    pub next_cursor: Option<String>,
 }
 
 impl<C> HandMessagesResponse<C>
@@ -94,6 +97,22 @@ where
             game_id,
             hand_id,
             messages: events,
+            next_cursor: None,
         })
     }
+
+    pub fn try_from_page(game_id: GameId, hand_id: HandId, page: MessagePage<C>) -> Result<Self> {
+        let mut response = Self::try_from_events(game_id, hand_id, page.messages)?;
+        response.next_cursor = page.next_cursor.map(|cursor| cursor.to_string());
+        Ok(response)
+    }
+}
+
+/// Changes a client must apply to its snapshot at `from_sequence` to reach the hand's tip.
+#[derive(Serialize)]
+pub struct SnapshotDiffResponse {
+    pub game_id: GameId,
+    pub hand_id: HandId,
+    #[serde(flatten)]
+    pub diff: SnapshotDiff,
 }
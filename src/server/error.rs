@@ -1,31 +1,87 @@
+use axum::extract::rejection::JsonRejection;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value as JsonValue};
 use tracing::error;
 
 use crate::ledger::query::LatestSnapshotError;
+use crate::ledger::{GameSetupError, VerifyError};
+use crate::shuffling::DecryptionError;
 
 const LOG_TARGET: &str = "server::error";
 
+/// Error returned by HTTP handlers.
+///
+/// Every error renders as `{ "code", "message", "details" }` with a status chosen from the
+/// domain error it came from, so clients can branch on `code` instead of parsing messages.
 #[derive(Debug)]
-pub enum ApiError {
-    NotFound,
-    BadRequest(String),
-    Internal(String),
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+    details: Option<JsonValue>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: &'a str,
+    message: &'a str,
+    details: Option<&'a JsonValue>,
 }
 
 impl ApiError {
+    fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
     pub fn not_found(message: impl Into<String>) -> Self {
-        // For now, we'll use BadRequest with a not found message
-        // Could extend the enum to have NotFound(String) variant
-        ApiError::NotFound
+        Self::new(StatusCode::NOT_FOUND, "not_found", message)
     }
 
     pub fn bad_request(message: impl Into<String>) -> Self {
-        ApiError::BadRequest(message.into())
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    pub fn conflict(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, code, message)
+    }
+
+    pub fn forbidden(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, code, message)
+    }
+
+    pub fn payload_too_large(limit_bytes: usize) -> Self {
+        Self::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            format!("request body exceeds {limit_bytes} bytes"),
+        )
+        .with_details(json!({ "limit_bytes": limit_bytes }))
     }
 
     pub fn internal(message: impl Into<String>) -> Self {
-        ApiError::Internal(message.into())
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", message)
+    }
+
+    /// Attach structured context (offending values, limits) to the error body.
+    pub fn with_details(mut self, details: JsonValue) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
     }
 }
 
@@ -34,7 +90,8 @@ impl From<LatestSnapshotError> for ApiError {
         match err {
             LatestSnapshotError::HandNotFound { .. }
             | LatestSnapshotError::GameMismatch { .. }
-            | LatestSnapshotError::HandMismatch { .. } => ApiError::NotFound,
+            | LatestSnapshotError::HandMismatch { .. }
+            | LatestSnapshotError::SequenceNotFound { .. } => ApiError::not_found(err.to_string()),
             LatestSnapshotError::MissingHandId { requested } => ApiError::internal(format!(
                 "hand {requested} missing hand id in latest snapshot"
             )),
@@ -42,15 +99,253 @@ impl From<LatestSnapshotError> for ApiError {
     }
 }
 
+impl From<GameSetupError> for ApiError {
+    fn from(err: GameSetupError) -> Self {
+        let message = err.to_string();
+        match err {
+            GameSetupError::Database(_) => {
+                Self::new(StatusCode::INTERNAL_SERVER_ERROR, "database_error", message)
+            }
+            GameSetupError::Validation(_) => {
+                Self::new(StatusCode::BAD_REQUEST, "validation_failed", message)
+            }
+            GameSetupError::NotFound(resource) => {
+                ApiError::not_found(message).with_details(json!({ "resource": resource }))
+            }
+            GameSetupError::InvalidBuyIn {
+                requested,
+                min,
+                max,
+            } => Self::new(StatusCode::BAD_REQUEST, "invalid_buy_in", message)
+                .with_details(json!({ "requested": requested, "min": min, "max": max })),
+            GameSetupError::SeatTaken { seat } => {
+                ApiError::conflict("seat_taken", message).with_details(json!({ "seat": seat }))
+            }
+            GameSetupError::InsufficientShufflers { have, need } => {
+                ApiError::conflict("insufficient_shufflers", message)
+                    .with_details(json!({ "have": have, "need": need }))
+            }
+        }
+    }
+}
+
+impl From<VerifyError> for ApiError {
+    fn from(err: VerifyError) -> Self {
+        let message = err.to_string();
+        match err {
+            VerifyError::BadSignature => {
+                Self::new(StatusCode::UNAUTHORIZED, "bad_signature", message)
+            }
+            VerifyError::Unauthorized => {
+                Self::new(StatusCode::FORBIDDEN, "unauthorized_actor", message)
+            }
+            VerifyError::PhaseMismatch => ApiError::conflict("phase_mismatch", message),
+            VerifyError::NonceConflict => ApiError::conflict("nonce_conflict", message),
+            VerifyError::Duplicate => ApiError::conflict("duplicate_message", message),
+            VerifyError::InvalidMessage => {
+                Self::new(StatusCode::BAD_REQUEST, "invalid_message", message)
+            }
+        }
+    }
+}
+
+impl From<DecryptionError> for ApiError {
+    fn from(err: DecryptionError) -> Self {
+        let message = err.to_string();
+        match err {
+            DecryptionError::UndealtCard => ApiError::conflict("card_not_dealt", message),
+            DecryptionError::UnknownCard => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, "unknown_card", message)
+            }
+            DecryptionError::InvalidShares(_) => {
+                Self::new(StatusCode::BAD_REQUEST, "invalid_shares", message)
+            }
+        }
+    }
+}
+
+/// Maps a domain error carried inside an `anyhow::Error` through its own `From` impl, so
+/// handlers that drive `anyhow` workflows still answer with the domain status and code.
+/// Anything else is an internal error.
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<GameSetupError>() {
+            Ok(err) => return err.into(),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<VerifyError>() {
+            Ok(err) => return err.into(),
+            Err(err) => err,
+        };
+        match err.downcast::<DecryptionError>() {
+            Ok(err) => err.into(),
+            Err(err) => ApiError::internal(format!("{err:#}")),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let status = rejection.status();
+        let code = match status {
+            StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+            _ => "invalid_body",
+        };
+        Self::new(status, code, rejection.body_text())
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        match self {
-            ApiError::NotFound => StatusCode::NOT_FOUND.into_response(),
-            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
-            ApiError::Internal(message) => {
-                error!(target = LOG_TARGET, %message, "internal server error");
-                (StatusCode::INTERNAL_SERVER_ERROR, message).into_response()
-            }
+        if self.status.is_server_error() {
+            error!(
+                target = LOG_TARGET,
+                code = self.code,
+                message = %self.message,
+                "internal server error"
+            );
         }
+        let body = ErrorBody {
+            code: self.code,
+            message: &self.message,
+            details: self.details.as_ref(),
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::extract::{FromRequest, Request};
+    use axum::http::header;
+
+    async fn render(err: impl Into<ApiError>) -> (StatusCode, JsonValue) {
+        let response = err.into().into_response();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        (status, serde_json::from_slice(&bytes).expect("json body"))
+    }
+
+    #[tokio::test]
+    async fn domain_errors_map_to_status_and_body() {
+        let cases = [
+            (
+                render(VerifyError::BadSignature).await,
+                StatusCode::UNAUTHORIZED,
+                "bad_signature",
+            ),
+            (
+                render(VerifyError::Unauthorized).await,
+                StatusCode::FORBIDDEN,
+                "unauthorized_actor",
+            ),
+            (
+                render(VerifyError::NonceConflict).await,
+                StatusCode::CONFLICT,
+                "nonce_conflict",
+            ),
+            (
+                render(VerifyError::PhaseMismatch).await,
+                StatusCode::CONFLICT,
+                "phase_mismatch",
+            ),
+            (
+                render(GameSetupError::validation("small blind must be positive")).await,
+                StatusCode::BAD_REQUEST,
+                "validation_failed",
+            ),
+            (
+                render(GameSetupError::SeatTaken { seat: 3 }).await,
+                StatusCode::CONFLICT,
+                "seat_taken",
+            ),
+            (
+                render(DecryptionError::UndealtCard).await,
+                StatusCode::CONFLICT,
+                "card_not_dealt",
+            ),
+            (
+                render(DecryptionError::UnknownCard).await,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "unknown_card",
+            ),
+            (
+                render(DecryptionError::InvalidShares("missing share")).await,
+                StatusCode::BAD_REQUEST,
+                "invalid_shares",
+            ),
+            (
+                render(GameSetupError::NotFound("game")).await,
+                StatusCode::NOT_FOUND,
+                "not_found",
+            ),
+            (
+                render(ApiError::payload_too_large(1024)).await,
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "payload_too_large",
+            ),
+        ];
+
+        for ((status, body), expected_status, expected_code) in cases {
+            assert_eq!(status, expected_status, "{body}");
+            assert_eq!(body["code"], expected_code);
+            assert!(body["message"].is_string());
+            assert!(body.get("details").is_some(), "details key always present");
+        }
+    }
+
+    #[tokio::test]
+    async fn buy_in_details_carry_the_allowed_range() {
+        let (status, body) = render(GameSetupError::InvalidBuyIn {
+            requested: 5,
+            min: 100,
+            max: 200,
+        })
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body["details"],
+            json!({ "requested": 5, "min": 100, "max": 200 })
+        );
+    }
+
+    #[tokio::test]
+    async fn domain_errors_inside_anyhow_keep_their_status() {
+        let seat_taken = anyhow::Error::new(GameSetupError::SeatTaken { seat: 2 })
+            .context("joining demo players");
+        let (status, body) = render(seat_taken).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "seat_taken");
+        assert_eq!(body["details"], json!({ "seat": 2 }));
+
+        let (status, body) = render(anyhow::Error::new(VerifyError::BadSignature)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "bad_signature");
+
+        let (status, body) = render(anyhow::anyhow!("disk on fire")).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "internal");
+    }
+
+    #[tokio::test]
+    async fn oversized_json_body_is_413() {
+        // Larger than axum's default 2 MiB body limit.
+        let payload = format!("\"{}\"", "a".repeat(3 * 1024 * 1024));
+        let request = Request::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(payload))
+            .expect("request");
+        let rejection = Json::<JsonValue>::from_request(request, &())
+            .await
+            .expect_err("body over the limit");
+
+        let (status, body) = render(rejection).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["code"], "payload_too_large");
     }
 }
@@ -0,0 +1,157 @@
+//! Liveness and readiness probes.
+//!
+//! `/healthz` only says the process is serving HTTP. `/readyz` additionally pings Postgres and
+//! checks that the realtime client is running, and answers 503 until both are healthy so load
+//! balancers hold traffic back.
+
+use std::sync::Arc;
+
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_serialize::CanonicalSerialize;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::curve_absorb::CurveAbsorb;
+use crate::game::coordinator::{GameCoordinator, RealtimeStatus};
+
+const LOG_TARGET: &str = "server::health";
+
+/// Source of the realtime status reported by `/readyz`.
+pub trait RealtimeProbe: Send + Sync {
+    fn realtime_status(&self) -> RealtimeStatus;
+}
+
+impl<C> RealtimeProbe for GameCoordinator<C>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField> + Send + Sync + 'static,
+    C::ScalarField: PrimeField + UniformRand + Absorb + CanonicalSerialize + Send + Sync + Clone,
+    C::BaseField: PrimeField + Send + Sync,
+    C::Affine: Absorb,
+{
+    fn realtime_status(&self) -> RealtimeStatus {
+        GameCoordinator::realtime_status(self)
+    }
+}
+
+#[derive(Clone)]
+struct HealthContext {
+    db: DatabaseConnection,
+    realtime: Arc<dyn RealtimeProbe>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DatabaseStatus {
+    Ok,
+    Unavailable,
+}
+
+#[derive(Debug, Serialize)]
+struct LivenessResponse {
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct ReadinessResponse {
+    ready: bool,
+    database: DatabaseStatus,
+    realtime: RealtimeStatus,
+}
+
+/// Router serving `/healthz` and `/readyz`.
+pub fn router(db: DatabaseConnection, realtime: Arc<dyn RealtimeProbe>) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .layer(Extension(HealthContext { db, realtime }))
+}
+
+async fn healthz() -> impl IntoResponse {
+    Json(LivenessResponse { status: "ok" })
+}
+
+async fn readyz(Extension(ctx): Extension<HealthContext>) -> impl IntoResponse {
+    let database = match ctx.db.ping().await {
+        Ok(()) => DatabaseStatus::Ok,
+        Err(err) => {
+            warn!(target = LOG_TARGET, error = %err, "readiness database ping failed");
+            DatabaseStatus::Unavailable
+        }
+    };
+    let realtime = ctx.realtime.realtime_status();
+    let ready =
+        matches!(database, DatabaseStatus::Ok) && matches!(realtime, RealtimeStatus::Running);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(ReadinessResponse {
+            ready,
+            database,
+            realtime,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{connect_to_postgres_db, postgres_test_url};
+    use tokio::net::TcpListener;
+
+    struct FixedRealtime(RealtimeStatus);
+
+    impl RealtimeProbe for FixedRealtime {
+        fn realtime_status(&self) -> RealtimeStatus {
+            self.0
+        }
+    }
+
+    async fn readyz_status(db: DatabaseConnection) -> (StatusCode, serde_json::Value) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let app = router(db, Arc::new(FixedRealtime(RealtimeStatus::Running)));
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("serve health");
+        });
+
+        let response = reqwest::get(format!("http://{addr}/readyz"))
+            .await
+            .expect("readyz request");
+        let status = StatusCode::from_u16(response.status().as_u16()).expect("status code");
+        let body = response.json().await.expect("json body");
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn readyz_reports_database_availability() {
+        let (status, body) = readyz_status(DatabaseConnection::Disconnected).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["database"], "unavailable");
+        assert_eq!(body["realtime"], "running");
+
+        let conn = match connect_to_postgres_db(&postgres_test_url()).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                eprintln!("skipping healthy readyz check: failed to connect to postgres ({err})");
+                return;
+            }
+        };
+        let (status, body) = readyz_status(conn).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["database"], "ok");
+    }
+}
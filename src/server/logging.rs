@@ -1,9 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
 use std::time::Instant;
 
-use axum::{extract::Request, middleware::Next, response::Response};
+use anyhow::Result;
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use tracing::{Instrument, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::debugging_tools::{RedactingJsonWriter, RedactionLayer};
+use crate::ledger::CorrelationId;
 
 const LOG_TARGET: &str = "server::http";
 
+/// How log lines are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable, one line per event, for local development.
+    #[default]
+    Pretty,
+    /// One JSON object per event, with the fields of the enclosing spans (game, hand, request)
+    /// attached, for log aggregation in production.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format {other:?}; expected \"pretty\" or \"json\""
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pretty => f.write_str("pretty"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// Tracing output settings for the server process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// Log secret-bearing fields verbatim instead of redacting them.
+    pub unsafe_logging: bool,
+}
+
+impl LogConfig {
+    /// The formatting layer for this config, writing to `writer`.
+    pub fn layer<S, W>(&self, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let layer = tracing_subscriber::fmt::layer().with_target(false);
+        match self.format {
+            LogFormat::Pretty => layer
+                .with_writer(writer)
+                .fmt_fields(RedactionLayer::new(self.unsafe_logging))
+                .compact()
+                .boxed(),
+            LogFormat::Json => layer
+                .with_writer(RedactingJsonWriter::new(writer, self.unsafe_logging))
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_list(true)
+                .boxed(),
+        }
+    }
+
+    /// Install the global subscriber, filtered by `RUST_LOG` (default `info`) and writing to
+    /// stdout.
+    pub fn init(&self) -> Result<()> {
+        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(self.layer(std::io::stdout))
+            .try_init()?;
+        Ok(())
+    }
+}
+
+/// Header carrying a request's correlation id, accepted on requests and echoed on responses.
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Middleware that tags each request with a [`CorrelationId`]: the client's `x-correlation-id`
+/// when it is valid, a fresh one otherwise. The id is stored in the request extensions, made
+/// the task's [`CorrelationId::current`] so envelopes submitted while handling the request
+/// carry it, recorded on a span wrapping the rest of the request, and echoed on the response.
+pub async fn correlate_requests(mut request: Request, next: Next) -> Response {
+    let correlation_id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(CorrelationId::parse)
+        .unwrap_or_else(CorrelationId::generate);
+    request.extensions_mut().insert(correlation_id.clone());
+
+    let span = tracing::info_span!(target: LOG_TARGET, "request", %correlation_id);
+    let mut response = correlation_id
+        .clone()
+        .scope(next.run(request))
+        .instrument(span)
+        .await;
+    if let Ok(value) = HeaderValue::from_str(correlation_id.as_str()) {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
+    response
+}
+
 /// Middleware that logs incoming HTTP requests and their responses
 pub async fn log_requests(request: Request, next: Next) -> Response {
     let method = request.method().clone();
@@ -50,3 +168,122 @@ pub async fn log_requests(request: Request, next: Next) -> Response {
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugging_tools::REDACTED_PLACEHOLDER;
+    use axum::middleware;
+    use axum::routing::get;
+    use axum::{Extension, Router};
+    use serde_json::Value as JsonValue;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    #[derive(Clone)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_format_emits_event_and_span_fields() {
+        let captured = CapturedLogs(Arc::new(Mutex::new(Vec::new())));
+        let writer = captured.clone();
+        let config = LogConfig {
+            format: "json".parse().expect("known format"),
+            unsafe_logging: false,
+        };
+        let subscriber = tracing_subscriber::registry().with(config.layer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("hand", game_id = 7, hand_id = 42);
+            let _entered = span.enter();
+            tracing::info!(seat = 3, "hole cards dealt");
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let line: JsonValue = serde_json::from_str(output.trim()).expect("one JSON log line");
+        assert_eq!(line["message"], "hole cards dealt");
+        assert_eq!(line["seat"], 3);
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["span"]["name"], "hand");
+        assert_eq!(line["span"]["game_id"], 7);
+        assert_eq!(line["span"]["hand_id"], 42);
+        assert_eq!(line["spans"][0]["hand_id"], 42);
+    }
+
+    #[test]
+    fn json_format_redacts_sensitive_event_and_span_fields() {
+        let captured = CapturedLogs(Arc::new(Mutex::new(Vec::new())));
+        let writer = captured.clone();
+        let config = LogConfig {
+            format: LogFormat::Json,
+            unsafe_logging: false,
+        };
+        let subscriber = tracing_subscriber::registry().with(config.layer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("decrypt", secret_key = "0xfeed");
+            let _entered = span.enter();
+            tracing::info!(player_secret = 42, seat = 3, "hole card decrypted");
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(!output.contains("0xfeed"), "secret leaked: {output}");
+        let line: JsonValue = serde_json::from_str(output.trim()).expect("one JSON log line");
+        assert_eq!(line["player_secret"], REDACTED_PLACEHOLDER);
+        assert_eq!(line["seat"], 3);
+        assert_eq!(line["span"]["secret_key"], REDACTED_PLACEHOLDER);
+        assert_eq!(line["spans"][0]["secret_key"], REDACTED_PLACEHOLDER);
+    }
+
+    #[tokio::test]
+    async fn correlation_id_is_propagated_or_generated() {
+        let app = Router::new()
+            .route(
+                "/echo",
+                get(|Extension(id): Extension<CorrelationId>| async move { id.to_string() }),
+            )
+            .layer(middleware::from_fn(correlate_requests));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("serve");
+        });
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}/echo");
+        let response = client
+            .get(&url)
+            .header(CORRELATION_ID_HEADER, "req-42")
+            .send()
+            .await
+            .expect("request");
+        assert_eq!(response.headers()[CORRELATION_ID_HEADER], "req-42");
+        assert_eq!(response.text().await.expect("body"), "req-42");
+
+        let response = client
+            .get(&url)
+            .header(CORRELATION_ID_HEADER, "not valid")
+            .send()
+            .await
+            .expect("request");
+        let generated = response.headers()[CORRELATION_ID_HEADER]
+            .to_str()
+            .expect("ascii")
+            .to_string();
+        assert_ne!(generated, "not valid");
+        assert_eq!(response.text().await.expect("body"), generated);
+    }
+}
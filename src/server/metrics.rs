@@ -0,0 +1,53 @@
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Router serving the process metrics at `/metrics`.
+pub fn router() -> Router {
+    Router::new().route("/metrics", get(render_metrics))
+}
+
+async fn render_metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        crate::metrics::render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::EVENTS_APPLIED;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn scrape_reports_incremented_counter() {
+        let before = EVENTS_APPLIED.get();
+        crate::metrics_counter_inc!(EVENTS_APPLIED);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(listener, router())
+                .await
+                .expect("serve metrics");
+        });
+
+        let body = reqwest::get(format!("http://{addr}/metrics"))
+            .await
+            .expect("scrape")
+            .text()
+            .await
+            .expect("body");
+        let scraped: u64 = body
+            .lines()
+            .find_map(|line| line.strip_prefix("legit_poker_events_applied_total "))
+            .expect("counter present")
+            .parse()
+            .expect("counter value");
+        assert!(scraped > before);
+    }
+}
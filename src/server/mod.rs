@@ -2,13 +2,21 @@ pub mod bootstrap;
 pub mod demo;
 mod dto;
 mod error;
+pub mod health;
 pub mod logging;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod rate_limit;
 pub mod routes;
+#[cfg(feature = "debug-tasks")]
+pub mod tasks;
 
 pub use bootstrap::{run_server, ServerConfig};
 pub use dto::{
     DemoCreateRequest, DemoCreateResponse, DemoStartResponse, HandMessagesResponse,
-    LatestSnapshotResponse,
+    LatestSnapshotResponse, SnapshotDiffResponse,
 };
 pub use error::ApiError;
+pub use logging::{LogConfig, LogFormat};
+pub use rate_limit::RateLimitConfig;
 pub use routes::{LegitPokerServer, ServerContext};
@@ -0,0 +1,245 @@
+//! Per-client token-bucket rate limiting.
+//!
+//! Clients are keyed by peer IP; request headers are not trusted, since nothing validates them
+//! before this layer. Each client gets a bucket of `burst` tokens that refills at
+//! `refill_per_minute`; a request spends one token, and an empty bucket yields 429 with a
+//! `Retry-After` hint.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use parking_lot::Mutex;
+use tracing::warn;
+
+use crate::clock::{Clock, SystemClock};
+
+const LOG_TARGET: &str = "server::rate_limit";
+/// Most clients tracked at once. When a new client arrives at the cap, fully refilled buckets
+/// are dropped first and then the bucket idle the longest.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimitConfig {
+    /// Requests a client may make back to back.
+    pub burst: u32,
+    /// Tokens added to each bucket per minute.
+    pub refill_per_minute: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            burst: 5,
+            refill_per_minute: 10,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    fn refill_per_second(&self) -> f64 {
+        f64::from(self.refill_per_minute) / 60.0
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// A limiter that refills its buckets by `clock` rather than the system clock.
+    pub fn with_clock(config: RateLimitConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            clock,
+        }
+    }
+
+    /// Spend one token for `key` at time `now`, or return how long until one is available.
+    pub fn check_at(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        let burst = f64::from(self.config.burst);
+        let rate = self.config.refill_per_second();
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(key) {
+            buckets.retain(|_, bucket| {
+                let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+                bucket.tokens + elapsed * rate < burst
+            });
+            if buckets.len() >= MAX_TRACKED_CLIENTS {
+                let idlest = buckets
+                    .iter()
+                    .min_by_key(|(_, bucket)| bucket.updated)
+                    .map(|(client, _)| client.clone());
+                if let Some(idlest) = idlest {
+                    buckets.remove(&idlest);
+                }
+            }
+        }
+
+        let bucket = buckets.entry(key.to_owned()).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if rate > 0.0 {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        } else {
+            Err(Duration::MAX)
+        }
+    }
+
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        self.check_at(key, self.clock.now())
+    }
+}
+
+/// Middleware rejecting requests from clients that have exhausted their bucket.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = client_key(&request);
+    match limiter.check(&key) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            warn!(
+                target = LOG_TARGET,
+                client = %key,
+                path = %request.uri().path(),
+                "rate limit exceeded"
+            );
+            let retry_after = wait.as_secs().saturating_add(1).min(u32::MAX as u64);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, HeaderValue::from(retry_after))],
+                "rate limit exceeded",
+            )
+                .into_response()
+        }
+    }
+}
+
+fn client_key(request: &Request) -> String {
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "unknown".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::middleware;
+    use axum::routing::post;
+    use axum::Router;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn requests_past_the_burst_get_429() {
+        let limiter = Arc::new(RateLimiter::new(RateLimitConfig {
+            burst: 2,
+            refill_per_minute: 1,
+        }));
+        let app = Router::new()
+            .route("/games/demo", post(|| async { "created" }))
+            .layer(middleware::from_fn_with_state(limiter, rate_limit));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .expect("serve");
+        });
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}/games/demo");
+        for _ in 0..2 {
+            let response = client.post(&url).send().await.expect("request");
+            assert_eq!(response.status().as_u16(), 200);
+        }
+        let response = client.post(&url).send().await.expect("request");
+        assert_eq!(response.status().as_u16(), 429);
+        let retry_after: u64 = response.headers()["retry-after"]
+            .to_str()
+            .expect("ascii")
+            .parse()
+            .expect("seconds");
+        assert!((1..=60).contains(&retry_after));
+
+        // A made-up API key does not buy a fresh bucket.
+        let response = client
+            .post(&url)
+            .header("x-api-key", "other")
+            .send()
+            .await
+            .expect("request");
+        assert_eq!(response.status().as_u16(), 429);
+    }
+
+    #[test]
+    fn tracked_clients_stay_bounded_when_every_bucket_is_active() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            burst: 1,
+            refill_per_minute: 1,
+        });
+        let start = Instant::now();
+        for client in 0..MAX_TRACKED_CLIENTS + 10 {
+            let now = start + Duration::from_millis(client as u64);
+            assert!(limiter.check_at(&format!("ip:{client}"), now).is_ok());
+        }
+        let buckets = limiter.buckets.lock();
+        assert_eq!(buckets.len(), MAX_TRACKED_CLIENTS);
+        // The clients idle the longest were evicted first.
+        assert!(!buckets.contains_key("ip:0"));
+        assert!(buckets.contains_key(&format!("ip:{}", MAX_TRACKED_CLIENTS + 9)));
+    }
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            burst: 1,
+            refill_per_minute: 60,
+        });
+        let start = Instant::now();
+        assert!(limiter.check_at("ip:127.0.0.1", start).is_ok());
+        let wait = limiter
+            .check_at("ip:127.0.0.1", start)
+            .expect_err("bucket is empty");
+        assert!(wait <= Duration::from_secs(1));
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check_at("ip:127.0.0.1", later).is_err());
+        let refilled = start + Duration::from_secs(1);
+        assert!(limiter.check_at("ip:127.0.0.1", refilled).is_ok());
+    }
+}
@@ -1,5 +1,7 @@
+use std::convert::Infallible;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
@@ -7,21 +9,36 @@ use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use axum::extract::{Path, Query};
 use axum::middleware;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
+use futures::{future, stream, Stream, StreamExt};
 use serde::Deserialize;
+use tokio_stream::wrappers::BroadcastStream;
 use tower_http::cors::{Any, CorsLayer};
 
 use crate::curve_absorb::CurveAbsorb;
 use crate::game::coordinator::GameCoordinator;
-use crate::ledger::query::{HandMessagesQuery, LatestSnapshotQuery, SequenceBounds};
-use crate::ledger::snapshot::SnapshotSeq;
+use crate::ledger::messages::SignedReconnectRequest;
+use crate::ledger::query::latest_snapshot::snapshot_ids;
+use crate::ledger::query::{
+    HandMessagesQuery, LatestSnapshotQuery, MessageCursor, MessagePageRequest, SequenceBounds,
+};
+use crate::ledger::serialization::{with_point_encoding, PointEncoding};
+use crate::ledger::snapshot::{
+    reconnect_player, redact_for_spectator, redact_messages_for_spectator, PlayerTableView,
+    ReconnectNonces, SnapshotSeq,
+};
+use crate::ledger::store::SharedEventStore;
 use crate::ledger::types::{GameId, HandId};
+use crate::ledger::verifier::SignatureValidator;
 use crate::ledger::LobbyService;
 
 use super::demo::{create_demo, stream_deal, stream_shuffle, DemoSessionStore};
-use super::dto::{HandMessagesResponse, LatestSnapshotResponse};
+use super::dto::{HandMessagesResponse, LatestSnapshotResponse, SnapshotDiffResponse};
 use super::error::ApiError;
+use super::rate_limit::{rate_limit, RateLimitConfig, RateLimiter};
 
 #[derive(Clone)]
 pub struct ServerContext<C>
@@ -39,6 +56,9 @@ where
 {
     pub coordinator: Arc<GameCoordinator<C>>,
     pub lobby: Arc<dyn LobbyService<C>>,
+    /// Checks player signatures on requests that are not ledger messages, e.g. reconnects.
+    pub signature: Arc<dyn SignatureValidator<C>>,
+    pub reconnect_nonces: Arc<ReconnectNonces>,
 }
 
 pub struct LegitPokerServer<C>
@@ -71,11 +91,24 @@ where
     C::BaseField: PrimeField + Send + Sync,
     C::Affine: Absorb,
 {
-    pub fn new(coordinator: Arc<GameCoordinator<C>>, lobby: Arc<dyn LobbyService<C>>) -> Self {
-        let context = Arc::new(ServerContext { coordinator, lobby });
+    pub fn new(
+        coordinator: Arc<GameCoordinator<C>>,
+        lobby: Arc<dyn LobbyService<C>>,
+        signature: Arc<dyn SignatureValidator<C>>,
+        demo_rate_limit: RateLimitConfig,
+    ) -> Self {
+        let event_store = coordinator.event_store();
+        let context = Arc::new(ServerContext {
+            coordinator,
+            lobby,
+            signature,
+            reconnect_nonces: Arc::new(ReconnectNonces::default()),
+        });
 
         // Create demo session store
         let demo_store = Arc::new(DemoSessionStore::<C>::new());
+        // Every demo allocates fresh crypto state, so creation is throttled per client
+        let demo_limiter = Arc::new(RateLimiter::new(demo_rate_limit));
 
         // Configure CORS to allow requests from the frontend
         let cors = CorsLayer::new()
@@ -85,7 +118,11 @@ where
 
         let router = Router::new()
             // Interactive demo endpoints
-            .route("/games/demo", post(create_demo::<C>))
+            .route(
+                "/games/demo",
+                post(create_demo::<C>)
+                    .layer(middleware::from_fn_with_state(demo_limiter, rate_limit)),
+            )
             .route("/games/demo/:id/shuffle", get(stream_shuffle::<C>))
             .route("/games/demo/:id/deal", get(stream_deal::<C>))
             // Hand query endpoints
@@ -97,9 +134,30 @@ where
                 "/games/:game_id/hands/:hand_id/messages",
                 get(get_hand_messages::<C>),
             )
+            .route(
+                "/games/:game_id/hands/:hand_id/diff",
+                get(get_hand_snapshot_diff::<C>),
+            )
+            .route(
+                "/games/:game_id/hands/:hand_id/spectate",
+                get(stream_spectator_snapshots::<C>),
+            )
+            .route(
+                "/games/:game_id/hands/:hand_id/reconnect",
+                post(reconnect_seat::<C>),
+            );
+        // Prometheus scrape endpoint
+        #[cfg(feature = "metrics")]
+        let router = router.merge(super::metrics::router());
+        // Live task listing for diagnosing stuck hands; unauthenticated, so opt-in only
+        #[cfg(feature = "debug-tasks")]
+        let router = router.merge(super::tasks::router());
+        let router = router
             .layer(Extension(context))
+            .layer(Extension(event_store))
             .layer(Extension(demo_store))
             .layer(middleware::from_fn(super::logging::log_requests))
+            .layer(middleware::from_fn(super::logging::correlate_requests))
             .layer(cors);
 
         Self {
@@ -127,18 +185,33 @@ struct HandPath {
 struct MessagesQuery {
     from_sequence: Option<u32>,
     to_sequence: Option<u32>,
+    limit: Option<usize>,
+    /// `next_cursor` from the previous page.
+    cursor: Option<String>,
+    /// Comma-separated message types, e.g. `shuffle,player_preflop`.
+    types: Option<String>,
+    /// Byte layout of curve points in the response; defaults to compressed.
+    point_encoding: Option<PointEncoding>,
 }
 
 #[derive(Debug, Default, Deserialize)]
 struct SnapshotQuery {
     include_messages: Option<bool>,
+    /// Byte layout of curve points in the response; defaults to compressed.
+    point_encoding: Option<PointEncoding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiffQuery {
+    /// Sequence of the snapshot the client already holds.
+    since: u32,
 }
 
 async fn get_hand_snapshot<C>(
     Extension(ctx): Extension<Arc<ServerContext<C>>>,
     Path(path): Path<HandPath>,
     Query(query_params): Query<SnapshotQuery>,
-) -> Result<Json<LatestSnapshotResponse<C>>, ApiError>
+) -> Result<Response, ApiError>
 where
     C: CurveGroup
         + CanonicalSerialize
@@ -175,21 +248,58 @@ where
         .map_err(ApiError::from)?;
 
     let messages = if query_params.include_messages.unwrap_or(false) {
-        Some(ledger_state.messages_up_to_sequence(path.hand_id, snapshot.sequence()))
+        Some(redact_messages_for_spectator(
+            ledger_state.messages_up_to_sequence(path.hand_id, snapshot.sequence()),
+        ))
     } else {
         None
     };
 
-    Ok(Json(LatestSnapshotResponse::from_domain_with_messages(
-        snapshot, messages,
-    )))
+    // The route is unauthenticated, so every seat's hole-card material is stripped from both
+    // the snapshot and its messages; seated players recover their own cards through the signed
+    // reconnect request.
+    let response = LatestSnapshotResponse::from_domain_with_messages(
+        redact_for_spectator(&snapshot),
+        messages,
+    );
+    Ok(render_json(query_params.point_encoding, response))
 }
 
-async fn get_hand_messages<C>(
+async fn get_hand_snapshot_diff<C>(
     Extension(ctx): Extension<Arc<ServerContext<C>>>,
     Path(path): Path<HandPath>,
-    Query(query): Query<MessagesQuery>,
-) -> Result<Json<HandMessagesResponse<C>>, ApiError>
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<SnapshotDiffResponse>, ApiError>
+where
+    C: CurveGroup
+        + CanonicalSerialize
+        + CanonicalDeserialize
+        + CurveAbsorb<C::BaseField>
+        + Send
+        + Sync
+        + 'static,
+    C::ScalarField: PrimeField + UniformRand + Absorb + CanonicalSerialize + Send + Sync,
+    C::BaseField: PrimeField + Send + Sync,
+    C::Affine: Absorb,
+{
+    let snapshot_query = LatestSnapshotQuery::new(ctx.coordinator.state());
+    // Resolving the tip first rejects hands that belong to another game.
+    snapshot_query.execute(path.game_id, path.hand_id)?;
+    let diff = snapshot_query.diff_since(path.hand_id, snapshot_seq_from_u32(query.since))?;
+
+    Ok(Json(SnapshotDiffResponse {
+        game_id: path.game_id,
+        hand_id: path.hand_id,
+        diff,
+    }))
+}
+
+/// Streams the hand's snapshots to a spectator with every player's hole cards redacted,
+/// starting from the current tip.
+async fn stream_spectator_snapshots<C>(
+    Extension(ctx): Extension<Arc<ServerContext<C>>>,
+    Path(path): Path<HandPath>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError>
 where
     C: CurveGroup
         + CanonicalSerialize
@@ -201,6 +311,81 @@ where
     C::ScalarField: PrimeField + UniformRand + Absorb + CanonicalSerialize + Send + Sync,
     C::BaseField: PrimeField + Send + Sync,
     C::Affine: Absorb,
+{
+    // Subscribe before reading the tip so no update can land between the two.
+    let updates = ctx.coordinator.operator().snapshot_updates();
+    let tip = LatestSnapshotQuery::new(ctx.coordinator.state())
+        .execute(path.game_id, path.hand_id)
+        .map_err(ApiError::from)?;
+    let tip_sequence = tip.sequence();
+
+    // A spectator that lags behind the channel skips ahead; every snapshot stands on its own.
+    let live = BroadcastStream::new(updates).filter_map(move |update| {
+        let redacted = update.ok().and_then(|snapshot| {
+            let (game_id, hand_id) = snapshot_ids(&snapshot);
+            (game_id == path.game_id
+                && hand_id == Some(path.hand_id)
+                && snapshot.sequence() > tip_sequence)
+                .then(|| redact_for_spectator(&snapshot))
+        });
+        future::ready(redacted)
+    });
+
+    let events = stream::once(future::ready(redact_for_spectator(&tip)))
+        .chain(live)
+        .map(|snapshot| {
+            let data = serde_json::to_string(&LatestSnapshotResponse::from_domain(snapshot))
+                .unwrap_or_else(|err| serde_json::json!({ "error": err.to_string() }).to_string());
+            Ok::<Event, Infallible>(Event::default().event("snapshot").data(data))
+        });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)).text(":")))
+}
+
+/// Returns a seated player's view of the hand, with their own hole cards, in exchange for a
+/// reconnect request signed by the seat's key.
+async fn reconnect_seat<C>(
+    Extension(ctx): Extension<Arc<ServerContext<C>>>,
+    Path(path): Path<HandPath>,
+    Json(request): Json<SignedReconnectRequest<C>>,
+) -> Result<Json<PlayerTableView<C>>, ApiError>
+where
+    C: CurveGroup
+        + CanonicalSerialize
+        + CanonicalDeserialize
+        + CurveAbsorb<C::BaseField>
+        + Send
+        + Sync
+        + 'static,
+    C::ScalarField: PrimeField + UniformRand + Absorb + CanonicalSerialize + Send + Sync,
+    C::BaseField: PrimeField + Send + Sync,
+    C::Affine: Absorb,
+{
+    if (request.value.game_id, request.value.hand_id) != (path.game_id, path.hand_id) {
+        return Err(ApiError::bad_request(
+            "reconnect request does not name this game and hand",
+        ));
+    }
+    let tip = LatestSnapshotQuery::new(ctx.coordinator.state())
+        .execute(path.game_id, path.hand_id)
+        .map_err(ApiError::from)?;
+    let view = reconnect_player(
+        &tip,
+        &request,
+        ctx.signature.as_ref(),
+        &ctx.reconnect_nonces,
+    )
+    .map_err(|err| ApiError::forbidden("reconnect_rejected", err.to_string()))?;
+    Ok(Json(view))
+}
+
+async fn get_hand_messages<C>(
+    Extension(event_store): Extension<SharedEventStore<C>>,
+    Path(path): Path<HandPath>,
+    Query(query): Query<MessagesQuery>,
+) -> Result<Response, ApiError>
+where
+    C: CurveGroup + CanonicalSerialize + CanonicalDeserialize + Send + Sync + 'static,
 {
     let bounds = SequenceBounds::new(
         query.from_sequence.map(snapshot_seq_from_u32),
@@ -208,20 +393,259 @@ where
     )
     .map_err(|err| ApiError::bad_request(err.to_string()))?;
 
-    let event_store = ctx.coordinator.event_store();
+    let after = query
+        .cursor
+        .as_deref()
+        .map(str::parse::<MessageCursor>)
+        .transpose()
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    let types = query.types.as_deref().map(|types| {
+        types
+            .split(',')
+            .map(str::trim)
+            .filter(|kind| !kind.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+    let page_request = MessagePageRequest {
+        limit: query.limit,
+        after,
+        types,
+    };
+    page_request
+        .page_limit()
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+
     let messages_query = HandMessagesQuery::new(event_store);
-    let events = messages_query
-        .execute(path.hand_id, &bounds)
+    let page = messages_query
+        .execute_page(path.hand_id, &bounds, &page_request)
         .await
         .map_err(|err| ApiError::internal(err.to_string()))?;
 
-    let response = HandMessagesResponse::try_from_events(path.game_id, path.hand_id, events)
+    let response = HandMessagesResponse::try_from_page(path.game_id, path.hand_id, page)
         .map_err(|err| ApiError::internal(err.to_string()))?;
 
-    Ok(Json(response))
+    Ok(render_json(query.point_encoding, response))
+}
+
+/// Serializes `body` with curve points in the requested encoding. The body is rendered here
+/// rather than by the router so the encoding is still in effect while serde runs.
+fn render_json<T>(encoding: Option<PointEncoding>, body: T) -> Response
+where
+    T: serde::Serialize,
+{
+    with_point_encoding(encoding.unwrap_or_default(), || Json(body).into_response())
 }
 
 #[inline]
 fn snapshot_seq_from_u32(value: u32) -> SnapshotSeq {
     value
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::nl::actions::PlayerBetAction;
+    use crate::ledger::actor::AnyActor;
+    use crate::ledger::messages::{
+        AnyGameMessage, AnyMessageEnvelope, FinalizedAnyMessageEnvelope, FlopStreet,
+        GamePlayerMessage, PreflopStreet,
+    };
+    use crate::ledger::snapshot::SnapshotStatus;
+    use crate::ledger::store::EventStore;
+    use crate::ledger::types::{EventId, EventPhase};
+    use crate::signing::WithSignature;
+    use ark_bn254::G1Projective as Curve;
+    use ark_ff::Zero;
+    use async_trait::async_trait;
+    use futures::stream::{self, BoxStream, StreamExt};
+    use sea_orm::{DatabaseConnection, DatabaseTransaction};
+    use serde_json::Value as JsonValue;
+    use tokio::net::TcpListener;
+
+    const GAME_ID: GameId = 1;
+    const HAND_ID: HandId = 2;
+
+    /// Read-only store serving a fixed set of events.
+    struct FixedEventStore {
+        events: Vec<FinalizedAnyMessageEnvelope<Curve>>,
+        connection: DatabaseConnection,
+    }
+
+    #[async_trait]
+    impl EventStore<Curve> for FixedEventStore {
+        async fn persist_event(
+            &self,
+            _: &FinalizedAnyMessageEnvelope<Curve>,
+        ) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("read-only test store"))
+        }
+
+        async fn persist_event_in_txn(
+            &self,
+            _: &DatabaseTransaction,
+            _: &FinalizedAnyMessageEnvelope<Curve>,
+        ) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("read-only test store"))
+        }
+
+        async fn remove_event(&self, _: HandId, _: u64) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("read-only test store"))
+        }
+
+        async fn load_all_events(&self) -> anyhow::Result<Vec<FinalizedAnyMessageEnvelope<Curve>>> {
+            Ok(self.events.clone())
+        }
+
+        async fn load_hand_events(
+            &self,
+            hand_id: HandId,
+        ) -> anyhow::Result<Vec<FinalizedAnyMessageEnvelope<Curve>>> {
+            self.load_hand_events_in_sequence_range(hand_id, None, None)
+                .await
+        }
+
+        async fn load_hand_events_in_sequence_range(
+            &self,
+            hand_id: HandId,
+            from: Option<SnapshotSeq>,
+            to: Option<SnapshotSeq>,
+        ) -> anyhow::Result<Vec<FinalizedAnyMessageEnvelope<Curve>>> {
+            Ok(self
+                .events
+                .iter()
+                .filter(|event| event.envelope.hand_id == hand_id)
+                .filter(|event| from.is_none_or(|from| event.snapshot_sequence_id >= from))
+                .filter(|event| to.is_none_or(|to| event.snapshot_sequence_id <= to))
+                .cloned()
+                .collect())
+        }
+
+        fn tail(
+            &self,
+            _: HandId,
+            _: Option<EventId>,
+        ) -> BoxStream<'static, FinalizedAnyMessageEnvelope<Curve>> {
+            stream::empty().boxed()
+        }
+
+        fn connection(&self) -> &DatabaseConnection {
+            &self.connection
+        }
+    }
+
+    fn player_event(
+        sequence: SnapshotSeq,
+        message: AnyGameMessage<Curve>,
+    ) -> FinalizedAnyMessageEnvelope<Curve> {
+        let envelope = AnyMessageEnvelope {
+            hand_id: HAND_ID,
+            game_id: GAME_ID,
+            actor: AnyActor::None,
+            nonce: u64::from(sequence),
+            public_key: Curve::zero(),
+            message: WithSignature {
+                value: message,
+                signature: Vec::new(),
+            },
+            correlation_id: None,
+        };
+        FinalizedAnyMessageEnvelope::new(
+            envelope,
+            SnapshotStatus::Success,
+            EventPhase::Betting,
+            sequence,
+        )
+    }
+
+    async fn serve_messages(events: Vec<FinalizedAnyMessageEnvelope<Curve>>) -> String {
+        let store: SharedEventStore<Curve> = Arc::new(FixedEventStore {
+            events,
+            connection: DatabaseConnection::Disconnected,
+        });
+        let app = Router::new()
+            .route(
+                "/games/:game_id/hands/:hand_id/messages",
+                get(get_hand_messages::<Curve>),
+            )
+            .layer(Extension(store));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("serve");
+        });
+        format!("http://{addr}/games/{GAME_ID}/hands/{HAND_ID}/messages")
+    }
+
+    async fn fetch(client: &reqwest::Client, url: &str, query: &[(&str, String)]) -> JsonValue {
+        let response = client.get(url).query(query).send().await.expect("request");
+        assert_eq!(response.status().as_u16(), 200);
+        response.json().await.expect("json body")
+    }
+
+    #[tokio::test]
+    async fn messages_route_pages_until_null_cursor() {
+        let preflop = || {
+            AnyGameMessage::PlayerPreflop(GamePlayerMessage::<PreflopStreet, Curve>::new(
+                PlayerBetAction::Call,
+            ))
+        };
+        let flop = || {
+            AnyGameMessage::PlayerFlop(GamePlayerMessage::<FlopStreet, Curve>::new(
+                PlayerBetAction::Check,
+            ))
+        };
+        let events = (1..=7)
+            .map(|sequence| {
+                let message = if sequence <= 5 { preflop() } else { flop() };
+                player_event(sequence, message)
+            })
+            .collect();
+        let url = serve_messages(events).await;
+        let client = reqwest::Client::new();
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut query = vec![("limit", "3".to_string())];
+            if let Some(cursor) = &cursor {
+                query.push(("cursor", cursor.clone()));
+            }
+            let page = fetch(&client, &url, &query).await;
+            let messages = page["messages"].as_array().expect("messages array");
+            assert!(messages.len() <= 3);
+            seen.extend(
+                messages
+                    .iter()
+                    .map(|m| m["snapshot_sequence_id"].as_u64().unwrap()),
+            );
+            match &page["next_cursor"] {
+                JsonValue::Null => break,
+                JsonValue::String(next) => cursor = Some(next.clone()),
+                other => panic!("unexpected cursor {other}"),
+            }
+        }
+        assert_eq!(seen, (1..=7).collect::<Vec<u64>>());
+
+        let filtered = fetch(
+            &client,
+            &url,
+            &[
+                ("types", "player_flop".to_string()),
+                ("limit", "2".to_string()),
+            ],
+        )
+        .await;
+        assert_eq!(filtered["messages"].as_array().unwrap().len(), 2);
+        assert_eq!(filtered["next_cursor"], JsonValue::Null);
+
+        let response = client
+            .get(&url)
+            .query(&[("limit", "0")])
+            .send()
+            .await
+            .expect("request");
+        assert_eq!(response.status().as_u16(), 400);
+    }
+}
@@ -0,0 +1,34 @@
+//! Debug listing of running per-hand tasks.
+//!
+//! `/debug/tasks` returns every task in the global [`TaskRegistry`]; `?hand_id=` narrows it to
+//! one hand, which is usually the first thing to check when a hand stops progressing.
+//!
+//! The endpoint is unauthenticated and exposes hand ids, so it is only compiled with the
+//! `debug-tasks` feature.
+
+use axum::extract::Query;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+
+use crate::ledger::types::HandId;
+use crate::tokio_tools::TaskRegistry;
+
+#[derive(Debug, Deserialize)]
+struct TasksQuery {
+    hand_id: Option<HandId>,
+}
+
+/// Router serving the live task list at `/debug/tasks`.
+pub fn router() -> Router {
+    Router::new().route("/debug/tasks", get(list_tasks))
+}
+
+async fn list_tasks(Query(query): Query<TasksQuery>) -> impl IntoResponse {
+    let registry = TaskRegistry::global();
+    Json(match query.hand_id {
+        Some(hand_id) => registry.snapshot_for_hand(hand_id),
+        None => registry.snapshot(),
+    })
+}
@@ -9,6 +9,7 @@ use ark_relations::gr1cs::{ConstraintSystemRef, Namespace, SynthesisError};
 use core::borrow::Borrow;
 use core::ops::Not;
 
+use crate::logup::{prove_lookup, uint_to_field, verify_distinct_lookup};
 use crate::showdown::{HandCategory, M0, M1, M2, M3, M4, M5};
 
 /// Circuit representation of HandCategory enum
@@ -113,6 +114,91 @@ fn decode_card_var<F: PrimeField>(idx: &UInt8<F>) -> Result<(UInt8<F>, UInt8<F>)
     Ok((rank, q))
 }
 
+/// Card lookup table rows pack `index + 2^8·rank + 2^12·suit`. The index is a `UInt8`, the rank
+/// is allocated with 4 bits and the suit with 2, so distinct triples never pack to the same value
+/// and every row fits in [`CARD_PACK_BITS`] bits.
+const CARD_PACK_RANK: u64 = 1 << 8;
+const CARD_PACK_SUIT: u64 = 1 << 12;
+const CARD_PACK_BITS: u32 = 14;
+
+fn card_lookup_table<F: PrimeField>() -> Vec<F> {
+    (0..52u64)
+        .map(|idx| F::from(idx + CARD_PACK_RANK * (idx % 13 + 2) + CARD_PACK_SUIT * (idx / 13)))
+        .collect()
+}
+
+/// Witness a `width`-bit value as a `UInt8` whose remaining high bits are constant zero.
+fn small_uint_witness<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    value: u8,
+    width: usize,
+) -> Result<UInt8<F>, SynthesisError> {
+    let bits = (0..8)
+        .map(|i| {
+            if i < width {
+                Boolean::new_witness(cs.clone(), || Ok((value >> i) & 1 == 1))
+            } else {
+                Ok(Boolean::FALSE)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(UInt8::from_bits_le(&bits))
+}
+
+/// Decode distinct card indices (0..51) into `(rank 2..14, suit 0..3)` with one LogUp lookup
+/// into the fixed 52-entry card table, as an alternative to the per-card divmod in
+/// `decode_card_var`.
+///
+/// The challenge is squeezed in-circuit and each multiplicity is a single bit, so the lookup
+/// also proves the cards are pairwise distinct. The table costs one constraint per entry plus
+/// a Poseidon permutation, and each card adds only six bits and one inverse, so decoding a
+/// whole hand in one call is cheaper than scanning the table per card.
+pub fn lookup_card_value_gadget<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    indices: &[UInt8<F>],
+) -> Result<Vec<(UInt8<F>, UInt8<F>)>, SynthesisError> {
+    let table = card_lookup_table::<F>();
+    let mut decoded = Vec::with_capacity(indices.len());
+    let mut queries = Vec::with_capacity(indices.len());
+
+    for idx in indices {
+        let idx_val = idx.value().unwrap_or(0);
+        let rank = small_uint_witness(cs.clone(), idx_val % 13 + 2, 4)?;
+        let suit = small_uint_witness(cs.clone(), idx_val / 13, 2)?;
+        queries.push(
+            uint_to_field(idx)?
+                + uint_to_field(&rank)? * F::from(CARD_PACK_RANK)
+                + uint_to_field(&suit)? * F::from(CARD_PACK_SUIT),
+        );
+        decoded.push((rank, suit));
+    }
+
+    // An index outside the table, or a repeated card, gets zero multiplicities, which leaves
+    // the system unsatisfied.
+    let counts = queries
+        .iter()
+        .map(|query| query.value().ok())
+        .collect::<Option<Vec<F>>>()
+        .map(|values| {
+            prove_lookup(&table, &values)
+                .map(|witness| witness.multiplicities)
+                .unwrap_or_else(|| vec![0; table.len()])
+        });
+    let multiplicities = (0..table.len())
+        .map(|i| {
+            Boolean::new_witness(cs.clone(), || {
+                counts
+                    .as_ref()
+                    .map(|counts| counts[i] == 1)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    verify_distinct_lookup(cs, &table, &queries, CARD_PACK_BITS, &multiplicities)?;
+    Ok(decoded)
+}
+
 /// Category-specific tie-break vector c[5] from canonical ranks r0..r4
 fn tiebreak_vector_var<F: PrimeField>(
     cat: &HandCategoryVar<F>,
@@ -180,6 +266,16 @@ pub fn verify_and_score_from_indices<F: PrimeField>(
         suits[i] = s;
     }
 
+    score_decoded_hand(claimed_cat, ranks, suits)
+}
+
+/// Verify category/canonical layout for already-decoded ranks and suits and output the packed
+/// score + tie-break digits.
+fn score_decoded_hand<F: PrimeField>(
+    claimed_cat: HandCategoryVar<F>,
+    ranks: [UInt8<F>; 5],
+    suits: [UInt8<F>; 5],
+) -> Result<(FpVar<F>, [UInt8<F>; 5]), SynthesisError> {
     // same-suit?
     let mut same_suit = Boolean::TRUE;
     for i in 1..5 {
@@ -312,6 +408,18 @@ impl<F: PrimeField> Best5HandVar<F> {
     pub fn score_and_tiebreak(&self) -> Result<(FpVar<F>, [UInt8<F>; 5]), SynthesisError> {
         verify_and_score_from_indices::<F>(self.category.clone(), self.idx5.clone())
     }
+
+    /// Same as [`Self::score_and_tiebreak`], but decodes the five indices with a single LogUp
+    /// lookup into the card table.
+    pub fn score_and_tiebreak_with_lookup(
+        &self,
+        cs: ConstraintSystemRef<F>,
+    ) -> Result<(FpVar<F>, [UInt8<F>; 5]), SynthesisError> {
+        let decoded = lookup_card_value_gadget(cs, &self.idx5)?;
+        let ranks = std::array::from_fn(|i| decoded[i].0.clone());
+        let suits = std::array::from_fn(|i| decoded[i].1.clone());
+        score_decoded_hand(self.category.clone(), ranks, suits)
+    }
 }
 
 /// Boolean assert helper
@@ -516,6 +624,110 @@ mod tests {
         assert_eq!(score.value().unwrap(), s_native);
     }
 
+    /// Reference decode that compares the index against every table row.
+    fn linear_scan_decode(idx: &UInt8<Fr>) -> (FpVar<Fr>, FpVar<Fr>) {
+        let idx_fp = uint_to_field(idx).unwrap();
+        let mut rank = FpVar::zero();
+        let mut suit = FpVar::zero();
+        let mut hits = FpVar::zero();
+        for row in 0..52u64 {
+            let hit = FpVar::from(idx_fp.is_eq(&FpVar::constant(Fr::from(row))).unwrap());
+            rank += &hit * Fr::from(row % 13 + 2);
+            suit += &hit * Fr::from(row / 13);
+            hits += hit;
+        }
+        hits.enforce_equal(&FpVar::one()).unwrap();
+        (rank, suit)
+    }
+
+    #[test]
+    fn logup_card_decode_is_cheaper_than_linear_scan() {
+        let hand = [
+            idx_of(14, Suit::Spades),
+            idx_of(13, Suit::Hearts),
+            idx_of(9, Suit::Clubs),
+            idx_of(9, Suit::Diamonds),
+            idx_of(2, Suit::Hearts),
+        ];
+
+        let logup_cs = ConstraintSystem::<Fr>::new_ref();
+        let idxv = hand.map(|i| UInt8::new_witness(logup_cs.clone(), || Ok(i)).unwrap());
+        let before = logup_cs.num_constraints();
+        let decoded = lookup_card_value_gadget(logup_cs.clone(), &idxv).unwrap();
+        let logup_cost = logup_cs.num_constraints() - before;
+        assert!(logup_cs.is_satisfied().unwrap());
+        for (i, (rank, suit)) in decoded.iter().enumerate() {
+            assert_eq!(rank.value().unwrap(), hand[i] % 13 + 2);
+            assert_eq!(suit.value().unwrap(), hand[i] / 13);
+        }
+
+        let scan_cs = ConstraintSystem::<Fr>::new_ref();
+        let idxv = hand.map(|i| UInt8::new_witness(scan_cs.clone(), || Ok(i)).unwrap());
+        let before = scan_cs.num_constraints();
+        for (i, idx) in idxv.iter().enumerate() {
+            let (rank, suit) = linear_scan_decode(idx);
+            assert_eq!(rank.value().unwrap(), Fr::from(hand[i] % 13 + 2));
+            assert_eq!(suit.value().unwrap(), Fr::from(hand[i] / 13));
+        }
+        let scan_cost = scan_cs.num_constraints() - before;
+        assert!(scan_cs.is_satisfied().unwrap());
+
+        assert!(
+            logup_cost < scan_cost,
+            "logup decode used {logup_cost} constraints, linear scan {scan_cost}"
+        );
+    }
+
+    #[test]
+    fn lookup_scoring_matches_divmod_scoring_and_rejects_bad_indices() {
+        let hand = [
+            idx_of(14, Suit::Hearts),
+            idx_of(12, Suit::Hearts),
+            idx_of(9, Suit::Hearts),
+            idx_of(7, Suit::Hearts),
+            idx_of(2, Suit::Hearts),
+        ];
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let cat = HandCategoryVar::new_witness(cs.clone(), || Ok(HandCategory::Flush)).unwrap();
+        let idxv = hand.map(|i| UInt8::new_witness(cs.clone(), || Ok(i)).unwrap());
+        let best5 = Best5HandVar::new(cat, idxv);
+        let (lookup_score, _) = best5.score_and_tiebreak_with_lookup(cs.clone()).unwrap();
+        let (divmod_score, _) = best5.score_and_tiebreak().unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(lookup_score.value().unwrap(), divmod_score.value().unwrap());
+
+        // An index past the table and a repeated card both leave the lookup unsatisfied.
+        for bad in [[52u8, 3], [hand[0], hand[0]]] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let idxv = bad.map(|i| UInt8::new_witness(cs.clone(), || Ok(i)).unwrap());
+            lookup_card_value_gadget(cs.clone(), &idxv).unwrap();
+            assert!(!cs.is_satisfied().unwrap(), "lookup accepted {bad:?}");
+        }
+    }
+
+    #[test]
+    fn forged_card_index_is_unsatisfiable() {
+        // 52 would decode to "rank 2 of a fifth suit" if the divmod were unconstrained.
+        for forged in [52u8, 60, 255] {
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let cat =
+                HandCategoryVar::new_witness(cs.clone(), || Ok(HandCategory::HighCard)).unwrap();
+            let idx5 = [
+                forged,
+                idx_of(12, Suit::Hearts),
+                idx_of(9, Suit::Clubs),
+                idx_of(7, Suit::Spades),
+                idx_of(4, Suit::Diamonds),
+            ]
+            .map(|i| UInt8::new_witness(cs.clone(), || Ok(i)).unwrap());
+            let _ = Best5HandVar::new(cat, idx5).score_and_tiebreak();
+            assert!(
+                !cs.is_satisfied().unwrap(),
+                "forged card index {forged} was accepted"
+            );
+        }
+    }
+
     #[test]
     fn gadget_ordering_intra_category() {
         let make_five_combo_hand = |cat: HandCategory, idx: [u8; 5]| -> Fr {
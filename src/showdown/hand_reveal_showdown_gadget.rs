@@ -192,7 +192,7 @@ where
     check_constraint_satisfaction::<_>(&cs, "LogUp verification");
 
     // Step 4: Hand Scoring
-    let (score, tiebreak) = best5.score_and_tiebreak()?;
+    let (score, tiebreak) = best5.score_and_tiebreak_with_lookup(cs.clone())?;
 
     tracing::info!(target: LOG_TARGET, "Score of the hand {:?} {:?}", score.value(), tiebreak.value());
 
@@ -1,5 +1,6 @@
 //! showdown: shared types, constants, helpers
 
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use core::cmp::Ordering;
 use serde::{Deserialize, Serialize};
 
@@ -72,6 +73,54 @@ impl HandCategory {
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(HandCategory::HighCard),
+            1 => Some(HandCategory::OnePair),
+            2 => Some(HandCategory::TwoPair),
+            3 => Some(HandCategory::ThreeOfAKind),
+            4 => Some(HandCategory::Straight),
+            5 => Some(HandCategory::Flush),
+            6 => Some(HandCategory::FullHouse),
+            7 => Some(HandCategory::FourOfAKind),
+            8 => Some(HandCategory::StraightFlush),
+            _ => None,
+        }
+    }
+}
+
+impl CanonicalSerialize for HandCategory {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        _compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        writer.write_all(&[self.as_u8()])?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, _compress: ark_serialize::Compress) -> usize {
+        1
+    }
+}
+
+impl ark_serialize::Valid for HandCategory {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for HandCategory {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        _compress: ark_serialize::Compress,
+        _validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        HandCategory::from_u8(byte[0]).ok_or(ark_serialize::SerializationError::InvalidData)
+    }
 }
 
 /// Base-16 multipliers (no shifting) for packing (cat,c1..c5)
@@ -120,16 +169,19 @@ pub fn is_run_desc_ranks(r: &[Rank; 5]) -> bool {
 pub mod gadget;
 pub mod hand_reveal_showdown_gadget;
 pub mod native;
+pub mod reveal;
 
 #[cfg(test)]
 mod e2e;
 
 pub use native::{
-    choose_best5_from7, classify_five_and_canonicalize, pack_score_field, pack_score_u32,
-    tiebreak_vector, verify_and_score_five, verify_and_score_from_indices, Best5Hand,
-    Best5HandWithScore,
+    choose_best5_for_hole, choose_best5_from7, choose_best5_omaha, classify_five_and_canonicalize,
+    pack_score_field, pack_score_u32, tiebreak_vector, verify_and_score_five,
+    verify_and_score_from_indices, Best5Hand, Best5HandWithScore,
 };
 
+pub use reveal::{could_win_a_pot, reveal_order};
+
 pub use gadget::{
     // re-export gadget API
     verify_and_score_from_indices as verify_and_score_from_indices_gadget,
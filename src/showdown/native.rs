@@ -345,6 +345,52 @@ pub fn choose_best5_from7(idx7: [Index; 7]) -> Best5HandWithScore {
     }
 }
 
+/// Omaha: the best hand using exactly two of the four hole cards and exactly three of the five
+/// board cards (60 candidates).
+pub fn choose_best5_omaha(hole: [Index; 4], board: [Index; 5]) -> Best5HandWithScore {
+    let hole = hole.map(decode_card);
+    let board = board.map(decode_card);
+    let mut best: Option<Best5HandWithScore> = None;
+
+    for a in 0..3 {
+        for b in (a + 1)..4 {
+            for c in 0..3 {
+                for d in (c + 1)..4 {
+                    for e in (d + 1)..5 {
+                        let hand5 = [hole[a], hole[b], board[c], board[d], board[e]];
+                        let (cat, k5) = classify_five_and_canonicalize(hand5);
+                        let cvec = tiebreak_vector(cat, &k5);
+                        let score = pack_score_u32(cat, cvec);
+                        if best.is_none_or(|best| score > best.score_u32) {
+                            best = Some(Best5HandWithScore {
+                                hand: Best5Hand {
+                                    cards: k5,
+                                    category: cat,
+                                },
+                                tiebreak: cvec,
+                                score_u32: score,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best.expect("an Omaha hand always has candidate five-card hands")
+}
+
+/// Best hand for `hole` on `board`, dispatching on the hole-card count: two cards play
+/// hold'em (any five of seven), four play Omaha. Returns `None` for other counts.
+pub fn choose_best5_for_hole(hole: &[Index], board: [Index; 5]) -> Option<Best5HandWithScore> {
+    match *hole {
+        [h0, h1] => Some(choose_best5_from7([
+            h0, h1, board[0], board[1], board[2], board[3], board[4],
+        ])),
+        [h0, h1, h2, h3] => Some(choose_best5_omaha([h0, h1, h2, h3], board)),
+        _ => None,
+    }
+}
+
 /// Native scorer for a canonical 5-card hand (already validated)
 pub fn verify_and_score_five(cat: HandCategory, k5: &[Card; 5]) -> (u32, [u8; 5], Fr) {
     // assuming canonical/validated by caller
@@ -697,4 +743,29 @@ mod tests {
                 && sop > shc
         );
     }
+
+    #[test]
+    fn omaha_must_play_two_hole_cards() {
+        let hole = [
+            idx_of(14, Suit::Hearts),
+            idx_of(14, Suit::Spades),
+            idx_of(7, Suit::Diamonds),
+            idx_of(7, Suit::Clubs),
+        ];
+        let board = [
+            idx_of(2, Suit::Hearts),
+            idx_of(5, Suit::Hearts),
+            idx_of(8, Suit::Hearts),
+            idx_of(11, Suit::Hearts),
+            idx_of(13, Suit::Clubs),
+        ];
+
+        // Hold'em plays the ace of hearts with four board hearts; Omaha cannot.
+        let holdem = choose_best5_for_hole(&hole[..2], board).expect("hold'em");
+        assert_eq!(holdem.hand.category, HandCategory::Flush);
+        let omaha = choose_best5_for_hole(&hole, board).expect("omaha");
+        assert_eq!(omaha, choose_best5_omaha(hole, board));
+        assert_eq!(omaha.hand.category, HandCategory::OnePair);
+        assert!(choose_best5_for_hole(&hole[..3], board).is_none());
+    }
 }
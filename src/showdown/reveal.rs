@@ -0,0 +1,150 @@
+//! Showdown reveal order
+
+use std::collections::BTreeMap;
+
+use crate::engine::nl::{BettingState, PlayerStatus, Pots, SeatId};
+
+/// Order in which contenders must reveal at showdown.
+///
+/// The last aggressor shows first and the remaining contenders follow clockwise. With no
+/// aggressor (or one who is no longer in the hand) the first contender left of the button
+/// shows first. Folded and sitting-out seats are excluded.
+pub fn reveal_order(state: &BettingState, last_aggressor: Option<SeatId>) -> Vec<SeatId> {
+    let mut contenders: Vec<SeatId> = state
+        .players
+        .iter()
+        .filter(|p| matches!(p.status, PlayerStatus::Active | PlayerStatus::AllIn))
+        .map(|p| p.seat)
+        .collect();
+    contenders.sort_unstable();
+
+    let start = last_aggressor
+        .and_then(|aggressor| contenders.iter().position(|seat| *seat == aggressor))
+        .or_else(|| contenders.iter().position(|seat| *seat > state.button))
+        .unwrap_or(0);
+    contenders.rotate_left(start);
+    contenders
+}
+
+/// Whether a hand scoring `score` can still take a share of some pot `seat` is eligible for.
+///
+/// Only hands revealed so far count against it, so a pot with no other revealed contender is
+/// still winnable. Ties split the pot, which makes an equal score a winner too.
+pub fn could_win_a_pot(
+    pots: &Pots,
+    seat: SeatId,
+    score: u32,
+    revealed_scores: &BTreeMap<SeatId, u32>,
+) -> bool {
+    std::iter::once(&pots.main)
+        .chain(&pots.sides)
+        .filter(|pot| pot.eligible.contains(&seat))
+        .any(|pot| {
+            pot.eligible
+                .iter()
+                .filter(|other| **other != seat)
+                .filter_map(|other| revealed_scores.get(other))
+                .all(|best| score >= *best)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::nl::{
+        BettingEngineNL, EngineNL, HandConfig, PlayerState, Pot, Pots, TableStakes,
+    };
+
+    fn state_with(button: SeatId, statuses: &[(SeatId, PlayerStatus)]) -> BettingState {
+        let cfg = HandConfig {
+            stakes: TableStakes {
+                small_blind: 1,
+                big_blind: 2,
+                ante: 0,
+            },
+            button,
+            small_blind_seat: (button + 1) % statuses.len() as SeatId,
+            big_blind_seat: (button + 2) % statuses.len() as SeatId,
+            check_raise_allowed: true,
+            hole_cards: crate::engine::nl::types::HOLDEM_HOLE_CARDS,
+            max_bet_bb: None,
+            dealing_order: crate::engine::nl::types::DealingOrder::OneAtATime,
+        };
+        let players = statuses
+            .iter()
+            .map(|&(seat, status)| PlayerState {
+                seat,
+                player_id: None,
+                stack: 100,
+                committed_this_round: 0,
+                committed_total: 10,
+                status,
+                has_acted_this_round: true,
+            })
+            .collect();
+        let pots = Pots {
+            main: Pot {
+                amount: 0,
+                eligible: vec![],
+            },
+            sides: vec![],
+        };
+        EngineNL::new_after_deal(cfg, players, pots)
+    }
+
+    #[test]
+    fn starts_at_last_aggressor_and_proceeds_clockwise() {
+        let state = state_with(
+            0,
+            &[
+                (0, PlayerStatus::Active),
+                (1, PlayerStatus::Active),
+                (2, PlayerStatus::Folded),
+                (3, PlayerStatus::AllIn),
+                (4, PlayerStatus::Active),
+            ],
+        );
+
+        assert_eq!(reveal_order(&state, Some(3)), vec![3, 4, 0, 1]);
+        assert_eq!(reveal_order(&state, Some(4)), vec![4, 0, 1, 3]);
+    }
+
+    #[test]
+    fn without_aggressor_starts_left_of_button() {
+        let state = state_with(
+            3,
+            &[
+                (0, PlayerStatus::Active),
+                (1, PlayerStatus::Active),
+                (2, PlayerStatus::Active),
+                (3, PlayerStatus::Active),
+            ],
+        );
+
+        assert_eq!(reveal_order(&state, None), vec![0, 1, 2, 3]);
+        // A folded aggressor falls back to the seat left of the button.
+        let mut folded = state.clone();
+        folded.players[1].status = PlayerStatus::Folded;
+        assert_eq!(reveal_order(&folded, Some(1)), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn side_pot_keeps_a_beaten_hand_live() {
+        let pot = |eligible: Vec<SeatId>| Pot {
+            amount: 10,
+            eligible,
+        };
+        let revealed = BTreeMap::from([(0, 900), (2, 400)]);
+        let mut pots = Pots {
+            main: pot(vec![0, 1, 2]),
+            sides: vec![pot(vec![1, 2])],
+        };
+        // Loses the main pot to seat 0 but beats seat 2 for the side pot.
+        assert!(could_win_a_pot(&pots, 1, 500, &revealed));
+        // A tie still splits the side pot.
+        assert!(could_win_a_pot(&pots, 1, 400, &revealed));
+
+        pots.sides.clear();
+        assert!(!could_win_a_pot(&pots, 1, 500, &revealed));
+    }
+}
@@ -5,17 +5,22 @@ use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{AffineRepr, CurveConfig, CurveGroup};
 use ark_ff::{PrimeField, UniformRand, Zero};
 use ark_serialize::CanonicalSerialize;
-use ark_std::rand::Rng;
-use sha2::Sha256;
+use ark_std::rand::{rngs::StdRng, Rng, SeedableRng};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::curve_absorb::CurveAbsorb;
+use crate::engine::nl::types::SeatId;
 use crate::ledger::actor::ShufflerActor;
 use crate::ledger::messages::{
     sign_enveloped_action, AnyGameMessage, AnyMessageEnvelope, EnvelopedMessage,
-    GameBlindingDecryptionMessage, GameMessage, GamePartialUnblindingShareMessage,
-    GameShuffleMessage, MetadataEnvelope,
+    GameBlindingDecryptionMessage, GameMessage, GameNonceCommitmentMessage,
+    GamePartialUnblindingShareMessage, GameShuffleMessage, MetadataEnvelope,
 };
+use crate::ledger::types::HandId;
+use crate::ledger::CanonicalKey;
 use crate::shuffling::data_structures::ShuffleProof;
 use crate::shuffling::{
     bayer_groth::decomposition::random_permutation as bg_random_permutation,
@@ -24,8 +29,9 @@ use crate::shuffling::{
     DECK_SIZE,
 };
 use crate::signing::{DomainSeparated, SignatureBytes, WithSignature};
+use crate::vrf::{commit_nonce, shuffle_seed, VrfNonce};
 
-use super::Deck;
+use super::{Deck, ShufflerScheme, SHUFFLER_SIGNING_SALT};
 
 pub trait ShufflerSigningSecret<C: CurveGroup> {
     fn as_scalar(&self) -> C::ScalarField;
@@ -33,15 +39,36 @@ pub trait ShufflerSigningSecret<C: CurveGroup> {
 
 pub trait ShufflerSigningParameters<C: CurveGroup> {
     fn set_canonical_generator(&mut self);
+    fn set_published_salt(&mut self);
 }
 
-impl<C: CurveGroup> ShufflerSigningParameters<C> for SchnorrParameters<C, Sha256> {
+impl<C: CurveGroup, D: Digest> ShufflerSigningParameters<C> for SchnorrParameters<C, D> {
     fn set_canonical_generator(&mut self) {
         self.generator = C::generator().into_affine();
     }
+
+    fn set_published_salt(&mut self) {
+        self.salt = SHUFFLER_SIGNING_SALT;
+    }
+}
+
+/// Identifies the hole card a blinding contribution was generated for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlindingCacheKey<C: CurveGroup> {
+    pub hand_id: HandId,
+    pub seat: SeatId,
+    pub hole_index: u8,
+    pub player_key: CanonicalKey<C>,
 }
 
-pub struct ShufflerEngine<C, S>
+struct CachedBlinding<C: CurveGroup> {
+    deck_digest: [u8; 32],
+    contribution: PlayerTargetedBlindingContribution<C>,
+}
+
+type BlindingCache<C> = Arc<Mutex<HashMap<BlindingCacheKey<C>, CachedBlinding<C>>>>;
+
+pub struct ShufflerEngine<C, S = ShufflerScheme<C>>
 where
     C: CurveGroup,
     S: SignatureScheme<PublicKey = C::Affine>,
@@ -50,6 +77,7 @@ where
     pub secret_key: Arc<S::SecretKey>,
     pub public_key: C,
     pub signing_params: Arc<S::Parameters>,
+    blinding_cache: BlindingCache<C>,
 }
 
 impl<C, S> ShufflerEngine<C, S>
@@ -74,6 +102,7 @@ where
         let mut params =
             S::setup(rng).map_err(|e| anyhow!("failed to setup signature scheme: {}", e))?;
         params.set_canonical_generator();
+        params.set_published_salt();
         let signing_params = Arc::new(params);
 
         // Generate keypair
@@ -87,6 +116,7 @@ where
             secret_key: Arc::new(secret_key),
             public_key,
             signing_params,
+            blinding_cache: Default::default(),
         })
     }
 
@@ -99,6 +129,7 @@ where
             secret_key,
             public_key,
             signing_params,
+            blinding_cache: Default::default(),
         }
     }
 
@@ -106,6 +137,31 @@ where
     pub fn encryption_scalar(&self) -> C::ScalarField {
         self.secret_key.as_ref().as_scalar()
     }
+
+    /// Whether a contribution for `key` generated against `deck` is cached.
+    pub fn is_blinding_cached(&self, key: &BlindingCacheKey<C>, deck: &Deck<C, DECK_SIZE>) -> bool {
+        let Ok(digest) = deck_digest(deck) else {
+            return false;
+        };
+        self.blinding_cache
+            .lock()
+            .get(key)
+            .is_some_and(|cached| cached.deck_digest == digest)
+    }
+
+    /// Drop every cached blinding contribution for `hand_id`, once the hand is finished.
+    pub fn evict_blinding_hand(&self, hand_id: HandId) {
+        self.blinding_cache
+            .lock()
+            .retain(|key, _| key.hand_id != hand_id);
+    }
+}
+
+fn deck_digest<C: CurveGroup>(deck: &Deck<C, DECK_SIZE>) -> Result<[u8; 32]> {
+    let mut bytes = Vec::new();
+    deck.serialize_compressed(&mut bytes)
+        .map_err(|err| anyhow!("failed to serialize deck for blinding cache: {err}"))?;
+    Ok(Sha256::digest(&bytes).into())
 }
 
 pub trait ShufflerApi<C, S>
@@ -137,6 +193,22 @@ where
         C::Affine: Absorb,
         C: CurveAbsorb<C::BaseField>;
 
+    /// Like [`Self::provide_blinding_player_decryption_share`], but returns the contribution
+    /// previously generated for the same hole card as long as the deck has not changed.
+    fn cached_blinding_player_decryption_share<R: Rng>(
+        &self,
+        key: &BlindingCacheKey<C>,
+        deck: &Deck<C, DECK_SIZE>,
+        aggregated_public_key: &C,
+        player_public_key: C,
+        rng: &mut R,
+    ) -> Result<PlayerTargetedBlindingContribution<C>>
+    where
+        C::BaseField: PrimeField,
+        C::ScalarField: PrimeField + Absorb,
+        C::Affine: Absorb,
+        C: CurveAbsorb<C::BaseField>;
+
     fn provide_unblinding_decryption_share(
         &self,
         player_ciphertext: &PlayerAccessibleCiphertext<C>,
@@ -154,12 +226,29 @@ where
         C::ScalarField: PrimeField + Absorb,
         C: CurveAbsorb<C::BaseField>;
 
+    /// Signs the `Poseidon(nonce)` commitment a shuffler publishes before its turn.
+    fn commit_nonce_and_sign<R: Rng>(
+        &self,
+        ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
+        vrf_nonce: &C::BaseField,
+        rng: &mut R,
+    ) -> Result<(
+        EnvelopedMessage<C, GameNonceCommitmentMessage<C>>,
+        AnyMessageEnvelope<C>,
+    )>
+    where
+        C::BaseField: PrimeField,
+        S::Signature: SignatureBytes;
+
+    /// Shuffles with randomness derived from `VRF(sk, vrf_nonce)` and reveals the nonce, along
+    /// with the beacon round it came from, if any.
     fn shuffle_and_sign<R: Rng>(
         &self,
         aggregated_public_key: &C,
         ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
         deck_in: &Deck<C, DECK_SIZE>,
         turn_index: u16,
+        vrf_nonce: &VrfNonce<C::BaseField>,
         rng: &mut R,
     ) -> Result<(
         EnvelopedMessage<C, GameShuffleMessage<C>>,
@@ -189,6 +278,21 @@ where
         C: CurveAbsorb<C::BaseField>,
         S::Signature: SignatureBytes;
 
+    /// Signs a blinding message carrying an already generated contribution.
+    fn sign_player_blinding<R: Rng>(
+        &self,
+        ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
+        deal_index: u8,
+        player_public_key: &C,
+        contribution: PlayerTargetedBlindingContribution<C>,
+        rng: &mut R,
+    ) -> Result<(
+        EnvelopedMessage<C, GameBlindingDecryptionMessage<C>>,
+        AnyMessageEnvelope<C>,
+    )>
+    where
+        S::Signature: SignatureBytes;
+
     fn player_unblinding_and_sign<R: Rng>(
         &self,
         ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
@@ -267,6 +371,43 @@ where
         Ok(contribution)
     }
 
+    fn cached_blinding_player_decryption_share<R: Rng>(
+        &self,
+        key: &BlindingCacheKey<C>,
+        deck: &Deck<C, DECK_SIZE>,
+        aggregated_public_key: &C,
+        player_public_key: C,
+        rng: &mut R,
+    ) -> Result<PlayerTargetedBlindingContribution<C>>
+    where
+        C::BaseField: PrimeField,
+        C::ScalarField: PrimeField + Absorb,
+        C::Affine: Absorb,
+        C: CurveAbsorb<C::BaseField>,
+    {
+        let digest = deck_digest(deck)?;
+        let mut cache = self.blinding_cache.lock();
+        if let Some(cached) = cache.get(key) {
+            if cached.deck_digest == digest {
+                return Ok(cached.contribution.clone());
+            }
+        }
+
+        let contribution = self.provide_blinding_player_decryption_share(
+            aggregated_public_key,
+            player_public_key,
+            rng,
+        )?;
+        cache.insert(
+            key.clone(),
+            CachedBlinding {
+                deck_digest: digest,
+                contribution: contribution.clone(),
+            },
+        );
+        Ok(contribution)
+    }
+
     fn provide_unblinding_decryption_share(
         &self,
         player_ciphertext: &PlayerAccessibleCiphertext<C>,
@@ -303,12 +444,30 @@ where
         Ok(share)
     }
 
+    fn commit_nonce_and_sign<R: Rng>(
+        &self,
+        ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
+        vrf_nonce: &C::BaseField,
+        rng: &mut R,
+    ) -> Result<(
+        EnvelopedMessage<C, GameNonceCommitmentMessage<C>>,
+        AnyMessageEnvelope<C>,
+    )>
+    where
+        C::BaseField: PrimeField,
+        S::Signature: SignatureBytes,
+    {
+        let message = GameNonceCommitmentMessage::new(commit_nonce(vrf_nonce));
+        self.sign_and_wrap(ctx, message, rng)
+    }
+
     fn shuffle_and_sign<R: Rng>(
         &self,
         aggregated_public_key: &C,
         ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
         deck_in: &Deck<C, DECK_SIZE>,
         turn_index: u16,
+        vrf_nonce: &VrfNonce<C::BaseField>,
         rng: &mut R,
     ) -> Result<(
         EnvelopedMessage<C, GameShuffleMessage<C>>,
@@ -320,9 +479,17 @@ where
         C::BaseField: PrimeField,
         S::Signature: SignatureBytes,
     {
-        let (deck_out, proof) =
-            self.shuffle::<DECK_SIZE, _>(aggregated_public_key, deck_in, rng)?;
-        let message = GameShuffleMessage::new(deck_in.clone(), deck_out, proof, turn_index);
+        let mut shuffle_rng =
+            StdRng::from_seed(shuffle_seed(&vrf_nonce.value, &self.secret_scalar()));
+        let (deck_out, proof) = crate::metrics_time!(
+            PROOF_GEN_SECONDS,
+            self.shuffle::<DECK_SIZE, _>(aggregated_public_key, deck_in, &mut shuffle_rng)
+        )?;
+        let mut message = GameShuffleMessage::new(deck_in.clone(), deck_out, proof, turn_index)
+            .with_vrf_nonce(vrf_nonce.value);
+        if let Some(round) = vrf_nonce.beacon_round {
+            message = message.with_beacon_round(round);
+        }
         self.sign_and_wrap(ctx, message, rng)
     }
 
@@ -349,6 +516,23 @@ where
             player_public_key.clone(),
             rng,
         )?;
+        self.sign_player_blinding(ctx, deal_index, player_public_key, contribution, rng)
+    }
+
+    fn sign_player_blinding<R: Rng>(
+        &self,
+        ctx: &MetadataEnvelope<C, ShufflerActor<C>>,
+        deal_index: u8,
+        player_public_key: &C,
+        contribution: PlayerTargetedBlindingContribution<C>,
+        rng: &mut R,
+    ) -> Result<(
+        EnvelopedMessage<C, GameBlindingDecryptionMessage<C>>,
+        AnyMessageEnvelope<C>,
+    )>
+    where
+        S::Signature: SignatureBytes,
+    {
         let message =
             GameBlindingDecryptionMessage::new(deal_index, contribution, player_public_key.clone());
         self.sign_and_wrap(ctx, message, rng)
@@ -442,6 +626,7 @@ where
                 value: AnyGameMessage::from(envelope.message.value.clone()),
                 signature: envelope.message.signature.clone(),
             },
+            correlation_id: None,
         }
     }
 }
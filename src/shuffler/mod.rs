@@ -9,7 +9,7 @@ use ark_ec::{CurveConfig, CurveGroup, PrimeGroup};
 use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::CanonicalSerialize;
 use rand::rngs::StdRng;
-use rand::RngCore;
+use rand::{RngCore, SeedableRng};
 use sha2::Sha256;
 
 use std::collections::HashMap;
@@ -17,9 +17,11 @@ use std::convert::TryInto;
 use tracing::{debug, info};
 
 mod api;
+mod proving_queue;
 mod service;
 mod state;
 
+pub use proving_queue::{ProvingPriority, ProvingQueue};
 pub use service::{ShufflerRunConfig, ShufflerService};
 pub use state::{
     BoardCardShufflerRequest, BoardCardSlot, DealShufflerRequest, DealingHandState, HandResources,
@@ -32,11 +34,37 @@ use crate::ledger::lobby::types::PlayerRecord;
 use crate::shuffling::player_decryption::recover_card_value;
 use crate::shuffling::ElGamalCiphertext;
 
-pub use api::{ShufflerApi, ShufflerEngine};
+pub use api::{BlindingCacheKey, ShufflerApi, ShufflerEngine};
 
 pub type Deck<C, const N: usize> = [ElGamalCiphertext<C>; N];
 
-pub type ShufflerScheme<C> = Schnorr<C, Sha256>;
+/// Schnorr signatures used by shufflers. The challenge hash defaults to SHA-256; pick another
+/// digest (e.g. Keccak-256) when signatures must be checked by another ecosystem, and verify
+/// ledger messages with a `SchnorrSignatureValidator` over the same digest.
+pub type ShufflerScheme<C, D = Sha256> = Schnorr<C, D>;
+
+/// Salt mixed into every shuffler Schnorr challenge. It is fixed and published so players and
+/// external verifiers can rebuild [`shuffler_signing_params`] without asking the server.
+pub const SHUFFLER_SIGNING_SALT: [u8; 32] = *b"legit-poker:shuffler-schnorr:v01";
+
+/// Schnorr parameters every shuffler signs with: the curve's canonical generator, which shuffler
+/// keys are derived from (`C::generator() * sk`), and [`SHUFFLER_SIGNING_SALT`].
+///
+/// The result is deterministic, so the verifier and any player holding the curve agree on it.
+pub fn shuffler_signing_params<C>() -> Result<<ShufflerScheme<C> as SignatureScheme>::Parameters>
+where
+    C: CurveGroup,
+{
+    use api::ShufflerSigningParameters;
+
+    // `Parameters` has a private field, so build it through `setup` and overwrite both public
+    // fields; the seeded rng only keeps the discarded draws reproducible.
+    let mut params = ShufflerScheme::<C>::setup(&mut StdRng::from_seed([0u8; 32]))
+        .map_err(|err| anyhow::anyhow!("failed to setup shuffler Schnorr parameters: {err}"))?;
+    params.set_canonical_generator();
+    params.set_published_salt();
+    Ok(params)
+}
 
 /// Aggregate multiple shuffler public keys into a single aggregated key.
 ///
@@ -68,7 +96,7 @@ const DEAL_CHANNEL_CAPACITY: usize = 1024;
 use crate::ledger::hash::LedgerHasher;
 use crate::ledger::messages::{
     EnvelopedMessage, FinalizedAnyMessageEnvelope, GameBlindingDecryptionMessage,
-    GamePartialUnblindingShareMessage, GameShuffleMessage,
+    GameNonceCommitmentMessage, GamePartialUnblindingShareMessage, GameShuffleMessage,
 };
 use crate::ledger::snapshot::{
     clone_snapshot_for_failure, AnyTableSnapshot, SnapshotStatus, TableAtDealing, TableAtShuffling,
@@ -104,6 +132,25 @@ where
 {
     let expected_shuffles = shuffler_engines.len();
 
+    // The ledger only accepts the first shuffle once every shuffler has committed to its nonce.
+    for (state, engine) in shuffler_states.iter_mut().zip(shuffler_engines) {
+        let Some(any_envelope) = state.try_emit_nonce_commitment::<S, _>(engine)? else {
+            continue;
+        };
+        let commitment_envelope: EnvelopedMessage<C, GameNonceCommitmentMessage<C>> =
+            (&any_envelope).try_into()?;
+        let next_snapshot = apply_transition(current_snapshot, &commitment_envelope, hasher)?;
+        current_snapshot = match next_snapshot {
+            AnyTableSnapshot::Shuffling(snapshot) => snapshot,
+            other => {
+                return Err(anyhow::anyhow!(
+                    "unexpected snapshot variant after nonce commitment: {:?}",
+                    std::mem::discriminant(&other)
+                ));
+            }
+        };
+    }
+
     for index in 0..expected_shuffles {
         let actor = {
             let state = &shuffler_states[index];
@@ -500,7 +547,10 @@ where
                 collected_unblinding_shares.clone(),
                 num_shufflers,
             )
-            .map_err(|e| anyhow::anyhow!("{}", e))?;
+            .map_err(|e| {
+                crate::metrics_counter_inc!(DECRYPT_FAILURES);
+                anyhow::anyhow!("{}", e)
+            })?;
 
             info!(
                 target: LOG_TARGET,
@@ -544,18 +594,21 @@ mod tests {
     use super::*;
     use crate::chaum_pedersen::ChaumPedersenProof;
     use crate::ledger::actor::ShufflerActor;
-    use crate::ledger::messages::AnyGameMessage;
+    use crate::ledger::messages::{AnyGameMessage, MetadataEnvelope};
     use crate::ledger::snapshot::{CardDestination, DealtCard};
     use crate::ledger::test_support::{
         fixture_dealing_snapshot, fixture_preflop_snapshot, FixtureContext,
     };
+    use crate::ledger::CanonicalKey;
     use crate::shuffler::BoardCardSlot;
+    use crate::shuffling::deck_source::{DeckSource, PlaintextDeck};
     use crate::shuffling::player_decryption::PlayerAccessibleCiphertext;
     use crate::shuffling::{
         combine_blinding_contributions_for_player, decrypt_community_card,
         generate_random_ciphertexts, make_global_public_keys, recover_card_value,
         PartialUnblindingShare, PlayerTargetedBlindingContribution, DECK_SIZE,
     };
+    use crate::vrf::{commit_nonce, VrfNonce};
     use ark_crypto_primitives::signature::SignatureScheme;
     use ark_ec::PrimeGroup;
     use ark_ff::Zero;
@@ -584,9 +637,8 @@ mod tests {
             public_keys.push(public_key);
         }
         let aggregated_public_key = make_global_public_keys(public_keys.clone());
-        let signing_params = Arc::new(
-            ShufflerScheme::<GrumpkinProjective>::setup(&mut rng).expect("schnorr params"),
-        );
+        let signing_params =
+            Arc::new(shuffler_signing_params::<GrumpkinProjective>().expect("schnorr params"));
         let shufflers: Vec<_> = secrets
             .into_iter()
             .zip(public_keys.into_iter())
@@ -668,6 +720,74 @@ mod tests {
         assert_eq!(recovered, expected_value);
     }
 
+    #[test]
+    fn shuffle_is_fixed_by_the_committed_nonce() {
+        type BaseField = <GrumpkinProjective as CurveGroup>::BaseField;
+
+        let mut rng = test_rng();
+        let secret = <GrumpkinProjective as PrimeGroup>::ScalarField::rand(&mut rng);
+        let public_key = GrumpkinProjective::generator() * secret;
+        let signing_params =
+            Arc::new(shuffler_signing_params::<GrumpkinProjective>().expect("schnorr params"));
+        let engine = ShufflerEngine::<GrumpkinProjective, ShufflerScheme<GrumpkinProjective>>::new(
+            Arc::new(SchnorrSecretKey::<GrumpkinProjective>(secret)),
+            public_key,
+            signing_params,
+        );
+        let ctx = MetadataEnvelope {
+            hand_id: 1,
+            game_id: 1,
+            actor: ShufflerActor {
+                shuffler_id: 0,
+                shuffler_key: CanonicalKey::new(public_key),
+            },
+            nonce: 0,
+            public_key,
+        };
+        let (deck, _) =
+            generate_random_ciphertexts::<GrumpkinProjective, DECK_N>(&public_key, &mut rng);
+        let nonce = BaseField::rand(&mut rng);
+
+        let (_, commitment) = engine
+            .commit_nonce_and_sign(&ctx, &nonce, &mut rng)
+            .expect("commit nonce");
+        match commitment.message.value {
+            AnyGameMessage::NonceCommitment(message) => {
+                assert_eq!(message.commitment, commit_nonce(&nonce));
+            }
+            other => panic!("expected nonce commitment, got {:?}", other),
+        }
+
+        let shuffle = |nonce: &BaseField, beacon_round: Option<u64>, seed: u64| {
+            let (typed, _) = engine
+                .shuffle_and_sign(
+                    &public_key,
+                    &ctx,
+                    &deck,
+                    0,
+                    &VrfNonce {
+                        value: *nonce,
+                        beacon_round,
+                    },
+                    &mut StdRng::seed_from_u64(seed),
+                )
+                .expect("shuffle");
+            typed.message.value
+        };
+        let first = shuffle(&nonce, None, 1);
+        let second = shuffle(&nonce, None, 2);
+        assert_eq!(first.vrf_nonce, Some(nonce));
+        assert_eq!(first.beacon_round, None);
+        assert_eq!(first.deck_out, second.deck_out);
+        let other = shuffle(&(nonce + BaseField::from(1u64)), None, 1);
+        assert_ne!(first.deck_out, other.deck_out);
+
+        // A beacon nonce carries its round onto the message without changing the shuffle.
+        let beacon = shuffle(&nonce, Some(4_242_424), 1);
+        assert_eq!(beacon.beacon_round, Some(4_242_424));
+        assert_eq!(beacon.deck_out, first.deck_out);
+    }
+
     #[test]
     fn test_community_decryption_flow() {
         let mut rng = test_rng();
@@ -681,9 +801,8 @@ mod tests {
             public_keys.push(public_key);
         }
         let aggregated_public_key = make_global_public_keys(public_keys.clone());
-        let signing_params = Arc::new(
-            ShufflerScheme::<GrumpkinProjective>::setup(&mut rng).expect("schnorr params"),
-        );
+        let signing_params =
+            Arc::new(shuffler_signing_params::<GrumpkinProjective>().expect("schnorr params"));
         let shufflers: Vec<_> = secrets
             .into_iter()
             .zip(public_keys.into_iter())
@@ -729,7 +848,7 @@ mod tests {
         let shuffle_secret = <Curve as PrimeGroup>::ScalarField::rand(&mut rng);
         let public_key = Curve::generator() * shuffle_secret;
 
-        let schnorr_params = ShufflerScheme::<Curve>::setup(&mut rng).expect("schnorr params");
+        let schnorr_params = shuffler_signing_params::<Curve>().expect("schnorr params");
         let signing_secret = SchnorrSecretKey::<Curve>(shuffle_secret.clone());
 
         let (submit_tx, mut submit_rx) = mpsc::channel(8);
@@ -748,9 +867,7 @@ mod tests {
         );
 
         let key = (11i64, 22i64);
-        let zero_cipher = ElGamalCiphertext::new(Curve::generator(), Curve::generator());
-        let deck: [ElGamalCiphertext<Curve>; DECK_SIZE] =
-            core::array::from_fn(|_| zero_cipher.clone());
+        let deck = PlaintextDeck::canonical().encrypted_deck(&public_key, &mut rng);
         let runtime_key = crate::ledger::CanonicalKey::new(Curve::zero());
         let state = ShufflerHandState {
             game_id: key.0,
@@ -768,6 +885,8 @@ mod tests {
                 initial_deck: deck.clone(),
                 latest_deck: deck,
                 acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
             },
             dealing: DealingHandState::new(),
         };
@@ -849,7 +968,7 @@ mod tests {
         let shuffle_secret = <Curve as PrimeGroup>::ScalarField::rand(&mut rng);
         let public_key = Curve::generator() * shuffle_secret;
 
-        let schnorr_params = ShufflerScheme::<Curve>::setup(&mut rng).expect("schnorr params");
+        let schnorr_params = shuffler_signing_params::<Curve>().expect("schnorr params");
         let signing_secret = SchnorrSecretKey::<Curve>(shuffle_secret.clone());
 
         let (submit_tx, mut submit_rx) = mpsc::channel(4);
@@ -868,8 +987,7 @@ mod tests {
         );
 
         let key = (5i64, 6i64);
-        let zero_cipher = ElGamalCiphertext::new(Curve::zero(), Curve::zero());
-        let deck = core::array::from_fn(|_| zero_cipher.clone());
+        let deck = PlaintextDeck::canonical().encrypted_deck(&public_key, &mut rng);
         let runtime_key = crate::ledger::CanonicalKey::new(Curve::zero());
         let state = ShufflerHandState {
             game_id: key.0,
@@ -885,8 +1003,10 @@ mod tests {
                 expected_order: vec![runtime_key.clone()],
                 buffered: Vec::new(),
                 initial_deck: deck.clone(),
-                latest_deck: deck,
+                latest_deck: deck.clone(),
                 acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
             },
             dealing: DealingHandState::new(),
         };
@@ -913,7 +1033,7 @@ mod tests {
             deal_index: 7,
             slot: BoardCardSlot::Flop(0),
             ciphertext: DealtCard {
-                cipher: ElGamalCiphertext::new(Curve::generator(), Curve::generator()),
+                cipher: deck[0].clone(),
                 source_index: Some(0),
             },
         };
@@ -930,6 +1050,85 @@ mod tests {
         shuffler.cancel_all();
     }
 
+    #[tokio::test]
+    async fn cancelling_the_hand_token_stops_the_deal_worker() {
+        type Curve = GrumpkinProjective;
+
+        let mut rng = StdRng::seed_from_u64(0xC0FFEEu64);
+        let shuffle_secret = <Curve as PrimeGroup>::ScalarField::rand(&mut rng);
+        let public_key = Curve::generator() * shuffle_secret;
+
+        let schnorr_params = shuffler_signing_params::<Curve>().expect("schnorr params");
+        let signing_secret = SchnorrSecretKey::<Curve>(shuffle_secret.clone());
+
+        let (submit_tx, _submit_rx) = mpsc::channel(4);
+        let (events_tx, _) = broadcast::channel(4);
+        let (snapshots_tx, _) = broadcast::channel(4);
+        let shuffler = ShufflerService::<Curve, ShufflerScheme<Curve>>::new(
+            0,
+            public_key.clone(),
+            public_key.clone(),
+            signing_secret,
+            schnorr_params,
+            submit_tx,
+            ShufflerRunConfig::new([3u8; 32]),
+            events_tx.subscribe(),
+            snapshots_tx.subscribe(),
+        );
+
+        let deck = PlaintextDeck::canonical().encrypted_deck(&public_key, &mut rng);
+        let runtime_key = crate::ledger::CanonicalKey::new(Curve::zero());
+        let state = ShufflerHandState {
+            game_id: 7,
+            hand_id: 8,
+            shuffler_id: 0,
+            shuffler_index: 0,
+            shuffler_key: runtime_key.clone(),
+            next_nonce: 0,
+            aggregated_public_key: public_key.clone(),
+            shuffling_rng: StdRng::seed_from_u64(0xDDDDu64),
+            dealing_rng: StdRng::seed_from_u64(0xDDDDu64),
+            shuffling: ShufflingHandState {
+                expected_order: vec![runtime_key.clone()],
+                buffered: Vec::new(),
+                initial_deck: deck.clone(),
+                latest_deck: deck,
+                acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
+            },
+            dealing: DealingHandState::new(),
+        };
+        let runtime = Arc::new(HandResources::new(state, Weak::new()));
+
+        // Keep the sender alive so the worker can only stop through cancellation.
+        let (_deal_tx, deal_rx) = broadcast::channel::<DealShufflerRequest<Curve>>(4);
+        let actor = ShufflerActor {
+            shuffler_id: 0,
+            shuffler_key: crate::ledger::CanonicalKey::new(shuffler.public_key().clone()),
+        };
+        let deal_handle = crate::shuffler::service::spawn_dealing_request_worker_for_tests(
+            0,
+            Arc::clone(&runtime),
+            deal_rx,
+            crate::shuffler::service::submit_sender_for_tests(&shuffler),
+            crate::shuffler::service::engine_for_tests(&shuffler),
+            &actor,
+        );
+        // A child that never polls the token must still be stopped by the hand.
+        let stubborn_handle = runtime.spawn_child("stubborn_child", std::future::pending::<()>());
+
+        runtime.cancel.cancel();
+        timeout(Duration::from_secs(1), deal_handle)
+            .await
+            .expect("deal worker exits after cancellation")
+            .expect("deal worker did not panic");
+        timeout(Duration::from_secs(1), stubborn_handle)
+            .await
+            .expect("child ignoring the token exits after cancellation")
+            .expect("child did not panic");
+    }
+
     #[test]
     fn dealing_state_emits_blinding_then_unblinding() {
         type Curve = GrumpkinProjective;
@@ -955,6 +1154,8 @@ mod tests {
                     crate::shuffling::ElGamalCiphertext::new(Curve::zero(), Curve::zero())
                 }),
                 acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
             },
             0,
             Curve::zero(),
@@ -1046,6 +1247,8 @@ mod tests {
                     crate::shuffling::ElGamalCiphertext::new(Curve::zero(), Curve::zero())
                 }),
                 acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
             },
             0,
             Curve::zero(),
@@ -1119,6 +1322,8 @@ mod tests {
                     crate::shuffling::ElGamalCiphertext::new(Curve::zero(), Curve::zero())
                 }),
                 acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
             },
             0,
             Curve::zero(),
@@ -1199,6 +1404,8 @@ mod tests {
                     crate::shuffling::ElGamalCiphertext::new(Curve::zero(), Curve::zero())
                 }),
                 acted: false,
+                vrf_nonce: Default::default(),
+                nonce_committed: false,
             },
             0,
             Curve::zero(),
@@ -1237,22 +1444,18 @@ mod tests {
         let second = state
             .process_snapshot_and_make_responses(&table)
             .expect("process snapshot");
-        let mut flop_slots: Vec<u8> = second
+        let mut board_slots: Vec<BoardCardSlot> = second
             .iter()
             .filter_map(|req| match req {
-                DealShufflerRequest::Board(board) => match board.slot {
-                    BoardCardSlot::Flop(idx) => Some(idx),
-                    _ => None,
-                },
+                DealShufflerRequest::Board(board) => Some(board.slot),
                 _ => None,
             })
             .collect();
-        flop_slots.sort_unstable();
-        assert_eq!(flop_slots, vec![0, 1, 2]);
-        assert!(second.iter().all(|req| match req {
-            DealShufflerRequest::Board(board) => matches!(board.slot, BoardCardSlot::Flop(_)),
-            _ => true,
-        }));
+        board_slots.sort_unstable();
+        assert_eq!(
+            board_slots,
+            BoardCardSlot::all_in_order().take(3).collect::<Vec<_>>()
+        );
 
         // Mark flop cards as revealed to unlock the turn.
         for (&deal_index, destination) in table.dealing.card_plan.iter() {
@@ -1319,4 +1522,58 @@ mod tests {
             DealShufflerRequest::PlayerBlinding(_) | DealShufflerRequest::PlayerUnblinding(_)
         )));
     }
+
+    #[test]
+    fn blinding_contribution_is_served_from_cache_until_deck_changes() {
+        type Curve = GrumpkinProjective;
+        let mut rng = test_rng();
+
+        let shuffler =
+            ShufflerEngine::<Curve, ShufflerScheme<Curve>>::generate(&mut rng).expect("shuffler");
+        let agg_pk = shuffler.public_key;
+        let player_pk = Curve::generator() * <Curve as PrimeGroup>::ScalarField::rand(&mut rng);
+        let (deck, _) = generate_random_ciphertexts::<Curve, DECK_SIZE>(&agg_pk, &mut rng);
+        let key = BlindingCacheKey {
+            hand_id: 7,
+            seat: 2,
+            hole_index: 1,
+            player_key: crate::ledger::CanonicalKey::new(player_pk),
+        };
+        assert!(!shuffler.is_blinding_cached(&key, &deck));
+
+        let serialize = |contribution: &PlayerTargetedBlindingContribution<Curve>| {
+            let mut bytes = Vec::new();
+            contribution
+                .serialize_compressed(&mut bytes)
+                .expect("serialize contribution");
+            bytes
+        };
+
+        let first = shuffler
+            .cached_blinding_player_decryption_share(&key, &deck, &agg_pk, player_pk, &mut rng)
+            .expect("first contribution");
+        assert!(shuffler.is_blinding_cached(&key, &deck));
+        let second = shuffler
+            .cached_blinding_player_decryption_share(&key, &deck, &agg_pk, player_pk, &mut rng)
+            .expect("second contribution");
+        assert_eq!(serialize(&first), serialize(&second));
+
+        let (reshuffled, _) = shuffler.shuffle(&agg_pk, &deck, &mut rng).expect("shuffle");
+        assert!(!shuffler.is_blinding_cached(&key, &reshuffled));
+        let regenerated = shuffler
+            .cached_blinding_player_decryption_share(
+                &key,
+                &reshuffled,
+                &agg_pk,
+                player_pk,
+                &mut rng,
+            )
+            .expect("regenerated contribution");
+        assert_ne!(serialize(&first), serialize(&regenerated));
+
+        shuffler.evict_blinding_hand(key.hand_id + 1);
+        assert!(shuffler.is_blinding_cached(&key, &reshuffled));
+        shuffler.evict_blinding_hand(key.hand_id);
+        assert!(!shuffler.is_blinding_cached(&key, &reshuffled));
+    }
 }
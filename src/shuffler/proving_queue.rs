@@ -0,0 +1,194 @@
+//! Prioritised queue for shuffle proving work.
+//!
+//! Shuffle proofs are CPU heavy. Rather than every hand proving on its own task, shufflers
+//! submit jobs here and a fixed pool of workers runs them on Tokio's blocking pool, highest
+//! [`ProvingPriority`] first and in submission order within a priority.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::sync::{oneshot, Semaphore};
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::warn;
+
+use super::{spawn_named_task, LOG_TARGET};
+
+/// Higher values are proved first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProvingPriority(pub u32);
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+struct QueuedJob {
+    priority: ProvingPriority,
+    sequence: u64,
+    job: Job,
+}
+
+impl PartialEq for QueuedJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedJob {}
+
+impl PartialOrd for QueuedJob {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedJob {
+    /// Max-heap order: higher priority, then earlier submission.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+struct QueueInner {
+    jobs: Mutex<BinaryHeap<QueuedJob>>,
+    /// One permit per queued job.
+    pending: Semaphore,
+    next_sequence: AtomicU64,
+    workers: usize,
+}
+
+/// Handle to a pool of proving workers. Clones share the pool, which stops once every handle
+/// has been dropped; jobs still queued at that point are discarded.
+#[derive(Clone)]
+pub struct ProvingQueue {
+    inner: Arc<QueueInner>,
+    _stop: Arc<DropGuard>,
+}
+
+impl ProvingQueue {
+    /// Start `workers` worker tasks on the current Tokio runtime.
+    pub fn new(workers: usize) -> Self {
+        assert!(workers > 0, "proving queue needs at least one worker");
+        let inner = Arc::new(QueueInner {
+            jobs: Mutex::new(BinaryHeap::new()),
+            pending: Semaphore::new(0),
+            next_sequence: AtomicU64::new(0),
+            workers,
+        });
+        let stop = CancellationToken::new();
+        for index in 0..workers {
+            spawn_named_task(
+                format!("proving-worker-{index}"),
+                run_worker(Arc::clone(&inner), stop.clone()),
+            );
+        }
+        Self {
+            inner,
+            _stop: Arc::new(stop.drop_guard()),
+        }
+    }
+
+    /// Queue `job` and return a receiver for its result. The receiver errors if the job panics
+    /// or the pool stops before running it.
+    pub fn submit<T, F>(&self, priority: ProvingPriority, job: F) -> oneshot::Receiver<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let sequence = self
+            .inner
+            .next_sequence
+            .fetch_add(1, AtomicOrdering::Relaxed);
+        self.inner.jobs.lock().push(QueuedJob {
+            priority,
+            sequence,
+            job: Box::new(move || {
+                let _ = tx.send(job());
+            }),
+        });
+        self.inner.pending.add_permits(1);
+        rx
+    }
+
+    /// Jobs waiting for a worker.
+    pub fn len(&self) -> usize {
+        self.inner.jobs.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for ProvingQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProvingQueue")
+            .field("workers", &self.inner.workers)
+            .field("queued", &self.len())
+            .finish()
+    }
+}
+
+async fn run_worker(inner: Arc<QueueInner>, stop: CancellationToken) {
+    loop {
+        let permit = tokio::select! {
+            _ = stop.cancelled() => break,
+            permit = inner.pending.acquire() => permit,
+        };
+        let Ok(permit) = permit else { break };
+        permit.forget();
+
+        let Some(queued) = inner.jobs.lock().pop() else {
+            continue;
+        };
+        if let Err(err) = tokio::task::spawn_blocking(queued.job).await {
+            warn!(
+                target = LOG_TARGET,
+                priority = queued.priority.0,
+                error = %err,
+                "proving job did not complete"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[tokio::test]
+    async fn higher_priority_jobs_run_first() {
+        let queue = ProvingQueue::new(1);
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Occupy the only worker until every other job is queued.
+        let (release, gate) = mpsc::channel::<()>();
+        let blocker = queue.submit(ProvingPriority(u32::MAX), move || {
+            gate.recv().expect("gate sender held by the test");
+        });
+
+        let results: Vec<_> = [(1, "low"), (5, "high"), (3, "mid"), (5, "high-later")]
+            .into_iter()
+            .map(|(priority, name)| {
+                let order = Arc::clone(&order);
+                queue.submit(ProvingPriority(priority), move || {
+                    order.lock().push(name);
+                })
+            })
+            .collect();
+        assert_eq!(queue.len(), 5);
+
+        release.send(()).expect("blocker holds the gate");
+        blocker.await.expect("blocker ran");
+        for result in results {
+            result.await.expect("job ran");
+        }
+        assert_eq!(*order.lock(), vec!["high", "high-later", "mid", "low"]);
+        assert!(queue.is_empty());
+    }
+}
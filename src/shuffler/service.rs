@@ -23,19 +23,23 @@ use crate::ledger::messages::{
 use crate::ledger::snapshot::{AnyTableSnapshot, Shared, TableAtShuffling};
 use crate::ledger::types::{GameId, HandId, ShufflerId};
 use crate::ledger::CanonicalKey;
+use crate::vrf::NonceSource;
 
 use super::api::{ShufflerEngine, ShufflerSigningParameters, ShufflerSigningSecret};
+use super::proving_queue::{ProvingPriority, ProvingQueue};
 use super::state::{
     BoardCardShufflerRequest, DealShufflerRequest, HandResources, HandSubscription,
     PlayerBlindingRequest, PlayerUnblindingRequest,
 };
-use super::{spawn_named_task, DEAL_CHANNEL_CAPACITY, LOG_TARGET};
+use super::{ShufflerScheme, DEAL_CHANNEL_CAPACITY, LOG_TARGET};
 use crate::signing::{SignatureBytes, WithSignature};
 
 #[derive(Clone, Debug)]
 pub struct ShufflerRunConfig {
     pub rng_seed: [u8; 32],
     pub message_history_cap: usize,
+    /// Where shuffles are proved. Without a queue each hand proves on its own shuffle task.
+    pub proving_queue: Option<ProvingQueue>,
 }
 
 impl ShufflerRunConfig {
@@ -43,6 +47,7 @@ impl ShufflerRunConfig {
         Self {
             rng_seed,
             message_history_cap: 64,
+            proving_queue: None,
         }
     }
 
@@ -50,9 +55,14 @@ impl ShufflerRunConfig {
         self.message_history_cap = cap;
         self
     }
+
+    pub fn with_proving_queue(mut self, queue: ProvingQueue) -> Self {
+        self.proving_queue = Some(queue);
+        self
+    }
 }
 
-pub struct ShufflerService<C, S>
+pub struct ShufflerService<C, S = ShufflerScheme<C>>
 where
     C: CurveGroup,
     S: SignatureScheme<PublicKey = C::Affine>,
@@ -65,9 +75,14 @@ where
     aggregated_public_key: C,
     engine: Arc<ShufflerEngine<C, S>>,
     submit: mpsc::Sender<AnyMessageEnvelope<C>>,
+    /// Every hand this shuffler is serving, keyed by `(game_id, hand_id)`. Each entry owns that
+    /// hand's shuffling and dealing state, so one shuffler can work several tables at once; an
+    /// entry drops itself from the map when its hand ends or is cancelled.
     states: Arc<DashMap<(GameId, HandId), Arc<HandResources<C>>>>,
     rng: Mutex<StdRng>,
     config: ShufflerRunConfig,
+    /// Where per-hand VRF nonces come from; `None` draws them from the hand's seeded rng.
+    nonce_source: Option<Arc<dyn NonceSource<C::BaseField>>>,
     events_rx: Mutex<broadcast::Receiver<FinalizedAnyMessageEnvelope<C>>>,
     snapshots_rx: Mutex<broadcast::Receiver<Shared<AnyTableSnapshot<C>>>>,
 }
@@ -111,11 +126,19 @@ where
             states: Arc::new(DashMap::new()),
             rng: Mutex::new(rng),
             config,
+            nonce_source: None,
             events_rx: Mutex::new(events_rx),
             snapshots_rx: Mutex::new(snapshots_rx),
         }
     }
 
+    /// Take each hand's VRF nonce from `source`, e.g. a [`DrandBeacon`](crate::vrf::DrandBeacon),
+    /// so the beacon round is recorded with the shuffle.
+    pub fn with_nonce_source(mut self, source: Arc<dyn NonceSource<C::BaseField>>) -> Self {
+        self.nonce_source = Some(source);
+        self
+    }
+
     pub fn shuffler_id(&self) -> ShufflerId {
         self.shuffler_id
     }
@@ -193,11 +216,26 @@ where
         }
 
         // Create ShufflerHandState from snapshot
-        let state = super::state::ShufflerHandState::from_shuffling_snapshot(
+        let mut state = super::state::ShufflerHandState::from_shuffling_snapshot(
             snapshot,
             &self.public_key,
             hand_seed,
         )?;
+        if let Some(source) = &self.nonce_source {
+            let nonce = source
+                .next_nonce()
+                .await
+                .map_err(|err| anyhow!("failed to draw VRF nonce for hand {hand_id}: {err}"))?;
+            state.use_vrf_nonce(nonce)?;
+        }
+
+        // Publish the nonce commitment now, ahead of any shuffle this shuffler can see.
+        if let Some(envelope) = state.try_emit_nonce_commitment::<S, _>(self.engine.as_ref())? {
+            self.submit
+                .send(envelope)
+                .await
+                .map_err(|err| anyhow!(err.to_string()))?;
+        }
 
         let registry = Arc::downgrade(&self.states);
         let runtime = Arc::new(HandResources::new(state, registry.clone()));
@@ -237,27 +275,20 @@ where
             guard.resubscribe()
         };
 
-        let history_cap = self.config.message_history_cap;
-        let shuffle_handle = Self::spawn_shuffle_loop_per_hand(
+        Self::spawn_shuffle_loop_per_hand(
             turn_index,
             engine.clone(),
             submit.clone(),
             Arc::clone(&runtime),
             events_rx,
-            history_cap,
+            self.config.clone(),
             public_key.clone(),
             &actor,
         );
 
-        runtime.set_shuffle_handle(shuffle_handle);
-
         let (deal_tx, deal_rx) = broadcast::channel(DEAL_CHANNEL_CAPACITY);
-        let dealing_producer = Self::spawn_dealing_request_producer(
-            Arc::clone(&runtime),
-            snapshots_rx,
-            deal_tx.clone(),
-        );
-        let dealing_worker = Self::spawn_dealing_request_worker(
+        Self::spawn_dealing_request_producer(Arc::clone(&runtime), snapshots_rx, deal_tx.clone());
+        Self::spawn_dealing_request_worker(
             turn_index,
             Arc::clone(&runtime),
             deal_rx,
@@ -266,16 +297,16 @@ where
             &actor,
         );
 
-        runtime.set_dealing_handles(dealing_producer, dealing_worker);
-
-        Ok(HandSubscription::new(runtime))
+        // The blinding cache only serves resyncs of a live hand; free it with the subscription.
+        Ok(HandSubscription::new(runtime).on_release(move || engine.evict_blinding_hand(hand_id)))
     }
 
     pub async fn kick_start_hand(&self, game_id: GameId, hand_id: HandId) -> Result<()>
     where
+        C: Send + Sync + 'static,
         C::Config: CurveConfig<ScalarField = C::ScalarField>,
-        C::ScalarField: CanonicalSerialize + PrimeField + UniformRand,
-        C::BaseField: PrimeField,
+        C::ScalarField: CanonicalSerialize + PrimeField + UniformRand + Send + Sync,
+        C::BaseField: PrimeField + Send + Sync,
         S::Signature: SignatureBytes,
     {
         let key = (game_id, hand_id);
@@ -313,6 +344,7 @@ where
             &runtime_arc,
             &public_key,
             &actor,
+            self.config.proving_queue.as_ref(),
         )
         .await
     }
@@ -323,7 +355,7 @@ where
         submit: mpsc::Sender<AnyMessageEnvelope<C>>,
         runtime: Arc<HandResources<C>>,
         shuffle_updates: broadcast::Receiver<FinalizedAnyMessageEnvelope<C>>,
-        history_cap: usize,
+        config: ShufflerRunConfig,
         public_key: C,
         actor: &ShufflerActor<C>,
     ) -> JoinHandle<()>
@@ -341,13 +373,13 @@ where
         let game_id = runtime.game_id;
         let hand_id = runtime.hand_id;
         let task_name = format!("shuffler-{shuffler_index}-game-{game_id}-hand-{hand_id}-shuffle");
-        spawn_named_task(task_name, async move {
+        Arc::clone(&runtime).spawn_child(task_name, async move {
             if let Err(err) = Self::shuffle_loop_per_hand(
                 engine,
                 submit,
                 Arc::clone(&runtime),
                 shuffle_updates,
-                history_cap,
+                config,
                 public_key,
                 &actor_clone,
                 shuffler_index,
@@ -376,15 +408,16 @@ where
         submit: mpsc::Sender<AnyMessageEnvelope<C>>,
         runtime: Arc<HandResources<C>>,
         mut updates: broadcast::Receiver<FinalizedAnyMessageEnvelope<C>>,
-        history_cap: usize,
+        config: ShufflerRunConfig,
         public_key: C,
         actor: &ShufflerActor<C>,
         shuffler_index: usize,
     ) -> Result<()>
     where
+        C: Send + Sync + 'static,
         C::Config: CurveConfig<ScalarField = C::ScalarField>,
-        C::ScalarField: CanonicalSerialize + PrimeField + UniformRand,
-        C::BaseField: PrimeField,
+        C::ScalarField: CanonicalSerialize + PrimeField + UniformRand + Send + Sync,
+        C::BaseField: PrimeField + Send + Sync,
         S::Signature: SignatureBytes,
     {
         loop {
@@ -409,7 +442,11 @@ where
                             }
 
                             if let Some(envelope) = Self::as_shuffle_envelope(&finalized) {
-                                let should_emit = Self::record_incoming(&runtime, &envelope, history_cap);
+                                let should_emit = Self::record_incoming(
+                                    &runtime,
+                                    &envelope,
+                                    config.message_history_cap,
+                                );
                                 if should_emit {
                                     if let Err(err) = Self::emit_shuffle(
                                         &engine,
@@ -417,6 +454,7 @@ where
                                         &runtime,
                                         &public_key,
                                         actor,
+                                        config.proving_queue.as_ref(),
                                     )
                                     .await
                                     {
@@ -550,13 +588,35 @@ where
         runtime: &Arc<HandResources<C>>,
         _public_key: &C,
         actor: &ShufflerActor<C>,
+        proving_queue: Option<&ProvingQueue>,
     ) -> Result<()>
     where
+        C: Send + Sync + 'static,
+        C::Config: CurveConfig<ScalarField = C::ScalarField>,
+        C::ScalarField: CanonicalSerialize + PrimeField + UniformRand + Send + Sync,
+        C::BaseField: PrimeField + Send + Sync,
         S::Signature: SignatureBytes,
     {
-        let any_envelope = {
-            let mut state = runtime.state.lock();
-            state.try_emit_shuffle::<S, _>(engine.as_ref(), actor)?
+        let any_envelope = match proving_queue {
+            Some(queue) => {
+                // Hands further through the shuffle are closer to dealing, so prove them first.
+                let position = runtime.state.lock().shuffling.buffered.len();
+                let priority = ProvingPriority(u32::try_from(position).unwrap_or(u32::MAX));
+                let engine = Arc::clone(engine);
+                let runtime = Arc::clone(runtime);
+                let actor = actor.clone();
+                queue
+                    .submit(priority, move || {
+                        let mut state = runtime.state.lock();
+                        state.try_emit_shuffle::<S, _>(engine.as_ref(), &actor)
+                    })
+                    .await
+                    .map_err(|_| anyhow!("proving queue dropped the shuffle job"))??
+            }
+            None => {
+                let mut state = runtime.state.lock();
+                state.try_emit_shuffle::<S, _>(engine.as_ref(), actor)?
+            }
         };
 
         if let Some(envelope) = any_envelope {
@@ -592,7 +652,7 @@ where
         let hand_id = runtime.hand_id;
         let task_name =
             format!("dealing-worker-shuffler-{shuffler_index}-game-{game_id}-hand-{hand_id}");
-        spawn_named_task(task_name, async move {
+        Arc::clone(&runtime).spawn_child(task_name, async move {
             let result = Self::deal_loop(
                 runtime.clone(),
                 updates,
@@ -645,7 +705,7 @@ where
         };
         let task_name =
             format!("dealing-producer-shuffler-{shuffler_id}-game-{game_id}-hand-{hand_id}");
-        spawn_named_task(task_name, async move {
+        Arc::clone(&runtime).spawn_child(task_name, async move {
             loop {
                 tokio::select! {
                     _ = runtime.cancel.cancelled() => break,
@@ -979,6 +1039,8 @@ where
             return Ok(None);
         }
 
+        runtime.state.lock().check_board_request(&request)?;
+
         // TODO: emit community decryption share once ledger message type is defined.
         debug!(
             target = LOG_TARGET,
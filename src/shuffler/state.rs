@@ -1,7 +1,8 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
 use std::sync::{Arc, Weak};
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
 use ark_ff::{PrimeField, UniformRand};
@@ -10,7 +11,7 @@ use dashmap::DashMap;
 use parking_lot::Mutex;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
-use tokio::task::JoinHandle;
+use tokio::task::{AbortHandle, JoinHandle};
 use tokio_util::sync::CancellationToken;
 
 use crate::curve_absorb::CurveAbsorb;
@@ -21,6 +22,7 @@ use crate::ledger::messages::{
     AnyMessageEnvelope, EnvelopedMessage, GameShuffleMessage, MetadataEnvelope,
 };
 use crate::ledger::snapshot::phases::HandPhase;
+pub use crate::ledger::snapshot::BoardCardSlot;
 use crate::ledger::snapshot::{
     CardDestination, CardPlan, DealingSnapshot, DealtCard, Shared, ShufflerRoster, TableAtDealing,
     TableAtShuffling, TableSnapshot,
@@ -31,9 +33,11 @@ use crate::ledger::CanonicalKey;
 use crate::shuffling::player_decryption::PlayerAccessibleCiphertext;
 use crate::shuffling::{ElGamalCiphertext, DECK_SIZE};
 use crate::signing::SignatureBytes;
+use crate::tokio_tools::TaskRegistry;
+use crate::vrf::VrfNonce;
 use tracing::{debug, field::display, info, warn};
 
-use super::api::{ShufflerApi, ShufflerSigningParameters, ShufflerSigningSecret};
+use super::api::{BlindingCacheKey, ShufflerApi, ShufflerSigningParameters, ShufflerSigningSecret};
 
 const LOG_TARGET: &str = "legit_poker::game::shuffler::deal";
 
@@ -73,7 +77,10 @@ pub struct ShufflerHandState<C: CurveGroup> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ledger::test_support::{fixture_shuffling_snapshot, FixtureContext};
+    use crate::engine::nl::types::{HandConfig, OMAHA_HOLE_CARDS};
+    use crate::ledger::test_support::{
+        fixture_dealing_snapshot, fixture_shuffling_snapshot, FixtureContext,
+    };
     use ark_bn254::G1Projective as TestCurve;
 
     #[test]
@@ -99,6 +106,82 @@ mod tests {
             "aggregated key should remain the global aggregate"
         );
     }
+
+    #[test]
+    fn omaha_hand_deals_four_hole_cards_per_seat() {
+        let mut ctx = FixtureContext::<TestCurve>::new(&[0, 1, 2], &[0, 1]);
+        ctx.cfg = Arc::new(HandConfig {
+            hole_cards: OMAHA_HOLE_CARDS,
+            ..(*ctx.cfg).clone()
+        });
+        let dealing = fixture_dealing_snapshot(&ctx).dealing;
+
+        let mut holes_by_seat: BTreeMap<SeatId, Vec<u8>> = BTreeMap::new();
+        for destination in dealing.card_plan.values() {
+            if let CardDestination::Hole { seat, hole_index } = destination {
+                holes_by_seat.entry(*seat).or_default().push(*hole_index);
+            }
+        }
+        assert_eq!(holes_by_seat.len(), 3);
+        for holes in holes_by_seat.values() {
+            assert_eq!(holes.len(), usize::from(OMAHA_HOLE_CARDS));
+        }
+        // Hole cards come first, so the board starts after 3 seats * 4 cards and a burn.
+        let first_board = dealing
+            .card_plan
+            .iter()
+            .find_map(|(&deal_index, destination)| {
+                matches!(destination, CardDestination::Board { .. }).then_some(deal_index)
+            });
+        assert_eq!(first_board, Some(13));
+        assert_eq!(dealing.player_ciphertexts.len(), 12);
+        assert!(dealing.player_ciphertexts.contains_key(&(2, 3)));
+
+        let state = DealingHandState::from_dealing_snapshot(
+            &dealing,
+            &ctx.shufflers,
+            &ctx.expected_shuffler_order[0],
+        );
+        assert_eq!(state.hole_cards_per_seat(), Some(OMAHA_HOLE_CARDS));
+    }
+
+    #[test]
+    fn board_request_must_match_deck_position() {
+        let ctx = FixtureContext::<TestCurve>::new(&[0, 1, 2], &[0, 1]);
+        let shuffler_key = ctx.expected_shuffler_order[0].value().clone();
+        let state = ShufflerHandState::from_shuffling_snapshot(
+            &fixture_shuffling_snapshot(&ctx),
+            &shuffler_key,
+            [7u8; 32],
+        )
+        .expect("state should build from snapshot");
+
+        let dealing = fixture_dealing_snapshot(&ctx);
+        let (deal_index, board_index) = dealing
+            .dealing
+            .card_plan
+            .iter()
+            .find_map(|(&deal_index, destination)| match destination {
+                CardDestination::Board { board_index } => Some((deal_index, *board_index)),
+                _ => None,
+            })
+            .expect("card plan has board cards");
+        let mut request = BoardCardShufflerRequest {
+            game_id: ctx.game_id,
+            hand_id: ctx.hand_id,
+            deal_index,
+            slot: BoardCardSlot::from_board_index(board_index).unwrap(),
+            ciphertext: dealing.dealing.assignments[&deal_index].clone(),
+        };
+        state
+            .check_board_request(&request)
+            .expect("untampered board card opens");
+
+        // Swap in a different deck card while keeping the claimed position.
+        let other = (deal_index as usize + 1) % DECK_SIZE;
+        request.ciphertext.cipher = ctx.initial_deck[other].clone();
+        assert!(state.check_board_request(&request).is_err());
+    }
 }
 
 impl<C: CurveGroup> ShufflerHandState<C> {
@@ -126,6 +209,20 @@ impl<C: CurveGroup> ShufflerHandState<C> {
         envelope
     }
 
+    /// Check that a board request's ciphertext is the card at its claimed position in the
+    /// shuffled deck this shuffler verified, before contributing a share for it.
+    pub fn check_board_request(&self, request: &BoardCardShufflerRequest<C>) -> Result<()> {
+        request
+            .ciphertext
+            .verify_deck_position(&self.shuffling.latest_deck)
+            .with_context(|| {
+                format!(
+                    "board request for deal index {} ({:?}) failed deck position check",
+                    request.deal_index, request.slot
+                )
+            })
+    }
+
     /// Record an incoming shuffle message and return whether shuffling is complete.
     pub fn record_incoming_shuffle(
         &mut self,
@@ -139,6 +236,44 @@ impl<C: CurveGroup> ShufflerHandState<C> {
         self.shuffling.is_complete()
     }
 
+    /// Replace the locally drawn VRF nonce, e.g. with one taken from a public beacon.
+    ///
+    /// Only possible before the commitment is emitted; afterwards the nonce is fixed.
+    pub fn use_vrf_nonce(&mut self, nonce: VrfNonce<C::BaseField>) -> Result<()> {
+        if self.shuffling.acted || self.shuffling.nonce_committed {
+            return Err(anyhow!(
+                "VRF nonce for hand {} is already committed",
+                self.hand_id
+            ));
+        }
+        self.shuffling.vrf_nonce = nonce;
+        Ok(())
+    }
+
+    /// Emit the commitment to this hand's VRF nonce, once and before the shuffle.
+    pub fn try_emit_nonce_commitment<S, A>(
+        &mut self,
+        shuffler: &A,
+    ) -> Result<Option<AnyMessageEnvelope<C>>>
+    where
+        C::BaseField: PrimeField,
+        S: ark_crypto_primitives::signature::SignatureScheme<PublicKey = C::Affine>,
+        S::Signature: SignatureBytes,
+        S::SecretKey: ShufflerSigningSecret<C>,
+        S::Parameters: ShufflerSigningParameters<C>,
+        A: ShufflerApi<C, S>,
+    {
+        if self.shuffling.acted || self.shuffling.nonce_committed {
+            return Ok(None);
+        }
+
+        let ctx = self.next_metadata_envelope();
+        let vrf_nonce = self.shuffling.vrf_nonce.value;
+        let (_, any) = shuffler.commit_nonce_and_sign(&ctx, &vrf_nonce, &mut self.shuffling_rng)?;
+        self.shuffling.nonce_committed = true;
+        Ok(Some(any))
+    }
+
     /// Attempt to emit a shuffle message if it's this shuffler's turn.
     /// Returns None if already acted or not our turn.
     pub fn try_emit_shuffle<S, A>(
@@ -186,6 +321,7 @@ impl<C: CurveGroup> ShufflerHandState<C> {
             &ctx,
             &deck_in,
             turn_index,
+            &self.shuffling.vrf_nonce,
             &mut self.shuffling_rng,
         )?;
 
@@ -235,13 +371,27 @@ impl<C: CurveGroup> ShufflerHandState<C> {
             return Ok(None);
         }
 
+        let cache_key = BlindingCacheKey {
+            hand_id: request.hand_id,
+            seat: request.seat,
+            hole_index: request.hole_index,
+            player_key: CanonicalKey::new(request.player_public_key.clone()),
+        };
+        let contribution = shuffler.cached_blinding_player_decryption_share(
+            &cache_key,
+            &self.shuffling.latest_deck,
+            &self.aggregated_public_key,
+            request.player_public_key.clone(),
+            &mut self.dealing_rng,
+        )?;
+
         let ctx = self.next_metadata_envelope();
 
-        let (_, any) = shuffler.player_blinding_and_sign(
-            &self.aggregated_public_key,
+        let (_, any) = shuffler.sign_player_blinding(
             &ctx,
             request.deal_index,
             &request.player_public_key,
+            contribution,
             &mut self.dealing_rng,
         )?;
 
@@ -406,10 +556,14 @@ impl<C: CurveGroup> ShufflerHandState<C> {
         let aggregated_public_key = crate::shuffling::make_global_public_keys(shuffler_public_keys);
 
         // Derive separate RNG seeds for shuffling and dealing
-        let shuffling_rng = StdRng::from_seed(rng_seed);
+        let mut shuffling_rng = StdRng::from_seed(rng_seed);
         let mut dealing_seed = rng_seed;
         dealing_seed[0] = dealing_seed[0].wrapping_add(1); // Perturb seed for isolation
         let dealing_rng = StdRng::from_seed(dealing_seed);
+        let vrf_nonce = VrfNonce {
+            value: C::BaseField::rand(&mut shuffling_rng),
+            beacon_round: None,
+        };
 
         // Check if this shuffler has already acted by looking at completed steps
         // A shuffler has acted if their key appears in any of the completed steps
@@ -417,6 +571,7 @@ impl<C: CurveGroup> ShufflerHandState<C> {
             let step_key = CanonicalKey::new(step.shuffler_public_key.clone());
             step_key == shuffler_key
         });
+        let nonce_committed = snapshot.shuffling.nonce_commitment(&shuffler_key).is_some();
 
         // Create shuffling state (fields moved to parent)
         // buffered starts empty when reconstructing from snapshot
@@ -426,6 +581,8 @@ impl<C: CurveGroup> ShufflerHandState<C> {
             initial_deck,
             latest_deck,
             acted,
+            vrf_nonce,
+            nonce_committed,
         };
 
         Ok(Self {
@@ -511,6 +668,8 @@ impl<C: CurveGroup> ShufflerHandState<C> {
             initial_deck,
             latest_deck,
             acted,
+            vrf_nonce: VrfNonce::default(),
+            nonce_committed: true,
         };
 
         // Create dealing state from snapshot
@@ -844,7 +1003,7 @@ impl<C: CurveGroup> ShufflerHandState<C> {
                         continue;
                     }
                     if let Some(dealt) = dealing_snapshot.assignments.get(&deal_index) {
-                        let slot = board_slot_from_index(*board_index)
+                        let slot = BoardCardSlot::from_board_index(*board_index)
                             .ok_or_else(|| anyhow!("invalid board index {board_index}"))?;
                         requests.push(DealShufflerRequest::Board(BoardCardShufflerRequest {
                             game_id: table.game_id(),
@@ -877,6 +1036,10 @@ pub struct ShufflingHandState<C: CurveGroup> {
     pub initial_deck: [ElGamalCiphertext<C>; DECK_SIZE],
     pub latest_deck: [ElGamalCiphertext<C>; DECK_SIZE],
     pub acted: bool,
+    /// VRF nonce this shuffler commits to before its turn and reveals with its shuffle.
+    pub vrf_nonce: VrfNonce<C::BaseField>,
+    /// Whether the commitment to `vrf_nonce` has been emitted.
+    pub nonce_committed: bool,
 }
 
 impl<C: CurveGroup> ShufflingHandState<C> {
@@ -924,17 +1087,10 @@ pub struct BoardCardShufflerRequest<C: CurveGroup> {
     pub hand_id: HandId,
     pub deal_index: u8,
     pub slot: BoardCardSlot,
+    /// Board ciphertext together with the deck position it opens.
     pub ciphertext: DealtCard<C>,
 }
 
-/// Location of a community card within the board.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum BoardCardSlot {
-    Flop(u8),
-    Turn,
-    River,
-}
-
 /// Per-hand bookkeeping for dealing phase signalling.
 #[derive(Debug)]
 pub struct DealingHandState<C: CurveGroup> {
@@ -986,6 +1142,19 @@ impl<C: CurveGroup> DealingHandState<C> {
         }
     }
 
+    /// Hole cards the card plan deals to each seat (2 for hold'em, 4 for Omaha), or `None`
+    /// before a plan has been observed.
+    pub fn hole_cards_per_seat(&self) -> Option<u8> {
+        self.card_plan
+            .as_ref()?
+            .values()
+            .filter_map(|destination| match destination {
+                CardDestination::Hole { hole_index, .. } => Some(hole_index + 1),
+                _ => None,
+            })
+            .max()
+    }
+
     pub fn reset(&mut self) {
         self.card_plan = None;
         self.shuffler_keys.clear();
@@ -1014,26 +1183,10 @@ where
     Ok(identity.public_key.clone())
 }
 
-fn board_slot_from_index(index: u8) -> Option<BoardCardSlot> {
-    match index {
-        0..=2 => Some(BoardCardSlot::Flop(index)),
-        3 => Some(BoardCardSlot::Turn),
-        4 => Some(BoardCardSlot::River),
-        _ => None,
-    }
-}
-
 // ============================================================================
 // Hand Resources (Coordination Layer)
 // ============================================================================
 
-#[derive(Debug, Default)]
-struct ShufflerTasks {
-    shuffle: Option<JoinHandle<()>>,
-    dealing_producer: Option<JoinHandle<()>>,
-    dealing_worker: Option<JoinHandle<()>>,
-}
-
 /// Private coordination structure for managing hand resources and async tasks.
 /// Holds pure state in a mutex and provides coordination primitives.
 #[derive(Debug)]
@@ -1042,7 +1195,7 @@ pub struct HandResources<C: CurveGroup> {
     pub hand_id: HandId,
     pub cancel: CancellationToken,
     pub state: Mutex<ShufflerHandState<C>>,
-    tasks: Mutex<ShufflerTasks>,
+    children: Mutex<Vec<AbortHandle>>,
     registry: Weak<DashMap<(GameId, HandId), Arc<HandResources<C>>>>,
 }
 
@@ -1059,39 +1212,34 @@ impl<C: CurveGroup> HandResources<C> {
             hand_id,
             cancel,
             state: Mutex::new(state),
-            tasks: Mutex::new(ShufflerTasks::default()),
+            children: Mutex::new(Vec::new()),
             registry,
         }
     }
 
-    pub fn set_shuffle_handle(&self, handle: JoinHandle<()>) {
-        let mut tasks = self.tasks.lock();
-        if let Some(existing) = tasks.shuffle.replace(handle) {
-            existing.abort();
-        }
-    }
-
-    pub fn set_dealing_handles(&self, producer: JoinHandle<()>, worker: JoinHandle<()>) {
-        let mut tasks = self.tasks.lock();
-        if let Some(existing) = tasks.dealing_producer.replace(producer) {
-            existing.abort();
-        }
-        if let Some(existing) = tasks.dealing_worker.replace(worker) {
-            existing.abort();
-        }
+    /// Spawn a task owned by this hand. The task stops as soon as [`Self::cancel`] fires, so
+    /// cancelling the token is enough to tear down every worker spawned for the hand.
+    pub fn spawn_child<F>(&self, name: impl Into<String>, future: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let cancel = self.cancel.clone();
+        let handle = TaskRegistry::global().spawn(name, Some(self.hand_id), async move {
+            tokio::select! {
+                _ = cancel.cancelled() => {}
+                _ = future => {}
+            }
+        });
+        let mut children = self.children.lock();
+        children.retain(|child| !child.is_finished());
+        children.push(handle.abort_handle());
+        handle
     }
 
     pub fn cancel_all(&self) {
         self.cancel.cancel();
-        let mut tasks = self.tasks.lock();
-        if let Some(handle) = tasks.shuffle.take() {
-            handle.abort();
-        }
-        if let Some(handle) = tasks.dealing_producer.take() {
-            handle.abort();
-        }
-        if let Some(handle) = tasks.dealing_worker.take() {
-            handle.abort();
+        for child in self.children.lock().drain(..) {
+            child.abort();
         }
     }
 
@@ -1107,6 +1255,7 @@ where
     C: CurveGroup,
 {
     resources: Arc<HandResources<C>>,
+    on_release: Option<Box<dyn FnOnce() + Send + Sync>>,
 }
 
 impl<C> HandSubscription<C>
@@ -1114,7 +1263,16 @@ where
     C: CurveGroup,
 {
     pub fn new(resources: Arc<HandResources<C>>) -> Self {
-        Self { resources }
+        Self {
+            resources,
+            on_release: None,
+        }
+    }
+
+    /// Run `hook` when the subscription is dropped, after the hand's tasks have been cancelled.
+    pub fn on_release(mut self, hook: impl FnOnce() + Send + Sync + 'static) -> Self {
+        self.on_release = Some(Box::new(hook));
+        self
     }
 
     pub fn cancel(&self) {
@@ -1129,5 +1287,8 @@ where
     fn drop(&mut self) {
         self.resources.cancel_all();
         self.resources.remove_from_registry();
+        if let Some(hook) = self.on_release.take() {
+            hook();
+        }
     }
 }
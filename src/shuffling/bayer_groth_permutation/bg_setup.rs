@@ -2,16 +2,18 @@
 
 use crate::pedersen_commitment::pedersen_commit_scalars;
 use crate::shuffling::curve_absorb::CurveAbsorb;
+use crate::transcript::Transcript;
 use ark_crypto_primitives::commitment::pedersen::Parameters;
 use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, CryptographicSponge};
 use ark_ec::CurveGroup;
 use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{fmt::Debug, vec::Vec};
 
 const LOG_TARGET: &str = "legit_poker::shuffling::bayer_groth_permutation::linking_rs_gadgets";
 
 /// Minimal output structure for power challenge setup (without product permutation)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BGPowerChallengeSetup<BaseField: PrimeField, ScalarField: PrimeField, G: CurveGroup> {
     /// The Fiat-Shamir challenge x ∈ F_q* in base field (for circuit computation)
     pub power_challenge_base: BaseField,
@@ -67,7 +69,7 @@ impl<F: PrimeField, RO: CryptographicSponge> BayerGrothTranscript<F, RO> {
     where
         G: CurveAbsorb<F, RO>,
     {
-        c_perm.curve_absorb(&mut self.sponge);
+        self.sponge.absorb_point(c_perm);
 
         tracing::debug!(target: LOG_TARGET, "Absorbed permutation vector commitment");
     }
@@ -75,10 +77,7 @@ impl<F: PrimeField, RO: CryptographicSponge> BayerGrothTranscript<F, RO> {
     /// Derive permutation power challenge from the transcript
     /// Returns perm_power_challenge which is used to compute the power vector
     fn derive_perm_power_challenge(&mut self) -> F {
-        // Squeeze one field element
-        let elements = self.sponge.squeeze_field_elements::<F>(1);
-
-        let mut perm_power_challenge: F = elements[0];
+        let mut perm_power_challenge: F = self.sponge.challenge();
 
         // Ensure challenge is non-zero (∈ F_q*)
         if perm_power_challenge.is_zero() {
@@ -96,7 +95,7 @@ impl<F: PrimeField, RO: CryptographicSponge> BayerGrothTranscript<F, RO> {
     where
         G: CurveAbsorb<F, RO>,
     {
-        c_power.curve_absorb(&mut self.sponge);
+        self.sponge.absorb_point(c_power);
 
         tracing::debug!(target: LOG_TARGET, "Absorbed power vector commitment");
     }
@@ -104,7 +103,7 @@ impl<F: PrimeField, RO: CryptographicSponge> BayerGrothTranscript<F, RO> {
     /// Derive final challenges for permutation equality check
     /// Returns (mixing_challenge_y, offset_challenge_z)
     fn derive_perm_challenges_y_z(&mut self) -> (F, F) {
-        let elements = self.sponge.squeeze_field_elements::<F>(2);
+        let elements = self.sponge.challenges::<F>(2);
         let mixing_challenge_y = elements[0];
         let offset_challenge_z = elements[1];
 
@@ -398,6 +397,42 @@ mod tests {
         );
     }
 
+    /// Golden vectors: the challenges the raw sponge produced before it went through
+    /// `Transcript`.
+    #[test]
+    fn test_challenges_match_golden_vectors() {
+        use std::str::FromStr;
+
+        let generator = G1Projective::generator();
+        let mut transcript = new_bayer_groth_transcript_with_poseidon::<Fq>(b"test-domain");
+
+        transcript.absorb_perm_vector_commitment(&(generator * Fr::from(5u64)));
+        assert_eq!(
+            transcript.derive_perm_power_challenge(),
+            Fq::from_str(
+                "7879236079966920673689081020136285293610819780847027789382617804444243741149"
+            )
+            .unwrap()
+        );
+
+        transcript.absorb_perm_power_vector_commitment(&(generator * Fr::from(7u64)));
+        let (mixing_challenge_y, offset_challenge_z) = transcript.derive_perm_challenges_y_z();
+        assert_eq!(
+            mixing_challenge_y,
+            Fq::from_str(
+                "16383785674599013283876520037231961030673293452071587565119635855913177350185"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            offset_challenge_z,
+            Fq::from_str(
+                "5094669697997170898817300697794070885783383479560324148233152782403540254575"
+            )
+            .unwrap()
+        );
+    }
+
     #[test]
     fn test_different_commitments_different_challenges() {
         let mut rng = test_rng();
@@ -1,6 +1,8 @@
 use super::chaum_pedersen::ChaumPedersenProof;
 use super::curve_absorb::CurveAbsorb;
 use super::data_structures::ElGamalCiphertext;
+use super::error::DecryptionError;
+use super::player_decryption::native::{lagrange_coefficient_at_zero, threshold_subset};
 use crate::poseidon_config;
 use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge};
 use ark_ec::{AffineRepr, CurveGroup};
@@ -107,6 +109,7 @@ where
 ///
 /// IMPORTANT: This is an n-of-n scheme - ALL committee members must provide shares.
 /// This is not a threshold scheme; if any member's share is missing, decryption will fail.
+/// See [`combine_community_shares_threshold`] for the `t`-of-`n` variant.
 ///
 /// # Arguments
 /// * `shares` - Decryption shares from ALL n committee members
@@ -218,13 +221,14 @@ impl<C: CurveGroup> Clone for CardValueMap<C> {
 /// * `expected_members` - The expected number of committee members
 ///
 /// # Returns
-/// The decrypted card value (0-51) or an error if decryption fails
+/// The decrypted card value (0-51) or an error if decryption fails. An identity ciphertext is
+/// rejected as [`DecryptionError::UndealtCard`] rather than decrypted to card 0.
 #[instrument(skip(decryption_shares), level = "trace")]
 pub fn decrypt_community_card<C>(
     ciphertext: &ElGamalCiphertext<C>,
     decryption_shares: Vec<CommunityDecryptionShare<C>>,
     expected_members: usize,
-) -> Result<u8, &'static str>
+) -> Result<u8, DecryptionError>
 where
     C: CurveGroup + 'static,
     C::ScalarField: PrimeField,
@@ -238,6 +242,11 @@ where
         "=== Community card decryption ==="
     );
 
+    if ciphertext.is_identity() {
+        warn!(target: LOG_TARGET, "Refusing to decrypt an identity ciphertext");
+        return Err(DecryptionError::UndealtCard);
+    }
+
     // Step 1: Combine committee decryption shares to get pk^r
     let combined_shares = combine_community_shares(&decryption_shares, expected_members)?;
     tracing::debug!(
@@ -256,10 +265,89 @@ where
     );
 
     // Step 3: Map the group element back to a card value using pre-computed table
+    card_value_of(&recovered_element)
+}
+
+/// Combine a `t`-of-`n` committee's decryption shares to compute pk^r.
+///
+/// The committee secret is Shamir-shared: member `j` holds `x_j = f(j)` for a polynomial `f` of
+/// degree `t - 1` with `f(0) = x`. A member's Lagrange coefficient depends on which members took
+/// part, so each share is paired with its participant index (the member's evaluation point,
+/// `1..=total_members`) and weighted over the participating subset.
+///
+/// # Arguments
+/// * `shares` - `(participant_index, share)` pairs from at least `threshold` distinct members
+/// * `threshold` - Minimum number of participating members `t`
+/// * `total_members` - Committee size `n`
+///
+/// # Returns
+/// The aggregated value pk^r = Σ(λ_j · share_j) = c1^x
+#[instrument(skip(shares), level = "trace")]
+pub fn combine_community_shares_threshold<C: CurveGroup>(
+    shares: &[(u64, CommunityDecryptionShare<C>)],
+    threshold: usize,
+    total_members: usize,
+) -> Result<C, &'static str> {
+    if threshold == 0 || threshold > total_members {
+        return Err("Threshold must be between 1 and the committee size");
+    }
+    let subset = threshold_subset(shares.iter().map(|(index, _)| *index), threshold)?;
+    if subset
+        .last()
+        .is_some_and(|&index| index > total_members as u64)
+    {
+        return Err("Participant index exceeds the committee size");
+    }
+
+    shares.iter().try_fold(
+        C::zero(),
+        |acc, (index, share)| -> Result<C, &'static str> {
+            let lambda = lagrange_coefficient_at_zero::<C::ScalarField>(*index, &subset)?;
+            Ok(acc + share.share * lambda)
+        },
+    )
+}
+
+/// Threshold variant of [`decrypt_community_card`]: any `threshold` of the `total_members`
+/// committee members can decrypt, so a shuffler dropping out mid-hand does not stall the board.
+///
+/// # Arguments
+/// * `ciphertext` - The encrypted community card
+/// * `decryption_shares` - `(participant_index, share)` pairs; see
+///   [`combine_community_shares_threshold`]
+/// * `threshold` - Minimum number of participating members `t`
+/// * `total_members` - Committee size `n`
+///
+/// # Returns
+/// The decrypted card value (0-51), or an error if there are too few valid shares or the
+/// result is not a card. Identity ciphertexts are rejected as in [`decrypt_community_card`].
+#[instrument(skip(decryption_shares), level = "trace")]
+pub fn decrypt_community_card_threshold<C>(
+    ciphertext: &ElGamalCiphertext<C>,
+    decryption_shares: Vec<(u64, CommunityDecryptionShare<C>)>,
+    threshold: usize,
+    total_members: usize,
+) -> Result<u8, DecryptionError>
+where
+    C: CurveGroup + 'static,
+    C::ScalarField: PrimeField,
+{
+    if ciphertext.is_identity() {
+        warn!(target: LOG_TARGET, "Refusing to decrypt an identity ciphertext");
+        return Err(DecryptionError::UndealtCard);
+    }
+
+    let combined_shares =
+        combine_community_shares_threshold(&decryption_shares, threshold, total_members)?;
+    card_value_of(&(ciphertext.c2 - combined_shares))
+}
+
+/// Look up the card value whose group element is `recovered_element` (g^m).
+fn card_value_of<C: CurveGroup + 'static>(recovered_element: &C) -> Result<u8, DecryptionError> {
     let card_map = get_card_value_map::<C>();
 
     tracing::debug!(target: LOG_TARGET, "Looking up card value in pre-computed map...");
-    match card_map.lookup(&recovered_element) {
+    match card_map.lookup(recovered_element) {
         Some(card_value) => {
             tracing::debug!(target: LOG_TARGET, "Successfully found card value: {}", card_value);
             Ok(card_value)
@@ -268,7 +356,7 @@ where
             warn!(target: LOG_TARGET,
                 "Failed to find card value for recovered element"
             );
-            Err("Recovered element does not correspond to a valid card value")
+            Err(DecryptionError::UnknownCard)
         }
     }
 }
@@ -506,6 +594,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn identity_ciphertext_is_an_undealt_card() {
+        let mut rng = test_rng();
+        let secret = ScalarField::rand(&mut rng);
+        let public_key = GrumpkinProjective::generator() * secret;
+        let placeholder =
+            ElGamalCiphertext::new(GrumpkinProjective::zero(), GrumpkinProjective::zero());
+
+        let share = CommunityDecryptionShare::generate(
+            &placeholder,
+            secret,
+            CanonicalKey::new(public_key),
+            &mut rng,
+        );
+        assert_eq!(
+            decrypt_community_card(&placeholder, vec![share], 1),
+            Err(DecryptionError::UndealtCard)
+        );
+    }
+
+    #[test]
+    fn threshold_decryption_two_of_three() {
+        let mut rng = test_rng();
+        let generator = GrumpkinProjective::generator();
+
+        // Shamir-share the committee secret with f(z) = x + a·z, so any two members suffice.
+        let committee_secret = ScalarField::rand(&mut rng);
+        let slope = ScalarField::rand(&mut rng);
+        let member_secret = |index: u64| committee_secret + slope * ScalarField::from(index);
+        let aggregated_pk = generator * committee_secret;
+
+        let card_value = 38u8;
+        let message_point = generator * ScalarField::from(card_value);
+        let ciphertext =
+            ElGamalCiphertext::encrypt(message_point, ScalarField::rand(&mut rng), aggregated_pk);
+
+        let mut share_from = |index: u64| {
+            let secret = member_secret(index);
+            let share = CommunityDecryptionShare::generate(
+                &ciphertext,
+                secret,
+                CanonicalKey::new(generator * secret),
+                &mut rng,
+            );
+            assert!(share.verify(&ciphertext, generator * secret));
+            (index, share)
+        };
+        let (one, two, three) = (share_from(1), share_from(2), share_from(3));
+
+        // Exactly the threshold, with member 2 offline.
+        let minimum = vec![three.clone(), one.clone()];
+        assert_eq!(
+            decrypt_community_card_threshold(&ciphertext, minimum, 2, 3),
+            Ok(card_value)
+        );
+
+        // Every member answering is still consistent.
+        let everyone = vec![one.clone(), two.clone(), three.clone()];
+        assert_eq!(
+            decrypt_community_card_threshold(&ciphertext, everyone, 2, 3),
+            Ok(card_value)
+        );
+
+        assert_eq!(
+            decrypt_community_card_threshold(&ciphertext, vec![two.clone()], 2, 3),
+            Err(DecryptionError::InvalidShares(
+                "Fewer contributions than the committee threshold"
+            ))
+        );
+        assert!(
+            decrypt_community_card_threshold(&ciphertext, vec![one.clone(), one], 2, 3).is_err()
+        );
+        assert!(decrypt_community_card_threshold(&ciphertext, vec![two, three], 2, 2).is_err());
+    }
+
     #[test]
     fn test_card_value_mapping() {
         // Test that the card value mapping works correctly
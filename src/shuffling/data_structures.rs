@@ -7,6 +7,7 @@ use ark_relations::gr1cs::Namespace;
 use ark_relations::gr1cs::{ConstraintSystemRef, SynthesisError};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::time::Duration;
 
 use crate::signing::DomainSeparated;
@@ -48,6 +49,11 @@ where
         Self { c1, c2 }
     }
 
+    /// Whether both components are the identity, the placeholder for an undealt card.
+    pub fn is_identity(&self) -> bool {
+        self.c1.is_zero() && self.c2.is_zero()
+    }
+
     /// Encrypt a message (curve point) using ElGamal encryption
     /// Returns ElGamalCiphertext(r*G, M + r*PK) where:
     /// - r is the randomness
@@ -131,6 +137,20 @@ impl<C: CurveGroup> ShuffleProof<C> {
             rerandomization_values,
         })
     }
+
+    /// SHA-256 of the proof's domain tag and compressed canonical bytes.
+    ///
+    /// Equal proofs always share an id, so a resubmitted proof can be recognised without
+    /// comparing the decks element by element.
+    pub fn content_id(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(self.compressed_size());
+        self.serialize_compressed(&mut bytes)
+            .expect("serializing into a Vec cannot fail");
+        let mut hasher = Sha256::new();
+        hasher.update(Self::domain_string().as_bytes());
+        hasher.update(&bytes);
+        hasher.finalize().into()
+    }
 }
 
 impl<C> DomainSeparated for ElGamalCiphertext<C>
@@ -210,6 +230,17 @@ mod tests {
         assert_ne!(bytes_a, bytes_different);
     }
 
+    #[test]
+    fn shuffle_proof_content_id_tracks_proof_contents() {
+        let proof_a = sample_shuffle_proof();
+        let proof_b = sample_shuffle_proof();
+        assert_eq!(proof_a.content_id(), proof_b.content_id());
+
+        let mut different = proof_a.clone();
+        different.sorted_deck[3].1 = Base::from(9u64);
+        assert_ne!(proof_a.content_id(), different.content_id());
+    }
+
     #[test]
     fn ciphertext_round_trips_with_serde() {
         assert_round_trip_eq(&sample_ciphertext());
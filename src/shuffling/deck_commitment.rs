@@ -0,0 +1,467 @@
+//! Commitments to a deck order (one byte per card) and to an encrypted deck.
+//!
+//! The permutation proof publishes a hiding commitment to the order its shuffle produced,
+//! under the [`DeckCommitmentScheme`] selected in the shuffle config. Pedersen over
+//! [`DeckHashWindow`] costs a windowed scalar multiplication in-circuit, while Poseidon costs
+//! one sponge permutation per rate-sized chunk. Both schemes implement [`DeckCommitment`]
+//! natively and [`DeckCommitmentGadget`] in-circuit, and for each the gadget computes exactly
+//! the native value.
+//!
+//! [`IncrementalDeckCommitment`] commits to an *encrypted* deck and can be carried across
+//! shuffle steps without revisiting every ciphertext.
+
+use ark_crypto_primitives::commitment::pedersen::{
+    constraints::CommGadget as PedersenCommGadget, Commitment as PedersenCommitment,
+    Parameters as PedersenParameters, Randomness as PedersenRandomness,
+};
+use ark_crypto_primitives::commitment::CommitmentGadget;
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::{PoseidonConfig, PoseidonSponge};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::groups::{CurveVar, GroupOpsBounds};
+use ark_r1cs_std::prelude::*;
+use ark_relations::gr1cs::{ConstraintSystemRef, SynthesisError};
+use ark_std::UniformRand;
+use rand::rngs::StdRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::data_structures::ElGamalCiphertext;
+use crate::pedersen_commitment::bytes_opening::{pedersen_commit, pedersen_setup, DeckHashWindow};
+
+/// Seed of the fixed Pedersen parameters behind [`DeckOrderCommitment::Pedersen`], shared by
+/// the prover and the circuit like the other permutation-proof parameters.
+const DECK_ORDER_PEDERSEN_SEED: u64 = 44;
+
+/// Which [`DeckCommitment`] a shuffle commits its deck order with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeckCommitmentScheme {
+    #[default]
+    Pedersen,
+    Poseidon,
+}
+
+impl DeckCommitmentScheme {
+    /// Byte identifying the scheme in persisted permutation SNARK keys.
+    pub fn tag(self) -> u8 {
+        match self {
+            Self::Pedersen => 0,
+            Self::Poseidon => 1,
+        }
+    }
+
+    /// Inverse of [`Self::tag`].
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Pedersen),
+            1 => Some(Self::Poseidon),
+            _ => None,
+        }
+    }
+
+    /// Fresh blinding for a deck-order commitment under this scheme.
+    pub fn sample_opening<C, R>(self, rng: &mut R) -> DeckOrderOpening<C>
+    where
+        C: CurveGroup,
+        R: RngCore + ?Sized,
+    {
+        match self {
+            Self::Pedersen => DeckOrderOpening::Pedersen(C::ScalarField::rand(rng)),
+            Self::Poseidon => DeckOrderOpening::Poseidon(C::BaseField::rand(rng)),
+        }
+    }
+}
+
+/// A commitment to a deck order, one byte per card.
+pub trait DeckCommitment {
+    type Output: Clone + core::fmt::Debug + PartialEq;
+    type Randomness;
+
+    /// Commit to `deck` natively.
+    fn commit(&self, deck: &[u8], randomness: &Self::Randomness) -> Self::Output;
+}
+
+/// In-circuit form of a [`DeckCommitment`] over the constraint field `F`.
+pub trait DeckCommitmentGadget<F: PrimeField, OutputVar>: DeckCommitment {
+    /// Commit to `deck` in-circuit, allocating the randomness as a witness. The result's value
+    /// equals [`DeckCommitment::commit`] on the same deck and randomness.
+    fn commit_gadget(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        deck: &[UInt8<F>],
+        randomness: &Self::Randomness,
+    ) -> Result<OutputVar, SynthesisError>;
+}
+
+/// Pedersen over [`DeckHashWindow`]. Decks longer than the window's 64 bytes cannot be
+/// committed.
+#[derive(Clone)]
+pub struct PedersenDeckCommitment<G: CurveGroup> {
+    pub params: PedersenParameters<G>,
+}
+
+impl<G: CurveGroup> PedersenDeckCommitment<G> {
+    pub fn new(params: PedersenParameters<G>) -> Self {
+        Self { params }
+    }
+
+    pub fn setup<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::new(pedersen_setup::<G, DeckHashWindow, R>(rng))
+    }
+
+    /// The parameters [`DeckOrderCommitment::Pedersen`] is computed under.
+    pub fn deck_order() -> Self {
+        Self::setup(&mut StdRng::seed_from_u64(DECK_ORDER_PEDERSEN_SEED))
+    }
+}
+
+impl<G: CurveGroup> DeckCommitment for PedersenDeckCommitment<G> {
+    type Output = G;
+    type Randomness = PedersenRandomness<G>;
+
+    fn commit(&self, deck: &[u8], randomness: &Self::Randomness) -> G {
+        pedersen_commit::<G, DeckHashWindow>(&self.params, deck, randomness).into_group()
+    }
+}
+
+impl<G, GG> DeckCommitmentGadget<G::BaseField, GG> for PedersenDeckCommitment<G>
+where
+    G: CurveGroup,
+    G::BaseField: PrimeField,
+    GG: CurveVar<G, G::BaseField>,
+    for<'a> &'a GG: GroupOpsBounds<'a, G, GG>,
+{
+    fn commit_gadget(
+        &self,
+        cs: ConstraintSystemRef<G::BaseField>,
+        deck: &[UInt8<G::BaseField>],
+        randomness: &Self::Randomness,
+    ) -> Result<GG, SynthesisError> {
+        type CG<G, GG> = PedersenCommGadget<G, GG, DeckHashWindow>;
+        type Scheme<G> = PedersenCommitment<G, DeckHashWindow>;
+
+        let params_var =
+            <CG<G, GG> as CommitmentGadget<Scheme<G>, G::BaseField>>::ParametersVar::new_constant(
+                ark_relations::ns!(cs, "deck_pedersen_params"),
+                &self.params,
+            )?;
+        let rand_var =
+            <CG<G, GG> as CommitmentGadget<Scheme<G>, G::BaseField>>::RandomnessVar::new_witness(
+                ark_relations::ns!(cs, "deck_pedersen_randomness"),
+                || Ok(randomness),
+            )?;
+        <CG<G, GG> as CommitmentGadget<Scheme<G>, G::BaseField>>::commit(
+            &params_var,
+            deck,
+            &rand_var,
+        )
+    }
+}
+
+/// Poseidon over `[randomness, card_0, .., card_n]`, each card absorbed as one field element.
+#[derive(Clone)]
+pub struct PoseidonDeckCommitment<F: PrimeField> {
+    pub config: PoseidonConfig<F>,
+}
+
+impl<F: PrimeField> Default for PoseidonDeckCommitment<F> {
+    fn default() -> Self {
+        Self {
+            config: crate::config::poseidon_config::<F>(),
+        }
+    }
+}
+
+impl<F: PrimeField + Absorb> DeckCommitment for PoseidonDeckCommitment<F> {
+    type Output = F;
+    type Randomness = F;
+
+    fn commit(&self, deck: &[u8], randomness: &F) -> F {
+        let inputs: Vec<F> = std::iter::once(*randomness)
+            .chain(deck.iter().map(|card| F::from(*card)))
+            .collect();
+        let mut sponge = PoseidonSponge::new(&self.config);
+        sponge.absorb(&inputs);
+        sponge.squeeze_field_elements(1)[0]
+    }
+}
+
+impl<F: PrimeField + Absorb> DeckCommitmentGadget<F, FpVar<F>> for PoseidonDeckCommitment<F> {
+    fn commit_gadget(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        deck: &[UInt8<F>],
+        randomness: &F,
+    ) -> Result<FpVar<F>, SynthesisError> {
+        let randomness_var =
+            FpVar::new_witness(ark_relations::ns!(cs, "deck_poseidon_randomness"), || {
+                Ok(*randomness)
+            })?;
+        let mut inputs = vec![randomness_var];
+        for card in deck {
+            inputs.push(Boolean::le_bits_to_fp(&card.to_bits_le()?)?);
+        }
+        let mut sponge = PoseidonSpongeVar::new(cs, &self.config);
+        sponge.absorb(&inputs)?;
+        Ok(sponge.squeeze_field_elements(1)?[0].clone())
+    }
+}
+
+/// Commitment to a shuffle's deck order, published by the permutation proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeckOrderCommitment<C: CurveGroup> {
+    Pedersen(C),
+    Poseidon(C::BaseField),
+}
+
+/// Blinding behind a [`DeckOrderCommitment`]. It stays with the prover; revealing it together
+/// with the order opens the commitment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeckOrderOpening<C: CurveGroup> {
+    Pedersen(C::ScalarField),
+    Poseidon(C::BaseField),
+}
+
+impl<C: CurveGroup> DeckOrderCommitment<C>
+where
+    C::BaseField: PrimeField + Absorb,
+{
+    /// Commit to `order` (one byte per card) under the scheme `opening` belongs to.
+    pub fn commit(order: &[u8], opening: &DeckOrderOpening<C>) -> Self {
+        match opening {
+            DeckOrderOpening::Pedersen(r) => Self::Pedersen(
+                PedersenDeckCommitment::<C>::deck_order().commit(order, &PedersenRandomness(*r)),
+            ),
+            DeckOrderOpening::Poseidon(r) => {
+                Self::Poseidon(PoseidonDeckCommitment::<C::BaseField>::default().commit(order, r))
+            }
+        }
+    }
+
+    pub fn scheme(&self) -> DeckCommitmentScheme {
+        match self {
+            Self::Pedersen(_) => DeckCommitmentScheme::Pedersen,
+            Self::Poseidon(_) => DeckCommitmentScheme::Poseidon,
+        }
+    }
+}
+
+/// Allocate `commitment` as a public input and enforce that `order` opens it under `opening`.
+///
+/// A Pedersen commitment is allocated as a curve point and a Poseidon commitment as a single
+/// field element. An opening for the other scheme leaves the circuit unsatisfiable.
+pub fn enforce_deck_order_commitment<C, GG>(
+    cs: ConstraintSystemRef<C::BaseField>,
+    order: &[UInt8<C::BaseField>],
+    commitment: &DeckOrderCommitment<C>,
+    opening: &DeckOrderOpening<C>,
+) -> Result<(), SynthesisError>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField + Absorb,
+    GG: CurveVar<C, C::BaseField>,
+    for<'a> &'a GG: GroupOpsBounds<'a, C, GG>,
+{
+    match (commitment, opening) {
+        (DeckOrderCommitment::Pedersen(value), DeckOrderOpening::Pedersen(r)) => {
+            let expected = GG::new_input(ark_relations::ns!(cs, "deck_order_commitment"), || {
+                Ok(*value)
+            })?;
+            let computed: GG = PedersenDeckCommitment::<C>::deck_order().commit_gadget(
+                cs,
+                order,
+                &PedersenRandomness(*r),
+            )?;
+            computed.enforce_equal(&expected)
+        }
+        (DeckOrderCommitment::Poseidon(value), DeckOrderOpening::Poseidon(r)) => {
+            let expected =
+                FpVar::new_input(ark_relations::ns!(cs, "deck_order_commitment"), || {
+                    Ok(*value)
+                })?;
+            let computed =
+                PoseidonDeckCommitment::<C::BaseField>::default().commit_gadget(cs, order, r)?;
+            computed.enforce_equal(&expected)
+        }
+        _ => Err(SynthesisError::Unsatisfiable),
+    }
+}
+
+/// Commitment to an encrypted deck: the component-wise sum of its ciphertexts.
+///
+/// The `c2` sum is a Pedersen commitment to `Σm` under bases `(g, pk)` with blinding `Σr`, and
+/// the `c1` sum is `g^Σr`. A shuffle step permutes the deck, which leaves the sums unchanged,
+/// and rerandomizes card `i` by `ρ_i`, which adds `(g^Σρ, pk^Σρ)`. The next commitment therefore
+/// follows from the previous one and the step's rerandomization scalars alone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncrementalDeckCommitment<C: CurveGroup> {
+    pub aggregate: ElGamalCiphertext<C>,
+}
+
+impl<C: CurveGroup> IncrementalDeckCommitment<C> {
+    /// Commit to `deck` from scratch.
+    pub fn from_deck(deck: &[ElGamalCiphertext<C>]) -> Self {
+        let aggregate = deck.iter().fold(
+            ElGamalCiphertext::new(C::zero(), C::zero()),
+            |acc, ciphertext| {
+                ElGamalCiphertext::new(acc.c1 + ciphertext.c1, acc.c2 + ciphertext.c2)
+            },
+        );
+        Self { aggregate }
+    }
+
+    /// Commitment after a shuffle step that rerandomized its cards by `rerandomizations`.
+    pub fn apply_step(&self, rerandomizations: &[C::ScalarField], public_key: C) -> Self {
+        self.apply_delta(rerandomizations.iter().sum(), public_key)
+    }
+
+    /// Commitment after a shuffle step whose rerandomization scalars sum to `delta`.
+    pub fn apply_delta(&self, delta: C::ScalarField, public_key: C) -> Self {
+        Self {
+            aggregate: self.aggregate.add_encryption_layer(delta, public_key),
+        }
+    }
+
+    /// Whether `next` is this commitment advanced by a step with aggregate rerandomization
+    /// `delta`. Costs two scalar multiplications regardless of deck size.
+    pub fn verify_step(&self, next: &Self, delta: C::ScalarField, public_key: C) -> bool {
+        self.apply_delta(delta, public_key) == *next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shuffling::data_structures::DECK_SIZE;
+    use crate::shuffling::{draw_shuffler_public_key, generate_random_ciphertexts};
+    use ark_bn254::{Fq as BaseField, Fr as ScalarField, G1Projective};
+    use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar;
+    use ark_r1cs_std::GR1CSVar;
+    use ark_relations::gr1cs::ConstraintSystem;
+    use ark_std::test_rng;
+
+    type G1Var = ProjectiveVar<ark_bn254::g1::Config, FpVar<BaseField>>;
+
+    fn deck_vars(cs: &ConstraintSystemRef<BaseField>, deck: &[u8]) -> Vec<UInt8<BaseField>> {
+        deck.iter()
+            .map(|card| UInt8::new_witness(cs.clone(), || Ok(*card)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn both_schemes_commit_and_gadgets_match_native() {
+        let mut rng = test_rng();
+        let deck: Vec<u8> = (0..52).rev().collect();
+        let mut swapped = deck.clone();
+        swapped.swap(0, 1);
+
+        let pedersen = PedersenDeckCommitment::<G1Projective>::setup(&mut rng);
+        let pedersen_rand = PedersenRandomness(ScalarField::rand(&mut rng));
+        let pedersen_value = pedersen.commit(&deck, &pedersen_rand);
+        assert_eq!(pedersen_value, pedersen.commit(&deck, &pedersen_rand));
+        assert_ne!(pedersen_value, pedersen.commit(&swapped, &pedersen_rand));
+
+        let poseidon = PoseidonDeckCommitment::<BaseField>::default();
+        let poseidon_rand = BaseField::rand(&mut rng);
+        let poseidon_value = poseidon.commit(&deck, &poseidon_rand);
+        assert_eq!(poseidon_value, poseidon.commit(&deck, &poseidon_rand));
+        assert_ne!(poseidon_value, poseidon.commit(&swapped, &poseidon_rand));
+
+        let cs = ConstraintSystem::<BaseField>::new_ref();
+        let deck_var = deck_vars(&cs, &deck);
+        let pedersen_var: G1Var = pedersen
+            .commit_gadget(cs.clone(), &deck_var, &pedersen_rand)
+            .unwrap();
+        let poseidon_var = poseidon
+            .commit_gadget(cs.clone(), &deck_var, &poseidon_rand)
+            .unwrap();
+
+        assert_eq!(pedersen_var.value().unwrap(), pedersen_value);
+        assert_eq!(poseidon_var.value().unwrap(), poseidon_value);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn deck_order_commitment_is_enforced_under_its_own_scheme() {
+        let mut rng = test_rng();
+        let order: Vec<u8> = (0..DECK_SIZE).map(|i| (i * 7 % DECK_SIZE) as u8).collect();
+
+        for scheme in [
+            DeckCommitmentScheme::Pedersen,
+            DeckCommitmentScheme::Poseidon,
+        ] {
+            let opening = scheme.sample_opening::<G1Projective, _>(&mut rng);
+            let commitment = DeckOrderCommitment::commit(&order, &opening);
+            assert_eq!(commitment.scheme(), scheme);
+
+            let cs = ConstraintSystem::<BaseField>::new_ref();
+            let order_var = deck_vars(&cs, &order);
+            enforce_deck_order_commitment::<G1Projective, G1Var>(
+                cs.clone(),
+                &order_var,
+                &commitment,
+                &opening,
+            )
+            .unwrap();
+            assert!(cs.is_satisfied().unwrap(), "{scheme:?}");
+
+            let mut reordered = order.clone();
+            reordered.swap(3, 4);
+            let cs = ConstraintSystem::<BaseField>::new_ref();
+            let order_var = deck_vars(&cs, &reordered);
+            enforce_deck_order_commitment::<G1Projective, G1Var>(
+                cs.clone(),
+                &order_var,
+                &commitment,
+                &opening,
+            )
+            .unwrap();
+            assert!(!cs.is_satisfied().unwrap(), "{scheme:?}");
+        }
+
+        let pedersen_opening = DeckCommitmentScheme::Pedersen.sample_opening(&mut rng);
+        let commitment = DeckOrderCommitment::<G1Projective>::commit(&order, &pedersen_opening);
+        let poseidon_opening = DeckCommitmentScheme::Poseidon.sample_opening(&mut rng);
+        let cs = ConstraintSystem::<BaseField>::new_ref();
+        let order_var = deck_vars(&cs, &order);
+        assert!(matches!(
+            enforce_deck_order_commitment::<G1Projective, G1Var>(
+                cs,
+                &order_var,
+                &commitment,
+                &poseidon_opening,
+            ),
+            Err(SynthesisError::Unsatisfiable)
+        ));
+    }
+
+    #[test]
+    fn incremental_commitment_tracks_three_shuffle_steps() {
+        let mut rng = test_rng();
+        let (_, public_key) = draw_shuffler_public_key::<G1Projective, _>(&mut rng);
+        let (mut deck, _) =
+            generate_random_ciphertexts::<G1Projective, DECK_SIZE>(&public_key, &mut rng);
+        let mut commitment = IncrementalDeckCommitment::from_deck(&deck);
+
+        for step in 1..=3 {
+            let rerandomizations: Vec<ScalarField> = (0..DECK_SIZE)
+                .map(|_| ScalarField::rand(&mut rng))
+                .collect();
+            deck = std::array::from_fn(|i| {
+                deck[(i * 7 + step) % DECK_SIZE]
+                    .add_encryption_layer(rerandomizations[i], public_key)
+            });
+            let next = commitment.apply_step(&rerandomizations, public_key);
+            let delta = rerandomizations.iter().sum();
+            assert!(commitment.verify_step(&next, delta, public_key));
+            commitment = next;
+        }
+
+        assert_eq!(commitment, IncrementalDeckCommitment::from_deck(&deck));
+    }
+}
@@ -0,0 +1,154 @@
+//! Proof that an initial deck encodes the canonical card values.
+//!
+//! The initial deck encrypts card `i` as `g^i` under the aggregated key, and every later shuffle
+//! proof only shows that the output is a permutation of that input. Without an encoding proof a
+//! dealer could start from a deck with duplicated or out-of-range plaintexts. For each position
+//! the dealer proves with a Chaum-Pedersen proof that `c1 = g^r` and `c2 - g^i = pk^r` share the
+//! same `r`, i.e. ciphertext `i` decrypts to exactly `g^i`. That pins the deck to the ordering
+//! `0..N`, which in particular makes every plaintext distinct and in range.
+
+use crate::chaum_pedersen::ChaumPedersenProof;
+use crate::curve_absorb::CurveAbsorb;
+use crate::poseidon_config;
+use crate::shuffling::data_structures::ElGamalCiphertext;
+use crate::shuffling::generate_random_ciphertexts;
+use crate::signing::DomainSeparated;
+use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge};
+use ark_ec::{CurveConfig, CurveGroup};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+const LOG_TARGET: &str = "legit_poker::shuffling::deck_encoding";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DeckEncodingError {
+    #[error("encoding proof covers {proofs} cards but the deck has {deck}")]
+    LengthMismatch { proofs: usize, deck: usize },
+    #[error("card {0} does not encrypt its canonical value")]
+    InvalidCard(usize),
+}
+
+/// One Chaum-Pedersen proof per deck position, in deck order.
+#[derive(Clone, Debug, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize)]
+#[serde(bound(
+    serialize = "C: CanonicalSerialize, C::ScalarField: CanonicalSerialize",
+    deserialize = "C: CanonicalDeserialize, C::ScalarField: CanonicalDeserialize"
+))]
+pub struct DeckEncodingProof<C: CurveGroup> {
+    pub card_proofs: Vec<ChaumPedersenProof<C>>,
+}
+
+impl<C: CurveGroup> DomainSeparated for DeckEncodingProof<C> {
+    fn domain_string() -> &'static str {
+        "legit_poker/deck_encoding_v1"
+    }
+}
+
+impl<C> DeckEncodingProof<C>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField>,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + Absorb,
+{
+    /// Check that `deck[i]` encrypts `g^i` under `public_key` for every position.
+    pub fn verify(
+        &self,
+        deck: &[ElGamalCiphertext<C>],
+        public_key: C,
+    ) -> Result<(), DeckEncodingError> {
+        if self.card_proofs.len() != deck.len() {
+            return Err(DeckEncodingError::LengthMismatch {
+                proofs: self.card_proofs.len(),
+                deck: deck.len(),
+            });
+        }
+
+        let g = C::generator();
+        for (index, (cipher, proof)) in deck.iter().zip(&self.card_proofs).enumerate() {
+            let mut sponge = card_sponge(index, cipher, public_key);
+            let unmasked = cipher.c2 - g * C::ScalarField::from(index as u64);
+            if !proof.verify(&mut sponge, g, public_key, cipher.c1, unmasked) {
+                warn!(target: LOG_TARGET, index, "deck encoding proof rejected card");
+                return Err(DeckEncodingError::InvalidCard(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generate an initial deck encrypted to `public_key` together with a proof that card `i`
+/// encrypts the canonical value `g^i`.
+pub fn generate_committed_deck_with_proof<C, const N: usize>(
+    public_key: &C,
+    rng: &mut impl Rng,
+) -> ([ElGamalCiphertext<C>; N], DeckEncodingProof<C>)
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField>,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + Absorb,
+    C::Config: CurveConfig<ScalarField = C::ScalarField>,
+{
+    let (deck, randomness) = generate_random_ciphertexts::<C, N>(public_key, rng);
+    let card_proofs = deck
+        .iter()
+        .zip(randomness)
+        .enumerate()
+        .map(|(index, (cipher, r))| {
+            let mut sponge = card_sponge(index, cipher, *public_key);
+            ChaumPedersenProof::prove(&mut sponge, r, C::generator(), *public_key, rng)
+        })
+        .collect();
+
+    (deck, DeckEncodingProof { card_proofs })
+}
+
+/// Bind each card's challenge to its position, ciphertext and key so a proof for one card
+/// cannot be replayed against another.
+fn card_sponge<C>(
+    index: usize,
+    cipher: &ElGamalCiphertext<C>,
+    public_key: C,
+) -> PoseidonSponge<C::BaseField>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    let config = poseidon_config::<C::BaseField>();
+    let mut sponge = PoseidonSponge::new(&config);
+    sponge.absorb(&DeckEncodingProof::<C>::domain_string().as_bytes());
+    sponge.absorb(&(index as u64));
+    public_key.curve_absorb(&mut sponge);
+    cipher.c1.curve_absorb(&mut sponge);
+    cipher.c2.curve_absorb(&mut sponge);
+    sponge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shuffling::{draw_shuffler_public_key, DECK_SIZE};
+    use ark_bn254::G1Projective as Curve;
+    use ark_ec::PrimeGroup;
+    use ark_std::test_rng;
+
+    #[test]
+    fn duplicated_card_value_fails_encoding_proof() {
+        let mut rng = test_rng();
+        let (_, public_key) = draw_shuffler_public_key::<Curve, _>(&mut rng);
+        let (mut deck, proof) =
+            generate_committed_deck_with_proof::<Curve, DECK_SIZE>(&public_key, &mut rng);
+        assert_eq!(proof.verify(&deck, public_key), Ok(()));
+
+        // Re-encode card 1 as a second copy of card 0: g^0 under fresh randomness.
+        let r = <Curve as PrimeGroup>::ScalarField::from(7u64);
+        deck[1] = ElGamalCiphertext::new(Curve::generator() * r, public_key * r);
+        assert_eq!(
+            proof.verify(&deck, public_key),
+            Err(DeckEncodingError::InvalidCard(1))
+        );
+    }
+}
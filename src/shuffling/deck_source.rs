@@ -0,0 +1,102 @@
+//! Where a hand's initial encrypted deck comes from.
+//!
+//! Production hands start from [`RandomDeck`]: card `i` encrypts `g^i` under the aggregated
+//! shuffler key with fresh randomness. Tests that need to know which card sits where can use
+//! [`PlaintextDeck`] instead, which encrypts a caller-chosen order.
+
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::rand::RngCore;
+
+use super::data_structures::{ElGamalCiphertext, DECK_SIZE};
+
+/// Produces the encrypted deck a hand is shuffled from.
+pub trait DeckSource<C: CurveGroup>: Send + Sync {
+    fn encrypted_deck(
+        &self,
+        public_key: &C,
+        rng: &mut dyn RngCore,
+    ) -> [ElGamalCiphertext<C>; DECK_SIZE];
+}
+
+/// The canonical deck, card `i` at position `i`, under fresh encryption randomness.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomDeck;
+
+impl<C> DeckSource<C> for RandomDeck
+where
+    C: CurveGroup,
+    C::ScalarField: PrimeField,
+{
+    fn encrypted_deck(
+        &self,
+        public_key: &C,
+        rng: &mut dyn RngCore,
+    ) -> [ElGamalCiphertext<C>; DECK_SIZE] {
+        std::array::from_fn(|index| encrypt_card(index as u8, public_key, rng))
+    }
+}
+
+/// A deck whose position `i` encrypts card `order[i]`. `order` must have [`DECK_SIZE`] entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlaintextDeck(pub Vec<u8>);
+
+impl PlaintextDeck {
+    /// Cards in index order, the same layout [`RandomDeck`] encrypts.
+    pub fn canonical() -> Self {
+        Self((0..DECK_SIZE as u8).collect())
+    }
+}
+
+impl<C> DeckSource<C> for PlaintextDeck
+where
+    C: CurveGroup,
+    C::ScalarField: PrimeField,
+{
+    fn encrypted_deck(
+        &self,
+        public_key: &C,
+        rng: &mut dyn RngCore,
+    ) -> [ElGamalCiphertext<C>; DECK_SIZE] {
+        assert_eq!(
+            self.0.len(),
+            DECK_SIZE,
+            "plaintext deck must list every card position"
+        );
+        std::array::from_fn(|index| encrypt_card(self.0[index], public_key, rng))
+    }
+}
+
+fn encrypt_card<C>(card: u8, public_key: &C, rng: &mut dyn RngCore) -> ElGamalCiphertext<C>
+where
+    C: CurveGroup,
+    C::ScalarField: PrimeField,
+{
+    let randomness = C::ScalarField::rand(rng);
+    ElGamalCiphertext::encrypt_scalar(C::ScalarField::from(card), randomness, *public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shuffling::draw_shuffler_public_key;
+    use ark_bn254::G1Projective as Curve;
+    use ark_ec::PrimeGroup;
+    use ark_std::test_rng;
+
+    #[test]
+    fn plaintext_deck_decrypts_to_its_order() {
+        let mut rng = test_rng();
+        let (secret, public_key) = draw_shuffler_public_key::<Curve, _>(&mut rng);
+        let order: Vec<u8> = (0..DECK_SIZE as u8).rev().collect();
+
+        let deck = PlaintextDeck(order.clone()).encrypted_deck(&public_key, &mut rng);
+        for (cipher, card) in deck.iter().zip(order) {
+            let plaintext = cipher.c2 - cipher.c1 * secret;
+            assert_eq!(
+                plaintext,
+                Curve::generator() * <Curve as PrimeGroup>::ScalarField::from(card)
+            );
+        }
+    }
+}
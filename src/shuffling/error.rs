@@ -26,4 +26,32 @@ pub enum ShuffleError {
 
     #[error("Unsatisfied constraint: {0}")]
     UnsatisfiedConstraint(String),
+
+    #[error(
+        "Circuit parameter mismatch: expected {}, found {}",
+        hex::encode(.expected),
+        hex::encode(.found)
+    )]
+    ParamMismatch { expected: [u8; 32], found: [u8; 32] },
+}
+
+/// Why a card ciphertext failed to decrypt.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecryptionError {
+    /// Every component of the ciphertext is the identity. That is the placeholder for a slot no
+    /// card was dealt to; decrypting it would otherwise yield card 0 (`g^0`).
+    #[error("ciphertext is the identity placeholder; no card was dealt to it")]
+    UndealtCard,
+
+    #[error("Recovered element does not correspond to a valid card value")]
+    UnknownCard,
+
+    #[error("{0}")]
+    InvalidShares(&'static str),
+}
+
+impl From<&'static str> for DecryptionError {
+    fn from(reason: &'static str) -> Self {
+        Self::InvalidShares(reason)
+    }
 }
@@ -0,0 +1,156 @@
+//! Deterministic stand-in for shuffle proofs, for tests only
+//!
+//! Producing a real shuffle proof needs a Groth16 trusted setup and several seconds of
+//! proving, which is far too slow for transition and operator tests. A
+//! [`MockShufflingProof`] instead encodes a known marker in the proof's sorted-deck random
+//! values. The shuffle transition recognises the marker and accepts a
+//! [`MockShufflingProof::Valid`] proof, and rejects a [`MockShufflingProof::Invalid`] one,
+//! so tests can assert both outcomes without any setup.
+//!
+//! Only compiled under `cfg(test)` or the `test-mocks` feature.
+
+use ark_ec::CurveGroup;
+use ark_ff::Zero;
+
+use super::data_structures::{ElGamalCiphertext, ShuffleProof, DECK_SIZE};
+
+/// Marker placed in the first sorted-deck random value of a mock proof ("MOCKPRF\0")
+pub const MOCK_PROOF_MARKER: u64 = 0x4d4f_434b_5052_4600;
+
+/// Outcome a mock shuffle proof asks the verifier to report
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MockShufflingProof {
+    Valid,
+    Invalid,
+}
+
+impl MockShufflingProof {
+    fn flag(self) -> u64 {
+        match self {
+            Self::Valid => 1,
+            Self::Invalid => 0,
+        }
+    }
+
+    /// Build a mock proof for shuffling `deck_in` into `deck_out`
+    pub fn build<C>(
+        self,
+        deck_in: &[ElGamalCiphertext<C>; DECK_SIZE],
+        deck_out: &[ElGamalCiphertext<C>; DECK_SIZE],
+    ) -> ShuffleProof<C>
+    where
+        C: CurveGroup,
+    {
+        let sorted_deck = deck_out
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, cipher)| {
+                let value = match i {
+                    0 => C::BaseField::from(MOCK_PROOF_MARKER),
+                    1 => C::BaseField::from(self.flag()),
+                    _ => C::BaseField::zero(),
+                };
+                (cipher, value)
+            })
+            .collect();
+        ShuffleProof::new(
+            deck_in.to_vec(),
+            sorted_deck,
+            vec![C::ScalarField::zero(); DECK_SIZE],
+        )
+        .expect("mock proof has deck-sized vectors")
+    }
+
+    /// Mock proof the verifier accepts
+    pub fn valid<C>(
+        deck_in: &[ElGamalCiphertext<C>; DECK_SIZE],
+        deck_out: &[ElGamalCiphertext<C>; DECK_SIZE],
+    ) -> ShuffleProof<C>
+    where
+        C: CurveGroup,
+    {
+        Self::Valid.build(deck_in, deck_out)
+    }
+
+    /// Mock proof the verifier rejects
+    pub fn invalid<C>(
+        deck_in: &[ElGamalCiphertext<C>; DECK_SIZE],
+        deck_out: &[ElGamalCiphertext<C>; DECK_SIZE],
+    ) -> ShuffleProof<C>
+    where
+        C: CurveGroup,
+    {
+        Self::Invalid.build(deck_in, deck_out)
+    }
+
+    /// Recognise a mock proof; returns `None` for proofs without the marker
+    pub fn detect<C>(proof: &ShuffleProof<C>) -> Option<Self>
+    where
+        C: CurveGroup,
+    {
+        let marker = proof.sorted_deck.first()?.1;
+        if marker != C::BaseField::from(MOCK_PROOF_MARKER) {
+            return None;
+        }
+        let flag = proof.sorted_deck.get(1)?.1;
+        if flag == C::BaseField::from(Self::Valid.flag()) {
+            Some(Self::Valid)
+        } else {
+            Some(Self::Invalid)
+        }
+    }
+
+    /// Whether a proof is a mock proof flagged as invalid
+    pub fn is_rejected<C>(proof: &ShuffleProof<C>) -> bool
+    where
+        C: CurveGroup,
+    {
+        Self::detect(proof) == Some(Self::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_grumpkin::Projective as Curve;
+
+    fn sample_deck() -> [ElGamalCiphertext<Curve>; DECK_SIZE] {
+        std::array::from_fn(|_| ElGamalCiphertext::new(Curve::zero(), Curve::zero()))
+    }
+
+    #[test]
+    fn detect_round_trips_factory_outcome() {
+        let deck = sample_deck();
+        let valid = MockShufflingProof::valid(&deck, &deck);
+        let invalid = MockShufflingProof::invalid(&deck, &deck);
+
+        assert_eq!(
+            MockShufflingProof::detect(&valid),
+            Some(MockShufflingProof::Valid)
+        );
+        assert_eq!(
+            MockShufflingProof::detect(&invalid),
+            Some(MockShufflingProof::Invalid)
+        );
+        assert!(!MockShufflingProof::is_rejected(&valid));
+        assert!(MockShufflingProof::is_rejected(&invalid));
+    }
+
+    #[test]
+    fn detect_ignores_unmarked_proofs() {
+        let deck = sample_deck();
+        let sorted = deck
+            .iter()
+            .cloned()
+            .map(|cipher| (cipher, <Curve as CurveGroup>::BaseField::zero()))
+            .collect();
+        let proof = ShuffleProof::new(
+            deck.to_vec(),
+            sorted,
+            vec![<Curve as ark_ec::PrimeGroup>::ScalarField::zero(); DECK_SIZE],
+        )
+        .unwrap();
+        assert_eq!(MockShufflingProof::detect(&proof), None);
+    }
+}
@@ -6,14 +6,20 @@ pub use og_shuffling as circuit;
 pub mod community_decryption;
 pub use crate::curve_absorb;
 pub mod data_structures;
+pub mod deck_commitment;
+pub mod deck_encoding;
+pub mod deck_source;
 pub mod encryption;
 pub mod error;
+#[cfg(any(test, feature = "test-mocks"))]
+pub mod mock_proof;
 pub use crate::field_conversion_gadget;
 pub use crate::pedersen_commitment;
 pub mod permutation_proof;
 pub mod permutation_serialization;
 pub mod player_decryption;
 pub use player_decryption::gadget as player_decryption_gadget;
+pub mod proof_backend;
 pub mod proof_system;
 use ark_ff::Field;
 use ark_std::rand::Rng;
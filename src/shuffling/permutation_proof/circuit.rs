@@ -7,10 +7,14 @@ use ark_ff::PrimeField;
 use ark_r1cs_std::alloc::AllocVar;
 use ark_r1cs_std::fields::{emulated_fp::EmulatedFpVar, fp::FpVar};
 use ark_r1cs_std::groups::CurveVar;
+use ark_r1cs_std::prelude::{Boolean, EqGadget, ToBitsGadget, UInt8};
 use ark_relations::gr1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
 use ark_std::marker::PhantomData;
 
 use crate::config::poseidon_config;
+use crate::shuffling::deck_commitment::{
+    enforce_deck_order_commitment, DeckCommitmentScheme, DeckOrderCommitment, DeckOrderOpening,
+};
 use crate::shuffling::pedersen_commitment::opening_proof_gadget::PedersenCommitmentOpeningProofVar;
 use crate::shuffling::permutation_proof::prove_permutation_gadget;
 use crate::shuffling::rs_shuffle::data_structures::{
@@ -23,6 +27,7 @@ type ConstraintF<C> = <<C as CurveGroup>::BaseField as ark_ff::Field>::BasePrime
 /// - VRF-derived randomness (nonce, sk, pk) → RS bit matrix binding
 /// - BG power challenge derived from `c_perm` equals `power_challenge_public`
 /// - Pedersen opening proof linking `c_power` to the scalar vector b = [x^π(i)]
+/// - `deck_commitment` opening to the shuffled order under its scheme
 #[derive(Clone)]
 pub struct PermutationProofCircuit<C, GG, const N: usize, const LEVELS: usize>
 where
@@ -48,12 +53,14 @@ where
     pub power_opening_proof: Option<
         crate::shuffling::pedersen_commitment::opening_proof::PedersenCommitmentOpeningProof<C>,
     >,
+    pub deck_commitment: Option<DeckOrderCommitment<C>>,
 
     // Private witnesses
     pub sk: Option<C::ScalarField>,
     pub rs_witness: Option<PermutationWitnessTrace<N, LEVELS>>,
     pub power_perm_vec_wit: Option<[ConstraintF<C>; N]>,
     pub power_perm_vec_scalar_wit: Option<[C::ScalarField; N]>,
+    pub deck_opening: Option<DeckOrderOpening<C>>,
     pub(crate) _pd: PhantomData<GG>,
 }
 
@@ -69,7 +76,7 @@ where
         >,
 {
     /// Construct a blank circuit with zero/identity public inputs and no witnesses.
-    pub fn blank(num_samples: usize) -> Self {
+    pub fn blank(num_samples: usize, deck_commitment: DeckCommitmentScheme) -> Self {
         // Helper defaults
         let zero_f = ConstraintF::<C>::from(0u64);
         let zero_g = C::zero();
@@ -89,10 +96,15 @@ where
                     r_final: C::ScalarField::from(0u64),
                 },
             ),
+            deck_commitment: Some(match deck_commitment {
+                DeckCommitmentScheme::Pedersen => DeckOrderCommitment::Pedersen(zero_g),
+                DeckCommitmentScheme::Poseidon => DeckOrderCommitment::Poseidon(zero_f),
+            }),
             sk: None,
             rs_witness: None,
             power_perm_vec_wit: None,
             power_perm_vec_scalar_wit: None,
+            deck_opening: None,
             _pd: PhantomData,
         }
     }
@@ -201,7 +213,7 @@ where
             N,
             LEVELS,
         >(
-            cs,
+            cs.clone(),
             &mut sponge_var,
             &nonce,
             sk_var,
@@ -215,6 +227,25 @@ where
             &power_opening_proof_var,
             &power_perm_vec_wit,
             &power_perm_vec_scalar_wit,
-        )
+        )?;
+
+        // 5) Publish the shuffled order under the configured deck commitment. Each card is a
+        //    byte witness tied to the RS output index, then the commitment is allocated as the
+        //    last public input.
+        let deck_order = rs_witness_var.sorted_levels[LEVELS - 1]
+            .iter()
+            .zip(&rs_witness_native.next_levels[LEVELS - 1])
+            .map(|(row_var, row)| {
+                let card = u8::try_from(row.idx).map_err(|_| SynthesisError::Unsatisfiable)?;
+                let card_var = UInt8::new_witness(cs.clone(), || Ok(card))?;
+                Boolean::le_bits_to_fp(&card_var.to_bits_le()?)?.enforce_equal(&row_var.idx)?;
+                Ok(card_var)
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        let deck_commitment = self
+            .deck_commitment
+            .ok_or(SynthesisError::AssignmentMissing)?;
+        let deck_opening = self.deck_opening.ok_or(SynthesisError::AssignmentMissing)?;
+        enforce_deck_order_commitment::<C, GG>(cs, &deck_order, &deck_commitment, &deck_opening)
     }
 }
@@ -19,14 +19,14 @@
 
 use crate::shuffling::bayer_groth_permutation::bg_setup::BGPowerChallengeSetup;
 use crate::shuffling::curve_absorb::{CurveAbsorb, CurveAbsorbGadget};
+use crate::shuffling::deck_commitment::{DeckOrderCommitment, DeckOrderOpening};
 use crate::shuffling::pedersen_commitment::opening_proof::PedersenCommitmentOpeningProof;
-use crate::vrf::simple::prove_simple_vrf;
 use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
 use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
 // use ark_crypto_primitives::sponge::poseidon::PoseidonSponge; // used in tests
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ec::CurveGroup;
-use ark_ff::{One, PrimeField, UniformRand};
+use ark_ff::{One, PrimeField};
 use ark_r1cs_std::groups::CurveVar;
 use ark_r1cs_std::{
     alloc::AllocVar,
@@ -49,9 +49,14 @@ type ConstraintF<C> = <<C as CurveGroup>::BaseField as ark_ff::Field>::BasePrime
 // Submodules providing the circuit wrapper and Groth16 proof system
 pub mod circuit;
 pub mod proof_system;
+pub mod witness_assembler;
 
 // Re-exports for ergonomic access to the proof system API
-pub use proof_system::{build_public_inputs, PermutationGroth16, PublicData, WitnessData};
+pub use proof_system::{
+    build_public_inputs, circuit_parameters_hash, keygen_circuit, PermutationGroth16,
+    PointFromCoordinates, PublicData, WitnessData,
+};
+pub use witness_assembler::{AssembledPermutationWitness, WitnessAssembler};
 
 /// Native (prover-side) configuration parameters for preparing permutation witnesses
 pub struct PermutationParameters<'a, C: CurveGroup, R: rand::RngCore> {
@@ -79,6 +84,73 @@ where
     pub power_opening_proof: PedersenCommitmentOpeningProof<C>,
     pub blinding_r: C::ScalarField,
     pub blinding_s: C::ScalarField,
+    pub deck_commitment: DeckOrderCommitment<C>,
+    pub deck_opening: DeckOrderOpening<C>,
+}
+
+/// Absorb the permutation proof's public inputs in transcript order: pk, nonce, power
+/// challenge, c_perm, c_power.
+///
+/// [`absorb_permutation_transcript_gadget`] absorbs the same values in the same order; both
+/// sides must change together or the RS challenge diverges between prover and circuit.
+pub fn absorb_permutation_transcript<C, RO>(
+    sponge: &mut RO,
+    pk: &C,
+    nonce: &C::BaseField,
+    power_challenge: &C::BaseField,
+    c_perm: &C,
+    c_power: &C,
+) where
+    C: CurveGroup + CurveAbsorb<C::BaseField, RO>,
+    C::BaseField: PrimeField + Absorb,
+    RO: CryptographicSponge,
+{
+    pk.curve_absorb(sponge);
+    sponge.absorb(nonce);
+    sponge.absorb(power_challenge);
+    c_perm.curve_absorb(sponge);
+    c_power.curve_absorb(sponge);
+}
+
+/// In-circuit counterpart of [`absorb_permutation_transcript`].
+pub fn absorb_permutation_transcript_gadget<C, GG>(
+    sponge: &mut PoseidonSpongeVar<ConstraintF<C>>,
+    pk: &GG,
+    nonce: &FpVar<ConstraintF<C>>,
+    power_challenge: &FpVar<ConstraintF<C>>,
+    c_perm: &GG,
+    c_power: &GG,
+) -> Result<(), SynthesisError>
+where
+    C: CurveGroup,
+    ConstraintF<C>: PrimeField + Absorb,
+    GG: CurveAbsorbGadget<ConstraintF<C>, PoseidonSpongeVar<ConstraintF<C>>>,
+{
+    pk.curve_absorb_gadget(sponge)?;
+    sponge.absorb(nonce)?;
+    sponge.absorb(power_challenge)?;
+    c_perm.curve_absorb_gadget(sponge)?;
+    c_power.curve_absorb_gadget(sponge)?;
+    Ok(())
+}
+
+/// Derive the RS shuffle challenge natively from a fresh Poseidon sponge over the public
+/// transcript, matching the first squeeze of [`prove_permutation_gadget`].
+pub fn derive_permutation_alpha<C>(
+    pk: &C,
+    nonce: &C::BaseField,
+    power_challenge: &C::BaseField,
+    c_perm: &C,
+    c_power: &C,
+) -> C::BaseField
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField>,
+    C::BaseField: PrimeField + Absorb,
+{
+    let sponge_cfg = crate::config::poseidon_config::<C::BaseField>();
+    let mut sponge = ark_crypto_primitives::sponge::poseidon::PoseidonSponge::new(&sponge_cfg);
+    absorb_permutation_transcript(&mut sponge, pk, nonce, power_challenge, c_perm, c_power);
+    sponge.squeeze_field_elements(1)[0]
 }
 
 /// Convenience builder to construct (PublicData, WitnessData) from a prepared bundle
@@ -100,6 +172,7 @@ where
         c_perm: prepared.bg_setup.permutation_commitment,
         c_power: prepared.bg_setup.power_permutation_commitment,
         power_opening_proof: prepared.power_opening_proof.clone(),
+        deck_commitment: prepared.deck_commitment.clone(),
     };
 
     let witness = WitnessData::<C, N, LEVELS> {
@@ -107,6 +180,7 @@ where
         rs_witness: prepared.rs_trace.witness_trace.clone(),
         power_perm_vec_wit: prepared.perm_power_vector_base.map(|x| x.into()),
         power_perm_vec_scalar_wit: prepared.perm_power_vector_scalar,
+        deck_opening: prepared.deck_opening.clone(),
     };
 
     (public, witness)
@@ -117,6 +191,8 @@ where
 /// Note: This function is scaffolded for completeness. It wires the existing native
 /// utilities to produce the objects that the circuit expects, but it is not used by
 /// the circuit gadgets directly.
+///
+/// Runs the same sections as [`WitnessAssembler`] but keeps every intermediate.
 pub fn prepare_witness<C, R, RO, const N: usize, const LEVELS: usize>(
     params: &mut PermutationParameters<'_, C, R>,
     nonce: C::BaseField,
@@ -130,63 +206,38 @@ where
     R: rand::RngCore,
     RO: CryptographicSponge,
 {
-    use crate::shuffling::bayer_groth_permutation::bg_setup::new_bayer_groth_transcript_with_poseidon;
-    use crate::shuffling::pedersen_commitment::opening_proof::{
-        prove_with_flexible_size, PedersenParams,
-    };
-    use crate::shuffling::rs_shuffle::native::run_rs_shuffle_permutation;
-    use ark_std::vec::Vec;
+    let mut assembler = WitnessAssembler::<C, R, N, LEVELS>::new(params, nonce, sk);
 
     // 1) Simple VRF native prove to obtain vrf_value and pk
-    let g = C::generator();
-    let pk = g * sk;
-    // Use the provided random oracle sponge
-    let vrf_value = prove_simple_vrf::<C, _>(sponge, &nonce, &sk, &pk);
+    let (pk, vrf_value) = assembler.vrf_section(sponge);
 
     // 2) RS shuffle witnesses using vrf_value as seed
-    let input: [usize; N] = std::array::from_fn(|i| i);
-    let rs_trace = run_rs_shuffle_permutation::<C::BaseField, usize, N, LEVELS>(vrf_value, &input);
+    let rs_trace = WitnessAssembler::<C, R, N, LEVELS>::rs_section(vrf_value);
+    let permutation = rs_trace.extract_permutation_array();
 
     // Extract 0-indexed permutation in scalar field
-    let permutation_vec: [C::ScalarField; N] = rs_trace
-        .extract_permutation_array()
-        .into_iter()
-        .map(|v| C::ScalarField::from(v as u64))
-        .collect::<Vec<_>>()
-        .try_into()
-        .map_err(|_| anyhow::anyhow!("Permutation length mismatch"))?;
+    let permutation_vec: [C::ScalarField; N] = permutation.map(|v| C::ScalarField::from(v as u64));
 
     // 3) Bayer–Groth setup (derive x from c_perm, compute b = [x^π(i)], commit c_power)
-    let mut transcript =
-        new_bayer_groth_transcript_with_poseidon::<C::BaseField>(b"permutation-proof");
-    let blinding_r = C::ScalarField::rand(params.rng);
-    let blinding_s = C::ScalarField::rand(params.rng);
-    let (perm_power_vector_base, perm_power_vector_scalar, bg_power_challenge_setup) = transcript
-        .compute_power_challenge_setup::<C, N>(
-            params.perm_params,
-            params.power_params,
-            &rs_trace.extract_permutation_array(),
-            blinding_r,
-            blinding_s,
-        );
+    let (
+        perm_power_vector_base,
+        perm_power_vector_scalar,
+        bg_power_challenge_setup,
+        blinding_r,
+        blinding_s,
+    ) = assembler.power_section(&permutation);
 
     // 4) Pedersen opening proof for c_power (flexible size; pads to next power of 2)
-    let padded_size = if N.is_power_of_two() {
-        N
-    } else {
-        N.next_power_of_two()
-    };
-    let ped_params =
-        PedersenParams::<C>::from_arkworks_dynamic(params.power_params.clone(), padded_size);
-    let opening = prove_with_flexible_size(
-        &ped_params,
+    let opening = assembler.opening_section(
         bg_power_challenge_setup.power_permutation_commitment,
         &perm_power_vector_scalar,
         blinding_s,
-        params.rng,
     );
 
-    // 5) Indices init (0..N-1) in base field
+    // 5) Commitment to the shuffled order
+    let (deck_commitment, deck_opening) = assembler.deck_section(&permutation);
+
+    // 6) Indices init (0..N-1) in base field
     let indices_init = std::array::from_fn(|i| C::BaseField::from(i as u64));
 
     Ok(PreparedPermutationWitness {
@@ -201,6 +252,8 @@ where
         power_opening_proof: opening,
         blinding_r,
         blinding_s,
+        deck_commitment,
+        deck_opening,
     })
 }
 
@@ -260,18 +313,25 @@ where
     // 3) Derive alpha and beta from a fresh Poseidon sponge bound to public inputs
     let sponge_cfg = crate::config::poseidon_config::<ConstraintF<C>>();
     let mut chall_sponge = PoseidonSpongeVar::<ConstraintF<C>>::new(cs.clone(), &sponge_cfg);
-    // Optional domain separation tag
-    // Absorb public inputs in the specified order
-    pk_public.curve_absorb_gadget(&mut chall_sponge)?; // curve
-    chall_sponge.absorb(nonce)?; // Fp
-    chall_sponge.absorb(power_challenge_public)?; // Fp
-    c_perm.curve_absorb_gadget(&mut chall_sponge)?; // curve
-    c_power.curve_absorb_gadget(&mut chall_sponge)?; // curve
-                                                     // Squeeze one element for alpha, set beta = alpha^2
+    absorb_permutation_transcript_gadget::<C, GG>(
+        &mut chall_sponge,
+        pk_public,
+        nonce,
+        power_challenge_public,
+        c_perm,
+        c_power,
+    )?;
+    // Squeeze one element for alpha, set beta = alpha^2
     let alpha = chall_sponge.squeeze_field_elements(1)?[0].clone();
     let beta = &alpha * &alpha;
 
-    // 4) RS shuffle constraints on indices
+    // 4) The RS shuffle must start from the identity order; otherwise the prover could pick
+    //    the starting indices and steer the resulting permutation.
+    for (i, idx) in indices_init.iter().enumerate() {
+        idx.enforce_equal(&FpVar::constant(ConstraintF::<C>::from(i as u64)))?;
+    }
+
+    // RS shuffle constraints on indices
     let indices_after_shuffle: [FpVar<ConstraintF<C>>; N] =
         std::array::from_fn(|i| rs_witness_var.sorted_levels[LEVELS - 1][i].idx.clone());
     rs_shuffle_indices::<ConstraintF<C>, N, LEVELS>(
@@ -352,6 +412,7 @@ mod tests {
     use super::*;
     use ark_bn254::{Fq as BaseField, Fr as ScalarField, G1Projective};
     use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
     use ark_r1cs_std::alloc::AllocVar;
     use ark_r1cs_std::fields::fp::FpVar;
     use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar;
@@ -380,6 +441,42 @@ mod tests {
 
     // Removed obsolete alpha → bit-matrix binding test
 
+    #[test]
+    fn test_permutation_transcript_alpha_matches_gadget() {
+        let mut rng = test_rng();
+        let pk = G1Projective::rand(&mut rng);
+        let nonce = BaseField::rand(&mut rng);
+        let power_challenge = BaseField::rand(&mut rng);
+        let c_perm = G1Projective::rand(&mut rng);
+        let c_power = G1Projective::rand(&mut rng);
+
+        let native = derive_permutation_alpha(&pk, &nonce, &power_challenge, &c_perm, &c_power);
+
+        let cs = ConstraintSystem::<BaseField>::new_ref();
+        let pk_var = G1Var::new_input(cs.clone(), || Ok(pk)).unwrap();
+        let nonce_var = FpVar::new_input(cs.clone(), || Ok(nonce)).unwrap();
+        let power_var = FpVar::new_input(cs.clone(), || Ok(power_challenge)).unwrap();
+        let c_perm_var = G1Var::new_input(cs.clone(), || Ok(c_perm)).unwrap();
+        let c_power_var = G1Var::new_input(cs.clone(), || Ok(c_power)).unwrap();
+        let sponge_cfg = crate::config::poseidon_config::<BaseField>();
+        let mut sponge = PoseidonSpongeVar::<BaseField>::new(cs.clone(), &sponge_cfg);
+        absorb_permutation_transcript_gadget::<G1Projective, G1Var>(
+            &mut sponge,
+            &pk_var,
+            &nonce_var,
+            &power_var,
+            &c_perm_var,
+            &c_power_var,
+        )
+        .unwrap();
+        let alpha = sponge.squeeze_field_elements(1).unwrap()[0]
+            .value()
+            .unwrap();
+
+        assert_eq!(native, alpha);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
     /// Mini-test: BG folding link consistency using verify_scalar_folding_link_gadget
     #[test]
     fn test_bg_power_challenge_and_opening_consistency() {
@@ -461,6 +558,32 @@ mod tests {
     /// scalar permutation check → Pedersen opening link
     #[test]
     fn test_end_to_end_permutation_proof() {
+        let _gaurd = setup_test_tracing();
+
+        let cs = run_end_to_end_permutation_proof(|_| {});
+
+        if !cs.is_satisfied().unwrap() {
+            let unsatisfied = cs.which_is_unsatisfied().unwrap();
+            if let Some(msg) = unsatisfied {
+                panic!("unsatisfied constraint: {}", msg);
+            } else {
+                panic!("constraint not satisfied but no specific constraint returned");
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_indices_init_is_unsatisfiable() {
+        let cs = run_end_to_end_permutation_proof(|order| order.swap(0, 1));
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    /// Builds the full permutation proof with the RS shuffle starting from `tamper_start_order`
+    /// applied to `0..N`. The RS trace is relabelled to shuffle that order, so a tampered start
+    /// is consistent with every constraint except the identity check on `indices_init`.
+    fn run_end_to_end_permutation_proof(
+        tamper_start_order: impl FnOnce(&mut [usize; 8]),
+    ) -> ConstraintSystemRef<BaseField> {
         use crate::pedersen_commitment::bytes_opening::{DeckHashWindow, ReencryptionWindow};
         use crate::shuffling::bayer_groth_permutation::bg_setup::new_bayer_groth_transcript_with_poseidon;
         use crate::shuffling::pedersen_commitment::opening_proof::{prove, PedersenParams};
@@ -472,8 +595,6 @@ mod tests {
         };
         use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
 
-        let _gaurd = setup_test_tracing();
-
         const N: usize = 8;
         const LEVELS: usize = 3;
 
@@ -489,9 +610,14 @@ mod tests {
             PoseidonSponge::<BaseField>::new(&crate::config::poseidon_config::<BaseField>());
         let vrf_value = prove_simple_vrf::<G1Projective, _>(&mut sponge_native, &nonce, &sk, &pk);
 
+        // Order the RS shuffle starts from
+        let mut start_order: [usize; N] = std::array::from_fn(|i| i);
+        tamper_start_order(&mut start_order);
+
         // RS trace from vrf_value
         let input: [usize; N] = std::array::from_fn(|i| i);
-        let rs_trace = run_rs_shuffle_permutation::<BaseField, usize, N, LEVELS>(vrf_value, &input);
+        let mut rs_trace =
+            run_rs_shuffle_permutation::<BaseField, usize, N, LEVELS>(vrf_value, &input);
 
         // Compute number of samples used for bit generation
         let (_bits_mat, num_samples) = derive_split_bits::<BaseField, N, LEVELS>(vrf_value);
@@ -522,9 +648,15 @@ mod tests {
         };
         let opening = prove(&ped_params, &vc, blinding_s, &mut rng);
 
-        // Prepare indices_init (0..N-1)
-        let indices_init_native: [BaseField; N] =
-            std::array::from_fn(|i| BaseField::from(i as u64));
+        // Relabel the trace so it shuffles `start_order`. The power vector stays keyed to
+        // positions, so the (index, power) pairs still match after relabelling.
+        let relabel = |idx: &mut u16| *idx = start_order[usize::from(*idx)] as u16;
+        let trace = &mut rs_trace.witness_trace;
+        for (unsorted, sorted) in trace.uns_levels.iter_mut().zip(&mut trace.next_levels) {
+            unsorted.iter_mut().for_each(|row| relabel(&mut row.idx));
+            sorted.iter_mut().for_each(|row| relabel(&mut row.idx));
+        }
+        let indices_init_native: [BaseField; N] = start_order.map(|i| BaseField::from(i as u64));
 
         // ---------- Circuit ----------
         let cs = ConstraintSystem::<BaseField>::new_ref();
@@ -606,13 +738,6 @@ mod tests {
         )
         .unwrap();
 
-        if !cs.is_satisfied().unwrap() {
-            let unsatisfied = cs.which_is_unsatisfied().unwrap();
-            if let Some(msg) = unsatisfied {
-                panic!("unsatisfied constraint: {}", msg);
-            } else {
-                panic!("constraint not satisfied but no specific constraint returned");
-            }
-        }
+        cs
     }
 }
@@ -1,22 +1,71 @@
+use anyhow::Context;
 use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
 use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
 use ark_ec::{pairing::Pairing, CurveGroup};
 use ark_ff::{Field, PrimeField, ToConstraintField};
 use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof, ProvingKey};
 use ark_r1cs_std::fields::emulated_fp::{params::OptimizationType, AllocatedEmulatedFpVar};
 use ark_r1cs_std::groups::{CurveVar, GroupOpsBounds};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 use ark_std::{marker::PhantomData, rand::RngCore, vec::Vec};
 use ark_std::{One, Zero};
 use rand::SeedableRng;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 
 use super::circuit::PermutationProofCircuit;
 use crate::shuffling::curve_absorb::CurveAbsorbGadget;
+use crate::shuffling::deck_commitment::{
+    DeckCommitmentScheme, DeckOrderCommitment, DeckOrderOpening,
+};
+use crate::shuffling::error::ShuffleError;
 use crate::shuffling::pedersen_commitment::opening_proof::PedersenCommitmentOpeningProof;
+use crate::shuffling::proof_backend::{Groth16Backend, ProofBackend};
 use crate::shuffling::rs_shuffle::data_structures::PermutationWitnessTrace;
 
 type ConstraintF<C> = <<C as CurveGroup>::BaseField as Field>::BasePrimeField;
 
+/// Format version written at the head of persisted permutation SNARK key files
+const PERMUTATION_KEYS_FORMAT_VERSION: u32 = 3;
+
+/// Domain tag for [`circuit_parameters_hash`]
+const CIRCUIT_PARAMETERS_DOMAIN: &[u8] = b"legit_poker/permutation_groth16/v2";
+
+/// Header written ahead of the serialized proving key so that keys generated for a
+/// different circuit shape are rejected on load instead of failing verification later.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+struct PermutationKeysHeader {
+    version: u32,
+    deck_commitment: u8,
+    parameters_hash: [u8; 32],
+}
+
+/// Hash identifying the permutation circuit shape that a set of Groth16 keys belongs to.
+///
+/// Keys are only valid for the exact `(N, LEVELS, num_samples)` and deck commitment scheme
+/// they were generated for; the hash is embedded in persisted keys and compared on load.
+pub fn circuit_parameters_hash(
+    n: usize,
+    levels: usize,
+    num_samples: usize,
+    deck_commitment: DeckCommitmentScheme,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(CIRCUIT_PARAMETERS_DOMAIN);
+    hasher.update((n as u64).to_le_bytes());
+    hasher.update((levels as u64).to_le_bytes());
+    hasher.update((num_samples as u64).to_le_bytes());
+    hasher.update([deck_commitment.tag()]);
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&digest);
+    bytes
+}
+
 /// Public data for the permutation proof circuit
 pub struct PublicData<C: CurveGroup, const N: usize>
 where
@@ -30,6 +79,187 @@ where
     pub c_perm: C,
     pub c_power: C,
     pub power_opening_proof: PedersenCommitmentOpeningProof<C>,
+    pub deck_commitment: DeckOrderCommitment<C>,
+}
+
+/// Version of the public-input layout produced by [`PublicData::to_field_elements_stable`]
+pub const PUBLIC_INPUTS_LAYOUT_VERSION: u32 = 2;
+
+impl<C: CurveGroup, const N: usize> PublicData<C, N>
+where
+    C: ToConstraintField<ConstraintF<C>>,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField,
+{
+    /// Flatten the public data into circuit field elements in allocation order.
+    ///
+    /// Layout v2 ([`PUBLIC_INPUTS_LAYOUT_VERSION`]); a point is its affine encoding
+    /// `(x, y, is_infinity)` and a scalar is its Weight-optimized emulated limbs, most
+    /// significant first:
+    /// 1. `pk_public`
+    /// 2. `nonce`
+    /// 3. `indices_init[0..N]`
+    /// 4. `power_challenge_public`
+    /// 5. `c_perm`
+    /// 6. `c_power`
+    /// 7. each opening round as `L_k` then `R_k`
+    /// 8. `a_final` limbs, then `r_final` limbs
+    /// 9. `deck_commitment`: a point for Pedersen, one element for Poseidon
+    ///
+    /// Any change to this order must bump the layout version.
+    pub fn to_field_elements_stable(&self) -> anyhow::Result<Vec<ConstraintF<C>>> {
+        fn point<C: CurveGroup + ToConstraintField<ConstraintF<C>>>(
+            out: &mut Vec<ConstraintF<C>>,
+            p: &C,
+            what: &str,
+        ) -> anyhow::Result<()> {
+            let elems = p
+                .to_field_elements()
+                .ok_or_else(|| anyhow::anyhow!("failed to serialize {what}"))?;
+            out.extend(elems);
+            Ok(())
+        }
+
+        let mut v = Vec::new();
+        point(&mut v, &self.pk_public, "pk_public")?;
+        v.push(self.nonce);
+        v.extend(self.indices_init.iter().copied());
+        v.push(self.power_challenge_public);
+        point(&mut v, &self.c_perm, "c_perm")?;
+        point(&mut v, &self.c_power, "c_power")?;
+        for (l, r) in &self.power_opening_proof.folding_challenge_commitment_rounds {
+            point(&mut v, l, "L_k")?;
+            point(&mut v, r, "R_k")?;
+        }
+        v.extend(scalar_limbs::<C>(&self.power_opening_proof.a_final)?);
+        v.extend(scalar_limbs::<C>(&self.power_opening_proof.r_final)?);
+        match &self.deck_commitment {
+            DeckOrderCommitment::Pedersen(p) => point(&mut v, p, "deck_commitment")?,
+            DeckOrderCommitment::Poseidon(x) => v.push(*x),
+        }
+        Ok(v)
+    }
+}
+
+/// A curve whose points can be rebuilt from the `(x, y, is_infinity)` encoding the permutation
+/// circuit allocates them as.
+pub trait PointFromCoordinates: CurveGroup {
+    /// Decode one point, rejecting coordinates off the curve or outside the prime-order
+    /// subgroup.
+    fn from_coordinates(coords: &[Self::BaseField]) -> anyhow::Result<Self>;
+}
+
+impl<P> PointFromCoordinates for Projective<P>
+where
+    P: SWCurveConfig,
+{
+    fn from_coordinates(coords: &[P::BaseField]) -> anyhow::Result<Self> {
+        anyhow::ensure!(coords.len() == 3, "a point takes three public inputs");
+        if coords[2].is_one() {
+            return Ok(Self::zero());
+        }
+        let affine = Affine::<P>::new_unchecked(coords[0], coords[1]);
+        anyhow::ensure!(
+            coords[2].is_zero()
+                && affine.is_on_curve()
+                && affine.is_in_correct_subgroup_assuming_on_curve(),
+            "public input is not a valid curve point"
+        );
+        Ok(affine.into())
+    }
+}
+
+impl<C, const N: usize> PublicData<C, N>
+where
+    C: PointFromCoordinates,
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField,
+{
+    /// Rebuild public data from the layout written by [`Self::to_field_elements_stable`].
+    ///
+    /// `deck_commitment` names the scheme the proof was generated under. The number of opening
+    /// rounds is inferred from the input length. Points must be on the curve and in the
+    /// prime-order subgroup.
+    pub fn from_field_elements(
+        elems: &[C::BaseField],
+        deck_commitment: DeckCommitmentScheme,
+    ) -> anyhow::Result<Self> {
+        const POINT_LEN: usize = 3;
+        let num_limbs = scalar_limbs::<C>(&C::ScalarField::zero())?.len();
+        let deck_commitment_len = match deck_commitment {
+            DeckCommitmentScheme::Pedersen => POINT_LEN,
+            DeckCommitmentScheme::Poseidon => 1,
+        };
+        let fixed = 3 * POINT_LEN + 2 + N + 2 * num_limbs + deck_commitment_len;
+        let round_elems = elems
+            .len()
+            .checked_sub(fixed)
+            .filter(|extra| extra % (2 * POINT_LEN) == 0)
+            .ok_or_else(|| anyhow::anyhow!("unexpected public input length {}", elems.len()))?;
+
+        let mut rest = elems;
+        let mut take = |len: usize| {
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            head
+        };
+        let point = C::from_coordinates;
+        let scalar = |limbs: &[C::BaseField]| {
+            AllocatedEmulatedFpVar::<C::ScalarField, C::BaseField>::limbs_to_value(
+                limbs.to_vec(),
+                OptimizationType::Weight,
+            )
+        };
+
+        let pk_public = point(take(POINT_LEN))?;
+        let nonce = take(1)[0];
+        let indices_init: [C::BaseField; N] = take(N)
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("indices_init length mismatch"))?;
+        let power_challenge_public = take(1)[0];
+        let c_perm = point(take(POINT_LEN))?;
+        let c_power = point(take(POINT_LEN))?;
+        let folding_challenge_commitment_rounds = (0..round_elems / (2 * POINT_LEN))
+            .map(|_| Ok((point(take(POINT_LEN))?, point(take(POINT_LEN))?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let a_final = scalar(take(num_limbs));
+        let r_final = scalar(take(num_limbs));
+        let deck_commitment = match deck_commitment {
+            DeckCommitmentScheme::Pedersen => {
+                DeckOrderCommitment::Pedersen(point(take(POINT_LEN))?)
+            }
+            DeckCommitmentScheme::Poseidon => DeckOrderCommitment::Poseidon(take(1)[0]),
+        };
+
+        Ok(Self {
+            nonce,
+            pk_public,
+            indices_init,
+            power_challenge_public,
+            c_perm,
+            c_power,
+            power_opening_proof: PedersenCommitmentOpeningProof {
+                folding_challenge_commitment_rounds,
+                a_final,
+                r_final,
+            },
+            deck_commitment,
+        })
+    }
+}
+
+/// Emulated-limb encoding of a scalar, matching `EmulatedFpVar` input allocation.
+fn scalar_limbs<C>(scalar: &C::ScalarField) -> anyhow::Result<Vec<ConstraintF<C>>>
+where
+    C: CurveGroup,
+    C::ScalarField: PrimeField,
+{
+    Ok(
+        AllocatedEmulatedFpVar::<C::ScalarField, ConstraintF<C>>::get_limbs_representations(
+            scalar,
+            OptimizationType::Weight,
+        )?,
+    )
 }
 
 /// Private witness data for the permutation proof circuit
@@ -42,6 +272,7 @@ where
     pub rs_witness: PermutationWitnessTrace<N, LEVELS>,
     pub power_perm_vec_wit: [ConstraintF<C>; N],
     pub power_perm_vec_scalar_wit: [C::ScalarField; N],
+    pub deck_opening: DeckOrderOpening<C>,
 }
 
 /// Groth16 wrapper for the permutation proof circuit
@@ -63,6 +294,8 @@ where
 {
     pk: ProvingKey<E>,
     pvk: PreparedVerifyingKey<E>,
+    num_samples: usize,
+    deck_commitment: DeckCommitmentScheme,
     _pd: PhantomData<(C, GG)>,
 }
 
@@ -81,99 +314,102 @@ where
     E::ScalarField: PrimeField + Absorb,
     C::ScalarField: Absorb,
 {
-    /// Setup the Groth16 proving and verifying keys for the circuit
+    /// Setup the Groth16 proving and verifying keys for the circuit, committing the deck order
+    /// with Pedersen
     pub fn setup(
         rng: &mut (impl RngCore + rand::CryptoRng),
         num_samples: usize,
     ) -> anyhow::Result<Self> {
-        use crate::shuffling::rs_shuffle::native::run_rs_shuffle_permutation;
-        use crate::vrf::simple::prove_simple_vrf;
-        use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, CryptographicSponge};
-        use ark_std::UniformRand;
-
-        // Deterministic small setup for params and secrets
-        let mut local_rng = rand::rngs::StdRng::seed_from_u64(42);
-
-        // Prover secrets and nonce
-        let sk = C::ScalarField::rand(&mut local_rng);
-        let nonce: ConstraintF<C> = ConstraintF::<C>::rand(&mut local_rng);
-        let mut sponge = PoseidonSponge::<ConstraintF<C>>::new(&crate::config::poseidon_config::<
-            ConstraintF<C>,
-        >());
-
-        // Minimal off-circuit preparation (avoid heavy opening for keygen)
-        // VRF
-        let pk = C::generator() * sk;
-        let vrf_value = prove_simple_vrf::<C, _>(&mut sponge, &nonce, &sk, &pk);
-        // RS witnesses
-        let input_indices: [usize; N] = core::array::from_fn(|i| i);
-        let rs_trace = run_rs_shuffle_permutation::<ConstraintF<C>, usize, N, LEVELS>(
-            vrf_value,
-            &input_indices,
-        );
-        let indices_init: [ConstraintF<C>; N] =
-            core::array::from_fn(|i| ConstraintF::<C>::from(i as u64));
-        // Lightweight placeholders for BG setup to avoid heavy work during setup
-        let power_vec_base: [ConstraintF<C>; N] =
-            core::array::from_fn(|_| ConstraintF::<C>::zero());
-        let power_vec_scalar: [C::ScalarField; N] =
-            core::array::from_fn(|_| C::ScalarField::zero());
-        let bg_setup = crate::shuffling::bayer_groth_permutation::bg_setup::BGPowerChallengeSetup::<
-            ConstraintF<C>,
-            C::ScalarField,
-            C,
-        > {
-            power_challenge_base: ConstraintF::<C>::one(),
-            power_challenge_scalar: C::ScalarField::one(),
-            permutation_commitment: C::zero(),
-            power_permutation_commitment: C::zero(),
-        };
-        // Dummy opening proof sized for padded length
-        let padded = if N.is_power_of_two() {
-            N
-        } else {
-            N.next_power_of_two()
-        };
-        let num_rounds = (padded.trailing_zeros()) as usize;
-        let opening_dummy =
-            crate::shuffling::pedersen_commitment::opening_proof::PedersenCommitmentOpeningProof::<
-                C,
-            > {
-                folding_challenge_commitment_rounds: core::iter::repeat((C::zero(), C::zero()))
-                    .take(num_rounds)
-                    .collect(),
-                a_final: C::ScalarField::zero(),
-                r_final: C::ScalarField::zero(),
-            };
-
-        // Construct a circuit instance
-        let circ = PermutationProofCircuit::<C, GG, N, LEVELS> {
+        Self::setup_with_deck_commitment(rng, num_samples, DeckCommitmentScheme::Pedersen)
+    }
+
+    /// [`Self::setup`] for a circuit that commits the deck order under `deck_commitment`
+    pub fn setup_with_deck_commitment(
+        rng: &mut (impl RngCore + rand::CryptoRng),
+        num_samples: usize,
+        deck_commitment: DeckCommitmentScheme,
+    ) -> anyhow::Result<Self> {
+        let circ = keygen_circuit::<C, GG, N, LEVELS>(num_samples, deck_commitment);
+        let (pk, pvk) = Groth16Backend::<E>::setup(circ, rng)?;
+        Ok(Self {
+            pk,
+            pvk,
             num_samples,
-            // Public
-            nonce: Some(nonce),
-            pk_public: Some(pk),
-            indices_init: Some(indices_init),
-            power_challenge_public: Some(bg_setup.power_challenge_base),
-            c_perm: Some(bg_setup.permutation_commitment),
-            c_power: Some(bg_setup.power_permutation_commitment),
-            power_opening_proof: Some(opening_dummy.clone()),
-            // Witness
-            sk: Some(sk),
-            rs_witness: Some(rs_trace.witness_trace.clone()),
-            power_perm_vec_wit: Some(power_vec_base),
-            power_perm_vec_scalar_wit: Some(power_vec_scalar),
+            deck_commitment,
             _pd: PhantomData,
-        };
+        })
+    }
+
+    /// Persist the proving key (which embeds the verifying key) to `path`.
+    ///
+    /// The file starts with a header recording the format version and the
+    /// [`circuit_parameters_hash`], so [`Self::load_keys`] can reject keys generated for
+    /// another circuit.
+    pub fn save_keys(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("failed to create key file {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        Self::keys_header(self.num_samples, self.deck_commitment)
+            .serialize_compressed(&mut writer)?;
+        self.pk.serialize_compressed(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Load keys previously written by [`Self::save_keys`] for the given `num_samples`.
+    ///
+    /// The deck commitment scheme is read from the file. Fails with
+    /// [`ShuffleError::ParamMismatch`] when the file was generated for a different
+    /// `(N, LEVELS, num_samples)`.
+    pub fn load_keys(path: impl AsRef<Path>, num_samples: usize) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("failed to open key file {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+
+        let found = PermutationKeysHeader::deserialize_compressed(&mut reader)?;
+        let deck_commitment =
+            DeckCommitmentScheme::from_tag(found.deck_commitment).ok_or_else(|| {
+                anyhow::anyhow!("unknown deck commitment scheme {}", found.deck_commitment)
+            })?;
+        let expected = Self::keys_header(num_samples, deck_commitment);
+        anyhow::ensure!(
+            found.version == expected.version,
+            "unsupported permutation SNARK key file version: expected {}, found {}",
+            expected.version,
+            found.version
+        );
+        if found.parameters_hash != expected.parameters_hash {
+            return Err(ShuffleError::ParamMismatch {
+                expected: expected.parameters_hash,
+                found: found.parameters_hash,
+            }
+            .into());
+        }
 
-        let (pk, vk) = Groth16::<E>::circuit_specific_setup(circ, rng)?;
-        let pvk = prepare_verifying_key(&vk);
+        let pk = ProvingKey::<E>::deserialize_compressed(&mut reader)?;
+        let pvk = prepare_verifying_key(&pk.vk);
         Ok(Self {
             pk,
             pvk,
+            num_samples,
+            deck_commitment,
             _pd: PhantomData,
         })
     }
 
+    fn keys_header(
+        num_samples: usize,
+        deck_commitment: DeckCommitmentScheme,
+    ) -> PermutationKeysHeader {
+        PermutationKeysHeader {
+            version: PERMUTATION_KEYS_FORMAT_VERSION,
+            deck_commitment: deck_commitment.tag(),
+            parameters_hash: circuit_parameters_hash(N, LEVELS, num_samples, deck_commitment),
+        }
+    }
+
     /// Create the Groth16 proof and return the proof along with the serialized public inputs
     pub fn prove(
         &self,
@@ -182,6 +418,12 @@ where
         witness: &WitnessData<C, N, LEVELS>,
         num_samples: usize,
     ) -> anyhow::Result<(Proof<E>, Vec<E::ScalarField>)> {
+        anyhow::ensure!(
+            public.deck_commitment.scheme() == self.deck_commitment,
+            "keys commit the deck order with {:?}, the witness with {:?}",
+            self.deck_commitment,
+            public.deck_commitment.scheme()
+        );
         // Build the circuit instance with assignments
         let circ = PermutationProofCircuit::<C, GG, N, LEVELS> {
             num_samples,
@@ -193,11 +435,13 @@ where
             c_perm: Some(public.c_perm),
             c_power: Some(public.c_power),
             power_opening_proof: Some(public.power_opening_proof.clone()),
+            deck_commitment: Some(public.deck_commitment.clone()),
             // Witness
             sk: Some(witness.sk),
             rs_witness: Some(witness.rs_witness.clone()),
             power_perm_vec_wit: Some(witness.power_perm_vec_wit),
             power_perm_vec_scalar_wit: Some(witness.power_perm_vec_scalar_wit),
+            deck_opening: Some(witness.deck_opening.clone()),
             _pd: PhantomData,
         };
 
@@ -229,6 +473,16 @@ where
         &self.pk
     }
 
+    /// Number of Poseidon samples the keys were generated for
+    pub fn num_samples(&self) -> usize {
+        self.num_samples
+    }
+
+    /// Scheme the circuit commits the deck order with
+    pub fn deck_commitment(&self) -> DeckCommitmentScheme {
+        self.deck_commitment
+    }
+
     /// Verify a proof against the provided public inputs
     pub fn verify(
         &self,
@@ -247,7 +501,106 @@ where
     }
 }
 
-/// Flatten public inputs to base field elements in the exact order of allocation
+/// Circuit instance with placeholder assignments of the right shape, used for key generation.
+///
+/// Keys depend only on the circuit shape, so the assignments here are cheap stand-ins rather
+/// than a real shuffle.
+pub fn keygen_circuit<C, GG, const N: usize, const LEVELS: usize>(
+    num_samples: usize,
+    deck_commitment: DeckCommitmentScheme,
+) -> PermutationProofCircuit<C, GG, N, LEVELS>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField + Absorb,
+    C::ScalarField: PrimeField,
+    GG: CurveVar<C, ConstraintF<C>>
+        + CurveAbsorbGadget<ConstraintF<C>, PoseidonSpongeVar<ConstraintF<C>>>,
+{
+    use crate::shuffling::rs_shuffle::native::run_rs_shuffle_permutation;
+    use crate::vrf::simple::prove_simple_vrf;
+    use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, CryptographicSponge};
+    use ark_std::UniformRand;
+
+    // Deterministic small setup for params and secrets
+    let mut local_rng = rand::rngs::StdRng::seed_from_u64(42);
+
+    // Prover secrets and nonce
+    let sk = C::ScalarField::rand(&mut local_rng);
+    let nonce: ConstraintF<C> = ConstraintF::<C>::rand(&mut local_rng);
+    let mut sponge =
+        PoseidonSponge::<ConstraintF<C>>::new(&crate::config::poseidon_config::<ConstraintF<C>>());
+
+    // Minimal off-circuit preparation (avoid heavy opening for keygen)
+    // VRF
+    let pk = C::generator() * sk;
+    let vrf_value = prove_simple_vrf::<C, _>(&mut sponge, &nonce, &sk, &pk);
+    // RS witnesses
+    let input_indices: [usize; N] = core::array::from_fn(|i| i);
+    let rs_trace =
+        run_rs_shuffle_permutation::<ConstraintF<C>, usize, N, LEVELS>(vrf_value, &input_indices);
+    let indices_init: [ConstraintF<C>; N] =
+        core::array::from_fn(|i| ConstraintF::<C>::from(i as u64));
+    // Lightweight placeholders for BG setup to avoid heavy work during setup
+    let power_vec_base: [ConstraintF<C>; N] = core::array::from_fn(|_| ConstraintF::<C>::zero());
+    let power_vec_scalar: [C::ScalarField; N] = core::array::from_fn(|_| C::ScalarField::zero());
+    let bg_setup = crate::shuffling::bayer_groth_permutation::bg_setup::BGPowerChallengeSetup::<
+        ConstraintF<C>,
+        C::ScalarField,
+        C,
+    > {
+        power_challenge_base: ConstraintF::<C>::one(),
+        power_challenge_scalar: C::ScalarField::one(),
+        permutation_commitment: C::zero(),
+        power_permutation_commitment: C::zero(),
+    };
+    // Dummy opening proof sized for padded length
+    let padded = if N.is_power_of_two() {
+        N
+    } else {
+        N.next_power_of_two()
+    };
+    let num_rounds = (padded.trailing_zeros()) as usize;
+    let opening_dummy =
+        crate::shuffling::pedersen_commitment::opening_proof::PedersenCommitmentOpeningProof::<C> {
+            folding_challenge_commitment_rounds: core::iter::repeat((C::zero(), C::zero()))
+                .take(num_rounds)
+                .collect(),
+            a_final: C::ScalarField::zero(),
+            r_final: C::ScalarField::zero(),
+        };
+    let deck_opening = deck_commitment.sample_opening::<C, _>(&mut local_rng);
+    let deck_order: Vec<u8> = rs_trace
+        .extract_permutation_array()
+        .iter()
+        .map(|&i| i as u8)
+        .collect();
+    let deck_commitment = DeckOrderCommitment::commit(&deck_order, &deck_opening);
+
+    // Construct a circuit instance
+    PermutationProofCircuit::<C, GG, N, LEVELS> {
+        num_samples,
+        // Public
+        nonce: Some(nonce),
+        pk_public: Some(pk),
+        indices_init: Some(indices_init),
+        power_challenge_public: Some(bg_setup.power_challenge_base),
+        c_perm: Some(bg_setup.permutation_commitment),
+        c_power: Some(bg_setup.power_permutation_commitment),
+        power_opening_proof: Some(opening_dummy.clone()),
+        deck_commitment: Some(deck_commitment),
+        // Witness
+        sk: Some(sk),
+        rs_witness: Some(rs_trace.witness_trace.clone()),
+        power_perm_vec_wit: Some(power_vec_base),
+        power_perm_vec_scalar_wit: Some(power_vec_scalar),
+        deck_opening: Some(deck_opening),
+        _pd: PhantomData,
+    }
+}
+
+/// Flatten public inputs to base field elements in the exact order of allocation.
+///
+/// See [`PublicData::to_field_elements_stable`] for the layout.
 pub fn build_public_inputs<E, C, const N: usize>(
     public: &PublicData<C, N>,
 ) -> anyhow::Result<Vec<E::ScalarField>>
@@ -258,93 +611,11 @@ where
     C::ScalarField: PrimeField,
     E::ScalarField: PrimeField + From<<C::BaseField as Field>::BasePrimeField>,
 {
-    let mut v: Vec<E::ScalarField> = Vec::new();
-
-    // 1) pk_public (full point serialization)
-    {
-        let elems: Vec<ConstraintF<C>> = public
-            .pk_public
-            .to_field_elements()
-            .ok_or_else(|| anyhow::anyhow!("failed to serialize pk_public"))?;
-        for e in elems {
-            v.push(e.into());
-        }
-    }
-
-    // 2) nonce (base field)
-    v.push(public.nonce.into());
-
-    // 3) indices_init (array of base field)
-    for x in public.indices_init.iter() {
-        v.push((*x).into());
-    }
-
-    // 4) power_challenge_public (base field)
-    v.push(public.power_challenge_public.into());
-
-    // 5) c_perm (full point serialization)
-    {
-        let elems: Vec<ConstraintF<C>> = public
-            .c_perm
-            .to_field_elements()
-            .ok_or_else(|| anyhow::anyhow!("failed to serialize c_perm"))?;
-        for e in elems {
-            v.push(e.into());
-        }
-    }
-
-    // 6) c_power (full point serialization)
-    {
-        let elems: Vec<ConstraintF<C>> = public
-            .c_power
-            .to_field_elements()
-            .ok_or_else(|| anyhow::anyhow!("failed to serialize c_power"))?;
-        for e in elems {
-            v.push(e.into());
-        }
-    }
-
-    // 8) power_opening_proof: rounds (L_k, R_k) as points, then a_final, r_final as emulated limbs
-    for (l, r) in public
-        .power_opening_proof
-        .folding_challenge_commitment_rounds
-        .iter()
-    {
-        let le: Vec<ConstraintF<C>> = l
-            .to_field_elements()
-            .ok_or_else(|| anyhow::anyhow!("failed to serialize L_k"))?;
-        for e in le {
-            v.push(e.into());
-        }
-        let re: Vec<ConstraintF<C>> = r
-            .to_field_elements()
-            .ok_or_else(|| anyhow::anyhow!("failed to serialize R_k"))?;
-        for e in re {
-            v.push(e.into());
-        }
-    }
-
-    // Serialize emulated scalars a_final and r_final as limbs using the same
-    // representation EmulatedFpVar(Input) uses in ToConstraintFieldGadget (Weight-optimized)
-    let limbs_a: Vec<ConstraintF<C>> =
-        AllocatedEmulatedFpVar::<C::ScalarField, ConstraintF<C>>::get_limbs_representations(
-            &public.power_opening_proof.a_final,
-            OptimizationType::Weight,
-        )?;
-    for e in limbs_a {
-        v.push(e.into());
-    }
-
-    let limbs_r: Vec<ConstraintF<C>> =
-        AllocatedEmulatedFpVar::<C::ScalarField, ConstraintF<C>>::get_limbs_representations(
-            &public.power_opening_proof.r_final,
-            OptimizationType::Weight,
-        )?;
-    for e in limbs_r {
-        v.push(e.into());
-    }
-
-    Ok(v)
+    Ok(public
+        .to_field_elements_stable()?
+        .into_iter()
+        .map(Into::into)
+        .collect())
 }
 
 #[cfg(test)]
@@ -386,21 +657,19 @@ mod tests {
             .set_default()
     }
 
-    #[test]
-    fn test_end_to_end_permutation_proof_groth16() {
-        let _guard = setup_test_tracing();
-        let mut rng = StdRng::seed_from_u64(12345);
-
-        // ---------- Native preparation via prepare_witness ----------
+    /// Prepare public/witness data for a fresh permutation proof
+    fn prepare_proof_inputs(
+        rng: &mut StdRng,
+    ) -> (PublicData<C, N>, WitnessData<C, N, LEVELS>, usize) {
         use crate::pedersen_commitment::bytes_opening::{DeckHashWindow, ReencryptionWindow};
 
         // Setup Pedersen parameters (inner curve C=Grumpkin)
-        let perm_params = PedersenCommitment::<C, DeckHashWindow>::setup(&mut rng).unwrap();
-        let power_params = PedersenCommitment::<C, ReencryptionWindow>::setup(&mut rng).unwrap();
+        let perm_params = PedersenCommitment::<C, DeckHashWindow>::setup(rng).unwrap();
+        let power_params = PedersenCommitment::<C, ReencryptionWindow>::setup(rng).unwrap();
 
         // Prover secrets
-        let sk = ScalarField::rand(&mut rng);
-        let nonce: BaseField = BaseField::rand(&mut rng);
+        let sk = ScalarField::rand(rng);
+        let nonce: BaseField = BaseField::rand(rng);
 
         // Poseidon sponge over BaseField (BN254 Fr)
         let mut sponge =
@@ -410,16 +679,12 @@ mod tests {
         let mut prep_params = PermutationParameters::<C, _> {
             perm_params: &perm_params,
             power_params: &power_params,
-            rng: &mut rng,
+            rng,
         };
         let prepared =
             prepare_witness::<C, _, _, N, LEVELS>(&mut prep_params, nonce, sk, &mut sponge)
                 .expect("prepare_witness");
 
-        // ---------- Proof system ----------
-        let sys: PermutationGroth16<Bn254, C, CVar, N, LEVELS> =
-            PermutationGroth16::setup(&mut rng, prepared.rs_trace.num_samples).expect("setup");
-
         let public = PublicData::<C, N> {
             nonce,
             pk_public: prepared.pk,
@@ -428,6 +693,7 @@ mod tests {
             c_perm: prepared.bg_setup.permutation_commitment,
             c_power: prepared.bg_setup.power_permutation_commitment,
             power_opening_proof: prepared.power_opening_proof.clone(),
+            deck_commitment: prepared.deck_commitment.clone(),
         };
 
         let witness = WitnessData::<C, N, LEVELS> {
@@ -435,13 +701,180 @@ mod tests {
             rs_witness: prepared.rs_trace.witness_trace.clone(),
             power_perm_vec_wit: prepared.perm_power_vector_base,
             power_perm_vec_scalar_wit: prepared.perm_power_vector_scalar,
+            deck_opening: prepared.deck_opening.clone(),
+        };
+
+        (public, witness, prepared.rs_trace.num_samples)
+    }
+
+    #[test]
+    fn test_public_inputs_layout_golden_vector() {
+        use ark_ec::{AffineRepr, PrimeGroup};
+
+        let g = C::generator();
+        let round = (g * ScalarField::from(3u64), g * ScalarField::from(4u64));
+        let public = PublicData::<C, N> {
+            nonce: BaseField::from(42u64),
+            pk_public: g,
+            indices_init: std::array::from_fn(|i| BaseField::from(i as u64)),
+            power_challenge_public: BaseField::from(9u64),
+            c_perm: g * ScalarField::from(2u64),
+            c_power: C::zero(),
+            power_opening_proof: PedersenCommitmentOpeningProof {
+                folding_challenge_commitment_rounds: vec![round; 3],
+                a_final: ScalarField::from(7u64),
+                r_final: ScalarField::from(11u64),
+            },
+            deck_commitment: DeckOrderCommitment::Pedersen(g * ScalarField::from(5u64)),
+        };
+
+        let coords = |p: C| {
+            let a = p.into_affine();
+            vec![a.x().unwrap(), a.y().unwrap(), BaseField::zero()]
+        };
+        let limbs = |value: u64| {
+            let mut limbs = scalar_limbs::<C>(&ScalarField::zero()).unwrap();
+            *limbs.last_mut().unwrap() = BaseField::from(value);
+            limbs
         };
 
+        let mut expected = coords(g);
+        expected.push(BaseField::from(42u64));
+        expected.extend((0..N as u64).map(BaseField::from));
+        expected.push(BaseField::from(9u64));
+        expected.extend(coords(g * ScalarField::from(2u64)));
+        expected.extend([BaseField::zero(), BaseField::zero(), BaseField::one()]);
+        for _ in 0..3 {
+            expected.extend(coords(round.0));
+            expected.extend(coords(round.1));
+        }
+        expected.extend(limbs(7));
+        expected.extend(limbs(11));
+        expected.extend(coords(g * ScalarField::from(5u64)));
+
+        let layout = public.to_field_elements_stable().unwrap();
+        assert_eq!(PUBLIC_INPUTS_LAYOUT_VERSION, 2);
+        assert_eq!(layout, expected);
+        assert_eq!(
+            build_public_inputs::<Bn254, C, N>(&public).unwrap(),
+            expected
+        );
+
+        let pedersen = DeckCommitmentScheme::Pedersen;
+        let decoded = PublicData::<C, N>::from_field_elements(&layout, pedersen).unwrap();
+        assert_eq!(decoded.to_field_elements_stable().unwrap(), layout);
+        assert!(PublicData::<C, N>::from_field_elements(&layout[1..], pedersen).is_err());
+
+        let mut poseidon_public = public;
+        poseidon_public.deck_commitment = DeckOrderCommitment::Poseidon(BaseField::from(13u64));
+        let poseidon_layout = poseidon_public.to_field_elements_stable().unwrap();
+        assert_eq!(
+            poseidon_layout[..layout.len() - 3],
+            layout[..layout.len() - 3]
+        );
+        assert_eq!(poseidon_layout.last(), Some(&BaseField::from(13u64)));
+        let decoded = PublicData::<C, N>::from_field_elements(
+            &poseidon_layout,
+            DeckCommitmentScheme::Poseidon,
+        )
+        .unwrap();
+        assert_eq!(decoded.deck_commitment, poseidon_public.deck_commitment);
+    }
+
+    #[test]
+    fn test_end_to_end_permutation_proof_groth16() {
+        let _guard = setup_test_tracing();
+        let mut rng = StdRng::seed_from_u64(12345);
+
+        // ---------- Native preparation via prepare_witness ----------
+        let (public, witness, num_samples) = prepare_proof_inputs(&mut rng);
+
+        // ---------- Proof system ----------
+        let sys: PermutationGroth16<Bn254, C, CVar, N, LEVELS> =
+            PermutationGroth16::setup(&mut rng, num_samples).expect("setup");
+
         let (proof, public_inputs) = sys
-            .prove(&mut rng, &public, &witness, prepared.rs_trace.num_samples)
+            .prove(&mut rng, &public, &witness, num_samples)
             .expect("prove");
 
         let ok = sys.verify(&proof, &public_inputs).expect("verify call");
         assert!(ok, "Groth16 proof should verify");
     }
+
+    #[test]
+    fn test_saved_keys_verify_fresh_proof_after_load() {
+        let _guard = setup_test_tracing();
+        let mut rng = StdRng::seed_from_u64(777);
+
+        let (public, witness, num_samples) = prepare_proof_inputs(&mut rng);
+        let sys: PermutationGroth16<Bn254, C, CVar, N, LEVELS> =
+            PermutationGroth16::setup(&mut rng, num_samples).expect("setup");
+
+        let path = std::env::temp_dir().join(format!(
+            "legit_poker_perm_keys_{}_{}.bin",
+            std::process::id(),
+            num_samples
+        ));
+        sys.save_keys(&path).expect("save keys");
+        let loaded: PermutationGroth16<Bn254, C, CVar, N, LEVELS> =
+            PermutationGroth16::load_keys(&path, num_samples).expect("load keys");
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.num_samples(), num_samples);
+        assert_eq!(loaded.prepared_vk().vk, sys.prepared_vk().vk);
+
+        let (proof, public_inputs) = sys
+            .prove(&mut rng, &public, &witness, num_samples)
+            .expect("prove");
+        assert!(
+            loaded.verify(&proof, &public_inputs).expect("verify call"),
+            "loaded VK should verify a proof from the original keys"
+        );
+
+        let (proof, public_inputs) = loaded
+            .prove(&mut rng, &public, &witness, num_samples)
+            .expect("prove with loaded keys");
+        assert!(
+            sys.verify(&proof, &public_inputs).expect("verify call"),
+            "original VK should verify a proof from the loaded keys"
+        );
+    }
+
+    #[test]
+    fn test_load_keys_rejects_mismatched_circuit_parameters() {
+        let _guard = setup_test_tracing();
+        let mut rng = StdRng::seed_from_u64(4242);
+
+        let (_public, _witness, num_samples) = prepare_proof_inputs(&mut rng);
+        let sys: PermutationGroth16<Bn254, C, CVar, N, LEVELS> =
+            PermutationGroth16::setup(&mut rng, num_samples).expect("setup");
+
+        let path = std::env::temp_dir().join(format!(
+            "legit_poker_perm_keys_mismatch_{}_{}.bin",
+            std::process::id(),
+            num_samples
+        ));
+        sys.save_keys(&path).expect("save keys");
+
+        const OTHER_N: usize = 16;
+        let result =
+            PermutationGroth16::<Bn254, C, CVar, OTHER_N, LEVELS>::load_keys(&path, num_samples);
+        std::fs::remove_file(&path).ok();
+
+        let err = result.err().expect("loading N=8 keys as N=16 should fail");
+        match err.downcast_ref::<ShuffleError>() {
+            Some(ShuffleError::ParamMismatch { expected, found }) => {
+                let pedersen = DeckCommitmentScheme::Pedersen;
+                assert_eq!(
+                    *expected,
+                    circuit_parameters_hash(OTHER_N, LEVELS, num_samples, pedersen)
+                );
+                assert_eq!(
+                    *found,
+                    circuit_parameters_hash(N, LEVELS, num_samples, pedersen)
+                );
+            }
+            other => panic!("expected ParamMismatch, got {other:?}"),
+        }
+    }
 }
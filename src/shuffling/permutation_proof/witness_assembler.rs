@@ -0,0 +1,314 @@
+//! Sectioned witness assembly for the permutation proof
+//!
+//! The [`WitnessAssembler`] builds the witness one section at a time. [`Self::assemble`]
+//! moves each column straight into [`PublicData`]/[`WitnessData`] and drops intermediates
+//! (RS permuted output, blinding `r`, the padded Pedersen generator table) as soon as the
+//! following section no longer needs them.
+//!
+//! [`prepare_witness`](super::prepare_witness) runs the same sections but keeps every
+//! intermediate, so for a fixed RNG seed both paths yield identical witnesses and therefore
+//! identical proofs.
+//!
+//! [`Self::assemble`]: WitnessAssembler::assemble
+
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+
+use super::{PermutationParameters, PublicData, WitnessData};
+use crate::shuffling::bayer_groth_permutation::bg_setup::{
+    new_bayer_groth_transcript_with_poseidon, BGPowerChallengeSetup,
+};
+use crate::shuffling::curve_absorb::CurveAbsorb;
+use crate::shuffling::deck_commitment::{
+    DeckCommitmentScheme, DeckOrderCommitment, DeckOrderOpening,
+};
+use crate::shuffling::pedersen_commitment::opening_proof::{
+    prove_with_flexible_size, PedersenCommitmentOpeningProof, PedersenParams,
+};
+use crate::shuffling::rs_shuffle::data_structures::RSShuffleTrace;
+use crate::shuffling::rs_shuffle::native::run_rs_shuffle_permutation;
+use crate::vrf::simple::prove_simple_vrf;
+
+/// Everything the shuffling prover needs from the permutation witness, without duplicates
+pub struct AssembledPermutationWitness<C: CurveGroup, const N: usize, const LEVELS: usize>
+where
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField,
+{
+    /// Public inputs of the permutation circuit
+    pub public: PublicData<C, N>,
+    /// Private witness of the permutation circuit
+    pub witness: WitnessData<C, N, LEVELS>,
+    /// Number of Poseidon samples used to derive the RS bits
+    pub num_samples: usize,
+    /// RS permutation (0-indexed) applied to the deck
+    pub permutation: [usize; N],
+    /// Bayer–Groth power challenge setup (x and commitments)
+    pub bg_setup: BGPowerChallengeSetup<C::BaseField, C::ScalarField, C>,
+    /// Blinding factor of `c_power`, needed by the re-encryption Σ‑protocol
+    pub blinding_s: C::ScalarField,
+}
+
+/// Section-by-section generator of the permutation proof witness
+pub struct WitnessAssembler<
+    'p,
+    'a,
+    C: CurveGroup,
+    R: rand::RngCore,
+    const N: usize,
+    const LEVELS: usize,
+> {
+    params: &'p mut PermutationParameters<'a, C, R>,
+    nonce: C::BaseField,
+    sk: C::ScalarField,
+    deck_commitment: DeckCommitmentScheme,
+}
+
+impl<'p, 'a, C, R, const N: usize, const LEVELS: usize> WitnessAssembler<'p, 'a, C, R, N, LEVELS>
+where
+    C: CurveGroup + CurveAbsorb<C::BaseField>,
+    C::BaseField: PrimeField + Absorb,
+    C::ScalarField: PrimeField + Absorb,
+    R: rand::RngCore,
+{
+    pub fn new(
+        params: &'p mut PermutationParameters<'a, C, R>,
+        nonce: C::BaseField,
+        sk: C::ScalarField,
+    ) -> Self {
+        Self {
+            params,
+            nonce,
+            sk,
+            deck_commitment: DeckCommitmentScheme::default(),
+        }
+    }
+
+    /// Commit the shuffled deck order with `scheme` instead of Pedersen
+    pub fn with_deck_commitment(mut self, scheme: DeckCommitmentScheme) -> Self {
+        self.deck_commitment = scheme;
+        self
+    }
+
+    /// Run all sections and return the circuit inputs plus the prover-side extras
+    pub fn assemble<RO: CryptographicSponge>(
+        mut self,
+        sponge: &mut RO,
+    ) -> anyhow::Result<AssembledPermutationWitness<C, N, LEVELS>> {
+        let (pk, vrf_value) = self.vrf_section(sponge);
+        let trace = Self::rs_section(vrf_value);
+        let permutation = trace.extract_permutation_array();
+        let RSShuffleTrace {
+            witness_trace: rs_witness,
+            num_samples,
+            ..
+        } = trace;
+        let (power_perm_vec_base, power_perm_vec_scalar, bg_setup, _blinding_r, blinding_s) =
+            self.power_section(&permutation);
+        let power_opening_proof = self.opening_section(
+            bg_setup.power_permutation_commitment,
+            &power_perm_vec_scalar,
+            blinding_s,
+        );
+        let (deck_commitment, deck_opening) = self.deck_section(&permutation);
+
+        let public = PublicData::<C, N> {
+            nonce: self.nonce.into(),
+            pk_public: pk,
+            indices_init: std::array::from_fn(|i| C::BaseField::from(i as u64).into()),
+            power_challenge_public: bg_setup.power_challenge_base.into(),
+            c_perm: bg_setup.permutation_commitment,
+            c_power: bg_setup.power_permutation_commitment,
+            power_opening_proof,
+            deck_commitment,
+        };
+        let witness = WitnessData::<C, N, LEVELS> {
+            sk: self.sk,
+            rs_witness,
+            power_perm_vec_wit: power_perm_vec_base.map(|x| x.into()),
+            power_perm_vec_scalar_wit: power_perm_vec_scalar,
+            deck_opening,
+        };
+
+        Ok(AssembledPermutationWitness {
+            public,
+            witness,
+            num_samples,
+            permutation,
+            bg_setup,
+            blinding_s,
+        })
+    }
+
+    /// Section 1: VRF public key and output used as the RS seed
+    pub(super) fn vrf_section<RO: CryptographicSponge>(
+        &self,
+        sponge: &mut RO,
+    ) -> (C, C::BaseField) {
+        let pk = C::generator() * self.sk;
+        let vrf_value = prove_simple_vrf::<C, _>(sponge, &self.nonce, &self.sk, &pk);
+        (pk, vrf_value)
+    }
+
+    /// Section 2: RS trace of the identity deck seeded by the VRF output
+    pub(super) fn rs_section(vrf_value: C::BaseField) -> RSShuffleTrace<usize, N, LEVELS> {
+        let input: [usize; N] = std::array::from_fn(|i| i);
+        run_rs_shuffle_permutation::<C::BaseField, usize, N, LEVELS>(vrf_value, &input)
+    }
+
+    /// Section 3: Bayer–Groth power vectors, commitments and their blindings `(r, s)`
+    #[allow(clippy::type_complexity)]
+    pub(super) fn power_section(
+        &mut self,
+        permutation: &[usize; N],
+    ) -> (
+        [C::BaseField; N],
+        [C::ScalarField; N],
+        BGPowerChallengeSetup<C::BaseField, C::ScalarField, C>,
+        C::ScalarField,
+        C::ScalarField,
+    ) {
+        let mut transcript =
+            new_bayer_groth_transcript_with_poseidon::<C::BaseField>(b"permutation-proof");
+        let blinding_r = C::ScalarField::rand(self.params.rng);
+        let blinding_s = C::ScalarField::rand(self.params.rng);
+        let (base, scalar, setup) = transcript.compute_power_challenge_setup::<C, N>(
+            self.params.perm_params,
+            self.params.power_params,
+            permutation,
+            blinding_r,
+            blinding_s,
+        );
+        (base, scalar, setup, blinding_r, blinding_s)
+    }
+
+    /// Section 4: opening proof for `c_power`; the padded generator table is freed on return
+    pub(super) fn opening_section(
+        &mut self,
+        commitment: C,
+        power_perm_vec_scalar: &[C::ScalarField; N],
+        blinding_s: C::ScalarField,
+    ) -> PedersenCommitmentOpeningProof<C> {
+        let padded_size = if N.is_power_of_two() {
+            N
+        } else {
+            N.next_power_of_two()
+        };
+        let ped_params = PedersenParams::<C>::from_arkworks_dynamic(
+            self.params.power_params.clone(),
+            padded_size,
+        );
+        prove_with_flexible_size(
+            &ped_params,
+            commitment,
+            power_perm_vec_scalar,
+            blinding_s,
+            self.params.rng,
+        )
+    }
+
+    /// Section 5: commitment to the shuffled order, one byte per card, and its blinding
+    pub(super) fn deck_section(
+        &mut self,
+        permutation: &[usize; N],
+    ) -> (DeckOrderCommitment<C>, DeckOrderOpening<C>) {
+        let order: Vec<u8> = permutation.iter().map(|&i| i as u8).collect();
+        let opening = self.deck_commitment.sample_opening::<C, _>(self.params.rng);
+        (DeckOrderCommitment::commit(&order, &opening), opening)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pedersen_commitment::bytes_opening::{DeckHashWindow, ReencryptionWindow};
+    use crate::shuffling::permutation_proof::{
+        construct_perm_io, prepare_witness, PermutationGroth16,
+    };
+    use ark_bn254::{Bn254, Fr as BaseField};
+    use ark_crypto_primitives::commitment::{
+        pedersen::Commitment as PedersenCommitment, CommitmentScheme,
+    };
+    use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
+    use ark_grumpkin::{Fr as ScalarField, Projective as C};
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar as SWVar;
+    use ark_std::rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    type CVar = SWVar<ark_grumpkin::GrumpkinConfig, FpVar<BaseField>>;
+
+    const N: usize = 8;
+    const LEVELS: usize = 3;
+
+    fn new_sponge() -> PoseidonSponge<BaseField> {
+        PoseidonSponge::<BaseField>::new(&crate::config::poseidon_config::<BaseField>())
+    }
+
+    #[test]
+    fn test_assembled_witness_matches_eager_proof() {
+        let mut setup_rng = StdRng::seed_from_u64(2024);
+        let perm_params = PedersenCommitment::<C, DeckHashWindow>::setup(&mut setup_rng).unwrap();
+        let power_params =
+            PedersenCommitment::<C, ReencryptionWindow>::setup(&mut setup_rng).unwrap();
+        let sk = ScalarField::rand(&mut setup_rng);
+        let nonce = BaseField::rand(&mut setup_rng);
+
+        // Eager path
+        let mut eager_rng = StdRng::seed_from_u64(99);
+        let mut eager_params = PermutationParameters::<C, _> {
+            perm_params: &perm_params,
+            power_params: &power_params,
+            rng: &mut eager_rng,
+        };
+        let prepared =
+            prepare_witness::<C, _, _, N, LEVELS>(&mut eager_params, nonce, sk, &mut new_sponge())
+                .expect("prepare_witness");
+        let (eager_public, eager_witness) = construct_perm_io::<C, N, LEVELS>(nonce, sk, &prepared);
+
+        // Sectioned path
+        let mut assembled_rng = StdRng::seed_from_u64(99);
+        let mut assembled_params = PermutationParameters::<C, _> {
+            perm_params: &perm_params,
+            power_params: &power_params,
+            rng: &mut assembled_rng,
+        };
+        let assembled = WitnessAssembler::<C, _, N, LEVELS>::new(&mut assembled_params, nonce, sk)
+            .assemble(&mut new_sponge())
+            .expect("assemble");
+
+        assert_eq!(assembled.num_samples, prepared.rs_trace.num_samples);
+        assert_eq!(
+            assembled.permutation,
+            prepared.rs_trace.extract_permutation_array()
+        );
+        assert_eq!(assembled.blinding_s, prepared.blinding_s);
+
+        let sys: PermutationGroth16<Bn254, C, CVar, N, LEVELS> =
+            PermutationGroth16::setup(&mut setup_rng, assembled.num_samples).expect("setup");
+
+        let (eager_proof, eager_inputs) = sys
+            .prove(
+                &mut StdRng::seed_from_u64(7),
+                &eager_public,
+                &eager_witness,
+                prepared.rs_trace.num_samples,
+            )
+            .expect("eager prove");
+        let (assembled_proof, assembled_inputs) = sys
+            .prove(
+                &mut StdRng::seed_from_u64(7),
+                &assembled.public,
+                &assembled.witness,
+                assembled.num_samples,
+            )
+            .expect("assembled prove");
+
+        assert_eq!(assembled_inputs, eager_inputs);
+        assert_eq!(assembled_proof, eager_proof);
+        assert!(sys
+            .verify(&assembled_proof, &assembled_inputs)
+            .expect("verify call"));
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::shuffling::bayer_groth_permutation::reencryption_protocol::ReencryptionProof;
 use crate::shuffling::data_structures::ElGamalCiphertext;
+use crate::shuffling::deck_commitment::{DeckCommitmentScheme, DeckOrderCommitment};
 use crate::shuffling::pedersen_commitment::opening_proof::PedersenCommitmentOpeningProof;
 use crate::shuffling::permutation_proof::proof_system::PublicData;
 use crate::shuffling::shuffling_proof::ShufflingProof;
@@ -65,6 +66,14 @@ pub struct StoredPublicData {
     pub c_perm: String,
     pub c_power: String,
     pub power_opening_proof: StoredPedersenOpeningProof,
+    pub deck_commitment: StoredDeckOrderCommitment,
+}
+
+/// Deck-order commitment tagged with the scheme it was computed under.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredDeckOrderCommitment {
+    pub scheme: DeckCommitmentScheme,
+    pub value: String,
 }
 
 impl StoredGroth16Proof {
@@ -268,6 +277,13 @@ impl StoredPublicData {
             power_opening_proof: StoredPedersenOpeningProof::from_proof(
                 &public.power_opening_proof,
             )?,
+            deck_commitment: StoredDeckOrderCommitment {
+                scheme: public.deck_commitment.scheme(),
+                value: match &public.deck_commitment {
+                    DeckOrderCommitment::Pedersen(point) => encode_hex(point)?,
+                    DeckOrderCommitment::Poseidon(value) => encode_hex(value)?,
+                },
+            },
         })
     }
 
@@ -291,6 +307,16 @@ impl StoredPublicData {
         let c_perm = decode_hex::<C>(&self.c_perm)?;
         let c_power = decode_hex::<C>(&self.c_power)?;
         let power_opening_proof = self.power_opening_proof.into_proof::<C>()?;
+        let deck_commitment = match self.deck_commitment.scheme {
+            DeckCommitmentScheme::Pedersen => {
+                DeckOrderCommitment::Pedersen(decode_hex::<C>(&self.deck_commitment.value)?)
+            }
+            DeckCommitmentScheme::Poseidon => {
+                DeckOrderCommitment::Poseidon(decode_hex::<C::BaseField>(
+                    &self.deck_commitment.value,
+                )?)
+            }
+        };
 
         Ok(PublicData {
             nonce,
@@ -300,6 +326,7 @@ impl StoredPublicData {
             c_perm,
             c_power,
             power_opening_proof,
+            deck_commitment,
         })
     }
 }
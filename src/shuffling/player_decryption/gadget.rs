@@ -415,11 +415,20 @@ where
     // This gives us A_u^(Σx_j) = A_u^x = g^((r+Δ) * x) = pk^(r+Δ)
     let mut mu = CV::zero();
     for (i, share) in shares.iter().enumerate() {
-        tracing::debug!(target: LOG_TARGET, "  Share[{}]: {:?}", i, share.share.value().ok());
+        tracing::debug!(
+            target: LOG_TARGET,
+            index = i,
+            unblinding_share = ?share.share.value().ok(),
+            "Unblinding share"
+        );
         mu = &mu + &share.share;
     }
 
-    tracing::debug!(target: LOG_TARGET, "Combined unblinding result: {:?}", mu.value().ok());
+    tracing::debug!(
+        target: LOG_TARGET,
+        combined_unblinding = ?mu.value().ok(),
+        "Combined unblinding result"
+    );
     tracing::debug!(target: LOG_TARGET, "=== End combine_unblinding_shares_gadget ===");
 
     Ok(mu)
@@ -457,14 +466,19 @@ where
         "=== Circuit recover_card_point_gadget ==="
     );
 
-    // Log player secret bits (be careful with this in production!)
+    // `player_secret_bits`, `player_unblinding`, `combined_unblinding` and `recovered_card_point`
+    // are listed in `debugging_tools::SENSITIVE_LOG_FIELDS` and are redacted unless unsafe
+    // logging is enabled.
     let secret_bits: Vec<bool> = player_secret_bits
         .iter()
         .map(|b| b.value().unwrap_or_default())
         .collect();
-    tracing::debug!(target: LOG_TARGET, "Player secret bits (length {}): first 8 bits: {:?}",
-        secret_bits.len(),
-        &secret_bits[..8.min(secret_bits.len())]);
+    tracing::debug!(
+        target: LOG_TARGET,
+        bit_len = secret_bits.len(),
+        player_secret_bits = ?&secret_bits[..8.min(secret_bits.len())],
+        "Player secret bits (first 8)"
+    );
 
     // Step 1: Compute player-specific unblinding using the helper element
     // Mathematical equation: player_unblinding = player_unblinding_helper^s_u
@@ -473,21 +487,21 @@ where
         .clone()
         .scalar_mul_le(player_secret_bits.iter())?;
 
-    println!(
-        "Step 1 - Player unblinding (helper^secret): {:?}",
-        player_unblinding.value().ok()
+    tracing::debug!(
+        target: LOG_TARGET,
+        player_unblinding = ?player_unblinding.value().ok(),
+        "Step 1 - Player unblinding (helper^secret)"
     );
-    tracing::debug!(target: LOG_TARGET, "Step 1 - Player unblinding (helper^secret): {:?}", player_unblinding.value().ok());
 
     // Step 2: Combine committee unblinding shares
     let combined_unblinding =
         combine_unblinding_shares_gadget(cs.clone(), unblinding_shares, expected_members)?;
 
-    println!(
-        "Step 2 - Combined unblinding from shares: {:?}",
-        combined_unblinding.value().ok()
+    tracing::debug!(
+        target: LOG_TARGET,
+        combined_unblinding = ?combined_unblinding.value().ok(),
+        "Step 2 - Combined unblinding from shares"
     );
-    tracing::debug!(target: LOG_TARGET, "Step 2 - Combined unblinding from shares: {:?}", combined_unblinding.value().ok());
 
     // Step 3: Recover the message group element by removing all blinding
     // Mathematical equation: g^m = blinded_message / (combined_unblinding · player_unblinding)
@@ -495,12 +509,11 @@ where
         - &combined_unblinding
         - &player_unblinding;
 
-    println!(
-        "Step 3 - Recovered element (g^m): {:?}",
-        recovered_element.value().ok()
+    tracing::debug!(
+        target: LOG_TARGET,
+        recovered_card_point = ?recovered_element.value().ok(),
+        "Step 3 - Recovered element (g^m)"
     );
-    println!("=== End Circuit recover_card_point_gadget ===");
-    tracing::debug!(target: LOG_TARGET, "Step 3 - Recovered element (g^m): {:?}", recovered_element.value().ok());
     tracing::debug!(target: LOG_TARGET, "=== End Circuit recover_card_point_gadget ===");
 
     Ok(recovered_element)
@@ -2,6 +2,7 @@ use crate::chaum_pedersen::ChaumPedersenProof;
 use crate::curve_absorb::CurveAbsorb;
 use crate::poseidon_config;
 use crate::shuffling::data_structures::ElGamalCiphertext;
+use crate::shuffling::error::DecryptionError;
 use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge};
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
@@ -146,6 +147,15 @@ pub struct PartialUnblindingShare<C: CurveGroup> {
     pub member_key: crate::ledger::CanonicalKey<C>,
 }
 
+impl<C: CurveGroup> PlayerAccessibleCiphertext<C> {
+    /// Whether every group component is the identity, the placeholder for an undealt card.
+    pub fn is_identity(&self) -> bool {
+        self.blinded_base.is_zero()
+            && self.blinded_message_with_player_key.is_zero()
+            && self.player_unblinding_helper.is_zero()
+    }
+}
+
 impl<C> DomainSeparated for PlayerTargetedBlindingContribution<C>
 where
     C: CurveGroup,
@@ -446,14 +456,15 @@ pub fn combine_unblinding_shares<C: CurveGroup>(
 /// * `expected_members` - The expected number of committee members
 ///
 /// # Returns
-/// The decrypted card value (0-51) or an error if decryption fails
+/// The decrypted card value (0-51) or an error if decryption fails. An identity ciphertext is
+/// rejected as [`DecryptionError::UndealtCard`] rather than decrypted to card 0.
 #[instrument(skip(player_secret, unblinding_shares), level = "trace")]
 pub fn recover_card_value<C>(
     player_ciphertext: &PlayerAccessibleCiphertext<C>,
     player_secret: C::ScalarField,
     unblinding_shares: Vec<PartialUnblindingShare<C>>,
     expected_members: usize,
-) -> Result<u8, &'static str>
+) -> Result<u8, DecryptionError>
 where
     C: CurveGroup + 'static,
     C::ScalarField: PrimeField,
@@ -467,6 +478,11 @@ where
         "=== Native recover_card_value ==="
     );
 
+    if player_ciphertext.is_identity() {
+        warn!(target: LOG_TARGET, "Refusing to decrypt an identity ciphertext");
+        return Err(DecryptionError::UndealtCard);
+    }
+
     // Step 1: Compute player-specific unblinding using the helper element
     // Only the player can do this as it requires knowing s_u
     let player_unblinding = player_ciphertext.player_unblinding_helper * player_secret;
@@ -551,11 +567,162 @@ where
                 ?recovered_element,
                 "Failed to find card value for recovered element via both linear search and hashmap"
             );
-            Err("Recovered element does not correspond to a valid card value")
+            Err(DecryptionError::UnknownCard)
         }
     }
 }
 
+/// Lagrange coefficient at zero for participant `index` within the subset `indices`.
+///
+/// Participant indices are the (non-zero) Shamir evaluation points of the committee members.
+pub fn lagrange_coefficient_at_zero<F: PrimeField>(
+    index: u64,
+    indices: &[u64],
+) -> Result<F, &'static str> {
+    if index == 0 || indices.contains(&0) {
+        return Err("Participant index 0 is reserved for the shared secret");
+    }
+    let x_i = F::from(index);
+    let (numerator, denominator) = indices.iter().filter(|&&other| other != index).fold(
+        (F::one(), F::one()),
+        |(num, den), &other| {
+            let x_j = F::from(other);
+            (num * x_j, den * (x_j - x_i))
+        },
+    );
+    denominator
+        .inverse()
+        .map(|inv| numerator * inv)
+        .ok_or("Degenerate Lagrange subset")
+}
+
+/// Sorted participant indices, rejecting duplicates and subsets smaller than `threshold`.
+pub(crate) fn threshold_subset(
+    indices: impl Iterator<Item = u64>,
+    threshold: usize,
+) -> Result<Vec<u64>, &'static str> {
+    let mut subset: Vec<u64> = indices.collect();
+    let len = subset.len();
+    subset.sort_unstable();
+    subset.dedup();
+    if subset.len() != len {
+        return Err("Duplicate participant index");
+    }
+    if subset.first() == Some(&0) {
+        return Err("Participant index 0 is reserved for the shared secret");
+    }
+    if subset.len() < threshold {
+        warn!(target: LOG_TARGET, threshold, contributors = subset.len(), "Below threshold");
+        return Err("Fewer contributions than the committee threshold");
+    }
+    Ok(subset)
+}
+
+/// Threshold variant of [`combine_blinding_contributions_for_player`] for a `t`-of-`n`
+/// committee whose aggregated key is Shamir-shared.
+///
+/// Any `threshold` or more members may blind the card. The returned participant indices are
+/// the subset that did; [`recover_card_value_threshold`] only accepts unblinding shares from
+/// exactly that subset so a card is blinded and unblinded by the same members.
+///
+/// # Arguments
+/// * `blinding_contributions` - `(participant_index, contribution)` pairs
+/// * `threshold` - Minimum number of contributing members `t`
+#[instrument(skip(initial_ciphertext, blinding_contributions), level = "trace")]
+#[allow(clippy::multiple_bound_locations)]
+pub fn combine_blinding_contributions_for_player_threshold<C: CurveGroup>(
+    initial_ciphertext: &ElGamalCiphertext<C>,
+    blinding_contributions: &[(u64, PlayerTargetedBlindingContribution<C>)],
+    threshold: usize,
+    aggregated_public_key: C,
+    player_public_key: C,
+) -> Result<(PlayerAccessibleCiphertext<C>, Vec<u64>), &'static str>
+where
+    C::BaseField: PrimeField,
+    C::ScalarField: PrimeField + Absorb,
+    C::Affine: Absorb,
+    C: CurveAbsorb<C::BaseField>,
+{
+    let contributors = threshold_subset(
+        blinding_contributions.iter().map(|(index, _)| *index),
+        threshold,
+    )?;
+
+    let contributions: Vec<_> = blinding_contributions
+        .iter()
+        .map(|(_, contribution)| contribution.clone())
+        .collect();
+    let ciphertext = combine_blinding_contributions_for_player(
+        initial_ciphertext,
+        &contributions,
+        aggregated_public_key,
+        player_public_key,
+    )?;
+
+    Ok((ciphertext, contributors))
+}
+
+/// Threshold variant of [`recover_card_value`].
+///
+/// Each share is `blinded_base^x_j` for the member's Shamir share `x_j`; weighting the shares
+/// by their Lagrange coefficients over the contributing subset reconstructs `pk^(r+Δ)`.
+///
+/// # Arguments
+/// * `contributors` - Participant indices returned when the card was blinded
+/// * `unblinding_shares` - `(participant_index, share)` pairs from the same members
+/// * `threshold` - Minimum number of contributing members `t`
+///
+/// Like [`recover_card_value`], an identity ciphertext is rejected as
+/// [`DecryptionError::UndealtCard`].
+#[instrument(skip(player_secret, unblinding_shares), level = "trace")]
+pub fn recover_card_value_threshold<C>(
+    player_ciphertext: &PlayerAccessibleCiphertext<C>,
+    contributors: &[u64],
+    player_secret: C::ScalarField,
+    unblinding_shares: &[(u64, PartialUnblindingShare<C>)],
+    threshold: usize,
+) -> Result<u8, DecryptionError>
+where
+    C: CurveGroup + 'static,
+    C::ScalarField: PrimeField,
+{
+    if player_ciphertext.is_identity() {
+        warn!(target: LOG_TARGET, "Refusing to decrypt an identity ciphertext");
+        return Err(DecryptionError::UndealtCard);
+    }
+
+    let subset = threshold_subset(unblinding_shares.iter().map(|(index, _)| *index), threshold)?;
+    if subset != contributors {
+        warn!(
+            target: LOG_TARGET,
+            ?subset,
+            ?contributors,
+            "Unblinding subset differs from blinding subset"
+        );
+        return Err(DecryptionError::InvalidShares(
+            "Unblinding shares must come from the members that blinded the card",
+        ));
+    }
+
+    let combined_unblinding = unblinding_shares.iter().try_fold(
+        C::zero(),
+        |acc, (index, share)| -> Result<C, &'static str> {
+            let lambda = lagrange_coefficient_at_zero::<C::ScalarField>(*index, &subset)?;
+            Ok(acc + share.share * lambda)
+        },
+    )?;
+    let player_unblinding = player_ciphertext.player_unblinding_helper * player_secret;
+    let recovered_element = (player_ciphertext.blinded_message_with_player_key
+        - combined_unblinding
+        - player_unblinding)
+        .into_affine()
+        .into_group();
+
+    get_card_value_map::<C>()
+        .lookup(&recovered_element)
+        .ok_or(DecryptionError::UnknownCard)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -857,6 +1024,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_threshold_blinding_and_recovery_two_of_three() {
+        let mut rng = test_rng();
+        let generator = GrumpkinProjective::generator();
+
+        // Shamir-share the committee secret with f(z) = x + a·z, so any two members suffice.
+        let committee_secret = ScalarField::rand(&mut rng);
+        let slope = ScalarField::rand(&mut rng);
+        let member_secret = |index: u64| committee_secret + slope * ScalarField::from(index);
+        let aggregated_pk = generator * committee_secret;
+
+        let player_secret = ScalarField::rand(&mut rng);
+        let player_public_key = generator * player_secret;
+
+        let card = 17u8;
+        let r = ScalarField::rand(&mut rng);
+        let ciphertext = ElGamalCiphertext::new(
+            generator * r,
+            aggregated_pk * r + generator * ScalarField::from(card as u64),
+        );
+
+        // Members 1 and 3 blind; member 2 is offline.
+        let contributions: Vec<_> = [1u64, 3]
+            .into_iter()
+            .map(|index| {
+                let contribution = PlayerTargetedBlindingContribution::generate(
+                    ScalarField::rand(&mut rng),
+                    aggregated_pk,
+                    player_public_key,
+                    &mut rng,
+                );
+                (index, contribution)
+            })
+            .collect();
+        let (player_ciphertext, contributors) =
+            combine_blinding_contributions_for_player_threshold(
+                &ciphertext,
+                &contributions,
+                2,
+                aggregated_pk,
+                player_public_key,
+            )
+            .unwrap();
+        assert_eq!(contributors, vec![1, 3]);
+
+        let share_from = |index: u64| {
+            let share = generate_committee_decryption_share(
+                &player_ciphertext,
+                member_secret(index),
+                CanonicalKey::new(generator * member_secret(index)),
+            );
+            (index, share)
+        };
+
+        let shares = vec![share_from(3), share_from(1)];
+        assert_eq!(
+            recover_card_value_threshold(
+                &player_ciphertext,
+                &contributors,
+                player_secret,
+                &shares,
+                2
+            ),
+            Ok(card)
+        );
+
+        // Member 2 holds a valid share of the key but did not blind this card.
+        let mixed = vec![share_from(1), share_from(2)];
+        assert!(recover_card_value_threshold(
+            &player_ciphertext,
+            &contributors,
+            player_secret,
+            &mixed,
+            2
+        )
+        .is_err());
+
+        assert!(combine_blinding_contributions_for_player_threshold(
+            &ciphertext,
+            &contributions[..1],
+            2,
+            aggregated_pk,
+            player_public_key,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn identity_player_ciphertext_is_an_undealt_card() {
+        let placeholder = PlayerAccessibleCiphertext {
+            blinded_base: GrumpkinProjective::zero(),
+            blinded_message_with_player_key: GrumpkinProjective::zero(),
+            player_unblinding_helper: GrumpkinProjective::zero(),
+            shuffler_proofs: vec![],
+        };
+        assert_eq!(
+            recover_card_value(&placeholder, ScalarField::from(5u64), vec![], 0),
+            Err(DecryptionError::UndealtCard)
+        );
+        assert_eq!(
+            recover_card_value_threshold(&placeholder, &[], ScalarField::from(5u64), &[], 0),
+            Err(DecryptionError::UndealtCard)
+        );
+    }
+
     #[test]
     fn test_card_value_mapping() {
         // Test that the card value mapping works correctly
@@ -0,0 +1,79 @@
+//! SNARK backend abstraction for the shuffle permutation proof.
+//!
+//! The shuffling prover and verifier only need key generation, proving and verification over
+//! an R1CS circuit, so the concrete proof system sits behind [`ProofBackend`]. Groth16 is the
+//! default; a transparent SNARK can be used by implementing the trait and instantiating
+//! [`ShufflingConfig`](super::shuffling_proof::ShufflingConfig) with it.
+
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof, ProvingKey};
+use ark_relations::gr1cs::ConstraintSynthesizer;
+use ark_snark::SNARK;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, RngCore};
+
+/// Proof system used for the permutation SNARK.
+pub trait ProofBackend<F: PrimeField> {
+    type ProvingKey: Clone;
+    type VerifyingKey: Clone;
+    type Proof: Clone;
+
+    /// Generate keys for the shape of `circuit`.
+    fn setup<C, R>(
+        circuit: C,
+        rng: &mut R,
+    ) -> anyhow::Result<(Self::ProvingKey, Self::VerifyingKey)>
+    where
+        C: ConstraintSynthesizer<F>,
+        R: RngCore + CryptoRng;
+
+    fn prove<C, R>(pk: &Self::ProvingKey, circuit: C, rng: &mut R) -> anyhow::Result<Self::Proof>
+    where
+        C: ConstraintSynthesizer<F>,
+        R: RngCore + CryptoRng;
+
+    fn verify(
+        vk: &Self::VerifyingKey,
+        public_inputs: &[F],
+        proof: &Self::Proof,
+    ) -> anyhow::Result<bool>;
+}
+
+/// Circuit-specific Groth16 over the pairing `E`.
+pub struct Groth16Backend<E>(PhantomData<E>);
+
+impl<E: Pairing> ProofBackend<E::ScalarField> for Groth16Backend<E> {
+    type ProvingKey = ProvingKey<E>;
+    type VerifyingKey = PreparedVerifyingKey<E>;
+    type Proof = Proof<E>;
+
+    fn setup<C, R>(
+        circuit: C,
+        rng: &mut R,
+    ) -> anyhow::Result<(Self::ProvingKey, Self::VerifyingKey)>
+    where
+        C: ConstraintSynthesizer<E::ScalarField>,
+        R: RngCore + CryptoRng,
+    {
+        let (pk, vk) = Groth16::<E>::circuit_specific_setup(circuit, rng)?;
+        let pvk = prepare_verifying_key(&vk);
+        Ok((pk, pvk))
+    }
+
+    fn prove<C, R>(pk: &Self::ProvingKey, circuit: C, rng: &mut R) -> anyhow::Result<Self::Proof>
+    where
+        C: ConstraintSynthesizer<E::ScalarField>,
+        R: RngCore + CryptoRng,
+    {
+        Ok(Groth16::<E>::prove(pk, circuit, rng)?)
+    }
+
+    fn verify(
+        vk: &Self::VerifyingKey,
+        public_inputs: &[E::ScalarField],
+        proof: &Self::Proof,
+    ) -> anyhow::Result<bool> {
+        Ok(Groth16::<E>::verify_proof(vk, proof, public_inputs)?)
+    }
+}
@@ -18,8 +18,9 @@ const LOG_TARGET: &str = "legit_poker::shuffling::rs_shuffle::bit_generation";
 
 /// Derive split bits from random seed using Poseidon hash
 ///
-/// Dynamically determines how many field elements are needed based on the field size,
-/// draws them from Poseidon, and fills a N×LEVELS matrix.
+/// Expands the seed into `N * LEVELS` bits with
+/// [`derive_seed_bits`](crate::vrf::derive_seed_bits), which squeezes as many Poseidon elements
+/// as the field size requires, and fills a N×LEVELS matrix.
 ///
 /// # Returns
 /// - The bit matrix [[bool; N]; LEVELS]
@@ -36,73 +37,25 @@ pub fn derive_split_bits<F, const N: usize, const LEVELS: usize>(
 where
     F: Field + PrimeField + ark_crypto_primitives::sponge::Absorb,
 {
-    use crate::shuffling::utils::generate_random_values;
-    use ark_ff::BigInteger;
+    use crate::vrf::{derive_seed_bits, seed_elements_needed};
 
-    // Calculate how many field elements we need
-    let field_bits = F::MODULUS_BIT_SIZE as usize;
-    let usable_bits_per_element = field_bits.saturating_sub(2); // After trimming first and last
-    let total_bits_needed = N * LEVELS; // 260 bits
-    let num_elements_needed =
-        (total_bits_needed + usable_bits_per_element - 1) / usable_bits_per_element;
+    let total_bits_needed = N * LEVELS;
+    let num_elements_needed = seed_elements_needed::<F>(total_bits_needed);
 
     tracing::debug!(
         target: LOG_TARGET,
         seed = ?seed,
-        field_bits,
-        usable_bits_per_element,
+        total_bits_needed,
         num_elements_needed,
         "Deriving split bits from seed"
     );
 
-    // Draw the required number of field elements from Poseidon
-    let random_values = generate_random_values(seed, num_elements_needed);
-
-    tracing::debug!(
-        target: LOG_TARGET,
-        num_random_values = random_values.len(),
-        "Random values generated"
-    );
-
-    // Debug: log the actual field values
-    #[cfg(test)]
-    {
-        for (i, val) in random_values.iter().enumerate() {
-            tracing::debug!(target: LOG_TARGET, "Random value[{}]: {:?}", i, val);
-        }
-    }
-
-    // Convert field elements to bits and collect into a single stream
-    let mut bit_stream = Vec::new();
-
-    for value in random_values.iter() {
-        // Get bit decomposition of the field element (LSB first)
-        // This produces exactly F::MODULUS_BIT_SIZE bits
-        let value_bigint = value.into_bigint();
-        let mut value_bits = Vec::with_capacity(F::MODULUS_BIT_SIZE as usize);
-
-        // Extract bits from the BigInteger representation
-        for i in 0..(F::MODULUS_BIT_SIZE as usize) {
-            value_bits.push(value_bigint.get_bit(i));
-        }
-
-        // Trim first and last bits
-        if value_bits.len() > 2 {
-            bit_stream.extend_from_slice(&value_bits[1..value_bits.len() - 1]);
-        }
-    }
+    // Draw exactly N×LEVELS bits, squeezing as many field elements as that takes
+    let bit_stream = derive_seed_bits(seed, total_bits_needed);
 
     // Fill the N×LEVELS matrix using array::from_fn
-    let bit_matrix = std::array::from_fn(|level| {
-        std::array::from_fn(|i| {
-            let bit_index = level * N + i;
-            if bit_index < bit_stream.len() {
-                bit_stream[bit_index]
-            } else {
-                false // Default to false if we somehow run out of bits
-            }
-        })
-    });
+    let bit_matrix =
+        std::array::from_fn(|level| std::array::from_fn(|i| bit_stream[level * N + i]));
 
     (bit_matrix, num_elements_needed)
 }
@@ -8,9 +8,11 @@
 
 use super::bayer_groth_permutation::reencryption_protocol::{prove, ReencryptionProof};
 use super::data_structures::ElGamalCiphertext;
+use super::deck_commitment::DeckCommitmentScheme;
 use crate::shuffling::bayer_groth_permutation::bg_setup::BGPowerChallengeSetup;
 
 use crate::curve_absorb::CurveAbsorb;
+use crate::field_conversion::convert_mod;
 use crate::pedersen_commitment::bytes_opening::{DeckHashWindow, ReencryptionWindow};
 use crate::shuffling::permutation_proof::circuit::PermutationProofCircuit;
 use ark_crypto_primitives::commitment::pedersen::Commitment as PedersenCommitment;
@@ -39,39 +41,47 @@ type PedersenDeck<G> = PedersenCommitment<G, DeckHashWindow>;
 // New prover/verifier (v2) using PermutationGroth16 + native Σ‑protocol
 // ============================================================================
 
+use crate::shuffling::pedersen_commitment::error::PedersenCommitmentOpeningError;
 use crate::shuffling::pedersen_commitment::opening_proof::{
     PedersenCommitmentOpeningProof, PedersenParams,
 };
 use crate::shuffling::permutation_proof::{
-    prepare_witness, PublicData as PermPublicData, WitnessData as PermWitnessData,
+    AssembledPermutationWitness, PointFromCoordinates, PublicData, WitnessAssembler,
 };
+use crate::shuffling::proof_backend::{Groth16Backend, ProofBackend};
 use ark_ec::pairing::Pairing;
-use ark_groth16::{Groth16, PreparedVerifyingKey, Proof as Groth16Proof, ProvingKey};
-use ark_snark::SNARK;
+use ark_groth16::{prepare_verifying_key, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use std::collections::BTreeMap;
+use thiserror::Error;
 
 /// Public configuration for the shuffling prover/verifier
-pub struct ShufflingConfig<E, G>
+pub struct ShufflingConfig<E, G, B = Groth16Backend<E>>
 where
     E: Pairing,
     G: CurveGroup,
+    B: ProofBackend<E::ScalarField>,
 {
     /// Generator on inner curve for ElGamal and commitments
     pub generator: G,
     /// Aggregated ElGamal public key
     pub public_key: G,
-    /// Cached Groth16 permutation SNARK keys keyed by `num_samples`
-    pub perm_snark_keys: BTreeMap<usize, (ProvingKey<E>, PreparedVerifyingKey<E>)>,
+    /// Cached permutation SNARK keys keyed by `num_samples`
+    pub perm_snark_keys: BTreeMap<usize, (B::ProvingKey, B::VerifyingKey)>,
+    /// Scheme the permutation proof commits the shuffled deck order with. The keys in
+    /// `perm_snark_keys` must be generated for the same scheme.
+    pub deck_commitment: DeckCommitmentScheme,
 }
 
 /// Complete shuffling proof artifacts
-pub struct ShufflingProof<E, G, const N: usize>
+pub struct ShufflingProof<E, G, const N: usize, B = Groth16Backend<E>>
 where
     E: Pairing,
     G: CurveGroup,
+    B: ProofBackend<E::ScalarField>,
 {
-    /// Groth16 proof for the permutation circuit
-    pub perm_snark_proof: Groth16Proof<E>,
+    /// SNARK proof for the permutation circuit
+    pub perm_snark_proof: B::Proof,
     /// Flattened public inputs used by the permutation circuit
     pub perm_snark_public_inputs: Vec<E::ScalarField>,
     /// Native Pedersen opening proof for c_power
@@ -81,7 +91,6 @@ where
 }
 
 /// Prove a shuffle with the new RS SNARK (with VRF) and native Σ‑protocol
-#[tracing::instrument(skip(config, ct_input, rng), target = LOG_TARGET)]
 pub fn prove_shuffling<E, G, GG, const N: usize, const LEVELS: usize>(
     config: &ShufflingConfig<E, G>,
     ct_input: &[ElGamalCiphertext<G>; N],
@@ -112,6 +121,45 @@ where
         G::BaseField,
         ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar<G::BaseField>,
     >,
+{
+    prove_shuffling_with_backend::<E, G, GG, Groth16Backend<E>, N, LEVELS>(
+        config, ct_input, vrf_nonce, rng,
+    )
+}
+
+/// [`prove_shuffling`] with the permutation SNARK produced by an arbitrary [`ProofBackend`].
+#[tracing::instrument(skip(config, ct_input, rng), target = LOG_TARGET)]
+pub fn prove_shuffling_with_backend<E, G, GG, B, const N: usize, const LEVELS: usize>(
+    config: &ShufflingConfig<E, G, B>,
+    ct_input: &[ElGamalCiphertext<G>; N],
+    // VRF input (public nonce). VRF secret key is sampled internally.
+    vrf_nonce: G::BaseField,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<
+    (
+        [ElGamalCiphertext<G>; N],
+        ShufflingProof<E, G, N, B>,
+        BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+    ),
+    Box<dyn std::error::Error>,
+>
+where
+    E: Pairing<ScalarField = G::BaseField>,
+    G: CurveGroup + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
+    G::Config: CurveConfig,
+    G::BaseField: PrimeField + Absorb,
+    G::ScalarField: PrimeField + Absorb + UniformRand,
+    B: ProofBackend<E::ScalarField>,
+    GG: CurveVar<G, G::BaseField>
+        + crate::shuffling::curve_absorb::CurveAbsorbGadget<
+            G::BaseField,
+            ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar<G::BaseField>,
+        >,
+    for<'a> &'a GG: ark_r1cs_std::groups::GroupOpsBounds<'a, G, GG>,
+    for<'a> &'a GG: crate::shuffling::curve_absorb::CurveAbsorbGadget<
+        G::BaseField,
+        ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar<G::BaseField>,
+    >,
 {
     tracing::info!(
         target = LOG_TARGET,
@@ -128,8 +176,9 @@ where
         "prove_shuffling: Pedersen params prepared"
     );
 
-    // 2) Prepare permutation witnesses (native), including BG setup + opening
-    // Sample a fresh VRF secret key locally; only pk goes public via the SNARK inputs
+    // 2) Assemble permutation witnesses section by section (native), including BG setup
+    // + opening. Sample a fresh VRF secret key locally; only pk goes public via the SNARK
+    // inputs.
     let vrf_sk = G::ScalarField::rand(rng);
     let mut prep_params = crate::shuffling::permutation_proof::PermutationParameters::<G, _> {
         perm_params: &perm_params,
@@ -138,18 +187,25 @@ where
     };
     let mut sponge =
         PoseidonSponge::<G::BaseField>::new(&crate::config::poseidon_config::<G::BaseField>());
-    let prepared =
-        prepare_witness::<G, _, _, N, LEVELS>(&mut prep_params, vrf_nonce, vrf_sk, &mut sponge)?;
+    let AssembledPermutationWitness {
+        public,
+        witness,
+        num_samples,
+        permutation: pi,
+        bg_setup,
+        blinding_s,
+    } = WitnessAssembler::<G, _, N, LEVELS>::new(&mut prep_params, vrf_nonce, vrf_sk)
+        .with_deck_commitment(config.deck_commitment)
+        .assemble(&mut sponge)?;
     tracing::info!(
         target = LOG_TARGET,
-        "prove_shuffling: witness prepared; indices_init_len={}",
-        prepared.indices_init.len()
+        "prove_shuffling: witness assembled; indices_init_len={}",
+        public.indices_init.len()
     );
 
-    // 3) Apply the exact prepared permutation to the input deck and re-encrypt
-    // Use the RS permutation extracted from the prepared witness to guarantee
-    // consistency between BG setup (b, commitments) and the output deck.
-    let pi: [usize; N] = prepared.rs_trace.extract_permutation_array();
+    // 3) Apply the exact assembled permutation to the input deck and re-encrypt
+    // Use the RS permutation extracted from the witness to guarantee consistency
+    // between BG setup (b, commitments) and the output deck.
     // Fresh rerandomization scalars
     let rerand: [G::ScalarField; N] =
         crate::shuffling::encryption::generate_randomization_array::<G::Config, N>(rng);
@@ -161,28 +217,14 @@ where
         "prove_shuffling: output deck constructed via prepared RS permutation"
     );
 
-    // 4) Groth16 permutation proof using preloaded keys from config
-    let num_samples = prepared.rs_trace.num_samples;
+    // 4) Permutation SNARK proof using preloaded keys from config
     let (pk, _pvk) = config
         .perm_snark_keys
         .get(&num_samples)
         .ok_or("missing permutation SNARK keys for num_samples in config")?;
-    let public = PermPublicData::<G, N> {
-        nonce: vrf_nonce,
-        pk_public: prepared.pk,
-        indices_init: prepared.indices_init.map(|x| x.into()),
-        power_challenge_public: prepared.bg_setup.power_challenge_base.into(),
-        c_perm: prepared.bg_setup.permutation_commitment,
-        c_power: prepared.bg_setup.power_permutation_commitment,
-        power_opening_proof: prepared.power_opening_proof.clone(),
-    };
-    let witness = PermWitnessData::<G, N, LEVELS> {
-        sk: vrf_sk,
-        rs_witness: prepared.rs_trace.witness_trace.clone(),
-        power_perm_vec_wit: prepared.perm_power_vector_base.map(|x| x.into()),
-        power_perm_vec_scalar_wit: prepared.perm_power_vector_scalar,
-    };
-    // Build circuit and prove
+    let power_opening_proof = public.power_opening_proof.clone();
+    let power_perm_vec_scalar = witness.power_perm_vec_scalar_wit;
+    // Build circuit (moving the witness columns in) and prove
     let circ = PermutationProofCircuit::<G, GG, N, LEVELS> {
         num_samples,
         nonce: Some(public.nonce),
@@ -191,14 +233,16 @@ where
         power_challenge_public: Some(public.power_challenge_public),
         c_perm: Some(public.c_perm),
         c_power: Some(public.c_power),
-        power_opening_proof: Some(public.power_opening_proof.clone()),
+        power_opening_proof: Some(public.power_opening_proof),
+        deck_commitment: Some(public.deck_commitment),
         sk: Some(witness.sk),
-        rs_witness: Some(witness.rs_witness.clone()),
+        rs_witness: Some(witness.rs_witness),
         power_perm_vec_wit: Some(witness.power_perm_vec_wit),
         power_perm_vec_scalar_wit: Some(witness.power_perm_vec_scalar_wit),
+        deck_opening: Some(witness.deck_opening),
         _pd: core::marker::PhantomData,
     };
-    let perm_proof = Groth16::<E>::prove(pk, circ.clone(), rng)?;
+    let perm_proof = B::prove(pk, circ.clone(), rng)?;
     // Derive public inputs from circuit
     use ark_relations::gr1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisMode};
     let cs = ConstraintSystem::<G::BaseField>::new_ref();
@@ -213,7 +257,7 @@ where
     let perm_public_inputs = inst[1..].to_vec();
     tracing::info!(
         target = LOG_TARGET,
-        "prove_shuffling: Groth16 done; public_inputs_len={}",
+        "prove_shuffling: permutation SNARK done; public_inputs_len={}",
         perm_public_inputs.len()
     );
 
@@ -225,10 +269,10 @@ where
         &power_params,
         ct_input,
         &ct_output,
-        prepared.bg_setup.power_challenge_scalar,
-        &prepared.bg_setup.power_permutation_commitment,
-        &prepared.perm_power_vector_scalar,
-        prepared.blinding_s,
+        bg_setup.power_challenge_scalar,
+        &bg_setup.power_permutation_commitment,
+        &power_perm_vec_scalar,
+        blinding_s,
         &rerand,
         &mut tr_sig,
         rng,
@@ -255,8 +299,7 @@ where
     {
         use crate::shuffling::bayer_groth_permutation::utils::compute_powers_sequence;
         use crate::shuffling::pedersen_commitment::msm_ciphertexts;
-        let powers: [G::ScalarField; N] =
-            compute_powers_sequence(prepared.bg_setup.power_challenge_scalar);
+        let powers: [G::ScalarField; N] = compute_powers_sequence(bg_setup.power_challenge_scalar);
         let input_ciphertext_aggregator = msm_ciphertexts(ct_input, &powers);
         let lhs = super::bayer_groth_permutation::reencryption_protocol::encrypt_one_and_combine(
             &config.public_key,
@@ -266,9 +309,9 @@ where
         );
         let rhs = crate::shuffling::data_structures::ElGamalCiphertext {
             c1: reencryption_proof.blinding_rerandomization_commitment.c1
-                + input_ciphertext_aggregator.c1 * prepared.bg_setup.power_challenge_scalar,
+                + input_ciphertext_aggregator.c1 * bg_setup.power_challenge_scalar,
             c2: reencryption_proof.blinding_rerandomization_commitment.c2
-                + input_ciphertext_aggregator.c2 * prepared.bg_setup.power_challenge_scalar,
+                + input_ciphertext_aggregator.c2 * bg_setup.power_challenge_scalar,
         };
         if lhs.c1 != rhs.c1 || lhs.c2 != rhs.c2 {
             tracing::warn!(
@@ -290,15 +333,40 @@ where
         ShufflingProof {
             perm_snark_proof: perm_proof,
             perm_snark_public_inputs: perm_public_inputs,
-            power_opening_proof: prepared.power_opening_proof,
+            power_opening_proof,
             reencryption_proof,
         },
-        prepared.bg_setup.clone(),
+        bg_setup,
     ))
 }
 
-/// Verify a shuffle with the Groth16 permutation SNARK and native Σ‑protocol
-#[tracing::instrument(skip(config, ct_input, ct_output, proof), target = LOG_TARGET)]
+/// Reason a shuffle failed [`verify_shuffling_detailed`]
+#[derive(Debug, Error)]
+pub enum ShuffleVerifyError {
+    #[error("deck length mismatch: expected {expected}, input {input}, output {output}")]
+    DeckLengthMismatch {
+        expected: usize,
+        input: usize,
+        output: usize,
+    },
+
+    #[error("permutation SNARK public input mismatch: {which}")]
+    PublicInputMismatch { which: &'static str },
+
+    #[error("power permutation commitment opening failed: {0}")]
+    CommitmentMismatch(PedersenCommitmentOpeningError),
+
+    #[error("invalid proof: {which}")]
+    ProofInvalid { which: &'static str },
+
+    #[error("verifier setup failed: {0}")]
+    Setup(String),
+}
+
+/// Verify a shuffle with the Groth16 permutation SNARK and native Σ‑protocol.
+///
+/// Returns `Ok(false)` when any check of [`verify_shuffling_detailed`] rejects the shuffle and
+/// `Err` only when the verifier itself cannot be set up.
 pub fn verify_shuffling<E, G, const N: usize>(
     config: &ShufflingConfig<E, G>,
     ct_input: &[ElGamalCiphertext<G>; N],
@@ -308,44 +376,380 @@ pub fn verify_shuffling<E, G, const N: usize>(
 ) -> Result<bool, Box<dyn std::error::Error>>
 where
     E: Pairing<ScalarField = G::BaseField>,
-    G: CurveGroup + CurveAbsorb<G::BaseField>,
+    G: PointFromCoordinates + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
     G::BaseField: PrimeField + Absorb,
     G::ScalarField: PrimeField + Absorb,
 {
-    tracing::info!(
-        target = LOG_TARGET,
-        "verify_shuffling: start; deck_size={}",
-        ct_input.len()
-    );
-    // 1) Verify Groth16 permutation proof
-    // We no longer carry the prepared VK in the proof. Verify against any prepared VK
-    // present in the config (in practice, there is typically just one entry).
-    let mut ok_snark = false;
-    for (_ns, (_pk, pvk)) in &config.perm_snark_keys {
-        if Groth16::<E>::verify_proof(
-            pvk,
-            &proof.perm_snark_proof,
-            &proof.perm_snark_public_inputs,
-        )? {
-            ok_snark = true;
-            break;
+    shuffle_verdict(verify_shuffling_detailed(
+        config, ct_input, ct_output, proof, bg_setup,
+    ))
+}
+
+/// [`verify_shuffling`] for a permutation SNARK produced by an arbitrary [`ProofBackend`].
+pub fn verify_shuffling_with_backend<E, G, B, const N: usize>(
+    config: &ShufflingConfig<E, G, B>,
+    ct_input: &[ElGamalCiphertext<G>; N],
+    ct_output: &[ElGamalCiphertext<G>; N],
+    proof: &ShufflingProof<E, G, N, B>,
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+) -> Result<bool, Box<dyn std::error::Error>>
+where
+    E: Pairing<ScalarField = G::BaseField>,
+    G: PointFromCoordinates + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
+    G::BaseField: PrimeField + Absorb,
+    G::ScalarField: PrimeField + Absorb,
+    B: ProofBackend<E::ScalarField>,
+{
+    shuffle_verdict(verify_shuffle_checks(
+        &config.public_key,
+        config.perm_snark_keys.values().map(|(_, vk)| vk),
+        config.deck_commitment,
+        ct_input,
+        ct_output,
+        proof,
+        bg_setup,
+    ))
+}
+
+/// Collapse a detailed verification result into the boolean verdict of [`verify_shuffling`].
+fn shuffle_verdict(
+    result: Result<(), ShuffleVerifyError>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(ShuffleVerifyError::Setup(reason)) => Err(reason.into()),
+        Err(err) => {
+            tracing::info!(target = LOG_TARGET, error = %err, "verify_shuffling: rejected");
+            Ok(false)
         }
     }
-    if !ok_snark {
-        tracing::info!(
+}
+
+/// Verify a shuffle like [`verify_shuffling`], reporting which check rejected it.
+///
+/// Checks run cheapest first: deck lengths, binding of the SNARK public inputs to
+/// `bg_setup`, the Pedersen opening of `c_power`, the Groth16 proof, and finally the
+/// re-encryption Σ‑protocol.
+#[tracing::instrument(skip(config, ct_input, ct_output, proof), target = LOG_TARGET)]
+pub fn verify_shuffling_detailed<E, G, const N: usize>(
+    config: &ShufflingConfig<E, G>,
+    ct_input: &[ElGamalCiphertext<G>],
+    ct_output: &[ElGamalCiphertext<G>],
+    proof: &ShufflingProof<E, G, N>,
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+) -> Result<(), ShuffleVerifyError>
+where
+    E: Pairing<ScalarField = G::BaseField>,
+    G: PointFromCoordinates + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
+    G::BaseField: PrimeField + Absorb,
+    G::ScalarField: PrimeField + Absorb,
+{
+    // 1) Deck lengths
+    let (ct_input, ct_output): (&[ElGamalCiphertext<G>; N], &[ElGamalCiphertext<G>; N]) =
+        match (ct_input.try_into(), ct_output.try_into()) {
+            (Ok(input), Ok(output)) => (input, output),
+            _ => {
+                return Err(ShuffleVerifyError::DeckLengthMismatch {
+                    expected: N,
+                    input: ct_input.len(),
+                    output: ct_output.len(),
+                })
+            }
+        };
+
+    verify_shuffle_checks(
+        &config.public_key,
+        config.perm_snark_keys.values().map(|(_, vk)| vk),
+        config.deck_commitment,
+        ct_input,
+        ct_output,
+        proof,
+        bg_setup,
+    )
+}
+
+/// Checks 2–5 of [`verify_shuffling_detailed`], for decks already known to hold `N` cards.
+fn verify_shuffle_checks<'a, E, G, B, const N: usize>(
+    public_key: &G,
+    vks: impl IntoIterator<Item = &'a B::VerifyingKey>,
+    deck_commitment: DeckCommitmentScheme,
+    ct_input: &[ElGamalCiphertext<G>; N],
+    ct_output: &[ElGamalCiphertext<G>; N],
+    proof: &ShufflingProof<E, G, N, B>,
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+) -> Result<(), ShuffleVerifyError>
+where
+    E: Pairing<ScalarField = G::BaseField>,
+    G: PointFromCoordinates + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
+    G::BaseField: PrimeField + Absorb,
+    G::ScalarField: PrimeField + Absorb,
+    B: ProofBackend<E::ScalarField> + 'a,
+{
+    // 2) SNARK public inputs must be bound to the BG setup the verifier was given
+    check_perm_public_inputs::<G, N>(&proof.perm_snark_public_inputs, deck_commitment, bg_setup)?;
+
+    // 3) Pedersen opening of c_power
+    let power_params_raw =
+        power_pedersen_params::<G>().map_err(|e| ShuffleVerifyError::Setup(e.to_string()))?;
+    verify_power_opening(&power_params_raw, bg_setup, proof)
+        .map_err(ShuffleVerifyError::CommitmentMismatch)?;
+
+    // 4) Groth16 permutation proof
+    let snark_ok =
+        perm_snark_verifies(vks, proof).map_err(|e| ShuffleVerifyError::Setup(e.to_string()))?;
+    if !snark_ok {
+        return Err(ShuffleVerifyError::ProofInvalid {
+            which: "permutation SNARK",
+        });
+    }
+
+    // 5) Re-encryption Σ‑protocol
+    if !reencryption_sigma_verifies(
+        public_key,
+        &power_params_raw,
+        ct_input,
+        ct_output,
+        proof,
+        bg_setup,
+    ) {
+        return Err(ShuffleVerifyError::ProofInvalid {
+            which: "re-encryption Σ-protocol",
+        });
+    }
+
+    tracing::debug!(
+        target = LOG_TARGET,
+        "verify_shuffling_detailed: all checks passed"
+    );
+    Ok(())
+}
+
+/// Why [`verify_from_bytes`] could not reach a verdict.
+#[derive(Debug, Error)]
+pub enum VerifyFromBytesError {
+    #[error("malformed {which}: {source}")]
+    Malformed {
+        which: &'static str,
+        #[source]
+        source: SerializationError,
+    },
+
+    #[error("malformed {which}: {trailing} trailing bytes")]
+    TrailingBytes {
+        which: &'static str,
+        trailing: usize,
+    },
+
+    #[error("instance decks hold {input} and {output} ciphertexts, expected {expected}")]
+    DeckLength {
+        expected: usize,
+        input: usize,
+        output: usize,
+    },
+
+    #[error("verifier setup failed: {0}")]
+    Setup(String),
+}
+
+/// Encode the verifier's view of `config` for [`verify_from_bytes`]: the aggregated public
+/// key, every permutation SNARK verifying key, then the deck commitment scheme tag,
+/// compressed.
+pub fn config_to_bytes<E, G>(config: &ShufflingConfig<E, G>) -> Result<Vec<u8>, SerializationError>
+where
+    E: Pairing,
+    G: CurveGroup,
+{
+    let vks: Vec<VerifyingKey<E>> = config
+        .perm_snark_keys
+        .values()
+        .map(|(_, pvk)| pvk.vk.clone())
+        .collect();
+    let mut bytes = Vec::new();
+    config.public_key.serialize_compressed(&mut bytes)?;
+    vks.serialize_compressed(&mut bytes)?;
+    config
+        .deck_commitment
+        .tag()
+        .serialize_compressed(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Encode a shuffle instance for [`verify_from_bytes`]: input deck, output deck, BG setup.
+pub fn instance_to_bytes<G: CurveGroup>(
+    ct_input: &[ElGamalCiphertext<G>],
+    ct_output: &[ElGamalCiphertext<G>],
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = Vec::new();
+    ct_input.to_vec().serialize_compressed(&mut bytes)?;
+    ct_output.to_vec().serialize_compressed(&mut bytes)?;
+    bg_setup.serialize_compressed(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Encode a Groth16 shuffling proof for [`verify_from_bytes`], fields in declaration order.
+pub fn proof_to_bytes<E, G, const N: usize>(
+    proof: &ShufflingProof<E, G, N>,
+) -> Result<Vec<u8>, SerializationError>
+where
+    E: Pairing,
+    G: CurveGroup,
+{
+    let mut bytes = Vec::new();
+    proof.perm_snark_proof.serialize_compressed(&mut bytes)?;
+    proof
+        .perm_snark_public_inputs
+        .serialize_compressed(&mut bytes)?;
+    proof.power_opening_proof.serialize_compressed(&mut bytes)?;
+    proof.reencryption_proof.serialize_compressed(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Verify a Groth16 shuffle given as bytes from [`config_to_bytes`], [`instance_to_bytes`]
+/// and [`proof_to_bytes`].
+///
+/// Bytes that do not decode (including invalid curve points) are an error; a well-formed
+/// proof that fails any check of [`verify_shuffling_detailed`] is `Ok(false)`.
+pub fn verify_from_bytes<E, G, const N: usize>(
+    config_bytes: &[u8],
+    instance_bytes: &[u8],
+    proof_bytes: &[u8],
+) -> Result<bool, VerifyFromBytesError>
+where
+    E: Pairing<ScalarField = G::BaseField>,
+    G: PointFromCoordinates + CurveAbsorb<G::BaseField> + ark_ff::ToConstraintField<G::BaseField>,
+    G::BaseField: PrimeField + Absorb,
+    G::ScalarField: PrimeField + Absorb,
+{
+    let mut reader = config_bytes;
+    let public_key: G = decode(&mut reader, "config")?;
+    let vks: Vec<VerifyingKey<E>> = decode(&mut reader, "config")?;
+    let scheme_tag: u8 = decode(&mut reader, "config")?;
+    let deck_commitment =
+        DeckCommitmentScheme::from_tag(scheme_tag).ok_or(VerifyFromBytesError::Malformed {
+            which: "config",
+            source: SerializationError::InvalidData,
+        })?;
+    expect_consumed(reader, "config")?;
+
+    let mut reader = instance_bytes;
+    let ct_input: Vec<ElGamalCiphertext<G>> = decode(&mut reader, "instance")?;
+    let ct_output: Vec<ElGamalCiphertext<G>> = decode(&mut reader, "instance")?;
+    let bg_setup: BGPowerChallengeSetup<G::BaseField, G::ScalarField, G> =
+        decode(&mut reader, "instance")?;
+    expect_consumed(reader, "instance")?;
+    // The scalar challenge is only a field conversion of the base one; the proof is never
+    // checked against it directly, so a forged value must be caught here.
+    if bg_setup.power_challenge_scalar
+        != convert_mod::<G::BaseField, G::ScalarField>(bg_setup.power_challenge_base)
+    {
+        tracing::debug!(
             target = LOG_TARGET,
-            "verify_shuffling: SNARK verification failed"
+            "verify_from_bytes: power challenge scalar does not match its base field challenge"
         );
         return Ok(false);
     }
-    tracing::info!(
-        target = LOG_TARGET,
-        "verify_shuffling: SNARK verification passed"
-    );
+    let (ct_input, ct_output): (&[ElGamalCiphertext<G>; N], &[ElGamalCiphertext<G>; N]) = match (
+        ct_input.as_slice().try_into(),
+        ct_output.as_slice().try_into(),
+    ) {
+        (Ok(input), Ok(output)) => (input, output),
+        _ => {
+            return Err(VerifyFromBytesError::DeckLength {
+                expected: N,
+                input: ct_input.len(),
+                output: ct_output.len(),
+            })
+        }
+    };
+
+    let mut reader = proof_bytes;
+    let proof: ShufflingProof<E, G, N> = ShufflingProof {
+        perm_snark_proof: decode(&mut reader, "proof")?,
+        perm_snark_public_inputs: decode(&mut reader, "proof")?,
+        power_opening_proof: decode(&mut reader, "proof")?,
+        reencryption_proof: decode(&mut reader, "proof")?,
+    };
+    expect_consumed(reader, "proof")?;
+
+    let pvks: Vec<_> = vks.iter().map(prepare_verifying_key).collect();
+    match verify_shuffle_checks(
+        &public_key,
+        &pvks,
+        deck_commitment,
+        ct_input,
+        ct_output,
+        &proof,
+        &bg_setup,
+    ) {
+        Ok(()) => Ok(true),
+        Err(ShuffleVerifyError::Setup(reason)) => Err(VerifyFromBytesError::Setup(reason)),
+        Err(err) => {
+            tracing::debug!(target = LOG_TARGET, %err, "verify_from_bytes: proof rejected");
+            Ok(false)
+        }
+    }
+}
 
-    // 2) Verify Pedersen opening of c_power (flexible, pad N to next power-of-two)
+fn decode<T: CanonicalDeserialize>(
+    reader: &mut &[u8],
+    which: &'static str,
+) -> Result<T, VerifyFromBytesError> {
+    T::deserialize_compressed(reader)
+        .map_err(|source| VerifyFromBytesError::Malformed { which, source })
+}
+
+fn expect_consumed(rest: &[u8], which: &'static str) -> Result<(), VerifyFromBytesError> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(VerifyFromBytesError::TrailingBytes {
+            which,
+            trailing: rest.len(),
+        })
+    }
+}
+
+/// Verify the permutation SNARK proof against any of the verifying keys
+/// (in practice, there is typically just one entry).
+fn perm_snark_verifies<'a, E, G, B, const N: usize>(
+    vks: impl IntoIterator<Item = &'a B::VerifyingKey>,
+    proof: &ShufflingProof<E, G, N, B>,
+) -> anyhow::Result<bool>
+where
+    E: Pairing,
+    G: CurveGroup,
+    B: ProofBackend<E::ScalarField> + 'a,
+{
+    for vk in vks {
+        if B::verify(vk, &proof.perm_snark_public_inputs, &proof.perm_snark_proof)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Pedersen parameters for `c_power` (deterministic seed shared with the prover)
+fn power_pedersen_params<G: CurveGroup>(
+) -> Result<ark_crypto_primitives::commitment::pedersen::Parameters<G>, ark_crypto_primitives::Error>
+{
     let mut power_rng = StdRng::seed_from_u64(43);
-    let power_params_raw = PedersenReenc::<G>::setup(&mut power_rng)?;
+    PedersenReenc::<G>::setup(&mut power_rng)
+}
+
+/// Verify the flexible Pedersen opening of `c_power` (N padded to the next power of two)
+fn verify_power_opening<E, G, B, const N: usize>(
+    power_params_raw: &ark_crypto_primitives::commitment::pedersen::Parameters<G>,
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+    proof: &ShufflingProof<E, G, N, B>,
+) -> Result<(), PedersenCommitmentOpeningError>
+where
+    E: Pairing,
+    B: ProofBackend<E::ScalarField>,
+    G: CurveGroup + CurveAbsorb<G::BaseField>,
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField,
+{
     let padded_n = if N.is_power_of_two() {
         N
     } else {
@@ -357,15 +761,24 @@ where
         &bg_setup.power_permutation_commitment,
         &proof.power_opening_proof,
     )
-    .map_err(|e| -> Box<dyn std::error::Error> {
-        format!("pedersen opening verify: {e:?}").into()
-    })?;
-    tracing::info!(
-        target = LOG_TARGET,
-        "verify_shuffling: Pedersen opening verified (flex)"
-    );
+}
 
-    // 3) Verify native re-encryption Σ‑protocol
+/// Verify the native re-encryption Σ‑protocol
+fn reencryption_sigma_verifies<E, G, B, const N: usize>(
+    public_key: &G,
+    power_params_raw: &ark_crypto_primitives::commitment::pedersen::Parameters<G>,
+    ct_input: &[ElGamalCiphertext<G>; N],
+    ct_output: &[ElGamalCiphertext<G>; N],
+    proof: &ShufflingProof<E, G, N, B>,
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+) -> bool
+where
+    E: Pairing,
+    B: ProofBackend<E::ScalarField>,
+    G: CurveGroup + CurveAbsorb<G::BaseField>,
+    G::BaseField: PrimeField + Absorb,
+    G::ScalarField: PrimeField + Absorb,
+{
     let mut tr_sig =
         PoseidonSponge::<G::BaseField>::new(&crate::config::poseidon_config::<G::BaseField>());
     // Normalize ciphertexts to affine-backed representations to avoid
@@ -378,25 +791,55 @@ where
         c1: ct_output[i].c1.into_affine().into(),
         c2: ct_output[i].c2.into_affine().into(),
     });
-    let ok_sigma = super::bayer_groth_permutation::reencryption_protocol::verify::<G, _, N>(
-        &config.public_key,
-        &power_params_raw,
+    super::bayer_groth_permutation::reencryption_protocol::verify::<G, _, N>(
+        public_key,
+        power_params_raw,
         &normalized_input,
         &normalized_output,
         bg_setup.power_challenge_scalar,
         &bg_setup.power_permutation_commitment,
         &proof.reencryption_proof,
         &mut tr_sig,
-    );
-    if !ok_sigma {
-        tracing::info!(
-            target = LOG_TARGET,
-            "verify_shuffling: Σ‑protocol verification failed"
-        );
-        return Ok(false);
+    )
+}
+
+/// Check that the SNARK public inputs carry the expected `indices_init`, power challenge,
+/// and commitments, decoding them with [`PublicData::from_field_elements`].
+fn check_perm_public_inputs<G, const N: usize>(
+    public_inputs: &[G::BaseField],
+    deck_commitment: DeckCommitmentScheme,
+    bg_setup: &BGPowerChallengeSetup<G::BaseField, G::ScalarField, G>,
+) -> Result<(), ShuffleVerifyError>
+where
+    G: PointFromCoordinates,
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField,
+{
+    let public = PublicData::<G, N>::from_field_elements(public_inputs, deck_commitment)
+        .map_err(|_| ShuffleVerifyError::PublicInputMismatch { which: "layout" })?;
+
+    let indices_ok = public
+        .indices_init
+        .iter()
+        .enumerate()
+        .all(|(i, x)| *x == G::BaseField::from(i as u64));
+    if !indices_ok {
+        return Err(ShuffleVerifyError::PublicInputMismatch {
+            which: "indices_init",
+        });
     }
-    tracing::info!(target = LOG_TARGET, "verify_shuffling: all checks passed");
-    Ok(true)
+    if public.power_challenge_public != bg_setup.power_challenge_base {
+        return Err(ShuffleVerifyError::PublicInputMismatch {
+            which: "power_challenge",
+        });
+    }
+    if public.c_perm != bg_setup.permutation_commitment {
+        return Err(ShuffleVerifyError::PublicInputMismatch { which: "c_perm" });
+    }
+    if public.c_power != bg_setup.power_permutation_commitment {
+        return Err(ShuffleVerifyError::PublicInputMismatch { which: "c_power" });
+    }
+    Ok(())
 }
 
 // Legacy generic proof system and tests have been removed in favor of the
@@ -413,12 +856,13 @@ mod tests {
     use ark_bn254::{Bn254, Fr as BaseField};
     use ark_crypto_primitives::sponge::{poseidon::constraints::PoseidonSpongeVar, Absorb};
     use ark_ec::{CurveConfig, CurveGroup, PrimeGroup};
-    use ark_ff::{PrimeField, UniformRand};
+    use ark_ff::{PrimeField, ToConstraintField, UniformRand};
     use ark_grumpkin::{Fr as ScalarField, GrumpkinConfig, Projective as G};
     use ark_r1cs_std::{
         fields::fp::FpVar, groups::curves::short_weierstrass::ProjectiveVar as SWVar,
     };
     use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use ark_std::Zero;
     use tracing_subscriber::{
         filter, fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt,
     };
@@ -469,6 +913,7 @@ mod tests {
             generator,
             public_key: pk,
             perm_snark_keys: Default::default(),
+            deck_commitment: DeckCommitmentScheme::Pedersen,
         };
 
         // Input deck (encrypt messages 0..N-1)
@@ -516,6 +961,310 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_prove_and_verify_shuffling_with_poseidon_deck_commitment() {
+        use crate::shuffling::permutation_proof::{PermutationGroth16, PublicData};
+        const N: usize = 8;
+        const LEVELS: usize = 3;
+
+        let mut rng = StdRng::seed_from_u64(31337);
+        let generator = G::generator();
+        let pk = generator * ScalarField::rand(&mut rng);
+        let (ct_input, _rand) =
+            crate::shuffling::generate_random_ciphertexts::<G, N>(&pk, &mut rng);
+        let nonce: BaseField = BaseField::rand(&mut rng);
+
+        let ns = run_rs_shuffle_permutation::<BaseField, ElGamalCiphertext<G>, N, LEVELS>(
+            nonce, &ct_input,
+        )
+        .num_samples;
+        let poseidon = DeckCommitmentScheme::Poseidon;
+        let perm_sys = PermutationGroth16::<Bn254, G, GVar, N, LEVELS>::setup_with_deck_commitment(
+            &mut rng, ns, poseidon,
+        )
+        .expect("perm setup");
+        let mut config: ShufflingConfig<Bn254, G> = ShufflingConfig {
+            generator,
+            public_key: pk,
+            perm_snark_keys: Default::default(),
+            deck_commitment: poseidon,
+        };
+        config.perm_snark_keys.insert(
+            ns,
+            (
+                perm_sys.proving_key().clone(),
+                perm_sys.prepared_vk().clone(),
+            ),
+        );
+
+        let (ct_output, proof, bg_setup) =
+            prove_shuffling::<Bn254, G, GVar, N, LEVELS>(&config, &ct_input, nonce, &mut rng)
+                .expect("prove_shuffling");
+        verify_shuffling_detailed::<Bn254, G, N>(&config, &ct_input, &ct_output, &proof, &bg_setup)
+            .expect("shuffle committed with Poseidon should verify");
+
+        let public =
+            PublicData::<G, N>::from_field_elements(&proof.perm_snark_public_inputs, poseidon)
+                .expect("public inputs decode");
+        assert_eq!(public.deck_commitment.scheme(), poseidon);
+
+        // The scheme travels with the config bytes, so the byte verifier decodes the
+        // Poseidon layout too.
+        let ok = verify_from_bytes::<Bn254, G, N>(
+            &config_to_bytes(&config).expect("config bytes"),
+            &instance_to_bytes(&ct_input, &ct_output, &bg_setup).expect("instance bytes"),
+            &proof_to_bytes(&proof).expect("proof bytes"),
+        )
+        .expect("well-formed bytes");
+        assert!(ok, "Poseidon-committed proof should verify from bytes");
+    }
+
+    #[test]
+    fn test_groth16_backend_proves_and_verifies_through_trait() {
+        const N: usize = 8;
+        const LEVELS: usize = 3;
+
+        let mut rng = StdRng::seed_from_u64(20240611);
+        let generator = G::generator();
+        let pk = generator * ScalarField::rand(&mut rng);
+        let (ct_input, _rand) =
+            crate::shuffling::generate_random_ciphertexts::<G, N>(&pk, &mut rng);
+        let nonce: BaseField = BaseField::rand(&mut rng);
+
+        let ns = run_rs_shuffle_permutation::<BaseField, ElGamalCiphertext<G>, N, LEVELS>(
+            nonce, &ct_input,
+        )
+        .num_samples;
+        let circuit = crate::shuffling::permutation_proof::keygen_circuit::<G, GVar, N, LEVELS>(
+            ns,
+            DeckCommitmentScheme::Pedersen,
+        );
+        let keys = Groth16Backend::<Bn254>::setup(circuit, &mut rng).expect("backend setup");
+        let mut config: ShufflingConfig<Bn254, G, Groth16Backend<Bn254>> = ShufflingConfig {
+            generator,
+            public_key: pk,
+            perm_snark_keys: Default::default(),
+            deck_commitment: DeckCommitmentScheme::Pedersen,
+        };
+        config.perm_snark_keys.insert(ns, keys);
+
+        let (ct_output, proof, bg_setup) =
+            prove_shuffling_with_backend::<Bn254, G, GVar, Groth16Backend<Bn254>, N, LEVELS>(
+                &config, &ct_input, nonce, &mut rng,
+            )
+            .expect("prove through backend");
+
+        let ok = verify_shuffling_with_backend::<Bn254, G, Groth16Backend<Bn254>, N>(
+            &config, &ct_input, &ct_output, &proof, &bg_setup,
+        )
+        .expect("verify through backend");
+        assert!(ok, "Groth16 backend proof should verify");
+    }
+
+    #[test]
+    fn test_verify_shuffling_detailed_reports_failure_reasons() {
+        let _guard = setup_test_tracing();
+        const N: usize = 8;
+        const LEVELS: usize = 3;
+
+        let mut rng = StdRng::seed_from_u64(987654321);
+        let generator = G::generator();
+        let pk = generator * ScalarField::rand(&mut rng);
+        let mut config: ShufflingConfig<Bn254, G> = ShufflingConfig {
+            generator,
+            public_key: pk,
+            perm_snark_keys: Default::default(),
+            deck_commitment: DeckCommitmentScheme::Pedersen,
+        };
+        let (ct_input, _rand) =
+            crate::shuffling::generate_random_ciphertexts::<G, N>(&pk, &mut rng);
+        let nonce: BaseField = BaseField::rand(&mut rng);
+
+        let ns = run_rs_shuffle_permutation::<BaseField, ElGamalCiphertext<G>, N, LEVELS>(
+            nonce, &ct_input,
+        )
+        .num_samples;
+        let perm_sys = crate::shuffling::permutation_proof::proof_system::PermutationGroth16::<
+            Bn254,
+            G,
+            GVar,
+            N,
+            LEVELS,
+        >::setup(&mut rng, ns)
+        .expect("perm setup");
+        config.perm_snark_keys.insert(
+            ns,
+            (
+                perm_sys.proving_key().clone(),
+                perm_sys.prepared_vk().clone(),
+            ),
+        );
+
+        let (ct_output, mut proof, bg_setup) =
+            prove_shuffling::<Bn254, G, GVar, N, LEVELS>(&config, &ct_input, nonce, &mut rng)
+                .expect("prove_shuffling");
+
+        verify_shuffling_detailed::<Bn254, G, N>(&config, &ct_input, &ct_output, &proof, &bg_setup)
+            .expect("honest shuffle should verify");
+
+        // Truncated output deck
+        let err = verify_shuffling_detailed::<Bn254, G, N>(
+            &config,
+            &ct_input,
+            &ct_output[..N - 1],
+            &proof,
+            &bg_setup,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ShuffleVerifyError::DeckLengthMismatch {
+                expected: N,
+                input: N,
+                output: 7
+            }
+        ));
+
+        // BG setup whose c_perm differs from the one the SNARK was proven for
+        let mut wrong_setup = bg_setup.clone();
+        wrong_setup.permutation_commitment += generator;
+        let err = verify_shuffling_detailed::<Bn254, G, N>(
+            &config,
+            &ct_input,
+            &ct_output,
+            &proof,
+            &wrong_setup,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ShuffleVerifyError::PublicInputMismatch { which: "c_perm" }
+        ));
+
+        // Opening proof that no longer opens c_power
+        proof.power_opening_proof.a_final += ScalarField::from(1u64);
+        let err = verify_shuffling_detailed::<Bn254, G, N>(
+            &config, &ct_input, &ct_output, &proof, &bg_setup,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ShuffleVerifyError::CommitmentMismatch(_)));
+        proof.power_opening_proof.a_final -= ScalarField::from(1u64);
+
+        // Tampered nonce input breaks the SNARK but none of the bound inputs
+        let nonce_index = G::zero().to_field_elements().expect("point elems").len();
+        proof.perm_snark_public_inputs[nonce_index] += BaseField::from(1u64);
+        let err = verify_shuffling_detailed::<Bn254, G, N>(
+            &config, &ct_input, &ct_output, &proof, &bg_setup,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ShuffleVerifyError::ProofInvalid {
+                which: "permutation SNARK"
+            }
+        ));
+        proof.perm_snark_public_inputs[nonce_index] -= BaseField::from(1u64);
+
+        // Σ‑protocol response tampering
+        proof.reencryption_proof.sigma_response_rerand += ScalarField::from(1u64);
+        let err = verify_shuffling_detailed::<Bn254, G, N>(
+            &config, &ct_input, &ct_output, &proof, &bg_setup,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ShuffleVerifyError::ProofInvalid {
+                which: "re-encryption Σ-protocol"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_verify_from_bytes_round_trip_and_truncation() {
+        const N: usize = 8;
+        const LEVELS: usize = 3;
+
+        let mut rng = StdRng::seed_from_u64(55555);
+        let generator = G::generator();
+        let pk = generator * ScalarField::rand(&mut rng);
+        let mut config: ShufflingConfig<Bn254, G> = ShufflingConfig {
+            generator,
+            public_key: pk,
+            perm_snark_keys: Default::default(),
+            deck_commitment: DeckCommitmentScheme::Pedersen,
+        };
+        let (ct_input, _rand) =
+            crate::shuffling::generate_random_ciphertexts::<G, N>(&pk, &mut rng);
+        let nonce: BaseField = BaseField::rand(&mut rng);
+
+        let ns = run_rs_shuffle_permutation::<BaseField, ElGamalCiphertext<G>, N, LEVELS>(
+            nonce, &ct_input,
+        )
+        .num_samples;
+        let perm_sys = crate::shuffling::permutation_proof::proof_system::PermutationGroth16::<
+            Bn254,
+            G,
+            GVar,
+            N,
+            LEVELS,
+        >::setup(&mut rng, ns)
+        .expect("perm setup");
+        config.perm_snark_keys.insert(
+            ns,
+            (
+                perm_sys.proving_key().clone(),
+                perm_sys.prepared_vk().clone(),
+            ),
+        );
+
+        let (ct_output, proof, bg_setup) =
+            prove_shuffling::<Bn254, G, GVar, N, LEVELS>(&config, &ct_input, nonce, &mut rng)
+                .expect("prove_shuffling");
+
+        let config_bytes = config_to_bytes(&config).expect("config bytes");
+        let instance_bytes =
+            instance_to_bytes(&ct_input, &ct_output, &bg_setup).expect("instance bytes");
+        let proof_bytes = proof_to_bytes(&proof).expect("proof bytes");
+
+        let ok = verify_from_bytes::<Bn254, G, N>(&config_bytes, &instance_bytes, &proof_bytes)
+            .expect("well-formed bytes");
+        assert!(ok, "round-tripped proof should verify");
+
+        // Swapped decks decode fine but do not verify
+        let swapped = instance_to_bytes(&ct_output, &ct_input, &bg_setup).expect("bytes");
+        let ok = verify_from_bytes::<Bn254, G, N>(&config_bytes, &swapped, &proof_bytes)
+            .expect("well-formed bytes");
+        assert!(!ok, "proof for another instance should be rejected");
+
+        // A power challenge scalar that is not the conversion of the base challenge
+        let mut forged_setup = bg_setup.clone();
+        forged_setup.power_challenge_scalar += ScalarField::from(1u64);
+        let forged = instance_to_bytes(&ct_input, &ct_output, &forged_setup).expect("bytes");
+        let ok = verify_from_bytes::<Bn254, G, N>(&config_bytes, &forged, &proof_bytes)
+            .expect("well-formed bytes");
+        assert!(!ok, "forged power challenge scalar should be rejected");
+
+        let mut unknown_scheme = config_bytes.clone();
+        *unknown_scheme.last_mut().expect("scheme tag") = u8::MAX;
+        let err = verify_from_bytes::<Bn254, G, N>(&unknown_scheme, &instance_bytes, &proof_bytes)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyFromBytesError::Malformed {
+                which: "config",
+                ..
+            }
+        ));
+
+        let truncated = &proof_bytes[..proof_bytes.len() - 1];
+        let err = verify_from_bytes::<Bn254, G, N>(&config_bytes, &instance_bytes, truncated)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyFromBytesError::Malformed { which: "proof", .. }
+        ));
+    }
+
     // ------------------------------------------------------------------------
     // Generic test runner to exercise prove/verify for arbitrary size/curve
     // ------------------------------------------------------------------------
@@ -529,7 +1278,7 @@ mod tests {
         rng_seed: u64,
     ) where
         E: ark_ec::pairing::Pairing<ScalarField = GCurve::BaseField>,
-        GCurve: CurveGroup
+        GCurve: PointFromCoordinates
             + CurveAbsorb<GCurve::BaseField>
             + ark_ff::ToConstraintField<GCurve::BaseField>,
         GCurve::Config: CurveConfig,
@@ -550,6 +1299,7 @@ mod tests {
             generator,
             public_key: pk,
             perm_snark_keys: Default::default(),
+            deck_commitment: DeckCommitmentScheme::Pedersen,
         };
 
         // Input deck (standardized helper for card games)
@@ -17,5 +17,3 @@ pub fn generate_random_values<F: Absorb + PrimeField>(seed: F, count: usize) ->
 
     values
 }
-
-// generate_chaum_pedersen_witness moved to crate::chaum_pedersen::generate_chaum_pedersen_witness
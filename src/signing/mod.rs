@@ -16,20 +16,31 @@ pub trait DomainSeparated {
 
 /// Compute canonical signing bytes for a value.
 ///
-/// This function serializes a value using arkworks' `CanonicalSerialize`
-/// trait, which provides deterministic byte encoding suitable for
-/// cryptographic signatures.
+/// The bytes are the value's [`DomainSeparated::domain_string`], length-prefixed, followed by
+/// its compressed canonical serialization (arkworks' `CanonicalSerialize`). Prefixing the domain
+/// means a signature over one message type never verifies as another type whose serialization
+/// happens to coincide.
 ///
 /// # Arguments
 /// * `value` - The value to serialize
 ///
 /// # Returns
-/// A vector of compressed canonical bytes suitable for signing
+/// A vector of domain-separated canonical bytes suitable for signing
 pub fn signing_bytes<T>(value: &T) -> Result<Vec<u8>>
 where
     T: CanonicalSerialize + DomainSeparated,
 {
-    let mut bytes = Vec::new();
+    domain_separated_bytes(T::domain_string(), value)
+}
+
+/// Signing bytes for `value` under an explicit `domain`; see [`signing_bytes`].
+pub fn domain_separated_bytes<T>(domain: &str, value: &T) -> Result<Vec<u8>>
+where
+    T: CanonicalSerialize,
+{
+    let mut bytes = Vec::with_capacity(4 + domain.len() + value.compressed_size());
+    bytes.extend_from_slice(&(domain.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(domain.as_bytes());
     value
         .serialize_compressed(&mut bytes)
         .map_err(|e| anyhow::anyhow!("canonical serialization failed: {}", e))?;
@@ -194,6 +205,55 @@ where
     }
 }
 
+impl<Sig, T> CanonicalSerialize for WithSignature<Sig, T>
+where
+    Sig: SignatureBytes,
+    T: CanonicalSerialize + DomainSeparated,
+{
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        self.value.serialize_with_mode(&mut writer, compress)?;
+        self.signature
+            .to_bytes()
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        self.value.serialized_size(compress) + self.signature.to_bytes().serialized_size(compress)
+    }
+}
+
+impl<Sig, T> CanonicalDeserialize for WithSignature<Sig, T>
+where
+    Sig: SignatureBytes + Sync,
+    T: CanonicalSerialize + CanonicalDeserialize + DomainSeparated,
+{
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let value = T::deserialize_with_mode(&mut reader, compress, validate)?;
+        let sig_bytes = Vec::<u8>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let signature = Sig::from_bytes(&sig_bytes)
+            .map_err(|_| ark_serialize::SerializationError::InvalidData)?;
+        Ok(WithSignature { value, signature })
+    }
+}
+
+impl<Sig, T> ark_serialize::Valid for WithSignature<Sig, T>
+where
+    Sig: SignatureBytes + Sync,
+    T: CanonicalSerialize + CanonicalDeserialize + DomainSeparated,
+{
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        self.value.check()
+    }
+}
+
 impl<Sig, T> WithSignature<Sig, T>
 where
     Sig: SignatureBytes,
@@ -237,3 +297,93 @@ where
             .map_err(|e| anyhow::anyhow!("signature error: {e}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chaum_pedersen::ChaumPedersenProof;
+    use crate::engine::nl::{HandConfig, PlayerBetAction};
+    use crate::ledger::actor::{AnyActor, PlayerActor, ShufflerActor};
+    use crate::ledger::identity::CanonicalKey;
+    use crate::ledger::messages::{
+        AnyGameMessage, GameBlindingDecryptionMessage, GameNonceCommitmentMessage,
+        GamePartialUnblindingShareMessage, GamePlayerMessage, GameShowdownMessage,
+        GameShuffleMessage, MuckDeclaration, PreflopStreet, ReconnectRequest,
+    };
+    use crate::ledger::snapshot::{PlayerIdentity, PlayerStackInfo, ShufflerIdentity};
+    use crate::player::PlayerActionBet;
+    use crate::shuffling::data_structures::{ElGamalCiphertext, ShuffleProof};
+    use crate::shuffling::deck_encoding::DeckEncodingProof;
+    use crate::shuffling::player_decryption::{
+        PartialUnblindingShare, PlayerAccessibleCiphertext, PlayerTargetedBlindingContribution,
+    };
+    use ark_crypto_primitives::signature::schnorr::Schnorr;
+    use ark_grumpkin::Projective as C;
+    use rand::{rngs::StdRng, SeedableRng};
+    use sha2::Sha256;
+    use std::collections::HashSet;
+
+    type Scheme = Schnorr<C, Sha256>;
+
+    /// Every signable type in the crate. Add new `DomainSeparated` impls here.
+    fn signing_domains() -> Vec<&'static str> {
+        vec![
+            HandConfig::domain_string(),
+            GamePlayerMessage::<PreflopStreet, C>::domain_string(),
+            GameShuffleMessage::<C>::domain_string(),
+            GameNonceCommitmentMessage::<C>::domain_string(),
+            GameBlindingDecryptionMessage::<C>::domain_string(),
+            GamePartialUnblindingShareMessage::<C>::domain_string(),
+            GameShowdownMessage::<C>::domain_string(),
+            MuckDeclaration::<C>::domain_string(),
+            ReconnectRequest::<C>::domain_string(),
+            AnyGameMessage::<C>::domain_string(),
+            CanonicalKey::<C>::domain_string(),
+            PlayerIdentity::<C>::domain_string(),
+            ShufflerIdentity::<C>::domain_string(),
+            PlayerStackInfo::<C>::domain_string(),
+            PlayerActor::<C>::domain_string(),
+            ShufflerActor::<C>::domain_string(),
+            AnyActor::<C>::domain_string(),
+            PlayerTargetedBlindingContribution::<C>::domain_string(),
+            PlayerAccessibleCiphertext::<C>::domain_string(),
+            PartialUnblindingShare::<C>::domain_string(),
+            ElGamalCiphertext::<C>::domain_string(),
+            ShuffleProof::<C>::domain_string(),
+            DeckEncodingProof::<C>::domain_string(),
+            PlayerActionBet::domain_string(),
+            ChaumPedersenProof::<C>::domain_string(),
+        ]
+    }
+
+    #[test]
+    fn signing_domains_are_unique() {
+        let domains = signing_domains();
+        let unique: HashSet<_> = domains.iter().collect();
+        assert_eq!(
+            unique.len(),
+            domains.len(),
+            "duplicate domain in {domains:?}"
+        );
+    }
+
+    #[test]
+    fn betting_signature_does_not_verify_as_shuffle_message() -> Result<()> {
+        let mut rng = StdRng::from_seed([3u8; 32]);
+        let params = Scheme::setup(&mut rng).expect("setup");
+        let (pk, sk) = Scheme::keygen(&params, &mut rng).expect("keygen");
+        let bet = PlayerActionBet {
+            seat: 2,
+            action: PlayerBetAction::Call,
+            nonce: 9,
+        };
+        let signed = WithSignature::new::<Scheme, _>(bet, &params, &sk, &mut rng)?;
+        assert!(signed.verify::<Scheme>(&params, &pk)?);
+
+        // The same payload bytes, presented under the shuffle message domain.
+        let as_shuffle =
+            domain_separated_bytes(GameShuffleMessage::<C>::domain_string(), &signed.value)?;
+        assert!(!Scheme::verify(&params, &pk, &as_shuffle, &signed.signature).expect("verify"));
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
+use std::collections::HashMap;
 use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
 use tokio::task::JoinHandle;
 
+use crate::ledger::types::HandId;
+
 /// Spawn a Tokio task with a stable name when supported, and trace span otherwise.
 pub fn spawn_named_task<F, S>(name: S, future: F) -> JoinHandle<F::Output>
 where
@@ -21,3 +30,121 @@ where
         tokio::spawn(future.instrument(span))
     }
 }
+
+/// A task that is still running, as recorded by [`TaskRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TaskInfo {
+    pub name: String,
+    pub hand_id: Option<HandId>,
+    pub spawned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct RegistryInner {
+    next_id: AtomicU64,
+    live: Mutex<HashMap<u64, TaskInfo>>,
+}
+
+/// Tracks the named tasks that are currently running, so a stalled hand can be diagnosed by
+/// listing what is still alive for it. Clones share the same set.
+#[derive(Clone, Debug, Default)]
+pub struct TaskRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+static GLOBAL_TASKS: Lazy<TaskRegistry> = Lazy::new(TaskRegistry::default);
+
+impl TaskRegistry {
+    /// The process-wide registry used for per-hand tasks.
+    pub fn global() -> &'static TaskRegistry {
+        &GLOBAL_TASKS
+    }
+
+    /// Spawn `future` via [`spawn_named_task`] and keep it listed until it completes or is
+    /// aborted.
+    pub fn spawn<F, S>(&self, name: S, hand_id: Option<HandId>, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+        S: Into<String>,
+    {
+        let name = name.into();
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        self.inner.live.lock().insert(
+            id,
+            TaskInfo {
+                name: name.clone(),
+                hand_id,
+                spawned_at: Utc::now(),
+            },
+        );
+        let entry = LiveEntry {
+            registry: Arc::clone(&self.inner),
+            id,
+        };
+        spawn_named_task(name, async move {
+            let _entry = entry;
+            future.await
+        })
+    }
+
+    /// Live tasks, oldest first.
+    pub fn snapshot(&self) -> Vec<TaskInfo> {
+        let mut tasks: Vec<_> = self.inner.live.lock().values().cloned().collect();
+        tasks.sort_by(|a, b| a.spawned_at.cmp(&b.spawned_at).then(a.name.cmp(&b.name)));
+        tasks
+    }
+
+    /// Live tasks spawned for `hand_id`, oldest first.
+    pub fn snapshot_for_hand(&self, hand_id: HandId) -> Vec<TaskInfo> {
+        self.snapshot()
+            .into_iter()
+            .filter(|task| task.hand_id == Some(hand_id))
+            .collect()
+    }
+}
+
+/// Removes a task's entry when its future is dropped, whether it finished or was aborted.
+struct LiveEntry {
+    registry: Arc<RegistryInner>,
+    id: u64,
+}
+
+impl Drop for LiveEntry {
+    fn drop(&mut self) {
+        self.registry.live.lock().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn snapshot_lists_only_running_tasks() {
+        let registry = TaskRegistry::default();
+        let (finish_first, first_gate) = oneshot::channel::<()>();
+        let (finish_second, second_gate) = oneshot::channel::<()>();
+
+        let first = registry.spawn("dealing-worker", Some(7), async move {
+            let _ = first_gate.await;
+        });
+        let _second = registry.spawn("shuffle", Some(8), async move {
+            let _ = second_gate.await;
+        });
+        assert_eq!(registry.snapshot().len(), 2);
+
+        finish_first.send(()).expect("first task is waiting");
+        first.await.expect("first task completes");
+
+        let live = registry.snapshot();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].name, "shuffle");
+        assert_eq!(live[0].hand_id, Some(8));
+        assert!(registry.snapshot_for_hand(7).is_empty());
+        assert_eq!(registry.snapshot_for_hand(8), live);
+
+        drop(finish_second);
+    }
+}
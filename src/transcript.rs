@@ -0,0 +1,98 @@
+//! Fiat-Shamir transcripts shared by the sigma protocols and the shuffle argument.
+//!
+//! [`Transcript`] is implemented for every [`CryptographicSponge`], so code that already threads
+//! a `&mut RO` keeps its signature and only swaps raw `absorb`/`squeeze` calls for typed ones.
+//! Points always go through [`CurveAbsorb`], which is what the circuits absorb, and challenges
+//! destined for a scalar field are reduced the same way the circuits reduce them.
+
+use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge};
+use ark_ff::PrimeField;
+
+use crate::curve_absorb::CurveAbsorb;
+use crate::field_conversion::convert_mod;
+use crate::poseidon_config;
+
+/// A domain-tagged sponge with typed absorb and challenge methods.
+pub trait Transcript: CryptographicSponge + Sized {
+    /// A fresh sponge whose first absorption is `domain`.
+    fn tagged(config: &Self::Config, domain: &impl Absorb) -> Self {
+        let mut sponge = Self::new(config);
+        sponge.absorb(domain);
+        sponge
+    }
+
+    /// Absorb a curve point exactly as the matching gadget does.
+    fn absorb_point<F, C>(&mut self, point: &C)
+    where
+        F: PrimeField,
+        C: CurveAbsorb<F, Self>,
+    {
+        point.curve_absorb(self);
+    }
+
+    /// Absorb a field element, or anything else with a canonical sponge encoding.
+    fn absorb_scalar(&mut self, value: &impl Absorb) {
+        self.absorb(value);
+    }
+
+    /// Squeeze one challenge in the sponge's native field.
+    fn challenge<F: PrimeField>(&mut self) -> F {
+        self.squeeze_field_elements::<F>(1)[0]
+    }
+
+    /// Squeeze `n` challenges in the sponge's native field.
+    fn challenges<F: PrimeField>(&mut self, n: usize) -> Vec<F> {
+        self.squeeze_field_elements::<F>(n)
+    }
+
+    /// Squeeze one challenge in `F` and reduce it into `S` by its little-endian bytes.
+    fn challenge_scalar<F: PrimeField, S: PrimeField>(&mut self) -> S {
+        convert_mod(self.challenge::<F>())
+    }
+}
+
+impl<RO: CryptographicSponge> Transcript for RO {}
+
+/// A Poseidon transcript over `F` tagged with `domain`, using the crate-wide parameters.
+pub fn poseidon_transcript<F: PrimeField>(domain: &impl Absorb) -> PoseidonSponge<F> {
+    PoseidonSponge::tagged(&poseidon_config::<F>(), domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::PrimeGroup;
+    use ark_ff::UniformRand;
+    use ark_grumpkin::{Fq, Fr, Projective as Curve};
+    use ark_std::test_rng;
+
+    #[test]
+    fn typed_calls_match_the_raw_sponge() {
+        let mut rng = test_rng();
+        let point = Curve::generator() * Fr::rand(&mut rng);
+        let scalar = Fq::rand(&mut rng);
+        let domain: &[u8] = b"legit_poker/transcript_test";
+
+        let mut transcript = poseidon_transcript::<Fq>(&domain);
+        transcript.absorb_point(&point);
+        transcript.absorb_scalar(&scalar);
+
+        let mut sponge = PoseidonSponge::new(&poseidon_config::<Fq>());
+        sponge.absorb(&domain);
+        point.curve_absorb(&mut sponge);
+        sponge.absorb(&scalar);
+
+        assert_eq!(
+            transcript.challenge::<Fq>(),
+            sponge.squeeze_field_elements::<Fq>(1)[0]
+        );
+        assert_eq!(
+            transcript.challenge_scalar::<Fq, Fr>(),
+            convert_mod::<Fq, Fr>(sponge.squeeze_field_elements(1)[0])
+        );
+        assert_eq!(
+            transcript.challenges::<Fq>(2),
+            sponge.squeeze_field_elements::<Fq>(2)
+        );
+    }
+}
@@ -1,8 +1,12 @@
 //! VRF gadgets for SNARK circuits
 
+use super::hash_to_curve::{
+    canonical_root, point_from_coordinates, quadratic_non_residue, sw_coefficients,
+    HashToCurveMethod, TRY_AND_INCREMENT_ATTEMPTS,
+};
 use super::{
-    cofactor::mul_by_cofactor_const, dst_beta_digest, dst_challenge_digest, dst_nonce_digest,
-    VrfParams, VrfPedersenWindow, VrfProof,
+    cofactor::mul_by_cofactor_const, dst_beta_digest, dst_challenge_digest,
+    dst_hash_to_curve_digest, dst_nonce_digest, VrfParams, VrfPedersenWindow, VrfProof,
 };
 use crate::field_conversion::{base_to_scalar_with_bits, scalar_to_base_field_elements_gadget};
 use crate::shuffling::curve_absorb::CurveAbsorbGadget;
@@ -18,6 +22,8 @@ use ark_ff::{Field, PrimeField};
 use ark_r1cs_std::{
     alloc::{AllocVar, AllocationMode},
     boolean::Boolean,
+    convert::ToConstraintFieldGadget,
+    eq::EqGadget,
     fields::{emulated_fp::EmulatedFpVar, fp::FpVar, FieldVar},
     groups::CurveVar,
     prelude::ToBitsGadget,
@@ -109,6 +115,81 @@ where
     Ok(h)
 }
 
+/// Hash message to curve point in circuit using try-and-increment, matching
+/// [`TryAndIncrement`](super::hash_to_curve::TryAndIncrement)
+///
+/// Every candidate x is resolved: the prover supplies a root of either `x^3 + a*x + b` or, when
+/// that is not a square, of its product with a fixed non-residue. The point is therefore pinned
+/// to the first candidate on the curve, and its y to the root no larger than `(p - 1) / 2`.
+pub fn try_and_increment_var<C, GG, RO, ROVar>(
+    cs: ConstraintSystemRef<ConstraintF<C>>,
+    sponge_params: &ROVar::Parameters,
+    msg_bytes: &[UInt8<ConstraintF<C>>],
+) -> Result<GG, SynthesisError>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, ConstraintF<C>> + ToConstraintFieldGadget<ConstraintF<C>>,
+    ConstraintF<C>: PrimeField + Absorb,
+    RO: CryptographicSponge,
+    ROVar: CryptographicSpongeVar<ConstraintF<C>, RO>,
+{
+    let (coeff_a, coeff_b) = sw_coefficients::<C>();
+    let non_residue = quadratic_non_residue::<ConstraintF<C>>();
+
+    let mut sponge = ROVar::new(cs.clone(), sponge_params);
+
+    // Use precomputed DST digest as constant (no constraints)
+    let dst_var = FpVar::constant(dst_hash_to_curve_digest::<ConstraintF<C>>());
+    sponge.absorb(&dst_var)?;
+
+    // Absorb message bytes one field element each, as the native map does
+    for byte in msg_bytes {
+        sponge.absorb(&Boolean::le_bits_to_fp(&byte.to_bits_le()?)?)?;
+    }
+    let candidates = sponge.squeeze_field_elements(TRY_AND_INCREMENT_ATTEMPTS)?;
+
+    let mut found = Boolean::FALSE;
+    let mut x = FpVar::zero();
+    let mut y = FpVar::zero();
+    for candidate in candidates {
+        let rhs = candidate.square()? * &candidate + &candidate * coeff_a + coeff_b;
+        let rhs_value = rhs.value().ok();
+
+        let is_square = Boolean::new_witness(cs.clone(), || {
+            rhs_value
+                .map(|v| v.sqrt().is_some())
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let root = FpVar::new_witness(cs.clone(), || {
+            let v = rhs_value.ok_or(SynthesisError::AssignmentMissing)?;
+            Ok(match v.sqrt() {
+                Some(root) => canonical_root(root),
+                None => (v * non_residue)
+                    .sqrt()
+                    .expect("a non-residue times a non-residue is a square"),
+            })
+        })?;
+        root.square()?
+            .enforce_equal(&is_square.select(&rhs, &(&rhs * non_residue))?)?;
+
+        let take = &is_square & &!found.clone();
+        x = take.select(&candidate, &x)?;
+        y = take.select(&root, &y)?;
+        found = &found | &is_square;
+    }
+    found.enforce_equal(&Boolean::TRUE)?;
+    y.enforce_smaller_or_equal_than_mod_minus_one_div_two()?;
+
+    let point = GG::new_witness(cs, || {
+        point_from_coordinates::<C>(x.value()?, y.value()?).ok_or(SynthesisError::Unsatisfiable)
+    })?;
+    let coordinates = point.to_constraint_field()?;
+    coordinates[0].enforce_equal(&x)?;
+    coordinates[1].enforce_equal(&y)?;
+
+    Ok(point)
+}
+
 /// Generate deterministic nonce k in circuit
 pub fn generate_nonce_var<C, GG, RO, ROVar>(
     cs: ConstraintSystemRef<ConstraintF<C>>,
@@ -255,7 +336,7 @@ where
 ///
 /// # Arguments
 /// * `cs` - Constraint system reference
-/// * `params` - VRF parameters (Pedersen CRH setup)
+/// * `params` - VRF parameters (Pedersen CRH setup and hash-to-curve map)
 /// * `sponge_params` - Sponge parameters for the transcript
 /// * `msg_bytes` - VRF input message (as byte variables)
 /// * `x_wit` - Secret key witness (as non-native field variable)
@@ -279,22 +360,30 @@ pub fn prove_vrf_gadget<C, GG, RO, ROVar>(
 >
 where
     C: CurveGroup,
-    GG: CurveVar<C, ConstraintF<C>> + CurveAbsorbGadget<ConstraintF<C>, ROVar>,
+    GG: CurveVar<C, ConstraintF<C>>
+        + CurveAbsorbGadget<ConstraintF<C>, ROVar>
+        + ToConstraintFieldGadget<ConstraintF<C>>,
     ConstraintF<C>: PrimeField + Absorb,
     C::ScalarField: PrimeField,
     RO: CryptographicSponge,
     ROVar: CryptographicSpongeVar<ConstraintF<C>, RO>,
     for<'a> &'a GG: ark_r1cs_std::groups::GroupOpsBounds<'a, C, GG>,
 {
-    // Allocate Pedersen parameters as constants
-    let pedersen_params_var = PedersenCRHParamsVar::<C, GG>::new_constant(
-        cs.clone(),
-        params.pedersen_crh_params.clone(),
-    )?;
-
     // 1. H = HashToCurve(msg)
     let h = track_constraints!(&cs, "hash_to_curve", LOG_TARGET, {
-        hash_to_curve_var::<C, GG>(&pedersen_params_var, msg_bytes)?
+        match params.hash_to_curve {
+            HashToCurveMethod::Pedersen => {
+                // Allocate Pedersen parameters as constants
+                let pedersen_params_var = PedersenCRHParamsVar::<C, GG>::new_constant(
+                    cs.clone(),
+                    params.pedersen_crh_params.clone(),
+                )?;
+                hash_to_curve_var::<C, GG>(&pedersen_params_var, msg_bytes)?
+            }
+            HashToCurveMethod::TryAndIncrement => {
+                try_and_increment_var::<C, GG, RO, ROVar>(cs.clone(), sponge_params, msg_bytes)?
+            }
+        }
     });
     tracing::debug!(target: LOG_TARGET, "Hash to curve in SNARK: {:?}", h.value());
 
@@ -0,0 +1,173 @@
+//! Hash-to-curve maps for `H = HashToCurve(msg)`.
+//!
+//! The VRF defaults to a Pedersen CRH over [`VrfPedersenWindow`], which is cheap in circuit but
+//! depends on the generators in [`VrfParams`](super::VrfParams). [`TryAndIncrement`] needs no
+//! setup and follows the try-and-increment construction used by standardized VRFs (RFC 9381
+//! style), with Poseidon in place of SHA so the circuit can recompute it. The map is chosen per
+//! `VrfParams` through
+//! [`HashToCurveMethod`]; the gadget counterpart is
+//! [`try_and_increment_var`](super::gadgets::try_and_increment_var).
+
+use super::{dst_hash_to_curve_digest, VrfError, VrfPedersenWindow};
+use crate::transcript::{poseidon_transcript, Transcript};
+use ark_crypto_primitives::crh::{pedersen, CRHScheme};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+
+type ConstraintF<C> = <<C as CurveGroup>::BaseField as Field>::BasePrimeField;
+
+/// Candidate x-coordinates squeezed per message. Each is on the curve with probability about
+/// one half, so running out is a 2^-32 event.
+pub const TRY_AND_INCREMENT_ATTEMPTS: usize = 32;
+
+/// Which hash-to-curve map a [`VrfParams`](super::VrfParams) uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashToCurveMethod {
+    /// Pedersen CRH over the parameters' generators, then cofactor clearing.
+    #[default]
+    Pedersen,
+    /// Poseidon-derived x-coordinates tried in order until one lies on the curve.
+    TryAndIncrement,
+}
+
+/// Maps a message to a curve point in the prime-order subgroup.
+pub trait HashToCurve<C: CurveGroup> {
+    fn hash_to_curve(&self, msg: &[u8]) -> Result<C, VrfError>;
+}
+
+/// Pedersen CRH + cofactor clearing.
+#[derive(Clone, Copy, Debug)]
+pub struct PedersenHashToCurve<'a, C: CurveGroup> {
+    pub params: &'a pedersen::Parameters<C>,
+}
+
+impl<'a, C: CurveGroup> PedersenHashToCurve<'a, C> {
+    pub fn new(params: &'a pedersen::Parameters<C>) -> Self {
+        Self { params }
+    }
+}
+
+impl<C: CurveGroup> HashToCurve<C> for PedersenHashToCurve<'_, C> {
+    fn hash_to_curve(&self, msg: &[u8]) -> Result<C, VrfError> {
+        let p = pedersen::CRH::<C, VrfPedersenWindow>::evaluate(self.params, msg)
+            .map_err(|err| VrfError::PedersenHash(err.to_string()))?;
+
+        // Cofactor clear (e.g., 1 for Grumpkin, 8 for Jubjub, 4 for Bandersnatch)
+        // This ensures the point is in the prime-order subgroup
+        Ok(p.mul_by_cofactor().into())
+    }
+}
+
+/// Try-and-increment over a short Weierstrass curve `y^2 = x^3 + a*x + b`.
+///
+/// A Poseidon sponge seeded with [`DST_HASH_TO_CURVE`](super::DST_HASH_TO_CURVE) absorbs the
+/// message one byte per field element and squeezes [`TRY_AND_INCREMENT_ATTEMPTS`] candidate
+/// x-coordinates. The first candidate with `x^3 + a*x + b` a square wins, and of its two roots
+/// the one no larger than `(p - 1) / 2` is taken as y.
+///
+/// Only meant for prime-order short Weierstrass curves over a prime field (Grumpkin, BN254 G1):
+/// the curve coefficients are recovered from the generator, no cofactor is cleared, and the
+/// circuit relies on `x^3 + a*x + b` never being zero, which holds without points of order two.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TryAndIncrement;
+
+impl<C> HashToCurve<C> for TryAndIncrement
+where
+    C: CurveGroup,
+    ConstraintF<C>: PrimeField + Absorb,
+{
+    fn hash_to_curve(&self, msg: &[u8]) -> Result<C, VrfError> {
+        try_and_increment_coordinates::<C>(msg)
+            .and_then(|(x, y)| point_from_coordinates::<C>(x, y))
+            .ok_or(VrfError::HashToCurveExhausted)
+    }
+}
+
+/// The `(x, y)` chosen by [`TryAndIncrement`], or `None` if every candidate missed the curve.
+pub fn try_and_increment_coordinates<C>(msg: &[u8]) -> Option<(ConstraintF<C>, ConstraintF<C>)>
+where
+    C: CurveGroup,
+    ConstraintF<C>: PrimeField + Absorb,
+{
+    let (a, b) = sw_coefficients::<C>();
+    let mut transcript =
+        poseidon_transcript::<ConstraintF<C>>(&dst_hash_to_curve_digest::<ConstraintF<C>>());
+    for byte in msg {
+        transcript.absorb_scalar(&ConstraintF::<C>::from(*byte as u64));
+    }
+    transcript
+        .challenges::<ConstraintF<C>>(TRY_AND_INCREMENT_ATTEMPTS)
+        .into_iter()
+        .find_map(|x| {
+            let y = (x.square() * x + a * x + b).sqrt()?;
+            Some((x, canonical_root(y)))
+        })
+}
+
+/// The coefficients `(a, b)` of `y^2 = x^3 + a*x + b`, solved from G and 2G.
+pub(crate) fn sw_coefficients<C>() -> (ConstraintF<C>, ConstraintF<C>)
+where
+    C: CurveGroup,
+    ConstraintF<C>: PrimeField,
+{
+    let coordinates = |p: C| {
+        let (x, y) = p
+            .into_affine()
+            .xy()
+            .expect("multiples of the generator are finite");
+        (prime_coordinate::<C>(x), prime_coordinate::<C>(y))
+    };
+    let (x1, y1) = coordinates(C::generator());
+    let (x2, y2) = coordinates(C::generator() + C::generator());
+    let r1 = y1.square() - x1.square() * x1;
+    let r2 = y2.square() - x2.square() * x2;
+    let a = (r1 - r2) / (x1 - x2);
+    (a, r1 - a * x1)
+}
+
+/// The smallest quadratic non-residue greater than one, used by the circuit to show that a
+/// candidate is off the curve.
+pub(crate) fn quadratic_non_residue<F: PrimeField>() -> F {
+    let mut candidate = F::from(2u64);
+    while !candidate.legendre().is_qnr() {
+        candidate += F::one();
+    }
+    candidate
+}
+
+/// Of `y` and `-y`, the one whose canonical integer is at most `(p - 1) / 2`.
+pub(crate) fn canonical_root<F: PrimeField>(y: F) -> F {
+    if y.into_bigint() <= F::MODULUS_MINUS_ONE_DIV_TWO {
+        y
+    } else {
+        -y
+    }
+}
+
+/// Build the curve point `(x, y)`, or `None` if it is not on the curve.
+pub(crate) fn point_from_coordinates<C>(x: ConstraintF<C>, y: ConstraintF<C>) -> Option<C>
+where
+    C: CurveGroup,
+    ConstraintF<C>: PrimeField,
+{
+    let mut bytes = Vec::new();
+    x.serialize_uncompressed(&mut bytes).ok()?;
+    y.serialize_uncompressed(&mut bytes).ok()?;
+    C::Affine::deserialize_uncompressed(bytes.as_slice())
+        .ok()
+        .map(|point| point.into_group())
+}
+
+fn prime_coordinate<C>(coordinate: C::BaseField) -> ConstraintF<C>
+where
+    C: CurveGroup,
+{
+    coordinate
+        .to_base_prime_field_elements()
+        .next()
+        .expect("a base field element has at least one prime field component")
+}
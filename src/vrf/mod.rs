@@ -6,59 +6,79 @@
 
 pub mod cofactor;
 pub mod gadgets;
+pub mod hash_to_curve;
 pub mod native;
+pub mod nonce_source;
 pub mod simple;
 pub mod simple_gadgets;
 #[cfg(test)]
 mod tests;
 
 use crate::poseidon_config;
+use crate::transcript::{poseidon_transcript, Transcript};
 use ark_crypto_primitives::crh::pedersen;
-use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
-use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::CurveGroup;
-use ark_ff::PrimeField;
+use ark_ff::{AdditiveGroup, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use thiserror::Error;
 
 // Re-export main functions
-pub use native::{prove_vrf, verify_vrf};
+pub use hash_to_curve::{HashToCurve, HashToCurveMethod, PedersenHashToCurve, TryAndIncrement};
+pub use native::{prove_vrf, verify_vrf, verify_vrf_batch};
+pub use nonce_source::{
+    commit_nonce, shuffle_seed, DrandBeacon, LocalRng, NonceSource, NonceSourceError, VrfNonce,
+};
+pub use simple::{derive_seed_bits, seed_elements_needed};
 
 // Domain separation tags
 pub const DST_CHALLENGE: &[u8] = b"VRF-CHALLENGE-v1";
 pub const DST_NONCE: &[u8] = b"VRF-NONCE-v1";
 pub const DST_BETA: &[u8] = b"VRF-OUTPUT-v1";
+pub const DST_HASH_TO_CURVE: &[u8] = b"VRF-HASH-TO-CURVE-v1";
+pub const DST_PEDERSEN_GENERATOR: &[u8] = b"VRF-PEDERSEN-GENERATOR-v1";
+
+/// Errors from VRF setup, hashing and proving.
+#[derive(Debug, Error)]
+pub enum VrfError {
+    #[error("randomness source failed: {0}")]
+    Rng(String),
+    #[error("Pedersen CRH setup failed: {0}")]
+    PedersenSetup(String),
+    #[error("Pedersen hash-to-curve failed: {0}")]
+    PedersenHash(String),
+    #[error("no try-and-increment candidate for the message lies on the curve")]
+    HashToCurveExhausted,
+}
 
 /// Precomputed domain separation tag digest for challenge
 /// Used as constant in SNARKs to avoid constraint costs
 pub fn dst_challenge_digest<F: PrimeField + Absorb>() -> F {
-    let config = poseidon_config::<F>();
-    let mut sponge = PoseidonSponge::new(&config);
-    for byte in DST_CHALLENGE {
-        sponge.absorb(&F::from(*byte as u64));
-    }
-    sponge.squeeze_field_elements(1)[0]
+    dst_digest(DST_CHALLENGE)
 }
 
 /// Precomputed domain separation tag digest for nonce
 /// Used as constant in SNARKs to avoid constraint costs
 pub fn dst_nonce_digest<F: PrimeField + Absorb>() -> F {
-    let config = poseidon_config::<F>();
-    let mut sponge = PoseidonSponge::new(&config);
-    for byte in DST_NONCE {
-        sponge.absorb(&F::from(*byte as u64));
-    }
-    sponge.squeeze_field_elements(1)[0]
+    dst_digest(DST_NONCE)
 }
 
 /// Precomputed domain separation tag digest for beta output
 /// Used as constant in SNARKs to avoid constraint costs
 pub fn dst_beta_digest<F: PrimeField + Absorb>() -> F {
-    let config = poseidon_config::<F>();
-    let mut sponge = PoseidonSponge::new(&config);
-    for byte in DST_BETA {
-        sponge.absorb(&F::from(*byte as u64));
-    }
-    sponge.squeeze_field_elements(1)[0]
+    dst_digest(DST_BETA)
+}
+
+/// Precomputed domain separation tag digest for try-and-increment hash-to-curve
+/// Used as constant in SNARKs to avoid constraint costs
+pub fn dst_hash_to_curve_digest<F: PrimeField + Absorb>() -> F {
+    dst_digest(DST_HASH_TO_CURVE)
+}
+
+/// Poseidon digest of a domain separation tag. The tag is absorbed as packed bytes, so it costs
+/// one or two field elements rather than one per byte.
+fn dst_digest<F: PrimeField>(tag: &[u8]) -> F {
+    poseidon_transcript::<F>(&tag).challenge()
 }
 
 /// VRF proof containing (Γ, c, s) for Schnorr-style DLEQ proof
@@ -82,7 +102,8 @@ impl pedersen::Window for VrfPedersenWindow {
     const NUM_WINDOWS: usize = 256;
 }
 
-/// VRF parameters containing Pedersen CRH setup for hash-to-curve and sponge config
+/// VRF parameters containing Pedersen CRH setup for hash-to-curve, the hash-to-curve map in use
+/// and sponge config
 #[derive(Clone)]
 pub struct VrfParams<
     C,
@@ -95,6 +116,7 @@ pub struct VrfParams<
 {
     pub pedersen_crh_params: pedersen::Parameters<C>,
     pub sponge_params: SP,
+    pub hash_to_curve: HashToCurveMethod,
 }
 
 impl<C, SP> VrfParams<C, SP>
@@ -107,8 +129,15 @@ where
         Self {
             pedersen_crh_params,
             sponge_params,
+            hash_to_curve: HashToCurveMethod::default(),
         }
     }
+
+    /// Use `method` for `H = HashToCurve(msg)` in both the native and circuit paths
+    pub fn with_hash_to_curve(mut self, method: HashToCurveMethod) -> Self {
+        self.hash_to_curve = method;
+        self
+    }
 }
 
 impl<C>
@@ -123,15 +152,61 @@ where
     <<C as CurveGroup>::BaseField as ark_ff::Field>::BasePrimeField: PrimeField,
 {
     /// Setup VRF parameters with random Pedersen generators and default Poseidon config
-    pub fn setup<R: ark_std::rand::Rng>(rng: &mut R) -> Self {
+    ///
+    /// Only a 32-byte seed is read from `rng`, through its fallible interface; the generators
+    /// are sampled from a generator seeded with it. A failing source therefore surfaces as
+    /// [`VrfError::Rng`] instead of panicking inside the infallible draws of the CRH setup.
+    pub fn setup<R: ark_std::rand::Rng>(rng: &mut R) -> Result<Self, VrfError> {
         use ark_crypto_primitives::crh::CRHScheme;
-        let pedersen_crh_params = <pedersen::CRH<C, VrfPedersenWindow> as CRHScheme>::setup(rng)
-            .expect("Pedersen CRH setup should not fail");
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut seed = [0u8; 32];
+        rng.try_fill_bytes(&mut seed)
+            .map_err(|err| VrfError::Rng(err.to_string()))?;
+        let mut seeded = StdRng::from_seed(seed);
+        let pedersen_crh_params =
+            <pedersen::CRH<C, VrfPedersenWindow> as CRHScheme>::setup(&mut seeded)
+                .map_err(|err| VrfError::PedersenSetup(err.to_string()))?;
         let sponge_params =
             poseidon_config::<<<C as CurveGroup>::BaseField as ark_ff::Field>::BasePrimeField>();
-        Self {
+        Ok(Self {
             pedersen_crh_params,
             sponge_params,
-        }
+            hash_to_curve: HashToCurveMethod::default(),
+        })
+    }
+
+    /// Setup VRF parameters with Pedersen generators derived from `seed` and default Poseidon
+    /// config, so every party holding the seed gets the same parameters and can verify the
+    /// others' proofs
+    ///
+    /// Each window's base generator is the [`TryAndIncrement`] hash of
+    /// `DST_PEDERSEN_GENERATOR || seed || window` (window as u32 LE), and the rest of the window
+    /// are its successive doublings, as in `pedersen::CRH::setup`. Like `TryAndIncrement`, this
+    /// needs a prime-order short Weierstrass curve.
+    pub fn setup_from_seed(seed: [u8; 32]) -> Result<Self, VrfError>
+    where
+        <<C as CurveGroup>::BaseField as ark_ff::Field>::BasePrimeField: Absorb,
+    {
+        use pedersen::Window;
+        let generators = (0..VrfPedersenWindow::NUM_WINDOWS as u32)
+            .map(|window| -> Result<Vec<C>, VrfError> {
+                let msg = [DST_PEDERSEN_GENERATOR, &seed, &window.to_le_bytes()].concat();
+                let mut base = HashToCurve::<C>::hash_to_curve(&TryAndIncrement, &msg)?;
+                Ok((0..VrfPedersenWindow::WINDOW_SIZE)
+                    .map(|_| {
+                        let power = base;
+                        base.double_in_place();
+                        power
+                    })
+                    .collect())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let sponge_params =
+            poseidon_config::<<<C as CurveGroup>::BaseField as ark_ff::Field>::BasePrimeField>();
+        Ok(Self {
+            pedersen_crh_params: pedersen::Parameters { generators },
+            sponge_params,
+            hash_to_curve: HashToCurveMethod::default(),
+        })
     }
 }
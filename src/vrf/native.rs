@@ -1,27 +1,31 @@
 //! Native VRF implementation (off-circuit)
 
+use super::hash_to_curve::{HashToCurve, HashToCurveMethod, PedersenHashToCurve, TryAndIncrement};
 use super::{
-    dst_beta_digest, dst_challenge_digest, dst_nonce_digest, VrfParams, VrfPedersenWindow, VrfProof,
+    dst_beta_digest, dst_challenge_digest, dst_nonce_digest, VrfError, VrfParams, VrfProof,
 };
 use crate::field_conversion::scalar_to_base_field_elements;
-use crate::poseidon_config;
 use crate::shuffling::curve_absorb::CurveAbsorb;
-use ark_crypto_primitives::crh::{pedersen, CRHScheme};
-use ark_crypto_primitives::sponge::{poseidon::PoseidonSponge, Absorb, CryptographicSponge};
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::{BigInteger, Field, PrimeField};
+use crate::transcript::{poseidon_transcript, Transcript};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField};
 use ark_serialize::CanonicalSerialize;
 
 const LOG_TARGET: &str = "vrf::native";
 
-/// Hash message to curve point using Pedersen CRH + cofactor clearing
-pub fn hash_to_curve<C: CurveGroup>(params: &VrfParams<C>, msg: &[u8]) -> C {
-    let p = pedersen::CRH::<C, VrfPedersenWindow>::evaluate(&params.pedersen_crh_params, msg)
-        .expect("Pedersen hash-to-curve should not fail");
-
-    // Cofactor clear (e.g., 1 for Grumpkin, 8 for Jubjub, 4 for Bandersnatch)
-    // This ensures the point is in the prime-order subgroup
-    p.mul_by_cofactor().into()
+/// Hash message to curve point with the map selected by `params.hash_to_curve`
+pub fn hash_to_curve<C>(params: &VrfParams<C>, msg: &[u8]) -> Result<C, VrfError>
+where
+    C: CurveGroup,
+    <C::BaseField as Field>::BasePrimeField: PrimeField + Absorb,
+{
+    match params.hash_to_curve {
+        HashToCurveMethod::Pedersen => {
+            PedersenHashToCurve::new(&params.pedersen_crh_params).hash_to_curve(msg)
+        }
+        HashToCurveMethod::TryAndIncrement => TryAndIncrement.hash_to_curve(msg),
+    }
 }
 
 /// Generate deterministic nonce k using Poseidon sponge
@@ -35,29 +39,26 @@ where
 {
     type BaseField<C> = <<C as CurveGroup>::BaseField as Field>::BasePrimeField;
 
-    let config = poseidon_config::<BaseField<C>>();
-    let mut sponge = PoseidonSponge::new(&config);
-
     // Use precomputed digest for efficiency and consistency with circuit
     let dst_digest = dst_nonce_digest::<BaseField<C>>();
     tracing::debug!(target: LOG_TARGET, "absorbing digest {}", dst_digest);
-    sponge.absorb(&dst_digest);
+    let mut transcript = poseidon_transcript::<BaseField<C>>(&dst_digest);
 
     // Convert secret key to base field and absorb
     // We absorb the bytes of the scalar field element
     tracing::debug!(target: LOG_TARGET, "absorbing sk {}", sk);
     let sk_base_fields: Vec<BaseField<C>> = scalar_to_base_field_elements(sk);
     for field_elem in sk_base_fields {
-        sponge.absorb(&field_elem);
+        transcript.absorb_scalar(&field_elem);
     }
 
     // Absorb curve point H using CurveAbsorb trait
-    h.curve_absorb(&mut sponge);
+    transcript.absorb_point(h);
     tracing::debug!(target: LOG_TARGET, "absorbed curve point H");
 
-    // Absorb message bytes
+    // Absorb message bytes one field element each, as the circuit does
     for byte in msg {
-        sponge.absorb(&BaseField::<C>::from(*byte as u64));
+        transcript.absorb_scalar(&BaseField::<C>::from(*byte as u64));
     }
 
     tracing::debug!(target: LOG_TARGET, "absorbing message {:?}", msg);
@@ -68,12 +69,9 @@ where
         msg.len()
     );
 
-    // Squeeze nonce in base field and convert to scalar field
-    let k_base: BaseField<C> = sponge.squeeze_field_elements(1)[0];
-
-    // Convert from base field to scalar field via bytes (mod order)
-    let k_bytes = k_base.into_bigint().to_bytes_le();
-    C::ScalarField::from_le_bytes_mod_order(&k_bytes)
+    // Squeeze nonce in base field and reduce into the scalar field; the circuit performs the
+    // same reduction
+    transcript.challenge_scalar::<BaseField<C>, _>()
 }
 
 /// Generate challenge c from transcript using Poseidon
@@ -87,27 +85,21 @@ where
 {
     type BaseField<C> = <<C as CurveGroup>::BaseField as Field>::BasePrimeField;
 
-    let config = poseidon_config::<BaseField<C>>();
-    let mut sponge = PoseidonSponge::new(&config);
-
     // Use precomputed digest for efficiency and consistency with circuit
     let dst_digest = dst_challenge_digest::<BaseField<C>>();
     tracing::trace!(target: LOG_TARGET, "digest challenge {}", dst_digest);
-    sponge.absorb(&dst_digest);
+    let mut transcript = poseidon_transcript::<BaseField<C>>(&dst_digest);
 
     // Absorb all points in order: pk, H, Γ, U, V using CurveAbsorb trait
     for p in [pk, h, gamma, u, v] {
-        p.curve_absorb(&mut sponge);
+        transcript.absorb_point(p);
     }
 
     tracing::trace!(target: LOG_TARGET, "Challenge generation: absorbed 5 curve points {:?}", [pk, h, gamma, u, v]);
 
-    // Squeeze challenge in base field and convert to scalar field
-    let c_base: BaseField<C> = sponge.squeeze_field_elements(1)[0];
-
-    // Convert from base field to scalar field via bytes (mod order)
-    let c_bytes = c_base.into_bigint().to_bytes_le();
-    C::ScalarField::from_le_bytes_mod_order(&c_bytes)
+    // Squeeze challenge in base field and reduce into the scalar field; the circuit performs the
+    // same reduction
+    transcript.challenge_scalar::<BaseField<C>, _>()
 }
 
 /// Compute β from Γ using Poseidon over base field
@@ -120,19 +112,16 @@ where
 {
     type BaseField<C> = <<C as CurveGroup>::BaseField as Field>::BasePrimeField;
 
-    let config = poseidon_config::<BaseField<C>>();
-    let mut sponge = PoseidonSponge::new(&config);
-
     // Use precomputed digest for efficiency and consistency with circuit
     let dst_digest = dst_beta_digest::<BaseField<C>>();
-    sponge.absorb(&dst_digest);
+    let mut transcript = poseidon_transcript::<BaseField<C>>(&dst_digest);
 
     // Absorb gamma using CurveAbsorb trait for consistency with circuit
-    gamma.curve_absorb(&mut sponge);
+    transcript.absorb_point(gamma);
 
     tracing::trace!(target: LOG_TARGET, "Beta computation: absorbed gamma point");
 
-    sponge.squeeze_field_elements(1)[0]
+    transcript.challenge()
 }
 
 /// Native VRF proving
@@ -147,12 +136,13 @@ where
 ///
 /// # Returns
 /// * `(VrfProof, beta)` - The proof and VRF output
+/// * `VrfError` if the message cannot be hashed to the curve
 pub fn prove_vrf<C>(
     params: &VrfParams<C>,
     pk: &C,
     sk: C::ScalarField,
     msg: &[u8],
-) -> (VrfProof<C>, <C::BaseField as Field>::BasePrimeField)
+) -> Result<(VrfProof<C>, <C::BaseField as Field>::BasePrimeField), VrfError>
 where
     C: CurveGroup + CanonicalSerialize,
     C::ScalarField: PrimeField + Absorb,
@@ -163,7 +153,7 @@ where
     tracing::debug!(target: LOG_TARGET, "Starting VRF proof generation");
 
     // H = HashToCurve(msg)
-    let h = hash_to_curve::<C>(params, msg);
+    let h = hash_to_curve::<C>(params, msg)?;
     tracing::debug!(target: LOG_TARGET, "Hash to curve {}", h);
 
     // Γ = x * H
@@ -197,7 +187,7 @@ where
         msg.len()
     );
 
-    (VrfProof { gamma, c, s }, beta)
+    Ok((VrfProof { gamma, c, s }, beta))
 }
 
 /// Native VRF verification
@@ -212,7 +202,7 @@ where
 ///
 /// # Returns
 /// * `Some(beta)` if proof is valid
-/// * `None` if proof is invalid
+/// * `None` if proof is invalid, including when the message cannot be hashed to the curve
 pub fn verify_vrf<C>(
     params: &VrfParams<C>,
     pk: &C,
@@ -229,7 +219,13 @@ where
     tracing::debug!(target: LOG_TARGET, "Starting VRF proof verification");
 
     // H = HashToCurve(msg)
-    let h = hash_to_curve::<C>(params, msg);
+    let h = match hash_to_curve::<C>(params, msg) {
+        Ok(h) => h,
+        Err(err) => {
+            tracing::warn!(target: LOG_TARGET, error = %err, "VRF proof verification failed");
+            return None;
+        }
+    };
 
     // U' = s * G - c * pk
     let u_prime = C::generator() * proof.s - *pk * proof.c;
@@ -253,3 +249,71 @@ where
         None
     }
 }
+
+/// Native batch VRF verification
+///
+/// Verifies many `(msg, pk, proof)` triples, e.g. one per shuffle step when replaying a game.
+/// The challenge of each proof commits to its own U and V, so every proof still needs its own
+/// U' and V'. The batch makes those cheaper: all G, pk, H and Γ bases are normalized with a
+/// single inversion, and U' = s * G - c * pk and V' = s * H - c * Γ are each computed as one
+/// two-base MSM rather than two scalar multiplications and a subtraction.
+///
+/// # Returns
+/// * `Ok(())` if every proof is valid
+/// * `Err(indices)` with the position of every invalid proof, in order
+pub fn verify_vrf_batch<C>(
+    params: &VrfParams<C>,
+    items: &[(&[u8], C, VrfProof<C>)],
+) -> Result<(), Vec<usize>>
+where
+    C: CurveGroup + CanonicalSerialize,
+    C::ScalarField: PrimeField + Absorb,
+    C::BaseField: PrimeField + Absorb,
+    <C::BaseField as Field>::BasePrimeField: PrimeField + Absorb,
+    C: CurveAbsorb<<C::BaseField as Field>::BasePrimeField>,
+{
+    tracing::debug!(target: LOG_TARGET, "Batch verifying {} VRF proofs", items.len());
+
+    // A message that cannot be hashed has no valid proof; its slot only keeps the bases aligned
+    let hashes: Vec<Option<C>> = items
+        .iter()
+        .map(|(msg, _, _)| hash_to_curve::<C>(params, msg).ok())
+        .collect();
+    let bases = C::normalize_batch(
+        &items
+            .iter()
+            .zip(&hashes)
+            .flat_map(|((_, pk, proof), h)| {
+                [C::generator(), *pk, h.unwrap_or_default(), proof.gamma]
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let failed: Vec<usize> = items
+        .iter()
+        .zip(&hashes)
+        .zip(bases.chunks_exact(4))
+        .enumerate()
+        .filter_map(|(index, (((_, pk, proof), h), bases))| {
+            let Some(h) = h else {
+                return Some(index);
+            };
+            let scalars = [proof.s, -proof.c];
+            let u_prime = C::msm_unchecked(&bases[..2], &scalars);
+            let v_prime = C::msm_unchecked(&bases[2..], &scalars);
+            let c_prime = generate_challenge::<C>(pk, h, &proof.gamma, &u_prime, &v_prime);
+            (c_prime != proof.c).then_some(index)
+        })
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        tracing::warn!(
+            target: LOG_TARGET,
+            failed = ?failed,
+            "VRF batch verification failed: challenge mismatch"
+        );
+        Err(failed)
+    }
+}
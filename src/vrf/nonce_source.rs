@@ -0,0 +1,262 @@
+//! Sources for the public VRF nonce that seeds a shuffle.
+//!
+//! The shuffle permutation is derived from `VRF(sk, nonce)`, so a shuffler that picks its own
+//! nonce can grind for a favourable deck. Taking the nonce from a public randomness beacon
+//! removes that freedom: the beacon round is recorded on the shuffle step and anyone can
+//! re-fetch it with [`DrandBeacon::nonce_for_round`] to check the nonce that was used. Either
+//! way the shuffler binds its nonce with a [`commit_nonce`] commitment published before its turn.
+
+use std::sync::Arc;
+
+use ark_ff::{BigInteger, PrimeField};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::transcript::{poseidon_transcript, Transcript};
+
+const LOG_TARGET: &str = "legit_poker::vrf::nonce_source";
+
+const NONCE_COMMITMENT_DOMAIN: &[u8] = b"legit_poker/vrf_nonce_commitment_v1";
+const SHUFFLE_SEED_DOMAIN: &[u8] = b"legit_poker/vrf_shuffle_seed_v1";
+
+/// Public drand mainnet HTTP relay.
+pub const DEFAULT_DRAND_URL: &str = "https://api.drand.sh";
+
+#[derive(Debug, Error)]
+pub enum NonceSourceError {
+    #[error("failed to fetch beacon round: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("beacon randomness is not valid hex: {0}")]
+    InvalidRandomness(#[from] hex::FromHexError),
+    #[error("beacon returned round {actual}, expected {expected}")]
+    RoundMismatch { expected: u64, actual: u64 },
+}
+
+/// A VRF nonce together with the beacon round it was taken from, if any.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VrfNonce<F> {
+    pub value: F,
+    pub beacon_round: Option<u64>,
+}
+
+impl<F: PrimeField> VrfNonce<F> {
+    pub fn commitment(&self) -> F {
+        commit_nonce(&self.value)
+    }
+}
+
+/// `Poseidon(nonce)` over the nonce's little-endian bytes.
+///
+/// A shuffler publishes this before it can see the deck it will shuffle and reveals the nonce
+/// with its shuffle, so the nonce cannot be chosen after the fact.
+pub fn commit_nonce<F: PrimeField>(nonce: &F) -> F {
+    let mut transcript = poseidon_transcript::<F>(&NONCE_COMMITMENT_DOMAIN);
+    transcript.absorb_scalar(&nonce.into_bigint().to_bytes_le());
+    transcript.challenge()
+}
+
+/// Seed for a shuffler's permutation and rerandomizers: `Poseidon(nonce, secret_key)`.
+///
+/// Once the nonce is committed, the shuffle a key can produce is fixed, so the shuffler
+/// cannot re-draw it after seeing the deck.
+pub fn shuffle_seed<F: PrimeField, S: PrimeField>(nonce: &F, secret_key: &S) -> [u8; 32] {
+    let mut transcript = poseidon_transcript::<F>(&SHUFFLE_SEED_DOMAIN);
+    transcript.absorb_scalar(&nonce.into_bigint().to_bytes_le());
+    transcript.absorb_scalar(&secret_key.into_bigint().to_bytes_le());
+    let digest = transcript.challenge::<F>().into_bigint().to_bytes_le();
+    let mut seed = [0u8; 32];
+    let len = digest.len().min(seed.len());
+    seed[..len].copy_from_slice(&digest[..len]);
+    seed
+}
+
+#[async_trait]
+pub trait NonceSource<F>: Send + Sync {
+    async fn next_nonce(&self) -> Result<VrfNonce<F>, NonceSourceError>;
+}
+
+/// Locally sampled nonces. Not publicly verifiable; intended for tests and local demos.
+#[derive(Clone)]
+pub struct LocalRng {
+    rng: Arc<Mutex<StdRng>>,
+}
+
+impl LocalRng {
+    pub fn new(rng: StdRng) -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(rng)),
+        }
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(StdRng::seed_from_u64(seed))
+    }
+}
+
+#[async_trait]
+impl<F: PrimeField> NonceSource<F> for LocalRng {
+    async fn next_nonce(&self) -> Result<VrfNonce<F>, NonceSourceError> {
+        Ok(VrfNonce {
+            value: F::rand(&mut *self.rng.lock()),
+            beacon_round: None,
+        })
+    }
+}
+
+/// A single round as returned by the drand HTTP API (`/public/latest`, `/public/{round}`).
+#[derive(Clone, Debug, Deserialize)]
+pub struct DrandRound {
+    pub round: u64,
+    /// Hex-encoded beacon output (SHA-256 of the round signature).
+    pub randomness: String,
+}
+
+impl DrandRound {
+    /// Reduce the beacon output into the nonce field.
+    pub fn to_nonce<F: PrimeField>(&self) -> Result<VrfNonce<F>, NonceSourceError> {
+        let bytes = hex::decode(&self.randomness)?;
+        Ok(VrfNonce {
+            value: F::from_be_bytes_mod_order(&bytes),
+            beacon_round: Some(self.round),
+        })
+    }
+}
+
+/// Nonce source backed by a drand HTTP endpoint.
+///
+/// The round signature is not checked against the drand group key here; callers that need
+/// that guarantee should verify the round independently using the round number recorded on
+/// the shuffle step.
+#[derive(Clone)]
+pub struct DrandBeacon {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl DrandBeacon {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Fetch the latest round, or a specific one when `round` is given.
+    pub async fn fetch_round(&self, round: Option<u64>) -> Result<DrandRound, NonceSourceError> {
+        let url = match round {
+            Some(round) => format!("{}/public/{round}", self.base_url),
+            None => format!("{}/public/latest", self.base_url),
+        };
+        let fetched: DrandRound = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(expected) = round {
+            if fetched.round != expected {
+                return Err(NonceSourceError::RoundMismatch {
+                    expected,
+                    actual: fetched.round,
+                });
+            }
+        }
+
+        tracing::debug!(target: LOG_TARGET, round = fetched.round, "fetched drand round");
+        Ok(fetched)
+    }
+
+    /// Re-derive the nonce recorded for `round`, e.g. to audit a completed shuffle.
+    pub async fn nonce_for_round<F: PrimeField>(
+        &self,
+        round: u64,
+    ) -> Result<VrfNonce<F>, NonceSourceError> {
+        self.fetch_round(Some(round)).await?.to_nonce()
+    }
+}
+
+impl Default for DrandBeacon {
+    fn default() -> Self {
+        Self::new(DEFAULT_DRAND_URL)
+    }
+}
+
+#[async_trait]
+impl<F: PrimeField> NonceSource<F> for DrandBeacon {
+    async fn next_nonce(&self) -> Result<VrfNonce<F>, NonceSourceError> {
+        self.fetch_round(None).await?.to_nonce()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pedersen_commitment::bytes_opening::{DeckHashWindow, ReencryptionWindow};
+    use crate::shuffling::permutation_proof::{PermutationParameters, WitnessAssembler};
+    use ark_bn254::Fr as BaseField;
+    use ark_crypto_primitives::commitment::{
+        pedersen::Commitment as PedersenCommitment, CommitmentScheme,
+    };
+    use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
+    use ark_crypto_primitives::sponge::CryptographicSponge;
+    use ark_grumpkin::{Fr as ScalarField, Projective as C};
+    use ark_std::UniformRand;
+    use axum::{routing::get, Json, Router};
+    use tokio::net::TcpListener;
+
+    const RANDOMNESS: &str = "8b676484b5fb1f37f9ec5c413d7d29883504e5b669f604a1ce68b3388e9ae3d9";
+    const ROUND: u64 = 4_242_424;
+
+    async fn spawn_mock_beacon() -> String {
+        let app = Router::new().route(
+            "/public/latest",
+            get(|| async {
+                Json(serde_json::json!({
+                    "round": ROUND,
+                    "randomness": RANDOMNESS,
+                    "signature": "00",
+                }))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("serve mock beacon");
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn drand_nonce_flows_into_shuffle_witness() {
+        let beacon = DrandBeacon::new(spawn_mock_beacon().await);
+        let nonce: VrfNonce<BaseField> = beacon.next_nonce().await.expect("beacon nonce");
+
+        let expected = BaseField::from_be_bytes_mod_order(&hex::decode(RANDOMNESS).unwrap());
+        assert_eq!(nonce.value, expected);
+        assert_eq!(nonce.beacon_round, Some(ROUND));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let perm_params = PedersenCommitment::<C, DeckHashWindow>::setup(&mut rng).unwrap();
+        let power_params = PedersenCommitment::<C, ReencryptionWindow>::setup(&mut rng).unwrap();
+        let sk = ScalarField::rand(&mut rng);
+        let mut params = PermutationParameters::<C, _> {
+            perm_params: &perm_params,
+            power_params: &power_params,
+            rng: &mut rng,
+        };
+        let mut sponge =
+            PoseidonSponge::<BaseField>::new(&crate::config::poseidon_config::<BaseField>());
+        let assembled = WitnessAssembler::<C, _, 8, 3>::new(&mut params, nonce.value, sk)
+            .assemble(&mut sponge)
+            .expect("assemble witness");
+
+        assert_eq!(assembled.public.nonce, expected);
+    }
+}
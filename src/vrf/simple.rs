@@ -2,12 +2,14 @@
 //!
 //! A minimal function that absorbs a hidden base-field message and a scalar-field
 //! secret key into a caller-provided cryptographic sponge, after enforcing that
-//! the provided public key equals `sk * G`.
+//! the provided public key equals `sk * G`. The output is a single field element;
+//! [`derive_seed_bits`] expands it into as many seed bits as a caller needs.
 
 use crate::field_conversion::scalar_to_base_field_elements;
+use crate::shuffling::utils::generate_random_values;
 use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
 use ark_ec::CurveGroup;
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
 
 /// Deterministically derive an output by hashing a hidden base-field message and a secret key
 /// with the provided cryptographic sponge, enforcing `public_key == secret_key * G`.
@@ -43,6 +45,36 @@ where
     sponge.squeeze_field_elements(1)[0]
 }
 
+/// Seed bits taken from each squeezed element: its lowest and highest bits are dropped.
+fn seed_bits_per_element<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize).saturating_sub(2)
+}
+
+/// Number of field elements [`derive_seed_bits`] squeezes to produce `num_bits` bits.
+pub fn seed_elements_needed<F: PrimeField>(num_bits: usize) -> usize {
+    num_bits.div_ceil(seed_bits_per_element::<F>())
+}
+
+/// Expand a VRF output into exactly `num_bits` seed bits.
+///
+/// Squeezes [`seed_elements_needed`] elements from a Poseidon sponge seeded with `vrf_value`
+/// and concatenates their little-endian bits, trimmed of the first and last bit of each
+/// element. A shorter request yields a prefix of a longer one, so callers such as the RS
+/// shuffle can ask for `N * LEVELS` bits regardless of the field size.
+pub fn derive_seed_bits<F: PrimeField + Absorb>(vrf_value: F, num_bits: usize) -> Vec<bool> {
+    let bits_per_element = seed_bits_per_element::<F>();
+    let mut bits: Vec<bool> =
+        generate_random_values(vrf_value, seed_elements_needed::<F>(num_bits))
+            .into_iter()
+            .flat_map(|value| {
+                let value = value.into_bigint();
+                (1..=bits_per_element).map(move |i| value.get_bit(i))
+            })
+            .collect();
+    bits.truncate(num_bits);
+    bits
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +112,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_derive_seed_bits_covers_deck() {
+        const N: usize = 64;
+        const LEVELS: usize = 6;
+        let vrf_value = BaseField::rand(&mut ark_std::test_rng());
+
+        let bits = derive_seed_bits(vrf_value, N * LEVELS);
+        assert_eq!(bits.len(), N * LEVELS);
+        assert_eq!(seed_elements_needed::<BaseField>(N * LEVELS), 2);
+        assert_eq!(bits, derive_seed_bits(vrf_value, N * LEVELS));
+        assert_eq!(bits[..N], derive_seed_bits(vrf_value, N)[..]);
+    }
+
     #[test]
     #[should_panic(expected = "public key does not match secret key")]
     fn test_simple_vrf_wrong_pk() {
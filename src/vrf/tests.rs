@@ -2,7 +2,7 @@
 
 use super::*;
 use crate::vrf::gadgets::{beta_from_gamma_var, prove_vrf_gadget};
-use crate::vrf::native::{prove_vrf, verify_vrf};
+use crate::vrf::native::{prove_vrf, verify_vrf, verify_vrf_batch};
 use ark_bn254::Fr as BaseField; // BN254's scalar field = Grumpkin's base field
 use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
 use ark_crypto_primitives::sponge::poseidon::PoseidonSponge;
@@ -20,7 +20,9 @@ use ark_r1cs_std::{
 use ark_relations::gr1cs::{
     ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError,
 };
+use ark_std::rand::RngCore;
 use ark_std::test_rng;
+use std::num::NonZeroU32;
 use tracing_subscriber::filter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -117,7 +119,7 @@ fn test_native_vrf_roundtrip() {
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate keypair
     let sk = ScalarField::rand(&mut rng);
@@ -127,7 +129,7 @@ fn test_native_vrf_roundtrip() {
     let msg = b"VRF-test-message-12345";
 
     // Prove
-    let (proof, beta) = prove_vrf(&params, &pk, sk, msg);
+    let (proof, beta) = prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
 
     // Verify - should succeed
     let beta_verified = verify_vrf(&params, &pk, msg, &proof);
@@ -150,13 +152,91 @@ fn test_native_vrf_roundtrip() {
     );
 }
 
+#[test]
+fn test_batch_verify_reports_tampered_proof() {
+    let _guard = setup_test_tracing();
+    let mut rng = test_rng();
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
+
+    let messages: [&[u8]; 4] = [b"round-0", b"round-1", b"round-2", b"round-3"];
+    let mut items: Vec<_> = messages
+        .iter()
+        .map(|msg| {
+            let sk = ScalarField::rand(&mut rng);
+            let pk = TestCurve::generator() * sk;
+            let (proof, _) = prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
+            (*msg, pk, proof)
+        })
+        .collect();
+    assert_eq!(verify_vrf_batch(&params, &items), Ok(()));
+
+    items[2].2.s += ScalarField::from(1u64);
+    assert_eq!(verify_vrf_batch(&params, &items), Err(vec![2]));
+    assert!(verify_vrf(&params, &items[2].1, items[2].0, &items[2].2).is_none());
+}
+
+/// An RNG whose entropy source is gone: fallible draws report an error, infallible ones panic.
+struct FailingRng;
+
+impl RngCore for FailingRng {
+    fn next_u32(&mut self) -> u32 {
+        panic!("infallible draw from a failing RNG")
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        panic!("infallible draw from a failing RNG")
+    }
+
+    fn fill_bytes(&mut self, _dest: &mut [u8]) {
+        panic!("infallible draw from a failing RNG")
+    }
+
+    fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), ark_std::rand::Error> {
+        Err(NonZeroU32::new(ark_std::rand::Error::CUSTOM_START)
+            .expect("CUSTOM_START is non-zero")
+            .into())
+    }
+}
+
+#[test]
+fn test_setup_with_failing_rng_returns_error() {
+    let result = VrfParams::<TestCurve>::setup(&mut FailingRng);
+    assert!(matches!(result, Err(VrfError::Rng(_))));
+}
+
+#[test]
+fn test_setup_from_seed_is_shared_across_processes() {
+    let seed = [7u8; 32];
+    let ours = VrfParams::<TestCurve>::setup_from_seed(seed).expect("seeded setup should succeed");
+    let theirs =
+        VrfParams::<TestCurve>::setup_from_seed(seed).expect("seeded setup should succeed");
+    assert_eq!(
+        ours.pedersen_crh_params.generators,
+        theirs.pedersen_crh_params.generators
+    );
+    assert_ne!(
+        ours.pedersen_crh_params.generators,
+        VrfParams::<TestCurve>::setup_from_seed([8u8; 32])
+            .expect("seeded setup should succeed")
+            .pedersen_crh_params
+            .generators
+    );
+
+    let mut rng = test_rng();
+    let sk = ScalarField::rand(&mut rng);
+    let pk = TestCurve::generator() * sk;
+    let msg = b"shared-params";
+    let (proof, beta) = prove_vrf(&ours, &pk, sk, msg).expect("VRF proving should succeed");
+    assert_eq!(verify_vrf(&theirs, &pk, msg, &proof), Some(beta));
+}
+
 #[test]
 fn test_native_vs_snark_parity() {
     let _guard = setup_test_tracing();
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate keypair
     let sk = ScalarField::rand(&mut rng);
@@ -166,7 +246,8 @@ fn test_native_vs_snark_parity() {
     let msg = b"VRF-parity-test-42";
 
     // Native proof generation
-    let (proof_native, beta_native) = prove_vrf(&params, &pk, sk, msg);
+    let (proof_native, beta_native) =
+        prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
 
     // Create circuit
     let circuit = VrfProveCircuit {
@@ -201,7 +282,7 @@ fn test_deterministic_vrf_output() {
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate keypair
     let sk = ScalarField::rand(&mut rng);
@@ -211,8 +292,8 @@ fn test_deterministic_vrf_output() {
     let msg = b"deterministic-test";
 
     // Generate proof multiple times
-    let (proof1, beta1) = prove_vrf(&params, &pk, sk, msg);
-    let (proof2, beta2) = prove_vrf(&params, &pk, sk, msg);
+    let (proof1, beta1) = prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
+    let (proof2, beta2) = prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
 
     // Should produce identical outputs
     assert_eq!(beta1, beta2, "VRF output should be deterministic");
@@ -227,7 +308,7 @@ fn test_different_messages_different_outputs() {
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate keypair
     let sk = ScalarField::rand(&mut rng);
@@ -238,8 +319,8 @@ fn test_different_messages_different_outputs() {
     let msg2 = b"message-2";
 
     // Generate proofs
-    let (_, beta1) = prove_vrf(&params, &pk, sk, msg1);
-    let (_, beta2) = prove_vrf(&params, &pk, sk, msg2);
+    let (_, beta1) = prove_vrf(&params, &pk, sk, msg1).expect("VRF proving should succeed");
+    let (_, beta2) = prove_vrf(&params, &pk, sk, msg2).expect("VRF proving should succeed");
 
     // Should produce different outputs
     assert_ne!(
@@ -254,7 +335,7 @@ fn test_different_keys_different_outputs() {
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate two different keypairs
     let sk1 = ScalarField::rand(&mut rng);
@@ -266,8 +347,8 @@ fn test_different_keys_different_outputs() {
     let msg = b"same-message";
 
     // Generate proofs
-    let (_, beta1) = prove_vrf(&params, &pk1, sk1, msg);
-    let (_, beta2) = prove_vrf(&params, &pk2, sk2, msg);
+    let (_, beta1) = prove_vrf(&params, &pk1, sk1, msg).expect("VRF proving should succeed");
+    let (_, beta2) = prove_vrf(&params, &pk2, sk2, msg).expect("VRF proving should succeed");
 
     // Should produce different outputs
     assert_ne!(
@@ -282,7 +363,7 @@ fn test_beta_computation_consistency() {
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate keypair
     let sk = ScalarField::rand(&mut rng);
@@ -292,7 +373,8 @@ fn test_beta_computation_consistency() {
     let msg = b"beta-test";
 
     // Generate proof
-    let (proof, beta_native) = prove_vrf(&params, &pk, sk, msg);
+    let (proof, beta_native) =
+        prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
 
     // Compute beta directly from gamma
     let beta_computed = super::native::beta_from_gamma::<TestCurve>(&proof.gamma);
@@ -331,7 +413,7 @@ fn test_hash_to_curve_consistency() {
     let _guard = setup_test_tracing();
 
     let mut rng = test_rng();
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Test multiple different messages
     let test_messages = vec![
@@ -350,7 +432,7 @@ fn test_hash_to_curve_consistency() {
         );
 
         // Native computation
-        let h_native = hash_to_curve::<TestCurve>(&params, &msg);
+        let h_native = hash_to_curve::<TestCurve>(&params, &msg).expect("Should hash to curve");
         tracing::debug!(target: TEST_TARGET, "Native hash_to_curve result: {:?}", h_native);
 
         // Circuit computation
@@ -392,6 +474,90 @@ fn test_hash_to_curve_consistency() {
     tracing::debug!(target: TEST_TARGET, "✅ All hash_to_curve consistency tests passed!");
 }
 
+#[test]
+fn test_try_and_increment_hash_to_curve() {
+    use crate::vrf::gadgets::try_and_increment_var;
+    use crate::vrf::native::hash_to_curve;
+
+    let _guard = setup_test_tracing();
+
+    let mut rng = test_rng();
+    let params = VrfParams::<TestCurve>::setup(&mut rng)
+        .expect("VRF setup should succeed")
+        .with_hash_to_curve(HashToCurveMethod::TryAndIncrement);
+
+    for msg in [b"".to_vec(), b"test1".to_vec(), vec![0xff; 32]] {
+        let h_native = hash_to_curve::<TestCurve>(&params, &msg).expect("Should hash to curve");
+        assert_eq!(
+            h_native,
+            HashToCurve::<TestCurve>::hash_to_curve(&TryAndIncrement, &msg).unwrap(),
+            "params should select try-and-increment"
+        );
+        assert_ne!(
+            h_native,
+            PedersenHashToCurve::new(&params.pedersen_crh_params)
+                .hash_to_curve(&msg)
+                .unwrap(),
+            "try-and-increment should not fall back to Pedersen"
+        );
+
+        let cs = ConstraintSystem::<BaseField>::new_ref();
+        let msg_var =
+            UInt8::<BaseField>::new_witness_vec(cs.clone(), &msg).expect("Should allocate message");
+        let h_circuit = try_and_increment_var::<
+            TestCurve,
+            TestCurveVar,
+            PoseidonSponge<BaseField>,
+            PoseidonSpongeVar<BaseField>,
+        >(cs.clone(), &params.sponge_params, &msg_var)
+        .expect("Should compute try-and-increment in circuit");
+
+        assert_eq!(
+            h_native,
+            h_circuit.value().expect("Should get value"),
+            "Native and circuit try-and-increment should match for message {:?}",
+            msg
+        );
+        assert!(
+            cs.is_satisfied().unwrap(),
+            "Circuit constraints should be satisfied"
+        );
+    }
+
+    // Prove and verify under the same map, natively and in circuit
+    let sk = ScalarField::rand(&mut rng);
+    let pk = TestCurve::generator() * sk;
+    let msg = b"VRF-try-and-increment";
+
+    let (proof, beta) = prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
+    assert_eq!(verify_vrf(&params, &pk, msg, &proof), Some(beta));
+
+    let pedersen_params = VrfParams::new(
+        params.pedersen_crh_params.clone(),
+        params.sponge_params.clone(),
+    );
+    assert_eq!(
+        verify_vrf(&pedersen_params, &pk, msg, &proof),
+        None,
+        "a proof is bound to the hash-to-curve map it was made with"
+    );
+
+    let circuit = VrfProveCircuit {
+        msg: msg.to_vec(),
+        sk,
+        expected_gamma: proof.gamma,
+        expected_c: proof.c,
+        expected_s: proof.s,
+        expected_beta: beta,
+        params: params.clone(),
+    };
+    let cs = ConstraintSystem::<BaseField>::new_ref();
+    circuit
+        .generate_constraints(cs.clone())
+        .expect("Circuit generation should succeed");
+    assert!(cs.is_satisfied().unwrap(), "Circuit should be satisfied");
+}
+
 #[test]
 fn test_generate_nonce_consistency() {
     let _guard = setup_test_tracing();
@@ -400,7 +566,7 @@ fn test_generate_nonce_consistency() {
     use ark_crypto_primitives::crh::pedersen::constraints::CRHParametersVar as PedersenCRHParamsVar;
 
     let mut rng = test_rng();
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Test with multiple secret keys and messages
     let test_cases = vec![
@@ -418,7 +584,7 @@ fn test_generate_nonce_consistency() {
         );
 
         // Compute H = hash_to_curve(msg) natively
-        let h_native = hash_to_curve::<TestCurve>(&params, &msg);
+        let h_native = hash_to_curve::<TestCurve>(&params, &msg).expect("Should hash to curve");
 
         // Native nonce generation
         let nonce_native = generate_nonce::<TestCurve>(&sk, &h_native, &msg);
@@ -486,7 +652,7 @@ fn test_challenge_generation_consistency() {
     use crate::vrf::native::{generate_challenge, generate_nonce, hash_to_curve};
 
     let mut rng = test_rng();
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate test data
     let sk = ScalarField::rand(&mut rng);
@@ -494,7 +660,7 @@ fn test_challenge_generation_consistency() {
     let msg = b"test message for challenge";
 
     // Compute intermediate values natively
-    let h = hash_to_curve::<TestCurve>(&params, msg);
+    let h = hash_to_curve::<TestCurve>(&params, msg).expect("Should hash to curve");
     let gamma = h * sk;
     let k = generate_nonce::<TestCurve>(&sk, &h, msg);
     let u = TestCurve::generator() * k;
@@ -556,7 +722,7 @@ fn test_vrf_proof_var_allocation() {
     let mut rng = test_rng();
 
     // Setup parameters
-    let params = VrfParams::<TestCurve>::setup(&mut rng);
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
 
     // Generate keypair and proof
     let sk = ScalarField::rand(&mut rng);
@@ -564,7 +730,8 @@ fn test_vrf_proof_var_allocation() {
     let msg = b"test-message";
 
     // Generate native proof
-    let (proof_native, _beta) = prove_vrf(&params, &pk, sk, msg);
+    let (proof_native, _beta) =
+        prove_vrf(&params, &pk, sk, msg).expect("VRF proving should succeed");
 
     // Test allocation in circuit
     let cs = ConstraintSystem::<BaseField>::new_ref();
@@ -598,3 +765,94 @@ fn test_vrf_proof_var_allocation() {
     assert!(cs.is_satisfied().unwrap());
     tracing::info!(target: TEST_TARGET, "✅ VrfProofVar allocation works correctly");
 }
+
+/// Golden vectors for the nonce, challenge and output transcripts with the packed DST digests.
+#[test]
+fn test_native_transcripts_match_golden_vectors() {
+    use crate::vrf::native::{beta_from_gamma, generate_challenge, generate_nonce};
+    use std::str::FromStr;
+
+    let sk = ScalarField::from(11u64);
+    let pk = TestCurve::generator() * sk;
+    let h = TestCurve::generator() * ScalarField::from(13u64);
+    let gamma = h * sk;
+
+    let k = generate_nonce::<TestCurve>(&sk, &h, b"golden transcript");
+    assert_eq!(
+        k,
+        ScalarField::from_str(
+            "5921858446579354913918961386289775845160115624521434545723847122798852922962"
+        )
+        .unwrap()
+    );
+
+    let (u, v) = (TestCurve::generator() * k, h * k);
+    assert_eq!(
+        generate_challenge::<TestCurve>(&pk, &h, &gamma, &u, &v),
+        ScalarField::from_str(
+            "13079724244620415648716541691099944532723648230354358232478333351075740903927"
+        )
+        .unwrap()
+    );
+
+    assert_eq!(
+        beta_from_gamma::<TestCurve>(&gamma),
+        BaseField::from_str(
+            "16548262096186509246612311900102224077081626881020682746290596392658545188117"
+        )
+        .unwrap()
+    );
+}
+
+/// The DST digests used to absorb their tags one byte per field element. They now absorb the
+/// packed tag, which changes every VRF output; the gadgets take the digests as constants, so
+/// native and circuit still agree.
+#[test]
+fn test_dst_digests_absorb_packed_tags() {
+    use std::str::FromStr;
+
+    // (digest, packed golden vector, former per-byte digest)
+    let digests = [
+        (
+            dst_challenge_digest::<BaseField>(),
+            "12988199091023346749542152505248730133336337363113400189722480958406907418651",
+            "7595699353710173506387978436558302097560773768227442352539345423118649660284",
+        ),
+        (
+            dst_nonce_digest::<BaseField>(),
+            "2278127104638815529671166850529243709179606748971630783157213460894824376151",
+            "984976973148870561907797402066679290591096153876679321305088256701350347382",
+        ),
+        (
+            dst_beta_digest::<BaseField>(),
+            "9599985949196493881111394178282622500463865266822902169988106230956341007479",
+            "13250053708429958171679009717656531673606835481466121843416400578606348488682",
+        ),
+    ];
+    for (digest, packed, per_byte) in digests {
+        assert_eq!(digest, BaseField::from_str(packed).unwrap());
+        assert_ne!(digest, BaseField::from_str(per_byte).unwrap());
+    }
+
+    let config = crate::poseidon_config::<BaseField>();
+
+    let mut rng = test_rng();
+    let params = VrfParams::<TestCurve>::setup(&mut rng).expect("VRF setup should succeed");
+    let sk = ScalarField::rand(&mut rng);
+    let pk = TestCurve::generator() * sk;
+    let (proof, beta) =
+        prove_vrf(&params, &pk, sk, b"migration").expect("VRF proving should succeed");
+    assert_eq!(verify_vrf(&params, &pk, b"migration", &proof), Some(beta));
+
+    let cs = ConstraintSystem::<BaseField>::new_ref();
+    let gamma_var =
+        TestCurveVar::new_witness(cs.clone(), || Ok(proof.gamma)).expect("Should allocate gamma");
+    let beta_var = beta_from_gamma_var::<
+        TestCurve,
+        TestCurveVar,
+        PoseidonSponge<BaseField>,
+        PoseidonSpongeVar<BaseField>,
+    >(cs.clone(), &config, &gamma_var)
+    .expect("Should compute beta in circuit");
+    assert_eq!(beta_var.value().unwrap(), beta);
+}
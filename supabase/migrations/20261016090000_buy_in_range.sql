@@ -0,0 +1,20 @@
+-- Replace the single game buy-in with a [min_buy_in, max_buy_in] range and record
+-- each player's chosen buy-in on join.
+
+ALTER TABLE public.games RENAME COLUMN buy_in TO max_buy_in;
+
+ALTER TABLE public.games
+    ADD COLUMN min_buy_in BIGINT NOT NULL DEFAULT 10000 CHECK (min_buy_in > 0);
+
+UPDATE public.games SET min_buy_in = max_buy_in;
+
+ALTER TABLE public.games
+    ADD CONSTRAINT games_buy_in_range CHECK (min_buy_in <= max_buy_in);
+
+COMMENT ON COLUMN public.games.min_buy_in IS 'Smallest buy-in a player may bring to the table';
+COMMENT ON COLUMN public.games.max_buy_in IS 'Largest buy-in a player may bring to the table (default when none is requested)';
+
+ALTER TABLE public.game_players
+    ADD COLUMN buy_in BIGINT CHECK (buy_in > 0);
+
+COMMENT ON COLUMN public.game_players.buy_in IS 'Buy-in chosen by the player when joining';
@@ -0,0 +1,12 @@
+-- Per-game hand number counter so the lobby can allocate gap-free hand numbers.
+
+ALTER TABLE public.games
+    ADD COLUMN last_hand_no BIGINT NOT NULL DEFAULT 0 CHECK (last_hand_no >= 0);
+
+UPDATE public.games AS g
+SET last_hand_no = COALESCE(
+    (SELECT MAX(h.hand_no) FROM public.hands AS h WHERE h.game_id = g.id),
+    0
+);
+
+COMMENT ON COLUMN public.games.last_hand_no IS 'Highest hand number allocated for this game (0 before the first hand)';
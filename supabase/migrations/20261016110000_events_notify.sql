@@ -0,0 +1,15 @@
+-- Notify listeners (EventStore::tail) whenever a ledger event is persisted.
+CREATE OR REPLACE FUNCTION public.notify_ledger_event()
+RETURNS trigger
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    PERFORM pg_notify('ledger_events', NEW.hand_id::text);
+    RETURN NEW;
+END;
+$$;
+
+CREATE TRIGGER events_notify_insert
+    AFTER INSERT ON public.events
+    FOR EACH ROW
+    EXECUTE FUNCTION public.notify_ledger_event();
@@ -0,0 +1,21 @@
+-- Cold storage for completed hands. Archiving moves a hand's events and snapshots here so the
+-- live tables only hold hands that are still in play; rows keep their original columns so they
+-- can be decoded with the same code paths.
+
+CREATE TABLE public.archived_events (
+    LIKE public.events INCLUDING DEFAULTS,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX idx_archived_events_hand
+    ON public.archived_events(hand_id, nonce);
+
+CREATE TABLE public.archived_snapshots (
+    LIKE public.table_snapshots INCLUDING DEFAULTS,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (snapshot_hash)
+);
+
+CREATE INDEX idx_archived_snapshots_hand_seq
+    ON public.archived_snapshots(hand_id, sequence DESC);
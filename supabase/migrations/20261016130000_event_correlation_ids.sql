@@ -0,0 +1,5 @@
+-- Id of the request that submitted each event, so a persisted event can be matched to the
+-- server logs for that request. Nullable: shuffler- and coordinator-originated events have none.
+
+ALTER TABLE public.events ADD COLUMN correlation_id TEXT;
+ALTER TABLE public.archived_events ADD COLUMN correlation_id TEXT;
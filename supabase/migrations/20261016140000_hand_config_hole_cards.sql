@@ -0,0 +1,4 @@
+-- Hole cards dealt to each player: 2 for hold'em, 4 for Omaha. Existing configs are hold'em.
+
+ALTER TABLE public.hand_configs
+    ADD COLUMN hole_cards SMALLINT NOT NULL DEFAULT 2 CHECK (hole_cards IN (2, 4));
@@ -0,0 +1,4 @@
+-- Optional responsible-gaming cap on bet sizes, as a multiple of the big blind. NULL is uncapped.
+
+ALTER TABLE public.hand_configs
+    ADD COLUMN max_bet_bb BIGINT CHECK (max_bet_bb IS NULL OR max_bet_bb > 0);
@@ -0,0 +1,6 @@
+-- Minimum number of registered shufflers required before a hand can commence.
+
+ALTER TABLE public.games
+    ADD COLUMN min_shufflers SMALLINT NOT NULL DEFAULT 1 CHECK (min_shufflers >= 1);
+
+COMMENT ON COLUMN public.games.min_shufflers IS 'Registered shufflers needed before a hand can commence';
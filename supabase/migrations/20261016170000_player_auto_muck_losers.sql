@@ -0,0 +1,13 @@
+-- Opt-in showdown preference: muck a verified hand automatically when it cannot win any pot.
+-- The player's preference is copied onto hand_player when a hand commences so it stays fixed
+-- for the rest of that hand.
+
+ALTER TABLE public.players
+    ADD COLUMN auto_muck_losers BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN public.players.auto_muck_losers IS 'Auto-muck losing hands at showdown';
+
+ALTER TABLE public.hand_player
+    ADD COLUMN auto_muck_losers BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN public.hand_player.auto_muck_losers IS 'Player auto-muck preference for this hand';
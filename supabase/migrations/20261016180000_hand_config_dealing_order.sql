@@ -0,0 +1,4 @@
+-- Order in which hole cards come off the deck: 0 = one at a time around the table, 1 = per player.
+
+ALTER TABLE public.hand_configs
+    ADD COLUMN dealing_order SMALLINT NOT NULL DEFAULT 0 CHECK (dealing_order IN (0, 1));
@@ -0,0 +1,15 @@
+-- A host's game names are unique, so idempotent hosting can rely on the insert conflicting.
+
+-- Keep the oldest game under each (host, name), which is the one lookups already returned, and
+-- suffix later duplicates with their id.
+UPDATE public.games AS g
+SET name = g.name || ' #' || g.id
+WHERE EXISTS (
+    SELECT 1
+    FROM public.games AS older
+    WHERE older.host_player_id = g.host_player_id
+      AND older.name = g.name
+      AND older.id < g.id
+);
+
+CREATE UNIQUE INDEX idx_games_host_name ON public.games(host_player_id, name);
@@ -0,0 +1,5 @@
+-- Table-wide cap on bet sizes, as a multiple of the big blind, copied into each hand's config.
+-- NULL is uncapped.
+
+ALTER TABLE public.games
+    ADD COLUMN max_bet_bb BIGINT CHECK (max_bet_bb IS NULL OR max_bet_bb > 0);
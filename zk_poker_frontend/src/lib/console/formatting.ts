@@ -102,6 +102,14 @@ export function formatMessageSummary(message: AnyGameMessage): string {
     return "Revealed hand";
   }
 
+  if (message.type === "nonce_commitment") {
+    return "Committed to shuffle nonce";
+  }
+
+  if (message.type === "muck") {
+    return "Mucked hand";
+  }
+
   return "Unknown message";
 }
 
@@ -167,6 +175,13 @@ export function getMessageSummaryParts(
     };
   }
 
+  if (message.type === "muck") {
+    return {
+      hasActor: true,
+      suffix: " mucked",
+    };
+  }
+
   return {
     hasActor: false,
     suffix: "Unknown message",
@@ -264,6 +279,7 @@ export function getPhaseConfig(
       };
 
     case "showdown":
+    case "muck":
       return {
         label: "Showdown",
         color: "var(--color-phase-showdown)",
@@ -186,6 +186,20 @@ const showdownMessageSchema = z.object({
   _curve: z.null().optional(),
 });
 
+const nonceCommitmentMessageSchema = z.object({
+  type: z.literal("nonce_commitment"),
+  commitment: hexString,
+  _curve: z.null().optional(),
+});
+
+const muckMessageSchema = z.object({
+  type: z.literal("muck"),
+  game_id: z.number().int().nonnegative(),
+  hand_id: z.number().int().nonnegative(),
+  seat: z.number().int().min(0).max(255),
+  player_key: hexString,
+});
+
 export const anyGameMessageSchema = z.discriminatedUnion("type", [
   shuffleMessageSchema,
   blindingMessageSchema,
@@ -195,6 +209,8 @@ export const anyGameMessageSchema = z.discriminatedUnion("type", [
   playerTurnMessageSchema,
   playerRiverMessageSchema,
   showdownMessageSchema,
+  nonceCommitmentMessageSchema,
+  muckMessageSchema,
 ]);
 export type AnyGameMessage = z.infer<typeof anyGameMessageSchema>;
 